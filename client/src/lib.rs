@@ -0,0 +1,5 @@
+//! Off-chain helpers for integrating with the ViralVault Raffle Program.
+
+pub use verify::*;
+
+pub mod verify;
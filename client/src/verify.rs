@@ -0,0 +1,455 @@
+use std::fmt;
+
+use anchor_lang::prelude::Pubkey;
+use raffle_program::{
+    instructions::derive_winning_tickets,
+    state::{held_ticket_number, Entry, Raffle, RandomnessMode, TicketRange},
+};
+
+/// The subset of raffle state required to replay a draw
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RaffleSnapshot {
+    pub randomness_mode: RandomnessMode,
+    pub current_tickets: u64,
+    /// Ticket ranges of released reservations, which the draw skips
+    pub voided_ranges: Vec<TicketRange>,
+    pub winner_count: u8,
+    /// Winning tickets in prize order
+    pub winning_tickets: Vec<u64>,
+    /// Published winners in prize order, `None` where not set yet or anonymized
+    pub winners: Vec<Option<Pubkey>>,
+    pub draw_target_slot: u64,
+    pub draw_slot: Option<u64>,
+    pub draw_slot_hash: Option<[u8; 32]>,
+    pub draw_timestamp: Option<i64>,
+}
+
+impl From<&Raffle> for RaffleSnapshot {
+    fn from(raffle: &Raffle) -> Self {
+        Self {
+            randomness_mode: raffle.randomness_mode.clone(),
+            current_tickets: raffle.current_tickets,
+            voided_ranges: raffle.voided_ranges.clone(),
+            winner_count: raffle.winner_count,
+            winning_tickets: raffle.winning_tickets.clone(),
+            winners: (0..raffle.winner_count)
+                .map(|winner_index| raffle.winner(winner_index))
+                .collect(),
+            draw_target_slot: raffle.draw_target_slot,
+            draw_slot: raffle.draw_slot,
+            draw_slot_hash: raffle.draw_slot_hash,
+            draw_timestamp: raffle.draw_timestamp,
+        }
+    }
+}
+
+/// The outcome of a successfully replayed draw
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerifiedDraw {
+    /// The winning ticket numbers derived from the stored entropy inputs, in prize order
+    pub winning_tickets: Vec<u64>,
+    /// The owners of the entries holding the winning tickets, in prize order
+    pub winners: Vec<Pubkey>,
+}
+
+/// Reasons a historical draw could not be verified
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VerifyError {
+    /// The raffle wasn't drawn from the SlotHashes sysvar, so its draw can't be replayed
+    /// from the entropy inputs stored on the raffle
+    NotVerifiable(RandomnessMode),
+    /// The raffle has not been drawn, or was drawn before entropy inputs were recorded
+    MissingEntropy,
+    /// The raffle has entropy inputs but no winning ticket
+    NoWinningTicket,
+    /// The entropy inputs could not be mapped to tickets (e.g. fewer tickets were sold
+    /// than the raffle has winners)
+    InvalidTicketCount,
+    /// The draw slot precedes the draw slot the raffle committed to
+    DrawSlotBeforeTarget { draw_slot: u64, target_slot: u64 },
+    /// The raffle records a different number of winning tickets than it has winners
+    WinningTicketCountMismatch { recorded: usize, replayed: usize },
+    /// Replaying the entropy inputs produced a different ticket than the one recorded
+    WinningTicketMismatch {
+        winner_index: u8,
+        recorded: u64,
+        replayed: u64,
+    },
+    /// None of the provided entries contain the winning ticket
+    WinningEntryNotFound(u64),
+    /// The winning entry is owned by a different wallet than the recorded winner
+    WinnerMismatch {
+        winner_index: u8,
+        recorded: Pubkey,
+        replayed: Pubkey,
+    },
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotVerifiable(mode) => write!(
+                f,
+                "raffle drawn with {mode:?} randomness can't be replayed from its entropy inputs"
+            ),
+            Self::MissingEntropy => write!(f, "raffle has no recorded draw entropy"),
+            Self::NoWinningTicket => write!(f, "raffle has no winning ticket"),
+            Self::InvalidTicketCount => write!(f, "raffle has too few tickets to draw from"),
+            Self::DrawSlotBeforeTarget {
+                draw_slot,
+                target_slot,
+            } => write!(
+                f,
+                "draw slot {draw_slot} precedes the committed draw slot {target_slot}"
+            ),
+            Self::WinningTicketCountMismatch { recorded, replayed } => write!(
+                f,
+                "raffle records {recorded} winning tickets but replayed {replayed}"
+            ),
+            Self::WinningTicketMismatch {
+                winner_index,
+                recorded,
+                replayed,
+            } => write!(
+                f,
+                "recorded winning ticket {recorded} of prize {winner_index} does not match replayed ticket {replayed}"
+            ),
+            Self::WinningEntryNotFound(ticket) => {
+                write!(f, "no entry contains winning ticket {ticket}")
+            }
+            Self::WinnerMismatch {
+                winner_index,
+                recorded,
+                replayed,
+            } => write!(
+                f,
+                "recorded winner {recorded} of prize {winner_index} does not match winning entry owner {replayed}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for VerifyError {}
+
+/// Replays a historical draw from the entropy inputs stored on the raffle and verifies
+/// that the recorded winning tickets and winners are consistent with it.
+///
+/// # Arguments
+/// * `raffle_snapshot` - The raffle state as fetched after the draw
+/// * `entries` - The raffle's entry accounts; only the winning entries need to be present
+///
+/// Only SlotHashes draws can be replayed, drawn over the tickets held by entries and
+/// mapped past voided ticket ranges exactly as on-chain. Winners not published yet, or
+/// anonymized, are returned without comparison.
+pub fn verify_winner(
+    raffle_snapshot: &RaffleSnapshot,
+    entries: &[Entry],
+) -> Result<VerifiedDraw, VerifyError> {
+    if raffle_snapshot.randomness_mode != RandomnessMode::SlotHashes {
+        return Err(VerifyError::NotVerifiable(
+            raffle_snapshot.randomness_mode.clone(),
+        ));
+    }
+    let (Some(slot), Some(slot_hash), Some(timestamp)) = (
+        raffle_snapshot.draw_slot,
+        raffle_snapshot.draw_slot_hash,
+        raffle_snapshot.draw_timestamp,
+    ) else {
+        return Err(VerifyError::MissingEntropy);
+    };
+    if raffle_snapshot.winning_tickets.is_empty() {
+        return Err(VerifyError::NoWinningTicket);
+    }
+    // A delayed draw reads the first slot produced at or after its committed slot
+    if slot < raffle_snapshot.draw_target_slot {
+        return Err(VerifyError::DrawSlotBeforeTarget {
+            draw_slot: slot,
+            target_slot: raffle_snapshot.draw_target_slot,
+        });
+    }
+
+    let replayed = derive_winning_tickets(
+        slot,
+        &slot_hash,
+        timestamp,
+        raffle_snapshot.current_tickets,
+        raffle_snapshot.winner_count,
+    )
+    .map_err(|_| VerifyError::InvalidTicketCount)?
+    .into_iter()
+    .map(|index| held_ticket_number(&raffle_snapshot.voided_ranges, index))
+    .collect::<Result<Vec<_>, _>>()
+    .map_err(|_| VerifyError::InvalidTicketCount)?;
+    if raffle_snapshot.winning_tickets.len() != replayed.len() {
+        return Err(VerifyError::WinningTicketCountMismatch {
+            recorded: raffle_snapshot.winning_tickets.len(),
+            replayed: replayed.len(),
+        });
+    }
+
+    let mut winners = Vec::with_capacity(replayed.len());
+    for (winner_index, (&recorded, &replayed)) in raffle_snapshot
+        .winning_tickets
+        .iter()
+        .zip(&replayed)
+        .enumerate()
+    {
+        let winner_index = winner_index as u8;
+        if recorded != replayed {
+            return Err(VerifyError::WinningTicketMismatch {
+                winner_index,
+                recorded,
+                replayed,
+            });
+        }
+
+        let winning_entry = entries
+            .iter()
+            .find(|entry| {
+                entry
+                    .ticket_start_index
+                    .checked_add(entry.ticket_count)
+                    .is_some_and(|end| (entry.ticket_start_index..end).contains(&replayed))
+            })
+            .ok_or(VerifyError::WinningEntryNotFound(replayed))?;
+
+        if let Some(Some(recorded_winner)) = raffle_snapshot.winners.get(winner_index as usize) {
+            if *recorded_winner != winning_entry.owner {
+                return Err(VerifyError::WinnerMismatch {
+                    winner_index,
+                    recorded: *recorded_winner,
+                    replayed: winning_entry.owner,
+                });
+            }
+        }
+        winners.push(winning_entry.owner);
+    }
+
+    Ok(VerifiedDraw {
+        winning_tickets: replayed,
+        winners,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SLOT: u64 = 345_678_901;
+    const SLOT_HASH: [u8; 32] = [7; 32];
+    const TIMESTAMP: i64 = 1_700_000_000;
+    const TICKETS: u64 = 1_000;
+
+    fn entry(owner: Pubkey, ticket_start_index: u64, ticket_count: u64) -> Entry {
+        Entry {
+            raffle: Pubkey::default(),
+            owner,
+            ticket_count,
+            ticket_start_index,
+            seed: [0; 8],
+            rent_sponsored: false,
+            memo: None,
+            next_entry: None,
+            entry_index: 0,
+            version: raffle_program::state::ENTRY_VERSION,
+            bump: 0,
+            created_at: 0,
+        }
+    }
+
+    fn snapshot(winner_address: Option<Pubkey>) -> RaffleSnapshot {
+        RaffleSnapshot {
+            randomness_mode: RandomnessMode::SlotHashes,
+            current_tickets: TICKETS,
+            voided_ranges: Vec::new(),
+            winner_count: 1,
+            winning_tickets: derive_winning_tickets(SLOT, &SLOT_HASH, TIMESTAMP, TICKETS, 1)
+                .unwrap(),
+            winners: vec![winner_address],
+            draw_target_slot: 0,
+            draw_slot: Some(SLOT),
+            draw_slot_hash: Some(SLOT_HASH),
+            draw_timestamp: Some(TIMESTAMP),
+        }
+    }
+
+    /// Splits the ticket supply into ten entries with a distinct owner each
+    fn entries() -> Vec<Entry> {
+        (0..10)
+            .map(|i| entry(Pubkey::new_unique(), i * 100, 100))
+            .collect()
+    }
+
+    #[test]
+    fn verifies_consistent_draw() {
+        let entries = entries();
+        let winning_ticket = snapshot(None).winning_tickets[0];
+        let winner = entries[(winning_ticket / 100) as usize].owner;
+
+        let verified = verify_winner(&snapshot(Some(winner)), &entries).unwrap();
+        assert_eq!(verified.winning_tickets, vec![winning_ticket]);
+        assert_eq!(verified.winners, vec![winner]);
+
+        // A raffle still in Drawing state has no winner to compare against
+        let verified = verify_winner(&snapshot(None), &entries).unwrap();
+        assert_eq!(verified.winners, vec![winner]);
+    }
+
+    #[test]
+    fn verifies_draw_skipping_voided_tickets() {
+        // Tickets 0..200 were voided by a released reservation, so the entries hold
+        // tickets 200..1_200
+        let voided_ranges = vec![TicketRange {
+            start: 0,
+            count: 200,
+        }];
+        let entries: Vec<Entry> = (0..10)
+            .map(|i| entry(Pubkey::new_unique(), 200 + i * 100, 100))
+            .collect();
+        let index = derive_winning_tickets(SLOT, &SLOT_HASH, TIMESTAMP, TICKETS, 1).unwrap()[0];
+        let winning_ticket = index + 200;
+        let winner = entries[(index / 100) as usize].owner;
+
+        let mut raffle = snapshot(Some(winner));
+        raffle.voided_ranges = voided_ranges;
+        raffle.winning_tickets = vec![winning_ticket];
+        let verified = verify_winner(&raffle, &entries).unwrap();
+        assert_eq!(verified.winning_tickets, vec![winning_ticket]);
+        assert_eq!(verified.winners, vec![winner]);
+
+        // Replaying without skipping the voided tickets lands on another ticket
+        raffle.voided_ranges = Vec::new();
+        assert!(matches!(
+            verify_winner(&raffle, &entries),
+            Err(VerifyError::WinningTicketMismatch { replayed, .. }) if replayed == index
+        ));
+    }
+
+    #[test]
+    fn verifies_every_winner_of_multi_winner_draw() {
+        let entries = entries();
+        let winning_tickets =
+            derive_winning_tickets(SLOT, &SLOT_HASH, TIMESTAMP, TICKETS, 3).unwrap();
+        let winners: Vec<Pubkey> = winning_tickets
+            .iter()
+            .map(|ticket| entries[(ticket / 100) as usize].owner)
+            .collect();
+
+        let mut raffle = snapshot(None);
+        raffle.winner_count = 3;
+        raffle.winning_tickets = winning_tickets.clone();
+        raffle.winners = winners.iter().copied().map(Some).collect();
+        let verified = verify_winner(&raffle, &entries).unwrap();
+        assert_eq!(verified.winning_tickets, winning_tickets);
+        assert_eq!(verified.winners, winners);
+
+        // A tampered ticket of a later prize is caught
+        raffle.winning_tickets[2] = (winning_tickets[2] + 1) % TICKETS;
+        assert!(matches!(
+            verify_winner(&raffle, &entries),
+            Err(VerifyError::WinningTicketMismatch {
+                winner_index: 2,
+                ..
+            })
+        ));
+
+        // Recording fewer tickets than the raffle has winners is caught
+        raffle.winning_tickets = winning_tickets[..1].to_vec();
+        assert_eq!(
+            verify_winner(&raffle, &entries),
+            Err(VerifyError::WinningTicketCountMismatch {
+                recorded: 1,
+                replayed: 3
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_tampered_winning_ticket() {
+        let mut raffle = snapshot(None);
+        let recorded = (raffle.winning_tickets[0] + 1) % TICKETS;
+        raffle.winning_tickets = vec![recorded];
+
+        assert!(matches!(
+            verify_winner(&raffle, &entries()),
+            Err(VerifyError::WinningTicketMismatch { recorded: r, .. }) if r == recorded
+        ));
+    }
+
+    #[test]
+    fn rejects_tampered_entropy() {
+        let mut raffle = snapshot(None);
+        raffle.draw_timestamp = Some(TIMESTAMP + 1);
+        let replayed =
+            derive_winning_tickets(SLOT, &SLOT_HASH, TIMESTAMP + 1, TICKETS, 1).unwrap()[0];
+
+        assert_ne!(replayed, raffle.winning_tickets[0]);
+
+        assert!(matches!(
+            verify_winner(&raffle, &entries()),
+            Err(VerifyError::WinningTicketMismatch { replayed: r, .. }) if r == replayed
+        ));
+    }
+
+    #[test]
+    fn rejects_wrong_winner() {
+        let recorded = Pubkey::new_unique();
+        assert!(matches!(
+            verify_winner(&snapshot(Some(recorded)), &entries()),
+            Err(VerifyError::WinnerMismatch { recorded: r, .. }) if r == recorded
+        ));
+    }
+
+    #[test]
+    fn rejects_missing_winning_entry() {
+        let winning_ticket = snapshot(None).winning_tickets[0];
+        let entries: Vec<Entry> = entries()
+            .into_iter()
+            .filter(|e| e.ticket_start_index != (winning_ticket / 100) * 100)
+            .collect();
+
+        assert_eq!(
+            verify_winner(&snapshot(None), &entries),
+            Err(VerifyError::WinningEntryNotFound(winning_ticket))
+        );
+    }
+
+    #[test]
+    fn rejects_undrawn_raffle() {
+        let mut raffle = snapshot(None);
+        raffle.draw_slot_hash = None;
+        assert_eq!(
+            verify_winner(&raffle, &entries()),
+            Err(VerifyError::MissingEntropy)
+        );
+    }
+
+    #[test]
+    fn rejects_draw_slot_before_committed_slot() {
+        let mut raffle = snapshot(None);
+        raffle.draw_target_slot = SLOT + 1;
+        assert_eq!(
+            verify_winner(&raffle, &entries()),
+            Err(VerifyError::DrawSlotBeforeTarget {
+                draw_slot: SLOT,
+                target_slot: SLOT + 1
+            })
+        );
+    }
+
+    #[test]
+    fn declines_draws_without_slot_hashes() {
+        for mode in [
+            RandomnessMode::Vrf,
+            RandomnessMode::Vote,
+            RandomnessMode::Provider,
+        ] {
+            let mut raffle = snapshot(None);
+            raffle.randomness_mode = mode.clone();
+            assert_eq!(
+                verify_winner(&raffle, &entries()),
+                Err(VerifyError::NotVerifiable(mode))
+            );
+        }
+    }
+}
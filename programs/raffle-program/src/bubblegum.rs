@@ -0,0 +1,204 @@
+//! Minting of ticket collectibles as compressed NFTs through Metaplex Bubblegum.
+//!
+//! The management authority creates a Bubblegum tree and delegates it to the config
+//! PDA with Bubblegum's set_tree_delegate, then registers it with set_collectible_tree.
+//! claim_ticket_collectible mints each claimed ticket number into the tree with a
+//! `mint_v1` invocation the config PDA signs as tree delegate. Bubblegum has no crate
+//! compatible with the program's dependencies, so its instruction is encoded here,
+//! identified by [`MINT_V1_DISCRIMINATOR`] and followed by a Borsh encoded
+//! [`MetadataArgs`].
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        program::invoke_signed,
+        pubkey,
+    },
+};
+
+use crate::state::{Config, Raffle};
+
+/// Metaplex Bubblegum program
+pub const BUBBLEGUM_PROGRAM_ID: Pubkey = pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+
+/// SPL Noop program, which Bubblegum logs the minted leaves through
+pub const SPL_NOOP_PROGRAM_ID: Pubkey = pubkey!("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+
+/// SPL Account Compression program, which owns Bubblegum trees
+pub const SPL_ACCOUNT_COMPRESSION_PROGRAM_ID: Pubkey =
+    pubkey!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+
+// sha256("global:mint_v1")[..8]
+pub const MINT_V1_DISCRIMINATOR: &[u8] = &[145, 98, 192, 118, 184, 147, 118, 104];
+
+/// Maximum length of a Bubblegum metadata URI
+pub const MAX_COLLECTIBLE_URI_LENGTH: usize = 200;
+
+/// Symbol of minted ticket collectibles
+pub const COLLECTIBLE_SYMBOL: &str = "TICKET";
+
+/// Bubblegum's token standard, of which collectibles use NonFungible
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub enum TokenStandard {
+    NonFungible,
+    FungibleAsset,
+    Fungible,
+    NonFungibleEdition,
+}
+
+/// Bubblegum's token program version, of which collectibles use Original
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub enum TokenProgramVersion {
+    Original,
+    Token2022,
+}
+
+/// Bubblegum's verified collection of an asset
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Collection {
+    pub verified: bool,
+    pub key: Pubkey,
+}
+
+/// Bubblegum's creator of an asset
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Creator {
+    pub address: Pubkey,
+    pub verified: bool,
+    pub share: u8,
+}
+
+/// Bubblegum's metadata of a minted asset, the only argument of `mint_v1`. Uses are
+/// never set, so they are encoded as an absent `Option<()>`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MetadataArgs {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub primary_sale_happened: bool,
+    pub is_mutable: bool,
+    pub edition_nonce: Option<u8>,
+    pub token_standard: Option<TokenStandard>,
+    pub collection: Option<Collection>,
+    pub uses: Option<()>,
+    pub token_program_version: TokenProgramVersion,
+    pub creators: Vec<Creator>,
+}
+
+/// Metadata of the collectible of `ticket_index` in `raffle`, pointing at the raffle's
+/// metadata. Immutable and without royalties, as nothing is sold
+pub(crate) fn ticket_metadata(raffle: &Raffle, ticket_index: u64) -> MetadataArgs {
+    MetadataArgs {
+        name: format!("Ticket #{ticket_index}"),
+        symbol: COLLECTIBLE_SYMBOL.to_string(),
+        uri: raffle.metadata_uri.clone(),
+        seller_fee_basis_points: 0,
+        primary_sale_happened: true,
+        is_mutable: false,
+        edition_nonce: None,
+        token_standard: Some(TokenStandard::NonFungible),
+        collection: None,
+        uses: None,
+        token_program_version: TokenProgramVersion::Original,
+        creators: Vec::new(),
+    }
+}
+
+/// Accounts of a Bubblegum `mint_v1` invocation, besides the config signing as tree
+/// delegate
+pub(crate) struct MintAccounts<'a, 'info> {
+    pub tree_authority: &'a AccountInfo<'info>,
+    pub leaf_owner: &'a AccountInfo<'info>,
+    pub merkle_tree: &'a AccountInfo<'info>,
+    pub payer: &'a AccountInfo<'info>,
+    pub log_wrapper: &'a AccountInfo<'info>,
+    pub compression_program: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub bubblegum_program: &'a AccountInfo<'info>,
+}
+
+/// Mints a compressed NFT with `metadata` to `accounts.leaf_owner`, who is also its
+/// delegate, signed by the config PDA as delegate of the tree
+pub(crate) fn mint_compressed_nft<'info>(
+    config: &Account<'info, Config>,
+    accounts: MintAccounts<'_, 'info>,
+    metadata: MetadataArgs,
+) -> Result<()> {
+    let mut data = MINT_V1_DISCRIMINATOR.to_vec();
+    metadata.serialize(&mut data)?;
+    let instruction = Instruction {
+        program_id: BUBBLEGUM_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(accounts.tree_authority.key(), false),
+            AccountMeta::new_readonly(accounts.leaf_owner.key(), false),
+            AccountMeta::new_readonly(accounts.leaf_owner.key(), false),
+            AccountMeta::new(accounts.merkle_tree.key(), false),
+            AccountMeta::new_readonly(accounts.payer.key(), true),
+            AccountMeta::new_readonly(config.key(), true),
+            AccountMeta::new_readonly(accounts.log_wrapper.key(), false),
+            AccountMeta::new_readonly(accounts.compression_program.key(), false),
+            AccountMeta::new_readonly(accounts.system_program.key(), false),
+        ],
+        data,
+    };
+    invoke_signed(
+        &instruction,
+        &[
+            accounts.tree_authority.clone(),
+            accounts.leaf_owner.clone(),
+            accounts.merkle_tree.clone(),
+            accounts.payer.clone(),
+            config.to_account_info(),
+            accounts.log_wrapper.clone(),
+            accounts.compression_program.clone(),
+            accounts.system_program.clone(),
+            accounts.bubblegum_program.clone(),
+        ],
+        &[&[b"config", &[config.bump]]],
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::hash::hash;
+
+    #[test]
+    fn discriminator_matches_bubblegum() {
+        assert_eq!(
+            MINT_V1_DISCRIMINATOR,
+            &hash(b"global:mint_v1").to_bytes()[..8]
+        );
+    }
+
+    #[test]
+    fn metadata_is_encoded_in_bubblegum_layout() {
+        let metadata = MetadataArgs {
+            name: "Ticket #7".to_string(),
+            symbol: COLLECTIBLE_SYMBOL.to_string(),
+            uri: "https://a.io".to_string(),
+            seller_fee_basis_points: 0,
+            primary_sale_happened: true,
+            is_mutable: false,
+            edition_nonce: None,
+            token_standard: Some(TokenStandard::NonFungible),
+            collection: None,
+            uses: None,
+            token_program_version: TokenProgramVersion::Original,
+            creators: Vec::new(),
+        };
+
+        let mut expected = Vec::new();
+        for field in ["Ticket #7", "TICKET", "https://a.io"] {
+            expected.extend((field.len() as u32).to_le_bytes());
+            expected.extend(field.as_bytes());
+        }
+        // Royalties, primary sale, mutability, edition nonce, token standard, collection,
+        // uses, token program version and the creators' length
+        expected.extend([0, 0, 1, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(metadata.try_to_vec().unwrap(), expected);
+    }
+}
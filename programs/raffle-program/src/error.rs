@@ -60,4 +60,412 @@ pub enum RaffleError {
     PurchaseExceedsThreshold,
     #[msg("Max tickets must be greater than min tickets")]
     MaxTicketsTooLow,
+    #[msg("Payout exceeds the funds collected by the treasury")]
+    TreasuryInsolvent,
+    #[msg("Treasury holds no lamports beyond its rent reserve and collected funds")]
+    NoTreasuryDust,
+    #[msg("Match basis points must be between 1 and 10000")]
+    InvalidMatchBps,
+    #[msg("Match cap must be greater than zero")]
+    InvalidMatchCap,
+    #[msg("Raffle has not been drawn or expired yet")]
+    RaffleNotConcluded,
+    #[msg("Raffle must be claimed or expired")]
+    RaffleNotFinalized,
+    #[msg("Invalid number or order of remaining accounts")]
+    InvalidRemainingAccounts,
+    #[msg("Entry does not belong to the given raffle")]
+    EntryRaffleMismatch,
+    #[msg("Purchase price exceeds the expected total")]
+    PriceExceedsExpectedTotal,
+    #[msg("Memo exceeds maximum length of 64 bytes")]
+    MemoTooLong,
+    #[msg("Entry tickets are not contiguous with the new purchase")]
+    EntryNotContiguous,
+    #[msg("Max tickets per purchase must be greater than zero")]
+    InvalidMaxPerPurchase,
+    #[msg("Purchase exceeds the maximum number of tickets per purchase")]
+    PurchaseExceedsMaxPerPurchase,
+    #[msg("Prize amount must be greater than zero")]
+    InvalidPrizeAmount,
+    #[msg("Raffle has not been claimed yet")]
+    RaffleNotClaimed,
+    #[msg("Prize vault does not belong to the given raffle")]
+    InvalidPrizeVault,
+    #[msg("Purchase dedupe account does not match the idempotency key")]
+    InvalidPurchaseDedupe,
+    #[msg("A purchase with this idempotency key was already made")]
+    DuplicatePurchase,
+    #[msg("Minimum revenue must be greater than zero")]
+    InvalidMinRevenue,
+    #[msg("Collectible raffles require a maximum ticket supply")]
+    CollectibleRequiresMaxTickets,
+    #[msg("Raffle does not offer ticket collectibles")]
+    RaffleNotCollectible,
+    #[msg("Entry does not contain the ticket")]
+    TicketNotInEntry,
+    #[msg("Prize title exceeds maximum length")]
+    PrizeTitleTooLong,
+    #[msg("Prize info can only be set before ticket sales start")]
+    PrizeInfoLocked,
+    #[msg("Claim and dispute windows must be greater than zero")]
+    InvalidWindow,
+    #[msg("Caller is not a registered keeper")]
+    KeeperNotRegistered,
+    #[msg("Heartbeat slot is in the future or behind the last report")]
+    InvalidHeartbeatSlot,
+    #[msg("Draw entropy is from before the last draw reset")]
+    StaleDrawEntropy,
+    #[msg("Treasury funds have already been withdrawn")]
+    TreasuryAlreadyWithdrawn,
+    #[msg("Funds of an expired raffle can only be refunded")]
+    RaffleIsExpired,
+    #[msg("Cash prize basis points must be between 1 and 10000")]
+    InvalidCashPrizeBps,
+    #[msg("Raffle does not have a cash prize")]
+    NoCashPrize,
+    #[msg("Cash prize raffles are paid out through pay_winner")]
+    CashPrizeRaffle,
+    #[msg("Top holders must be between 1 and 10 and requires a cash prize")]
+    InvalidTopHolders,
+    #[msg("Leaderboard account is missing or belongs to another raffle")]
+    InvalidLeaderboard,
+    #[msg("Raffle does not split its prize among top holders")]
+    NoTopHoldersSplit,
+    #[msg("Top holder raffles are paid out through pay_top_holders")]
+    TopHoldersRaffle,
+    #[msg("Only the program upgrade authority can change this setting")]
+    NotProgramUpgradeAuthority,
+    #[msg("Too many metadata hosts")]
+    TooManyMetadataHosts,
+    #[msg("Metadata URI host is not allowed")]
+    MetadataHostNotAllowed,
+    #[msg("Bundles must include 2 to 5 distinct open raffles")]
+    InvalidBundle,
+    #[msg("Bundle price must be greater than zero and below the sum of its ticket prices")]
+    BundleNotDiscounted,
+    #[msg("Reservation must expire after now and no later than the raffle end time")]
+    InvalidReservationExpiry,
+    #[msg("Reservation has expired and can only be released")]
+    ReservationExpired,
+    #[msg("Refund fee cannot exceed 200 basis points")]
+    InvalidRefundFee,
+    #[msg("Too many payout destinations")]
+    TooManyPayoutDestinations,
+    #[msg("Withdrawal destination is not an approved payout destination")]
+    PayoutDestinationNotAllowed,
+    #[msg("Only raffles that were not expired or claimed can be recovered")]
+    RaffleNotRecoverable,
+    #[msg("Emergency recovery can't be executed before its timelock elapses")]
+    RecoveryTimelockActive,
+    #[msg("Raffle has pending reservations or funds that were not refunded or withdrawn")]
+    TreasuryNotSettled,
+    #[msg("Rent can only be returned to the account that paid it")]
+    NotRentPayer,
+    #[msg("Claim instructions must be between 1 and 256 bytes")]
+    InvalidClaimInstructions,
+    #[msg("Claim instructions can only be set before ticket sales start")]
+    ClaimInstructionsLocked,
+    #[msg("Supported format versions and encryption schemes must each hold 1 to 4 values")]
+    InvalidWinnerDataFormats,
+    #[msg("Winner data format version or encryption scheme is not supported")]
+    UnsupportedWinnerDataFormat,
+    #[msg("Winner data is locked and can no longer be changed")]
+    WinnerDataLocked,
+    #[msg("Winner data can only be locked by the winner before its edit window ends")]
+    WinnerDataEditWindowOpen,
+    #[msg("Campaign discount must be between 1 and 10000 basis points with a budget")]
+    InvalidCampaign,
+    #[msg("Campaign budget is exhausted")]
+    CampaignBudgetExhausted,
+    #[msg("Only the region attestor registered in the config can attest regions")]
+    NotRegionAttestor,
+    #[msg("Region attestation must be for a valid region and expire in the future")]
+    InvalidRegionAttestation,
+    #[msg("This raffle requires a valid region attestation")]
+    RegionAttestationRequired,
+    #[msg("This raffle is not available in the attested region")]
+    RegionNotAllowed,
+    #[msg("Only the age attestor registered in the config can attest ages")]
+    NotAgeAttestor,
+    #[msg("Age attestation must expire in the future")]
+    InvalidAgeAttestation,
+    #[msg("This raffle is restricted to buyers verified to be 18 or older")]
+    AgeVerificationRequired,
+    #[msg("Only the raffle's draw authority can trigger its draw")]
+    NotDrawAuthority,
+    #[msg("Ticket has not been sold")]
+    TicketNotSold,
+    #[msg("Priority phase must end in the future before the raffle ends, with a tier above zero")]
+    InvalidPriorityAccess,
+    #[msg("Only buyers of a sufficient tier can buy tickets during the priority phase")]
+    PriorityAccessRequired,
+    #[msg("Minimum unique buyers must be greater than 0 and at most the maximum tickets")]
+    InvalidMinUniqueBuyers,
+    #[msg("Not enough unique buyers to draw the raffle")]
+    InsufficientUniqueBuyers,
+    #[msg("Token accounts do not match the raffle's payment mint")]
+    InvalidPaymentMint,
+    #[msg("Not supported for raffles paid in an SPL token")]
+    TokenPaymentsNotSupported,
+    #[msg("Raffle must be drawn with verifiable randomness")]
+    SlotHashDrawDisabled,
+    #[msg("Raffle does not use verifiable randomness")]
+    VrfNotEnabled,
+    #[msg("Randomness has already been requested for this raffle")]
+    RandomnessAlreadyRequested,
+    #[msg("Randomness account does not match the raffle's request")]
+    InvalidRandomnessAccount,
+    #[msg("Randomness account must be committed in the previous slot")]
+    RandomnessNotFresh,
+    #[msg("Randomness has not been revealed yet")]
+    RandomnessNotRevealed,
+    #[msg("The raffle's prize must be escrowed before its funds can be withdrawn")]
+    PrizeNotDeposited,
+    #[msg("Token accounts do not match the escrowed prize")]
+    InvalidPrizeMint,
+    #[msg("Only the current holder of an authority can propose its successor")]
+    NotCurrentAuthority,
+    #[msg("Only the proposed authority can accept the handover")]
+    NotPendingAuthority,
+    #[msg("Tickets can only be bought by top-level instructions")]
+    CpiPurchaseRejected,
+    #[msg("The raffle was cancelled")]
+    RaffleIsCancelled,
+    #[msg("Ticket holders of the cancelled raffle must be refunded first")]
+    RefundsPending,
+    #[msg("Winner count must be between 1 and the maximum")]
+    InvalidWinnerCount,
+    #[msg("Winner index is out of range or skips a prize whose winner is not set yet")]
+    InvalidWinnerIndex,
+    #[msg("The winner of this prize was already set")]
+    WinnerAlreadySet,
+    #[msg("This prize was already claimed")]
+    PrizeAlreadyClaimed,
+    #[msg("Purchase exceeds the maximum number of tickets per wallet")]
+    WalletLimitExceeded,
+    #[msg("Maximum tickets per wallet must be greater than 0")]
+    InvalidMaxTicketsPerWallet,
+    #[msg("Token holder gates must require a minimum amount greater than 0")]
+    InvalidGate,
+    #[msg("Wallet does not hold enough of the raffle's gate token")]
+    GateTokenRequired,
+    #[msg("Wallet is not on the raffle's allowlist")]
+    NotAllowlisted,
+    #[msg("Notifications account does not belong to the winner")]
+    InvalidNotifications,
+    #[msg("Raffle does not defer ticket numbering")]
+    NumberingNotDeferred,
+    #[msg("Entry is not the next one in creation order")]
+    EntryOutOfOrder,
+    #[msg("Tickets must be numbered with finalize_numbering before the draw")]
+    NumberingNotFinalized,
+    #[msg("Platform fee cannot exceed 2000 basis points")]
+    InvalidPlatformFee,
+    #[msg("Fee recipient does not match the config's fee recipient")]
+    InvalidFeeRecipient,
+    #[msg("The winning entry can't be closed before its prize is claimed")]
+    WinningEntryInUse,
+    #[msg("Winner data must be locked before it is closed")]
+    WinnerDataNotLocked,
+    #[msg("Snapshot of the raffle was already recorded")]
+    SnapshotAlreadyRecorded,
+    #[msg("Ticket balances must be snapshotted in ascending owner order")]
+    TicketBalanceOutOfOrder,
+    #[msg("Snapshot tree cannot hold more ticket holders")]
+    SnapshotTreeFull,
+    #[msg("Snapshotted tickets do not match the tickets sold")]
+    SnapshotMismatch,
+    #[msg("Unknown operator status")]
+    InvalidOperatorStatus,
+    #[msg("Program is paused")]
+    ProgramPaused,
+    #[msg("Withdrawal exceeds the threshold and was not approved by the withdrawal approver")]
+    WithdrawalNotApproved,
+    #[msg("Signer is not the config's withdrawal approver")]
+    NotWithdrawalApprover,
+    #[msg("Raffle parameters can only be updated before the first ticket sale")]
+    TicketsAlreadySold,
+    #[msg("Funding deadline must be in the future and before the raffle's end time")]
+    InvalidFundingDeadline,
+    #[msg("Raffle missed its funding deadline")]
+    FundingDeadlineMissed,
+    #[msg("Pricing tiers must have ascending quantity thresholds above 1 and descending prices below the ticket price")]
+    InvalidPricingTiers,
+    #[msg("Crank reward cannot exceed 100 basis points")]
+    InvalidCrankReward,
+    #[msg("Raffle is not drawn by votes")]
+    VoteDrawDisabled,
+    #[msg("Voting must end in the future")]
+    InvalidVotingWindow,
+    #[msg("Shortlist must hold between 1 and 8 distinct entries of the raffle")]
+    InvalidShortlist,
+    #[msg("Voting has ended")]
+    VotingClosed,
+    #[msg("Vote must lock tokens for a shortlisted entry")]
+    InvalidVoteChoice,
+    #[msg("Token account is not for the ballot's vote mint")]
+    InvalidVoteMint,
+    #[msg("Voting has not ended yet")]
+    VotingNotEnded,
+    #[msg("Wallet held no tickets in the raffle")]
+    NoTicketsHeld,
+    #[msg("Referral fee cannot exceed 1000 basis points")]
+    InvalidReferralFee,
+    #[msg("Referral stats account does not belong to the raffle and referrer")]
+    InvalidReferralStats,
+    #[msg("Buyers cannot refer their own purchases")]
+    SelfReferral,
+    #[msg("Alarm coverage cannot exceed 10000 basis points")]
+    InvalidAlarmThreshold,
+    #[msg("Total extension must be between 0 and 30 days")]
+    InvalidExtensionLimits,
+    #[msg("Raffle cannot be extended any further")]
+    ExtensionLimitReached,
+    #[msg("New end time must be later than the current one")]
+    InvalidExtension,
+    #[msg("Refund window must be zero or at least 30 days")]
+    InvalidRefundWindow,
+    #[msg("The refund deadline has passed")]
+    RefundWindowClosed,
+    #[msg("The refund deadline has not passed yet")]
+    RefundWindowOpen,
+    #[msg("No unclaimed refunds to sweep")]
+    NoUnclaimedRefunds,
+    #[msg("Forfeited refunds must be swept to the insurance fund or the payout authority")]
+    InvalidForfeitureDestination,
+    #[msg("The config requires a creator bond account")]
+    CreatorBondRequired,
+    #[msg("Creator bond account does not match the raffle")]
+    InvalidCreatorBond,
+    #[msg("Creator bond can only be released once the raffle is fulfilled, or by the upgrade authority if it was voided")]
+    CreatorBondLocked,
+    #[msg("Entries of this raffle cannot be transferred")]
+    EntryNotTransferable,
+    #[msg("Entries cannot be transferred to their owner")]
+    InvalidTransferRecipient,
+    #[msg("Previous entry does not link to the transferred entry")]
+    InvalidPreviousEntry,
+    #[msg("Ticket count display cap must be greater than 0")]
+    InvalidTicketCountDisplayCap,
+    #[msg("The raffle issues ticket receipts, which require the receipt accounts")]
+    TicketReceiptRequired,
+    #[msg("Receipt account does not match the entry's ticket receipt")]
+    InvalidTicketReceipt,
+    #[msg("Ticket receipts are only minted for entries bought with buy_tickets in raffles numbering tickets at purchase")]
+    TicketReceiptsNotSupported,
+    #[msg("The Arweave transaction id of raffles with ar:// metadata URIs is fixed by the URI")]
+    ArweaveTxIdFixedByUri,
+    #[msg("Creators can only manage the raffles they created")]
+    NotRaffleCreator,
+    #[msg("The creator has reached the maximum number of active raffles of its role")]
+    CreatorRaffleLimitReached,
+    #[msg("Ticket price exceeds the maximum of the creator's role")]
+    CreatorTicketPriceTooHigh,
+    #[msg("Draw delay exceeds the maximum number of slots")]
+    InvalidDrawDelay,
+    #[msg("The raffle does not delay its draw slot")]
+    DrawDelayNotEnabled,
+    #[msg("The draw slot of the raffle has not been committed with commit_draw_slot")]
+    DrawSlotNotCommitted,
+    #[msg("The draw slot of the raffle is already committed")]
+    DrawSlotAlreadyCommitted,
+    #[msg("The committed draw slot has not been reached yet")]
+    DrawSlotNotReached,
+    #[msg("The committed draw slot is no longer in the SlotHashes sysvar, commit a new one")]
+    DrawSlotExpired,
+    #[msg("The gate token account is written to by an instruction of the same transaction")]
+    GateBalanceNotSettled,
+    #[msg("The treasury's payment token account is not frozen")]
+    PaymentMintNotFrozen,
+    #[msg("The raffle already has a refund mint")]
+    RefundMintAlreadySet,
+    #[msg("Refund mint must differ from the payment mint")]
+    InvalidRefundMint,
+    #[msg("Price feed is not a valid Switchboard pull feed or its value is stale")]
+    InvalidPriceFeed,
+    #[msg("Refund rate deviates too far from the price feed")]
+    RefundRateDeviates,
+    #[msg("Lookup table is not the program's lookup table")]
+    InvalidLookupTable,
+    #[msg("Lookup table extension must add between 1 and MAX_LOOKUP_TABLE_EXTENSION addresses")]
+    InvalidLookupTableExtension,
+    #[msg("The value of the drawn raffle is already recorded")]
+    DrawValueAlreadyRecorded,
+    #[msg("The config's notifier program must be passed")]
+    NotifierProgramRequired,
+    #[msg("Notifier program is not the one registered in the config")]
+    InvalidNotifierProgram,
+    #[msg("The maximum number of open raffles has been reached")]
+    OpenRaffleLimitReached,
+    #[msg("Successor raffle is not the one designated or can't take rolled over balances")]
+    InvalidSuccessorRaffle,
+    #[msg("The ticket balance did not opt into rollover")]
+    RolloverNotEnabled,
+    #[msg("Balance doesn't cover a ticket of the successor raffle")]
+    RolloverBalanceTooLow,
+    #[msg("Randomness provider is not registered in the config")]
+    RandomnessProviderNotRegistered,
+    #[msg("Raffle is not drawn from a randomness provider, or the provider can't be used this way")]
+    InvalidRandomnessProvider,
+    #[msg("Commitment is missing or doesn't match the revealed secret")]
+    InvalidRandomnessCommitment,
+    #[msg("Entry is not a v1 entry awaiting migration")]
+    EntryNotLegacy,
+    #[msg("Timestamp must be in seconds, not milliseconds")]
+    TimestampNotInSeconds,
+    #[msg("The clock offset can only be set on devnet builds")]
+    ClockOffsetUnavailable,
+    #[msg("Raffle requires an access code to buy tickets")]
+    AccessCodeRequired,
+    #[msg("Access code doesn't match the raffle's access code")]
+    InvalidAccessCode,
+    #[msg("Signer is not the identity issuer registered in the config")]
+    NotIdentityIssuer,
+    #[msg("Identity attestation is missing, expired or not from the identity issuer")]
+    InvalidIdentityAttestation,
+    #[msg("No raffle preset is registered under this name")]
+    RafflePresetNotFound,
+    #[msg("Raffle preset has an invalid name, duration or ticket limits, or too many are registered")]
+    InvalidRafflePreset,
+    #[msg("The draw can no longer be disputed, as the dispute window has closed")]
+    DisputeWindowClosed,
+    #[msg("Dispute bond must be greater than zero")]
+    InvalidDisputeBond,
+    #[msg("Raffle has no snapshot of its ticket holders")]
+    SnapshotNotRecorded,
+    #[msg("Proof doesn't prove the holding against the raffle's snapshot")]
+    InvalidSnapshotProof,
+    #[msg("Snapshot claim account is missing or not the holder's PDA")]
+    InvalidSnapshotClaim,
+    #[msg("Holding in the raffle's snapshot was already settled")]
+    SnapshotHoldingClaimed,
+    #[msg("Refunds of raffles with pricing tier discounts can't be proven from the snapshot")]
+    TierDiscountNotProvable,
+    #[msg("A draw can only be reset once the claim window passed since the draw")]
+    ResetDrawTooEarly,
+    #[msg("Purchases of raffles with pricing tiers must bound the total with expected_total")]
+    ExpectedTotalRequired,
+    #[msg("Winners of anonymized raffles are set with the winner's ticket balance")]
+    WinnerTicketBalanceRequired,
+    #[msg("Raffle doesn't anonymize its winners")]
+    RaffleNotAnonymized,
+    #[msg("Reservation can only be released by anyone once it has expired")]
+    ReservationNotExpired,
+    #[msg("No Bubblegum tree is registered to mint ticket collectibles into")]
+    CollectibleTreeNotSet,
+    #[msg("Merkle tree isn't the registered collectible tree")]
+    InvalidCollectibleTree,
+    #[msg("Metadata URI is too long for a compressed NFT")]
+    CollectibleUriTooLong,
+    #[msg("Raffle has too many pending reservations and voided ticket ranges")]
+    TooManyReservations,
+    #[msg("Winners can't be drawn while reservations are pending")]
+    ReservationsPending,
+    #[msg("Ticket balance is subsidized by a different campaign")]
+    CampaignMismatch,
+    #[msg("Refunds of raffles with campaign subsidies can't be proven from the snapshot")]
+    CampaignSubsidyNotProvable,
+    #[msg("Ticket balances subsidized by a campaign can't be rolled over")]
+    SubsidizedRollover,
 }
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{AuthorityRole, Config},
+};
+
+/// Event emitted when one of the config authorities is handed over
+#[event]
+pub struct ConfigUpdated {
+    /// The authority that was handed over
+    pub role: AuthorityRole,
+    /// The previous holder of the authority
+    pub previous_authority: Pubkey,
+    /// The new holder of the authority
+    pub new_authority: Pubkey,
+}
+
+/// Instruction for the successor proposed with propose_authority to take over the
+/// authority. Multisigs accept by signing with their vault, e.g. from a Squads
+/// transaction.
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `role` - The authority to take over
+///
+/// # Security Considerations
+/// - Only the proposed successor can accept, proving it controls the key
+/// - The pending proposal is cleared, so it can't be accepted twice
+pub fn accept_authority(ctx: Context<AcceptAuthority>, role: AuthorityRole) -> Result<()> {
+    let new_authority = ctx.accounts.new_authority.key();
+    let (authority, pending_authority) = ctx.accounts.config.authority_slots(role);
+    require!(
+        *pending_authority == Some(new_authority),
+        RaffleError::NotPendingAuthority
+    );
+
+    let previous_authority = *authority;
+    *authority = new_authority;
+    *pending_authority = None;
+
+    emit!(ConfigUpdated {
+        role,
+        previous_authority,
+        new_authority,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    /// The successor proposed for the authority
+    pub new_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, Raffle, Treasury},
+};
+
+/// Event emitted when the withdrawal approver approves a withdrawal from a treasury
+#[event]
+pub struct WithdrawalApproved {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// Largest amount the next withdrawal from the raffle's treasury may withdraw
+    pub amount: u64,
+    /// The approver that signed the approval
+    pub approver: Pubkey,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction for the config's withdrawal approver to co-sign a treasury withdrawal
+/// above the approval threshold before withdraw_from_treasury executes it
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `amount` - Largest amount, including the platform fee, the next withdrawal from
+///   the raffle's treasury may withdraw
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Verifies the signer is the config's withdrawal approver
+/// 2. Ensures the treasury is the raffle's treasury
+///
+/// # Implementation Notes
+/// - Approving again replaces the previous approval, so an approval of 0 revokes it
+/// - The approval only covers the next withdrawal, which consumes it
+pub fn approve_withdrawal(ctx: Context<ApproveWithdrawal>, amount: u64) -> Result<()> {
+    ctx.accounts.treasury.approved_withdrawal = amount;
+
+    let event_seq = ctx.accounts.raffle.next_event_seq()?;
+    emit!(WithdrawalApproved {
+        raffle: ctx.accounts.raffle.key(),
+        amount,
+        approver: ctx.accounts.approver.key(),
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ApproveWithdrawal<'info> {
+    /// The raffle whose treasury withdrawal is approved
+    #[account(mut)]
+    pub raffle: Account<'info, Raffle>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"treasury",
+            raffle.key().as_ref(),
+        ],
+        bump = treasury.bump,
+        constraint = treasury.key() == raffle.treasury @ RaffleError::InvalidTreasury,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    pub approver: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.withdrawal_approver == Some(approver.key()) @ RaffleError::NotWithdrawalApprover,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{AgeAttestation, Config, AGE_ATTESTATION_ACCOUNT_SIZE},
+    time::{now, require_seconds},
+};
+
+/// Event emitted when the age attestor vouches that a wallet's owner is of legal age
+#[event]
+pub struct AgeAttested {
+    /// The attested wallet
+    pub wallet: Pubkey,
+    /// Time after which the attestation is no longer accepted
+    pub expires_at: i64,
+}
+
+/// Instruction for the age attestor to record that a wallet's owner was verified to be
+/// 18 or older, checked by `buy_tickets` for age restricted raffles
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `wallet` - The wallet whose owner was verified
+/// * `expires_at` - Time after which the attestation is no longer accepted
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Only the age attestor registered in the config can attest ages
+/// 2. Ensures the attestation doesn't expire immediately
+///
+/// # Account Validations
+/// * Age Attestation - PDA with seeds ["age_attestation", wallet], created on first use
+/// * Attestor - Must match the age attestor stored in config, pays the rent
+///
+/// # Implementation Notes
+/// - Attesting a wallet again overwrites its previous attestation, e.g. to renew an
+///   expiring attestation
+pub fn attest_age(ctx: Context<AttestAge>, wallet: Pubkey, expires_at: i64) -> Result<()> {
+    require_seconds(expires_at)?;
+    require!(
+        expires_at > now(&ctx.accounts.config)?,
+        RaffleError::InvalidAgeAttestation
+    );
+
+    let age_attestation = &mut ctx.accounts.age_attestation;
+    age_attestation.wallet = wallet;
+    age_attestation.attestor = ctx.accounts.attestor.key();
+    age_attestation.expires_at = expires_at;
+    age_attestation.bump = ctx.bumps.age_attestation;
+
+    emit!(AgeAttested { wallet, expires_at });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct AttestAge<'info> {
+    #[account(
+        init_if_needed,
+        payer = attestor,
+        space = AGE_ATTESTATION_ACCOUNT_SIZE,
+        seeds = [
+            b"age_attestation",
+            wallet.as_ref(),
+        ],
+        bump,
+    )]
+    pub age_attestation: Account<'info, AgeAttestation>,
+
+    #[account(mut)]
+    pub attestor: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.age_attestor == Some(attestor.key())
+            @ RaffleError::NotAgeAttestor,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub system_program: Program<'info, System>,
+}
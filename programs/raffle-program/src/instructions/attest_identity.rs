@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, IdentityAttestation, IDENTITY_ATTESTATION_ACCOUNT_SIZE},
+    time::{now, require_seconds},
+};
+
+/// Event emitted when the identity issuer vouches for a wallet's identity credential
+#[event]
+pub struct IdentityAttested {
+    /// The attested wallet
+    pub wallet: Pubkey,
+    /// Hash of the credential the issuer verified
+    pub credential_hash: [u8; 32],
+    /// Time after which the attestation is no longer accepted
+    pub expires_at: i64,
+}
+
+/// Instruction for the identity issuer to record that a wallet's owner holds a
+/// verifiable identity credential, accepted by `submit_winner_attestation` in place of
+/// contact data
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `wallet` - The wallet whose owner holds the credential
+/// * `credential_hash` - Hash of the credential the issuer verified
+/// * `expires_at` - Time after which the attestation is no longer accepted
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Only the identity issuer registered in the config can attest identities
+/// 2. Ensures the attestation doesn't expire immediately
+///
+/// # Account Validations
+/// * Identity Attestation - PDA with seeds ["identity_attestation", wallet], created on
+///   first use
+/// * Issuer - Must match the identity issuer stored in config, pays the rent
+///
+/// # Implementation Notes
+/// - Attesting a wallet again overwrites its previous attestation, e.g. to renew an
+///   expiring attestation
+pub fn attest_identity(
+    ctx: Context<AttestIdentity>,
+    wallet: Pubkey,
+    credential_hash: [u8; 32],
+    expires_at: i64,
+) -> Result<()> {
+    require_seconds(expires_at)?;
+    require!(
+        expires_at > now(&ctx.accounts.config)?,
+        RaffleError::InvalidIdentityAttestation
+    );
+
+    let identity_attestation = &mut ctx.accounts.identity_attestation;
+    identity_attestation.wallet = wallet;
+    identity_attestation.issuer = ctx.accounts.issuer.key();
+    identity_attestation.credential_hash = credential_hash;
+    identity_attestation.expires_at = expires_at;
+    identity_attestation.bump = ctx.bumps.identity_attestation;
+
+    emit!(IdentityAttested {
+        wallet,
+        credential_hash,
+        expires_at,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct AttestIdentity<'info> {
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        space = IDENTITY_ATTESTATION_ACCOUNT_SIZE,
+        seeds = [
+            b"identity_attestation",
+            wallet.as_ref(),
+        ],
+        bump,
+    )]
+    pub identity_attestation: Account<'info, IdentityAttestation>,
+
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.identity_issuer == Some(issuer.key())
+            @ RaffleError::NotIdentityIssuer,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, RegionAttestation, MAX_REGIONS, REGION_ATTESTATION_ACCOUNT_SIZE},
+    time::{now, require_seconds},
+};
+
+/// Event emitted when the region attestor vouches for a wallet's region
+#[event]
+pub struct RegionAttested {
+    /// The attested wallet
+    pub wallet: Pubkey,
+    /// The attested region code
+    pub region: u8,
+    /// Time after which the attestation is no longer accepted
+    pub expires_at: i64,
+}
+
+/// Instruction for the region attestor to record a wallet's region, checked by
+/// `buy_tickets` for region restricted raffles
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `wallet` - The wallet whose region is attested
+/// * `region` - The region code, the index of the region's bit in `allowed_regions`
+/// * `expires_at` - Time after which the attestation is no longer accepted
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Only the region attestor registered in the config can attest regions
+/// 2. Ensures the region code fits the `allowed_regions` bitmask
+/// 3. Ensures the attestation doesn't expire immediately
+///
+/// # Account Validations
+/// * Region Attestation - PDA with seeds ["region_attestation", wallet], created on
+///   first use
+/// * Attestor - Must match the region attestor stored in config, pays the rent
+///
+/// # Implementation Notes
+/// - Attesting a wallet again overwrites its previous attestation, e.g. after the
+///   wallet moved or to renew an expiring attestation
+pub fn attest_region(
+    ctx: Context<AttestRegion>,
+    wallet: Pubkey,
+    region: u8,
+    expires_at: i64,
+) -> Result<()> {
+    require_seconds(expires_at)?;
+    require!(
+        region < MAX_REGIONS && expires_at > now(&ctx.accounts.config)?,
+        RaffleError::InvalidRegionAttestation
+    );
+
+    let region_attestation = &mut ctx.accounts.region_attestation;
+    region_attestation.wallet = wallet;
+    region_attestation.region = region;
+    region_attestation.attestor = ctx.accounts.attestor.key();
+    region_attestation.expires_at = expires_at;
+    region_attestation.bump = ctx.bumps.region_attestation;
+
+    emit!(RegionAttested {
+        wallet,
+        region,
+        expires_at,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct AttestRegion<'info> {
+    #[account(
+        init_if_needed,
+        payer = attestor,
+        space = REGION_ATTESTATION_ACCOUNT_SIZE,
+        seeds = [
+            b"region_attestation",
+            wallet.as_ref(),
+        ],
+        bump,
+    )]
+    pub region_attestation: Account<'info, RegionAttestation>,
+
+    #[account(mut)]
+    pub attestor: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.region_attestor == Some(attestor.key())
+            @ RaffleError::NotRegionAttestor,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub system_program: Program<'info, System>,
+}
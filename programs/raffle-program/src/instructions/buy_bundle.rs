@@ -0,0 +1,366 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{create_account, CreateAccount},
+};
+
+use crate::{
+    error::RaffleError,
+    instructions::buy_tickets::{
+        emit_large_purchase, validate_direct_purchase, validate_purchase,
+        validate_wallet_limit, TicketsPurchased,
+    },
+    math::{checked_add, checked_sub},
+    notifier::notify_if_sold_out,
+    state::{
+        Bundle, Config, Entry, Raffle, RaffleState, TicketBalance, Treasury, ENTRY_ACCOUNT_SIZE,
+        ENTRY_VERSION,
+    },
+    time::now,
+    transfer::rent_reserve,
+    treasury::{LamportVault, TreasuryVault},
+};
+
+/// Event emitted when a bundle is purchased
+#[event]
+pub struct BundlePurchased {
+    /// The pubkey of the bundle
+    pub bundle: Pubkey,
+    /// The buyer of the bundle
+    pub buyer: Pubkey,
+    /// Lamports paid for the bundle
+    pub price: u64,
+    /// Seed of the entries created in each raffle
+    pub entry_seed: [u8; 8],
+}
+
+/// Instruction to buy a bundle, purchasing one ticket in each of its raffles atomically
+/// for the bundle's discounted price
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `entry_seed` - Seed used to derive the new entry PDA in each raffle
+/// * `expected_total` - Optional maximum amount in lamports the buyer is willing to pay
+///
+/// # Remaining Accounts
+/// Groups of writable accounts `[raffle, treasury, ticket_balance, entry]` for each
+/// raffle of the bundle, in bundle order:
+/// * `raffle` - The bundle's raffle
+/// * `treasury` - The raffle's treasury
+/// * `ticket_balance` - The buyer's initialized ticket balance in the raffle
+/// * `entry` - The uninitialized entry PDA with seeds ["entry", raffle_key, entry_seed]
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Ensures the remaining accounts match the bundle's raffles and their PDAs
+/// 2. Validates every raffle is open, not past its end time and has tickets left
+/// 3. Ensures the bundle price does not exceed `expected_total`, if given
+/// 4. Splits the price among the treasuries in proportion to the ticket prices, so
+///    every raffle receives its share of the discount
+/// 5. Rejects region and age restricted raffles, as no attestations can be presented,
+///    private raffles, as no access code can be presented, and raffles in their
+///    priority phase
+/// 6. Rejects raffles paid in an SPL token, as bundles are paid in lamports, raffles
+///    issuing ticket receipts, which only buy_tickets mints, and raffles splitting their
+///    prize among top holders, as no leaderboard can be presented
+/// 7. Records each raffle's share of the discount as a pricing tier discount, so refunds
+///    return what was paid and the revenue threshold only counts what was collected
+/// 8. If the config rejects CPI purchases, ensures buy_bundle is a top-level
+///    instruction of the transaction
+///
+/// # Implementation Notes
+/// - Either all tickets are bought or the whole transaction fails
+/// - Each raffle records a TicketsPurchased event with the share it was paid, and a
+///   LargePurchase event if the share exceeds the config's threshold
+/// - Raffles the bundle sells out notify the config's notifier program
+pub fn buy_bundle<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BuyBundle<'info>>,
+    entry_seed: [u8; 8],
+    expected_total: Option<u64>,
+) -> Result<()> {
+    validate_direct_purchase(&ctx.accounts.config)?;
+
+    let bundle = &ctx.accounts.bundle;
+    let remaining_accounts = ctx.remaining_accounts;
+    require!(
+        remaining_accounts.len() == bundle.raffles.len() * 4,
+        RaffleError::InvalidRemainingAccounts
+    );
+
+    if let Some(expected_total) = expected_total {
+        require!(
+            bundle.price <= expected_total,
+            RaffleError::PriceExceedsExpectedTotal
+        );
+    }
+
+    let now = now(&ctx.accounts.config)?;
+    let mut raffles = Vec::with_capacity(bundle.raffles.len());
+    let mut full_price: u64 = 0;
+    for (group, raffle_key) in remaining_accounts.chunks(4).zip(&bundle.raffles) {
+        let raffle = Account::<Raffle>::try_from(&group[0])?;
+        require_keys_eq!(
+            raffle.key(),
+            *raffle_key,
+            RaffleError::InvalidRemainingAccounts
+        );
+        require!(
+            raffle.raffle_state == RaffleState::Open,
+            RaffleError::RaffleNotOpen
+        );
+        require!(
+            now < raffle.end_time,
+            RaffleError::RaffleEnded
+        );
+        require!(
+            raffle.allowed_regions == 0,
+            RaffleError::RegionAttestationRequired
+        );
+        require!(!raffle.age_restricted, RaffleError::AgeVerificationRequired);
+        require!(
+            raffle.access_code_hash.is_none(),
+            RaffleError::AccessCodeRequired
+        );
+        require!(
+            !raffle.in_priority_phase(now),
+            RaffleError::PriorityAccessRequired
+        );
+        require!(
+            raffle.payment_mint.is_none(),
+            RaffleError::TokenPaymentsNotSupported
+        );
+        require!(
+            !raffle.ticket_receipts,
+            RaffleError::TicketReceiptsNotSupported
+        );
+        require!(raffle.top_holders.is_none(), RaffleError::InvalidLeaderboard);
+        // The bundle share paid for the ticket is bounded by `expected_total` above, and
+        // tiered prices never exceed the ticket price
+        validate_purchase(
+            &raffle,
+            &ctx.accounts.signer,
+            1,
+            Some(raffle.ticket_price),
+            now,
+        )?;
+
+        full_price = checked_add(full_price, raffle.ticket_price)?;
+        raffles.push(raffle);
+    }
+
+    let mut paid: u64 = 0;
+    let raffle_count = raffles.len();
+    for (index, (mut raffle, group)) in raffles
+        .into_iter()
+        .zip(remaining_accounts.chunks(4))
+        .enumerate()
+    {
+        let [_, treasury_info, ticket_balance_info, entry_info] = group else {
+            return err!(RaffleError::InvalidRemainingAccounts);
+        };
+
+        // Split the price by ticket price, leaving the rounding remainder to the last raffle
+        let payment_amount = if index + 1 == raffle_count {
+            checked_sub(bundle.price, paid)?
+        } else {
+            let share = (bundle.price as u128)
+                .checked_mul(raffle.ticket_price as u128)
+                .and_then(|share| share.checked_div(full_price as u128))
+                .ok_or(RaffleError::Overflow)?;
+            u64::try_from(share).map_err(|_| RaffleError::Overflow)?
+        };
+        paid = checked_add(paid, payment_amount)?;
+
+        let mut treasury = Account::<Treasury>::try_from(treasury_info)?;
+        require_keys_eq!(
+            treasury.key(),
+            raffle.treasury,
+            RaffleError::InvalidTreasury
+        );
+
+        let mut ticket_balance = Account::<TicketBalance>::try_from(ticket_balance_info)?;
+        let ticket_balance_address = Pubkey::create_program_address(
+            &[
+                b"ticket_balance",
+                raffle.key().as_ref(),
+                ctx.accounts.signer.key().as_ref(),
+                &[ticket_balance.bump],
+            ],
+            &crate::ID,
+        )
+        .map_err(|_| RaffleError::TicketBalanceNotInitialized)?;
+        require!(
+            ticket_balance.key() == ticket_balance_address
+                && ticket_balance.owner == ctx.accounts.signer.key(),
+            RaffleError::TicketBalanceNotInitialized
+        );
+        validate_wallet_limit(&raffle, ticket_balance.ticket_count, 1)?;
+
+        let (entry_index, ticket_start_index) = raffle.record_entry()?;
+        create_entry(
+            entry_info,
+            &ctx.accounts.signer,
+            &ctx.accounts.system_program,
+            Entry {
+                raffle: raffle.key(),
+                owner: ctx.accounts.signer.key(),
+                ticket_count: 1,
+                ticket_start_index,
+                seed: entry_seed,
+                rent_sponsored: false,
+                memo: None,
+                next_entry: ticket_balance.head_entry,
+                entry_index,
+                ..Default::default()
+            },
+            now,
+        )?;
+        ticket_balance.head_entry = Some(entry_info.key());
+
+        raffle.current_tickets = checked_add(raffle.current_tickets, 1)?;
+        if ticket_balance.ticket_count == 0 {
+            raffle.participant_count = checked_add(raffle.participant_count, 1)?;
+        }
+        ticket_balance.ticket_count = checked_add(ticket_balance.ticket_count, 1)?;
+        ticket_balance.total_spent = checked_add(ticket_balance.total_spent, payment_amount)?;
+
+        // Record the raffle's share of the bundle discount, so a refund returns what was
+        // paid. The share of the last raffle may exceed its ticket price by the rounding
+        // remainder, which is no discount
+        let unit_price = payment_amount.min(raffle.ticket_price);
+        raffle.record_tier_discount(&mut ticket_balance, 1, unit_price)?;
+
+        LamportVault::new(&mut treasury, &ctx.accounts.system_program)
+            .collect(&ctx.accounts.signer.to_account_info(), payment_amount)?;
+
+        let event_seq = raffle.next_event_seq()?;
+        emit!(TicketsPurchased {
+            raffle: raffle.key(),
+            buyer: ctx.accounts.signer.key(),
+            ticket_count: 1,
+            payment_amount,
+            unit_price: payment_amount,
+            ticket_start_index,
+            entry_seed,
+            campaign_discount: 0,
+            rent_sponsored: false,
+            memo: None,
+            referrer: None,
+            cumulative_tickets: ticket_balance.ticket_count,
+            cumulative_spend: ticket_balance.total_spent,
+            win_probability_bps: raffle.win_probability_bps(ticket_balance.ticket_count),
+            total_tickets: raffle.current_tickets,
+            event_seq,
+        });
+        emit_large_purchase(
+            &ctx.accounts.config,
+            &mut raffle,
+            ctx.accounts.signer.key(),
+            1,
+            payment_amount,
+        )?;
+
+        // Accounts loaded from remaining accounts are not persisted by Anchor
+        raffle.exit(&crate::ID)?;
+        treasury.exit(&crate::ID)?;
+        ticket_balance.exit(&crate::ID)?;
+
+        notify_if_sold_out(
+            &ctx.accounts.config,
+            ctx.accounts.notifier_program.as_ref(),
+            &raffle,
+        )?;
+    }
+
+    emit!(BundlePurchased {
+        bundle: bundle.key(),
+        buyer: ctx.accounts.signer.key(),
+        price: bundle.price,
+        entry_seed,
+    });
+
+    Ok(())
+}
+
+/// Creates the entry PDA with seeds ["entry", raffle_key, entry_seed], failing if it
+/// already exists, and writes `entry` to it, stamped with the current layout version,
+/// the PDA's bump and `now` as the creation time
+pub(crate) fn create_entry<'info>(
+    entry_info: &AccountInfo<'info>,
+    signer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    entry: Entry,
+    now: i64,
+) -> Result<()> {
+    let (address, bump) = Pubkey::find_program_address(
+        &[b"entry", entry.raffle.as_ref(), entry.seed.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(
+        entry_info.key(),
+        address,
+        RaffleError::InvalidRemainingAccounts
+    );
+
+    create_account(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            CreateAccount {
+                from: signer.to_account_info(),
+                to: entry_info.clone(),
+            },
+            &[&[
+                b"entry",
+                entry.raffle.as_ref(),
+                entry.seed.as_ref(),
+                &[bump],
+            ]],
+        ),
+        rent_reserve(ENTRY_ACCOUNT_SIZE)?,
+        ENTRY_ACCOUNT_SIZE as u64,
+        &crate::ID,
+    )?;
+
+    let entry = Entry {
+        version: ENTRY_VERSION,
+        bump,
+        created_at: now,
+        ..entry
+    };
+    entry.try_serialize(&mut &mut entry_info.try_borrow_mut_data()?[..])?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct BuyBundle<'info> {
+    /// The bundle being purchased
+    /// PDA with seeds ["bundle", bundle_id]
+    #[account(
+        seeds = [
+            b"bundle",
+            bundle.bundle_id.to_le_bytes().as_ref(),
+        ],
+        bump = bundle.bump,
+    )]
+    pub bundle: Account<'info, Bundle>,
+
+    /// The account purchasing the bundle and paying for the entry accounts
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Required for creating the entries and transferring the payment
+    pub system_program: Program<'info, System>,
+
+    /// Program config, checked for the pause switch and the large purchase threshold
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ RaffleError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Program notified of each raffle the bundle sells out, required then if the config
+    /// registers one
+    /// CHECK: Validated against the config's notifier program in the instruction
+    pub notifier_program: Option<UncheckedAccount<'info>>,
+}
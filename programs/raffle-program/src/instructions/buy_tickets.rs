@@ -1,13 +1,49 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        compute_units::sol_remaining_compute_units,
+        instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT},
+    },
+    system_program::{create_account, CreateAccount},
+};
 
 use crate::{
     error::RaffleError,
+    instructions::set_access_code::access_code_hash,
+    math::{bps_of, checked_add, checked_sub, ticket_cost},
+    notifier::notify_if_sold_out,
     state::{
         entry::Entry,
         raffle::{Raffle, RaffleState},
-        TicketBalance, Treasury, ENTRY_ACCOUNT_SIZE,
+        AgeAttestation, Campaign, Config, Leaderboard, PurchaseDedupe, ReferralStats, RegionAttestation, RentPool, TicketBalance,
+        Treasury, UserProfile, ENTRY_ACCOUNT_SIZE, ENTRY_VERSION,
+        MAX_MEMO_LENGTH, PURCHASE_DEDUPE_ACCOUNT_SIZE,
     },
+    receipt::mint_ticket_receipt,
+    time::now,
+    transfer::{excess_lamports, move_lamports, rent_reserve, rent_reserve_of},
+    treasury::{token_accounts, LamportVault, TokenVault, TreasuryVault},
 };
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Token, TokenAccount},
+    token_2022::Token2022,
+};
+
+/// Compute units buy_tickets keeps in reserve for the optional rent pool reimbursement
+/// and the TicketsPurchased event. Both are skipped if less remain after the purchase
+pub const OPTIONAL_PURCHASE_WORK_COMPUTE_UNITS: u64 = 15_000;
+
+/// Outcome of a purchase returned by `buy_tickets`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PurchaseReceipt {
+    /// Index of the first purchased ticket, or `UNNUMBERED_TICKET_START` if the raffle
+    /// defers numbering
+    pub ticket_start_index: u64,
+    /// Whether the rent pool reimbursement and the TicketsPurchased event were skipped
+    /// because the transaction was running out of compute units
+    pub degraded: bool,
+}
 
 /// Event emitted when tickets are purchased
 #[event]
@@ -18,12 +54,55 @@ pub struct TicketsPurchased {
     pub buyer: Pubkey,
     /// Number of tickets purchased
     pub ticket_count: u64,
-    /// Total amount paid in lamports
+    /// Total amount paid in lamports, or in base units of the raffle's payment mint
     pub payment_amount: u64,
-    /// Starting ticket index for this purchase
+    /// Effective price per ticket after the raffle's pricing tiers, in the unit of
+    /// `payment_amount`
+    pub unit_price: u64,
+    /// Starting ticket index for this purchase, or `UNNUMBERED_TICKET_START` if the
+    /// raffle defers numbering
     pub ticket_start_index: u64,
     /// The seed that was used to create the entry
     pub entry_seed: [u8; 8],
+    /// Part of the payment in lamports covered by a promotional campaign
+    pub campaign_discount: u64,
+    /// Whether the entry rent was reimbursed by the rent pool
+    pub rent_sponsored: bool,
+    /// Optional memo attached to the purchase, left out if the config's events are
+    /// compact
+    pub memo: Option<String>,
+    /// The referrer the purchase was attributed to, if any
+    pub referrer: Option<Pubkey>,
+    /// Tickets the buyer holds in this raffle after the purchase
+    pub cumulative_tickets: u64,
+    /// Total the buyer has paid for tickets in this raffle after the purchase, in the
+    /// unit of `payment_amount`, so receipts don't require replaying earlier purchases
+    pub cumulative_spend: u64,
+    /// Chance in basis points, rounded down, of each drawn ticket being one of the
+    /// buyer's after the purchase
+    pub win_probability_bps: u16,
+    /// Tickets sold in the raffle after the purchase
+    pub total_tickets: u64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Event emitted alongside TicketsPurchased when a single purchase exceeds the config's
+/// large purchase threshold, so monitoring bots don't have to filter every purchase
+#[event]
+pub struct LargePurchase {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The buyer's address
+    pub buyer: Pubkey,
+    /// Number of tickets purchased
+    pub ticket_count: u64,
+    /// Total amount paid in lamports
+    pub payment_amount: u64,
+    /// The threshold the payment exceeded, in lamports
+    pub threshold: u64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
 }
 
 /// Instruction to purchase tickets for a raffle
@@ -31,16 +110,60 @@ pub struct TicketsPurchased {
 /// # Arguments
 /// * `ctx` - The context object containing all required accounts
 /// * `ticket_count` - The number of tickets to purchase
+/// * `entry_seed` - Seed used to derive the new entry PDA
+/// * `expected_total` - Optional maximum amount in lamports, or in base units of the
+///   payment mint, the buyer is willing to pay, protecting against price changes between simulation and execution.
+///   Required if the raffle has pricing tiers
+/// * `memo` - Optional memo of at most 64 bytes, stored on the entry
+/// * `idempotency_key` - Optional client-supplied key recorded in a PurchaseDedupe PDA,
+///   so a retried transaction with the same key cannot purchase twice
+/// * `referrer` - Optional wallet that referred the buyer, credited in its ReferralStats
+///   PDA
+/// * `rollover` - Whether the buyer opts in to roll their balance over to the raffle's
+///   successor with rollover_tickets, should the raffle expire
+/// * `access_code` - The raffle's access code, required if the raffle is private
 ///
 /// # Security Considerations
 /// The instruction performs several critical checks:
 /// 1. Validates ticket count is greater than 0
 /// 2. If the raffle has a maximum ticket count, ensures the purchase does not exceed that limit
+///    and, if it limits tickets per purchase, that the ticket count stays within it
 /// 3. Ensures buyer has sufficient funds to purchase tickets
+///    and, if given, that the payment amount does not exceed `expected_total`
 /// 4. Verifies the treasury account matches the one stored in raffle
 /// 5. Validates raffle is in Open state through account constraints
 /// 6. Ensures raffle hasn't ended through timestamp constraint
 /// 7. Uses PDAs with proper seeds for entry and ticket_balance accounts
+/// 8. Validates the memo, if given, does not exceed 64 bytes
+/// 9. If an idempotency key is given, ensures the dedupe account is the PDA for that key
+///    and fails with DuplicatePurchase if it already exists
+/// 10. Only reimburses entry rent from the rent pool if it is enabled and keeps its own rent
+/// 11. If the raffle splits its prize among top holders, ensures the leaderboard is the
+///     raffle's leaderboard
+/// 12. Never pays more from a campaign than its remaining budget, and only from the
+///     campaign that subsidized the buyer's earlier tickets, if any
+/// 13. If the raffle is region restricted, ensures the buyer holds an unexpired
+///     attestation of an allowed region signed by the config's current region attestor
+/// 14. If the raffle is age restricted, ensures the buyer holds an unexpired attestation
+///     of being 18 or older signed by the config's current age attestor
+/// 15. During the raffle's priority phase, ensures the buyer's profile has at least the
+///     required loyalty tier
+/// 16. If the raffle is paid in an SPL token, ensures the payment goes to the treasury's
+///     associated token account for the payment mint, and rejects campaigns, whose
+///     budgets are held in lamports
+/// 17. If the config rejects CPI purchases, ensures buy_tickets is a top-level
+///     instruction of the transaction rather than invoked by another program
+/// 18. If the raffle limits tickets per wallet, ensures the buyer's total tickets stay
+///     within that limit
+/// 19. If the raffle is gated, only sells to buyers whose ticket balance was initialized,
+///     which `init_ticket_balance` only allows for wallets meeting the gate
+/// 20. Fails with ProgramPaused while the config's pause switch is on
+/// 21. If a referrer is given, ensures it is not the buyer and the referral stats are
+///     the PDA of the raffle and referrer
+/// 22. If the raffle issues ticket receipts, ensures the receipt mint is the PDA of the
+///     new entry
+/// 23. If the raffle is private, ensures the access code hashes to the raffle's access
+///     code hash
 ///
 /// # Account Validations
 /// * Raffle - Must be in Open state and not expired
@@ -48,40 +171,113 @@ pub struct TicketsPurchased {
 /// * TicketBalance - Existing PDA tracking user's total tickets
 /// * Signer - Must have sufficient funds for purchase
 /// * Treasury - Must match raffle's treasury and uses proper PDA seeds
+/// * RentPool - Optional, the program's rent pool that sponsors the entry rent
+/// * PurchaseDedupe - Optional, new PDA created for the idempotency key
+/// * Leaderboard - Optional, required if the raffle splits its prize among top holders
+/// * Campaign - Optional, promotional campaign paying part of the ticket cost
+/// * Config - PDA storing the region and age attestors and the CPI purchase policy
+/// * RegionAttestation - Optional, required if the raffle is region restricted
+/// * AgeAttestation - Optional, required if the raffle is age restricted
+/// * UserProfile - Optional, required during the raffle's priority phase
+/// * PayerTokenAccount - Optional, the buyer's token account paying for the tickets,
+///   required if the raffle is paid in an SPL token
+/// * TreasuryTokenAccount - Optional, required if the raffle is paid in an SPL token
+/// * TokenProgram - Optional, required if the raffle is paid in an SPL token
+/// * ReferralStats - Optional, required if a referrer is given
+/// * ReceiptMint - Optional, new PDA with seeds ["ticket_receipt", entry_key], required
+///   if the raffle issues ticket receipts, as are the buyer's receipt token account and
+///   the Token-2022 and associated token programs
 ///
 /// # Implementation Notes
 /// - Uses checked arithmetic operations to prevent overflow
 /// - Updates state before performing external calls
 /// - Implements safe lamport calculations
-pub fn buy_tickets(ctx: Context<BuyTickets>, ticket_count: u64, entry_seed: [u8; 8]) -> Result<()> {
-    // Validate ticket count
-    require!(ticket_count > 0, RaffleError::InvalidTicketCount);
-
-    // Check if still allowed to buy tickets
-    if let Some(max_tickets) = ctx.accounts.raffle.max_tickets {
-        require!(
-            ctx.accounts.raffle.current_tickets < max_tickets, 
-            RaffleError::MaximumTicketsSold
-        );
+/// - Records the buyer's running ticket and spend totals on the ticket balance, and
+///   reports them in the TicketsPurchased event for receipt generation
+/// - The buyer pays the entry rent on creation and is reimbursed by the rent pool if
+///   it is enabled and funded; otherwise the buyer keeps paying the rent
+/// - A campaign pays its discount share of the ticket cost into the treasury until its
+///   budget is exhausted, after which purchases are charged the full price. The share
+///   is recorded on the ticket balance, whose tickets can only be subsidized by one
+///   campaign
+/// - The new entry is prepended to the buyer's linked list of entries, so all entries
+///   of a wallet can be walked starting at `TicketBalance::head_entry`
+/// - If fewer than `OPTIONAL_PURCHASE_WORK_COMPUTE_UNITS` remain once the tickets are
+///   recorded and paid, the rent pool reimbursement and the TicketsPurchased event are
+///   skipped, so the purchase still succeeds inside large composed transactions. The
+///   returned receipt reports this as `degraded`, and the entry account remains the
+///   record of the purchase
+/// - If the raffle defers numbering, the entry, event and receipt don't reveal the
+///   ticket start index until `finalize_numbering` assigns it after the sale
+/// - Referred purchases accrue the raffle's referral fee on the full payment amount,
+///   which is reserved in the treasury and paid to the referrer at withdrawal
+/// - If the raffle issues ticket receipts, mints the entry's receipt NFT to the buyer,
+///   who pays the rent of the mint and of their token account, see `receipt`
+/// - Purchases paid in lamports above the config's large purchase threshold also emit
+///   a LargePurchase event, skipped along with TicketsPurchased if degraded
+/// - Opting in to rollover applies to the buyer's whole balance in the raffle and can't
+///   be withdrawn by later purchases
+#[allow(clippy::too_many_arguments)]
+pub fn buy_tickets(
+    ctx: Context<BuyTickets>,
+    ticket_count: u64,
+    entry_seed: [u8; 8],
+    expected_total: Option<u64>,
+    memo: Option<String>,
+    idempotency_key: Option<[u8; 16]>,
+    referrer: Option<Pubkey>,
+    rollover: bool,
+    access_code: Option<String>,
+) -> Result<PurchaseReceipt> {
+    validate_direct_purchase(&ctx.accounts.config)?;
 
-        require!(
-            ctx.accounts.raffle.max_tickets >= ctx.accounts.raffle.current_tickets.checked_add(ticket_count), 
-            RaffleError::PurchaseExceedsThreshold
-        );
+    // Validate memo length, as the entry only reserves space for a short memo
+    if let Some(memo) = &memo {
+        require!(memo.len() <= MAX_MEMO_LENGTH, RaffleError::MemoTooLong);
     }
-    
-    // Calculate payment amount with overflow protection
-    let payment_amount = ticket_count
-        .checked_mul(ctx.accounts.raffle.ticket_price)
-        .ok_or(RaffleError::Overflow)?;
-    
-    // Validate buyer has sufficient funds using checked comparison
-    require!(
-        ctx.accounts.signer.lamports()
-            .checked_sub(payment_amount)
-            .ok_or(RaffleError::InsufficientFunds)? > 0,
-        RaffleError::InsufficientFunds,
-    );
+
+    // Validate the ticket count, limits, price and buyer funds
+    let payment_amount = validate_purchase(
+        &ctx.accounts.raffle,
+        &ctx.accounts.signer,
+        ticket_count,
+        expected_total,
+        now(&ctx.accounts.config)?,
+    )?;
+
+    // Keep a single wallet from buying up the supply, if the raffle limits it
+    validate_wallet_limit(
+        &ctx.accounts.raffle,
+        ctx.accounts.ticket_balance.ticket_count,
+        ticket_count,
+    )?;
+
+    // Enforce the raffle's jurisdiction rules, if it is region restricted
+    validate_region(
+        &ctx.accounts.raffle,
+        ctx.accounts.region_attestation.as_deref(),
+        &ctx.accounts.config,
+        &ctx.accounts.signer,
+    )?;
+
+    // Only sell to buyers verified to be of legal age, if the raffle is age restricted
+    validate_age(
+        &ctx.accounts.raffle,
+        ctx.accounts.age_attestation.as_deref(),
+        &ctx.accounts.config,
+        &ctx.accounts.signer,
+    )?;
+
+    // Only sell to buyers who were shared the code, if the raffle is private
+    validate_access_code(&ctx.accounts.raffle, access_code.as_deref())?;
+
+    // Only sell to buyers of a sufficient tier until the priority phase ends
+    validate_priority_access(
+        &ctx.accounts.raffle,
+        ctx.accounts.user_profile.as_deref(),
+        &ctx.accounts.signer,
+        now(&ctx.accounts.config)?,
+    )?;
 
     // Ensure treasury account matches the one stored in raffle
     require!(
@@ -97,71 +293,565 @@ pub fn buy_tickets(ctx: Context<BuyTickets>, ticket_count: u64, entry_seed: [u8;
 
     // Initialize entry data in the PDA
     // Each entry represents a single purchase transaction
+    let (entry_index, ticket_start_index) = ctx.accounts.raffle.record_entry()?;
     let entry = &mut ctx.accounts.entry;
     entry.raffle = ctx.accounts.raffle.key();
     entry.owner = ctx.accounts.signer.key();
     entry.ticket_count = ticket_count;
-    entry.ticket_start_index = ctx.accounts.raffle.current_tickets;
+    entry.ticket_start_index = ticket_start_index;
     entry.seed = entry_seed;
+    entry.memo = memo.clone();
+    entry.entry_index = entry_index;
+    entry.version = ENTRY_VERSION;
+    entry.bump = ctx.bumps.entry;
+    entry.created_at = now(&ctx.accounts.config)?;
+
+    // Prepend the entry to the buyer's list of entries
+    entry.next_entry = ctx.accounts.ticket_balance.head_entry;
+    ctx.accounts.ticket_balance.head_entry = Some(entry.key());
+
+    // Record the purchase under its idempotency key, failing if it was already used
+    if let Some(idempotency_key) = idempotency_key {
+        let purchase_dedupe = ctx
+            .accounts
+            .purchase_dedupe
+            .as_ref()
+            .ok_or(RaffleError::InvalidPurchaseDedupe)?;
+        record_purchase_dedupe(
+            purchase_dedupe,
+            &ctx.accounts.signer,
+            &ctx.accounts.system_program,
+            ctx.accounts.raffle.key(),
+            ctx.accounts.entry.key(),
+            idempotency_key,
+        )?;
+    }
 
     // Update raffle state with new ticket count using checked arithmetic
-    ctx.accounts.raffle.current_tickets = ctx.accounts.raffle.current_tickets
-        .checked_add(ticket_count)
-        .ok_or(RaffleError::Overflow)?;
+    ctx.accounts.raffle.current_tickets = checked_add(ctx.accounts.raffle.current_tickets, ticket_count)?;
+
+    // Count the buyer as a participant on their first purchase
+    if ctx.accounts.ticket_balance.ticket_count == 0 {
+        ctx.accounts.raffle.participant_count = checked_add(ctx.accounts.raffle.participant_count, 1)?;
+    }
 
     // Update user's total ticket balance with overflow protection
     let ticket_balance = &mut ctx.accounts.ticket_balance;
-    ticket_balance.ticket_count = ticket_balance.ticket_count
-        .checked_add(ticket_count)
-        .ok_or(RaffleError::Overflow)?;
-
-    // Store pre-transfer balance for verification
-    let pre_transfer_balance = ctx.accounts.treasury.to_account_info().lamports();
-
-    // Transfer lamports from the buyer to the raffle treasury
-    anchor_lang::solana_program::program::invoke(
-        &anchor_lang::solana_program::system_instruction::transfer(
-            &ctx.accounts.signer.key(),
-            &ctx.accounts.treasury.key(),
-            payment_amount,
-        ),
-        &[
-            ctx.accounts.signer.to_account_info(),
-            ctx.accounts.system_program.to_account_info(),
-            ctx.accounts.treasury.to_account_info(),
-        ],
+    ticket_balance.ticket_count = checked_add(ticket_balance.ticket_count, ticket_count)?;
+
+    // Once opted in, the whole balance rolls over, including earlier purchases
+    ticket_balance.rollover |= rollover;
+
+    // Record the bulk discount, so a refund returns what was paid for the tickets
+    let unit_price = ctx.accounts.raffle.unit_price(ticket_count);
+    ctx.accounts
+        .raffle
+        .record_tier_discount(ticket_balance, ticket_count, unit_price)?;
+
+    // Track the buyer's new balance on the leaderboard, if the prize is split among top holders
+    record_top_holder(
+        &ctx.accounts.raffle,
+        ctx.accounts.leaderboard.as_mut(),
+        &ctx.accounts.ticket_balance,
     )?;
 
-    // Verify the transfer was successful by checking treasury balance
-    let post_transfer_balance = ctx.accounts.treasury.to_account_info().lamports();
-    require!(
-        post_transfer_balance == pre_transfer_balance.checked_add(payment_amount).ok_or(RaffleError::Overflow)?,
-        RaffleError::TransferFailed
-    );
+    // Pay part of the ticket cost from the campaign budget, until it is exhausted
+    let mut campaign_discount = 0;
+    if let Some(campaign) = &mut ctx.accounts.campaign {
+        require!(
+            ctx.accounts.raffle.payment_mint.is_none(),
+            RaffleError::TokenPaymentsNotSupported
+        );
+        campaign_discount = bps_of(payment_amount, campaign.discount_bps)?.min(campaign.remaining()?);
+        if campaign_discount > 0 {
+            campaign.record_spend(campaign_discount)?;
+
+            // Record the subsidy, so a refund returns it to the campaign
+            let ticket_balance = &mut ctx.accounts.ticket_balance;
+            require!(
+                ticket_balance.subsidy_campaign.unwrap_or(campaign.key()) == campaign.key(),
+                RaffleError::CampaignMismatch
+            );
+            ticket_balance.subsidy_campaign = Some(campaign.key());
+            ticket_balance.campaign_subsidy =
+                checked_add(ticket_balance.campaign_subsidy, campaign_discount)?;
+            ctx.accounts.raffle.campaign_subsidies =
+                checked_add(ctx.accounts.raffle.campaign_subsidies, campaign_discount)?;
+
+            // The campaign is a PDA owned by our program, so lamports can be moved directly
+            move_lamports(
+                &campaign.to_account_info(),
+                &ctx.accounts.treasury.to_account_info(),
+                campaign_discount,
+            )?;
+            ctx.accounts.treasury.record_deposit(campaign_discount)?;
+            ctx.accounts.treasury.record_collection(campaign_discount)?;
+        }
+    }
+
+    // Credit the referrer with the purchase and accrue its referral fee
+    if let Some(referrer) = referrer {
+        require_keys_neq!(
+            referrer,
+            ctx.accounts.signer.key(),
+            RaffleError::SelfReferral
+        );
+        let referral_stats = ctx
+            .accounts
+            .referral_stats
+            .as_mut()
+            .ok_or(RaffleError::InvalidReferralStats)?;
+        require!(
+            referral_stats.raffle == ctx.accounts.raffle.key()
+                && referral_stats.referrer == referrer,
+            RaffleError::InvalidReferralStats
+        );
+        let referral_fee = bps_of(payment_amount, ctx.accounts.raffle.referral_fee_bps)?;
+        referral_stats.record(ticket_count, payment_amount, referral_fee)?;
+        ctx.accounts.treasury.record_referral_fee(referral_fee)?;
+    }
+
+    // Transfer the payment to the treasury and track the collected funds
+    let amount_due = checked_sub(payment_amount, campaign_discount)?;
+    ctx.accounts.ticket_balance.total_spent = checked_add(ctx.accounts.ticket_balance.total_spent, amount_due)?;
+    if ctx.accounts.raffle.payment_mint.is_some() {
+        let (vault, token_program) = token_accounts(
+            &ctx.accounts.raffle,
+            &ctx.accounts.treasury,
+            ctx.accounts.treasury_token_account.as_mut(),
+            ctx.accounts.token_program.as_ref(),
+        )?;
+        let payer_token_account = ctx
+            .accounts
+            .payer_token_account
+            .as_ref()
+            .ok_or(RaffleError::InvalidPaymentMint)?;
+        require!(
+            payer_token_account.amount >= amount_due,
+            RaffleError::InsufficientFunds
+        );
+        TokenVault::new(&mut ctx.accounts.treasury, vault, token_program)
+            .with_payer_authority(&ctx.accounts.signer.to_account_info())
+            .collect(&payer_token_account.to_account_info(), amount_due)?;
+    } else {
+        LamportVault::new(&mut ctx.accounts.treasury, &ctx.accounts.system_program)
+            .collect(&ctx.accounts.signer.to_account_info(), amount_due)?;
+    }
+
+    // Mint the entry's transferable receipt, if the raffle issues them
+    mint_ticket_receipt(
+        &ctx.accounts.raffle,
+        &ctx.accounts.entry,
+        &ctx.accounts.signer,
+        ctx.accounts.receipt_mint.as_ref(),
+        ctx.accounts.receipt_token_account.as_ref(),
+        ctx.accounts.token_2022_program.as_ref(),
+        ctx.accounts.associated_token_program.as_ref(),
+        &ctx.accounts.system_program,
+    )?;
+
+    notify_if_sold_out(
+        &ctx.accounts.config,
+        ctx.accounts.notifier_program.as_ref(),
+        &ctx.accounts.raffle,
+    )?;
+
+    // The purchase is complete, so skip the optional work rather than failing the whole
+    // transaction if it is running out of compute units, e.g. inside a large composition
+    let degraded = sol_remaining_compute_units() < OPTIONAL_PURCHASE_WORK_COMPUTE_UNITS;
+    if degraded {
+        return Ok(PurchaseReceipt {
+            ticket_start_index,
+            degraded,
+        });
+    }
+
+    // Reimburse the entry rent from the rent pool, if enabled and sufficiently funded
+    let mut rent_sponsored = false;
+    if let Some(rent_pool) = &mut ctx.accounts.rent_pool {
+        let entry_rent = rent_reserve(ENTRY_ACCOUNT_SIZE)?;
+        let rent_pool_info = rent_pool.to_account_info();
+        let available = excess_lamports(&rent_pool_info, rent_reserve_of(&rent_pool_info)?);
+
+        if rent_pool.enabled && available >= entry_rent {
+            rent_pool.total_reimbursed = checked_add(rent_pool.total_reimbursed, entry_rent)?;
+
+            // The rent pool is a PDA owned by our program, so lamports can be moved directly
+            move_lamports(
+                &rent_pool.to_account_info(),
+                &ctx.accounts.signer.to_account_info(),
+                entry_rent,
+            )?;
+            rent_sponsored = true;
+        }
+    }
+    ctx.accounts.entry.rent_sponsored = rent_sponsored;
 
     // Emit the tickets purchased event
+    let event_seq = ctx.accounts.raffle.next_event_seq()?;
     emit!(TicketsPurchased {
         raffle: ctx.accounts.raffle.key(),
         buyer: ctx.accounts.signer.key(),
         ticket_count,
         payment_amount,
-        ticket_start_index: entry.ticket_start_index,
+        unit_price,
+        ticket_start_index,
         entry_seed,
+        campaign_discount,
+        rent_sponsored,
+        memo: memo.filter(|_| !ctx.accounts.config.compact_events),
+        referrer,
+        cumulative_tickets: ctx.accounts.ticket_balance.ticket_count,
+        cumulative_spend: ctx.accounts.ticket_balance.total_spent,
+        win_probability_bps: ctx
+            .accounts
+            .raffle
+            .win_probability_bps(ctx.accounts.ticket_balance.ticket_count),
+        total_tickets: ctx.accounts.raffle.current_tickets,
+        event_seq,
     });
+    emit_large_purchase(
+        &ctx.accounts.config,
+        &mut ctx.accounts.raffle,
+        ctx.accounts.signer.key(),
+        ticket_count,
+        payment_amount,
+    )?;
+
+    Ok(PurchaseReceipt {
+        ticket_start_index,
+        degraded,
+    })
+}
+
+/// Validates a purchase of `ticket_count` tickets and returns the payment amount in lamports,
+/// or in base units of the raffle's payment mint
+///
+/// Shared by all instructions that sell tickets, so the limits and price checks
+/// are enforced identically regardless of how the tickets are recorded.
+pub(crate) fn validate_purchase(
+    raffle: &Raffle,
+    signer: &Signer,
+    ticket_count: u64,
+    expected_total: Option<u64>,
+    now: i64,
+) -> Result<u64> {
+    // Validate ticket count
+    require!(ticket_count > 0, RaffleError::InvalidTicketCount);
+
+    // Raffles that missed their funding deadline only await expiry
+    require!(
+        !raffle.funding_failed(now)?,
+        RaffleError::FundingDeadlineMissed
+    );
+
+    // Snapshotted holdings are refundable by proof, so they must not change anymore
+    require!(
+        raffle.snapshot_root.is_none(),
+        RaffleError::SnapshotAlreadyRecorded
+    );
+
+    // Check the single purchase limit
+    if let Some(max_per_purchase) = raffle.max_per_purchase {
+        require!(
+            ticket_count <= max_per_purchase,
+            RaffleError::PurchaseExceedsMaxPerPurchase
+        );
+    }
+
+    // Check if still allowed to buy tickets
+    if let Some(max_tickets) = raffle.max_tickets {
+        // Tickets held by pending reservations are not available for sale
+        let held_tickets = checked_add(raffle.current_tickets, raffle.reserved_tickets)?;
+        require!(
+            held_tickets < max_tickets, 
+            RaffleError::MaximumTicketsSold
+        );
+
+        require!(
+            raffle.max_tickets >= held_tickets.checked_add(ticket_count), 
+            RaffleError::PurchaseExceedsThreshold
+        );
+    }
+    
+    // Calculate payment amount at the price of the purchase's pricing tier, with
+    // overflow protection
+    let payment_amount = ticket_cost(ticket_count, raffle.unit_price(ticket_count))?;
+
+    // The price of a tiered raffle depends on the purchase size, so buyers must state
+    // the total they agreed to
+    require!(
+        raffle.pricing_tiers.is_empty() || expected_total.is_some(),
+        RaffleError::ExpectedTotalRequired
+    );
+
+    // Protect the buyer from paying more than the amount they expected
+    if let Some(expected_total) = expected_total {
+        require!(
+            payment_amount <= expected_total,
+            RaffleError::PriceExceedsExpectedTotal
+        );
+    }
+    
+    // Validate buyer has sufficient funds using checked comparison. Token balances are
+    // checked against the paying token account by the caller
+    if raffle.payment_mint.is_none() {
+        require!(
+            signer.lamports()
+                .checked_sub(payment_amount)
+                .ok_or(RaffleError::InsufficientFunds)? > 0,
+            RaffleError::InsufficientFunds,
+        );
+    }
+
+    Ok(payment_amount)
+}
+
+/// Ensures the purchase is a top-level instruction of the transaction rather than invoked
+/// by another program, if the operator opted out of composability. Shared by all
+/// instructions that sell tickets, so the policy can't be bypassed through another path.
+pub(crate) fn validate_direct_purchase(config: &Config) -> Result<()> {
+    if config.reject_cpi_purchases {
+        require!(
+            get_stack_height() == TRANSACTION_LEVEL_STACK_HEIGHT,
+            RaffleError::CpiPurchaseRejected
+        );
+    }
+    Ok(())
+}
+
+/// Ensures a wallet already holding `held_tickets` may acquire `ticket_count` more, if
+/// the raffle limits the tickets a single wallet can hold
+pub(crate) fn validate_wallet_limit(raffle: &Raffle, held_tickets: u64, ticket_count: u64) -> Result<()> {
+    if let Some(max_tickets_per_wallet) = raffle.max_tickets_per_wallet {
+        require!(
+            checked_add(held_tickets, ticket_count)? <= max_tickets_per_wallet,
+            RaffleError::WalletLimitExceeded
+        );
+    }
+    Ok(())
+}
+
+/// Ensures the buyer may purchase tickets of a region restricted raffle, by holding an
+/// unexpired attestation of one of the raffle's allowed regions, signed by the region
+/// attestor currently registered in the config. Instructions that don't accept an
+/// attestation pass `None`, which rejects all region restricted raffles.
+pub(crate) fn validate_region(
+    raffle: &Raffle,
+    region_attestation: Option<&RegionAttestation>,
+    config: &Config,
+    signer: &Signer,
+) -> Result<()> {
+    if raffle.allowed_regions == 0 {
+        return Ok(());
+    }
+
+    let region_attestation = region_attestation.ok_or(RaffleError::RegionAttestationRequired)?;
+    require!(
+        region_attestation.wallet == signer.key()
+            && config.region_attestor == Some(region_attestation.attestor)
+            && now(config)? < region_attestation.expires_at,
+        RaffleError::RegionAttestationRequired
+    );
+    require!(
+        region_attestation.allows(raffle.allowed_regions),
+        RaffleError::RegionNotAllowed
+    );
+
+    Ok(())
+}
+
+/// Ensures the buyer may purchase tickets of an age restricted raffle, by holding an
+/// unexpired attestation of being 18 or older, signed by the age attestor currently
+/// registered in the config. All failures report AgeVerificationRequired, so frontends
+/// can direct buyers to age verification.
+pub(crate) fn validate_age(
+    raffle: &Raffle,
+    age_attestation: Option<&AgeAttestation>,
+    config: &Config,
+    signer: &Signer,
+) -> Result<()> {
+    if !raffle.age_restricted {
+        return Ok(());
+    }
+
+    let age_attestation = age_attestation.ok_or(RaffleError::AgeVerificationRequired)?;
+    require!(
+        age_attestation.wallet == signer.key()
+            && config.age_attestor == Some(age_attestation.attestor)
+            && now(config)? < age_attestation.expires_at,
+        RaffleError::AgeVerificationRequired
+    );
+
+    Ok(())
+}
+
+/// Ensures the buyer presented the access code of a private raffle, by hashing it with
+/// the raffle's key as set_access_code does.
+fn validate_access_code(raffle: &Account<Raffle>, access_code: Option<&str>) -> Result<()> {
+    let Some(expected_hash) = raffle.access_code_hash else {
+        return Ok(());
+    };
+
+    let access_code = access_code.ok_or(RaffleError::AccessCodeRequired)?;
+    require!(
+        access_code_hash(&raffle.key(), access_code) == expected_hash,
+        RaffleError::InvalidAccessCode
+    );
+
+    Ok(())
+}
+
+/// Ensures the buyer may purchase tickets during the raffle's priority phase, by holding
+/// a profile of at least the raffle's priority tier. Anyone may buy once the phase ended.
+pub(crate) fn validate_priority_access(
+    raffle: &Raffle,
+    user_profile: Option<&UserProfile>,
+    signer: &Signer,
+    now: i64,
+) -> Result<()> {
+    if !raffle.in_priority_phase(now) {
+        return Ok(());
+    }
+
+    let user_profile = user_profile.ok_or(RaffleError::PriorityAccessRequired)?;
+    require!(
+        user_profile.wallet == signer.key() && user_profile.tier >= raffle.priority_min_tier,
+        RaffleError::PriorityAccessRequired
+    );
+
+    Ok(())
+}
+
+/// Records the buyer's ticket balance on the raffle's leaderboard, if the raffle splits its
+/// prize among its top holders. The leaderboard is required for such raffles, so no
+/// purchase can be missed.
+pub(crate) fn record_top_holder(
+    raffle: &Account<Raffle>,
+    leaderboard: Option<&mut Account<Leaderboard>>,
+    ticket_balance: &TicketBalance,
+) -> Result<()> {
+    let Some(top_holders) = raffle.top_holders else {
+        return Ok(());
+    };
+
+    let leaderboard = leaderboard.ok_or(RaffleError::InvalidLeaderboard)?;
+    require!(
+        leaderboard.raffle == raffle.key(),
+        RaffleError::InvalidLeaderboard
+    );
+    leaderboard.record(
+        ticket_balance.owner,
+        ticket_balance.ticket_count,
+        top_holders as usize,
+    );
+
+    Ok(())
+}
+
+/// Emits a LargePurchase event if a purchase of `ticket_count` tickets for
+/// `payment_amount` lamports exceeds the config's large purchase threshold. Purchases
+/// paid in an SPL token are not compared against the lamport threshold.
+pub(crate) fn emit_large_purchase(
+    config: &Config,
+    raffle: &mut Account<Raffle>,
+    buyer: Pubkey,
+    ticket_count: u64,
+    payment_amount: u64,
+) -> Result<()> {
+    let threshold = config.large_purchase_threshold;
+    if threshold == 0 || raffle.payment_mint.is_some() || payment_amount <= threshold {
+        return Ok(());
+    }
+
+    let event_seq = raffle.next_event_seq()?;
+    emit!(LargePurchase {
+        raffle: raffle.key(),
+        buyer,
+        ticket_count,
+        payment_amount,
+        threshold,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+/// Creates the PurchaseDedupe PDA recording a purchase's idempotency key
+///
+/// The record is created manually rather than through an `init` constraint so an
+/// existing record can be reported as a DuplicatePurchase.
+fn record_purchase_dedupe<'info>(
+    purchase_dedupe: &UncheckedAccount<'info>,
+    signer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    raffle: Pubkey,
+    entry: Pubkey,
+    idempotency_key: [u8; 16],
+) -> Result<()> {
+    let buyer = signer.key();
+    let (address, bump) = Pubkey::find_program_address(
+        &[
+            b"purchase_dedupe",
+            raffle.as_ref(),
+            buyer.as_ref(),
+            idempotency_key.as_ref(),
+        ],
+        &crate::ID,
+    );
+    require_keys_eq!(
+        purchase_dedupe.key(),
+        address,
+        RaffleError::InvalidPurchaseDedupe
+    );
+
+    // An initialized record means a purchase with this key already went through
+    require!(
+        purchase_dedupe.data_is_empty(),
+        RaffleError::DuplicatePurchase
+    );
+
+    create_account(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            CreateAccount {
+                from: signer.to_account_info(),
+                to: purchase_dedupe.to_account_info(),
+            },
+            &[&[
+                b"purchase_dedupe",
+                raffle.as_ref(),
+                buyer.as_ref(),
+                idempotency_key.as_ref(),
+                &[bump],
+            ]],
+        ),
+        rent_reserve(PURCHASE_DEDUPE_ACCOUNT_SIZE)?,
+        PURCHASE_DEDUPE_ACCOUNT_SIZE as u64,
+        &crate::ID,
+    )?;
+
+    let record = PurchaseDedupe {
+        raffle,
+        buyer,
+        entry,
+        bump,
+    };
+    record.try_serialize(&mut &mut purchase_dedupe.try_borrow_mut_data()?[..])?;
 
     Ok(())
 }
 
 /// Accounts required for the buy_tickets instruction
 #[derive(Accounts)]
-#[instruction(ticket_count: u64, entry_seed: [u8; 8])]
+#[instruction(ticket_count: u64, entry_seed: [u8; 8], expected_total: Option<u64>, memo: Option<String>)]
 pub struct BuyTickets<'info> {
     /// The raffle account that tickets are being purchased for
     /// Must be in Open state and not past end time
     #[account(
         mut,
         constraint = raffle.raffle_state == RaffleState::Open @ RaffleError::RaffleNotOpen,
-        constraint = Clock::get()?.unix_timestamp < raffle.end_time @ RaffleError::RaffleEnded,
+        constraint = now(&config)? < raffle.end_time @ RaffleError::RaffleEnded,
     )]
     pub raffle: Account<'info, Raffle>,
 
@@ -211,4 +901,97 @@ pub struct BuyTickets<'info> {
         bump = treasury.bump,
     )]
     pub treasury: Account<'info, Treasury>,
+
+    /// Optional rent pool that reimburses the entry rent when enabled.
+    /// Only a single RentPool can exist, at the PDA with seeds ["rent_pool"], so the
+    /// owner and discriminator checks performed by Anchor are sufficient.
+    #[account(mut)]
+    pub rent_pool: Option<Account<'info, RentPool>>,
+
+    /// Optional record of the purchase's idempotency key, required if a key is given.
+    /// PDA with seeds ["purchase_dedupe", raffle_key, signer_key, idempotency_key],
+    /// validated and created in the instruction.
+    /// CHECK: The address is checked against the PDA derived from the idempotency key
+    #[account(mut)]
+    pub purchase_dedupe: Option<UncheckedAccount<'info>>,
+
+    /// Optional leaderboard of the raffle's largest holders, required if the raffle splits
+    /// its prize among top holders. Validated against the raffle in the instruction.
+    #[account(mut)]
+    pub leaderboard: Option<Account<'info, Leaderboard>>,
+
+    /// Optional promotional campaign paying part of the ticket cost from its budget.
+    /// Campaigns only exist at PDAs with seeds ["campaign", campaign_id], so the owner
+    /// and discriminator checks performed by Anchor are sufficient.
+    #[account(mut)]
+    pub campaign: Option<Account<'info, Campaign>>,
+
+    /// Program config storing the region and age attestors and the large purchase
+    /// threshold
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ RaffleError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Optional attestation of the buyer's region, required if the raffle is region
+    /// restricted. Attestations only exist at PDAs with seeds ["region_attestation", wallet]
+    /// and are validated against the signer in the instruction.
+    pub region_attestation: Option<Account<'info, RegionAttestation>>,
+
+    /// Optional attestation that the buyer is 18 or older, required if the raffle is age
+    /// restricted. Attestations only exist at PDAs with seeds ["age_attestation", wallet]
+    /// and are validated against the signer in the instruction.
+    pub age_attestation: Option<Account<'info, AgeAttestation>>,
+
+    /// Optional loyalty profile of the buyer, required during the raffle's priority phase.
+    /// Profiles only exist at PDAs with seeds ["user_profile", wallet] and are validated
+    /// against the signer in the instruction.
+    pub user_profile: Option<Account<'info, UserProfile>>,
+
+    /// Optional token account the buyer pays from, required if the raffle is paid in an
+    /// SPL token. The token program ensures it holds the payment mint and that the
+    /// signer may spend from it.
+    #[account(mut)]
+    pub payer_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Optional associated token account of the treasury receiving the payment, required
+    /// if the raffle is paid in an SPL token. Validated against the payment mint in the
+    /// instruction.
+    #[account(mut)]
+    pub treasury_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Required if the raffle is paid in an SPL token
+    pub token_program: Option<Program<'info, Token>>,
+
+    /// Optional stats of the referrer, required if a referrer is given.
+    /// PDA with seeds ["referral_stats", raffle_key, referrer], validated against the
+    /// raffle and referrer in the instruction.
+    #[account(mut)]
+    pub referral_stats: Option<Account<'info, ReferralStats>>,
+
+    /// Optional receipt mint of the new entry, required if the raffle issues ticket
+    /// receipts. PDA with seeds ["ticket_receipt", entry_key], validated and created in
+    /// the instruction.
+    /// CHECK: The address is checked against the PDA derived from the entry
+    #[account(mut)]
+    pub receipt_mint: Option<UncheckedAccount<'info>>,
+
+    /// Optional associated token account of the buyer receiving the receipt, required if
+    /// the raffle issues ticket receipts
+    /// CHECK: Created by the associated token program, which only accepts the address
+    /// derived from the buyer and the receipt mint
+    #[account(mut)]
+    pub receipt_token_account: Option<UncheckedAccount<'info>>,
+
+    /// Required if the raffle issues ticket receipts
+    pub token_2022_program: Option<Program<'info, Token2022>>,
+
+    /// Required if the raffle issues ticket receipts
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+    /// Program notified if the purchase sells the raffle out, required then if the
+    /// config registers one
+    /// CHECK: Validated against the config's notifier program in the instruction
+    pub notifier_program: Option<UncheckedAccount<'info>>,
 }
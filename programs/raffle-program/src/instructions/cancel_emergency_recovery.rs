@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, Raffle, RecoveryRequest},
+};
+
+/// Event emitted when a scheduled emergency recovery is cancelled
+#[event]
+pub struct EmergencyRecoveryCancelled {
+    /// The pubkey of the raffle that is no longer recovered
+    pub raffle: Pubkey,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to cancel a scheduled emergency recovery, e.g. once the raffle is
+/// unstuck by other means
+///
+/// # Security Considerations
+/// - Only the upgrade authority can cancel a recovery
+///
+/// # Account Validations
+/// * Recovery Request - PDA with seeds ["recovery_request", raffle_key], closed to the
+///   upgrade authority
+/// * Upgrade Authority - Must match the upgrade authority stored in config
+pub fn cancel_emergency_recovery(ctx: Context<CancelEmergencyRecovery>) -> Result<()> {
+    let event_seq = ctx.accounts.raffle.next_event_seq()?;
+    emit!(EmergencyRecoveryCancelled {
+        raffle: ctx.accounts.raffle.key(),
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelEmergencyRecovery<'info> {
+    #[account(mut)]
+    pub raffle: Account<'info, Raffle>,
+
+    #[account(
+        mut,
+        close = upgrade_authority,
+        seeds = [
+            b"recovery_request",
+            raffle.key().as_ref(),
+        ],
+        bump = recovery_request.bump,
+    )]
+    pub recovery_request: Account<'info, RecoveryRequest>,
+
+    #[account(mut)]
+    pub upgrade_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = upgrade_authority @ RaffleError::NotProgramUpgradeAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, Raffle, RaffleState, Treasury, CLOSE_REASON_CANCELLED},
+    time::now,
+};
+
+/// Event emitted when the management authority cancels a raffle
+#[event]
+pub struct RaffleCancelled {
+    /// The pubkey of the cancelled raffle
+    pub raffle: Pubkey,
+    /// The timestamp when the raffle was cancelled
+    pub cancelled_at: i64,
+    /// The final number of tickets sold
+    pub final_ticket_count: u64,
+    /// Funds held by the treasury for refunds
+    pub refundable_amount: u64,
+    /// Why the raffle left the Open state, one of the `CLOSE_REASON_*` values
+    pub close_reason: u8,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to abort a raffle that was created with wrong parameters or whose prize
+/// fell through, refunding every ticket holder in full
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Only the management authority can cancel a raffle
+/// 2. Ensures the raffle is in Open or AwaitingPrize state and no randomness was
+///    requested for it, so no draw has started
+/// 3. Ensures nothing was withdrawn from the treasury, so every ticket holder can be
+///    refunded in full
+///
+/// # Account Validations
+/// * Raffle - Must be in Open or AwaitingPrize state
+/// * Treasury - PDA with seeds ["treasury", raffle_key], nothing withdrawn
+/// * Config - PDA storing the management authority
+///
+/// # Implementation Notes
+/// - Changes raffle state to Cancelled, after which ticket holders reclaim the full
+///   ticket cost through reclaim_expired_tickets, without a refund processing fee
+/// - Withdrawals from the treasury are rejected once the raffle is cancelled, and stray
+///   funds can only be swept once every ticket holder was refunded
+/// - An escrowed prize is returned with release_prize
+pub fn cancel_raffle(ctx: Context<CancelRaffle>) -> Result<()> {
+    require!(
+        matches!(
+            ctx.accounts.raffle.raffle_state,
+            RaffleState::Open | RaffleState::AwaitingPrize
+        ),
+        RaffleError::RaffleNotOpen
+    );
+    require!(
+        ctx.accounts.raffle.randomness_account.is_none(),
+        RaffleError::RandomnessAlreadyRequested
+    );
+    require!(
+        ctx.accounts.treasury.total_withdrawn == 0,
+        RaffleError::TreasuryAlreadyWithdrawn
+    );
+
+    let cancelled_at = now(&ctx.accounts.config)?;
+    ctx.accounts.raffle.raffle_state = RaffleState::Cancelled;
+    ctx.accounts.raffle.close_reason = CLOSE_REASON_CANCELLED;
+    ctx.accounts.raffle.refund_deadline = ctx.accounts.config.refund_deadline(cancelled_at)?;
+    ctx.accounts
+        .config
+        .record_raffle_closed(&mut ctx.accounts.raffle);
+
+    let event_seq = ctx.accounts.raffle.next_event_seq()?;
+    emit!(RaffleCancelled {
+        raffle: ctx.accounts.raffle.key(),
+        cancelled_at,
+        final_ticket_count: ctx.accounts.raffle.current_tickets,
+        refundable_amount: ctx.accounts.treasury.outstanding()?,
+        close_reason: CLOSE_REASON_CANCELLED,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelRaffle<'info> {
+    #[account(mut)]
+    pub raffle: Account<'info, Raffle>,
+
+    #[account(
+        seeds = [
+            b"treasury",
+            raffle.key().as_ref(),
+        ],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
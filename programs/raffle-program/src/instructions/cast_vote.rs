@@ -0,0 +1,155 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::get_associated_token_address,
+    token::{self, Token, TokenAccount, Transfer},
+};
+
+use crate::{
+    error::RaffleError,
+    math::checked_add,
+    state::{Ballot, Config, Raffle, Vote, VOTE_ACCOUNT_SIZE},
+    time::now,
+};
+
+/// Event emitted when a vote is cast
+#[event]
+pub struct VoteCast {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The wallet that voted
+    pub voter: Pubkey,
+    /// The shortlisted entry voted for
+    pub entry: Pubkey,
+    /// Tokens locked as the weight of the vote
+    pub amount: u64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to vote for a shortlisted entry of a raffle drawn by votes, weighted by
+/// the vote mint tokens the voter locks in the ballot
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `choice` - Index of the shortlisted entry voted for
+/// * `amount` - Tokens of the vote mint to lock, which is the weight of the vote
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Ensures voting has not ended
+/// 2. Locks the tokens in the ballot until voting ends, so the same tokens can't be
+///    voted again from another wallet
+/// 3. Allows one vote per wallet, enforced by the vote PDA
+///
+/// # Account Validations
+/// * Ballot - PDA with seeds ["ballot", raffle_key]
+/// * Vote - New PDA with seeds ["vote", ballot_key, voter]
+/// * VoterTokenAccount - Token account of the vote mint; the token program ensures the
+///   voter may spend from it
+/// * BallotTokenAccount - Associated token account of the ballot for the vote mint
+///
+/// # Implementation Notes
+/// - The tokens are returned by reclaim_vote once voting ended
+pub fn cast_vote(ctx: Context<CastVote>, choice: u8, amount: u64) -> Result<()> {
+    let ballot = &mut ctx.accounts.ballot;
+    require!(
+        now(&ctx.accounts.config)? < ballot.voting_ends_at,
+        RaffleError::VotingClosed
+    );
+    require!(
+        (choice as usize) < ballot.shortlist.len(),
+        RaffleError::InvalidVoteChoice
+    );
+    require!(amount > 0, RaffleError::InvalidVoteChoice);
+    require_keys_eq!(
+        ctx.accounts.ballot_token_account.key(),
+        get_associated_token_address(&ballot.key(), &ballot.vote_mint),
+        RaffleError::InvalidVoteMint
+    );
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.voter_token_account.to_account_info(),
+                to: ctx.accounts.ballot_token_account.to_account_info(),
+                authority: ctx.accounts.voter.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+    ballot.tallies[choice as usize] = checked_add(ballot.tallies[choice as usize], amount)?;
+
+    let vote = &mut ctx.accounts.vote;
+    vote.ballot = ballot.key();
+    vote.voter = ctx.accounts.voter.key();
+    vote.choice = choice;
+    vote.amount = amount;
+    vote.bump = ctx.bumps.vote;
+
+    let raffle = &mut ctx.accounts.raffle;
+    let event_seq = raffle.next_event_seq()?;
+    emit!(VoteCast {
+        raffle: raffle.key(),
+        voter: ctx.accounts.voter.key(),
+        entry: ballot.shortlist[choice as usize],
+        amount,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CastVote<'info> {
+    #[account(mut)]
+    pub raffle: Account<'info, Raffle>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"ballot",
+            raffle.key().as_ref(),
+        ],
+        bump = ballot.bump,
+    )]
+    pub ballot: Account<'info, Ballot>,
+
+    #[account(
+        init,
+        payer = voter,
+        space = VOTE_ACCOUNT_SIZE,
+        seeds = [
+            b"vote",
+            ballot.key().as_ref(),
+            voter.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub vote: Account<'info, Vote>,
+
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    /// Token account the locked tokens are transferred from
+    #[account(
+        mut,
+        constraint = voter_token_account.mint == ballot.vote_mint @ RaffleError::InvalidVoteMint,
+    )]
+    pub voter_token_account: Account<'info, TokenAccount>,
+
+    /// Associated token account of the ballot for the vote mint
+    #[account(mut)]
+    pub ballot_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Program config, storing the clock offset of devnet builds
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
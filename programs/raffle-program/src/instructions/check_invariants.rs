@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, Raffle, Treasury},
+    transfer::{excess_lamports, rent_reserve_of},
+};
+use anchor_spl::{associated_token::get_associated_token_address, token::TokenAccount};
+
+/// Event emitted when a treasury's balance doesn't cover its tracked liabilities
+#[event]
+pub struct TreasuryShortfallDetected {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// Funds the treasury tracks as owed to ticket holders, referrers and the payout
+    /// authority
+    pub liabilities: u64,
+    /// Funds the treasury holds above its rent reserve
+    pub available: u64,
+    /// Liabilities covered by the configured alarm coverage that the treasury lacks
+    pub shortfall: u64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to check that a raffle's treasury holds the funds it tracks as owed,
+/// emitting an alert event for monitoring if it doesn't
+///
+/// # Arguments
+/// * `ctx` - The context object containing:
+///   - `raffle`: The raffle to check
+///   - `treasury`: PDA with seeds ["treasury", raffle_key]
+///   - `config`: The config holding the alarm thresholds
+///   - `treasury_token_account`: The treasury's associated token account, required if
+///     the raffle is paid in an SPL token
+///
+/// # Security Considerations
+/// - Anyone can run the check, so monitoring doesn't depend on the operator
+/// - The check never moves funds or fails on a shortfall, it only reports it
+///
+/// # Implementation Notes
+/// - The liabilities are the treasury's outstanding funds. The lamports above the rent
+///   reserve, or the tokens held by the treasury's token account, must cover
+///   `alarm_coverage_bps` of them
+/// - Shortfalls below `alarm_min_shortfall` are tolerated as rounding dust
+///
+/// # Errors
+/// - `Overflow` if the treasury's records are inconsistent
+/// - `InvalidPaymentMint` if the raffle is paid in an SPL token and the treasury's token
+///   account is missing or doesn't match
+pub fn check_invariants(ctx: Context<CheckInvariants>) -> Result<()> {
+    let treasury = &ctx.accounts.treasury;
+    let available = match ctx.accounts.raffle.payment_mint {
+        Some(payment_mint) => {
+            let vault = ctx
+                .accounts
+                .treasury_token_account
+                .as_ref()
+                .ok_or(RaffleError::InvalidPaymentMint)?;
+            require_keys_eq!(
+                vault.key(),
+                get_associated_token_address(&treasury.key(), &payment_mint),
+                RaffleError::InvalidPaymentMint
+            );
+            vault.amount
+        }
+        None => {
+            let treasury_info = treasury.to_account_info();
+            excess_lamports(&treasury_info, rent_reserve_of(&treasury_info)?)
+        }
+    };
+    let liabilities = treasury.outstanding()?;
+
+    if let Some(shortfall) = ctx
+        .accounts
+        .config
+        .alarm_shortfall(liabilities, available)?
+    {
+        let event_seq = ctx.accounts.raffle.next_event_seq()?;
+        emit!(TreasuryShortfallDetected {
+            raffle: ctx.accounts.raffle.key(),
+            liabilities,
+            available,
+            shortfall,
+            event_seq,
+        });
+    }
+
+    Ok(())
+}
+
+/// Accounts required for the check_invariants instruction
+#[derive(Accounts)]
+pub struct CheckInvariants<'info> {
+    /// The raffle to check
+    #[account(mut)]
+    pub raffle: Account<'info, Raffle>,
+
+    /// The raffle's treasury
+    #[account(
+        seeds = [
+            b"treasury",
+            raffle.key().as_ref(),
+        ],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The treasury's associated token account, required if the raffle is paid in an SPL
+    /// token
+    pub treasury_token_account: Option<Account<'info, TokenAccount>>,
+}
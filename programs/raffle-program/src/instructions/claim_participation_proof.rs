@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{
+        Config, ParticipationProof, Raffle, RaffleState, TicketBalance,
+        PARTICIPATION_PROOF_ACCOUNT_SIZE,
+    },
+    time::now,
+};
+
+/// Event emitted when a participant claims a participation proof
+#[event]
+pub struct ParticipationProofClaimed {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The participant who claimed the proof
+    pub owner: Pubkey,
+    /// Number of tickets the participant held
+    pub ticket_count: u64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Records a proof that the signer held tickets in a drawn raffle, so partners can
+/// airdrop to participants holding at least a number of tickets without trusting our
+/// indexer.
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Validates the raffle has been drawn, so ticket balances are final
+/// 2. Reads the ticket count from the signer's ticket balance, so it can't be inflated
+/// 3. Ensures each wallet claims at most one proof per raffle, via its proof PDA
+///
+/// # Account Validations
+/// * Raffle - Must be in Drawn or Claimed state
+/// * TicketBalance - PDA with seeds ["ticket_balance", raffle_key, owner] holding tickets
+/// * ParticipationProof - New PDA with seeds ["participation_proof", raffle_key, owner]
+///
+/// # Implementation Notes
+/// - Partners filter proofs by `ticket_count` to airdrop to wallets that held at least
+///   N tickets
+/// - Proofs outlive the raffle's ticket balances and entries, so they can be claimed
+///   before the raffle is closed and checked at any time after
+pub fn claim_participation_proof(ctx: Context<ClaimParticipationProof>) -> Result<()> {
+    let ticket_count = ctx.accounts.ticket_balance.ticket_count;
+    require!(ticket_count > 0, RaffleError::NoTicketsHeld);
+
+    let participation_proof = &mut ctx.accounts.participation_proof;
+    participation_proof.raffle = ctx.accounts.raffle.key();
+    participation_proof.owner = ctx.accounts.owner.key();
+    participation_proof.ticket_count = ticket_count;
+    participation_proof.claimed_at = now(&ctx.accounts.config)?;
+    participation_proof.bump = ctx.bumps.participation_proof;
+
+    let event_seq = ctx.accounts.raffle.next_event_seq()?;
+    emit!(ParticipationProofClaimed {
+        raffle: ctx.accounts.raffle.key(),
+        owner: ctx.accounts.owner.key(),
+        ticket_count,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimParticipationProof<'info> {
+    /// The drawn raffle
+    #[account(
+        mut,
+        constraint = matches!(
+            raffle.raffle_state,
+            RaffleState::Drawn | RaffleState::Claimed
+        ) @ RaffleError::RaffleNotDrawn,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    /// The participant's ticket balance
+    /// PDA with seeds ["ticket_balance", raffle_key, owner]
+    #[account(
+        seeds = [
+            b"ticket_balance",
+            raffle.key().as_ref(),
+            owner.key().as_ref(),
+        ],
+        bump = ticket_balance.bump,
+    )]
+    pub ticket_balance: Account<'info, TicketBalance>,
+
+    /// The proof of participation
+    /// PDA with seeds ["participation_proof", raffle_key, owner]
+    #[account(
+        init,
+        payer = owner,
+        space = PARTICIPATION_PROOF_ACCOUNT_SIZE,
+        seeds = [
+            b"participation_proof",
+            raffle.key().as_ref(),
+            owner.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub participation_proof: Account<'info, ParticipationProof>,
+
+    /// The participant, who pays for the proof
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Program config, storing the clock offset of devnet builds
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
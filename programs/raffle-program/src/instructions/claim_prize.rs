@@ -0,0 +1,264 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::get_associated_token_address,
+    token::{self, CloseAccount, Token, TokenAccount, Transfer},
+    token_interface::TokenAccount as TokenInterfaceAccount,
+};
+
+use crate::{
+    error::RaffleError,
+    instructions::submit_winner_data::verify_winner,
+    math::checked_sub,
+    receipt::verify_receipt_winner,
+    state::{Config, Entry, PrizeVault, Raffle, RaffleState},
+    transfer::move_lamports,
+};
+
+/// Event emitted when the winner claims the escrowed prize
+#[event]
+pub struct PrizeClaimed {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The winner the prize was paid to
+    pub winner: Pubkey,
+    /// The prize value paid in lamports, or in base units of the prize mint
+    pub amount: u64,
+    /// Mint of the SPL token prize, or None for a SOL prize
+    pub prize_mint: Option<Pubkey>,
+    /// Index of the prize claimed, in prize order
+    pub winner_index: u8,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Pays `share` of the escrowed prize out to `recipient`, or the whole remaining prize if
+/// `share` is None, and returns the amount paid. SOL prizes are moved out of the vault
+/// directly, token prizes are transferred from the vault's associated token account to
+/// `recipient_token_account`, which must be owned by the recipient. Once the whole
+/// remaining prize is paid, the emptied token account is closed to `rent_recipient`, the
+/// vault itself is closed by the calling instruction.
+pub(crate) fn pay_out_prize<'info>(
+    prize_vault: &mut Account<'info, PrizeVault>,
+    share: Option<u64>,
+    recipient: &AccountInfo<'info>,
+    recipient_token_account: Option<&Account<'info, TokenAccount>>,
+    vault_token_account: Option<&Account<'info, TokenAccount>>,
+    token_program: Option<&Program<'info, Token>>,
+    rent_recipient: &AccountInfo<'info>,
+) -> Result<u64> {
+    let Some(prize_mint) = prize_vault.prize_mint else {
+        let amount = share.unwrap_or(prize_vault.amount);
+        move_lamports(&prize_vault.to_account_info(), recipient, amount)?;
+        prize_vault.amount = checked_sub(prize_vault.amount, amount)?;
+        return Ok(amount);
+    };
+
+    let (Some(recipient_token_account), Some(vault_token_account), Some(token_program)) =
+        (recipient_token_account, vault_token_account, token_program)
+    else {
+        return err!(RaffleError::InvalidPrizeMint);
+    };
+    require_keys_eq!(
+        vault_token_account.key(),
+        get_associated_token_address(&prize_vault.key(), &prize_mint),
+        RaffleError::InvalidPrizeMint
+    );
+    require_keys_eq!(
+        recipient_token_account.owner,
+        recipient.key(),
+        RaffleError::InvalidPrizeMint
+    );
+
+    let raffle = prize_vault.raffle;
+    let signer_seeds: &[&[&[u8]]] = &[&[b"prize_vault", raffle.as_ref(), &[prize_vault.bump]]];
+
+    // Pay out the whole balance with the last share, so tokens sent to the vault after
+    // the deposit aren't stuck in it
+    let amount = share.unwrap_or(vault_token_account.amount);
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: vault_token_account.to_account_info(),
+                to: recipient_token_account.to_account_info(),
+                authority: prize_vault.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+    prize_vault.amount = prize_vault.amount.saturating_sub(amount);
+    if share.is_some() {
+        return Ok(amount);
+    }
+
+    token::close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: vault_token_account.to_account_info(),
+            destination: rent_recipient.clone(),
+            authority: prize_vault.to_account_info(),
+        },
+        signer_seeds,
+    ))?;
+
+    Ok(amount)
+}
+
+/// Instruction for the winner of a raffle to claim the escrowed prize
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `winner_secret` - Secret the winner committed to with commit_winner_secret,
+///   required for anonymized raffles to reveal the preimage of the stored winner hash
+/// * `winner_index` - Index of the prize the signer won, in prize order
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Validates the raffle is in Drawn or Claimed state, so the prize can be claimed
+///    before or after the winner submits their data
+/// 2. Ensures signer is the designated winner of the prize, or for anonymized raffles
+///    that the signer and revealed secret hash to the stored winner hash. If the raffle
+///    issues ticket receipts, instead ensures the signer holds the receipt of the
+///    winning entry
+/// 3. Ensures the prize was not claimed yet
+/// 4. Token prizes can only be paid to a token account owned by the winner
+///
+/// # Account Validations
+/// * Raffle - Must be in Drawn or Claimed state
+/// * PrizeVault - PDA with seeds ["prize_vault", raffle_key], closed to the management
+///   authority, who paid its rent, once all prizes are claimed
+/// * Config - PDA storing the management authority
+/// * WinnerTokenAccount - Token account of the winner receiving a token prize
+/// * VaultTokenAccount - Associated token account of the prize vault holding a token
+///   prize, closed to the management authority
+/// * WinningEntry - Optional, the entry holding the prize's winning ticket, required if
+///   the raffle issues ticket receipts
+/// * ReceiptTokenAccount - Optional, the signer's token account holding the winning
+///   entry's receipt, required if the raffle issues ticket receipts
+///
+/// # Implementation Notes
+/// - Does not change the raffle state, as the winner may still have to submit their data
+/// - Raffles with multiple winners split the escrowed prize evenly, each claim paying
+///   the remaining prize divided by the number of unclaimed prizes, the last claim paying
+///   whatever remains
+/// - Publishes the winner's address on anonymized raffles, as the claim reveals it anyway
+/// - The prize of a raffle issuing ticket receipts is paid to the current holder of the
+///   winning receipt, who is recorded as the winner, even if it was sold after the draw
+pub fn claim_prize(
+    ctx: Context<ClaimPrize>,
+    winner_secret: Option<[u8; 32]>,
+    winner_index: u8,
+) -> Result<()> {
+    let raffle = &mut ctx.accounts.raffle;
+    if raffle.ticket_receipts {
+        verify_receipt_winner(
+            raffle,
+            &ctx.accounts.winner.key(),
+            ctx.accounts.winning_entry.as_ref(),
+            ctx.accounts.receipt_token_account.as_deref(),
+            winner_index,
+        )?;
+    } else {
+        verify_winner(raffle, &ctx.accounts.winner.key(), winner_secret, winner_index)?;
+    }
+
+    let prize_bit = 1 << winner_index;
+    require!(
+        raffle.prizes_claimed & prize_bit == 0,
+        RaffleError::PrizeAlreadyClaimed
+    );
+    let unclaimed = raffle.winner_count as u32 - raffle.prizes_claimed.count_ones();
+    raffle.prizes_claimed |= prize_bit;
+
+    let prize_vault = &mut ctx.accounts.prize_vault;
+    let share = if unclaimed > 1 {
+        Some(prize_vault.amount / unclaimed as u64)
+    } else {
+        None
+    };
+    let management_authority = ctx.accounts.management_authority.to_account_info();
+    let amount = pay_out_prize(
+        prize_vault,
+        share,
+        &ctx.accounts.winner.to_account_info(),
+        ctx.accounts.winner_token_account.as_ref(),
+        ctx.accounts.vault_token_account.as_ref(),
+        ctx.accounts.token_program.as_ref(),
+        &management_authority,
+    )?;
+    if share.is_none() {
+        prize_vault.close(management_authority)?;
+    }
+
+    let event_seq = ctx.accounts.raffle.next_event_seq()?;
+    emit!(PrizeClaimed {
+        raffle: ctx.accounts.raffle.key(),
+        winner: ctx.accounts.winner.key(),
+        amount,
+        prize_mint: ctx.accounts.prize_vault.prize_mint,
+        winner_index,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimPrize<'info> {
+    /// The raffle account that must be in Drawn or Claimed state
+    /// Must have the signer as the designated winner, verified in the instruction
+    #[account(
+        mut,
+        constraint = matches!(
+            raffle.raffle_state,
+            RaffleState::Drawn | RaffleState::Claimed
+        ) @ RaffleError::RaffleNotDrawn,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    /// Closed to the management authority in the instruction once all prizes are claimed
+    #[account(
+        mut,
+        seeds = [
+            b"prize_vault",
+            raffle.key().as_ref(),
+        ],
+        bump = prize_vault.bump,
+    )]
+    pub prize_vault: Account<'info, PrizeVault>,
+
+    /// The winner claiming the prize
+    #[account(mut)]
+    pub winner: Signer<'info>,
+
+    /// The management authority, refunded the rent of the escrow accounts
+    #[account(mut)]
+    pub management_authority: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Token account of the winner, required for token prizes
+    #[account(mut)]
+    pub winner_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Associated token account of the prize vault, required for token prizes
+    #[account(mut)]
+    pub vault_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Required for token prizes
+    pub token_program: Option<Program<'info, Token>>,
+
+    /// The entry holding the prize's winning ticket, required if the raffle issues ticket
+    /// receipts. Validated against the raffle in the instruction.
+    pub winning_entry: Option<Account<'info, Entry>>,
+
+    /// Token account of the winner holding the winning entry's receipt, required if the
+    /// raffle issues ticket receipts. Validated against the entry in the instruction.
+    pub receipt_token_account: Option<InterfaceAccount<'info, TokenInterfaceAccount>>,
+}
@@ -0,0 +1,314 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{create_account, CreateAccount},
+};
+
+use crate::{
+    error::RaffleError,
+    math::{bps_of, checked_sub, ticket_cost},
+    merkle::{snapshot_leaf, verify_proof, SNAPSHOT_TREE_DEPTH},
+    state::{Config, Raffle, RaffleState, SnapshotClaim, Treasury, SNAPSHOT_CLAIM_ACCOUNT_SIZE},
+    time::now,
+    transfer::rent_reserve,
+    treasury::{refund_token_accounts, LamportVault, TokenVault, TreasuryVault},
+};
+use anchor_spl::token::{Token, TokenAccount};
+
+/// Event emitted when a snapshotted ticket holder claims their refund with a Merkle proof
+#[event]
+pub struct RefundClaimedWithProof {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The ticket holder
+    pub owner: Pubkey,
+    /// Number of tickets refunded, as recorded in the snapshot
+    pub ticket_count: u64,
+    /// Amount refunded, in lamports or base units of the payment mint
+    pub refund_amount: u64,
+    /// Refund processing fee retained by the treasury
+    pub fee_amount: u64,
+    /// Share of the crank reward deducted from the refund
+    pub crank_share: u64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to refund the tickets of an expired or cancelled raffle by proving the
+/// holding against the raffle's snapshot root, so holders are refunded even if their
+/// ticket balance is gone
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `ticket_count` - Number of tickets the signer held, as recorded in the snapshot
+/// * `proof` - Sibling hashes from the signer's snapshot leaf up to the root
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Validates the raffle is in Expired or Cancelled state
+/// 2. Ensures the raffle's refund deadline has not passed, if it has one
+/// 3. Verifies the (signer, ticket_count) leaf against `Raffle::snapshot_root`, so only
+///    holdings recorded by snapshot_raffle are refunded
+/// 4. Ensures each holding is settled at most once, via its snapshot claim PDA, which
+///    reclaim_expired_tickets and rollover_tickets also create on snapshotted raffles
+/// 5. Rejects raffles that granted pricing tier discounts or campaign subsidies, since
+///    the snapshot doesn't record the price each holder paid
+/// 6. Ensures the refund does not exceed the funds collected by the treasury
+///
+/// # Account Validations
+/// * Raffle - Must be in Expired or Cancelled state with a snapshot root
+/// * SnapshotClaim - The uninitialized PDA with seeds ["snapshot_claim", raffle_key,
+///   signer], created by the instruction
+/// * Treasury - PDA with seeds ["treasury", raffle_key]
+/// * RecipientTokenAccount - Optional, token account of the signer receiving the refund,
+///   required if the raffle is paid in an SPL token
+/// * TreasuryTokenAccount - Optional, required if the raffle is paid in an SPL token
+/// * TokenProgram - Optional, required if the raffle is paid in an SPL token
+///
+/// # Implementation Notes
+/// - The refund is computed as in reclaim_expired_tickets, less the refund processing
+///   fee of expired raffles and the tickets' share of the crank reward
+/// - The signer's ticket balance is neither read nor required, so it may have been
+///   closed. If it still exists, reclaim_expired_tickets fails for it afterwards
+/// - The signer pays the rent of the snapshot claim, the only state kept per claim
+/// - Emits RefundClaimedWithProof with the refund and the deductions
+pub fn claim_refund_with_proof(
+    ctx: Context<ClaimRefundWithProof>,
+    ticket_count: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let cancelled = ctx.accounts.raffle.raffle_state == RaffleState::Cancelled;
+    require!(
+        ctx.accounts.raffle.raffle_state == RaffleState::Expired || cancelled,
+        RaffleError::RaffleNotExpired
+    );
+    let now = now(&ctx.accounts.config)?;
+    require!(
+        !ctx.accounts.raffle.refunds_lapsed(now),
+        RaffleError::RefundWindowClosed
+    );
+    let snapshot_root = ctx
+        .accounts
+        .raffle
+        .snapshot_root
+        .ok_or(RaffleError::SnapshotNotRecorded)?;
+    require!(ticket_count > 0, RaffleError::NoTicketsOwned);
+    require!(
+        proof.len() == SNAPSHOT_TREE_DEPTH
+            && verify_proof(
+                snapshot_leaf(&ctx.accounts.signer.key(), ticket_count),
+                &proof,
+                &snapshot_root,
+            ),
+        RaffleError::InvalidSnapshotProof
+    );
+    require!(
+        ctx.accounts.raffle.tier_discounts == 0,
+        RaffleError::TierDiscountNotProvable
+    );
+    require!(
+        ctx.accounts.raffle.campaign_subsidies == 0,
+        RaffleError::CampaignSubsidyNotProvable
+    );
+
+    record_snapshot_claim(
+        &ctx.accounts.snapshot_claim,
+        &ctx.accounts.signer,
+        &ctx.accounts.system_program,
+        ctx.accounts.raffle.key(),
+        ticket_count,
+        now,
+    )?;
+
+    let ticket_total = ticket_cost(ticket_count, ctx.accounts.raffle.ticket_price)?;
+    let fee_amount = if cancelled {
+        0
+    } else {
+        bps_of(ticket_total, ctx.accounts.raffle.refund_fee_bps)?
+    };
+    let crank_share = if ctx.accounts.treasury.total_crank_rewards > 0 {
+        ctx.accounts.raffle.crank_share(ticket_count)?
+    } else {
+        0
+    };
+    let refund_amount = checked_sub(checked_sub(ticket_total, fee_amount)?, crank_share)?;
+    ctx.accounts.treasury.record_refund_fee(fee_amount)?;
+
+    // Refund the tickets, failing if the treasury would pay out more than it collected
+    if ctx.accounts.raffle.payment_mint.is_some() {
+        let (vault, token_program) = refund_token_accounts(
+            &ctx.accounts.raffle,
+            &ctx.accounts.treasury,
+            ctx.accounts.treasury_token_account.as_mut(),
+            ctx.accounts.token_program.as_ref(),
+        )?;
+        let recipient_token_account = ctx
+            .accounts
+            .recipient_token_account
+            .as_ref()
+            .ok_or(RaffleError::InvalidPaymentMint)?;
+        require!(
+            recipient_token_account.owner == ctx.accounts.signer.key(),
+            RaffleError::OwnerMismatch
+        );
+        TokenVault::new(&mut ctx.accounts.treasury, vault, token_program)
+            .with_refund_mint(&ctx.accounts.raffle)
+            .refund(&recipient_token_account.to_account_info(), refund_amount)?;
+    } else {
+        LamportVault::new(&mut ctx.accounts.treasury, &ctx.accounts.system_program)
+            .refund(&ctx.accounts.signer.to_account_info(), refund_amount)?;
+    }
+
+    let raffle = &mut ctx.accounts.raffle;
+    let event_seq = raffle.next_event_seq()?;
+    emit!(RefundClaimedWithProof {
+        raffle: raffle.key(),
+        owner: ctx.accounts.signer.key(),
+        ticket_count,
+        refund_amount,
+        fee_amount,
+        crank_share,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+/// Creates the SnapshotClaim PDA settling the signer's holding in a snapshotted raffle
+///
+/// The record is created manually rather than through an `init` constraint so an
+/// existing record can be reported as SnapshotHoldingClaimed, and so instructions
+/// settling holdings of raffles without a snapshot don't need it.
+pub(crate) fn record_snapshot_claim<'info>(
+    snapshot_claim: &UncheckedAccount<'info>,
+    signer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    raffle: Pubkey,
+    ticket_count: u64,
+    now: i64,
+) -> Result<()> {
+    let owner = signer.key();
+    let (address, bump) = Pubkey::find_program_address(
+        &[b"snapshot_claim", raffle.as_ref(), owner.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(
+        snapshot_claim.key(),
+        address,
+        RaffleError::InvalidSnapshotClaim
+    );
+
+    // An initialized record means the holding was already refunded or rolled over
+    require!(
+        snapshot_claim.data_is_empty(),
+        RaffleError::SnapshotHoldingClaimed
+    );
+
+    create_account(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            CreateAccount {
+                from: signer.to_account_info(),
+                to: snapshot_claim.to_account_info(),
+            },
+            &[&[b"snapshot_claim", raffle.as_ref(), owner.as_ref(), &[bump]]],
+        ),
+        rent_reserve(SNAPSHOT_CLAIM_ACCOUNT_SIZE)?,
+        SNAPSHOT_CLAIM_ACCOUNT_SIZE as u64,
+        &crate::ID,
+    )?;
+
+    let record = SnapshotClaim {
+        raffle,
+        owner,
+        ticket_count,
+        claimed_at: now,
+        bump,
+    };
+    record.try_serialize(&mut &mut snapshot_claim.try_borrow_mut_data()?[..])?;
+
+    Ok(())
+}
+
+/// Settles the signer's holding in the snapshot of `raffle`, if it has one, before its
+/// ticket balance is refunded or rolled over
+pub(crate) fn settle_snapshot_holding<'info>(
+    raffle: &Account<'info, Raffle>,
+    snapshot_claim: Option<&UncheckedAccount<'info>>,
+    signer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    ticket_count: u64,
+    now: i64,
+) -> Result<()> {
+    if raffle.snapshot_root.is_none() {
+        return Ok(());
+    }
+    let snapshot_claim = snapshot_claim.ok_or(RaffleError::InvalidSnapshotClaim)?;
+    record_snapshot_claim(
+        snapshot_claim,
+        signer,
+        system_program,
+        raffle.key(),
+        ticket_count,
+        now,
+    )
+}
+
+#[derive(Accounts)]
+pub struct ClaimRefundWithProof<'info> {
+    /// The snapshotted ticket holder, who pays for the snapshot claim
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// The raffle that must be in Expired or Cancelled state, mutable to advance its
+    /// event sequence
+    #[account(mut)]
+    pub raffle: Account<'info, Raffle>,
+
+    /// Record settling the signer's holding
+    /// PDA with seeds ["snapshot_claim", raffle_key, signer], created in the instruction
+    /// CHECK: The address is checked against the PDA and the account must be empty
+    #[account(
+        mut,
+        seeds = [
+            b"snapshot_claim",
+            raffle.key().as_ref(),
+            signer.key().as_ref()
+        ],
+        bump,
+    )]
+    pub snapshot_claim: UncheckedAccount<'info>,
+
+    /// Treasury PDA for this raffle that holds the funds
+    #[account(
+        mut,
+        seeds = [
+            b"treasury",
+            raffle.key().as_ref(),
+        ],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Optional token account of the signer receiving the refund, required if the raffle
+    /// is paid in an SPL token
+    #[account(mut)]
+    pub recipient_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Optional associated token account of the treasury holding the payments, or the
+    /// refund mint if the raffle has one, required if the raffle is paid in an SPL token.
+    /// Validated against the mint in the instruction.
+    #[account(mut)]
+    pub treasury_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Required if the raffle is paid in an SPL token
+    pub token_program: Option<Program<'info, Token>>,
+
+    /// Program config, storing the clock offset of devnet builds
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
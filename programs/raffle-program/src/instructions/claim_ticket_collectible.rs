@@ -0,0 +1,201 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    bubblegum::{
+        mint_compressed_nft, ticket_metadata, MintAccounts, BUBBLEGUM_PROGRAM_ID,
+        MAX_COLLECTIBLE_URI_LENGTH, SPL_ACCOUNT_COMPRESSION_PROGRAM_ID, SPL_NOOP_PROGRAM_ID,
+    },
+    error::RaffleError,
+    math::checked_add,
+    state::{Config, Entry, Raffle, RaffleState, TicketCollectible, TICKET_COLLECTIBLE_ACCOUNT_SIZE},
+    time::now,
+};
+
+/// Event emitted when a ticket number is claimed as a collectible
+#[event]
+pub struct TicketCollectibleClaimed {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The entry owner who claimed the ticket number
+    pub owner: Pubkey,
+    /// The claimed ticket number
+    pub ticket_index: u64,
+    /// Maximum ticket supply of the raffle, i.e. the size of the collection
+    pub max_tickets: u64,
+    /// The Bubblegum tree the collectible was minted into
+    pub merkle_tree: Pubkey,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Claims a ticket number of a drawn collectible raffle and mints it as a numbered
+/// compressed NFT ("ticket #777") to the entry owner.
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `entry_seed` - Seed of the owner's entry holding the ticket
+/// * `ticket_index` - The ticket number to claim
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Validates the raffle was created in collectible mode
+/// 2. Validates the raffle has been drawn, so the ticket numbers are final
+/// 3. Verifies the signer owns the entry and the entry contains the ticket
+/// 4. Ensures each ticket number is claimed at most once, via its claim PDA
+/// 5. Only mints into the config's collectible tree, through the Bubblegum program
+///
+/// # Account Validations
+/// * Raffle - Must be collectible and in Drawn or Claimed state
+/// * Entry - PDA with seeds ["entry", raffle_key, entry_seed] owned by the signer
+/// * Ticket Collectible - New PDA with seeds ["ticket_collectible", raffle_key, ticket_index]
+/// * Merkle Tree - The config's collectible tree, delegated to the config PDA
+/// * Tree Authority, Log Wrapper, Compression Program - Validated by Bubblegum
+///
+/// # Implementation Notes
+/// - The NFT is minted with Bubblegum's mint_v1, signed by the config PDA as tree
+///   delegate, see `bubblegum`. Its metadata points at the raffle's metadata URI
+/// - Entries must be claimed before they are closed by the `close_entries` crank
+pub fn claim_ticket_collectible(
+    ctx: Context<ClaimTicketCollectible>,
+    _entry_seed: [u8; 8],
+    ticket_index: u64,
+) -> Result<()> {
+    let entry = &ctx.accounts.entry;
+    require!(
+        ticket_index >= entry.ticket_start_index
+            && ticket_index < checked_add(entry.ticket_start_index, entry.ticket_count)?,
+        RaffleError::TicketNotInEntry
+    );
+
+    let ticket_collectible = &mut ctx.accounts.ticket_collectible;
+    ticket_collectible.raffle = ctx.accounts.raffle.key();
+    ticket_collectible.owner = ctx.accounts.owner.key();
+    ticket_collectible.ticket_index = ticket_index;
+    ticket_collectible.claimed_at = now(&ctx.accounts.config)?;
+    ticket_collectible.bump = ctx.bumps.ticket_collectible;
+
+    let collectible_tree = ctx
+        .accounts
+        .config
+        .collectible_tree
+        .ok_or(RaffleError::CollectibleTreeNotSet)?;
+    require_keys_eq!(
+        ctx.accounts.merkle_tree.key(),
+        collectible_tree,
+        RaffleError::InvalidCollectibleTree
+    );
+    require!(
+        ctx.accounts.raffle.metadata_uri.len() <= MAX_COLLECTIBLE_URI_LENGTH,
+        RaffleError::CollectibleUriTooLong
+    );
+    mint_compressed_nft(
+        &ctx.accounts.config,
+        MintAccounts {
+            tree_authority: &ctx.accounts.tree_authority,
+            leaf_owner: &ctx.accounts.owner,
+            merkle_tree: &ctx.accounts.merkle_tree,
+            payer: &ctx.accounts.owner,
+            log_wrapper: &ctx.accounts.log_wrapper,
+            compression_program: &ctx.accounts.compression_program,
+            system_program: &ctx.accounts.system_program,
+            bubblegum_program: &ctx.accounts.bubblegum_program,
+        },
+        ticket_metadata(&ctx.accounts.raffle, ticket_index),
+    )?;
+
+    let max_tickets = ctx
+        .accounts
+        .raffle
+        .max_tickets
+        .ok_or(RaffleError::CollectibleRequiresMaxTickets)?;
+    let event_seq = ctx.accounts.raffle.next_event_seq()?;
+    emit!(TicketCollectibleClaimed {
+        raffle: ctx.accounts.raffle.key(),
+        owner: ctx.accounts.owner.key(),
+        ticket_index,
+        max_tickets,
+        merkle_tree: collectible_tree,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(entry_seed: [u8; 8], ticket_index: u64)]
+pub struct ClaimTicketCollectible<'info> {
+    /// The drawn collectible raffle
+    #[account(
+        mut,
+        constraint = raffle.collectible @ RaffleError::RaffleNotCollectible,
+        constraint = matches!(
+            raffle.raffle_state,
+            RaffleState::Drawn | RaffleState::Claimed
+        ) @ RaffleError::RaffleNotDrawn,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    /// The owner's entry holding the ticket
+    /// PDA with seeds ["entry", raffle_key, entry_seed]
+    #[account(
+        seeds = [
+            b"entry",
+            raffle.key().as_ref(),
+            entry_seed.as_ref()
+        ],
+        bump,
+        constraint = entry.owner == owner.key() @ RaffleError::OwnerMismatch,
+    )]
+    pub entry: Account<'info, Entry>,
+
+    /// The claim record for the ticket number
+    /// PDA with seeds ["ticket_collectible", raffle_key, ticket_index]
+    #[account(
+        init,
+        payer = owner,
+        space = TICKET_COLLECTIBLE_ACCOUNT_SIZE,
+        seeds = [
+            b"ticket_collectible",
+            raffle.key().as_ref(),
+            ticket_index.to_le_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub ticket_collectible: Account<'info, TicketCollectible>,
+
+    /// The entry owner, who pays for the claim record
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Program config, storing the collectible tree and the clock offset of devnet
+    /// builds, and signing the mint as tree delegate
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Bubblegum's tree config of the collectible tree
+    /// CHECK: Validated by Bubblegum against the merkle tree
+    #[account(mut)]
+    pub tree_authority: UncheckedAccount<'info>,
+
+    /// The config's collectible tree
+    /// CHECK: Validated against the config in the instruction, and by Bubblegum
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: The SPL Noop program
+    #[account(address = SPL_NOOP_PROGRAM_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+
+    /// CHECK: The SPL Account Compression program
+    #[account(address = SPL_ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    /// CHECK: The Bubblegum program
+    #[account(address = BUBBLEGUM_PROGRAM_ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+}
@@ -0,0 +1,194 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    instructions::create_raffle::{deposit_creator_bond, init_raffle, RaffleParams},
+    notifier::{notify_lifecycle, LifecycleEvent},
+    state::{Config, Raffle, Treasury, RAFFLE_ACCOUNT_SIZE, TREASURY_ACCOUNT_SIZE},
+    time::now,
+    treasury::open_token_vault,
+};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token},
+};
+
+/// Event emitted when a raffle is created as a copy of another raffle
+#[event]
+pub struct RaffleCloned {
+    /// The pubkey of the created raffle
+    pub raffle: Pubkey,
+    /// The pubkey of the raffle whose parameters were copied
+    pub source: Pubkey,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to create a new raffle with the parameters of an existing one, so popular
+/// raffles can be rerun without retyping their configuration
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Validates caller is the management authority via config PDA
+/// 2. Applies all checks of create_raffle to the copied parameters, so a raffle created
+///    under a looser config can't be copied past the current metadata host allowlist
+///
+/// # Account Validations
+/// * Source - Any existing raffle, in any state
+/// * Raffle - New PDA with seeds ["raffle", raffle_counter]
+/// * Treasury - New PDA initialized with seeds ["treasury", raffle_key]
+/// * Config - PDA storing the management authority and raffle counter
+/// * PaymentMint - Required if the source raffle is paid in an SPL token, must be its
+///   payment mint. The treasury's token account is created as by create_raffle
+/// * CreatorBond - Required if the config requires a creator bond, created as by
+///   create_raffle
+///
+/// # Implementation Notes
+/// - Copies the metadata URI, price, ticket limits, threshold, prize mechanics, region
+///   and age restrictions, the draw authority, the priority access tier, the unique
+///   buyer requirement, the payment mint, the randomness mode, the purchase gate and
+///   whether ticket numbering is deferred
+/// - The new raffle runs for as long as the source raffle did, starting now, and so does
+///   its priority phase
+/// - Sales, winner, prize deposit state, the source's claim instructions and prize
+///   details and its platform fee override are not copied
+pub fn clone_raffle(ctx: Context<CloneRaffle>) -> Result<()> {
+    let source = &ctx.accounts.source;
+    let duration = source
+        .end_time
+        .checked_sub(source.creation_time)
+        .ok_or(RaffleError::Overflow)?;
+    let now = now(&ctx.accounts.config)?;
+    let end_time = now.checked_add(duration).ok_or(RaffleError::Overflow)?;
+
+    // The priority phase keeps its length relative to the start of the raffle
+    let priority_until = if source.priority_until == 0 {
+        0
+    } else {
+        source
+            .priority_until
+            .checked_sub(source.creation_time)
+            .and_then(|priority_duration| now.checked_add(priority_duration))
+            .ok_or(RaffleError::Overflow)?
+    };
+
+    // The new raffle is paid in the same asset as the source
+    require!(
+        ctx.accounts.payment_mint.as_ref().map(|mint| mint.key()) == source.payment_mint,
+        RaffleError::InvalidPaymentMint
+    );
+
+    let params = RaffleParams {
+        end_time,
+        priority_until,
+        ..RaffleParams::from_raffle(source)
+    };
+    let rent_payer = ctx.accounts.management_authority.key();
+    init_raffle(
+        &mut ctx.accounts.raffle,
+        &mut ctx.accounts.treasury,
+        ctx.bumps.treasury,
+        &mut ctx.accounts.config,
+        rent_payer,
+        params,
+    )?;
+    deposit_creator_bond(
+        &mut ctx.accounts.raffle,
+        &ctx.accounts.config,
+        ctx.accounts.creator_bond.as_ref(),
+        &ctx.accounts.management_authority,
+        &ctx.accounts.system_program,
+    )?;
+    open_token_vault(
+        &ctx.accounts.management_authority,
+        &ctx.accounts.treasury.to_account_info(),
+        ctx.accounts.payment_mint.as_ref(),
+        ctx.accounts.treasury_token_account.as_ref(),
+        ctx.accounts.token_program.as_ref(),
+        ctx.accounts.associated_token_program.as_ref(),
+        &ctx.accounts.system_program,
+    )?;
+
+    let event_seq = ctx.accounts.raffle.next_event_seq()?;
+    emit!(RaffleCloned {
+        raffle: ctx.accounts.raffle.key(),
+        source: ctx.accounts.source.key(),
+        event_seq,
+    });
+
+    notify_lifecycle(
+        &ctx.accounts.config,
+        ctx.accounts.notifier_program.as_ref(),
+        &ctx.accounts.raffle,
+        LifecycleEvent::Created,
+    )
+}
+
+#[derive(Accounts)]
+pub struct CloneRaffle<'info> {
+    /// The raffle whose parameters are copied
+    pub source: Account<'info, Raffle>,
+
+    #[account(
+        init,
+        payer = management_authority,
+        space = RAFFLE_ACCOUNT_SIZE,
+        seeds = [
+            b"raffle",
+            config.raffle_counter.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    #[account(mut)]
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = management_authority,
+        space = TREASURY_ACCOUNT_SIZE,
+        seeds = [
+            b"treasury",
+            raffle.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+        constraint = !config.paused @ RaffleError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub system_program: Program<'info, System>,
+
+    /// The source raffle's payment mint, required if it is paid in an SPL token
+    pub payment_mint: Option<Account<'info, Mint>>,
+
+    /// Associated token account of the treasury for the payment mint, required with it
+    /// CHECK: Created by the associated token program, which only accepts the address
+    /// derived from the treasury and the mint
+    #[account(mut)]
+    pub treasury_token_account: Option<UncheckedAccount<'info>>,
+
+    /// Required if the source raffle is paid in an SPL token
+    pub token_program: Option<Program<'info, Token>>,
+
+    /// Required if the source raffle is paid in an SPL token
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+
+    /// Bond of the raffle's creator, required if the config requires a creator bond.
+    /// PDA with seeds ["creator_bond", raffle_key], validated and created in the
+    /// instruction.
+    /// CHECK: The address is checked against the PDA derived from the raffle
+    #[account(mut)]
+    pub creator_bond: Option<UncheckedAccount<'info>>,
+    /// Program notified of the raffle's creation, required if the config registers one
+    /// CHECK: Validated against the config's notifier program in the instruction
+    pub notifier_program: Option<UncheckedAccount<'info>>,
+}
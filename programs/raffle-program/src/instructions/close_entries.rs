@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    math::checked_add,
+    state::{Entry, Raffle, RaffleState, RentPool},
+};
+
+/// Event emitted when entries are closed by the close crank
+#[event]
+pub struct EntriesClosed {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// Number of entry accounts closed
+    pub entry_count: u64,
+    /// Total rent refunded in lamports
+    pub rent_refunded: u64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Crank instruction that closes a batch of entries of a concluded raffle and refunds
+/// their rent, so participants are made whole on account costs. Can be called by anyone.
+///
+/// # Remaining Accounts
+/// Pairs of writable accounts `[entry, rent_recipient]` for each entry to close:
+/// * `entry` - An Entry account belonging to the raffle
+/// * `rent_recipient` - The entry owner, or the rent pool if the pool sponsored the rent
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Validates the raffle is in Claimed, Expired or Cancelled state, so the winning entry can
+///    no longer be needed to set the winner
+/// 2. Verifies each entry belongs to the raffle
+/// 3. Ensures rent is only returned to whoever paid it
+pub fn close_entries<'info>(ctx: Context<'_, '_, 'info, 'info, CloseEntries<'info>>) -> Result<()> {
+    let remaining_accounts = ctx.remaining_accounts;
+    require!(
+        !remaining_accounts.is_empty(),
+        RaffleError::InvalidRemainingAccounts
+    );
+
+    let mut rent_refunded: u64 = 0;
+    for pair in remaining_accounts.chunks(2) {
+        let [entry_info, recipient_info] = pair else {
+            return err!(RaffleError::InvalidRemainingAccounts);
+        };
+        let entry = Account::<Entry>::try_from(entry_info)?;
+        require!(
+            entry.raffle == ctx.accounts.raffle.key(),
+            RaffleError::EntryRaffleMismatch
+        );
+
+        // Sponsored rent goes back to the rent pool, everything else to the entry owner
+        if entry.rent_sponsored {
+            Account::<RentPool>::try_from(recipient_info)?;
+        } else {
+            require!(
+                recipient_info.key() == entry.owner,
+                RaffleError::OwnerMismatch
+            );
+        }
+
+        rent_refunded = checked_add(rent_refunded, entry_info.lamports())?;
+        entry.close(recipient_info.clone())?;
+    }
+
+    let event_seq = ctx.accounts.raffle.next_event_seq()?;
+    emit!(EntriesClosed {
+        raffle: ctx.accounts.raffle.key(),
+        entry_count: (remaining_accounts.len() / 2) as u64,
+        rent_refunded,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseEntries<'info> {
+    /// The concluded raffle the entries belong to
+    #[account(
+        mut,
+        constraint = matches!(
+            raffle.raffle_state,
+            RaffleState::Claimed | RaffleState::Expired | RaffleState::Cancelled
+        ) @ RaffleError::RaffleNotFinalized,
+    )]
+    pub raffle: Account<'info, Raffle>,
+}
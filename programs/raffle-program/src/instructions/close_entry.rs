@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Entry, Raffle, RaffleState, RentPool, Treasury},
+};
+
+/// Event emitted when an entry owner closes their entry
+#[event]
+pub struct EntryClosed {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The pubkey of the closed entry
+    pub entry: Pubkey,
+    /// The account the rent was returned to
+    pub rent_recipient: Pubkey,
+    /// Rent returned in lamports
+    pub rent_refunded: u64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction for an entry owner to close their entry of a settled raffle and reclaim
+/// its rent, without waiting for the close_entries crank
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `entry_seed` - Seed the entry PDA was derived from
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Validates the raffle is in Claimed or Expired state and nothing in its treasury is
+///    still owed to ticket holders or the payout authority, so the entry can no longer
+///    be needed for a refund
+/// 2. Only the entry owner can close the entry
+/// 3. Ensures a winning entry of a raffle with an escrowed prize is only closed once
+///    every prize was claimed
+/// 4. Ensures rent is only returned to whoever paid it
+///
+/// # Account Validations
+/// * Raffle - Must be in Claimed or Expired state
+/// * Treasury - PDA with seeds ["treasury", raffle_key], fully paid out
+/// * Entry - PDA with seeds ["entry", raffle_key, entry_seed], owned by the signer
+/// * RentPool - Optional, required if the pool sponsored the entry rent
+pub fn close_entry(ctx: Context<CloseEntry>, _entry_seed: [u8; 8]) -> Result<()> {
+    let raffle = &ctx.accounts.raffle;
+    let entry = &ctx.accounts.entry;
+
+    // The winning entry stays available while a prize can still be claimed with it
+    if raffle.prize_escrowed && holds_winning_ticket(raffle, entry) {
+        require!(
+            raffle.prizes_claimed == raffle.all_winners_mask(),
+            RaffleError::WinningEntryInUse
+        );
+    }
+
+    // Sponsored rent goes back to the rent pool, everything else to the entry owner
+    let rent_recipient = if entry.rent_sponsored {
+        ctx.accounts
+            .rent_pool
+            .as_ref()
+            .ok_or(RaffleError::OwnerMismatch)?
+            .to_account_info()
+    } else {
+        ctx.accounts.owner.to_account_info()
+    };
+
+    let rent_refunded = entry.to_account_info().lamports();
+    let entry_key = entry.key();
+    ctx.accounts.entry.close(rent_recipient.clone())?;
+
+    let event_seq = ctx.accounts.raffle.next_event_seq()?;
+    emit!(EntryClosed {
+        raffle: ctx.accounts.raffle.key(),
+        entry: entry_key,
+        rent_recipient: rent_recipient.key(),
+        rent_refunded,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+/// Whether any of the raffle's winning tickets belongs to the entry
+fn holds_winning_ticket(raffle: &Raffle, entry: &Entry) -> bool {
+    raffle.winning_tickets.iter().any(|&ticket| {
+        ticket >= entry.ticket_start_index && ticket - entry.ticket_start_index < entry.ticket_count
+    })
+}
+
+#[derive(Accounts)]
+#[instruction(entry_seed: [u8; 8])]
+pub struct CloseEntry<'info> {
+    /// The settled raffle the entry belongs to
+    #[account(
+        mut,
+        constraint = matches!(
+            raffle.raffle_state,
+            RaffleState::Claimed | RaffleState::Expired
+        ) @ RaffleError::RaffleNotFinalized,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    #[account(
+        seeds = [
+            b"treasury",
+            raffle.key().as_ref(),
+        ],
+        bump = treasury.bump,
+        constraint = treasury.outstanding()? == 0 @ RaffleError::TreasuryNotSettled,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"entry",
+            raffle.key().as_ref(),
+            entry_seed.as_ref(),
+        ],
+        bump,
+        has_one = owner @ RaffleError::OwnerMismatch,
+    )]
+    pub entry: Account<'info, Entry>,
+
+    /// The entry owner, receiving the rent unless the rent pool sponsored it
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Rent pool receiving the rent of a sponsored entry.
+    /// Only a single RentPool can exist, at the PDA with seeds ["rent_pool"], so the
+    /// owner and discriminator checks performed by Anchor are sufficient.
+    #[account(mut)]
+    pub rent_pool: Option<Account<'info, RentPool>>,
+}
@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    math::checked_add,
+    state::{PurchaseDedupe, Raffle, RaffleState},
+};
+
+/// Event emitted when purchase dedupe records are closed by the close crank
+#[event]
+pub struct PurchaseDedupesClosed {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// Number of dedupe accounts closed
+    pub dedupe_count: u64,
+    /// Total rent refunded in lamports
+    pub rent_refunded: u64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Crank instruction that closes a batch of purchase dedupe records of a concluded
+/// raffle and refunds their rent to the buyers. Can be called by anyone.
+///
+/// # Remaining Accounts
+/// Pairs of writable accounts `[purchase_dedupe, buyer]` for each record to close:
+/// * `purchase_dedupe` - A PurchaseDedupe account belonging to the raffle
+/// * `buyer` - The buyer that paid for the record
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Validates the raffle is in Claimed, Expired or Cancelled state, so no purchase can
+///    be retried
+/// 2. Verifies each record belongs to the raffle
+/// 3. Ensures rent is only returned to the buyer that paid it
+pub fn close_purchase_dedupes<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ClosePurchaseDedupes<'info>>,
+) -> Result<()> {
+    let remaining_accounts = ctx.remaining_accounts;
+    require!(
+        !remaining_accounts.is_empty(),
+        RaffleError::InvalidRemainingAccounts
+    );
+
+    let mut rent_refunded: u64 = 0;
+    for pair in remaining_accounts.chunks(2) {
+        let [dedupe_info, buyer_info] = pair else {
+            return err!(RaffleError::InvalidRemainingAccounts);
+        };
+        let purchase_dedupe = Account::<PurchaseDedupe>::try_from(dedupe_info)?;
+        require!(
+            purchase_dedupe.raffle == ctx.accounts.raffle.key(),
+            RaffleError::InvalidPurchaseDedupe
+        );
+        require!(
+            buyer_info.key() == purchase_dedupe.buyer,
+            RaffleError::OwnerMismatch
+        );
+
+        rent_refunded = checked_add(rent_refunded, dedupe_info.lamports())?;
+        purchase_dedupe.close(buyer_info.clone())?;
+    }
+
+    let event_seq = ctx.accounts.raffle.next_event_seq()?;
+    emit!(PurchaseDedupesClosed {
+        raffle: ctx.accounts.raffle.key(),
+        dedupe_count: (remaining_accounts.len() / 2) as u64,
+        rent_refunded,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClosePurchaseDedupes<'info> {
+    /// The concluded raffle the purchase dedupe records belong to
+    #[account(
+        mut,
+        constraint = matches!(
+            raffle.raffle_state,
+            RaffleState::Claimed | RaffleState::Expired | RaffleState::Cancelled
+        ) @ RaffleError::RaffleNotFinalized,
+    )]
+    pub raffle: Account<'info, Raffle>,
+}
@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    math::checked_add,
+    state::{Config, CreatorRole, Raffle, RaffleState, Treasury},
+};
+
+/// Event emitted when a concluded raffle and its treasury are closed
+#[event]
+pub struct RaffleClosed {
+    /// The pubkey of the closed raffle
+    pub raffle: Pubkey,
+    /// The account the rent was returned to
+    pub rent_payer: Pubkey,
+    /// Total rent returned in lamports
+    pub rent_refunded: u64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to close a concluded raffle and its treasury, returning their rent to
+/// whoever paid it at creation rather than to the current authorities
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Verifies the signer is the management authority
+/// 2. Validates the raffle is in Claimed, Expired or Cancelled state
+/// 3. Ensures no tickets are held by pending reservations
+/// 4. Ensures nothing in the treasury is still owed to ticket holders or the payout
+///    authority
+/// 5. Ensures the rent is only returned to the recorded rent payer
+///
+/// # Account Validations
+/// * Raffle - Must be in Claimed, Expired or Cancelled state
+/// * Treasury - PDA with seeds ["treasury", raffle_key], fully paid out
+/// * Rent Payer - Must match the rent payer recorded in the raffle
+/// * CreatorRole - Optional, the role of the raffle's creator, whose active raffles are
+///   decremented if given
+///
+/// # Implementation Notes
+/// - Entries, ticket balances and winner data can't be closed once their raffle is, so
+///   they should be closed first
+/// - The creator role of a raffle's creator should be passed, to free the slot the
+///   raffle takes up among the creator's active raffles
+/// - Stray lamports should be swept with sweep_treasury_dust first, as they are
+///   otherwise returned along with the rent
+pub fn close_raffle(ctx: Context<CloseRaffle>) -> Result<()> {
+    require!(
+        ctx.accounts.raffle.reserved_tickets == 0,
+        RaffleError::TreasuryNotSettled
+    );
+    require!(
+        ctx.accounts.treasury.outstanding()? == 0,
+        RaffleError::TreasuryNotSettled
+    );
+
+    // Free the raffle's slot among its creator's active raffles
+    if let Some(creator_role) = ctx.accounts.creator_role.as_mut() {
+        require!(
+            ctx.accounts.raffle.creator == Some(creator_role.creator),
+            RaffleError::NotRaffleCreator
+        );
+        creator_role.active_raffles = creator_role.active_raffles.saturating_sub(1);
+    }
+
+    let rent_refunded = checked_add(
+        ctx.accounts.raffle.to_account_info().lamports(),
+        ctx.accounts.treasury.to_account_info().lamports(),
+    )?;
+
+    let event_seq = ctx.accounts.raffle.next_event_seq()?;
+    emit!(RaffleClosed {
+        raffle: ctx.accounts.raffle.key(),
+        rent_payer: ctx.accounts.rent_payer.key(),
+        rent_refunded,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseRaffle<'info> {
+    #[account(
+        mut,
+        close = rent_payer,
+        has_one = rent_payer @ RaffleError::NotRentPayer,
+        constraint = matches!(
+            raffle.raffle_state,
+            RaffleState::Claimed | RaffleState::Expired | RaffleState::Cancelled
+        ) @ RaffleError::RaffleNotFinalized,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    #[account(
+        mut,
+        close = rent_payer,
+        seeds = [
+            b"treasury",
+            raffle.key().as_ref(),
+        ],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    /// The account that paid the rent of the raffle and its treasury
+    #[account(mut)]
+    pub rent_payer: SystemAccount<'info>,
+
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Creator role of the raffle's creator, if it created the raffle through a role that
+    /// was not revoked since. Validated against the raffle in the instruction.
+    #[account(mut)]
+    pub creator_role: Option<Account<'info, CreatorRole>>,
+}
@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, Raffle, RaffleState, Treasury, WinnerData},
+};
+
+/// Event emitted when the winner data of a settled raffle is closed
+#[event]
+pub struct WinnerDataClosed {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The winner whose data was closed and who received the rent
+    pub winner: Pubkey,
+    /// Rent returned in lamports
+    pub rent_refunded: u64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to close the winner data of a settled raffle once the prize was
+/// fulfilled, returning its rent to the winner who paid it
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Verifies the signer is the management authority
+/// 2. Validates the raffle is in Claimed state and nothing in its treasury is still
+///    owed to the payout authority
+/// 3. Ensures the data is locked, so the winner has confirmed it is final
+/// 4. Ensures the rent is only returned to the winner the data belongs to
+///
+/// # Account Validations
+/// * Raffle - Must be in Claimed state
+/// * Treasury - PDA with seeds ["treasury", raffle_key], fully paid out
+/// * Winner - Must be one of the raffle's winners
+/// * WinnerData - PDA with seeds ["winner_data", raffle_key, winner], locked
+/// * Config - PDA storing the management authority
+///
+/// # Implementation Notes
+/// - Winner data can't be closed once its raffle is, so it should be closed before
+///   close_raffle
+pub fn close_winner_data(ctx: Context<CloseWinnerData>) -> Result<()> {
+    let rent_refunded = ctx.accounts.winner_data.to_account_info().lamports();
+
+    let event_seq = ctx.accounts.raffle.next_event_seq()?;
+    emit!(WinnerDataClosed {
+        raffle: ctx.accounts.raffle.key(),
+        winner: ctx.accounts.winner.key(),
+        rent_refunded,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseWinnerData<'info> {
+    #[account(
+        mut,
+        constraint = raffle.raffle_state == RaffleState::Claimed @ RaffleError::RaffleNotClaimed,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    #[account(
+        seeds = [
+            b"treasury",
+            raffle.key().as_ref(),
+        ],
+        bump = treasury.bump,
+        constraint = treasury.outstanding()? == 0 @ RaffleError::TreasuryNotSettled,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    /// The winner who paid the rent of the winner data
+    #[account(
+        mut,
+        constraint = raffle.winner_indices(&winner.key()) != 0 @ RaffleError::NotWinner,
+    )]
+    pub winner: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        close = winner,
+        seeds = [
+            b"winner_data",
+            raffle.key().as_ref(),
+            winner.key().as_ref(),
+        ],
+        bump,
+        constraint = winner_data.locked @ RaffleError::WinnerDataNotLocked,
+    )]
+    pub winner_data: Account<'info, WinnerData>,
+
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    instructions::draw_winning_ticket::{
+        authorize_draw, read_latest_slot_hash, slot_hash_entropy, SLOT_HASHES_MAX_ENTRIES,
+    },
+    math::checked_add,
+    rand::unbiased_range,
+    state::{Config, Keeper, Raffle, RaffleState, RandomnessMode},
+    time::{clock, now},
+};
+
+/// Event emitted when a raffle commits to the slot its draw is taken from
+#[event]
+pub struct DrawSlotCommitted {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// Slot the draw slot was committed in
+    pub commit_slot: u64,
+    /// Slot whose hash, or that of the next produced slot, draws the raffle
+    pub draw_target_slot: u64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Commits a raffle delaying its draw slot to the slot draw_winning_ticket takes its
+/// entropy from, a random number of slots after the current one. The delay is derived
+/// from the latest slot hash, so nobody can know which slot decides the winner until the
+/// sale has closed.
+///
+/// Execution requirements:
+/// 1. The raffle must be in Open state, use the SlotHashes randomness mode and delay its
+///    draw slot
+/// 2. The raffle end time must have passed, or all tickets must be sold
+/// 3. The minimum ticket threshold and unique buyer requirement must be met, and at
+///    least as many tickets must be sold as the raffle has winners
+/// 4. The caller must be the raffle's draw authority, if it has one, or otherwise a
+///    registered keeper, if the config restricts cranks to keepers
+/// 5. No draw slot may be committed yet, unless the committed slot rotated out of the
+///    SlotHashes sysvar before the raffle was drawn
+/// 6. If the raffle defers numbering, finalize_numbering must have numbered every entry
+///
+/// # Arguments
+/// * `ctx` - The context object containing:
+///   - `raffle`: The mutable raffle account being drawn
+///   - `recent_slothashes`: The SlotHashes sysvar (manually validated)
+///   - `keeper`, `keeper_registration`: The calling keeper and its registration, if
+///     cranks are restricted
+///   - `draw_authority`: The raffle's draw authority, if it has one
+///
+/// # Errors
+/// - `ProgramPaused` if the config's pause switch is on
+/// - `NotDrawAuthority` if the raffle has a draw authority that did not sign
+/// - `KeeperNotRegistered` if cranks are restricted and the caller is not a keeper
+/// - `RaffleNotOpen` if the raffle is not in Open state
+/// - `SlotHashDrawDisabled` if the raffle must be drawn with verifiable randomness or votes
+/// - `DrawDelayNotEnabled` if the raffle draws from the latest slot hash
+/// - `DrawSlotAlreadyCommitted` if the committed draw slot can still be drawn from
+/// - `RaffleNotEnded` if the raffle end time hasn't been reached
+/// - `InsufficientUniqueBuyers` if too few distinct wallets bought tickets
+/// - `InsufficientTickets` if minimum ticket threshold not met, or fewer tickets were
+///   sold than the raffle has winners
+/// - `NumberingNotFinalized` if the raffle defers numbering and finalize_numbering has
+///   not numbered every entry
+/// - `InvalidSlotHashesAccount` if the provided SlotHashes account is invalid
+pub fn commit_draw_slot(ctx: Context<CommitDrawSlot>) -> Result<()> {
+    authorize_draw(
+        &ctx.accounts.raffle,
+        &ctx.accounts.config,
+        ctx.accounts.keeper.as_ref(),
+        ctx.accounts.keeper_registration.as_ref(),
+        ctx.accounts.draw_authority.as_ref(),
+    )?;
+
+    let clock = clock(&ctx.accounts.config)?;
+    let raffle = &mut ctx.accounts.raffle;
+    // A committed slot can only be replaced once it can no longer be drawn from
+    require!(
+        raffle.draw_target_slot == 0
+            || clock.slot > checked_add(raffle.draw_target_slot, SLOT_HASHES_MAX_ENTRIES)?,
+        RaffleError::DrawSlotAlreadyCommitted
+    );
+
+    // Derive the delay from the latest slot hash, which nobody controls at sale close
+    let (slot, slot_hash) = read_latest_slot_hash(&ctx.accounts.recent_slothashes)?;
+    let delay = unbiased_range(
+        slot_hash_entropy(slot, &slot_hash, clock.unix_timestamp),
+        raffle.draw_delay_slots as u64 + 1,
+    )?;
+    let draw_target_slot = checked_add(checked_add(clock.slot, 1)?, delay)?;
+    raffle.draw_target_slot = draw_target_slot;
+
+    let event_seq = raffle.next_event_seq()?;
+    emit!(DrawSlotCommitted {
+        raffle: raffle.key(),
+        commit_slot: clock.slot,
+        draw_target_slot,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+/// Accounts required for the commit_draw_slot instruction
+#[derive(Accounts)]
+pub struct CommitDrawSlot<'info> {
+    /// The raffle account to draw a winner for.
+    /// Must be in Open state, past end time or sold out, and have met minimum ticket
+    /// threshold and unique buyer requirement, with a ticket sold for every winner
+    #[account(
+        mut,
+        constraint = raffle.raffle_state == RaffleState::Open @ RaffleError::RaffleNotOpen,
+        constraint = raffle.randomness_mode == RandomnessMode::SlotHashes @ RaffleError::SlotHashDrawDisabled,
+        constraint = raffle.draw_delay_slots > 0 @ RaffleError::DrawDelayNotEnabled,
+        constraint = now(&config)? >= raffle.end_time
+            || raffle.max_tickets == Some(raffle.current_tickets)
+            @ RaffleError::RaffleNotEnded,
+        constraint = raffle.unique_buyers_met() @ RaffleError::InsufficientUniqueBuyers,
+        constraint = raffle.threshold_met()? @ RaffleError::InsufficientTickets,
+        constraint = raffle.current_tickets >= raffle.winner_count as u64 @ RaffleError::InsufficientTickets,
+        constraint = raffle.numbering_finalized() @ RaffleError::NumberingNotFinalized,
+        constraint = raffle.reserved_tickets == 0 @ RaffleError::ReservationsPending,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    /// The SlotHashes sysvar, whose latest hash derives the delay
+    /// CHECK: Using UncheckedAccount because we manually validate the correct sysvar.
+    /// This is needed because Anchor will always throw an error on the SlotHashes sysvar.
+    pub recent_slothashes: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ RaffleError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The calling keeper, required if the config restricts cranks to keepers
+    pub keeper: Option<Signer<'info>>,
+
+    /// The registration of the calling keeper, validated manually
+    pub keeper_registration: Option<Account<'info, Keeper>>,
+
+    /// The raffle's draw authority, required if the raffle has one
+    pub draw_authority: Option<Signer<'info>>,
+}
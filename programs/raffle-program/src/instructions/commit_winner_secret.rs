@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Raffle, RaffleState, TicketBalance},
+};
+
+/// Event emitted when a ticket holder of an anonymized raffle commits to a winner secret
+#[event]
+pub struct WinnerSecretCommitted {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The ticket balance holding the commitment
+    pub ticket_balance: Pubkey,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction for a ticket holder of an anonymized raffle to commit to a secret, which
+/// they reveal to claim if they win. set_winner stores only the commitment, so the raffle
+/// account doesn't name the winner until they claim.
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `commitment` - `winner_hash` of the raffle, the signer and a secret the signer keeps
+///   off-chain
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Only the owner of the ticket balance can commit, as the ticket balance is the PDA
+///    of the signer
+/// 2. Only raffles that anonymize their winners accept commitments
+/// 3. The commitment can only be set or replaced while the raffle is open, so it is
+///    fixed before the winning tickets are drawn
+///
+/// # Account Validations
+/// * Raffle - Must be in Open state and anonymize its winners
+/// * TicketBalance - PDA with seeds ["ticket_balance", raffle_key, signer]
+///
+/// # Implementation Notes
+/// - Winners who didn't commit are stored in plaintext by set_winner
+/// - The program can't check the commitment, so a holder committing to a hash they
+///   can't reproduce can't claim their win
+pub fn commit_winner_secret(ctx: Context<CommitWinnerSecret>, commitment: [u8; 32]) -> Result<()> {
+    ctx.accounts.ticket_balance.winner_commitment = Some(commitment);
+
+    let raffle = &mut ctx.accounts.raffle;
+    let event_seq = raffle.next_event_seq()?;
+    emit!(WinnerSecretCommitted {
+        raffle: raffle.key(),
+        ticket_balance: ctx.accounts.ticket_balance.key(),
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CommitWinnerSecret<'info> {
+    /// The anonymized raffle, mutable to advance its event sequence
+    #[account(
+        mut,
+        constraint = raffle.raffle_state == RaffleState::Open @ RaffleError::RaffleNotOpen,
+        constraint = raffle.anonymize_winner @ RaffleError::RaffleNotAnonymized,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    /// The signer's ticket balance in the raffle, storing the commitment
+    #[account(
+        mut,
+        seeds = [
+            b"ticket_balance",
+            raffle.key().as_ref(),
+            signer.key().as_ref()
+        ],
+        bump = ticket_balance.bump,
+    )]
+    pub ticket_balance: Account<'info, TicketBalance>,
+
+    pub signer: Signer<'info>,
+}
@@ -0,0 +1,355 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{create_account, CreateAccount},
+};
+
+use crate::{
+    error::RaffleError,
+    instructions::{
+        buy_bundle::create_entry,
+        buy_tickets::{
+            emit_large_purchase, record_top_holder, validate_direct_purchase,
+            validate_purchase, validate_wallet_limit, TicketsPurchased,
+        },
+    },
+    math::{checked_add, ticket_cost},
+    notifier::notify_if_sold_out,
+    state::{
+        Config, Entry, Leaderboard, Raffle, RaffleState, Reservation, TicketBalance, Treasury,
+        TICKET_BALANCE_ACCOUNT_SIZE,
+    },
+    time::now,
+    transfer::rent_reserve,
+    treasury::{LamportVault, TreasuryVault},
+};
+
+/// Event emitted when a reservation is released without creating an entry
+#[event]
+pub struct ReservationReleased {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The pubkey of the released reservation
+    pub reservation: Pubkey,
+    /// The wallet the tickets were reserved for
+    pub buyer: Pubkey,
+    /// Number of tickets returned to sale
+    pub ticket_count: u64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to conclude a reservation once its off-chain payment has settled or
+/// failed. Finalizing creates an entry with the reserved tickets for the buyer, paid in
+/// SOL by the management authority; releasing returns the tickets to sale. Either way
+/// the reservation account is closed.
+///
+/// The management authority can release a reservation at any time, while anyone can
+/// release an expired reservation with release_expired_reservation.
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `finalize` - Whether to create the entry, or release the reservation
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Validates caller is the management authority via config PDA
+/// 2. Only finalizes reservations of open raffles before both the raffle end time and
+///    the reservation expiry
+/// 3. Applies the same ticket limits as a purchase when finalizing
+/// 4. Collects the ticket cost into the treasury, so refunds and withdrawals stay
+///    covered by the treasury's own funds
+/// 5. If the config rejects CPI purchases, ensures a finalizing confirm_reservation is
+///    a top-level instruction of the transaction
+/// 6. The entry receives the ticket range recorded by reserve_tickets
+///
+/// # Account Validations
+/// * Reservation - PDA with seeds ["reservation", raffle_key, seed], closed to the
+///   management authority
+/// * Treasury - PDA with seeds ["treasury", raffle_key]
+/// * Entry - Required when finalizing, the uninitialized PDA with seeds
+///   ["entry", raffle_key, reservation.seed]
+/// * TicketBalance - Required when finalizing, the buyer's ticket balance PDA, created
+///   if the buyer has none yet
+/// * Leaderboard - Optional, required if the raffle splits its prize among top holders
+pub fn confirm_reservation<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ConfirmReservation<'info>>,
+    finalize: bool,
+) -> Result<()> {
+    let reservation = &ctx.accounts.reservation;
+    let raffle = &mut ctx.accounts.raffle;
+
+    if !finalize {
+        return release_reservation(raffle, reservation);
+    }
+
+    // The tickets are no longer held, as they are sold to the buyer
+    raffle.release_reservation(
+        reservation.ticket_start_index,
+        reservation.ticket_count,
+        true,
+    )?;
+
+    validate_direct_purchase(&ctx.accounts.config)?;
+    let current_time = now(&ctx.accounts.config)?;
+    require!(
+        raffle.raffle_state == RaffleState::Open,
+        RaffleError::RaffleNotOpen
+    );
+    require!(current_time < raffle.end_time, RaffleError::RaffleEnded);
+    require!(
+        current_time <= reservation.expires_at,
+        RaffleError::ReservationExpired
+    );
+
+    // Tiered prices never exceed the ticket price, which bounds the cost of the tickets
+    let payment_amount = validate_purchase(
+        raffle,
+        &ctx.accounts.management_authority,
+        reservation.ticket_count,
+        Some(ticket_cost(reservation.ticket_count, raffle.ticket_price)?),
+        current_time,
+    )?;
+
+    let entry_info = ctx
+        .accounts
+        .entry
+        .as_ref()
+        .ok_or(RaffleError::InvalidRemainingAccounts)?;
+    let ticket_balance_info = ctx
+        .accounts
+        .ticket_balance
+        .as_ref()
+        .ok_or(RaffleError::TicketBalanceNotInitialized)?;
+    let mut ticket_balance = load_ticket_balance(
+        ticket_balance_info,
+        &ctx.accounts.management_authority,
+        &ctx.accounts.system_program,
+        raffle.key(),
+        reservation.buyer,
+    )?;
+    validate_wallet_limit(
+        raffle,
+        ticket_balance.ticket_count,
+        reservation.ticket_count,
+    )?;
+
+    // The entry receives the ticket range fixed when reserving
+    let (entry_index, _) = raffle.record_entry()?;
+    let ticket_start_index = reservation.ticket_start_index;
+    create_entry(
+        entry_info,
+        &ctx.accounts.management_authority,
+        &ctx.accounts.system_program,
+        Entry {
+            raffle: raffle.key(),
+            owner: reservation.buyer,
+            ticket_count: reservation.ticket_count,
+            ticket_start_index,
+            seed: reservation.seed,
+            rent_sponsored: false,
+            memo: None,
+            next_entry: ticket_balance.head_entry,
+            entry_index,
+            ..Default::default()
+        },
+        current_time,
+    )?;
+    ticket_balance.head_entry = Some(entry_info.key());
+
+    raffle.current_tickets = checked_add(raffle.current_tickets, reservation.ticket_count)?;
+    if ticket_balance.ticket_count == 0 {
+        raffle.participant_count = checked_add(raffle.participant_count, 1)?;
+    }
+    ticket_balance.ticket_count =
+        checked_add(ticket_balance.ticket_count, reservation.ticket_count)?;
+    ticket_balance.total_spent = checked_add(ticket_balance.total_spent, payment_amount)?;
+    let unit_price = raffle.unit_price(reservation.ticket_count);
+    raffle.record_tier_discount(&mut ticket_balance, reservation.ticket_count, unit_price)?;
+
+    record_top_holder(raffle, ctx.accounts.leaderboard.as_mut(), &ticket_balance)?;
+    ticket_balance.try_serialize(&mut &mut ticket_balance_info.try_borrow_mut_data()?[..])?;
+
+    // The management authority pays the ticket cost from the settled off-chain payment
+    LamportVault::new(&mut ctx.accounts.treasury, &ctx.accounts.system_program).collect(
+        &ctx.accounts.management_authority.to_account_info(),
+        payment_amount,
+    )?;
+
+    let event_seq = raffle.next_event_seq()?;
+    emit!(TicketsPurchased {
+        raffle: raffle.key(),
+        buyer: reservation.buyer,
+        ticket_count: reservation.ticket_count,
+        payment_amount,
+        unit_price,
+        ticket_start_index,
+        entry_seed: reservation.seed,
+        campaign_discount: 0,
+        rent_sponsored: false,
+        memo: None,
+        referrer: None,
+        cumulative_tickets: ticket_balance.ticket_count,
+        cumulative_spend: ticket_balance.total_spent,
+        win_probability_bps: raffle.win_probability_bps(ticket_balance.ticket_count),
+        total_tickets: raffle.current_tickets,
+        event_seq,
+    });
+    emit_large_purchase(
+        &ctx.accounts.config,
+        raffle,
+        reservation.buyer,
+        reservation.ticket_count,
+        payment_amount,
+    )?;
+
+    notify_if_sold_out(
+        &ctx.accounts.config,
+        ctx.accounts.notifier_program.as_ref(),
+        raffle,
+    )
+}
+
+/// Returns the tickets of `reservation` to sale, see `Raffle::release_reservation`.
+/// Only while the raffle is open, as a drawn or closed raffle no longer sells them.
+///
+/// Shared by confirm_reservation and release_expired_reservation.
+pub(crate) fn release_reservation(
+    raffle: &mut Account<Raffle>,
+    reservation: &Account<Reservation>,
+) -> Result<()> {
+    require!(
+        raffle.raffle_state == RaffleState::Open,
+        RaffleError::RaffleNotOpen
+    );
+    raffle.release_reservation(
+        reservation.ticket_start_index,
+        reservation.ticket_count,
+        false,
+    )?;
+
+    let event_seq = raffle.next_event_seq()?;
+    emit!(ReservationReleased {
+        raffle: raffle.key(),
+        reservation: reservation.key(),
+        buyer: reservation.buyer,
+        ticket_count: reservation.ticket_count,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+/// Loads the owner's ticket balance PDA with seeds ["ticket_balance", raffle_key,
+/// owner], creating it first if the owner has none yet. Changes must be written back
+/// by the caller.
+pub(crate) fn load_ticket_balance<'info>(
+    ticket_balance_info: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    raffle: Pubkey,
+    owner: Pubkey,
+) -> Result<TicketBalance> {
+    let (address, bump) = Pubkey::find_program_address(
+        &[b"ticket_balance", raffle.as_ref(), owner.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(
+        ticket_balance_info.key(),
+        address,
+        RaffleError::TicketBalanceNotInitialized
+    );
+
+    // Only the program can create accounts at its PDAs, so an existing account at the
+    // address is a ticket balance, which the discriminator check confirms
+    if !ticket_balance_info.data_is_empty() {
+        return TicketBalance::try_deserialize(&mut &ticket_balance_info.try_borrow_data()?[..]);
+    }
+
+    create_account(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            CreateAccount {
+                from: payer.to_account_info(),
+                to: ticket_balance_info.clone(),
+            },
+            &[&[b"ticket_balance", raffle.as_ref(), owner.as_ref(), &[bump]]],
+        ),
+        rent_reserve(TICKET_BALANCE_ACCOUNT_SIZE)?,
+        TICKET_BALANCE_ACCOUNT_SIZE as u64,
+        &crate::ID,
+    )?;
+
+    Ok(TicketBalance {
+        owner,
+        ticket_count: 0,
+        bump,
+        head_entry: None,
+        total_spent: 0,
+        tier_discount: 0,
+        rollover: false,
+        winner_commitment: None,
+        campaign_subsidy: 0,
+        subsidy_campaign: None,
+    })
+}
+
+#[derive(Accounts)]
+pub struct ConfirmReservation<'info> {
+    #[account(mut)]
+    pub raffle: Account<'info, Raffle>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"reservation",
+            raffle.key().as_ref(),
+            reservation.seed.as_ref(),
+        ],
+        bump = reservation.bump,
+        has_one = raffle,
+        close = management_authority,
+    )]
+    pub reservation: Account<'info, Reservation>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"treasury",
+            raffle.key().as_ref(),
+        ],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    /// The entry created for the buyer, required when finalizing
+    /// CHECK: The address is checked against the entry PDA when the entry is created
+    #[account(mut)]
+    pub entry: Option<UncheckedAccount<'info>>,
+
+    /// The buyer's ticket balance, required when finalizing
+    /// CHECK: The address is checked against the ticket balance PDA of the buyer
+    #[account(mut)]
+    pub ticket_balance: Option<UncheckedAccount<'info>>,
+
+    /// Optional leaderboard of the raffle's largest holders, required if the raffle splits
+    /// its prize among top holders. Validated against the raffle in the instruction.
+    #[account(mut)]
+    pub leaderboard: Option<Account<'info, Leaderboard>>,
+
+    #[account(mut)]
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+        constraint = !config.paused @ RaffleError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub system_program: Program<'info, System>,
+    /// Program notified if the purchase sells the raffle out, required then if the
+    /// config registers one
+    /// CHECK: Validated against the config's notifier program in the instruction
+    pub notifier_program: Option<UncheckedAccount<'info>>,
+}
@@ -0,0 +1,182 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    instructions::withdraw_from_treasury::{
+        fee_recipient, payout_recipient, split_platform_fee, withdrawable_amount,
+        TreasuryWithdrawn,
+    },
+    math::checked_add,
+    state::{Config, Raffle, RaffleState, Treasury},
+    treasury::{LamportVault, TreasuryVault},
+};
+
+/// Event emitted when the treasuries of several raffles are consolidated
+#[event]
+pub struct TreasuriesConsolidated {
+    /// The account the withdrawals were paid to
+    pub recipient: Pubkey,
+    /// Number of treasuries funds were withdrawn from
+    pub raffle_count: u64,
+    /// Total lamports paid to the recipient
+    pub total_amount: u64,
+    /// Total lamports of platform fees paid to the fee recipient
+    pub total_fee_amount: u64,
+}
+
+/// Instruction to withdraw the funds of many concluded raffles in a single transaction,
+/// paying them to the payout authority, or to an approved payout destination if the
+/// config approves any
+///
+/// # Remaining Accounts
+/// Pairs of writable accounts `[raffle, treasury]` for each raffle to withdraw from:
+/// * `raffle` - A Drawn, Claimed or Expired raffle paid in lamports
+/// * `treasury` - The raffle's treasury
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Verifies the signer is the management authority
+/// 2. Ensures every treasury matches the one stored in its raffle
+/// 3. Applies the same limits as withdraw_from_treasury to every raffle, so only
+///    retained refund fees are withdrawn from expired raffles, cash prize and cancelled
+///    raffles are rejected and revenue is withheld until the prize is escrowed if the
+///    config requires prize deposits
+/// 4. Leaves every treasury its rent reserve
+/// 5. If the config approves payout destinations, ensures the withdrawals are paid to
+///    one of them
+/// 6. If a platform fee is charged, ensures it is paid to the config's fee recipient
+/// 7. If the config has a withdrawal approver, ensures withdrawals above its threshold
+///    were approved with approve_withdrawal
+///
+/// # Implementation Notes
+/// - Raffles paid in an SPL token are rejected, they are withdrawn with
+///   withdraw_from_treasury
+/// - Treasuries with nothing left to withdraw are skipped, so a batch can be retried
+/// - Each raffle records a TreasuryWithdrawn event for its withdrawal
+/// - The platform fee is split off every withdrawal as by withdraw_from_treasury
+pub fn consolidate_treasuries<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ConsolidateTreasuries<'info>>,
+) -> Result<()> {
+    let remaining_accounts = ctx.remaining_accounts;
+    require!(
+        !remaining_accounts.is_empty(),
+        RaffleError::InvalidRemainingAccounts
+    );
+
+    let recipient = payout_recipient(
+        &ctx.accounts.config,
+        &ctx.accounts.payout_authority,
+        ctx.accounts.destination.as_ref(),
+    )?;
+    let mut raffle_count: u64 = 0;
+    let mut total_amount: u64 = 0;
+    let mut total_fee_amount: u64 = 0;
+    for pair in remaining_accounts.chunks(2) {
+        let [raffle_info, treasury_info] = pair else {
+            return err!(RaffleError::InvalidRemainingAccounts);
+        };
+        let mut raffle = Account::<Raffle>::try_from(raffle_info)?;
+        let mut treasury = Account::<Treasury>::try_from(treasury_info)?;
+        require_keys_eq!(
+            treasury.key(),
+            raffle.treasury,
+            RaffleError::InvalidTreasury
+        );
+        require!(
+            matches!(
+                raffle.raffle_state,
+                RaffleState::Drawn | RaffleState::Claimed | RaffleState::Expired
+            ),
+            RaffleError::RaffleNotFinalized
+        );
+        require!(
+            raffle.payment_mint.is_none(),
+            RaffleError::TokenPaymentsNotSupported
+        );
+
+        // Only pay out funds collected from ticket sales, like withdraw_from_treasury
+        let amount = treasury
+            .recognized_balance
+            .min(treasury.outstanding()?)
+            .min(withdrawable_amount(
+                &raffle,
+                &treasury,
+                &ctx.accounts.config,
+            )?);
+        if amount == 0 {
+            continue;
+        }
+        treasury.consume_withdrawal_approval(&ctx.accounts.config, amount)?;
+
+        let (payout_amount, fee_amount) =
+            split_platform_fee(&raffle, &ctx.accounts.config, amount)?;
+        let mut lamport_vault = LamportVault::new(&mut treasury, &ctx.accounts.system_program);
+        lamport_vault.withdraw(&recipient, payout_amount)?;
+        let fee_recipient = if fee_amount > 0 {
+            let fee_recipient =
+                fee_recipient(&ctx.accounts.config, ctx.accounts.fee_recipient.as_ref())?;
+            lamport_vault.withdraw(&fee_recipient, fee_amount)?;
+            Some(fee_recipient.key())
+        } else {
+            None
+        };
+
+        let event_seq = raffle.next_event_seq()?;
+        emit!(TreasuryWithdrawn {
+            raffle: raffle.key(),
+            amount: payout_amount,
+            recipient: recipient.key(),
+            fee_amount,
+            fee_recipient,
+            remaining_balance: treasury_info.lamports(),
+            raffle_state: raffle.raffle_state.clone(),
+            event_seq,
+        });
+
+        // Accounts loaded from remaining accounts are not persisted by Anchor
+        raffle.exit(&crate::ID)?;
+        treasury.exit(&crate::ID)?;
+
+        raffle_count = checked_add(raffle_count, 1)?;
+        total_amount = checked_add(total_amount, payout_amount)?;
+        total_fee_amount = checked_add(total_fee_amount, fee_amount)?;
+    }
+
+    emit!(TreasuriesConsolidated {
+        recipient: recipient.key(),
+        raffle_count,
+        total_amount,
+        total_fee_amount,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ConsolidateTreasuries<'info> {
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+        has_one = payout_authority @ RaffleError::NotPayoutAuthority,
+        constraint = !config.paused @ RaffleError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub payout_authority: SystemAccount<'info>,
+
+    /// Approved payout destination the withdrawals are paid to, required if the config
+    /// approves payout destinations
+    #[account(mut)]
+    pub destination: Option<SystemAccount<'info>>,
+
+    /// The config's fee recipient the platform fees are paid to, required if a fee is
+    /// charged
+    #[account(mut)]
+    pub fee_recipient: Option<SystemAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
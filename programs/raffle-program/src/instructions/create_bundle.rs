@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    math::checked_add,
+    state::{Bundle, Config, Raffle, RaffleState, BUNDLE_ACCOUNT_SIZE, MAX_BUNDLE_RAFFLES},
+};
+
+/// Event emitted when a bundle is created
+#[event]
+pub struct BundleCreated {
+    /// The pubkey of the bundle
+    pub bundle: Pubkey,
+    /// The raffles included in the bundle
+    pub raffles: Vec<Pubkey>,
+    /// Price of the bundle in lamports
+    pub price: u64,
+    /// Sum of the ticket prices of the included raffles
+    pub full_price: u64,
+}
+
+/// Instruction to create a bundle selling one ticket in each of several raffles for a
+/// discounted price
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `bundle_id` - Identifier of the bundle, used to derive its PDA
+/// * `price` - Price of the bundle in lamports
+///
+/// # Remaining Accounts
+/// The raffles to include in the bundle, in the order tickets are bought
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Validates caller is the management authority via config PDA
+/// 2. Ensures the bundle includes 2 to `MAX_BUNDLE_RAFFLES` distinct raffles
+/// 3. Ensures every raffle is open and doesn't split its prize among top holders, whose
+///    purchases must be tracked on a leaderboard
+/// 4. Ensures the price is greater than zero and below the sum of the ticket prices
+///
+/// # Account Validations
+/// * Bundle - New PDA initialized with seeds ["bundle", bundle_id]
+/// * Management Authority - Must match the management authority stored in config
+/// * Remaining accounts - Raffle accounts
+pub fn create_bundle<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CreateBundle<'info>>,
+    bundle_id: u64,
+    price: u64,
+) -> Result<()> {
+    let remaining_accounts = ctx.remaining_accounts;
+    require!(
+        (2..=MAX_BUNDLE_RAFFLES).contains(&remaining_accounts.len()),
+        RaffleError::InvalidBundle
+    );
+
+    let mut raffles = Vec::with_capacity(remaining_accounts.len());
+    let mut full_price: u64 = 0;
+    for raffle_info in remaining_accounts {
+        let raffle = Account::<Raffle>::try_from(raffle_info)?;
+        require!(
+            raffle.raffle_state == RaffleState::Open
+                && raffle.top_holders.is_none()
+                && !raffles.contains(&raffle.key()),
+            RaffleError::InvalidBundle
+        );
+
+        full_price = checked_add(full_price, raffle.ticket_price)?;
+        raffles.push(raffle.key());
+    }
+
+    require!(
+        price > 0 && price < full_price,
+        RaffleError::BundleNotDiscounted
+    );
+
+    let bundle = &mut ctx.accounts.bundle;
+    bundle.bundle_id = bundle_id;
+    bundle.raffles = raffles.clone();
+    bundle.price = price;
+    bundle.bump = ctx.bumps.bundle;
+
+    emit!(BundleCreated {
+        bundle: bundle.key(),
+        raffles,
+        price,
+        full_price,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(bundle_id: u64)]
+pub struct CreateBundle<'info> {
+    #[account(
+        init,
+        payer = management_authority,
+        space = BUNDLE_ACCOUNT_SIZE,
+        seeds = [
+            b"bundle",
+            bundle_id.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub bundle: Account<'info, Bundle>,
+
+    #[account(mut)]
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub system_program: Program<'info, System>,
+}
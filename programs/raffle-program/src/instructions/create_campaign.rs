@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    math::BPS_DENOMINATOR,
+    state::{Campaign, Config, CAMPAIGN_ACCOUNT_SIZE},
+    transfer::transfer_lamports,
+};
+
+/// Event emitted when a promotional campaign is created
+#[event]
+pub struct CampaignCreated {
+    /// The pubkey of the campaign
+    pub campaign: Pubkey,
+    /// Share of the ticket cost in basis points paid from the budget
+    pub discount_bps: u16,
+    /// Lamports escrowed for discounts
+    pub budget: u64,
+}
+
+/// Instruction to create a promotional campaign discounting ticket purchases, funded with
+/// its full budget upfront
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `campaign_id` - Identifier of the campaign, used to derive its PDA
+/// * `discount_bps` - Share of the ticket cost in basis points paid from the budget
+///   (1-10000)
+/// * `budget` - Lamports transferred from the management authority into the campaign
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Validates caller is the management authority via config PDA
+/// 2. Ensures the discount is between 1 and 10000 basis points and the budget is
+///    greater than zero
+///
+/// # Account Validations
+/// * Campaign - New PDA initialized with seeds ["campaign", campaign_id]
+/// * Management Authority - Must match the management authority stored in config
+///
+/// # Implementation Notes
+/// - Purchases passing the campaign are discounted until the budget is spent, after
+///   which they are charged the full price
+pub fn create_campaign(
+    ctx: Context<CreateCampaign>,
+    campaign_id: u64,
+    discount_bps: u16,
+    budget: u64,
+) -> Result<()> {
+    require!(
+        discount_bps > 0 && discount_bps as u64 <= BPS_DENOMINATOR && budget > 0,
+        RaffleError::InvalidCampaign
+    );
+
+    let campaign = &mut ctx.accounts.campaign;
+    campaign.campaign_id = campaign_id;
+    campaign.discount_bps = discount_bps;
+    campaign.budget = budget;
+    campaign.spent = 0;
+    campaign.bump = ctx.bumps.campaign;
+
+    // Escrow the budget in the campaign
+    transfer_lamports(
+        &ctx.accounts.management_authority.to_account_info(),
+        &ctx.accounts.campaign.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        budget,
+    )?;
+
+    emit!(CampaignCreated {
+        campaign: ctx.accounts.campaign.key(),
+        discount_bps,
+        budget,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(campaign_id: u64)]
+pub struct CreateCampaign<'info> {
+    #[account(
+        init,
+        payer = management_authority,
+        space = CAMPAIGN_ACCOUNT_SIZE,
+        seeds = [
+            b"campaign",
+            campaign_id.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub campaign: Account<'info, Campaign>,
+
+    #[account(mut)]
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub system_program: Program<'info, System>,
+}
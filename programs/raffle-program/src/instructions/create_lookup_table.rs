@@ -0,0 +1,173 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        address_lookup_table::{self, instruction::derive_lookup_table_address},
+        program::invoke_signed,
+        sysvar,
+    },
+};
+use anchor_spl::{associated_token, token, token_2022};
+
+use crate::{error::RaffleError, state::Config};
+
+/// Event emitted when the program's address lookup table is created
+#[event]
+pub struct LookupTableCreated {
+    /// The created address lookup table
+    pub lookup_table: Pubkey,
+    /// Recent slot the table address was derived from
+    pub recent_slot: u64,
+    /// Addresses the table was seeded with
+    pub addresses: Vec<Pubkey>,
+}
+
+/// Instruction to create the program's address lookup table, holding the config and the
+/// accounts most transactions of the program reference, so composed transactions (a
+/// purchase with a hook and token transfers) fit within the transaction size limit
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `recent_slot` - Recent slot the table address is derived from, as required by the
+///   address lookup table program
+///
+/// # Security Considerations
+/// - Only the management authority can create the table
+/// - The table is owned by a PDA of the program, so only extend_lookup_table can add
+///   addresses to it, and it can't be deactivated or closed under the program's users
+///
+/// # Account Validations
+/// * LookupTableAuthority - PDA with seeds ["lookup_table_authority"]
+/// * LookupTable - Address derived from the authority PDA and `recent_slot`
+/// * Config - PDA storing the management authority
+///
+/// # Implementation Notes
+/// - Seeds the table with the config, the rent pool, the fee recipient and insurance
+///   fund if set, the program, the system, token, Token-2022 and associated token
+///   programs, and the SlotHashes and instructions sysvars
+/// - Records the table in the config, so clients can discover it. A new table replaces
+///   the recorded one
+/// - The management authority pays the rent of the table
+pub fn create_lookup_table(ctx: Context<CreateLookupTable>, recent_slot: u64) -> Result<()> {
+    let authority = ctx.accounts.lookup_table_authority.key();
+    let (lookup_table, _) = derive_lookup_table_address(&authority, recent_slot);
+    require_keys_eq!(
+        ctx.accounts.lookup_table.key(),
+        lookup_table,
+        RaffleError::InvalidLookupTable
+    );
+
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"lookup_table_authority",
+        &[ctx.bumps.lookup_table_authority],
+    ]];
+    let (instruction, _) = address_lookup_table::instruction::create_lookup_table(
+        authority,
+        ctx.accounts.management_authority.key(),
+        recent_slot,
+    );
+    invoke_signed(
+        &instruction,
+        &[
+            ctx.accounts.lookup_table.to_account_info(),
+            ctx.accounts.lookup_table_authority.to_account_info(),
+            ctx.accounts.management_authority.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+        signer_seeds,
+    )?;
+
+    let config = &ctx.accounts.config;
+    let mut addresses = vec![
+        config.key(),
+        Pubkey::find_program_address(&[b"rent_pool"], &crate::ID).0,
+        crate::ID,
+        System::id(),
+        token::ID,
+        token_2022::ID,
+        associated_token::ID,
+        sysvar::slot_hashes::ID,
+        sysvar::instructions::ID,
+    ];
+    addresses.extend(config.fee_recipient);
+    addresses.extend(config.insurance_fund);
+    extend_table(
+        &ctx.accounts.lookup_table,
+        &ctx.accounts.lookup_table_authority,
+        ctx.bumps.lookup_table_authority,
+        &ctx.accounts.management_authority,
+        &ctx.accounts.system_program,
+        addresses.clone(),
+    )?;
+
+    ctx.accounts.config.lookup_table = Some(lookup_table);
+
+    emit!(LookupTableCreated {
+        lookup_table,
+        recent_slot,
+        addresses,
+    });
+
+    Ok(())
+}
+
+/// Appends `addresses` to the program's lookup table, signed by the authority PDA. The
+/// payer funds the rent of the grown table.
+pub(crate) fn extend_table<'info>(
+    lookup_table: &UncheckedAccount<'info>,
+    lookup_table_authority: &UncheckedAccount<'info>,
+    authority_bump: u8,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    addresses: Vec<Pubkey>,
+) -> Result<()> {
+    let instruction = address_lookup_table::instruction::extend_lookup_table(
+        lookup_table.key(),
+        lookup_table_authority.key(),
+        Some(payer.key()),
+        addresses,
+    );
+    invoke_signed(
+        &instruction,
+        &[
+            lookup_table.to_account_info(),
+            lookup_table_authority.to_account_info(),
+            payer.to_account_info(),
+            system_program.to_account_info(),
+        ],
+        &[&[b"lookup_table_authority", &[authority_bump]]],
+    )?;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateLookupTable<'info> {
+    /// Pays the rent of the lookup table
+    #[account(mut)]
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: PDA owning the program's lookup tables, holds no data
+    #[account(
+        seeds = [b"lookup_table_authority"],
+        bump,
+    )]
+    pub lookup_table_authority: UncheckedAccount<'info>,
+
+    /// CHECK: The lookup table to create, validated against the derived address in the
+    /// instruction
+    #[account(mut)]
+    pub lookup_table: UncheckedAccount<'info>,
+
+    /// CHECK: The address lookup table program
+    #[account(address = address_lookup_table::program::ID)]
+    pub address_lookup_table_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
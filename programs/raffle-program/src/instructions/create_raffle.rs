@@ -1,41 +1,87 @@
 use crate::{
     error::RaffleError,
+    instructions::set_winner_count::validate_winner_count,
+    math::{checked_add, ticket_cost, BPS_DENOMINATOR},
+    notifier::{notify_lifecycle, LifecycleEvent},
     state::{
-        raffle::{Raffle, RaffleState},
-        Config, Treasury, RAFFLE_ACCOUNT_SIZE, TREASURY_ACCOUNT_SIZE,
+        raffle::{Gate, PricingTier, Raffle, RaffleState, RandomnessMode, MAX_PRICING_TIERS},
+        require_raffle_manager, Config, CreatorBond, CreatorRole, Treasury, CLOSE_REASON_NONE, CREATOR_BOND_ACCOUNT_SIZE, MAX_TOP_HOLDERS,
+        OPERATOR_STATUS_NONE, RAFFLE_ACCOUNT_SIZE, TREASURY_ACCOUNT_SIZE,
     },
+    time::{in_seconds, now},
+    transfer::rent_reserve,
+    treasury::open_token_vault,
+};
+use anchor_lang::{
+    prelude::*,
+    solana_program::hash::hash,
+    system_program::{create_account, CreateAccount},
+};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token},
 };
-use anchor_lang::prelude::*;
 
 // Constants for validation
 const MAX_TICKET_PRICE: u64 = 100_000_000_000; // 100 SOL
 const MIN_TICKET_PRICE: u64 = 100_000_000; // 0.1 SOL
 const MAX_MIN_TICKETS: u64 = 1_000_000; // 1 million tickets
-const MAX_DURATION: i64 = 30 * 24 * 60 * 60; // 30 days in seconds
-const MIN_DURATION: i64 = 1 * 60 * 60; // 1 hour in seconds
+pub(crate) const MAX_DURATION: i64 = 30 * 24 * 60 * 60; // 30 days in seconds
+pub(crate) const MIN_DURATION: i64 = 60 * 60; // 1 hour in seconds
 
 // Valid URI prefixes
-const VALID_URI_PREFIXES: [&str; 3] = [
-    "https://",     // Standard HTTPS
-    "ipfs://",      // IPFS protocol
-    "ipfs://ipfs/", // Alternative IPFS format
+const VALID_URI_PREFIXES: [&str; 4] = [
+    "https://",         // Standard HTTPS
+    "ipfs://",          // IPFS protocol
+    "ipfs://ipfs/",     // Alternative IPFS format
+    ARWEAVE_URI_PREFIX, // Arweave permanent storage
 ];
 
+const ARWEAVE_URI_PREFIX: &str = "ar://";
+// Length of a base64url encoded 32 byte Arweave transaction id, without padding
+const ARWEAVE_TX_ID_LEN: usize = 43;
+
 /// Event emitted when a raffle is created
 #[event]
 pub struct RaffleCreated {
     /// The pubkey of the created raffle
     pub raffle: Pubkey,
-    /// The metadata URI for the raffle
+    /// The metadata URI for the raffle, or empty if the config's events are compact
     pub metadata_uri: String,
     /// Price per ticket in lamports
     pub ticket_price: u64,
     /// Minimum number of tickets required
     pub min_tickets: u64,
+    /// Maximum number of tickets that can be sold, if capped
+    pub max_tickets: Option<u64>,
+    /// The raffle's treasury collecting ticket payments
+    pub treasury: Pubkey,
     /// When the raffle ends
     pub end_time: i64,
     /// When the raffle was created
     pub creation_time: i64,
+    /// Bitfield of the `FEATURE_*` mechanics enabled for the raffle
+    pub features: u32,
+    /// Maximum number of tickets a single wallet can hold, if limited
+    pub max_tickets_per_wallet: Option<u64>,
+    /// Bulk prices by ascending quantity threshold, empty if every ticket sells at
+    /// `ticket_price`
+    pub pricing_tiers: Vec<PricingTier>,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Event emitted when the creator of a raffle deposits the bond required by the config
+#[event]
+pub struct CreatorBondDeposited {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The account that deposited the bond
+    pub depositor: Pubkey,
+    /// Bonded lamports
+    pub amount: u64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
 }
 
 /// Instruction to create a new raffle with specified parameters
@@ -43,31 +89,85 @@ pub struct RaffleCreated {
 /// # Arguments
 /// * `ctx` - The context object containing all required accounts
 /// * `metadata_uri` - URI pointing to the raffle's metadata (max 256 chars)
-/// * `ticket_price` - Price per ticket in lamports (must be > 0)
+/// * `ticket_price` - Price per ticket in lamports, or in base units of the payment mint
+///   (must be > 0)
 /// * `min_tickets` - Minimum number of tickets that must be sold (must be > 0)
 /// * `end_time` - Unix timestamp when the raffle ends (must be in future)
+/// * `max_tickets` - Optional maximum number of tickets that can be sold
+/// * `max_per_purchase` - Optional maximum number of tickets per purchase (must be > 0)
+/// * `anonymize_winner` - Whether to only publish a salted hash of the winner until they claim
+/// * `min_revenue` - Optional revenue target in lamports replacing `min_tickets` as the
+///   threshold (must be > 0 and reachable within `max_tickets`)
+/// * `collectible` - Whether ticket numbers can be claimed as collectibles after the draw
+///   (requires `max_tickets`, so the collection has a fixed supply)
+/// * `cash_prize_bps` - Optional share of the treasury in basis points paid to the winner
+///   in SOL instead of an off-chain prize (1-10000)
+/// * `top_holders` - Optional number of largest ticket holders the cash prize is split
+///   among instead of a random winner (1-10, requires `cash_prize_bps`)
+/// * `min_unique_buyers` - Optional number of distinct wallets that must buy tickets for
+///   the raffle to be drawn, so a single wallet can't meet the threshold on its own
+///   (must be > 0 and at most `max_tickets`)
+/// * `max_tickets_per_wallet` - Optional maximum number of tickets a single wallet can
+///   hold across all its purchases, so whales can't buy up the supply (must be > 0)
+/// * `pricing_tiers` - Optional schedule of up to 4 bulk prices, each charged per ticket
+///   for purchases of at least its quantity threshold (thresholds ascending from 2,
+///   prices descending below `ticket_price`)
+/// * `prize_value_usd_cents` - Optional value of the prize in US cents, published as a
+///   typed field so frontends don't parse it out of the metadata in their locale
+/// * `ticket_count_display_cap` - Optional ticket count above which frontends display the
+///   count as capped (must be > 0)
 ///
 /// # Security Considerations
 /// The instruction performs several critical checks:
-/// 1. Validates caller is the program authority via config PDA
-/// 2. Validates metadata_uri length is <= 256 characters and starts with https://, ipfs://, or ipfs://ipfs/
-/// 3. Ensures ticket_price is greater than 0 and <= 100 SOL
+/// 1. Validates caller is the program authority via config PDA, or holds a creator role
+///    granted by it
+/// 2. Validates metadata_uri length is <= 256 characters and starts with https://, ipfs://, ipfs://ipfs/
+///    or ar://, that HTTPS URIs point to a host in the config's allowlist, if it is not
+///    empty, and that Arweave URIs reference a well-formed transaction id
+/// 3. Ensures ticket_price is greater than 0 and, for raffles paid in lamports, between
+///    0.1 and 100 SOL
 /// 4. Ensures min_tickets is greater than 0 and <= 1 million
-/// 5. Verifies end_time is in the future but not more than 30 days ahead
-/// 6. Uses a PDA for treasury with proper seeds
-/// 7. Validates authority has sufficient funds for account creation
+/// 5. Ensures max_per_purchase, max_tickets_per_wallet and ticket_count_display_cap, if
+///    given, are greater than 0, and min_revenue, if given, is greater than 0 and
+///    reachable
+/// 6. Verifies end_time is in the future but not more than 30 days ahead
+/// 7. Uses a PDA for treasury with proper seeds
+/// 8. Validates authority has sufficient funds for account creation
+/// 9. Rejects cash prizes for raffles paid in an SPL token, as prizes are paid in lamports
+/// 10. Fails with ProgramPaused while the config's pause switch is on
+/// 11. Ensures the pricing schedule only ever lowers the price of larger purchases
+/// 12. Ensures the creator bond required by the config is deposited
+/// 13. Limits raffles of creators to the number of active raffles and the ticket price
+///     allowed by their role
 ///
 /// # Account Validations
 /// * Raffle - New account initialized with proper space allocation
-/// * Authority - Must be program authority stored in config account
+/// * Creator - Must be program authority stored in config account, or the holder of the
+///   creator role
+/// * CreatorRole - Optional, PDA with seeds ["creator_role", creator], required unless
+///   the creator is the management authority
 /// * Treasury - New PDA initialized with seeds ["treasury", raffle_key]
 /// * Config - PDA storing program authority
+/// * PaymentMint - Optional SPL token mint tickets are paid in, instead of lamports
+/// * TreasuryTokenAccount - Associated token account of the treasury for the payment
+///   mint, created here. Required with the payment mint, as are the token and associated
+///   token programs
+/// * CreatorBond - PDA with seeds ["creator_bond", raffle_key], created here. Required
+///   if the config requires a creator bond
 ///
 /// # Implementation Notes
-/// - Initializes raffle in Open state
+/// - Initializes raffle in Open state, or in AwaitingPrize state if the config requires
+///   the prize to be deposited before ticket sales start
 /// - Sets creation time to current timestamp
-/// - Creates treasury PDA linked to raffle
+/// - Creates treasury PDA linked to raffle, and its token account if the raffle is paid
+///   in an SPL token
+/// - Records the creator as the rent payer, so the rent of both accounts is returned to
+///   it by close_raffle even if the authority is rotated
+/// - Raffles created through a creator role record the creator, which may then update
+///   and extend them, and count against the role's active raffles until closed
+/// - Records the enabled mechanics in the raffle's feature flags
 /// - Space allocation accounts for max metadata_uri length
+#[allow(clippy::too_many_arguments)]
 pub fn create_raffle(
     ctx: Context<CreateRaffle>,
     metadata_uri: String,
@@ -75,94 +175,711 @@ pub fn create_raffle(
     end_time: i64,
     min_tickets: u64,
     max_tickets: Option<u64>,
+    max_per_purchase: Option<u64>,
+    anonymize_winner: bool,
+    min_revenue: Option<u64>,
+    collectible: bool,
+    cash_prize_bps: Option<u16>,
+    top_holders: Option<u8>,
+    min_unique_buyers: Option<u64>,
+    max_tickets_per_wallet: Option<u64>,
+    pricing_tiers: Option<Vec<PricingTier>>,
+    prize_value_usd_cents: Option<u64>,
+    ticket_count_display_cap: Option<u64>,
 ) -> Result<()> {
-    let current_time = Clock::get()?.unix_timestamp;
+    let payment_mint = ctx.accounts.payment_mint.as_ref().map(|mint| mint.key());
+    create_raffle_with_params(
+        ctx,
+        RaffleParams {
+            metadata_uri,
+            ticket_price,
+            end_time,
+            min_tickets,
+            max_tickets,
+            max_per_purchase,
+            anonymize_winner,
+            min_revenue,
+            collectible,
+            cash_prize_bps,
+            top_holders,
+            allowed_regions: 0,
+            age_restricted: false,
+            draw_authority: None,
+            priority_min_tier: 0,
+            priority_until: 0,
+            min_unique_buyers,
+            payment_mint,
+            randomness_mode: RandomnessMode::SlotHashes,
+            winner_count: 1,
+            max_tickets_per_wallet,
+            gate: None,
+            deferred_numbering: false,
+            pricing_tiers: pricing_tiers.unwrap_or_default(),
+            prize_value_usd_cents,
+            ticket_count_display_cap,
+        },
+    )
+}
 
-    // Validate inputs
-    // URI format check - must start with one of the valid prefixes
-    require!(
-        VALID_URI_PREFIXES
-            .iter()
-            .any(|prefix| metadata_uri.starts_with(prefix)),
-        RaffleError::InvalidMetadataUri
-    );
-    require!(metadata_uri.len() <= 256, RaffleError::MetadataUriTooLong);
+/// Creates a raffle with `params` through the accounts of create_raffle, limiting
+/// delegated creators to their role and depositing the creator bond.
+///
+/// Shared by create_raffle and create_raffle_with_preset, so both apply the same checks
+/// to the creator.
+pub(crate) fn create_raffle_with_params(
+    ctx: Context<CreateRaffle>,
+    params: RaffleParams,
+) -> Result<()> {
+    require_raffle_manager(
+        &ctx.accounts.config,
+        &ctx.accounts.creator,
+        Some(ctx.accounts.creator.key()),
+        ctx.accounts.creator_role.as_ref(),
+    )?;
+    let delegated = ctx.accounts.creator.key() != ctx.accounts.config.management_authority;
+    if delegated {
+        let creator_role = ctx
+            .accounts
+            .creator_role
+            .as_mut()
+            .ok_or(RaffleError::NotProgramManagementAuthority)?;
+        require!(
+            creator_role.active_raffles < creator_role.max_active_raffles,
+            RaffleError::CreatorRaffleLimitReached
+        );
+        require!(
+            params.ticket_price <= creator_role.max_ticket_price,
+            RaffleError::CreatorTicketPriceTooHigh
+        );
+        creator_role.active_raffles += 1;
+    }
 
-    // Price checks
-    require!(
-        ticket_price >= MIN_TICKET_PRICE,
-        RaffleError::TicketPriceTooLow
-    );
-    require!(
-        ticket_price <= MAX_TICKET_PRICE,
-        RaffleError::TicketPriceTooHigh
-    );
+    let rent_payer = ctx.accounts.creator.key();
+    init_raffle(
+        &mut ctx.accounts.raffle,
+        &mut ctx.accounts.treasury,
+        ctx.bumps.treasury,
+        &mut ctx.accounts.config,
+        rent_payer,
+        params,
+    )?;
+    if delegated {
+        ctx.accounts.raffle.creator = Some(rent_payer);
+    }
 
-    // Ticket count checks
-    require!(min_tickets > 0, RaffleError::MinTicketsTooLow);
-    require!(
-        min_tickets <= MAX_MIN_TICKETS,
-        RaffleError::MinTicketsTooHigh
-    );
+    deposit_creator_bond(
+        &mut ctx.accounts.raffle,
+        &ctx.accounts.config,
+        ctx.accounts.creator_bond.as_ref(),
+        &ctx.accounts.creator,
+        &ctx.accounts.system_program,
+    )?;
 
-    // Check that max tickets is greater than or equal to min tickets
-    if let Some(max_tickets) = max_tickets {
-        require!(max_tickets >= min_tickets, RaffleError::MaxTicketsTooLow);
+    // Open the treasury's token account, if tickets are paid in an SPL token
+    open_token_vault(
+        &ctx.accounts.creator,
+        &ctx.accounts.treasury.to_account_info(),
+        ctx.accounts.payment_mint.as_ref(),
+        ctx.accounts.treasury_token_account.as_ref(),
+        ctx.accounts.token_program.as_ref(),
+        ctx.accounts.associated_token_program.as_ref(),
+        &ctx.accounts.system_program,
+    )?;
+
+    notify_lifecycle(
+        &ctx.accounts.config,
+        ctx.accounts.notifier_program.as_ref(),
+        &ctx.accounts.raffle,
+        LifecycleEvent::Created,
+    )
+}
+
+/// Creates the CreatorBond PDA of a new raffle holding the bond required by the config,
+/// funded by `depositor` together with its rent. Nothing is deposited while the config
+/// requires no bond.
+///
+/// Shared by all instructions that create raffles, so no raffle can be created without
+/// the bond. The bond is created manually rather than through an `init` constraint, as
+/// it is only required while the config asks for one.
+pub(crate) fn deposit_creator_bond<'info>(
+    raffle: &mut Account<'info, Raffle>,
+    config: &Config,
+    creator_bond: Option<&UncheckedAccount<'info>>,
+    depositor: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let amount = config.creator_bond;
+    if amount == 0 {
+        return Ok(());
     }
+    let creator_bond = creator_bond.ok_or(RaffleError::CreatorBondRequired)?;
 
-    // Time checks
-    require!(
-        end_time > current_time.checked_add(MIN_DURATION).unwrap(),
-        RaffleError::EndTimeTooClose
-    );
-    require!(
-        end_time <= current_time.checked_add(MAX_DURATION).unwrap(),
-        RaffleError::DurationTooLong
+    let raffle_key = raffle.key();
+    let (address, bump) =
+        Pubkey::find_program_address(&[b"creator_bond", raffle_key.as_ref()], &crate::ID);
+    require_keys_eq!(
+        creator_bond.key(),
+        address,
+        RaffleError::InvalidCreatorBond
     );
 
+    create_account(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            CreateAccount {
+                from: depositor.to_account_info(),
+                to: creator_bond.to_account_info(),
+            },
+            &[&[b"creator_bond", raffle_key.as_ref(), &[bump]]],
+        ),
+        checked_add(
+            rent_reserve(CREATOR_BOND_ACCOUNT_SIZE)?,
+            amount,
+        )?,
+        CREATOR_BOND_ACCOUNT_SIZE as u64,
+        &crate::ID,
+    )?;
+
+    let bond = CreatorBond {
+        raffle: raffle_key,
+        depositor: depositor.key(),
+        amount,
+        bump,
+    };
+    bond.try_serialize(&mut &mut creator_bond.try_borrow_mut_data()?[..])?;
+
+    let event_seq = raffle.next_event_seq()?;
+    emit!(CreatorBondDeposited {
+        raffle: raffle_key,
+        depositor: depositor.key(),
+        amount,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+/// Parameters of a new raffle, as passed to `create_raffle` or copied by `clone_raffle`
+pub(crate) struct RaffleParams {
+    pub metadata_uri: String,
+    pub ticket_price: u64,
+    pub end_time: i64,
+    pub min_tickets: u64,
+    pub max_tickets: Option<u64>,
+    pub max_per_purchase: Option<u64>,
+    pub anonymize_winner: bool,
+    pub min_revenue: Option<u64>,
+    pub collectible: bool,
+    pub cash_prize_bps: Option<u16>,
+    pub top_holders: Option<u8>,
+    pub allowed_regions: u64,
+    pub age_restricted: bool,
+    pub draw_authority: Option<Pubkey>,
+    pub priority_min_tier: u8,
+    pub priority_until: i64,
+    pub min_unique_buyers: Option<u64>,
+    pub payment_mint: Option<Pubkey>,
+    pub randomness_mode: RandomnessMode,
+    pub winner_count: u8,
+    pub max_tickets_per_wallet: Option<u64>,
+    pub gate: Option<Gate>,
+    pub deferred_numbering: bool,
+    pub pricing_tiers: Vec<PricingTier>,
+    pub prize_value_usd_cents: Option<u64>,
+    pub ticket_count_display_cap: Option<u64>,
+}
+
+impl RaffleParams {
+    /// Parameters of an existing raffle
+    pub fn from_raffle(raffle: &Raffle) -> Self {
+        Self {
+            metadata_uri: raffle.metadata_uri.clone(),
+            ticket_price: raffle.ticket_price,
+            end_time: raffle.end_time,
+            min_tickets: raffle.min_tickets,
+            max_tickets: raffle.max_tickets,
+            max_per_purchase: raffle.max_per_purchase,
+            anonymize_winner: raffle.anonymize_winner,
+            min_revenue: raffle.min_revenue,
+            collectible: raffle.collectible,
+            cash_prize_bps: raffle.cash_prize_bps,
+            top_holders: raffle.top_holders,
+            allowed_regions: raffle.allowed_regions,
+            age_restricted: raffle.age_restricted,
+            draw_authority: raffle.draw_authority,
+            priority_min_tier: raffle.priority_min_tier,
+            priority_until: raffle.priority_until,
+            min_unique_buyers: raffle.min_unique_buyers,
+            payment_mint: raffle.payment_mint,
+            // The randomness provider is selected per raffle, along with its commitment
+            randomness_mode: match raffle.randomness_mode {
+                RandomnessMode::Provider => RandomnessMode::SlotHashes,
+                ref randomness_mode => randomness_mode.clone(),
+            },
+            winner_count: raffle.winner_count,
+            max_tickets_per_wallet: raffle.max_tickets_per_wallet,
+            gate: raffle.gate.clone(),
+            deferred_numbering: raffle.deferred_numbering,
+            pricing_tiers: raffle.pricing_tiers.clone(),
+            prize_value_usd_cents: raffle.prize_value_usd_cents,
+            ticket_count_display_cap: raffle.ticket_count_display_cap,
+        }
+    }
+}
+
+/// Validates the parameters of a new raffle and initializes the raffle and its treasury,
+/// advancing the config's raffle counter.
+///
+/// Shared by all instructions that create raffles, so every raffle passes the same
+/// checks regardless of where its parameters come from.
+pub(crate) fn init_raffle(
+    raffle: &mut Account<Raffle>,
+    treasury: &mut Account<Treasury>,
+    treasury_bump: u8,
+    config: &mut Account<Config>,
+    rent_payer: Pubkey,
+    params: RaffleParams,
+) -> Result<()> {
+    let current_time = now(config)?;
+    validate_raffle_params(&params, config, current_time)?;
+
+    let RaffleParams {
+        metadata_uri,
+        ticket_price,
+        end_time,
+        min_tickets,
+        max_tickets,
+        max_per_purchase,
+        anonymize_winner,
+        min_revenue,
+        collectible,
+        cash_prize_bps,
+        top_holders,
+        allowed_regions,
+        age_restricted,
+        draw_authority,
+        priority_min_tier,
+        priority_until,
+        min_unique_buyers,
+        payment_mint,
+        randomness_mode,
+        winner_count,
+        max_tickets_per_wallet,
+        gate,
+        deferred_numbering,
+        pricing_tiers,
+        prize_value_usd_cents,
+        ticket_count_display_cap,
+    } = params;
+
     // Set inputs from transaction data
-    ctx.accounts.raffle.metadata_uri = metadata_uri;
-    ctx.accounts.raffle.ticket_price = ticket_price;
-    ctx.accounts.raffle.min_tickets = min_tickets;
-    ctx.accounts.raffle.end_time = end_time;
-    ctx.accounts.raffle.treasury = ctx.accounts.treasury.key();
-    ctx.accounts.treasury.bump = ctx.bumps.treasury;
-    ctx.accounts.treasury.raffle = ctx.accounts.raffle.key();
-    ctx.accounts.raffle.max_tickets = max_tickets;
+    raffle.metadata_uri = metadata_uri;
+    raffle.ticket_price = ticket_price;
+    raffle.min_tickets = min_tickets;
+    raffle.end_time = end_time;
+    raffle.treasury = treasury.key();
+    treasury.bump = treasury_bump;
+    treasury.raffle = raffle.key();
+    treasury.total_collected = 0;
+    treasury.total_refunded = 0;
+    treasury.total_withdrawn = 0;
+    treasury.total_sponsored = 0;
+    treasury.total_refund_fees = 0;
+    treasury.approved_withdrawal = 0;
+    treasury.total_crank_rewards = 0;
+    treasury.total_referral_fees = 0;
+    treasury.total_referral_fees_paid = 0;
+    treasury.total_forfeited = 0;
+    treasury.recognized_balance = 0;
+    raffle.max_tickets = max_tickets;
+    raffle.max_per_purchase = max_per_purchase;
+    raffle.max_tickets_per_wallet = max_tickets_per_wallet;
+    raffle.anonymize_winner = anonymize_winner;
+    raffle.min_revenue = min_revenue;
+    raffle.collectible = collectible;
+    raffle.cash_prize_bps = cash_prize_bps;
+    raffle.top_holders = top_holders;
+    raffle.refund_fee_bps = config.refund_fee_bps;
+    // Crank rewards are paid in lamports, which token treasuries don't hold
+    raffle.crank_reward_bps = if payment_mint.is_none() {
+        config.crank_reward_bps
+    } else {
+        0
+    };
+    // Referral fees are paid in lamports at withdrawal, which token treasuries don't hold
+    // and cash prize raffles don't allow
+    raffle.referral_fee_bps = if payment_mint.is_none() && cash_prize_bps.is_none() {
+        config.referral_fee_bps
+    } else {
+        0
+    };
+    raffle.extension_count = 0;
+    raffle.extended_secs = 0;
+    raffle.refund_deadline = 0;
+    raffle.fee_bps_override = None;
+    raffle.snapshot_root = None;
+    raffle.operator_status = OPERATOR_STATUS_NONE;
+    raffle.status_note_hash = None;
+    raffle.funding_deadline = None;
+    raffle.rent_payer = rent_payer;
+    raffle.allowed_regions = allowed_regions;
+    raffle.age_restricted = age_restricted;
+    raffle.draw_authority = draw_authority;
+    raffle.priority_min_tier = priority_min_tier;
+    raffle.priority_until = priority_until;
+    raffle.min_unique_buyers = min_unique_buyers;
+    raffle.payment_mint = payment_mint;
+    raffle.randomness_mode = randomness_mode;
+    raffle.gate = gate;
+    raffle.deferred_numbering = deferred_numbering;
+    raffle.pricing_tiers = pricing_tiers;
+    raffle.tier_discounts = 0;
+    raffle.prize_value_usd_cents = prize_value_usd_cents;
+    raffle.ticket_count_display_cap = ticket_count_display_cap;
+    raffle.ticket_receipts = false;
+    raffle.arweave_tx_id = arweave_tx_id(&raffle.metadata_uri);
+    raffle.creator = None;
+    raffle.draw_delay_slots = 0;
+    raffle.draw_target_slot = 0;
+    raffle.refund_mint = None;
+    raffle.refund_rate = 0;
+    raffle.draw_value_lamports = 0;
+    raffle.draw_usd_rate = None;
+    raffle.successor = None;
+    raffle.randomness_provider = None;
+    raffle.randomness_commitment = None;
+    raffle.access_code_hash = None;
+    raffle.close_reason = CLOSE_REASON_NONE;
+    raffle.crank_reward_paid = false;
 
     // Set default values
-    ctx.accounts.raffle.current_tickets = 0;
-    ctx.accounts.raffle.creation_time = current_time;
-    ctx.accounts.raffle.raffle_state = RaffleState::Open;
-    ctx.accounts.raffle.winner_address = None;
-    ctx.accounts.raffle.winning_ticket = None;
+    raffle.current_tickets = 0;
+    raffle.reserved_tickets = 0;
+    raffle.participant_count = 0;
+    raffle.entry_count = 0;
+    raffle.numbered_entries = 0;
+    raffle.numbered_tickets = 0;
+    raffle.creation_time = current_time;
+    raffle.raffle_state = if config.require_prize_deposit {
+        RaffleState::AwaitingPrize
+    } else {
+        RaffleState::Open
+    };
+    raffle.winner_address = None;
+    raffle.winner_hash = None;
+    raffle.event_seq = 0;
+    raffle.winning_ticket = None;
+    raffle.draw_slot = None;
+    raffle.draw_slot_hash = None;
+    raffle.draw_timestamp = None;
+    raffle.min_draw_slot = 0;
+    raffle.randomness_account = None;
+    raffle.randomness_commit_slot = 0;
+    raffle.prize_escrowed = false;
+    raffle.winner_count = winner_count;
+    raffle.winning_tickets = Vec::new();
+    raffle.additional_winners = Vec::new();
+    raffle.additional_winner_hashes = Vec::new();
+    raffle.voided_ranges = Vec::new();
+    raffle.pending_reservations = 0;
+    raffle.open_slot_released = false;
+    raffle.campaign_subsidies = 0;
+    raffle.winner_data_submitted = 0;
+    raffle.prizes_claimed = 0;
+    raffle.features = raffle.enabled_features();
 
     // Increment the raffle counter
-    ctx.accounts.config.raffle_counter = ctx
-        .accounts
-        .config
-        .raffle_counter
-        .checked_add(1)
-        .ok_or(RaffleError::Overflow)?;
+    config.raffle_counter = checked_add(config.raffle_counter, 1)?;
+    config.record_raffle_opened()?;
 
     // Emit the raffle created event
+    let event_seq = raffle.next_event_seq()?;
     emit!(RaffleCreated {
-        raffle: ctx.accounts.raffle.key(),
-        metadata_uri: ctx.accounts.raffle.metadata_uri.clone(),
+        raffle: raffle.key(),
+        metadata_uri: config.event_text(raffle.metadata_uri.clone()),
         ticket_price,
         min_tickets,
+        max_tickets: raffle.max_tickets,
+        treasury: raffle.treasury,
         end_time,
         creation_time: current_time,
+        features: raffle.features,
+        max_tickets_per_wallet,
+        pricing_tiers: raffle.pricing_tiers.clone(),
+        event_seq,
     });
 
     Ok(())
 }
 
+/// Validates the parameters of a raffle against the config at `current_time`, failing
+/// with the first rule they violate
+///
+/// Shared by all instructions that create or update raffles, so every raffle passes the
+/// same checks regardless of where its parameters come from.
+pub(crate) fn validate_raffle_params(
+    params: &RaffleParams,
+    config: &Config,
+    current_time: i64,
+) -> Result<()> {
+    match raffle_param_violations(params, config, current_time).first() {
+        Some(&violation) => Err(violation.into()),
+        None => Ok(()),
+    }
+}
+
+/// Returns every rule of `validate_raffle_params` the parameters of a raffle violate, in
+/// the order they are checked
+pub(crate) fn raffle_param_violations(
+    params: &RaffleParams,
+    config: &Config,
+    current_time: i64,
+) -> Vec<RaffleError> {
+    let &RaffleParams {
+        ref metadata_uri,
+        ticket_price,
+        end_time,
+        min_tickets,
+        max_tickets,
+        max_per_purchase,
+        min_revenue,
+        collectible,
+        cash_prize_bps,
+        top_holders,
+        min_unique_buyers,
+        payment_mint,
+        winner_count,
+        max_tickets_per_wallet,
+        ref pricing_tiers,
+        ticket_count_display_cap,
+        ..
+    } = params;
+    let mut violations = Vec::new();
+    let mut check = |valid: bool, violation: RaffleError| {
+        if !valid {
+            violations.push(violation);
+        }
+    };
+
+    // URI format check - must start with one of the valid prefixes
+    check(
+        VALID_URI_PREFIXES
+            .iter()
+            .any(|prefix| metadata_uri.starts_with(prefix)),
+        RaffleError::InvalidMetadataUri,
+    );
+    check(metadata_uri.len() <= 256, RaffleError::MetadataUriTooLong);
+    // Arweave URIs must reference a well-formed transaction id, which the raffle stores
+    check(
+        !metadata_uri.starts_with(ARWEAVE_URI_PREFIX) || arweave_tx_id(metadata_uri).is_some(),
+        RaffleError::InvalidMetadataUri,
+    );
+
+    // HTTPS metadata must be hosted on an allowed domain, if the config restricts hosts
+    let allowed_hosts = &config.allowed_metadata_hosts;
+    if !allowed_hosts.is_empty() {
+        if let Some(host) = metadata_host(metadata_uri) {
+            check(
+                allowed_hosts.contains(&metadata_host_hash(host)),
+                RaffleError::MetadataHostNotAllowed,
+            );
+        }
+    }
+
+    // Price checks. The bounds are denominated in lamports, so they don't apply to
+    // raffles paid in an SPL token
+    if payment_mint.is_none() {
+        check(
+            ticket_price >= MIN_TICKET_PRICE,
+            RaffleError::TicketPriceTooLow,
+        );
+        check(
+            ticket_price <= MAX_TICKET_PRICE,
+            RaffleError::TicketPriceTooHigh,
+        );
+    } else {
+        check(ticket_price > 0, RaffleError::TicketPriceTooLow);
+        // Cash prizes are paid out of the treasury in lamports
+        check(
+            cash_prize_bps.is_none(),
+            RaffleError::TokenPaymentsNotSupported,
+        );
+    }
+
+    // Ticket count checks
+    check(min_tickets > 0, RaffleError::MinTicketsTooLow);
+    check(
+        min_tickets <= MAX_MIN_TICKETS,
+        RaffleError::MinTicketsTooHigh,
+    );
+
+    // Check that max tickets is greater than or equal to min tickets
+    if let Some(max_tickets) = max_tickets {
+        check(max_tickets >= min_tickets, RaffleError::MaxTicketsTooLow);
+    }
+
+    // A revenue target must be reachable by selling all available tickets
+    if let Some(min_revenue) = min_revenue {
+        check(min_revenue > 0, RaffleError::InvalidMinRevenue);
+        if let Some(max_tickets) = max_tickets {
+            match ticket_cost(max_tickets, ticket_price) {
+                Ok(cost) => check(cost >= min_revenue, RaffleError::MaxTicketsTooLow),
+                Err(_) => check(false, RaffleError::Overflow),
+            }
+        }
+    }
+
+    // Numbered collectibles need a fixed total supply
+    if collectible {
+        check(
+            max_tickets.is_some(),
+            RaffleError::CollectibleRequiresMaxTickets,
+        );
+    }
+
+    if let Some(cash_prize_bps) = cash_prize_bps {
+        check(
+            cash_prize_bps > 0 && cash_prize_bps as u64 <= BPS_DENOMINATOR,
+            RaffleError::InvalidCashPrizeBps,
+        );
+    }
+
+    // The split is paid out of the cash prize share
+    if let Some(top_holders) = top_holders {
+        check(
+            top_holders > 0 && top_holders <= MAX_TOP_HOLDERS && cash_prize_bps.is_some(),
+            RaffleError::InvalidTopHolders,
+        );
+    }
+
+    // Every unique buyer holds at least one ticket
+    if let Some(min_unique_buyers) = min_unique_buyers {
+        check(min_unique_buyers > 0, RaffleError::InvalidMinUniqueBuyers);
+        if let Some(max_tickets) = max_tickets {
+            check(
+                min_unique_buyers <= max_tickets,
+                RaffleError::InvalidMinUniqueBuyers,
+            );
+        }
+    }
+
+    check(
+        validate_winner_count(winner_count).is_ok(),
+        RaffleError::InvalidWinnerCount,
+    );
+
+    // A purchase limit of zero would make the raffle impossible to enter
+    if let Some(max_per_purchase) = max_per_purchase {
+        check(max_per_purchase > 0, RaffleError::InvalidMaxPerPurchase);
+    }
+    if let Some(max_tickets_per_wallet) = max_tickets_per_wallet {
+        check(
+            max_tickets_per_wallet > 0,
+            RaffleError::InvalidMaxTicketsPerWallet,
+        );
+    }
+    if let Some(ticket_count_display_cap) = ticket_count_display_cap {
+        check(
+            ticket_count_display_cap > 0,
+            RaffleError::InvalidTicketCountDisplayCap,
+        );
+    }
+
+    // Every tier must sell larger purchases at a lower price than the tier before it
+    check(
+        pricing_tiers.len() <= MAX_PRICING_TIERS,
+        RaffleError::InvalidPricingTiers,
+    );
+    let mut previous_tier = PricingTier {
+        quantity_threshold: 1,
+        price_per_ticket: ticket_price,
+    };
+    for &tier in pricing_tiers {
+        check(
+            tier.quantity_threshold > previous_tier.quantity_threshold
+                && tier.price_per_ticket > 0
+                && tier.price_per_ticket < previous_tier.price_per_ticket,
+            RaffleError::InvalidPricingTiers,
+        );
+        previous_tier = tier;
+    }
+
+    // Time checks. An end time in milliseconds is only reported as such, rather than
+    // as a raffle running for too long
+    if in_seconds(end_time) {
+        check(
+            end_time > current_time.checked_add(MIN_DURATION).unwrap(),
+            RaffleError::EndTimeTooClose,
+        );
+        check(
+            end_time <= current_time.checked_add(MAX_DURATION).unwrap(),
+            RaffleError::DurationTooLong,
+        );
+    } else {
+        check(false, RaffleError::TimestampNotInSeconds);
+    }
+
+    violations
+}
+
+/// Returns the hostname of an HTTPS URI, without userinfo and port, or `None` for other
+/// schemes
+fn metadata_host(uri: &str) -> Option<&str> {
+    let rest = uri.strip_prefix("https://")?;
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    // Everything before the last '@' is userinfo, which browsers don't navigate to
+    let host_port = authority.rsplit('@').next().unwrap_or_default();
+    Some(host_port.split(':').next().unwrap_or_default())
+}
+
+/// Returns the Arweave transaction id an `ar://` URI references, decoded from its
+/// base64url form, or `None` for other schemes and malformed ids
+pub fn arweave_tx_id(uri: &str) -> Option<[u8; 32]> {
+    let rest = uri.strip_prefix(ARWEAVE_URI_PREFIX)?;
+    // Paths into an Arweave manifest follow the transaction id
+    let encoded = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    if encoded.len() != ARWEAVE_TX_ID_LEN {
+        return None;
+    }
+
+    let mut tx_id = [0u8; 32];
+    let mut written = 0;
+    let mut buffer = 0u32;
+    let mut buffered_bits = 0;
+    for c in encoded.bytes() {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'-' => 62,
+            b'_' => 63,
+            _ => return None,
+        };
+        buffer = (buffer << 6) | value as u32;
+        buffered_bits += 6;
+        if buffered_bits >= 8 {
+            buffered_bits -= 8;
+            tx_id[written] = (buffer >> buffered_bits) as u8;
+            written += 1;
+            buffer &= (1 << buffered_bits) - 1;
+        }
+    }
+
+    // 43 characters encode 258 bits, the last 2 of which must be zero in a canonical id
+    (buffer == 0).then_some(tx_id)
+}
+
+/// Hash of a hostname as stored in the config's metadata host allowlist. Hostnames are
+/// case-insensitive, so they are lowercased before hashing.
+pub fn metadata_host_hash(host: &str) -> [u8; 32] {
+    hash(host.to_ascii_lowercase().as_bytes()).to_bytes()
+}
+
 #[derive(Accounts)]
 pub struct CreateRaffle<'info> {
     #[account(
         init,
-        payer = management_authority,
+        payer = creator,
         space = RAFFLE_ACCOUNT_SIZE,
         seeds = [
             b"raffle",
@@ -172,12 +889,13 @@ pub struct CreateRaffle<'info> {
     )]
     pub raffle: Account<'info, Raffle>,
 
+    /// The management authority, or a wallet holding a creator role
     #[account(mut)]
-    pub management_authority: Signer<'info>,
+    pub creator: Signer<'info>,
 
     #[account(
         init,
-        payer = management_authority,
+        payer = creator,
         space = TREASURY_ACCOUNT_SIZE,
         seeds = [
             b"treasury",
@@ -192,9 +910,93 @@ pub struct CreateRaffle<'info> {
         mut,
         seeds = [b"config"],
         bump = config.bump,
-        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+        constraint = !config.paused @ RaffleError::ProgramPaused,
     )]
     pub config: Account<'info, Config>,
 
     pub system_program: Program<'info, System>,
+
+    /// Creator role of the signer, required unless the signer is the management
+    /// authority. Validated against the signer in the instruction.
+    #[account(mut)]
+    pub creator_role: Option<Account<'info, CreatorRole>>,
+
+    /// Optional SPL token mint tickets are paid in. Tickets are paid in lamports if omitted
+    pub payment_mint: Option<Account<'info, Mint>>,
+
+    /// Associated token account of the treasury for the payment mint, required if a
+    /// payment mint is given
+    /// CHECK: Created by the associated token program, which only accepts the address
+    /// derived from the treasury and the mint
+    #[account(mut)]
+    pub treasury_token_account: Option<UncheckedAccount<'info>>,
+
+    /// Required if a payment mint is given
+    pub token_program: Option<Program<'info, Token>>,
+
+    /// Required if a payment mint is given
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+
+    /// Bond of the raffle's creator, required if the config requires a creator bond.
+    /// PDA with seeds ["creator_bond", raffle_key], validated and created in the
+    /// instruction.
+    /// CHECK: The address is checked against the PDA derived from the raffle
+    #[account(mut)]
+    pub creator_bond: Option<UncheckedAccount<'info>>,
+    /// Program notified of the raffle's creation, required if the config registers one
+    /// CHECK: Validated against the config's notifier program in the instruction
+    pub notifier_program: Option<UncheckedAccount<'info>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metadata_host_strips_path_userinfo_and_port() {
+        assert_eq!(
+            metadata_host("https://www.example.org/raffle.json"),
+            Some("www.example.org")
+        );
+        assert_eq!(metadata_host("https://example.org"), Some("example.org"));
+        assert_eq!(
+            metadata_host("https://example.org:8443?id=1"),
+            Some("example.org")
+        );
+        assert_eq!(
+            metadata_host("https://example.org@phishing.example/x#y"),
+            Some("phishing.example")
+        );
+        assert_eq!(metadata_host("ipfs://bafybeigdyrzt"), None);
+    }
+
+    #[test]
+    fn arweave_tx_id_decodes_canonical_ids() {
+        let zeros = "A".repeat(ARWEAVE_TX_ID_LEN);
+        assert_eq!(arweave_tx_id(&format!("ar://{zeros}")), Some([0; 32]));
+
+        let mut last_byte_one = [0; 32];
+        last_byte_one[31] = 1;
+        let id = format!("{}E", "A".repeat(ARWEAVE_TX_ID_LEN - 1));
+        assert_eq!(
+            arweave_tx_id(&format!("ar://{id}/metadata.json")),
+            Some(last_byte_one)
+        );
+
+        // Trailing bits set, wrong length, invalid characters and other schemes
+        let id = format!("{}B", "A".repeat(ARWEAVE_TX_ID_LEN - 1));
+        assert_eq!(arweave_tx_id(&format!("ar://{id}")), None);
+        assert_eq!(arweave_tx_id(&format!("ar://{zeros}A")), None);
+        let id = format!("{}+", "A".repeat(ARWEAVE_TX_ID_LEN - 1));
+        assert_eq!(arweave_tx_id(&format!("ar://{id}")), None);
+        assert_eq!(arweave_tx_id(&format!("https://{zeros}")), None);
+    }
+
+    #[test]
+    fn metadata_host_hash_ignores_case() {
+        assert_eq!(
+            metadata_host_hash("WWW.Example.org"),
+            metadata_host_hash("www.example.org")
+        );
+    }
 }
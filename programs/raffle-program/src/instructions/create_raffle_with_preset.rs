@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    instructions::create_raffle::{create_raffle_with_params, CreateRaffle, RaffleParams},
+    state::raffle::RandomnessMode,
+    time::now,
+};
+
+/// Instruction to create a routine raffle from terms registered in the config with
+/// set_raffle_preset, so operators only pass the metadata and the preset's name
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts, the same as
+///   create_raffle's
+/// * `metadata_uri` - URI pointing to the raffle's metadata
+/// * `preset` - Name of the preset, UTF-8 padded with zero bytes
+///
+/// # Errors
+/// * `RafflePresetNotFound` - If no preset is registered under `preset`
+///
+/// # Security Considerations
+/// - Applies the same checks to the creator and the raffle's terms as create_raffle,
+///   including the limits of delegated creator roles
+///
+/// # Implementation Notes
+/// - The raffle ends the preset's duration after its creation, and takes its ticket
+///   price and limits from the preset. All other options take create_raffle's defaults
+/// - The preset's terms are copied into the raffle, so later changes to the preset
+///   don't affect it
+pub fn create_raffle_with_preset(
+    ctx: Context<CreateRaffle>,
+    metadata_uri: String,
+    preset: [u8; 16],
+) -> Result<()> {
+    let terms = ctx.accounts.config.raffle_preset(&preset)?;
+    let end_time = now(&ctx.accounts.config)?.saturating_add(terms.duration_secs);
+    let payment_mint = ctx.accounts.payment_mint.as_ref().map(|mint| mint.key());
+
+    create_raffle_with_params(
+        ctx,
+        RaffleParams {
+            metadata_uri,
+            ticket_price: terms.ticket_price,
+            end_time,
+            min_tickets: terms.min_tickets,
+            max_tickets: terms.max_tickets,
+            max_per_purchase: None,
+            anonymize_winner: false,
+            min_revenue: None,
+            collectible: false,
+            cash_prize_bps: None,
+            top_holders: None,
+            allowed_regions: 0,
+            age_restricted: false,
+            draw_authority: None,
+            priority_min_tier: 0,
+            priority_until: 0,
+            min_unique_buyers: None,
+            payment_mint,
+            randomness_mode: RandomnessMode::SlotHashes,
+            winner_count: 1,
+            max_tickets_per_wallet: None,
+            gate: None,
+            deferred_numbering: false,
+            pricing_tiers: Vec::new(),
+            prize_value_usd_cents: None,
+            ticket_count_display_cap: None,
+        },
+    )
+}
@@ -0,0 +1,182 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, Token, TokenAccount, Transfer},
+};
+
+use crate::{
+    error::RaffleError,
+    state::{Config, PrizeVault, Raffle, RaffleState, PRIZE_VAULT_ACCOUNT_SIZE},
+    transfer::transfer_lamports,
+    treasury::open_token_vault,
+};
+
+/// Event emitted when a prize is deposited into escrow
+#[event]
+pub struct PrizeDeposited {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The prize value escrowed in lamports, or in base units of the prize mint
+    pub amount: u64,
+    /// Mint of the escrowed SPL token prize, or None for a SOL prize
+    pub prize_mint: Option<Pubkey>,
+    /// Whether the deposit opened the raffle for ticket sales
+    pub opened: bool,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to escrow the prize of a raffle
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `amount` - The prize value in lamports, or in base units of the prize mint, to
+///   escrow (must be > 0). NFT prizes escrow an amount of 1
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Only the management authority can deposit the prize
+/// 2. Ensures the raffle is awaiting its prize or still open
+/// 3. The prize vault is a PDA with seeds ["prize_vault", raffle_key], so only a single
+///    deposit can be made per raffle
+///
+/// # Account Validations
+/// * Raffle - Must be in AwaitingPrize or Open state
+/// * PrizeVault - New PDA initialized for this raffle
+/// * Config - PDA storing the management authority
+/// * PrizeMint - Optional mint of an SPL token or NFT prize. The prize is escrowed in
+///   lamports if omitted
+/// * SourceTokenAccount - Token account of the management authority holding the prize,
+///   required with the prize mint
+/// * VaultTokenAccount - Associated token account of the prize vault for the prize mint,
+///   created here. Required with the prize mint, as are the token and associated token
+///   programs
+///
+/// # Implementation Notes
+/// - Moves a raffle in AwaitingPrize state to Open, allowing ticket sales
+/// - Open raffles may also escrow their prize voluntarily
+/// - The winner claims the escrowed prize with claim_prize once the raffle is drawn
+/// - The escrow can be released with release_prize if the raffle expires
+pub fn deposit_prize(ctx: Context<DepositPrize>, amount: u64) -> Result<()> {
+    require!(amount > 0, RaffleError::InvalidPrizeAmount);
+
+    let opened = match ctx.accounts.raffle.raffle_state {
+        RaffleState::AwaitingPrize => true,
+        RaffleState::Open => false,
+        _ => return err!(RaffleError::RaffleNotOpen),
+    };
+
+    let prize_mint = ctx.accounts.prize_mint.as_ref().map(|mint| mint.key());
+    if prize_mint.is_some() {
+        let (Some(source_token_account), Some(vault_token_account), Some(token_program), Some(_)) = (
+            &ctx.accounts.source_token_account,
+            &ctx.accounts.vault_token_account,
+            &ctx.accounts.token_program,
+            &ctx.accounts.associated_token_program,
+        ) else {
+            return err!(RaffleError::InvalidPrizeMint);
+        };
+
+        // Token prizes are held by the vault's associated token account
+        open_token_vault(
+            &ctx.accounts.management_authority,
+            &ctx.accounts.prize_vault.to_account_info(),
+            ctx.accounts.prize_mint.as_ref(),
+            ctx.accounts.vault_token_account.as_ref(),
+            ctx.accounts.token_program.as_ref(),
+            ctx.accounts.associated_token_program.as_ref(),
+            &ctx.accounts.system_program,
+        )?;
+        token::transfer(
+            CpiContext::new(
+                token_program.to_account_info(),
+                Transfer {
+                    from: source_token_account.to_account_info(),
+                    to: vault_token_account.to_account_info(),
+                    authority: ctx.accounts.management_authority.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+    } else {
+        transfer_lamports(
+            &ctx.accounts.management_authority.to_account_info(),
+            &ctx.accounts.prize_vault.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            amount,
+        )?;
+    }
+
+    let prize_vault = &mut ctx.accounts.prize_vault;
+    prize_vault.raffle = ctx.accounts.raffle.key();
+    prize_vault.amount = amount;
+    prize_vault.bump = ctx.bumps.prize_vault;
+    prize_vault.prize_mint = prize_mint;
+
+    if opened {
+        ctx.accounts.raffle.raffle_state = RaffleState::Open;
+    }
+    ctx.accounts.raffle.prize_escrowed = true;
+
+    let event_seq = ctx.accounts.raffle.next_event_seq()?;
+    emit!(PrizeDeposited {
+        raffle: ctx.accounts.raffle.key(),
+        amount,
+        prize_mint,
+        opened,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct DepositPrize<'info> {
+    #[account(mut)]
+    pub raffle: Account<'info, Raffle>,
+
+    #[account(
+        init,
+        payer = management_authority,
+        space = PRIZE_VAULT_ACCOUNT_SIZE,
+        seeds = [
+            b"prize_vault",
+            raffle.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub prize_vault: Account<'info, PrizeVault>,
+
+    #[account(mut)]
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Optional mint of an SPL token or NFT prize. The prize is escrowed in lamports if
+    /// omitted
+    pub prize_mint: Option<Account<'info, Mint>>,
+
+    /// Token account the prize is transferred from, required with the prize mint. The
+    /// token program ensures the management authority may spend from it
+    #[account(mut)]
+    pub source_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Associated token account of the prize vault for the prize mint, required with it
+    /// CHECK: Created by the associated token program, which only accepts the address
+    /// derived from the prize vault and the mint
+    #[account(mut)]
+    pub vault_token_account: Option<UncheckedAccount<'info>>,
+
+    /// Required with the prize mint
+    pub token_program: Option<Program<'info, Token>>,
+
+    /// Required with the prize mint
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+}
@@ -5,9 +5,43 @@ use arrayref::array_ref;
 
 use crate::{
     error::RaffleError,
-    state::raffle::{Raffle, RaffleState},
+    notifier::{notify_lifecycle, LifecycleEvent},
+    rand::{distinct_in_range, mix, unbiased_range},
+    instructions::{finalize_raffle::pay_crank_reward, set_winner::record_winner},
+    state::{
+        entry::Entry,
+        raffle::{Raffle, RaffleState, RandomnessMode},
+        require_keeper, Config, Keeper, Treasury,
+    },
+    time::now,
 };
 
+// 8 (entry count) + 8 (slot) + 32 (hash) bytes of the first SlotHashes entry
+const SLOT_HASHES_FIRST_ENTRY_END: usize = 8 + 8 + 32;
+// 8 (slot) + 32 (hash) bytes of every SlotHashes entry
+const SLOT_HASHES_ENTRY_LEN: usize = 8 + 32;
+/// Number of recent slots the SlotHashes sysvar holds the hashes of
+pub(crate) const SLOT_HASHES_MAX_ENTRIES: u64 = 512;
+
+/// Event emitted when the winning tickets of a raffle are drawn from the SlotHashes sysvar
+#[event]
+pub struct WinningTicketDrawn {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The drawn winning tickets, in prize order
+    pub winning_tickets: Vec<u64>,
+    /// The slot whose hash was used as entropy
+    pub draw_slot: u64,
+    /// The hash of `draw_slot`
+    pub draw_slot_hash: [u8; 32],
+    /// The unix timestamp used as entropy
+    pub draw_timestamp: i64,
+    /// Why the raffle left the Open state, one of the `CLOSE_REASON_*` values
+    pub close_reason: u8,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
 /// Draws a winning ticket for a raffle using on-chain randomness from block hashes.
 /// This function selects a winner in a cryptographically fair way without centralized
 /// control by leveraging Solana's on-chain entropy sources.
@@ -15,120 +49,384 @@ use crate::{
 /// Execution requirements:
 /// 1. The raffle must be in Open state
 /// 2. The raffle end time must have passed
-/// 3. The minimum ticket threshold must be met
+/// 3. The minimum ticket threshold and unique buyer requirement must be met, and at
+///    least as many tickets must be sold as the raffle has winners
+/// 4. The caller must be the raffle's draw authority, if it has one, or otherwise a
+///    registered keeper, if the config restricts cranks to keepers
+/// 5. The raffle must not require verifiable randomness, which is drawn with
+///    request_randomness and settle_randomness instead
+/// 6. No reservations may be pending and, if the raffle defers numbering,
+///    finalize_numbering must have numbered every entry
+/// 7. If the raffle delays its draw slot, commit_draw_slot must have committed the slot
+///    and the slot must have passed
+/// 8. The program must not be paused
 ///
 /// The randomness is generated with these steps:
-/// 1. Extract entropy from the SlotHashes sysvar
+/// 1. Extract entropy from the SlotHashes sysvar, from the latest slot or, if the
+///    raffle delays its draw slot, from the first slot produced at or after the
+///    committed draw slot
 /// 2. Combine multiple entropy sources (block hash and current timestamp)
 /// 3. Apply cryptographic mixing
-/// 4. Map the result to a ticket number without bias
+/// 4. Map the result to a held ticket without bias, rerolling further tickets for
+///    raffles with multiple winners until they are all distinct
+/// 5. Map the held tickets to their ticket numbers, skipping the voided ranges of
+///    released reservations
 ///
 /// After execution:
-/// - The winning ticket number is stored in the raffle account
+/// - The winning ticket numbers are stored in the raffle account in prize order
+/// - The slot, slot hash and timestamp used as entropy are stored in the raffle account,
+///   so the draw can be replayed after the SlotHashes sysvar has rotated
 /// - The raffle state is changed to Drawing
+/// - The winning tickets and entropy inputs are recorded in the WinningTicketDrawn event
+/// - The winners whose entries were passed as remaining accounts are set in prize order,
+///   see set_winner, moving the raffle to Drawn state once all winners are set
+/// - The raffle's crank reward is paid to the cranker, if one signed
 ///
 /// # Arguments
 /// * `ctx` - The context object containing:
 ///   - `raffle`: The mutable raffle account being drawn
 ///   - `recent_slothashes`: The SlotHashes sysvar containing block hashes (manually validated)
+///   - `keeper`, `keeper_registration`: The calling keeper and its registration, if
+///     cranks are restricted
+///   - `draw_authority`: The raffle's draw authority, if it has one
+///   - `treasury`: The raffle's treasury, paying the crank reward
+///   - `cranker`: The account receiving the crank reward, if any
+///   - remaining accounts: Entries of the raffle expected to hold winning tickets, in
+///     any order
 ///
 /// # Errors
+/// - `ProgramPaused` if the config's pause switch is on
+/// - `NotDrawAuthority` if the raffle has a draw authority that did not sign
+/// - `KeeperNotRegistered` if cranks are restricted and the caller is not a keeper
 /// - `RaffleNotOpen` if the raffle is not in Open state
+/// - `SlotHashDrawDisabled` if the raffle must be drawn with verifiable randomness
 /// - `RaffleNotEnded` if the raffle end time hasn't been reached
-/// - `InsufficientTickets` if minimum ticket threshold not met
+/// - `InsufficientUniqueBuyers` if too few distinct wallets bought tickets
+/// - `InsufficientTickets` if minimum ticket threshold not met, or fewer tickets were
+///   sold than the raffle has winners
+/// - `NumberingNotFinalized` if the raffle defers numbering and finalize_numbering has
+///   not numbered every entry
 /// - `InvalidSlotHashesAccount` if the provided SlotHashes account is invalid
+/// - `DrawSlotNotCommitted` if the raffle delays its draw slot and commit_draw_slot has
+///   not committed it
+/// - `DrawSlotNotReached` if the committed draw slot has not passed yet
+/// - `DrawSlotExpired` if the committed draw slot rotated out of the SlotHashes sysvar,
+///   in which case a new slot has to be committed
+/// - `StaleDrawEntropy` if the latest slot hash predates the last draw reset
+/// - `EntryRaffleMismatch` if a passed entry belongs to another raffle
 /// - `Overflow` if arithmetic overflow occurs during random number generation
-pub fn draw_winning_ticket(ctx: Context<DrawWinningTicket>) -> Result<()> {
-    // Manually validate the recent_slothashes account
-    let pubkey_matches = Pubkey::from_str("SysvarS1otHashes111111111111111111111111111")
-        .or(Err(RaffleError::InvalidSlotHashesAccount))?
-        .eq(&ctx.accounts.recent_slothashes.key());
-    require!(pubkey_matches, RaffleError::InvalidSlotHashesAccount);
+pub fn draw_winning_ticket<'info>(
+    ctx: Context<'_, '_, 'info, 'info, DrawWinningTicket<'info>>,
+) -> Result<()> {
+    authorize_draw(
+        &ctx.accounts.raffle,
+        &ctx.accounts.config,
+        ctx.accounts.keeper.as_ref(),
+        ctx.accounts.keeper_registration.as_ref(),
+        ctx.accounts.draw_authority.as_ref(),
+    )?;
 
-    let recent_slothashes = &ctx.accounts.recent_slothashes;
-    let data = recent_slothashes.data.borrow();
+    let now = now(&ctx.accounts.config)?;
+    draw_with_slot_hashes(&mut ctx.accounts.raffle, &ctx.accounts.recent_slothashes, now)?;
+
+    resolve_winners(&mut ctx.accounts.raffle, ctx.remaining_accounts, now)?;
+
+    notify_lifecycle(
+        &ctx.accounts.config,
+        ctx.accounts.notifier_program.as_ref(),
+        &ctx.accounts.raffle,
+        LifecycleEvent::Drawn,
+    )?;
 
-    // Extract entropy from SlotHashes data
-    let chunk1 = array_ref![data, 12, 8];
-    let chunk2 = if data.len() >= 28 {
-        // Get second 8-byte block if available
-        array_ref![data, 20, 8]
+    pay_crank_reward(
+        &mut ctx.accounts.raffle,
+        &mut ctx.accounts.treasury,
+        &ctx.accounts.system_program,
+        ctx.accounts.cranker.as_ref(),
+    )
+}
+
+/// Draws the winning tickets of a raffle from the latest SlotHashes entry at `now`,
+/// moving it to Drawing state and emitting WinningTicketDrawn.
+///
+/// Shared by draw_winning_ticket and finalize_raffle, which validate that the raffle
+/// can be drawn before calling it.
+pub(crate) fn draw_with_slot_hashes(
+    raffle: &mut Account<Raffle>,
+    recent_slothashes: &AccountInfo,
+    now: i64,
+) -> Result<()> {
+    let (draw_slot, draw_slot_hash) = if raffle.draw_delay_slots > 0 {
+        require!(
+            raffle.draw_target_slot > 0,
+            RaffleError::DrawSlotNotCommitted
+        );
+        read_slot_hash_at(recent_slothashes, raffle.draw_target_slot)?
     } else {
-        // Otherwise use the first block again
-        chunk1
+        read_latest_slot_hash(recent_slothashes)?
     };
 
-    let hash_value1 = u64::from_le_bytes(*chunk1);
-    let hash_value2 = u64::from_le_bytes(*chunk2);
-    let clock = Clock::get()?;
-    let timestamp = clock.unix_timestamp as u64;
+    // A reset draw must not reuse the entropy of the draw it replaced
+    require!(
+        draw_slot >= raffle.min_draw_slot,
+        RaffleError::StaleDrawEntropy
+    );
 
-    // Combine entropy sources through cryptographic mixing
-    let mut mixed_value = mix(hash_value1, timestamp);
-    mixed_value = mix(mixed_value, hash_value2);
+    // Map the entropy inputs to distinct held tickets without statistical bias
+    let winning_tickets = raffle.held_ticket_numbers(derive_winning_tickets(
+        draw_slot,
+        &draw_slot_hash,
+        now,
+        raffle.current_tickets,
+        raffle.winner_count,
+    )?)?;
 
-    // Map the random value to a ticket number without statistical bias
-    let winning_ticket = unbiased_range(mixed_value, ctx.accounts.raffle.current_tickets)?;
+    // Store winning tickets along with the entropy inputs and update state
+    raffle.winning_ticket = winning_tickets.first().copied();
+    raffle.winning_tickets = winning_tickets.clone();
+    raffle.draw_slot = Some(draw_slot);
+    raffle.draw_slot_hash = Some(draw_slot_hash);
+    raffle.draw_timestamp = Some(now);
+    raffle.raffle_state = RaffleState::Drawing;
+    raffle.close_reason = raffle.draw_close_reason(now);
 
-    // Store winning ticket and update state
-    ctx.accounts.raffle.winning_ticket = Some(winning_ticket);
-    ctx.accounts.raffle.raffle_state = RaffleState::Drawing;
+    let event_seq = raffle.next_event_seq()?;
+    emit!(WinningTicketDrawn {
+        raffle: raffle.key(),
+        winning_tickets,
+        draw_slot,
+        draw_slot_hash,
+        draw_timestamp: now,
+        close_reason: raffle.close_reason,
+        event_seq,
+    });
 
     Ok(())
 }
 
-/// Cryptographic mixing function with strong avalanche properties
-/// Each bit in the output has a ~50% chance of flipping when any input bit changes.
-/// Based on splitmix64 algorithm used in high-quality PRNGs.
-fn mix(a: u64, b: u64) -> u64 {
-    let mut z = a.wrapping_add(b);
+/// Sets the winners of a freshly drawn raffle from the entries passed with the draw, so
+/// the winners are resolved in the same transaction as the draw and can't be delayed.
+///
+/// Winners are set in prize order, stopping at the first prize whose winning entry was
+/// not passed; the remaining winners are set with set_winner. Raffles issuing ticket
+/// receipts are won by the receipt holders, so their winners can only be set with
+/// set_winner. Winners of anonymized raffles are set with set_winner too, which reads
+/// their commitments.
+fn resolve_winners<'info>(
+    raffle: &mut Account<'info, Raffle>,
+    entry_infos: &'info [AccountInfo<'info>],
+    now: i64,
+) -> Result<()> {
+    if entry_infos.is_empty() {
+        return Ok(());
+    }
+    require!(!raffle.ticket_receipts, RaffleError::TicketReceiptRequired);
+    require!(
+        !raffle.anonymize_winner,
+        RaffleError::WinnerTicketBalanceRequired
+    );
+
+    let mut entries = Vec::with_capacity(entry_infos.len());
+    for entry_info in entry_infos {
+        let entry = Account::<Entry>::try_from(entry_info)?;
+        require_keys_eq!(
+            entry.raffle,
+            raffle.key(),
+            RaffleError::EntryRaffleMismatch
+        );
+        entries.push(entry);
+    }
 
-    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
-    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
-    z = z ^ (z >> 31);
+    for winner_index in 0..raffle.winner_count {
+        let winning_ticket = raffle.winning_tickets[winner_index as usize];
+        let Some(entry) = entries.iter().find(|entry| {
+            winning_ticket >= entry.ticket_start_index
+                && winning_ticket - entry.ticket_start_index < entry.ticket_count
+        }) else {
+            break;
+        };
+        record_winner(
+            raffle,
+            entry,
+            entry.owner,
+            None,
+            winner_index,
+            None,
+            now,
+        )?;
+    }
 
-    z
+    Ok(())
 }
 
-/// Maps a random number to a range without introducing statistical bias
-/// Standard modulo operations can bias results when the range isn't a power of 2.
-/// This function uses specialized techniques based on range size to ensure fairness.
-fn unbiased_range(x: u64, range: u64) -> Result<u64> {
-    if range == 0 {
-        return Err(RaffleError::Overflow.into());
+/// Ensures the caller may draw the raffle. A raffle's draw authority replaces the keepers
+/// as the only account able to draw; otherwise the caller must be a registered keeper if
+/// the config restricts cranks to keepers.
+pub(crate) fn authorize_draw(
+    raffle: &Raffle,
+    config: &Config,
+    keeper: Option<&Signer>,
+    keeper_registration: Option<&Account<Keeper>>,
+    draw_authority: Option<&Signer>,
+) -> Result<()> {
+    match raffle.draw_authority {
+        Some(expected) => {
+            let signer = draw_authority.map(|signer| signer.key());
+            require!(signer == Some(expected), RaffleError::NotDrawAuthority);
+            Ok(())
+        }
+        None => require_keeper(config, keeper, keeper_registration),
     }
+}
 
-    // If range is a power of 2, we can use a simple mask which is unbiased
-    if range.is_power_of_two() {
-        return Ok(x & (range - 1));
-    }
+/// Reads the most recent slot and its hash from the SlotHashes sysvar, after
+/// validating that the account is the sysvar.
+pub(crate) fn read_latest_slot_hash(recent_slothashes: &AccountInfo) -> Result<(u64, [u8; 32])> {
+    require_slot_hashes(recent_slothashes)?;
+    let data = recent_slothashes.data.borrow();
 
-    // For small ranges, simple modulo is fine as bias is minimal
-    if range <= 256 {
-        return Ok(x % range);
+    // The sysvar is laid out as a u64 entry count followed by (slot, hash) pairs,
+    // so we need at least one full entry to record the entropy inputs
+    require!(
+        data.len() >= SLOT_HASHES_FIRST_ENTRY_END,
+        RaffleError::InvalidSlotHashesAccount
+    );
+    let slot = u64::from_le_bytes(*array_ref![data, 8, 8]);
+    let slot_hash = *array_ref![data, 16, 32];
+
+    Ok((slot, slot_hash))
+}
+
+/// Reads the first slot produced at or after `target_slot` and its hash from the
+/// SlotHashes sysvar, after validating that the account is the sysvar. Skipped slots
+/// have no hash, so the draw falls through to the next produced slot.
+pub(crate) fn read_slot_hash_at(
+    recent_slothashes: &AccountInfo,
+    target_slot: u64,
+) -> Result<(u64, [u8; 32])> {
+    require_slot_hashes(recent_slothashes)?;
+    let data = recent_slothashes.data.borrow();
+    require!(data.len() >= 8, RaffleError::InvalidSlotHashesAccount);
+    let entry_count = (u64::from_le_bytes(*array_ref![data, 0, 8]) as usize)
+        .min((data.len() - 8) / SLOT_HASHES_ENTRY_LEN);
+
+    // Entries are ordered from the latest slot, so the last entry at or after the target
+    // is the first slot produced since
+    let mut found = None;
+    let mut passed_target = false;
+    for index in 0..entry_count {
+        let offset = 8 + index * SLOT_HASHES_ENTRY_LEN;
+        let slot = u64::from_le_bytes(*array_ref![data, offset, 8]);
+        if slot < target_slot {
+            passed_target = true;
+            break;
+        }
+        found = Some((slot, *array_ref![data, offset + 8, 32]));
     }
 
-    // Find threshold value to ensure unbiased selection
-    let threshold = u64::MAX - (u64::MAX % range);
+    let (slot, slot_hash) = found.ok_or(RaffleError::DrawSlotNotReached)?;
+    // Without an older entry, the first slot produced may have rotated out already
+    require!(
+        passed_target || slot == target_slot,
+        RaffleError::DrawSlotExpired
+    );
+
+    Ok((slot, slot_hash))
+}
+
+/// Ensures the account is the SlotHashes sysvar, which Anchor can't deserialize
+fn require_slot_hashes(recent_slothashes: &AccountInfo) -> Result<()> {
+    let pubkey_matches = Pubkey::from_str("SysvarS1otHashes111111111111111111111111111")
+        .or(Err(RaffleError::InvalidSlotHashesAccount))?
+        .eq(&recent_slothashes.key());
+    require!(pubkey_matches, RaffleError::InvalidSlotHashesAccount);
+
+    Ok(())
+}
 
-    // Use rejection sampling with a limit on computational cost
-    let mut value = x;
+/// Derives the winning ticket from the entropy inputs recorded on the raffle.
+/// This is shared by the on-chain draw and off-chain replay tooling, so historical
+/// draws can be verified with exactly the same logic that produced them.
+///
+/// # Arguments
+/// * `slot` - The most recent slot in the SlotHashes sysvar at draw time
+/// * `slot_hash` - The hash of `slot`
+/// * `timestamp` - The unix timestamp at draw time
+/// * `ticket_count` - The number of held tickets to draw from, mapped to ticket numbers
+///   with `Raffle::held_ticket_numbers`
+pub fn derive_winning_ticket(
+    slot: u64,
+    slot_hash: &[u8; 32],
+    timestamp: i64,
+    ticket_count: u64,
+) -> Result<u64> {
+    // Map the random value to a ticket number without statistical bias
+    unbiased_range(slot_hash_entropy(slot, slot_hash, timestamp), ticket_count)
+}
 
-    // Cap iterations to ensure reasonable gas costs
-    const MAX_ATTEMPTS: u8 = 3;
+/// Derives the distinct winning tickets of a raffle with `winner_count` winners from the
+/// entropy inputs recorded on the raffle, in prize order. The first ticket is the one
+/// `derive_winning_ticket` derives.
+pub fn derive_winning_tickets(
+    slot: u64,
+    slot_hash: &[u8; 32],
+    timestamp: i64,
+    ticket_count: u64,
+    winner_count: u8,
+) -> Result<Vec<u64>> {
+    distinct_in_range(
+        slot_hash_entropy(slot, slot_hash, timestamp),
+        ticket_count,
+        winner_count as usize,
+    )
+}
 
-    for i in 0..MAX_ATTEMPTS {
-        // If value is below threshold, we can use modulo safely
-        if value < threshold {
-            return Ok(value % range);
-        }
+/// Combines the entropy inputs of a SlotHashes draw into a single random value
+pub(crate) fn slot_hash_entropy(slot: u64, slot_hash: &[u8; 32], timestamp: i64) -> u64 {
+    // Rebuild the first SlotHashes entry as it is laid out in the sysvar
+    let mut slot_entry = [0u8; 40];
+    slot_entry[..8].copy_from_slice(&slot.to_le_bytes());
+    slot_entry[8..].copy_from_slice(slot_hash);
 
-        // Try a new value with additional mixing
-        value = mix(value, value.wrapping_add(i as u64 + 1));
-    }
+    // Extract entropy from the entry, matching sysvar offsets 12..20 and 20..28
+    let hash_value1 = u64::from_le_bytes(*array_ref![slot_entry, 4, 8]);
+    let hash_value2 = u64::from_le_bytes(*array_ref![slot_entry, 12, 8]);
+
+    // Combine entropy sources through cryptographic mixing
+    let mixed_value = mix(hash_value1, timestamp as u64);
+    mix(mixed_value, hash_value2)
+}
+
+/// Derives the winning ticket from a revealed Switchboard randomness value, for raffles
+/// drawn with verifiable randomness. Shared with off-chain replay tooling like
+/// `derive_winning_ticket`.
+///
+/// # Arguments
+/// * `value` - The randomness value revealed by the Switchboard oracle
+/// * `ticket_count` - The number of held tickets to draw from, mapped to ticket numbers
+///   with `Raffle::held_ticket_numbers`
+pub fn derive_vrf_winning_ticket(value: &[u8; 32], ticket_count: u64) -> Result<u64> {
+    unbiased_range(vrf_entropy(value), ticket_count)
+}
+
+/// Derives the distinct winning tickets of a raffle with `winner_count` winners from a
+/// revealed Switchboard randomness value, in prize order. The first ticket is the one
+/// `derive_vrf_winning_ticket` derives.
+pub fn derive_vrf_winning_tickets(
+    value: &[u8; 32],
+    ticket_count: u64,
+    winner_count: u8,
+) -> Result<Vec<u64>> {
+    distinct_in_range(vrf_entropy(value), ticket_count, winner_count as usize)
+}
+
+/// Folds a revealed Switchboard randomness value into a single random value
+fn vrf_entropy(value: &[u8; 32]) -> u64 {
+    let value1 = u64::from_le_bytes(*array_ref![value, 0, 8]);
+    let value2 = u64::from_le_bytes(*array_ref![value, 8, 8]);
 
-    // Fallback case - the bias is minimal after the mixing operations
-    Ok(value % range)
+    // The value is already uniformly random, mixing just folds in more of its bits
+    mix(value1, value2)
 }
 
 /// Accounts required for the draw_winning_ticket instruction
@@ -136,12 +434,18 @@ fn unbiased_range(x: u64, range: u64) -> Result<u64> {
 pub struct DrawWinningTicket<'info> {
     /// The raffle account to draw a winner for.
     /// Must be in Open state, past end time, and have met minimum ticket threshold
+    /// and unique buyer requirement, with a ticket sold for every winner
     #[account(
         mut,
         constraint = raffle.raffle_state == RaffleState::Open @ RaffleError::RaffleNotOpen,
-        constraint = (Clock::get()?.unix_timestamp >= raffle.end_time) 
+        constraint = raffle.randomness_mode == RandomnessMode::SlotHashes @ RaffleError::SlotHashDrawDisabled,
+        constraint = (now(&config)? >= raffle.end_time) 
             || (raffle.max_tickets.is_some() && raffle.current_tickets == raffle.max_tickets.unwrap())  @ RaffleError::RaffleNotEnded,
-        constraint = raffle.current_tickets >= raffle.min_tickets @ RaffleError::InsufficientTickets,
+        constraint = raffle.unique_buyers_met() @ RaffleError::InsufficientUniqueBuyers,
+        constraint = raffle.threshold_met()? @ RaffleError::InsufficientTickets,
+        constraint = raffle.current_tickets >= raffle.winner_count as u64 @ RaffleError::InsufficientTickets,
+        constraint = raffle.numbering_finalized() @ RaffleError::NumberingNotFinalized,
+        constraint = raffle.reserved_tickets == 0 @ RaffleError::ReservationsPending,
     )]
     pub raffle: Account<'info, Raffle>,
 
@@ -150,4 +454,40 @@ pub struct DrawWinningTicket<'info> {
     /// CHECK: Using UncheckedAccount because we manually validate the correct sysvar.
     /// This is needed because Anchor will always throw an error on the SlotHashes sysvar.
     pub recent_slothashes: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ RaffleError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The calling keeper, required if the config restricts cranks to keepers
+    pub keeper: Option<Signer<'info>>,
+
+    /// The registration of the calling keeper, validated manually
+    pub keeper_registration: Option<Account<'info, Keeper>>,
+
+    /// The raffle's draw authority, required if the raffle has one
+    pub draw_authority: Option<Signer<'info>>,
+
+    /// The raffle's treasury, paying the crank reward
+    #[account(
+        mut,
+        seeds = [
+            b"treasury",
+            raffle.key().as_ref(),
+        ],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    /// The account receiving the crank reward, if any
+    #[account(mut)]
+    pub cranker: Option<Signer<'info>>,
+
+    pub system_program: Program<'info, System>,
+    /// Program notified of the raffle's draw, required if the config registers one
+    /// CHECK: Validated against the config's notifier program in the instruction
+    pub notifier_program: Option<UncheckedAccount<'info>>,
 }
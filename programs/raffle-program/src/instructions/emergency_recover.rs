@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, Raffle, RaffleState, RecoveryRequest, Treasury, CLOSE_REASON_VOIDED},
+    time::now,
+};
+
+/// Event emitted when a stuck raffle is recovered by the upgrade authority
+#[event]
+pub struct RaffleEmergencyRecovered {
+    /// The pubkey of the recovered raffle
+    pub raffle: Pubkey,
+    /// The state the raffle was stuck in
+    pub previous_state: RaffleState,
+    /// The timestamp when the raffle was recovered
+    pub recovered_at: i64,
+    /// Lamports held by the treasury for refunds
+    pub refundable_amount: u64,
+    /// Why the raffle left the Open state, one of the `CLOSE_REASON_*` values
+    pub close_reason: u8,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Fails unless the raffle can still be recovered, i.e. its ticket holders can't reclaim
+/// their tickets yet nor was its prize claimed
+pub(crate) fn require_recoverable(raffle: &Raffle) -> Result<()> {
+    require!(
+        !matches!(
+            raffle.raffle_state,
+            RaffleState::Expired | RaffleState::Claimed | RaffleState::Cancelled
+        ),
+        RaffleError::RaffleNotRecoverable
+    );
+    Ok(())
+}
+
+/// Instruction to execute a scheduled emergency recovery, as a last resort for raffles
+/// deadlocked in their state machine. The raffle is expired regardless of its state, so
+/// ticket holders can reclaim the treasury's funds through reclaim_expired_tickets.
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Only the upgrade authority can execute a recovery
+/// 2. Ensures the recovery was requested at least `EMERGENCY_RECOVERY_DELAY_SECS` ago
+///    and not cancelled since
+/// 3. Ensures the raffle was not expired, claimed or cancelled in the meantime
+///
+/// # Account Validations
+/// * Raffle - Must not be in Expired, Claimed or Cancelled state
+/// * Treasury - PDA with seeds ["treasury", raffle_key]
+/// * Recovery Request - PDA with seeds ["recovery_request", raffle_key], closed to the
+///   upgrade authority
+/// * Upgrade Authority - Must match the upgrade authority stored in config
+///
+/// # Implementation Notes
+/// - Funds already withdrawn from the treasury are not restored, so refunds are paid
+///   until the outstanding funds run out
+pub fn emergency_recover(ctx: Context<EmergencyRecover>) -> Result<()> {
+    require_recoverable(&ctx.accounts.raffle)?;
+
+    let recovered_at = now(&ctx.accounts.config)?;
+    require!(
+        recovered_at >= ctx.accounts.recovery_request.executable_at,
+        RaffleError::RecoveryTimelockActive
+    );
+
+    let previous_state = ctx.accounts.raffle.raffle_state.clone();
+    ctx.accounts.raffle.raffle_state = RaffleState::Expired;
+    ctx.accounts.raffle.close_reason = CLOSE_REASON_VOIDED;
+    ctx.accounts.raffle.refund_deadline = ctx.accounts.config.refund_deadline(recovered_at)?;
+    ctx.accounts
+        .config
+        .record_raffle_closed(&mut ctx.accounts.raffle);
+
+    let event_seq = ctx.accounts.raffle.next_event_seq()?;
+    emit!(RaffleEmergencyRecovered {
+        raffle: ctx.accounts.raffle.key(),
+        previous_state,
+        recovered_at,
+        refundable_amount: ctx.accounts.treasury.outstanding()?,
+        close_reason: CLOSE_REASON_VOIDED,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct EmergencyRecover<'info> {
+    #[account(mut)]
+    pub raffle: Account<'info, Raffle>,
+
+    #[account(
+        seeds = [
+            b"treasury",
+            raffle.key().as_ref(),
+        ],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        mut,
+        close = upgrade_authority,
+        seeds = [
+            b"recovery_request",
+            raffle.key().as_ref(),
+        ],
+        bump = recovery_request.bump,
+    )]
+    pub recovery_request: Account<'info, RecoveryRequest>,
+
+    #[account(mut)]
+    pub upgrade_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = upgrade_authority @ RaffleError::NotProgramUpgradeAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
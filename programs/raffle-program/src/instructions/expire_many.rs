@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    instructions::{expire_raffle::expire, finalize_raffle::pay_crank_reward},
+    math::checked_add,
+    state::{require_keeper, Config, Keeper, Raffle, RaffleState, Treasury},
+    time::now,
+};
+
+/// Event emitted when a batch of raffles is expired by expire_many
+#[event]
+pub struct RafflesExpired {
+    /// Number of raffles expired
+    pub raffle_count: u64,
+    /// Number of raffles skipped because they didn't qualify for expiry
+    pub skipped_count: u64,
+    /// The timestamp when the raffles were expired
+    pub expired_at: i64,
+}
+
+/// Crank instruction expiring many raffles that didn't meet their threshold in a single
+/// transaction, so keepers don't need a transaction per raffle
+///
+/// # Remaining Accounts
+/// Pairs of writable accounts `[raffle, treasury]` for each raffle to expire:
+/// * `raffle` - A raffle to expire
+/// * `treasury` - The raffle's treasury, paying the crank reward
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Ensures the caller is a registered keeper, if the config restricts cranks
+/// 2. Ensures every treasury matches the one stored in its raffle
+/// 3. Expires each raffle under the same conditions as expire_raffle
+///
+/// # Implementation Notes
+/// - Raffles that don't qualify for expiry are skipped instead of failing the batch, so
+///   keepers can pass every candidate raffle and retry batches
+/// - Each expired raffle records a RaffleExpired event and pays its crank reward to the
+///   cranker, if one signed
+pub fn expire_many<'info>(ctx: Context<'_, '_, 'info, 'info, ExpireMany<'info>>) -> Result<()> {
+    require_keeper(
+        &ctx.accounts.config,
+        ctx.accounts.keeper.as_ref(),
+        ctx.accounts.keeper_registration.as_ref(),
+    )?;
+
+    let remaining_accounts = ctx.remaining_accounts;
+    require!(
+        !remaining_accounts.is_empty(),
+        RaffleError::InvalidRemainingAccounts
+    );
+
+    let now = now(&ctx.accounts.config)?;
+    let mut raffle_count: u64 = 0;
+    let mut skipped_count: u64 = 0;
+    for pair in remaining_accounts.chunks(2) {
+        let [raffle_info, treasury_info] = pair else {
+            return err!(RaffleError::InvalidRemainingAccounts);
+        };
+        let mut raffle = Account::<Raffle>::try_from(raffle_info)?;
+        let mut treasury = Account::<Treasury>::try_from(treasury_info)?;
+        require_keys_eq!(
+            treasury.key(),
+            raffle.treasury,
+            RaffleError::InvalidTreasury
+        );
+
+        if !expirable(&raffle, now)? {
+            skipped_count = checked_add(skipped_count, 1)?;
+            continue;
+        }
+
+        expire(&mut raffle, &mut ctx.accounts.config, now)?;
+        pay_crank_reward(
+            &mut raffle,
+            &mut treasury,
+            &ctx.accounts.system_program,
+            ctx.accounts.cranker.as_ref(),
+        )?;
+
+        // Accounts loaded from remaining accounts are not persisted by Anchor
+        raffle.exit(&crate::ID)?;
+        treasury.exit(&crate::ID)?;
+
+        raffle_count = checked_add(raffle_count, 1)?;
+    }
+
+    emit!(RafflesExpired {
+        raffle_count,
+        skipped_count,
+        expired_at: now,
+    });
+
+    Ok(())
+}
+
+/// Whether `raffle` meets the conditions checked by expire at `now`
+fn expirable(raffle: &Raffle, now: i64) -> Result<bool> {
+    if !matches!(
+        raffle.raffle_state,
+        RaffleState::Open | RaffleState::AwaitingPrize
+    ) {
+        return Ok(false);
+    }
+    let deadline = raffle.funding_deadline.unwrap_or(raffle.end_time);
+    Ok(deadline < now && !raffle.threshold_met()?)
+}
+
+#[derive(Accounts)]
+pub struct ExpireMany<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The calling keeper, required if the config restricts cranks to keepers
+    pub keeper: Option<Signer<'info>>,
+
+    /// The registration of the calling keeper, validated manually
+    pub keeper_registration: Option<Account<'info, Keeper>>,
+
+    /// The account receiving the crank rewards, if any
+    #[account(mut)]
+    pub cranker: Option<Signer<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -2,7 +2,12 @@ use anchor_lang::prelude::*;
 
 use crate::{
     error::RaffleError,
-    state::{Raffle, RaffleState},
+    instructions::finalize_raffle::pay_crank_reward,
+    state::{
+        require_keeper, Config, Keeper, Raffle, RaffleState, Treasury, CLOSE_REASON_EARLY_CLOSE,
+        CLOSE_REASON_TIME_ELAPSED,
+    },
+    time::now,
 };
 
 /// Event emitted when a raffle is expired
@@ -14,50 +19,97 @@ pub struct RaffleExpired {
     pub expired_at: i64,
     /// The final number of tickets sold
     pub final_ticket_count: u64,
+    /// Why the raffle left the Open state, one of the `CLOSE_REASON_*` values
+    pub close_reason: u8,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
 }
 
 /// Instruction to expire a raffle that didn't meet the minimum ticket threshold
 ///
 /// # Arguments
-/// * `ctx` - The context object containing the raffle account
+/// * `ctx` - The context object containing the raffle account and its treasury, the
+///   calling keeper if the config restricts cranks to keepers, and the account receiving
+///   the crank reward
 ///
 /// # Security Considerations
 /// The instruction performs several critical checks:
-/// 1. Ensures raffle is in Open state
-/// 2. Verifies the raffle's end time has passed
-/// 3. Validates that minimum ticket threshold was not met
+/// 1. Ensures the caller is a registered keeper, if the config restricts cranks
+/// 2. Ensures raffle is in Open or AwaitingPrize state
+/// 3. Verifies the raffle's end time has passed, or its funding deadline if it has one
+/// 4. Validates that the threshold was not met, by revenue if the raffle has a
+///    revenue target and by ticket count otherwise, or that too few distinct wallets
+///    bought tickets if the raffle requires a number of unique buyers
 ///
 /// # Account Validations
-/// * Raffle - Must be in Open state
-/// * Raffle - Must be past end time
-/// * Raffle - Current tickets must be less than minimum required tickets
+/// * Raffle - Must be in Open or AwaitingPrize state
+/// * Raffle - Must be past end time, or past its funding deadline
+/// * Raffle - Must not have met its ticket or revenue threshold
+/// * Keeper Registration - Must belong to the keeper signer, if cranks are restricted
+/// * Treasury - PDA with seeds ["treasury", raffle_key]
 ///
 /// # Implementation Notes
-/// - Changes raffle state to Expired
-/// - No funds are transferred in this instruction
+/// - Changes raffle state to Expired and fixes the raffle's refund deadline from the
+///   config's refund window
+/// - A raffle that missed its funding deadline expires early, so buyers are refunded
+///   without waiting for the end time
+/// - A raffle whose prize was never deposited expires like any raffle without sales
+/// - Pays the raffle's crank reward to the cranker, if one signed. Refunds of the
+///   raffle's tickets recover the reward from each ticket holder's refund
 pub fn expire_raffle(ctx: Context<ExpireRaffle>) -> Result<()> {
+    require_keeper(
+        &ctx.accounts.config,
+        ctx.accounts.keeper.as_ref(),
+        ctx.accounts.keeper_registration.as_ref(),
+    )?;
+
+    let now = now(&ctx.accounts.config)?;
+    expire(&mut ctx.accounts.raffle, &mut ctx.accounts.config, now)?;
+
+    pay_crank_reward(
+        &mut ctx.accounts.raffle,
+        &mut ctx.accounts.treasury,
+        &ctx.accounts.system_program,
+        ctx.accounts.cranker.as_ref(),
+    )
+}
+
+/// Expires a raffle that missed its threshold by its end time or funding deadline.
+///
+/// Shared by expire_raffle, expire_many and finalize_raffle, so a raffle expires under
+/// the same conditions regardless of which crank expired it.
+pub(crate) fn expire(raffle: &mut Account<Raffle>, config: &mut Config, now: i64) -> Result<()> {
     require!(
-        ctx.accounts.raffle.raffle_state == RaffleState::Open,
+        matches!(
+            raffle.raffle_state,
+            RaffleState::Open | RaffleState::AwaitingPrize
+        ),
         RaffleError::RaffleNotOpen
     );
 
-    let clock = Clock::get()?;
-    require!(
-        ctx.accounts.raffle.end_time < clock.unix_timestamp,
-        RaffleError::RaffleNotEnded
-    );
-    require!(
-        ctx.accounts.raffle.current_tickets < ctx.accounts.raffle.min_tickets,
-        RaffleError::ThresholdIsMet
-    );
+    // The funding deadline always precedes the end time
+    let deadline = raffle.funding_deadline.unwrap_or(raffle.end_time);
+    require!(deadline < now, RaffleError::RaffleNotEnded);
+    require!(!raffle.threshold_met()?, RaffleError::ThresholdIsMet);
 
-    ctx.accounts.raffle.raffle_state = RaffleState::Expired;
+    raffle.raffle_state = RaffleState::Expired;
+    // Raffles expiring before their end time missed their funding deadline
+    raffle.close_reason = if now < raffle.end_time {
+        CLOSE_REASON_EARLY_CLOSE
+    } else {
+        CLOSE_REASON_TIME_ELAPSED
+    };
+    raffle.refund_deadline = config.refund_deadline(now)?;
+    config.record_raffle_closed(raffle);
 
     // Emit the raffle expired event
+    let event_seq = raffle.next_event_seq()?;
     emit!(RaffleExpired {
-        raffle: ctx.accounts.raffle.key(),
-        expired_at: clock.unix_timestamp,
-        final_ticket_count: ctx.accounts.raffle.current_tickets,
+        raffle: raffle.key(),
+        expired_at: now,
+        final_ticket_count: raffle.current_tickets,
+        close_reason: raffle.close_reason,
+        event_seq,
     });
 
     Ok(())
@@ -67,4 +119,34 @@ pub fn expire_raffle(ctx: Context<ExpireRaffle>) -> Result<()> {
 pub struct ExpireRaffle<'info> {
     #[account(mut)]
     pub raffle: Account<'info, Raffle>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The calling keeper, required if the config restricts cranks to keepers
+    pub keeper: Option<Signer<'info>>,
+
+    /// The registration of the calling keeper, validated manually
+    pub keeper_registration: Option<Account<'info, Keeper>>,
+
+    /// The raffle's treasury, paying the crank reward
+    #[account(
+        mut,
+        seeds = [
+            b"treasury",
+            raffle.key().as_ref(),
+        ],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    /// The account receiving the crank reward, if any
+    #[account(mut)]
+    pub cranker: Option<Signer<'info>>,
+
+    pub system_program: Program<'info, System>,
 }
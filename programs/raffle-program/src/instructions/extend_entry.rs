@@ -0,0 +1,272 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    instructions::buy_tickets::{
+        emit_large_purchase, record_top_holder, validate_direct_purchase, validate_purchase,
+        validate_wallet_limit, TicketsPurchased,
+    },
+    math::checked_add,
+    notifier::notify_if_sold_out,
+    state::{
+        entry::Entry,
+        raffle::{Raffle, RaffleState},
+        Config, Leaderboard, TicketBalance, Treasury,
+    },
+    time::now,
+    treasury::{LamportVault, TreasuryVault},
+};
+
+/// Instruction to purchase tickets by extending the buyer's existing entry
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `ticket_count` - The number of tickets to purchase
+/// * `expected_total` - Optional maximum amount in lamports the buyer is willing to pay,
+///   required if the raffle has pricing tiers
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Applies the same ticket count, threshold, per purchase limit, price and funds
+///    checks as buy_tickets
+/// 2. Ensures the entry belongs to the signer and to the given raffle
+/// 3. Ensures the entry's tickets end exactly where the new purchase starts, so
+///    every entry keeps covering a single contiguous range of ticket indices. Entries
+///    of raffles with deferred numbering are numbered as a whole after the sale
+/// 4. Verifies the treasury account matches the one stored in raffle
+/// 5. Validates raffle is in Open state and hasn't ended through account constraints
+/// 6. If the config rejects CPI purchases, ensures extend_entry is a top-level
+///    instruction of the transaction
+///
+/// # Account Validations
+/// * Raffle - Must be in Open state and not expired
+/// * Entry - Existing PDA owned by the signer, re-derived from its stored seed
+/// * TicketBalance - Existing PDA tracking user's total tickets
+/// * Signer - Must have sufficient funds for purchase
+/// * Treasury - Must match raffle's treasury and uses proper PDA seeds
+///
+/// # Implementation Notes
+/// - No account is created, so repeat buyers pay no additional rent
+/// - Only possible when no other purchase happened since the entry was last extended;
+///   otherwise a new entry has to be created with buy_tickets
+/// - Not available for region or age restricted raffles, whose buyers have to present
+///   their attestations to buy_tickets, for private raffles, whose buyers have to
+///   present the access code to buy_tickets, nor during a priority phase
+/// - Not available for raffles paid in an SPL token, which only buy_tickets accepts
+/// - Not available for raffles issuing ticket receipts, as the entry's receipt records
+///   its ticket range
+/// - Emits the same TicketsPurchased and LargePurchase events as buy_tickets
+pub fn extend_entry(
+    ctx: Context<ExtendEntry>,
+    ticket_count: u64,
+    expected_total: Option<u64>,
+) -> Result<()> {
+    validate_direct_purchase(&ctx.accounts.config)?;
+
+    // Region and age restricted raffles check the buyer's attestations and private
+    // raffles the access code on every purchase, which only buy_tickets accepts
+    require!(
+        ctx.accounts.raffle.allowed_regions == 0,
+        RaffleError::RegionAttestationRequired
+    );
+    require!(
+        !ctx.accounts.raffle.age_restricted,
+        RaffleError::AgeVerificationRequired
+    );
+    require!(
+        ctx.accounts.raffle.access_code_hash.is_none(),
+        RaffleError::AccessCodeRequired
+    );
+    require!(
+        !ctx.accounts
+            .raffle
+            .in_priority_phase(now(&ctx.accounts.config)?),
+        RaffleError::PriorityAccessRequired
+    );
+    require!(
+        ctx.accounts.raffle.payment_mint.is_none(),
+        RaffleError::TokenPaymentsNotSupported
+    );
+    require!(
+        !ctx.accounts.raffle.ticket_receipts,
+        RaffleError::TicketReceiptsNotSupported
+    );
+
+    // Validate the ticket count, limits, price and buyer funds
+    let payment_amount = validate_purchase(
+        &ctx.accounts.raffle,
+        &ctx.accounts.signer,
+        ticket_count,
+        expected_total,
+        now(&ctx.accounts.config)?,
+    )?;
+
+    validate_wallet_limit(
+        &ctx.accounts.raffle,
+        ctx.accounts.ticket_balance.ticket_count,
+        ticket_count,
+    )?;
+
+    // Ensure treasury account matches the one stored in raffle
+    require!(
+        ctx.accounts.treasury.key() == ctx.accounts.raffle.treasury.key(),
+        RaffleError::InvalidTreasury,
+    );
+
+    // Verify ticket balance account is initialized
+    require!(
+        ctx.accounts.ticket_balance.owner == ctx.accounts.signer.key(),
+        RaffleError::TicketBalanceNotInitialized,
+    );
+
+    // The new tickets must directly follow the entry's existing tickets. Entries of
+    // raffles with deferred numbering have no tickets numbered yet, so they can always grow
+    let ticket_start_index = ctx.accounts.raffle.next_ticket_start()?;
+    let entry = &mut ctx.accounts.entry;
+    if !ctx.accounts.raffle.deferred_numbering {
+        require!(
+            checked_add(entry.ticket_start_index, entry.ticket_count)? == ticket_start_index,
+            RaffleError::EntryNotContiguous
+        );
+    }
+    entry.ticket_count = checked_add(entry.ticket_count, ticket_count)?;
+
+    // Update raffle state with new ticket count using checked arithmetic
+    ctx.accounts.raffle.current_tickets =
+        checked_add(ctx.accounts.raffle.current_tickets, ticket_count)?;
+
+    // Update user's total ticket balance with overflow protection
+    let ticket_balance = &mut ctx.accounts.ticket_balance;
+    ticket_balance.ticket_count = checked_add(ticket_balance.ticket_count, ticket_count)?;
+    ticket_balance.total_spent = checked_add(ticket_balance.total_spent, payment_amount)?;
+
+    // Record the bulk discount, so a refund returns what was paid for the tickets
+    let unit_price = ctx.accounts.raffle.unit_price(ticket_count);
+    ctx.accounts
+        .raffle
+        .record_tier_discount(ticket_balance, ticket_count, unit_price)?;
+
+    // Track the buyer's new balance on the leaderboard, if the prize is split among top holders
+    record_top_holder(
+        &ctx.accounts.raffle,
+        ctx.accounts.leaderboard.as_mut(),
+        &ctx.accounts.ticket_balance,
+    )?;
+
+    // Transfer the payment to the treasury and track the collected funds
+    LamportVault::new(&mut ctx.accounts.treasury, &ctx.accounts.system_program)
+        .collect(&ctx.accounts.signer.to_account_info(), payment_amount)?;
+
+    let event_seq = ctx.accounts.raffle.next_event_seq()?;
+    emit!(TicketsPurchased {
+        raffle: ctx.accounts.raffle.key(),
+        buyer: ctx.accounts.signer.key(),
+        ticket_count,
+        payment_amount,
+        unit_price,
+        ticket_start_index,
+        entry_seed: ctx.accounts.entry.seed,
+        campaign_discount: 0,
+        rent_sponsored: false,
+        memo: None,
+        referrer: None,
+        cumulative_tickets: ctx.accounts.ticket_balance.ticket_count,
+        cumulative_spend: ctx.accounts.ticket_balance.total_spent,
+        win_probability_bps: ctx
+            .accounts
+            .raffle
+            .win_probability_bps(ctx.accounts.ticket_balance.ticket_count),
+        total_tickets: ctx.accounts.raffle.current_tickets,
+        event_seq,
+    });
+    emit_large_purchase(
+        &ctx.accounts.config,
+        &mut ctx.accounts.raffle,
+        ctx.accounts.signer.key(),
+        ticket_count,
+        payment_amount,
+    )?;
+
+    notify_if_sold_out(
+        &ctx.accounts.config,
+        ctx.accounts.notifier_program.as_ref(),
+        &ctx.accounts.raffle,
+    )
+}
+
+/// Accounts required for the extend_entry instruction
+#[derive(Accounts)]
+pub struct ExtendEntry<'info> {
+    /// The raffle account that tickets are being purchased for
+    /// Must be in Open state and not past end time
+    #[account(
+        mut,
+        constraint = raffle.raffle_state == RaffleState::Open @ RaffleError::RaffleNotOpen,
+        constraint = now(&config)? < raffle.end_time @ RaffleError::RaffleEnded,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    /// The signer's existing entry that is extended with the new tickets
+    /// PDA with seeds ["entry", raffle_key, entry_seed]
+    #[account(
+        mut,
+        seeds = [
+            b"entry",
+            raffle.key().as_ref(),
+            entry.seed.as_ref()
+        ],
+        bump,
+        has_one = raffle @ RaffleError::EntryRaffleMismatch,
+        constraint = entry.owner == signer.key() @ RaffleError::OwnerMismatch,
+    )]
+    pub entry: Account<'info, Entry>,
+
+    /// User's ticket balance account
+    /// PDA with seeds ["ticket_balance", raffle_key, signer_key]
+    #[account(
+        mut,
+        seeds = [
+            b"ticket_balance",
+            raffle.key().as_ref(),
+            signer.key().as_ref()
+        ],
+        bump = ticket_balance.bump
+    )]
+    pub ticket_balance: Account<'info, TicketBalance>,
+
+    /// The account purchasing the tickets
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Required for transferring the payment
+    pub system_program: Program<'info, System>,
+
+    /// Treasury account that receives payment for tickets
+    /// PDA with seeds ["treasury", raffle_key]
+    #[account(
+        mut,
+        seeds = [
+            b"treasury",
+            raffle.key().as_ref(),
+        ],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    /// Optional leaderboard of the raffle's largest holders, required if the raffle splits
+    /// its prize among top holders. Validated against the raffle in the instruction.
+    #[account(mut)]
+    pub leaderboard: Option<Account<'info, Leaderboard>>,
+
+    /// Program config, checked for the pause switch and the large purchase threshold
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ RaffleError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+    /// Program notified if the purchase sells the raffle out, required then if the
+    /// config registers one
+    /// CHECK: Validated against the config's notifier program in the instruction
+    pub notifier_program: Option<UncheckedAccount<'info>>,
+}
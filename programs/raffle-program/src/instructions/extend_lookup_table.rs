@@ -0,0 +1,91 @@
+use anchor_lang::{prelude::*, solana_program::address_lookup_table};
+
+use crate::{error::RaffleError, instructions::create_lookup_table::extend_table, state::Config};
+
+/// Maximum number of addresses added to the lookup table by one extend_lookup_table
+pub const MAX_LOOKUP_TABLE_EXTENSION: usize = 20;
+
+/// Event emitted when addresses are added to the program's address lookup table
+#[event]
+pub struct LookupTableExtended {
+    /// The extended address lookup table
+    pub lookup_table: Pubkey,
+    /// Addresses added to the table
+    pub addresses: Vec<Pubkey>,
+}
+
+/// Instruction to add addresses to the program's address lookup table, e.g. the PDAs of
+/// a popular raffle and its treasury
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `addresses` - Addresses to add, at most MAX_LOOKUP_TABLE_EXTENSION
+///
+/// # Security Considerations
+/// - Only the management authority can extend the table
+/// - Only the table recorded in the config can be extended
+///
+/// # Account Validations
+/// * LookupTableAuthority - PDA with seeds ["lookup_table_authority"]
+/// * LookupTable - Must be the table recorded in the config
+/// * Config - PDA storing the management authority
+///
+/// # Implementation Notes
+/// - The management authority pays the rent of the grown table
+/// - Addresses are only usable through the table from the slot after they were added
+pub fn extend_lookup_table(ctx: Context<ExtendLookupTable>, addresses: Vec<Pubkey>) -> Result<()> {
+    require!(
+        !addresses.is_empty() && addresses.len() <= MAX_LOOKUP_TABLE_EXTENSION,
+        RaffleError::InvalidLookupTableExtension
+    );
+
+    extend_table(
+        &ctx.accounts.lookup_table,
+        &ctx.accounts.lookup_table_authority,
+        ctx.bumps.lookup_table_authority,
+        &ctx.accounts.management_authority,
+        &ctx.accounts.system_program,
+        addresses.clone(),
+    )?;
+
+    emit!(LookupTableExtended {
+        lookup_table: ctx.accounts.lookup_table.key(),
+        addresses,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExtendLookupTable<'info> {
+    /// Pays the rent of the grown lookup table
+    #[account(mut)]
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: PDA owning the program's lookup tables, holds no data
+    #[account(
+        seeds = [b"lookup_table_authority"],
+        bump,
+    )]
+    pub lookup_table_authority: UncheckedAccount<'info>,
+
+    /// CHECK: The lookup table recorded in the config
+    #[account(
+        mut,
+        constraint = config.lookup_table == Some(lookup_table.key()) @ RaffleError::InvalidLookupTable,
+    )]
+    pub lookup_table: UncheckedAccount<'info>,
+
+    /// CHECK: The address lookup table program
+    #[account(address = address_lookup_table::program::ID)]
+    pub address_lookup_table_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
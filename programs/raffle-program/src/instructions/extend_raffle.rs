@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    instructions::create_raffle::MAX_DURATION,
+    state::{require_raffle_manager, Config, CreatorRole, Raffle, RaffleState},
+    time::{now, require_seconds},
+};
+
+/// Event emitted when the end time of a raffle short of its threshold is pushed back
+#[event]
+pub struct RaffleExtended {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// End time before the extension
+    pub previous_end_time: i64,
+    /// Unix timestamp when the raffle now ends
+    pub end_time: i64,
+    /// Number of times the raffle was extended, including this extension
+    pub extension_count: u8,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to push back the end time of a raffle that is about to miss its ticket or
+/// revenue threshold, giving it more time to sell instead of expiring and refunding
+/// everyone
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `end_time` - Unix timestamp when the raffle now ends
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Only the management authority, or the creator of a raffle created through a
+///    creator role, can extend a raffle
+/// 2. Ensures the raffle hasn't ended or passed its funding deadline, so buyers expecting
+///    a refund never see the sale reopen
+/// 3. Ensures the threshold is not met, so raffles that will be drawn keep their end time
+/// 4. Limits the number of extensions and their total duration to the config's
+///    `max_raffle_extensions` and `max_extension_secs`
+/// 5. Ensures the new end time is later than the current one and at most 30 days ahead
+///
+/// # Account Validations
+/// * Raffle - Must be in Open state
+/// * Config - PDA storing the management authority and the extension limits
+/// * CreatorRole - Optional, the signer's creator role, required unless the signer is
+///   the management authority
+///
+/// # Implementation Notes
+/// - A funding deadline set on the raffle is left unchanged
+/// - Every extension is recorded in `extension_count` and announced with a
+///   `RaffleExtended` event, so buyers can see the deadline change
+pub fn extend_raffle(ctx: Context<ExtendRaffle>, end_time: i64) -> Result<()> {
+    require_raffle_manager(
+        &ctx.accounts.config,
+        &ctx.accounts.authority,
+        ctx.accounts.raffle.creator,
+        ctx.accounts.creator_role.as_ref(),
+    )?;
+
+    let raffle = &mut ctx.accounts.raffle;
+    let config = &ctx.accounts.config;
+    let current_time = now(&ctx.accounts.config)?;
+    require_seconds(end_time)?;
+
+    require!(
+        current_time < raffle.funding_deadline.unwrap_or(raffle.end_time),
+        RaffleError::RaffleEnded
+    );
+    require!(!raffle.threshold_met()?, RaffleError::ThresholdIsMet);
+    require!(
+        end_time > raffle.end_time && end_time <= current_time.saturating_add(MAX_DURATION),
+        RaffleError::InvalidExtension
+    );
+
+    let extended_secs = raffle
+        .extended_secs
+        .checked_add(end_time - raffle.end_time)
+        .ok_or(RaffleError::Overflow)?;
+    require!(
+        raffle.extension_count < config.max_raffle_extensions
+            && extended_secs <= config.max_extension_secs,
+        RaffleError::ExtensionLimitReached
+    );
+
+    let previous_end_time = raffle.end_time;
+    raffle.end_time = end_time;
+    raffle.extended_secs = extended_secs;
+    raffle.extension_count += 1;
+
+    let event_seq = raffle.next_event_seq()?;
+    emit!(RaffleExtended {
+        raffle: raffle.key(),
+        previous_end_time,
+        end_time,
+        extension_count: raffle.extension_count,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExtendRaffle<'info> {
+    /// The raffle whose end time is pushed back
+    #[account(
+        mut,
+        constraint = raffle.raffle_state == RaffleState::Open @ RaffleError::RaffleNotOpen,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    /// The management authority, or the creator of the raffle
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Creator role of the signer, required unless the signer is the management
+    /// authority. Validated against the signer and the raffle in the instruction.
+    pub creator_role: Option<Account<'info, CreatorRole>>,
+}
@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    math::checked_add,
+    state::{Config, Entry, Raffle, RaffleState},
+    time::now,
+};
+
+/// Event emitted when a batch of entries of a raffle with deferred numbering is numbered
+#[event]
+pub struct NumberingFinalized {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// Number of entries numbered by this call
+    pub entry_count: u64,
+    /// Number of entries numbered so far, out of `Raffle::entry_count`
+    pub numbered_entries: u64,
+    /// Number of tickets numbered so far, out of `Raffle::current_tickets`
+    pub numbered_tickets: u64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Crank instruction that assigns ticket numbers to the entries of a raffle with
+/// deferred numbering once its sale closed. Entries are numbered in creation order, each
+/// starting where the previous one ended, so the result doesn't depend on who cranks it.
+/// Can be called by anyone, in as many batches as needed, and must have numbered every
+/// entry before the raffle can be drawn.
+///
+/// # Remaining Accounts
+/// Writable Entry accounts of the raffle, continuing the creation order from
+/// `Raffle::numbered_entries`
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Validates the raffle defers numbering, is in Open state and its sale closed, either
+///    because the end time passed or because it sold out, so no entry can be added or
+///    extended after numbering started
+/// 2. Verifies each entry belongs to the raffle and is the next one in creation order,
+///    so no entry is skipped or numbered twice
+///
+/// # Account Validations
+/// * Raffle - Must defer numbering, be in Open state and past end time or sold out
+pub fn finalize_numbering<'info>(
+    ctx: Context<'_, '_, 'info, 'info, FinalizeNumbering<'info>>,
+) -> Result<()> {
+    let remaining_accounts = ctx.remaining_accounts;
+    require!(
+        !remaining_accounts.is_empty(),
+        RaffleError::InvalidRemainingAccounts
+    );
+
+    let raffle = &mut ctx.accounts.raffle;
+    for entry_info in remaining_accounts {
+        let mut entry = Account::<Entry>::try_from(entry_info)?;
+        require!(
+            entry.raffle == raffle.key(),
+            RaffleError::EntryRaffleMismatch
+        );
+        require!(
+            entry.entry_index == raffle.numbered_entries,
+            RaffleError::EntryOutOfOrder
+        );
+
+        entry.ticket_start_index = raffle.numbered_tickets;
+        entry.exit(&crate::ID)?;
+
+        raffle.numbered_tickets = checked_add(raffle.numbered_tickets, entry.ticket_count)?;
+        raffle.numbered_entries = checked_add(raffle.numbered_entries, 1)?;
+    }
+
+    let event_seq = raffle.next_event_seq()?;
+    emit!(NumberingFinalized {
+        raffle: raffle.key(),
+        entry_count: remaining_accounts.len() as u64,
+        numbered_entries: raffle.numbered_entries,
+        numbered_tickets: raffle.numbered_tickets,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FinalizeNumbering<'info> {
+    /// The raffle whose entries are numbered, once its sale closed
+    #[account(
+        mut,
+        constraint = raffle.deferred_numbering @ RaffleError::NumberingNotDeferred,
+        constraint = raffle.raffle_state == RaffleState::Open @ RaffleError::RaffleNotOpen,
+        constraint = (now(&config)? >= raffle.end_time)
+            || (raffle.max_tickets.is_some() && raffle.current_tickets == raffle.max_tickets.unwrap()) @ RaffleError::RaffleNotEnded,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    /// Program config, storing the clock offset of devnet builds
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,207 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    instructions::{
+        draw_winning_ticket::{authorize_draw, draw_with_slot_hashes},
+        expire_raffle::expire,
+    },
+    notifier::{notify_lifecycle, LifecycleEvent},
+    state::{
+        raffle::{Raffle, RaffleState, RandomnessMode},
+        require_keeper, Config, Keeper, Treasury,
+    },
+    time::now,
+    treasury::LamportVault,
+};
+
+/// Event emitted when the caller of a crank is paid the raffle's crank reward
+#[event]
+pub struct CrankRewardPaid {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The account the reward was paid to
+    pub cranker: Pubkey,
+    /// Lamports paid from the raffle's treasury
+    pub amount: u64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to settle a raffle once its sale is over, drawing it if it met its
+/// threshold and expiring it otherwise, so a single permissionless crank can move any
+/// raffle forward
+///
+/// # Arguments
+/// * `ctx` - The context object containing the raffle account and its treasury, the
+///   SlotHashes sysvar, the calling keeper or draw authority if required, and the
+///   account receiving the crank reward
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Applies the checks of draw_winning_ticket to raffles that met their threshold,
+///    including the draw authority and pause switch
+/// 2. Applies the checks of expire_raffle to all other raffles, including the funding
+///    deadline
+/// 3. Ensures the caller is a registered keeper if the config restricts cranks, unless
+///    the raffle's draw authority draws it
+///
+/// # Account Validations
+/// * Raffle - Must be in Open state, or AwaitingPrize state to be expired
+/// * RecentSlothashes - Must be the SlotHashes sysvar, validated manually when drawing
+/// * Treasury - PDA with seeds ["treasury", raffle_key]
+///
+/// # Implementation Notes
+/// - Raffles requiring verifiable randomness can only be expired here, their draws are
+///   requested with request_randomness
+/// - Raffles delaying their draw slot are only drawn here once commit_draw_slot committed
+///   the slot and it passed
+/// - Pays the raffle's crank reward to the cranker, if one signed
+pub fn finalize_raffle(ctx: Context<FinalizeRaffle>) -> Result<()> {
+    let raffle = &mut ctx.accounts.raffle;
+    let now = now(&ctx.accounts.config)?;
+
+    if raffle.threshold_met()? {
+        authorize_draw(
+            raffle,
+            &ctx.accounts.config,
+            ctx.accounts.keeper.as_ref(),
+            ctx.accounts.keeper_registration.as_ref(),
+            ctx.accounts.draw_authority.as_ref(),
+        )?;
+        require!(!ctx.accounts.config.paused, RaffleError::ProgramPaused);
+        require!(
+            raffle.raffle_state == RaffleState::Open,
+            RaffleError::RaffleNotOpen
+        );
+        require!(
+            raffle.randomness_mode == RandomnessMode::SlotHashes,
+            RaffleError::SlotHashDrawDisabled
+        );
+        let sold_out = raffle.max_tickets == Some(raffle.current_tickets);
+        require!(
+            now >= raffle.end_time || sold_out,
+            RaffleError::RaffleNotEnded
+        );
+        require!(
+            raffle.current_tickets >= raffle.winner_count as u64,
+            RaffleError::InsufficientTickets
+        );
+        require!(
+            raffle.numbering_finalized(),
+            RaffleError::NumberingNotFinalized
+        );
+        require!(
+            raffle.reserved_tickets == 0,
+            RaffleError::ReservationsPending
+        );
+
+        draw_with_slot_hashes(raffle, &ctx.accounts.recent_slothashes, now)?;
+        notify_lifecycle(
+            &ctx.accounts.config,
+            ctx.accounts.notifier_program.as_ref(),
+            raffle,
+            LifecycleEvent::Drawn,
+        )?;
+    } else {
+        require_keeper(
+            &ctx.accounts.config,
+            ctx.accounts.keeper.as_ref(),
+            ctx.accounts.keeper_registration.as_ref(),
+        )?;
+
+        expire(raffle, &mut ctx.accounts.config, now)?;
+    }
+
+    pay_crank_reward(
+        &mut ctx.accounts.raffle,
+        &mut ctx.accounts.treasury,
+        &ctx.accounts.system_program,
+        ctx.accounts.cranker.as_ref(),
+    )
+}
+
+/// Pays the raffle's crank reward for all tickets sold to `cranker`, if one signed and
+/// the reward wasn't paid yet.
+///
+/// Shared by all crank instructions settling a raffle, so each raffle pays its reward
+/// once, when it first leaves Open state. A raffle reopened by reset_draw keeps its
+/// `crank_reward_paid` flag, since refunds only recover one share per ticket.
+pub(crate) fn pay_crank_reward<'info>(
+    raffle: &mut Account<'info, Raffle>,
+    treasury: &mut Account<'info, Treasury>,
+    system_program: &Program<'info, System>,
+    cranker: Option<&Signer<'info>>,
+) -> Result<()> {
+    let Some(cranker) = cranker else {
+        return Ok(());
+    };
+    if raffle.crank_reward_paid {
+        return Ok(());
+    }
+    let amount = raffle.crank_share(raffle.current_tickets)?;
+    if amount == 0 {
+        return Ok(());
+    }
+
+    LamportVault::new(treasury, system_program)
+        .pay_crank_reward(&cranker.to_account_info(), amount)?;
+    raffle.crank_reward_paid = true;
+
+    let event_seq = raffle.next_event_seq()?;
+    emit!(CrankRewardPaid {
+        raffle: raffle.key(),
+        cranker: cranker.key(),
+        amount,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FinalizeRaffle<'info> {
+    #[account(mut)]
+    pub raffle: Account<'info, Raffle>,
+
+    /// The SlotHashes sysvar, drawn from if the raffle met its threshold
+    /// CHECK: Using UncheckedAccount because we manually validate the correct sysvar.
+    /// This is needed because Anchor will always throw an error on the SlotHashes sysvar.
+    pub recent_slothashes: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The calling keeper, required if the config restricts cranks to keepers
+    pub keeper: Option<Signer<'info>>,
+
+    /// The registration of the calling keeper, validated manually
+    pub keeper_registration: Option<Account<'info, Keeper>>,
+
+    /// The raffle's draw authority, required to draw a raffle that has one
+    pub draw_authority: Option<Signer<'info>>,
+
+    /// The raffle's treasury, paying the crank reward
+    #[account(
+        mut,
+        seeds = [
+            b"treasury",
+            raffle.key().as_ref(),
+        ],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    /// The account receiving the crank reward, if any
+    #[account(mut)]
+    pub cranker: Option<Signer<'info>>,
+
+    pub system_program: Program<'info, System>,
+    /// Program notified of the raffle's draw, required if the config registers one
+    /// CHECK: Validated against the config's notifier program in the instruction
+    pub notifier_program: Option<UncheckedAccount<'info>>,
+}
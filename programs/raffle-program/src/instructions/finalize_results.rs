@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    math::lamports_to_usd_cents,
+    state::{Config, Raffle, RaffleState, Results, RESULTS_ACCOUNT_SIZE},
+    time::now,
+};
+
+/// Event emitted when the results of a raffle are recorded
+#[event]
+pub struct ResultsFinalized {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The pubkey of the results account
+    pub results: Pubkey,
+    /// The winner of the raffle
+    pub winner: Pubkey,
+    /// The winning ticket
+    pub winning_ticket: u64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to record the outcome of a claimed raffle in a permanent Results PDA.
+/// Can only be called by the management authority, who pays for the account.
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Validates the raffle is in Claimed state, so the outcome can no longer change
+/// 2. Only the management authority can record the results, as they can't be corrected
+///    once recorded
+/// 3. The prize vault is derived from the raffle rather than passed in, so it is
+///    recorded whenever a prize was deposited
+/// 4. The results PDA uses seeds ["results", raffle_key], so the results can only be
+///    recorded once per raffle
+///
+/// # Account Validations
+/// * Raffle - Must be in Claimed state with a winner and winning ticket
+/// * Results - New PDA initialized for this raffle
+/// * Config - PDA storing the management authority
+///
+/// # Implementation Notes
+/// - All values are copied from the raffle; no funds are moved
+/// - The prize vault is recorded by its PDA address, since claim_prize closes it once
+///   all prizes are claimed
+/// - The prize value snapshot of record_draw_value is included if it was recorded
+/// - The results account has no close instruction, it is meant to be a stable record
+pub fn finalize_results(ctx: Context<FinalizeResults>) -> Result<()> {
+    let raffle = &ctx.accounts.raffle;
+    let winner = raffle.winner_address.ok_or(RaffleError::RaffleNotDrawn)?;
+    let winning_ticket = raffle.winning_ticket.ok_or(RaffleError::NoWinningTicket)?;
+
+    let prize_vault = raffle.prize_escrowed.then(|| {
+        Pubkey::find_program_address(&[b"prize_vault", raffle.key().as_ref()], &crate::ID).0
+    });
+
+    let results = &mut ctx.accounts.results;
+    results.raffle = raffle.key();
+    results.winner = winner;
+    results.winning_ticket = winning_ticket;
+    results.total_tickets = raffle.current_tickets;
+    results.total_participants = raffle.participant_count;
+    results.prize_vault = prize_vault;
+    results.draw_slot = raffle.draw_slot;
+    results.draw_slot_hash = raffle.draw_slot_hash;
+    results.draw_timestamp = raffle.draw_timestamp;
+    results.finalized_at = now(&ctx.accounts.config)?;
+    results.bump = ctx.bumps.results;
+    results.prize_value_lamports = raffle.draw_value_lamports;
+    results.usd_rate = raffle.draw_usd_rate;
+    results.prize_value_usd_cents = raffle
+        .draw_usd_rate
+        .map(|usd_rate| lamports_to_usd_cents(raffle.draw_value_lamports, usd_rate))
+        .transpose()?;
+
+    let results_key = results.key();
+    let event_seq = ctx.accounts.raffle.next_event_seq()?;
+    emit!(ResultsFinalized {
+        raffle: ctx.accounts.raffle.key(),
+        results: results_key,
+        winner,
+        winning_ticket,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FinalizeResults<'info> {
+    /// The raffle account that must be in Claimed state
+    #[account(
+        mut,
+        constraint = raffle.raffle_state == RaffleState::Claimed @ RaffleError::RaffleNotClaimed,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    /// New PDA recording the outcome of the raffle
+    #[account(
+        init,
+        payer = management_authority,
+        space = RESULTS_ACCOUNT_SIZE,
+        seeds = [
+            b"results",
+            raffle.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub results: Account<'info, Results>,
+
+    /// The management authority, paying for the results account
+    #[account(mut)]
+    pub management_authority: Signer<'info>,
+
+    /// Required by Anchor for account creation
+    pub system_program: Program<'info, System>,
+
+    /// Program config, storing the management authority and the clock offset of devnet
+    /// builds
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Entry, Raffle},
+};
+
+/// Owner of a ticket returned by `find_entry_for_ticket`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TicketOwner {
+    /// The raffle the ticket belongs to
+    pub raffle: Pubkey,
+    /// The looked up ticket index
+    pub ticket_index: u64,
+    /// The entry containing the ticket
+    pub entry: Pubkey,
+    /// The owner of the entry
+    pub owner: Pubkey,
+    /// Index of the first ticket of the entry
+    pub ticket_start_index: u64,
+    /// Number of tickets in the entry
+    pub ticket_count: u64,
+}
+
+/// Returns the entry and wallet owning a ticket, via return data.
+///
+/// Entries are not indexed on-chain, so support tooling passes the raffle's entries,
+/// e.g. fetched with `getProgramAccounts`, and the program picks the one whose range
+/// covers the ticket. The result never depends on the client's bookkeeping, as every
+/// candidate is validated as an entry of the raffle.
+///
+/// # Arguments
+/// * `ctx` - The context object containing:
+///   - `raffle`: The raffle the ticket belongs to
+/// * `ticket_index` - Index of the ticket to look up
+///
+/// # Remaining Accounts
+/// Candidate Entry accounts of the raffle, in any order
+///
+/// # Errors
+/// - `InvalidRemainingAccounts` if no entries are given
+/// - `TicketNotSold` if the raffle did not sell the ticket
+/// - `EntryRaffleMismatch` if an entry does not belong to the raffle
+/// - `TicketNotInEntry` if none of the entries contains the ticket
+pub fn find_entry_for_ticket<'info>(
+    ctx: Context<'_, '_, 'info, 'info, FindEntryForTicket<'info>>,
+    ticket_index: u64,
+) -> Result<TicketOwner> {
+    let remaining_accounts = ctx.remaining_accounts;
+    require!(
+        !remaining_accounts.is_empty(),
+        RaffleError::InvalidRemainingAccounts
+    );
+    require!(
+        ticket_index < ctx.accounts.raffle.current_tickets,
+        RaffleError::TicketNotSold
+    );
+
+    for entry_info in remaining_accounts {
+        let entry = Account::<Entry>::try_from(entry_info)?;
+        require!(
+            entry.raffle == ctx.accounts.raffle.key(),
+            RaffleError::EntryRaffleMismatch
+        );
+
+        if ticket_index >= entry.ticket_start_index
+            && ticket_index - entry.ticket_start_index < entry.ticket_count
+        {
+            return Ok(TicketOwner {
+                raffle: entry.raffle,
+                ticket_index,
+                entry: entry.key(),
+                owner: entry.owner,
+                ticket_start_index: entry.ticket_start_index,
+                ticket_count: entry.ticket_count,
+            });
+        }
+    }
+
+    err!(RaffleError::TicketNotInEntry)
+}
+
+/// Accounts required for the find_entry_for_ticket instruction
+#[derive(Accounts)]
+pub struct FindEntryForTicket<'info> {
+    /// The raffle the ticket belongs to
+    pub raffle: Account<'info, Raffle>,
+}
@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, Raffle, RaffleState, Treasury, CLOSE_REASON_VOIDED},
+    time::now,
+};
+
+/// Event emitted when the management authority expires a raffle regardless of its sales
+#[event]
+pub struct RaffleForceExpired {
+    /// The pubkey of the expired raffle
+    pub raffle: Pubkey,
+    /// The timestamp when the raffle was expired
+    pub expired_at: i64,
+    /// The final number of tickets sold
+    pub final_ticket_count: u64,
+    /// Lamports held by the treasury for refunds
+    pub refundable_amount: u64,
+    /// Why the raffle left the Open state, one of the `CLOSE_REASON_*` values
+    pub close_reason: u8,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to expire a raffle even though its threshold was met, e.g. when the
+/// prize falls through after a successful sale
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Only the management authority can force an expiry
+/// 2. Ensures the raffle is in Open or AwaitingPrize state, so no draw has happened
+/// 3. Ensures no randomness was requested and no draw slot committed, so the management
+///    authority can't void a draw whose outcome it can already predict
+/// 4. Ensures nothing was withdrawn from the treasury, so every ticket holder can be
+///    refunded in full
+///
+/// # Account Validations
+/// * Raffle - Must be in Open or AwaitingPrize state
+/// * Treasury - PDA with seeds ["treasury", raffle_key], nothing withdrawn
+/// * Config - PDA storing the management authority
+///
+/// # Implementation Notes
+/// - Changes raffle state to Expired, after which ticket holders reclaim their funds
+///   through reclaim_expired_tickets
+/// - Withdrawals from the treasury are rejected once the raffle is expired
+pub fn force_expire(ctx: Context<ForceExpire>) -> Result<()> {
+    require!(
+        matches!(
+            ctx.accounts.raffle.raffle_state,
+            RaffleState::Open | RaffleState::AwaitingPrize
+        ),
+        RaffleError::RaffleNotOpen
+    );
+    require!(
+        ctx.accounts.raffle.randomness_account.is_none(),
+        RaffleError::RandomnessAlreadyRequested
+    );
+    require!(
+        ctx.accounts.raffle.draw_target_slot == 0,
+        RaffleError::DrawSlotAlreadyCommitted
+    );
+    require!(
+        ctx.accounts.treasury.total_withdrawn == 0,
+        RaffleError::TreasuryAlreadyWithdrawn
+    );
+
+    let expired_at = now(&ctx.accounts.config)?;
+    ctx.accounts.raffle.raffle_state = RaffleState::Expired;
+    ctx.accounts.raffle.close_reason = CLOSE_REASON_VOIDED;
+    ctx.accounts.raffle.refund_deadline = ctx.accounts.config.refund_deadline(expired_at)?;
+    ctx.accounts
+        .config
+        .record_raffle_closed(&mut ctx.accounts.raffle);
+
+    let event_seq = ctx.accounts.raffle.next_event_seq()?;
+    emit!(RaffleForceExpired {
+        raffle: ctx.accounts.raffle.key(),
+        expired_at,
+        final_ticket_count: ctx.accounts.raffle.current_tickets,
+        refundable_amount: ctx.accounts.treasury.outstanding()?,
+        close_reason: CLOSE_REASON_VOIDED,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ForceExpire<'info> {
+    #[account(mut)]
+    pub raffle: Account<'info, Raffle>,
+
+    #[account(
+        seeds = [
+            b"treasury",
+            raffle.key().as_ref(),
+        ],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
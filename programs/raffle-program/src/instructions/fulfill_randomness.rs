@@ -0,0 +1,244 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    instructions::draw_winning_ticket::{
+        authorize_draw, derive_vrf_winning_tickets, read_latest_slot_hash,
+    },
+    notifier::{notify_lifecycle, LifecycleEvent},
+    randomness::{reveal_commitment, reveal_orao, reveal_switchboard, slot_hash_value},
+    state::{Config, Keeper, Raffle, RaffleState, RandomnessMode, RandomnessProvider},
+    time::{clock, now},
+};
+
+/// Event emitted when a raffle's winning ticket is drawn from its randomness provider
+#[event]
+pub struct RandomnessFulfilled {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The provider the raffle was drawn from
+    pub provider: RandomnessProvider,
+    /// The value the winning tickets were derived from
+    pub value: [u8; 32],
+    /// The drawn winning ticket of the first prize
+    pub winning_ticket: u64,
+    /// The drawn winning tickets of all prizes, in prize order
+    pub winning_tickets: Vec<u64>,
+    /// The slot the value was produced in, stored as the draw slot
+    pub draw_slot: u64,
+    /// Why the raffle left the Open state, one of the `CLOSE_REASON_*` values
+    pub close_reason: u8,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Draws the winning ticket of a raffle from the randomness provider it selected with
+/// set_randomness_provider.
+///
+/// Execution requirements:
+/// 1. The raffle must be in Open state and be drawn from a randomness provider that is
+///    still registered in the config
+/// 2. The raffle end time must have passed, or all tickets must be sold
+/// 3. The minimum ticket threshold and unique buyer requirement must be met, and at
+///    least as many tickets must be sold as the raffle has winners
+/// 4. No reservations may be pending and, if the raffle defers numbering,
+///    finalize_numbering must have numbered every entry
+/// 5. SlotHashes and CommitReveal raffles must be drawn by the raffle's draw authority,
+///    if it has one, or otherwise a registered keeper, if the config restricts cranks to
+///    keepers. Switchboard and ORAO raffles can be fulfilled by anyone, as the outcome
+///    is fixed by the account committed to with request_randomness
+///
+/// After execution:
+/// - The winning ticket numbers are stored in the raffle account in prize order
+/// - The draw slot and timestamp are stored, and the value the tickets were derived
+///   from is recorded in the RandomnessFulfilled event
+/// - The raffle state is changed to Drawing
+///
+/// # Arguments
+/// * `ctx` - The context object containing:
+///   - `raffle`: The mutable raffle account being drawn
+///   - `randomness_account`: The SlotHashes sysvar for SlotHashes raffles, or the
+///     randomness account the raffle committed to for Switchboard and ORAO raffles
+///   - `keeper`, `keeper_registration`: The calling keeper and its registration, if
+///     cranks are restricted
+///   - `draw_authority`: The raffle's draw authority, if it has one
+/// * `secret` - The secret hashing to the commitment of a CommitReveal raffle
+///
+/// # Errors
+/// - `ProgramPaused` if the program is paused
+/// - `RaffleNotOpen` if the raffle is not in Open state
+/// - `InvalidRandomnessProvider` if the raffle is not drawn from a randomness provider
+/// - `RandomnessProviderNotRegistered` if the raffle's provider was removed from the
+///   config
+/// - `RaffleNotEnded` if the raffle end time hasn't been reached
+/// - `InsufficientUniqueBuyers` if too few distinct wallets bought tickets
+/// - `InsufficientTickets` if minimum ticket threshold not met, or fewer tickets were
+///   sold than the raffle has winners
+/// - `NumberingNotFinalized` if the raffle defers numbering and finalize_numbering has
+///   not numbered every entry
+/// - `NotDrawAuthority` if the raffle has a draw authority that did not sign
+/// - `KeeperNotRegistered` if cranks are restricted and the caller is not a keeper
+/// - `InvalidSlotHashesAccount` if a SlotHashes raffle is not passed the sysvar
+/// - `StaleDrawEntropy` if the latest slot hash predates the last draw reset
+/// - `InvalidRandomnessCommitment` if the secret does not hash to the commitment
+/// - `InvalidRandomnessAccount` if the account is not the one the raffle committed to,
+///   or was recommitted since
+/// - `RandomnessNotRevealed` if the randomness was not requested or revealed yet
+pub fn fulfill_randomness(ctx: Context<FulfillRandomness>, secret: Option<[u8; 32]>) -> Result<()> {
+    let raffle = &ctx.accounts.raffle;
+    let provider = raffle
+        .randomness_provider
+        .ok_or(RaffleError::InvalidRandomnessProvider)?;
+    let program_id = ctx.accounts.config.randomness_program(provider)?;
+    let clock = clock(&ctx.accounts.config)?;
+
+    let (value, draw_slot, draw_slot_hash) = match provider {
+        RandomnessProvider::SlotHashes | RandomnessProvider::CommitReveal => {
+            authorize_draw(
+                raffle,
+                &ctx.accounts.config,
+                ctx.accounts.keeper.as_ref(),
+                ctx.accounts.keeper_registration.as_ref(),
+                ctx.accounts.draw_authority.as_ref(),
+            )?;
+            if provider == RandomnessProvider::SlotHashes {
+                let (slot, slot_hash) = read_latest_slot_hash(randomness_account(&ctx)?)?;
+                // A reset draw must not reuse the entropy of the draw it replaced
+                require!(slot >= raffle.min_draw_slot, RaffleError::StaleDrawEntropy);
+                (
+                    slot_hash_value(&raffle.key(), &slot_hash),
+                    slot,
+                    Some(slot_hash),
+                )
+            } else {
+                let value = reveal_commitment(
+                    &raffle.key(),
+                    raffle.randomness_commitment,
+                    secret,
+                    raffle.min_draw_slot,
+                )?;
+                (value, clock.slot, None)
+            }
+        }
+        RandomnessProvider::Switchboard | RandomnessProvider::Orao => {
+            let randomness_account = randomness_account(&ctx)?;
+            require!(
+                raffle.randomness_account == Some(randomness_account.key()),
+                RaffleError::RandomnessNotRevealed
+            );
+            require_keys_eq!(
+                *randomness_account.owner,
+                program_id,
+                RaffleError::InvalidRandomnessAccount
+            );
+            if provider == RandomnessProvider::Orao {
+                (
+                    reveal_orao(randomness_account, &program_id)?,
+                    clock.slot,
+                    None,
+                )
+            } else {
+                let (value, reveal_slot) =
+                    reveal_switchboard(randomness_account, raffle.randomness_commit_slot)?;
+                (value, reveal_slot, None)
+            }
+        }
+    };
+
+    let raffle = &mut ctx.accounts.raffle;
+    let winning_tickets = raffle.held_ticket_numbers(derive_vrf_winning_tickets(
+        &value,
+        raffle.current_tickets,
+        raffle.winner_count,
+    )?)?;
+    let winning_ticket = *winning_tickets
+        .first()
+        .ok_or(RaffleError::NoWinningTicket)?;
+
+    raffle.winning_ticket = Some(winning_ticket);
+    raffle.winning_tickets = winning_tickets.clone();
+    raffle.draw_slot = Some(draw_slot);
+    raffle.draw_slot_hash = draw_slot_hash;
+    raffle.draw_timestamp = Some(clock.unix_timestamp);
+    raffle.raffle_state = RaffleState::Drawing;
+    raffle.close_reason = raffle.draw_close_reason(clock.unix_timestamp);
+
+    let event_seq = raffle.next_event_seq()?;
+    emit!(RandomnessFulfilled {
+        raffle: raffle.key(),
+        provider,
+        value,
+        winning_ticket,
+        winning_tickets,
+        draw_slot,
+        close_reason: raffle.close_reason,
+        event_seq,
+    });
+
+    notify_lifecycle(
+        &ctx.accounts.config,
+        ctx.accounts.notifier_program.as_ref(),
+        &ctx.accounts.raffle,
+        LifecycleEvent::Drawn,
+    )
+}
+
+/// Returns the randomness account passed to fulfill_randomness, which every provider
+/// but CommitReveal requires
+fn randomness_account<'a, 'info>(
+    ctx: &'a Context<FulfillRandomness<'info>>,
+) -> Result<&'a AccountInfo<'info>> {
+    ctx.accounts
+        .randomness_account
+        .as_ref()
+        .map(|account| account.as_ref())
+        .ok_or(error!(RaffleError::InvalidRandomnessAccount))
+}
+
+/// Accounts required for the fulfill_randomness instruction
+#[derive(Accounts)]
+pub struct FulfillRandomness<'info> {
+    /// The raffle account to draw a winner for.
+    /// Must be in Open state, past end time or sold out, and have met minimum ticket
+    /// threshold and unique buyer requirement, with a ticket sold for every winner
+    #[account(
+        mut,
+        constraint = raffle.raffle_state == RaffleState::Open @ RaffleError::RaffleNotOpen,
+        constraint = raffle.randomness_mode == RandomnessMode::Provider @ RaffleError::InvalidRandomnessProvider,
+        constraint = now(&config)? >= raffle.end_time
+            || raffle.max_tickets == Some(raffle.current_tickets)
+            @ RaffleError::RaffleNotEnded,
+        constraint = raffle.unique_buyers_met() @ RaffleError::InsufficientUniqueBuyers,
+        constraint = raffle.threshold_met()? @ RaffleError::InsufficientTickets,
+        constraint = raffle.current_tickets >= raffle.winner_count as u64 @ RaffleError::InsufficientTickets,
+        constraint = raffle.numbering_finalized() @ RaffleError::NumberingNotFinalized,
+        constraint = raffle.reserved_tickets == 0 @ RaffleError::ReservationsPending,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    /// The SlotHashes sysvar, or the Switchboard or ORAO randomness account the raffle
+    /// committed to. Not required by CommitReveal raffles
+    /// CHECK: Validated against the raffle's provider in the instruction
+    pub randomness_account: Option<UncheckedAccount<'info>>,
+
+    /// Program config, storing the registered providers and the notifier program
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ RaffleError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The calling keeper, required if the config restricts cranks to keepers
+    pub keeper: Option<Signer<'info>>,
+
+    /// The registration of the calling keeper, validated manually
+    pub keeper_registration: Option<Account<'info, Keeper>>,
+
+    /// The raffle's draw authority, required if the raffle has one
+    pub draw_authority: Option<Signer<'info>>,
+
+    /// Program notified of the raffle's draw, required if the config registers one
+    /// CHECK: Validated against the config's notifier program in the instruction
+    pub notifier_program: Option<UncheckedAccount<'info>>,
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::RaffleError, state::RentPool, transfer::transfer_lamports};
+
+/// Event emitted when the rent pool is funded
+#[event]
+pub struct RentPoolFunded {
+    /// The account that funded the pool
+    pub funder: Pubkey,
+    /// Amount added in lamports
+    pub amount: u64,
+}
+
+/// Instruction to add lamports to the rent pool. Can be called by anyone.
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `amount` - Lamports to transfer from the funder to the pool (must be > 0)
+pub fn fund_rent_pool(ctx: Context<FundRentPool>, amount: u64) -> Result<()> {
+    require!(amount > 0, RaffleError::InsufficientFunds);
+
+    transfer_lamports(
+        &ctx.accounts.funder.to_account_info(),
+        &ctx.accounts.rent_pool.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        amount,
+    )?;
+
+    emit!(RentPoolFunded {
+        funder: ctx.accounts.funder.key(),
+        amount,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FundRentPool<'info> {
+    #[account(
+        mut,
+        seeds = [b"rent_pool"],
+        bump = rent_pool.bump,
+    )]
+    pub rent_pool: Account<'info, RentPool>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
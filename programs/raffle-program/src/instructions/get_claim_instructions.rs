@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{ClaimInstructions, Raffle, RaffleState},
+};
+
+/// Claim flow returned by `get_claim_instructions`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ClaimInstructionsView {
+    /// The raffle the instructions belong to
+    pub raffle: Pubkey,
+    /// The winner of the raffle, unless it is anonymized
+    pub winner: Option<Pubkey>,
+    /// The claim instructions, or a URI to a document containing them
+    pub instructions: String,
+    /// SHA-256 hash of the referenced document, if any
+    pub content_hash: Option<[u8; 32]>,
+}
+
+/// Returns the claim instructions of a drawn raffle together with its winner, via return
+/// data.
+///
+/// Wallets and frontends surface this to the winner instead of claim instructions
+/// delivered off-chain, so the winner can verify the flow was fixed before the sale.
+///
+/// # Arguments
+/// * `ctx` - The context object containing:
+///   - `raffle`: The drawn raffle
+///   - `claim_instructions`: PDA with seeds ["claim_instructions", raffle_key]
+///
+/// # Errors
+/// - `RaffleNotDrawn` if the raffle is not in Drawn state
+pub fn get_claim_instructions(ctx: Context<GetClaimInstructions>) -> Result<ClaimInstructionsView> {
+    require!(
+        ctx.accounts.raffle.raffle_state == RaffleState::Drawn,
+        RaffleError::RaffleNotDrawn
+    );
+
+    let claim_instructions = &ctx.accounts.claim_instructions;
+    Ok(ClaimInstructionsView {
+        raffle: claim_instructions.raffle,
+        winner: ctx.accounts.raffle.winner_address,
+        instructions: claim_instructions.instructions.clone(),
+        content_hash: claim_instructions.content_hash,
+    })
+}
+
+/// Accounts required for the get_claim_instructions instruction
+#[derive(Accounts)]
+pub struct GetClaimInstructions<'info> {
+    /// The drawn raffle
+    pub raffle: Account<'info, Raffle>,
+
+    /// The claim instructions of the raffle
+    #[account(
+        seeds = [
+            b"claim_instructions",
+            raffle.key().as_ref(),
+        ],
+        bump = claim_instructions.bump,
+    )]
+    pub claim_instructions: Account<'info, ClaimInstructions>,
+}
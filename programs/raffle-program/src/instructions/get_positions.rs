@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, Raffle, RaffleState, TicketBalance},
+    time::now,
+};
+
+/// Maximum number of raffles looked up by one `get_positions` call, so the result fits
+/// the return data
+pub const MAX_POSITIONS: usize = 24;
+
+/// A wallet's position in a raffle returned by `get_positions`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Position {
+    /// The raffle the wallet holds tickets in
+    pub raffle: Pubkey,
+    /// Number of tickets the wallet holds
+    pub tickets: u64,
+    /// Whether the tickets can be refunded now with reclaim_expired_tickets
+    pub refundable: bool,
+    /// State of the raffle
+    pub state: RaffleState,
+}
+
+/// Returns the positions a wallet holds in the given raffles via return data, so
+/// clients can populate a wallet's tickets with a single simulated call instead of
+/// fetching and decoding every raffle and ticket balance.
+///
+/// # Arguments
+/// * `ctx` - The context object containing:
+///   - `config`: The config, whose clock decides whether refunds lapsed
+/// * `wallet` - The wallet to look up the positions of
+///
+/// # Remaining Accounts
+/// Pairs of a Raffle account and the wallet's TicketBalance PDA with seeds
+/// ["ticket_balance", raffle_key, wallet] for it, which may be uninitialized. At most
+/// `MAX_POSITIONS` pairs
+///
+/// Raffles the wallet holds no tickets in are left out of the result, which lists the
+/// remaining raffles in the order they were passed.
+///
+/// # Errors
+/// - `InvalidRemainingAccounts` if the accounts are not pairs, there are more than
+///   `MAX_POSITIONS` of them, or a ticket balance is not the wallet's PDA for its raffle
+pub fn get_positions<'info>(
+    ctx: Context<'_, '_, 'info, 'info, GetPositions<'info>>,
+    wallet: Pubkey,
+) -> Result<Vec<Position>> {
+    let remaining_accounts = ctx.remaining_accounts;
+    require!(
+        remaining_accounts.len().is_multiple_of(2) && remaining_accounts.len() / 2 <= MAX_POSITIONS,
+        RaffleError::InvalidRemainingAccounts
+    );
+    let current_time = now(&ctx.accounts.config)?;
+
+    let mut positions = Vec::new();
+    for pair in remaining_accounts.chunks(2) {
+        let raffle = Account::<Raffle>::try_from(&pair[0])?;
+        let ticket_balance_info = &pair[1];
+        let (address, _) = Pubkey::find_program_address(
+            &[b"ticket_balance", raffle.key().as_ref(), wallet.as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(
+            ticket_balance_info.key(),
+            address,
+            RaffleError::InvalidRemainingAccounts
+        );
+        if ticket_balance_info.data_is_empty() {
+            continue;
+        }
+
+        let ticket_balance = Account::<TicketBalance>::try_from(ticket_balance_info)?;
+        if ticket_balance.ticket_count == 0 {
+            continue;
+        }
+        let refundable = matches!(
+            raffle.raffle_state,
+            RaffleState::Expired | RaffleState::Cancelled
+        ) && !raffle.refunds_lapsed(current_time);
+
+        positions.push(Position {
+            raffle: raffle.key(),
+            tickets: ticket_balance.ticket_count,
+            refundable,
+            state: raffle.raffle_state.clone(),
+        });
+    }
+
+    Ok(positions)
+}
+
+/// Accounts required for the get_positions instruction
+#[derive(Accounts)]
+pub struct GetPositions<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
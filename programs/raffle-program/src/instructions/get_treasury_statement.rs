@@ -0,0 +1,152 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, Raffle, RaffleState, Treasury},
+};
+use anchor_spl::{associated_token::get_associated_token_address, token::TokenAccount};
+
+/// Finances of a raffle returned by `get_treasury_statement`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TreasuryStatement {
+    /// The raffle the treasury belongs to
+    pub raffle: Pubkey,
+    /// The raffle state at the time of the statement
+    pub raffle_state: RaffleState,
+    /// Lamports received from ticket sales
+    pub total_collected: u64,
+    /// Lamports added to the prize pool by sponsor matching contributions
+    pub total_sponsored: u64,
+    /// Lamports refunded to ticket holders
+    pub total_refunded: u64,
+    /// Lamports retained from refunds as refund processing fees
+    pub total_refund_fees: u64,
+    /// Lamports withdrawn by the operator
+    pub total_withdrawn: u64,
+    /// Lamports paid to the callers cranking the raffle's expiry or draw
+    pub total_crank_rewards: u64,
+    /// Lamports accrued to referrers of ticket purchases
+    pub total_referral_fees: u64,
+    /// Lamports paid to referrers
+    pub total_referral_fees_paid: u64,
+    /// Lamports of refunds left unclaimed past the refund deadline and swept
+    pub total_forfeited: u64,
+    /// Lamports collected that were not yet refunded, withdrawn, forfeited or paid as
+    /// crank rewards or referral fees
+    pub outstanding: u64,
+    /// Lamports `withdraw_from_treasury` would currently pay out
+    pub withdrawable: u64,
+    /// Lamports held by the treasury, including its rent reserve and any lamports sent
+    /// to it directly. For raffles paid in an SPL token, the tokens held by the
+    /// treasury's token account instead
+    pub balance: u64,
+    /// Lamports moved into the treasury by program instructions and not yet paid out,
+    /// excluding its rent reserve and any lamports sent to it directly
+    pub recognized_balance: u64,
+}
+
+/// Returns a statement of a raffle's treasury via return data, so sponsors can audit a
+/// raffle's finances with a single simulated call.
+///
+/// # Arguments
+/// * `ctx` - The context object containing:
+///   - `raffle`: The raffle to report on
+///   - `treasury`: PDA with seeds ["treasury", raffle_key]
+///   - `config`: The config, which may require the prize to be escrowed before
+///     withdrawals
+///   - `treasury_token_account`: The treasury's associated token account, required if
+///     the raffle is paid in an SPL token
+///
+/// The amounts are in lamports, or in base units of the raffle's payment mint.
+///
+/// # Errors
+/// - `Overflow` if the treasury's records are inconsistent
+/// - `InvalidPaymentMint` if the raffle is paid in an SPL token and the treasury's token
+///   account is missing or doesn't match
+pub fn get_treasury_statement(ctx: Context<GetTreasuryStatement>) -> Result<TreasuryStatement> {
+    let raffle = &ctx.accounts.raffle;
+    let treasury = &ctx.accounts.treasury;
+    let (balance, available) = match raffle.payment_mint {
+        Some(payment_mint) => {
+            let vault = ctx
+                .accounts
+                .treasury_token_account
+                .as_ref()
+                .ok_or(RaffleError::InvalidPaymentMint)?;
+            require_keys_eq!(
+                vault.key(),
+                get_associated_token_address(&treasury.key(), &payment_mint),
+                RaffleError::InvalidPaymentMint
+            );
+            (vault.amount, vault.amount)
+        }
+        None => (
+            treasury.to_account_info().lamports(),
+            treasury.recognized_balance,
+        ),
+    };
+    let outstanding = treasury.outstanding()?;
+
+    // Mirrors the limits applied by withdraw_from_treasury, reporting zero instead of
+    // failing when nothing can be withdrawn
+    let withdrawable =
+        if raffle.cash_prize_bps.is_some() || raffle.raffle_state == RaffleState::Cancelled {
+            0
+        } else if raffle.raffle_state == RaffleState::Expired {
+            treasury
+                .total_refund_fees
+                .saturating_sub(treasury.total_withdrawn)
+        } else if raffle.threshold_met()?
+            && (raffle.prize_escrowed || !ctx.accounts.config.require_prize_deposit)
+        {
+            outstanding
+        } else {
+            0
+        };
+    let withdrawable = withdrawable.min(outstanding).min(available);
+
+    Ok(TreasuryStatement {
+        raffle: raffle.key(),
+        raffle_state: raffle.raffle_state.clone(),
+        total_collected: treasury.total_collected,
+        total_sponsored: treasury.total_sponsored,
+        total_refunded: treasury.total_refunded,
+        total_refund_fees: treasury.total_refund_fees,
+        total_withdrawn: treasury.total_withdrawn,
+        total_crank_rewards: treasury.total_crank_rewards,
+        total_referral_fees: treasury.total_referral_fees,
+        total_referral_fees_paid: treasury.total_referral_fees_paid,
+        total_forfeited: treasury.total_forfeited,
+        outstanding,
+        withdrawable,
+        balance,
+        recognized_balance: treasury.recognized_balance,
+    })
+}
+
+/// Accounts required for the get_treasury_statement instruction
+#[derive(Accounts)]
+pub struct GetTreasuryStatement<'info> {
+    /// The raffle to report on
+    pub raffle: Account<'info, Raffle>,
+
+    /// The raffle's treasury
+    #[account(
+        seeds = [
+            b"treasury",
+            raffle.key().as_ref(),
+        ],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The treasury's associated token account, required if the raffle is paid in an SPL
+    /// token
+    pub treasury_token_account: Option<Account<'info, TokenAccount>>,
+}
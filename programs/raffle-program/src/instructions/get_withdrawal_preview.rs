@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    instructions::withdraw_from_treasury::{split_platform_fee, withdrawable_amount},
+    state::{Config, Raffle, RaffleState, Treasury},
+    treasury::available_lamports,
+};
+use anchor_spl::{associated_token::get_associated_token_address, token::TokenAccount};
+
+/// Distribution of a withdrawal returned by `get_withdrawal_preview`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct WithdrawalPreview {
+    /// The raffle the treasury belongs to
+    pub raffle: Pubkey,
+    /// Amount `withdraw_from_treasury` would withdraw, including the platform fee
+    pub amount: u64,
+    /// Platform fee in basis points charged on the withdrawal
+    pub fee_bps: u16,
+    /// Platform fee deducted from the withdrawal
+    pub fee_amount: u64,
+    /// The account the platform fee would be paid to, if one is charged
+    pub fee_recipient: Option<Pubkey>,
+    /// Net amount paid to the payout authority, or to an approved payout destination
+    pub payout_amount: u64,
+    /// Unpaid referral fees kept in the treasury for the referrers
+    pub reserved_referral_fees: u64,
+    /// Whether the withdrawal needs the withdrawal approver's approval first
+    pub approval_required: bool,
+}
+
+/// Returns the distribution of the withdrawal `withdraw_from_treasury` would make from a
+/// raffle's treasury at its current balance via return data, so operators can confirm
+/// the fee split and net payout with a single simulated call before signing.
+///
+/// # Arguments
+/// * `ctx` - The context object containing:
+///   - `raffle`: The raffle to preview the withdrawal of
+///   - `treasury`: PDA with seeds ["treasury", raffle_key]
+///   - `config`: The config, which charges the platform fee
+///   - `treasury_token_account`: The treasury's associated token account, required if
+///     the raffle is paid in an SPL token
+///
+/// The amounts are in lamports, or in base units of the raffle's payment mint. Referral
+/// fees paid alongside the withdrawal leave the previewed amounts unchanged, unless the
+/// treasury's balance can't cover both.
+///
+/// # Errors
+/// Fails with the error withdraw_from_treasury would fail with because of the raffle's
+/// state, e.g. `ThresholdNotMet`, `CashPrizeRaffle` or `PrizeNotDeposited`
+pub fn get_withdrawal_preview(ctx: Context<GetWithdrawalPreview>) -> Result<WithdrawalPreview> {
+    let raffle = &ctx.accounts.raffle;
+    let treasury = &ctx.accounts.treasury;
+    let config = &ctx.accounts.config;
+    let withdrawable = withdrawable_amount(raffle, treasury, config)?;
+
+    let available = match raffle.payment_mint {
+        Some(payment_mint) => {
+            let vault = ctx
+                .accounts
+                .treasury_token_account
+                .as_ref()
+                .ok_or(RaffleError::InvalidPaymentMint)?;
+            require_keys_eq!(
+                vault.key(),
+                get_associated_token_address(&treasury.key(), &payment_mint),
+                RaffleError::InvalidPaymentMint
+            );
+            vault.amount
+        }
+        None => available_lamports(treasury)?,
+    };
+    let amount = available.min(treasury.outstanding()?).min(withdrawable);
+    let (payout_amount, fee_amount) = split_platform_fee(raffle, config, amount)?;
+
+    // Referral fees of expired raffles are never paid, their purchases are refunded
+    let reserved_referral_fees = if raffle.raffle_state == RaffleState::Expired {
+        0
+    } else {
+        treasury.unpaid_referral_fees()?
+    };
+
+    Ok(WithdrawalPreview {
+        raffle: raffle.key(),
+        amount,
+        fee_bps: config.platform_fee_bps(raffle),
+        fee_amount,
+        fee_recipient: config.fee_recipient.filter(|_| fee_amount > 0),
+        payout_amount,
+        reserved_referral_fees,
+        approval_required: config.withdrawal_requires_approval(amount)
+            && amount > treasury.approved_withdrawal,
+    })
+}
+
+/// Accounts required for the get_withdrawal_preview instruction
+#[derive(Accounts)]
+pub struct GetWithdrawalPreview<'info> {
+    /// The raffle to preview the withdrawal of
+    pub raffle: Account<'info, Raffle>,
+
+    /// The raffle's treasury
+    #[account(
+        seeds = [
+            b"treasury",
+            raffle.key().as_ref(),
+        ],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The treasury's associated token account, required if the raffle is paid in an SPL
+    /// token
+    pub treasury_token_account: Option<Account<'info, TokenAccount>>,
+}
@@ -0,0 +1,261 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    instructions::{
+        buy_bundle::create_entry,
+        buy_tickets::{
+            record_top_holder, validate_direct_purchase, validate_purchase,
+            validate_wallet_limit, TicketsPurchased,
+        },
+        confirm_reservation::load_ticket_balance,
+    },
+    math::{checked_add, ticket_cost},
+    state::{Config, Entry, Leaderboard, Raffle, RaffleState, Treasury},
+    time::now,
+    treasury::{LamportVault, TreasuryVault},
+};
+
+/// Maximum number of recipients of a single gift_tickets_multi call
+pub const MAX_GIFT_RECIPIENTS: usize = 10;
+
+/// Tickets gifted to a single recipient by `gift_tickets_multi`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct GiftAllocation {
+    /// Number of tickets credited to the recipient
+    pub ticket_count: u64,
+    /// Seed used to derive the recipient's new entry PDA
+    pub entry_seed: [u8; 8],
+}
+
+/// Event emitted when a payer gifts tickets to several recipients
+#[event]
+pub struct TicketsGifted {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The account that paid for the tickets
+    pub payer: Pubkey,
+    /// Number of recipients
+    pub recipient_count: u64,
+    /// Total number of tickets gifted
+    pub ticket_count: u64,
+    /// Total amount paid in lamports
+    pub payment_amount: u64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction for a payer to buy tickets on behalf of several recipients at once, e.g.
+/// for team giveaways. Every recipient gets their own entry and ticket balance credit,
+/// as if they had bought the tickets themselves.
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `gifts` - Tickets and entry seed of each recipient, in remaining accounts order
+/// * `expected_total` - Optional maximum amount in lamports the payer is willing to pay,
+///   required if the raffle has pricing tiers
+///
+/// # Remaining Accounts
+/// Groups of accounts `[recipient, ticket_balance, entry]` for each gift:
+/// * `recipient` - The wallet credited with the tickets
+/// * `ticket_balance` - Writable, the recipient's ticket balance PDA, created if the
+///   recipient has none yet
+/// * `entry` - Writable, the uninitialized entry PDA with seeds
+///   ["entry", raffle_key, entry_seed]
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Ensures there are 1 to `MAX_GIFT_RECIPIENTS` gifts with matching account groups
+/// 2. Applies the same ticket limits, price and funds checks as buy_tickets to the total
+///    of all gifts
+/// 3. Rejects region, age and priority restricted raffles, as recipients can't present
+///    their attestations or profiles, raffles paid in an SPL token and raffles issuing
+///    ticket receipts
+/// 4. If the raffle splits its prize among top holders, ensures the leaderboard is the
+///    raffle's leaderboard
+/// 5. If the config rejects CPI purchases, ensures gift_tickets_multi is a top-level
+///    instruction of the transaction
+///
+/// # Account Validations
+/// * Raffle - Must be in Open state and not expired
+/// * Treasury - PDA with seeds ["treasury", raffle_key]
+/// * Leaderboard - Optional, required if the raffle splits its prize among top holders
+///
+/// # Implementation Notes
+/// - The payer pays the rent of the created entries and ticket balances
+/// - Emits a TicketsPurchased event for every recipient, followed by TicketsGifted
+pub fn gift_tickets_multi<'info>(
+    ctx: Context<'_, '_, 'info, 'info, GiftTicketsMulti<'info>>,
+    gifts: Vec<GiftAllocation>,
+    expected_total: Option<u64>,
+) -> Result<()> {
+    validate_direct_purchase(&ctx.accounts.config)?;
+
+    let remaining_accounts = ctx.remaining_accounts;
+    require!(
+        (1..=MAX_GIFT_RECIPIENTS).contains(&gifts.len())
+            && remaining_accounts.len() == gifts.len() * 3,
+        RaffleError::InvalidRemainingAccounts
+    );
+
+    let now = now(&ctx.accounts.config)?;
+    let raffle = &mut ctx.accounts.raffle;
+    require!(
+        raffle.allowed_regions == 0,
+        RaffleError::RegionAttestationRequired
+    );
+    require!(!raffle.age_restricted, RaffleError::AgeVerificationRequired);
+    require!(
+        !raffle.in_priority_phase(now),
+        RaffleError::PriorityAccessRequired
+    );
+    require!(
+        raffle.payment_mint.is_none(),
+        RaffleError::TokenPaymentsNotSupported
+    );
+    require!(
+        !raffle.ticket_receipts,
+        RaffleError::TicketReceiptsNotSupported
+    );
+
+    // Validate the limits, price and payer funds against the whole purchase
+    let mut total_tickets: u64 = 0;
+    for gift in &gifts {
+        require!(gift.ticket_count > 0, RaffleError::InvalidTicketCount);
+        total_tickets = checked_add(total_tickets, gift.ticket_count)?;
+    }
+    let total_payment = validate_purchase(
+        raffle,
+        &ctx.accounts.signer,
+        total_tickets,
+        expected_total,
+        now,
+    )?;
+    // All gifts are priced at the pricing tier of the whole purchase
+    let unit_price = raffle.unit_price(total_tickets);
+
+    for (gift, group) in gifts.iter().zip(remaining_accounts.chunks(3)) {
+        let [recipient_info, ticket_balance_info, entry_info] = group else {
+            return err!(RaffleError::InvalidRemainingAccounts);
+        };
+        let recipient = recipient_info.key();
+
+        let mut ticket_balance = load_ticket_balance(
+            ticket_balance_info,
+            &ctx.accounts.signer,
+            &ctx.accounts.system_program,
+            raffle.key(),
+            recipient,
+        )?;
+        validate_wallet_limit(raffle, ticket_balance.ticket_count, gift.ticket_count)?;
+
+        let (entry_index, ticket_start_index) = raffle.record_entry()?;
+        create_entry(
+            entry_info,
+            &ctx.accounts.signer,
+            &ctx.accounts.system_program,
+            Entry {
+                raffle: raffle.key(),
+                owner: recipient,
+                ticket_count: gift.ticket_count,
+                ticket_start_index,
+                seed: gift.entry_seed,
+                rent_sponsored: false,
+                memo: None,
+                next_entry: ticket_balance.head_entry,
+                entry_index,
+                ..Default::default()
+            },
+            now,
+        )?;
+        ticket_balance.head_entry = Some(entry_info.key());
+
+        raffle.current_tickets = checked_add(raffle.current_tickets, gift.ticket_count)?;
+        if ticket_balance.ticket_count == 0 {
+            raffle.participant_count = checked_add(raffle.participant_count, 1)?;
+        }
+        ticket_balance.ticket_count = checked_add(ticket_balance.ticket_count, gift.ticket_count)?;
+        raffle.record_tier_discount(&mut ticket_balance, gift.ticket_count, unit_price)?;
+
+        record_top_holder(raffle, ctx.accounts.leaderboard.as_mut(), &ticket_balance)?;
+        ticket_balance.try_serialize(&mut &mut ticket_balance_info.try_borrow_mut_data()?[..])?;
+
+        let event_seq = raffle.next_event_seq()?;
+        emit!(TicketsPurchased {
+            raffle: raffle.key(),
+            buyer: recipient,
+            ticket_count: gift.ticket_count,
+            payment_amount: ticket_cost(gift.ticket_count, unit_price)?,
+            unit_price,
+            ticket_start_index,
+            entry_seed: gift.entry_seed,
+            campaign_discount: 0,
+            rent_sponsored: false,
+            memo: None,
+            referrer: None,
+            cumulative_tickets: ticket_balance.ticket_count,
+            cumulative_spend: ticket_balance.total_spent,
+            win_probability_bps: raffle.win_probability_bps(ticket_balance.ticket_count),
+            total_tickets: raffle.current_tickets,
+            event_seq,
+        });
+    }
+
+    LamportVault::new(&mut ctx.accounts.treasury, &ctx.accounts.system_program)
+        .collect(&ctx.accounts.signer.to_account_info(), total_payment)?;
+
+    let event_seq = ctx.accounts.raffle.next_event_seq()?;
+    emit!(TicketsGifted {
+        raffle: ctx.accounts.raffle.key(),
+        payer: ctx.accounts.signer.key(),
+        recipient_count: gifts.len() as u64,
+        ticket_count: total_tickets,
+        payment_amount: total_payment,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct GiftTicketsMulti<'info> {
+    /// The raffle the tickets are gifted in
+    /// Must be in Open state and not past end time
+    #[account(
+        mut,
+        constraint = raffle.raffle_state == RaffleState::Open @ RaffleError::RaffleNotOpen,
+        constraint = now(&config)? < raffle.end_time @ RaffleError::RaffleEnded,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    /// Treasury account that receives payment for tickets
+    #[account(
+        mut,
+        seeds = [
+            b"treasury",
+            raffle.key().as_ref(),
+        ],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    /// The account paying for the tickets and the created accounts
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Optional leaderboard of the raffle's largest holders, required if the raffle splits
+    /// its prize among top holders. Validated against the raffle in the instruction.
+    #[account(mut)]
+    pub leaderboard: Option<Account<'info, Leaderboard>>,
+
+    /// Required for creating the accounts and transferring the payment
+    pub system_program: Program<'info, System>,
+
+    /// Program config, checked for the pause switch
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ RaffleError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
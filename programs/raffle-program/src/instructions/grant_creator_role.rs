@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, CreatorRole, CREATOR_ROLE_ACCOUNT_SIZE},
+    time::now,
+};
+
+/// Event emitted when a creator role is granted or its limits are changed
+#[event]
+pub struct CreatorRoleGranted {
+    /// The wallet holding the role
+    pub creator: Pubkey,
+    /// Maximum number of the creator's raffles that can be open at once
+    pub max_active_raffles: u16,
+    /// Maximum ticket price of the creator's raffles
+    pub max_ticket_price: u64,
+}
+
+/// Instruction to allow a creator wallet to create raffles and update and extend the
+/// raffles it created, within the given limits
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `creator` - The wallet the role is granted to
+/// * `max_active_raffles` - Maximum number of the creator's raffles that can be open at
+///   once, counting every raffle until it is closed
+/// * `max_ticket_price` - Maximum ticket price of the creator's raffles, in lamports or
+///   base units of the payment mint
+///
+/// # Security Considerations
+/// - Only the management authority can grant creator roles
+/// - The role is a PDA with seeds ["creator_role", creator], so a creator holds at most
+///   one role
+///
+/// # Implementation Notes
+/// - Granting the role again replaces its limits, keeping the count of active raffles.
+///   Lowering a limit below the current count only blocks new raffles
+pub fn grant_creator_role(
+    ctx: Context<GrantCreatorRole>,
+    creator: Pubkey,
+    max_active_raffles: u16,
+    max_ticket_price: u64,
+) -> Result<()> {
+    let creator_role = &mut ctx.accounts.creator_role;
+    creator_role.creator = creator;
+    creator_role.max_active_raffles = max_active_raffles;
+    creator_role.max_ticket_price = max_ticket_price;
+    creator_role.granted_at = now(&ctx.accounts.config)?;
+    creator_role.bump = ctx.bumps.creator_role;
+
+    emit!(CreatorRoleGranted {
+        creator,
+        max_active_raffles,
+        max_ticket_price,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(creator: Pubkey)]
+pub struct GrantCreatorRole<'info> {
+    #[account(
+        init_if_needed,
+        payer = management_authority,
+        space = CREATOR_ROLE_ACCOUNT_SIZE,
+        seeds = [
+            b"creator_role",
+            creator.as_ref(),
+        ],
+        bump,
+    )]
+    pub creator_role: Account<'info, CreatorRole>,
+
+    #[account(mut)]
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub system_program: Program<'info, System>,
+}
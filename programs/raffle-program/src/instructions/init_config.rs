@@ -1,6 +1,26 @@
-use crate::state::{Config, CONFIG_ACCOUNT_SIZE};
+use crate::state::{
+    Config, CONFIG_ACCOUNT_SIZE, DEFAULT_ALARM_COVERAGE_BPS, DEFAULT_CLAIM_WINDOW_SECS,
+    DEFAULT_DISPUTE_BOND, DEFAULT_DISPUTE_WINDOW_SECS, ENCRYPTION_SCHEME_X25519, WINNER_DATA_FORMAT_V1,
+};
 use anchor_lang::prelude::*;
 
+/// Event emitted when the program config is initialized
+#[event]
+pub struct ConfigInitialized {
+    /// The pubkey of the config
+    pub config: Pubkey,
+    /// The program management authority
+    pub management_authority: Pubkey,
+    /// The program payout authority
+    pub payout_authority: Pubkey,
+    /// The program upgrade authority
+    pub upgrade_authority: Pubkey,
+    /// Default window for winners to claim their prize, in seconds
+    pub claim_window_secs: i64,
+    /// Default window for disputing a draw, in seconds
+    pub dispute_window_secs: i64,
+}
+
 /// Instruction to initialize the program configuration
 /// This should be called once during program deployment
 ///
@@ -8,8 +28,8 @@ use anchor_lang::prelude::*;
 /// - Creates a PDA with seed "config" to store program authority
 /// - Only needs to be called once during deployment
 /// - The caller of this instruction must be the owner of the program
-/// - The management authority will be set and locked
-/// - The payout authority will be set and locked
+/// - The management, payout and upgrade authorities can only be rotated by their
+///   current holder with propose_authority, followed by accept_authority
 ///
 /// # Account Validations
 /// * Config - New PDA initialized with proper space allocation
@@ -22,6 +42,58 @@ pub fn init_config(ctx: Context<InitConfig>) -> Result<()> {
     ctx.accounts.config.upgrade_authority = ctx.accounts.upgrade_authority.key();
     ctx.accounts.config.bump = ctx.bumps.config;
     ctx.accounts.config.raffle_counter = 0;
+    ctx.accounts.config.require_prize_deposit = false;
+    ctx.accounts.config.claim_window_secs = DEFAULT_CLAIM_WINDOW_SECS;
+    ctx.accounts.config.dispute_window_secs = DEFAULT_DISPUTE_WINDOW_SECS;
+    ctx.accounts.config.restrict_keepers = false;
+    ctx.accounts.config.allowed_metadata_hosts = Vec::new();
+    ctx.accounts.config.refund_fee_bps = 0;
+    ctx.accounts.config.payout_destinations = Vec::new();
+    ctx.accounts.config.supported_format_versions = vec![WINNER_DATA_FORMAT_V1];
+    ctx.accounts.config.supported_encryption_schemes = vec![ENCRYPTION_SCHEME_X25519];
+    ctx.accounts.config.region_attestor = None;
+    ctx.accounts.config.age_attestor = None;
+    ctx.accounts.config.pending_management_authority = None;
+    ctx.accounts.config.pending_payout_authority = None;
+    ctx.accounts.config.pending_upgrade_authority = None;
+    ctx.accounts.config.reject_cpi_purchases = false;
+    ctx.accounts.config.fee_bps = 0;
+    ctx.accounts.config.fee_recipient = None;
+    ctx.accounts.config.compact_events = false;
+    ctx.accounts.config.paused = false;
+    ctx.accounts.config.withdrawal_approver = None;
+    ctx.accounts.config.withdrawal_approval_threshold = 0;
+    ctx.accounts.config.crank_reward_bps = 0;
+    ctx.accounts.config.referral_fee_bps = 0;
+    ctx.accounts.config.alarm_coverage_bps = DEFAULT_ALARM_COVERAGE_BPS;
+    ctx.accounts.config.alarm_min_shortfall = 0;
+    ctx.accounts.config.max_raffle_extensions = 0;
+    ctx.accounts.config.max_extension_secs = 0;
+    ctx.accounts.config.refund_window_secs = 0;
+    ctx.accounts.config.insurance_fund = None;
+    ctx.accounts.config.creator_bond = 0;
+    ctx.accounts.config.large_purchase_threshold = 0;
+    ctx.accounts.config.lookup_table = None;
+    ctx.accounts.config.usd_price_feed = None;
+    ctx.accounts.config.notifier_program = None;
+    ctx.accounts.config.max_open_raffles = 0;
+    ctx.accounts.config.open_raffle_count = 0;
+    ctx.accounts.config.randomness_providers = Vec::new();
+    ctx.accounts.config.clock_offset = 0;
+    ctx.accounts.config.identity_issuer = None;
+    ctx.accounts.config.raffle_presets = Vec::new();
+    ctx.accounts.config.dispute_bond = DEFAULT_DISPUTE_BOND;
+    ctx.accounts.config.collectible_tree = None;
+
+    emit!(ConfigInitialized {
+        config: ctx.accounts.config.key(),
+        management_authority: ctx.accounts.config.management_authority,
+        payout_authority: ctx.accounts.config.payout_authority,
+        upgrade_authority: ctx.accounts.config.upgrade_authority,
+        claim_window_secs: DEFAULT_CLAIM_WINDOW_SECS,
+        dispute_window_secs: DEFAULT_DISPUTE_WINDOW_SECS,
+    });
+
     Ok(())
 }
 
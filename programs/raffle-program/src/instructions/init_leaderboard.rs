@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Leaderboard, Raffle, LEADERBOARD_ACCOUNT_SIZE},
+};
+
+/// Initializes the leaderboard of a raffle that splits its prize among its top holders.
+/// Ticket purchases of such a raffle require the leaderboard, so it has to be created
+/// before sales start. The account is PDA-derived using ["leaderboard", raffle_pubkey].
+///
+/// # Account Structure
+/// - `signer` (Signer): Pays for the leaderboard account
+/// - `leaderboard` (PDA): The account tracking the raffle's largest ticket holders
+///   - Seeds: ["leaderboard", raffle.key()]
+/// - `raffle` (Account): The raffle the leaderboard is created for
+/// - `system_program`: Required for account creation
+///
+/// # Access Control
+/// - Anyone can initialize the leaderboard, usually the raffle creator right after
+///   creating the raffle
+/// - One leaderboard per raffle
+pub fn init_leaderboard(ctx: Context<InitLeaderboard>) -> Result<()> {
+    require!(
+        ctx.accounts.raffle.top_holders.is_some(),
+        RaffleError::NoTopHoldersSplit
+    );
+
+    let leaderboard = &mut ctx.accounts.leaderboard;
+    leaderboard.raffle = ctx.accounts.raffle.key();
+    leaderboard.holders = Vec::new();
+    leaderboard.bump = ctx.bumps.leaderboard;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitLeaderboard<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = LEADERBOARD_ACCOUNT_SIZE,
+        seeds = [
+            b"leaderboard",
+            raffle.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub leaderboard: Account<'info, Leaderboard>,
+
+    pub raffle: Account<'info, Raffle>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Notifications, NOTIFICATIONS_ACCOUNT_SIZE};
+
+/// Initializes the win notifications of a wallet, to which `set_winner` appends a record
+/// whenever the wallet wins a prize. The account is PDA-derived using
+/// ["notifications", wallet].
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `wallet` - The wallet whose wins are recorded
+///
+/// # Account Structure
+/// - `signer` (Signer): Pays for the notifications account
+/// - `notifications` (PDA): The account collecting the wallet's recent wins
+///   - Seeds: ["notifications", wallet]
+/// - `system_program`: Required for account creation
+///
+/// # Access Control
+/// - Anyone can initialize the notifications of any wallet, so apps can sponsor the rent
+///   for their users
+/// - One notifications account per wallet
+pub fn init_notifications(ctx: Context<InitNotifications>, wallet: Pubkey) -> Result<()> {
+    let notifications = &mut ctx.accounts.notifications;
+    notifications.wallet = wallet;
+    notifications.total_count = 0;
+    notifications.records = Vec::new();
+    notifications.bump = ctx.bumps.notifications;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct InitNotifications<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = NOTIFICATIONS_ACCOUNT_SIZE,
+        seeds = [
+            b"notifications",
+            wallet.as_ref(),
+        ],
+        bump,
+    )]
+    pub notifications: Account<'info, Notifications>,
+
+    pub system_program: Program<'info, System>,
+}
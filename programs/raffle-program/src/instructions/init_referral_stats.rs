@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Raffle, ReferralStats, REFERRAL_STATS_ACCOUNT_SIZE};
+
+/// Initializes the referral stats of a referrer in a raffle, which `buy_tickets`
+/// accumulates the referred tickets and volume in. The account is PDA-derived using
+/// ["referral_stats", raffle, referrer].
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `referrer` - The wallet referring buyers to the raffle
+///
+/// # Account Structure
+/// - `signer` (Signer): Pays for the referral stats account
+/// - `raffle`: The raffle purchases are referred to
+/// - `referral_stats` (PDA): The account accumulating the referrer's purchases
+///   - Seeds: ["referral_stats", raffle.key(), referrer]
+/// - `system_program`: Required for account creation
+///
+/// # Access Control
+/// - Anyone can initialize the referral stats of any referrer, so referrers or the
+///   growth team can set up referral links without the buyer paying extra rent
+/// - One referral stats account per raffle and referrer
+pub fn init_referral_stats(ctx: Context<InitReferralStats>, referrer: Pubkey) -> Result<()> {
+    let referral_stats = &mut ctx.accounts.referral_stats;
+    referral_stats.raffle = ctx.accounts.raffle.key();
+    referral_stats.referrer = referrer;
+    referral_stats.ticket_count = 0;
+    referral_stats.volume = 0;
+    referral_stats.fees_owed = 0;
+    referral_stats.fees_paid = 0;
+    referral_stats.bump = ctx.bumps.referral_stats;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(referrer: Pubkey)]
+pub struct InitReferralStats<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub raffle: Account<'info, Raffle>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = REFERRAL_STATS_ACCOUNT_SIZE,
+        seeds = [
+            b"referral_stats",
+            raffle.key().as_ref(),
+            referrer.as_ref(),
+        ],
+        bump,
+    )]
+    pub referral_stats: Account<'info, ReferralStats>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, RentPool, RENT_POOL_ACCOUNT_SIZE},
+};
+
+/// Instruction to initialize the config-level rent pool that sponsors entry rent
+///
+/// # Security Considerations
+/// - Creates a PDA with seed "rent_pool", so only one pool can exist
+/// - Only the management authority can initialize the pool
+/// - The pool starts out disabled
+pub fn init_rent_pool(ctx: Context<InitRentPool>) -> Result<()> {
+    ctx.accounts.rent_pool.enabled = false;
+    ctx.accounts.rent_pool.total_reimbursed = 0;
+    ctx.accounts.rent_pool.bump = ctx.bumps.rent_pool;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitRentPool<'info> {
+    #[account(
+        init,
+        payer = management_authority,
+        space = RENT_POOL_ACCOUNT_SIZE,
+        seeds = [b"rent_pool"],
+        bump
+    )]
+    pub rent_pool: Account<'info, RentPool>,
+
+    #[account(mut)]
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -1,49 +1,174 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{
+    prelude::*,
+    solana_program::sysvar::instructions::{
+        self as instructions_sysvar, load_instruction_at_checked,
+    },
+};
+use anchor_spl::token::TokenAccount;
 
 use crate::{
     error::RaffleError,
-    state::{Raffle, RaffleState, TicketBalance, TICKET_BALANCE_ACCOUNT_SIZE},
+    merkle::{allowlist_leaf, verify_proof},
+    state::{Gate, Raffle, RaffleState, TicketBalance, TICKET_BALANCE_ACCOUNT_SIZE},
 };
 
+/// Event emitted when a wallet initializes its ticket balance in a raffle
+#[event]
+pub struct TicketBalanceInitialized {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The initialized ticket balance
+    pub ticket_balance: Pubkey,
+    /// The wallet owning the ticket balance
+    pub owner: Pubkey,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
 /// Initializes a new ticket balance account for a user in a specific raffle.
 /// This account tracks how many tickets a user owns in a particular raffle.
 /// The account is PDA-derived using ["ticket_balance", raffle_pubkey, user_pubkey].
 ///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `allowlist_proof` - Merkle proof of the signer's allowlist leaf, required if the
+///   raffle is gated to an allowlist
+///
 /// # Events
-/// None
+/// - `TicketBalanceInitialized` with the new ticket balance and its owner
 ///
 /// # Account Structure
 /// - `signer` (Signer): The user who will own the ticket balance account
 /// - `ticket_balance` (PDA): The account to store the user's ticket balance
 ///   - Seeds: ["ticket_balance", raffle.key(), signer.key()]
-///   - Space: 8 (discriminator) + 32 (owner) + 8 (ticket_count) + 1 (bump) = 49 bytes
-/// - `raffle` (Account): The raffle account this ticket balance is associated with
+///   - Space: 8 (discriminator) + 32 (owner) + 8 (ticket_count) + 1 (bump)
+///     + 33 (head_entry) = 82 bytes
+/// - `raffle` (Account): The raffle account this ticket balance is associated with,
+///   mutable to advance its event sequence
+/// - `gate_token_account` (Optional): The signer's token account of the gate mint,
+///   required if the raffle is gated to token holders
+/// - `instructions_sysvar`: The instructions sysvar, inspected for other instructions
+///   of the transaction touching the gate token account
 /// - `system_program`: Required for account creation
 ///
 /// # State Changes
 /// - Creates a new `TicketBalance` account
 /// - Initializes owner to signer's pubkey
 /// - Sets initial ticket_count to 0
+/// - Starts with an empty list of entries
 /// - Stores the PDA bump
 ///
 /// # Access Control
-/// - Anyone can initialize their own ticket balance account
-/// - One ticket balance account per user per raffle
+/// - Anyone can initialize their own ticket balance account, unless the raffle is gated
+/// - Gated to token holders: the signer's token account must hold at least the gate's
+///   minimum amount of its mint, and no instruction of the transaction may write to
+///   that token account, so the gate can't be passed with tokens borrowed for the
+///   duration of the transaction, e.g. from a flash loan
+/// - Gated to an allowlist: the proof must show the signer's leaf is part of the gate's
+///   Merkle root
+/// - One ticket balance account per user per raffle, so the gate applies to every
+///   purchase of the wallet
 ///
 /// # Lifecycle
 /// - Account is created when user wants to participate in a raffle
 /// - Account is automatically closed when expired tickets are reclaimed
-pub fn init_ticket_balance(ctx: Context<InitTicketBalance>) -> Result<()> {
+pub fn init_ticket_balance(
+    ctx: Context<InitTicketBalance>,
+    allowlist_proof: Option<Vec<[u8; 32]>>,
+) -> Result<()> {
     // Verify raffle is in active state
     require!(
         ctx.accounts.raffle.raffle_state == RaffleState::Open,
         RaffleError::RaffleNotOpen
     );
 
+    // Only let wallets meeting the raffle's gate participate, if it is gated
+    validate_gate(
+        &ctx.accounts.raffle,
+        ctx.accounts.gate_token_account.as_ref(),
+        allowlist_proof.as_deref(),
+        &ctx.accounts.signer,
+        &ctx.accounts.instructions_sysvar,
+    )?;
+
     let ticket_balance = &mut ctx.accounts.ticket_balance;
     ticket_balance.owner = ctx.accounts.signer.key();
     ticket_balance.ticket_count = 0;
     ticket_balance.bump = ctx.bumps.ticket_balance;
+    ticket_balance.head_entry = None;
+    ticket_balance.total_spent = 0;
+    ticket_balance.tier_discount = 0;
+    ticket_balance.rollover = false;
+    ticket_balance.winner_commitment = None;
+    ticket_balance.campaign_subsidy = 0;
+    ticket_balance.subsidy_campaign = None;
+
+    let raffle = &mut ctx.accounts.raffle;
+    let event_seq = raffle.next_event_seq()?;
+    emit!(TicketBalanceInitialized {
+        raffle: raffle.key(),
+        ticket_balance: ticket_balance.key(),
+        owner: ticket_balance.owner,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+/// Ensures the signer meets the raffle's gate, by holding enough of the gate mint in
+/// `gate_token_account` or by proving its allowlist membership with `allowlist_proof`
+fn validate_gate(
+    raffle: &Raffle,
+    gate_token_account: Option<&Account<TokenAccount>>,
+    allowlist_proof: Option<&[[u8; 32]]>,
+    signer: &Signer,
+    instructions_sysvar: &UncheckedAccount,
+) -> Result<()> {
+    match &raffle.gate {
+        None => {}
+        Some(Gate::TokenHolder { mint, min_amount }) => {
+            let token_account = gate_token_account.ok_or(RaffleError::GateTokenRequired)?;
+            require!(
+                token_account.owner == signer.key()
+                    && token_account.mint == *mint
+                    && token_account.amount >= *min_amount,
+                RaffleError::GateTokenRequired
+            );
+            require_settled_balance(instructions_sysvar, &token_account.key())?;
+        }
+        Some(Gate::MerkleAllowlist { root }) => {
+            let allowlist_proof = allowlist_proof.ok_or(RaffleError::NotAllowlisted)?;
+            require!(
+                verify_proof(allowlist_leaf(&signer.key()), allowlist_proof, root),
+                RaffleError::NotAllowlisted
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Ensures no instruction of the transaction writes to `token_account`. Tokens moved
+/// into the account for the transaction, e.g. by a flash loan, and out again by a later
+/// instruction would otherwise pass the gate. The flags recorded in the instructions
+/// sysvar are those of the whole transaction, and programs can only move tokens in CPIs
+/// through accounts passed to their top-level instruction, so inner instructions are
+/// covered as well.
+fn require_settled_balance(
+    instructions_sysvar: &UncheckedAccount,
+    token_account: &Pubkey,
+) -> Result<()> {
+    let mut index = 0;
+    while let Ok(instruction) = load_instruction_at_checked(index, instructions_sysvar) {
+        require!(
+            !instruction
+                .accounts
+                .iter()
+                .any(|meta| meta.pubkey == *token_account && meta.is_writable),
+            RaffleError::GateBalanceNotSettled
+        );
+        index += 1;
+    }
 
     Ok(())
 }
@@ -66,6 +191,16 @@ pub struct InitTicketBalance<'info> {
     )]
     pub ticket_balance: Account<'info, TicketBalance>,
 
+    #[account(mut)]
     pub raffle: Account<'info, Raffle>,
+
+    /// Optional token account of the signer holding the gate mint, required if the raffle
+    /// is gated to token holders. Validated against the gate in the instruction.
+    pub gate_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: The instructions sysvar, inspected by token holder gates
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
     pub system_program: Program<'info, System>,
 }
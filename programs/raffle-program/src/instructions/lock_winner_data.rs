@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, Raffle, RaffleState, WinnerData},
+    time::now,
+};
+
+/// Event emitted when the winner data of a raffle becomes final
+#[event]
+pub struct WinnerDataLocked {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The winner whose data was locked
+    pub winner: Pubkey,
+    /// The timestamp when the data was locked
+    pub locked_at: i64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to lock the winner data of a raffle, signalling to the fulfillment team
+/// that the data is final
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Validates the raffle is in Claimed state
+/// 2. Ensures the data belongs to the raffle's winner
+/// 3. Only the winner can lock the data while the edit window is open. Anyone can lock
+///    it afterwards, as it can no longer change anyway
+/// 4. Ensures the data is not locked already
+///
+/// # Account Validations
+/// * Raffle - Must be in Claimed state
+/// * Winner - Must be the winner stored in the raffle
+/// * WinnerData - PDA with seeds ["winner_data", raffle_key, winner]
+pub fn lock_winner_data(ctx: Context<LockWinnerData>) -> Result<()> {
+    let locked_at = now(&ctx.accounts.config)?;
+    let winner_data = &mut ctx.accounts.winner_data;
+    require!(!winner_data.locked, RaffleError::WinnerDataLocked);
+    require!(
+        ctx.accounts.signer.key() == ctx.accounts.winner.key() || !winner_data.editable(locked_at),
+        RaffleError::WinnerDataEditWindowOpen
+    );
+
+    winner_data.locked = true;
+
+    let event_seq = ctx.accounts.raffle.next_event_seq()?;
+    emit!(WinnerDataLocked {
+        raffle: ctx.accounts.raffle.key(),
+        winner: ctx.accounts.winner.key(),
+        locked_at,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct LockWinnerData<'info> {
+    #[account(
+        mut,
+        constraint = raffle.raffle_state == RaffleState::Claimed @ RaffleError::RaffleNotClaimed,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    /// CHECK: Only used to derive the winner data PDA, checked against the raffle's winners
+    #[account(
+        constraint = raffle.winner_indices(&winner.key()) != 0 @ RaffleError::NotWinner,
+    )]
+    pub winner: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"winner_data",
+            raffle.key().as_ref(),
+            winner.key().as_ref(),
+        ],
+        bump
+    )]
+    pub winner_data: Account<'info, WinnerData>,
+
+    /// The winner, or anyone once the edit window has passed
+    pub signer: Signer<'info>,
+
+    /// Program config, storing the clock offset of devnet builds
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{
+        Entry, EntryV1, ENTRY_ACCOUNT_SIZE, ENTRY_DISCRIMINATOR, ENTRY_V1_ACCOUNT_SIZE,
+        ENTRY_VERSION,
+    },
+    transfer::{rent_reserve, transfer_lamports},
+};
+
+/// Event emitted when a legacy entry is migrated to the current layout
+#[event]
+pub struct EntryMigrated {
+    /// The pubkey of the migrated entry
+    pub entry: Pubkey,
+    /// The raffle the entry belongs to
+    pub raffle: Pubkey,
+    /// Layout version the entry was migrated to
+    pub version: u8,
+}
+
+/// Instruction to upgrade an entry created with the v1 layout of the first deployed
+/// program in place, growing the account to the current layout so in-flight raffles
+/// keep working with the upgraded program. Can be called by anyone.
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Only accounts owned by the program with the entry discriminator and the v1 size
+///    can be migrated, so current entries are never rewritten
+/// 2. The account must be the entry PDA of its raffle and seed, whose bump is recorded
+/// 3. Every v1 field is carried over unchanged, so migrating never alters ownership or
+///    ticket ranges
+///
+/// # Account Validations
+/// * Entry - Legacy entry owned by the program, validated manually
+/// * Payer - Funds the rent of the grown account
+///
+/// # Implementation Notes
+/// - v1 entries didn't record their creation time, so migrated entries record 0
+/// - v1 entries predate rent sponsoring, memos, the owner's list of entries and
+///   deferred numbering, so migrated entries are unsponsored, have no memo, aren't
+///   linked from the owner's ticket balance and record entry index 0, which is unused
+///   as their raffles number tickets at purchase
+/// - The added rent is returned along with the rest of the entry's rent when it is
+///   closed
+pub fn migrate_entry_v1_to_v2(ctx: Context<MigrateEntryV1ToV2>) -> Result<()> {
+    let entry_info = ctx.accounts.entry.to_account_info();
+    let legacy = {
+        let data = entry_info.try_borrow_data()?;
+        require!(
+            data.len() == ENTRY_V1_ACCOUNT_SIZE && data[..8] == *ENTRY_DISCRIMINATOR,
+            RaffleError::EntryNotLegacy
+        );
+        EntryV1::deserialize(&mut &data[8..])?
+    };
+
+    let (address, bump) = Pubkey::find_program_address(
+        &[b"entry", legacy.raffle.as_ref(), legacy.seed.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(entry_info.key(), address, RaffleError::EntryNotLegacy);
+
+    // Fund the rent of the added fields before growing the account
+    let rent_due = rent_reserve(ENTRY_ACCOUNT_SIZE)?.saturating_sub(entry_info.lamports());
+    if rent_due > 0 {
+        transfer_lamports(
+            &ctx.accounts.payer.to_account_info(),
+            &entry_info,
+            &ctx.accounts.system_program.to_account_info(),
+            rent_due,
+        )?;
+    }
+    entry_info.realloc(ENTRY_ACCOUNT_SIZE, true)?;
+
+    let entry = Entry {
+        raffle: legacy.raffle,
+        owner: legacy.owner,
+        ticket_count: legacy.ticket_count,
+        ticket_start_index: legacy.ticket_start_index,
+        seed: legacy.seed,
+        rent_sponsored: false,
+        memo: None,
+        next_entry: None,
+        entry_index: 0,
+        version: ENTRY_VERSION,
+        bump,
+        created_at: 0,
+    };
+    entry.try_serialize(&mut &mut entry_info.try_borrow_mut_data()?[..])?;
+
+    emit!(EntryMigrated {
+        entry: entry_info.key(),
+        raffle: entry.raffle,
+        version: ENTRY_VERSION,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigrateEntryV1ToV2<'info> {
+    /// The legacy entry to migrate
+    /// CHECK: The discriminator, size and PDA address are validated in the instruction,
+    /// as a v1 entry doesn't deserialize as the current Entry
+    #[account(mut, owner = crate::ID @ RaffleError::EntryNotLegacy)]
+    pub entry: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -1,21 +1,303 @@
+pub use accept_authority::*;
+pub use approve_withdrawal::*;
+pub use attest_age::*;
+pub use attest_identity::*;
+pub use attest_region::*;
+pub use buy_bundle::*;
 pub use buy_tickets::*;
+pub use cancel_emergency_recovery::*;
+pub use cancel_raffle::*;
+pub use cast_vote::*;
+pub use check_invariants::*;
+pub use claim_participation_proof::*;
+pub use claim_prize::*;
+pub use claim_refund_with_proof::*;
+pub use claim_ticket_collectible::*;
+pub use clone_raffle::*;
+pub use close_entries::*;
+pub use close_entry::*;
+pub use close_purchase_dedupes::*;
+pub use close_raffle::*;
+pub use close_winner_data::*;
+pub use commit_draw_slot::*;
+pub use commit_winner_secret::*;
+pub use confirm_reservation::*;
+pub use consolidate_treasuries::*;
+pub use create_bundle::*;
+pub use create_campaign::*;
+pub use create_lookup_table::*;
 pub use create_raffle::*;
+pub use create_raffle_with_preset::*;
+pub use deposit_prize::*;
 pub use draw_winning_ticket::*;
+pub use emergency_recover::*;
+pub use expire_many::*;
 pub use expire_raffle::*;
+pub use extend_entry::*;
+pub use extend_lookup_table::*;
+pub use extend_raffle::*;
+pub use finalize_numbering::*;
+pub use finalize_raffle::*;
+pub use finalize_results::*;
+pub use find_entry_for_ticket::*;
+pub use force_expire::*;
+pub use fulfill_randomness::*;
+pub use fund_rent_pool::*;
+pub use get_claim_instructions::*;
+pub use get_positions::*;
+pub use get_treasury_statement::*;
+pub use get_withdrawal_preview::*;
+pub use gift_tickets_multi::*;
+pub use grant_creator_role::*;
 pub use init_config::*;
+pub use init_leaderboard::*;
+pub use init_notifications::*;
+pub use init_referral_stats::*;
+pub use init_rent_pool::*;
 pub use init_ticket_balance::*;
+pub use lock_winner_data::*;
+pub use migrate_entry_v1_to_v2::*;
+pub use open_dispute::*;
+pub use open_vote::*;
+pub use pay_top_holders::*;
+pub use pay_winner::*;
+pub use propose_authority::*;
+pub use prove_entry::*;
 pub use reclaim_expired_tickets::*;
+pub use reclaim_vote::*;
+pub use record_draw_value::*;
+pub use register_keeper::*;
+pub use register_randomness_provider::*;
+pub use release_creator_bond::*;
+pub use release_expired_reservation::*;
+pub use release_prize::*;
+pub use remove_keeper::*;
+pub use report_heartbeat::*;
+pub use request_emergency_recovery::*;
+pub use request_randomness::*;
+pub use reserve_tickets::*;
+pub use reset_draw::*;
+pub use resolve_dispute::*;
+pub use revoke_creator_role::*;
+pub use rollover_tickets::*;
+pub use set_access_code::*;
+pub use set_age_attestor::*;
+pub use set_age_restricted::*;
+pub use set_alarm_thresholds::*;
+pub use set_allowed_regions::*;
+pub use set_arweave_mirror::*;
+pub use set_claim_instructions::*;
+pub use set_claim_windows::*;
+pub use set_clock_offset::*;
+pub use set_collectible_tree::*;
+pub use set_compact_events::*;
+pub use set_cpi_purchases_rejected::*;
+pub use set_creator_bond::*;
+pub use set_crank_reward::*;
+pub use set_deferred_numbering::*;
+pub use set_dispute_bond::*;
+pub use set_draw_authority::*;
+pub use set_draw_delay::*;
+pub use set_extension_limits::*;
+pub use set_funding_deadline::*;
+pub use set_gate::*;
+pub use set_identity_issuer::*;
+pub use set_keepers_restricted::*;
+pub use set_large_purchase_threshold::*;
+pub use set_max_open_raffles::*;
+pub use set_metadata_hosts::*;
+pub use set_notifier_program::*;
+pub use set_operator_status::*;
+pub use set_pause::*;
+pub use set_payout_destinations::*;
+pub use set_platform_fee::*;
+pub use set_priority_access::*;
+pub use set_prize_deposit_required::*;
+pub use set_prize_info::*;
+pub use set_raffle_fee::*;
+pub use set_raffle_preset::*;
+pub use set_randomness_mode::*;
+pub use set_randomness_provider::*;
+pub use set_referral_fee::*;
+pub use set_refund_fee::*;
+pub use set_refund_mint::*;
+pub use set_refund_window::*;
+pub use set_region_attestor::*;
+pub use set_rent_pool_enabled::*;
+pub use set_successor_raffle::*;
+pub use set_ticket_receipts::*;
+pub use set_usd_price_feed::*;
+pub use set_user_tier::*;
 pub use set_winner::*;
+pub use set_winner_count::*;
+pub use set_winner_data_formats::*;
+pub use set_withdrawal_approver::*;
+pub use settle_randomness::*;
+pub use settle_sponsor_match::*;
+pub use simulate_draw::*;
+pub use slash_creator_bond::*;
+pub use snapshot_raffle::*;
+pub use sponsor_match::*;
+pub use submit_winner_attestation::*;
 pub use submit_winner_data::*;
+pub use sweep_treasury_dust::*;
+pub use sweep_unclaimed_refunds::*;
+pub use tally_vote::*;
+pub use transfer_entry::*;
+pub use update_max_tickets::*;
+pub use update_raffle::*;
+pub use update_winner_data::*;
+pub use validate_raffle_params::*;
 pub use withdraw_from_treasury::*;
 
+pub mod accept_authority;
+pub mod approve_withdrawal;
+pub mod attest_age;
+pub mod attest_identity;
+pub mod attest_region;
+pub mod buy_bundle;
 pub mod buy_tickets;
+pub mod cancel_emergency_recovery;
+pub mod cancel_raffle;
+pub mod cast_vote;
+pub mod check_invariants;
+pub mod claim_participation_proof;
+pub mod claim_prize;
+pub mod claim_refund_with_proof;
+pub mod claim_ticket_collectible;
+pub mod clone_raffle;
+pub mod close_entries;
+pub mod close_entry;
+pub mod close_purchase_dedupes;
+pub mod close_raffle;
+pub mod close_winner_data;
+pub mod commit_draw_slot;
+pub mod commit_winner_secret;
+pub mod confirm_reservation;
+pub mod consolidate_treasuries;
+pub mod create_bundle;
+pub mod create_campaign;
+pub mod create_lookup_table;
 pub mod create_raffle;
+pub mod create_raffle_with_preset;
+pub mod deposit_prize;
 pub mod draw_winning_ticket;
+pub mod emergency_recover;
+pub mod expire_many;
 pub mod expire_raffle;
+pub mod extend_entry;
+pub mod extend_lookup_table;
+pub mod extend_raffle;
+pub mod finalize_numbering;
+pub mod finalize_raffle;
+pub mod finalize_results;
+pub mod find_entry_for_ticket;
+pub mod force_expire;
+pub mod fulfill_randomness;
+pub mod fund_rent_pool;
+pub mod get_claim_instructions;
+pub mod get_positions;
+pub mod get_treasury_statement;
+pub mod get_withdrawal_preview;
+pub mod gift_tickets_multi;
+pub mod grant_creator_role;
 pub mod init_config;
+pub mod init_leaderboard;
+pub mod init_notifications;
+pub mod init_referral_stats;
+pub mod init_rent_pool;
 pub mod init_ticket_balance;
+pub mod lock_winner_data;
+pub mod migrate_entry_v1_to_v2;
+pub mod open_dispute;
+pub mod open_vote;
+pub mod pay_top_holders;
+pub mod pay_winner;
+pub mod propose_authority;
+pub mod prove_entry;
 pub mod reclaim_expired_tickets;
+pub mod reclaim_vote;
+pub mod record_draw_value;
+pub mod register_keeper;
+pub mod register_randomness_provider;
+pub mod release_creator_bond;
+pub mod release_expired_reservation;
+pub mod release_prize;
+pub mod remove_keeper;
+pub mod report_heartbeat;
+pub mod request_emergency_recovery;
+pub mod request_randomness;
+pub mod reserve_tickets;
+pub mod reset_draw;
+pub mod resolve_dispute;
+pub mod revoke_creator_role;
+pub mod rollover_tickets;
+pub mod set_access_code;
+pub mod set_age_attestor;
+pub mod set_age_restricted;
+pub mod set_alarm_thresholds;
+pub mod set_allowed_regions;
+pub mod set_arweave_mirror;
+pub mod set_claim_instructions;
+pub mod set_claim_windows;
+pub mod set_clock_offset;
+pub mod set_collectible_tree;
+pub mod set_compact_events;
+pub mod set_cpi_purchases_rejected;
+pub mod set_creator_bond;
+pub mod set_crank_reward;
+pub mod set_deferred_numbering;
+pub mod set_dispute_bond;
+pub mod set_draw_authority;
+pub mod set_draw_delay;
+pub mod set_extension_limits;
+pub mod set_funding_deadline;
+pub mod set_gate;
+pub mod set_identity_issuer;
+pub mod set_keepers_restricted;
+pub mod set_large_purchase_threshold;
+pub mod set_max_open_raffles;
+pub mod set_metadata_hosts;
+pub mod set_notifier_program;
+pub mod set_operator_status;
+pub mod set_pause;
+pub mod set_payout_destinations;
+pub mod set_platform_fee;
+pub mod set_priority_access;
+pub mod set_prize_deposit_required;
+pub mod set_prize_info;
+pub mod set_raffle_fee;
+pub mod set_raffle_preset;
+pub mod set_randomness_mode;
+pub mod set_randomness_provider;
+pub mod set_referral_fee;
+pub mod set_refund_fee;
+pub mod set_refund_mint;
+pub mod set_refund_window;
+pub mod set_region_attestor;
+pub mod set_rent_pool_enabled;
+pub mod set_successor_raffle;
+pub mod set_ticket_receipts;
+pub mod set_usd_price_feed;
+pub mod set_user_tier;
 pub mod set_winner;
+pub mod set_winner_count;
+pub mod set_winner_data_formats;
+pub mod set_withdrawal_approver;
+pub mod settle_randomness;
+pub mod settle_sponsor_match;
+pub mod simulate_draw;
+pub mod slash_creator_bond;
+pub mod snapshot_raffle;
+pub mod sponsor_match;
+pub mod submit_winner_attestation;
 pub mod submit_winner_data;
+pub mod sweep_treasury_dust;
+pub mod sweep_unclaimed_refunds;
+pub mod tally_vote;
+pub mod transfer_entry;
+pub mod update_max_tickets;
+pub mod update_raffle;
+pub mod update_winner_data;
+pub mod validate_raffle_params;
 pub mod withdraw_from_treasury;
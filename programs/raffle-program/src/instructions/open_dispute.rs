@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, Dispute, Raffle, DISPUTE_ACCOUNT_SIZE},
+    time::now,
+    transfer::transfer_lamports,
+};
+
+/// Event emitted when a raffle's draw is disputed
+#[event]
+pub struct DisputeOpened {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The wallet that opened the dispute
+    pub disputer: Pubkey,
+    /// Bonded lamports, excluding the rent
+    pub amount: u64,
+    /// Hash of the disputer's statement of the grounds for the dispute
+    pub reason_hash: [u8; 32],
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to dispute a raffle's draw by bonding lamports, which the management
+/// authority refunds if it upholds the dispute with resolve_dispute and slashes
+/// otherwise. Can be called by any wallet.
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `reason_hash` - Hash of the disputer's off-chain statement of the grounds for the
+///   dispute
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Ensures the raffle was drawn, and that the config's dispute window since the draw
+///    has not closed
+/// 2. Bonds the lamports required by the config at the time the dispute is opened, so
+///    later changes to the bond don't affect open disputes
+/// 3. Allows a single dispute per wallet and raffle, as the dispute is a PDA of both
+///
+/// # Account Validations
+/// * Raffle - Must have been drawn
+/// * Dispute - New PDA with seeds ["dispute", raffle_key, disputer]
+/// * Disputer - Pays the bond and the rent of the dispute
+/// * Config - PDA storing the dispute window and bond
+pub fn open_dispute(ctx: Context<OpenDispute>, reason_hash: [u8; 32]) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let draw_timestamp = ctx
+        .accounts
+        .raffle
+        .draw_timestamp
+        .ok_or(RaffleError::RaffleNotDrawn)?;
+    let current_time = now(config)?;
+    require!(
+        current_time <= draw_timestamp.saturating_add(config.dispute_window_secs),
+        RaffleError::DisputeWindowClosed
+    );
+
+    let amount = config.dispute_bond;
+    transfer_lamports(
+        &ctx.accounts.disputer.to_account_info(),
+        &ctx.accounts.dispute.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        amount,
+    )?;
+
+    let dispute = &mut ctx.accounts.dispute;
+    dispute.raffle = ctx.accounts.raffle.key();
+    dispute.disputer = ctx.accounts.disputer.key();
+    dispute.amount = amount;
+    dispute.reason_hash = reason_hash;
+    dispute.opened_at = current_time;
+    dispute.bump = ctx.bumps.dispute;
+
+    let raffle = &mut ctx.accounts.raffle;
+    let event_seq = raffle.next_event_seq()?;
+    emit!(DisputeOpened {
+        raffle: raffle.key(),
+        disputer: ctx.accounts.disputer.key(),
+        amount,
+        reason_hash,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct OpenDispute<'info> {
+    #[account(mut)]
+    pub raffle: Account<'info, Raffle>,
+
+    #[account(
+        init,
+        payer = disputer,
+        space = DISPUTE_ACCOUNT_SIZE,
+        seeds = [
+            b"dispute",
+            raffle.key().as_ref(),
+            disputer.key().as_ref(),
+        ],
+        bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(mut)]
+    pub disputer: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,165 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token},
+};
+
+use crate::{
+    error::RaffleError,
+    state::{
+        Ballot, Config, Entry, Raffle, RaffleState, RandomnessMode, BALLOT_ACCOUNT_SIZE,
+        MAX_SHORTLIST,
+    },
+    time::{now, require_seconds},
+    treasury::open_token_vault,
+};
+
+/// Event emitted when the community vote of a raffle is opened
+#[event]
+pub struct VoteOpened {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// Mint of the tokens votes are weighted by
+    pub vote_mint: Pubkey,
+    /// Entries the winner is chosen from
+    pub shortlist: Vec<Pubkey>,
+    /// Time after which the vote can be tallied
+    pub voting_ends_at: i64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to shortlist entries of a raffle drawn by votes and open the vote
+/// deciding which of them wins
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts, with the shortlisted
+///   entries as remaining accounts
+/// * `voting_ends_at` - Time after which no more votes are accepted
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Only the management authority can shortlist entries
+/// 2. Ensures the raffle is decided by votes, its sale ended and it met its threshold,
+///    so the vote only decides raffles that would otherwise be drawn
+/// 3. Validates that every shortlisted entry belongs to the raffle, at most once
+///
+/// # Account Validations
+/// * Raffle - Must be in Open state with the Vote randomness mode
+/// * Ballot - New PDA with seeds ["ballot", raffle_key], so each raffle has one vote
+/// * BallotTokenAccount - Associated token account of the ballot for the vote mint,
+///   created here to lock the voters' tokens
+///
+/// # Implementation Notes
+/// - Raffles decided by votes have a single winner, as a vote ranks one entry first
+/// - Numbering must be finalized, as the winning ticket is the first ticket of the
+///   winning entry
+pub fn open_vote<'info>(
+    ctx: Context<'_, '_, 'info, 'info, OpenVote<'info>>,
+    voting_ends_at: i64,
+) -> Result<()> {
+    let raffle = &mut ctx.accounts.raffle;
+    let now = now(&ctx.accounts.config)?;
+    require!(now >= raffle.end_time, RaffleError::RaffleNotEnded);
+    require!(raffle.threshold_met()?, RaffleError::InsufficientTickets);
+    require!(raffle.winner_count == 1, RaffleError::InvalidWinnerCount);
+    require!(
+        raffle.numbering_finalized(),
+        RaffleError::NumberingNotFinalized
+    );
+    require_seconds(voting_ends_at)?;
+    require!(voting_ends_at > now, RaffleError::InvalidVotingWindow);
+
+    let remaining_accounts = ctx.remaining_accounts;
+    require!(
+        !remaining_accounts.is_empty() && remaining_accounts.len() <= MAX_SHORTLIST,
+        RaffleError::InvalidShortlist
+    );
+    let mut shortlist = Vec::with_capacity(remaining_accounts.len());
+    for entry_info in remaining_accounts {
+        let entry = Account::<Entry>::try_from(entry_info)?;
+        require_keys_eq!(entry.raffle, raffle.key(), RaffleError::EntryRaffleMismatch);
+        require!(
+            !shortlist.contains(&entry_info.key()),
+            RaffleError::InvalidShortlist
+        );
+        shortlist.push(entry_info.key());
+    }
+
+    let ballot = &mut ctx.accounts.ballot;
+    ballot.raffle = raffle.key();
+    ballot.vote_mint = ctx.accounts.vote_mint.key();
+    ballot.tallies = vec![0; shortlist.len()];
+    ballot.shortlist = shortlist;
+    ballot.voting_ends_at = voting_ends_at;
+    ballot.bump = ctx.bumps.ballot;
+
+    // Open the token account locking the voters' tokens until voting ends
+    open_token_vault(
+        &ctx.accounts.management_authority,
+        &ctx.accounts.ballot.to_account_info(),
+        Some(&ctx.accounts.vote_mint),
+        Some(&ctx.accounts.ballot_token_account),
+        Some(&ctx.accounts.token_program),
+        Some(&ctx.accounts.associated_token_program),
+        &ctx.accounts.system_program,
+    )?;
+
+    let event_seq = raffle.next_event_seq()?;
+    emit!(VoteOpened {
+        raffle: raffle.key(),
+        vote_mint: ctx.accounts.ballot.vote_mint,
+        shortlist: ctx.accounts.ballot.shortlist.clone(),
+        voting_ends_at,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct OpenVote<'info> {
+    #[account(
+        mut,
+        constraint = raffle.raffle_state == RaffleState::Open @ RaffleError::RaffleNotOpen,
+        constraint = raffle.randomness_mode == RandomnessMode::Vote @ RaffleError::VoteDrawDisabled,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    #[account(
+        init,
+        payer = management_authority,
+        space = BALLOT_ACCOUNT_SIZE,
+        seeds = [
+            b"ballot",
+            raffle.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub ballot: Account<'info, Ballot>,
+
+    #[account(mut)]
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Mint of the tokens votes are weighted by
+    pub vote_mint: Account<'info, Mint>,
+
+    /// Associated token account of the ballot for the vote mint
+    /// CHECK: Created by the associated token program, which only accepts the address
+    /// derived from the ballot and the mint
+    #[account(mut)]
+    pub ballot_token_account: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+}
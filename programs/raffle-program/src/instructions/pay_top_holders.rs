@@ -0,0 +1,171 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    math::{bps_of, checked_add, checked_sub},
+    state::{Config, Leaderboard, Raffle, RaffleState, Treasury},
+    treasury::{LamportVault, TreasuryVault},
+};
+
+/// Event emitted when the cash prize of a raffle is split among its top holders
+#[event]
+pub struct TopHoldersPaid {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The holders receiving a share of the cash prize, in leaderboard order
+    pub holders: Vec<Pubkey>,
+    /// Lamports paid to each holder
+    pub amounts: Vec<u64>,
+    /// Lamports paid to the payout authority, including rounding remainders
+    pub payout_amount: u64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to pay out the treasury of a raffle that splits its cash prize among its
+/// largest ticket holders. The raffle's `cash_prize_bps` share is divided among the
+/// holders on the leaderboard in proportion to their tickets, and the remainder is sent
+/// to the payout authority. Can be called by anyone.
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Validates the raffle splits its prize among top holders
+/// 2. Validates ticket sales have closed, i.e. the raffle has been drawn
+/// 3. Ensures the remaining accounts are exactly the holders on the leaderboard
+/// 4. Limits the payout to collected funds that were not yet refunded or withdrawn
+///
+/// # Account Validations
+/// * Raffle - Must split its prize among top holders and be Drawing, Drawn or Claimed
+/// * Leaderboard - PDA with seeds ["leaderboard", raffle_key]
+/// * Treasury - PDA with seeds ["treasury", raffle_key]
+/// * Payout Authority - Must match the payout authority stored in config
+/// * Remaining accounts - The wallet of every leaderboard holder, in leaderboard order
+///
+/// # Implementation Notes
+/// - The leaderboard is maintained by every purchase, so the split is computed without
+///   walking the raffle's entries
+/// - Sponsor contributions settled after the payout can be paid out by calling again
+/// - The first payout stops counting the raffle as open, as it may never be claimed
+pub fn pay_top_holders<'info>(
+    ctx: Context<'_, '_, 'info, 'info, PayTopHolders<'info>>,
+) -> Result<()> {
+    require!(
+        ctx.accounts.raffle.top_holders.is_some(),
+        RaffleError::NoTopHoldersSplit
+    );
+    let cash_prize_bps = ctx
+        .accounts
+        .raffle
+        .cash_prize_bps
+        .ok_or(RaffleError::NoCashPrize)?;
+
+    let holders = &ctx.accounts.leaderboard.holders;
+    require!(
+        ctx.remaining_accounts.len() == holders.len(),
+        RaffleError::InvalidRemainingAccounts
+    );
+    for (account, holder) in ctx.remaining_accounts.iter().zip(holders) {
+        require!(
+            account.key() == holder.owner && account.is_writable,
+            RaffleError::InvalidRemainingAccounts
+        );
+    }
+
+    // Only pay out tracked funds, keeping the treasury's rent reserve
+    let available = ctx
+        .accounts
+        .treasury
+        .recognized_balance
+        .min(ctx.accounts.treasury.outstanding()?);
+    require!(available > 0, RaffleError::InsufficientFunds);
+
+    let prize = bps_of(available, cash_prize_bps)?;
+    let total_tickets = holders.iter().try_fold(0u64, |total, holder| {
+        checked_add(total, holder.ticket_count)
+    })?;
+
+    // Split the prize in proportion to each holder's tickets, rounding down
+    let mut amounts = Vec::with_capacity(holders.len());
+    for holder in holders {
+        let amount = (prize as u128)
+            .checked_mul(holder.ticket_count as u128)
+            .and_then(|share| share.checked_div(total_tickets as u128))
+            .ok_or(RaffleError::Overflow)?;
+        amounts.push(u64::try_from(amount).map_err(|_| RaffleError::Overflow)?);
+    }
+    let paid = amounts
+        .iter()
+        .try_fold(0u64, |total, amount| checked_add(total, *amount))?;
+    let payout_amount = checked_sub(available, paid)?;
+
+    let holder_keys: Vec<Pubkey> = holders.iter().map(|holder| holder.owner).collect();
+
+    let mut vault = LamportVault::new(&mut ctx.accounts.treasury, &ctx.accounts.system_program);
+    for (account, amount) in ctx.remaining_accounts.iter().zip(&amounts) {
+        vault.withdraw(account, *amount)?;
+    }
+    vault.withdraw(
+        &ctx.accounts.payout_authority.to_account_info(),
+        payout_amount,
+    )?;
+
+    // The raffle is settled, so it no longer counts as open
+    ctx.accounts
+        .config
+        .record_raffle_closed(&mut ctx.accounts.raffle);
+
+    let event_seq = ctx.accounts.raffle.next_event_seq()?;
+    emit!(TopHoldersPaid {
+        raffle: ctx.accounts.raffle.key(),
+        holders: holder_keys,
+        amounts,
+        payout_amount,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct PayTopHolders<'info> {
+    #[account(
+        mut,
+        constraint = matches!(
+            raffle.raffle_state,
+            RaffleState::Drawing | RaffleState::Drawn | RaffleState::Claimed
+        ) @ RaffleError::RaffleNotDrawn,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    #[account(
+        seeds = [
+            b"leaderboard",
+            raffle.key().as_ref(),
+        ],
+        bump = leaderboard.bump,
+    )]
+    pub leaderboard: Account<'info, Leaderboard>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"treasury",
+            raffle.key().as_ref(),
+        ],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(mut)]
+    pub payout_authority: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = payout_authority @ RaffleError::NotPayoutAuthority,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub system_program: Program<'info, System>,
+}
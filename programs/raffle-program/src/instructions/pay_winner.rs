@@ -0,0 +1,133 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    math::{bps_of, checked_sub},
+    state::{Config, Raffle, RaffleState, Treasury},
+    treasury::{LamportVault, TreasuryVault},
+};
+
+/// Event emitted when the cash prize of a raffle is paid out
+#[event]
+pub struct WinnerPaid {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The winner receiving the cash prize
+    pub winner: Pubkey,
+    /// Lamports paid to the winner
+    pub winner_amount: u64,
+    /// Lamports paid to the payout authority
+    pub payout_amount: u64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to pay out the treasury of a cash prize raffle, sending the raffle's
+/// `cash_prize_bps` share to the winner and the remainder to the payout authority.
+/// Can be called by anyone.
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Validates the raffle has a cash prize paid to a single winner
+/// 2. Validates the raffle is Drawn or Claimed and its winner is known
+/// 3. Ensures the winner account matches the winner stored in the raffle
+/// 4. Limits the payout to collected funds that were not yet refunded or withdrawn
+///
+/// # Account Validations
+/// * Raffle - Must have a cash prize and be in Drawn or Claimed state
+/// * Treasury - PDA with seeds ["treasury", raffle_key]
+/// * Winner - Must be the raffle's winner
+/// * Payout Authority - Must match the payout authority stored in config
+///
+/// # Implementation Notes
+/// - Anonymized winners are only known once they claim, so their cash prize can only be
+///   paid after the raffle is Claimed
+/// - Sponsor contributions settled after the payout can be paid out by calling again
+/// - The first payout stops counting the raffle as open, as it may never be claimed
+pub fn pay_winner(ctx: Context<PayWinner>) -> Result<()> {
+    let cash_prize_bps = ctx
+        .accounts
+        .raffle
+        .cash_prize_bps
+        .ok_or(RaffleError::NoCashPrize)?;
+    require!(
+        ctx.accounts.raffle.top_holders.is_none(),
+        RaffleError::TopHoldersRaffle
+    );
+
+    // Only pay out tracked funds, keeping the treasury's rent reserve
+    let available = ctx
+        .accounts
+        .treasury
+        .recognized_balance
+        .min(ctx.accounts.treasury.outstanding()?);
+    require!(available > 0, RaffleError::InsufficientFunds);
+
+    let winner_amount = bps_of(available, cash_prize_bps)?;
+    let payout_amount = checked_sub(available, winner_amount)?;
+
+    let mut vault = LamportVault::new(&mut ctx.accounts.treasury, &ctx.accounts.system_program);
+    vault.withdraw(&ctx.accounts.winner.to_account_info(), winner_amount)?;
+    vault.withdraw(
+        &ctx.accounts.payout_authority.to_account_info(),
+        payout_amount,
+    )?;
+
+    // The raffle is settled, so it no longer counts as open
+    ctx.accounts
+        .config
+        .record_raffle_closed(&mut ctx.accounts.raffle);
+
+    let event_seq = ctx.accounts.raffle.next_event_seq()?;
+    emit!(WinnerPaid {
+        raffle: ctx.accounts.raffle.key(),
+        winner: ctx.accounts.winner.key(),
+        winner_amount,
+        payout_amount,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct PayWinner<'info> {
+    #[account(
+        mut,
+        constraint = matches!(
+            raffle.raffle_state,
+            RaffleState::Drawn | RaffleState::Claimed
+        ) @ RaffleError::RaffleNotDrawn,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"treasury",
+            raffle.key().as_ref(),
+        ],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    /// The raffle's winner
+    #[account(
+        mut,
+        constraint = raffle.winner_address == Some(winner.key()) @ RaffleError::NotWinner,
+    )]
+    pub winner: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub payout_authority: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = payout_authority @ RaffleError::NotPayoutAuthority,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{AuthorityRole, Config},
+};
+
+/// Event emitted when a successor is proposed for one of the config authorities
+#[event]
+pub struct AuthorityProposed {
+    /// The authority being handed over
+    pub role: AuthorityRole,
+    /// The current holder of the authority
+    pub current_authority: Pubkey,
+    /// The proposed successor, or None if a pending proposal was withdrawn
+    pub proposed_authority: Option<Pubkey>,
+}
+
+/// Instruction to start handing one of the config authorities over to a new key, such
+/// as a Squads multisig vault. The handover completes once the successor signs
+/// accept_authority, so a mistyped key can't lock the authority.
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `role` - The authority to hand over
+/// * `proposed_authority` - The successor, or None to withdraw a pending proposal
+///
+/// # Security Considerations
+/// - Only the current holder of the authority can propose its successor
+/// - A new proposal replaces any pending one for the same authority
+pub fn propose_authority(
+    ctx: Context<ProposeAuthority>,
+    role: AuthorityRole,
+    proposed_authority: Option<Pubkey>,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let current_authority = config.authority(role);
+    require_keys_eq!(
+        ctx.accounts.authority.key(),
+        current_authority,
+        RaffleError::NotCurrentAuthority
+    );
+
+    let (_, pending_authority) = config.authority_slots(role);
+    *pending_authority = proposed_authority;
+
+    emit!(AuthorityProposed {
+        role,
+        current_authority,
+        proposed_authority,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    /// The current holder of the authority being handed over
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
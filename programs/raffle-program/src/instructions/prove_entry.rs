@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Entry, Raffle, RaffleState},
+};
+
+/// Proof of participation returned by `prove_entry`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct EntryProof {
+    /// The raffle the entry belongs to
+    pub raffle: Pubkey,
+    /// The owner of the entry
+    pub owner: Pubkey,
+    /// Index of the first ticket of the entry
+    pub ticket_start_index: u64,
+    /// Number of tickets in the entry, covering indices
+    /// `ticket_start_index..ticket_start_index + ticket_count`
+    pub ticket_count: u64,
+    /// Current state of the raffle
+    pub raffle_state: RaffleState,
+}
+
+/// Returns the owner and ticket range of an entry together with the state of its raffle,
+/// via return data.
+///
+/// Partner programs can call this through CPI to verify that a wallet participated in a
+/// raffle, without deserializing our account layouts themselves. The program derives the
+/// entry address from its seeds, so a returned proof always describes a genuine entry.
+///
+/// # Arguments
+/// * `ctx` - The context object containing:
+///   - `raffle`: The raffle the entry belongs to
+///   - `entry`: The entry to prove, PDA with seeds ["entry", raffle_key, entry.seed]
+///
+/// # Errors
+/// - `EntryRaffleMismatch` if the entry does not belong to the raffle
+pub fn prove_entry(ctx: Context<ProveEntry>) -> Result<EntryProof> {
+    let entry = &ctx.accounts.entry;
+
+    Ok(EntryProof {
+        raffle: entry.raffle,
+        owner: entry.owner,
+        ticket_start_index: entry.ticket_start_index,
+        ticket_count: entry.ticket_count,
+        raffle_state: ctx.accounts.raffle.raffle_state.clone(),
+    })
+}
+
+/// Accounts required for the prove_entry instruction
+#[derive(Accounts)]
+pub struct ProveEntry<'info> {
+    /// The raffle the entry belongs to
+    pub raffle: Account<'info, Raffle>,
+
+    /// The entry to prove
+    #[account(
+        seeds = [
+            b"entry",
+            raffle.key().as_ref(),
+            entry.seed.as_ref()
+        ],
+        bump,
+        has_one = raffle @ RaffleError::EntryRaffleMismatch,
+    )]
+    pub entry: Account<'info, Entry>,
+}
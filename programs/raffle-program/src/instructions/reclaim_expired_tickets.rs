@@ -1,31 +1,94 @@
 use anchor_lang::prelude::*;
 
-use crate::{error::RaffleError, state::{Raffle, RaffleState, TicketBalance, Treasury}};
+use crate::{
+    error::RaffleError,
+    instructions::claim_refund_with_proof::settle_snapshot_holding,
+    math::{bps_of, checked_add, checked_sub, ticket_cost},
+    state::{Campaign, Config, Raffle, RaffleState, TicketBalance, Treasury},
+    time::now,
+    treasury::{refund_token_accounts, LamportVault, TokenVault, TreasuryVault},
+};
+use anchor_spl::token::{Token, TokenAccount};
 
-/// Instruction to reclaim funds from tickets purchased in an expired raffle
+/// Event emitted when a ticket holder reclaims the funds of an expired or cancelled raffle
+#[event]
+pub struct TicketsReclaimed {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The ticket holder, who closed their ticket balance
+    pub owner: Pubkey,
+    /// Number of tickets refunded
+    pub ticket_count: u64,
+    /// Amount refunded, in lamports or base units of the payment mint
+    pub refund_amount: u64,
+    /// Refund processing fee retained by the treasury
+    pub fee_amount: u64,
+    /// Share of the crank reward deducted from the refund
+    pub crank_share: u64,
+    /// Campaign subsidy returned to the campaign that paid it
+    pub subsidy_returned: u64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to reclaim funds from tickets purchased in an expired or cancelled raffle
 ///
 /// # Security Considerations
 /// The instruction performs several critical checks:
-/// 1. Validates the raffle is in Expired state
-/// 2. Ensures signer is the owner of the ticket balance
-/// 3. Verifies the treasury account matches the one stored in raffle
-/// 4. Confirms the ticket balance is greater than 0
+/// 1. Validates the raffle is in Expired or Cancelled state
+/// 2. Ensures the raffle's refund deadline has not passed, if it has one
+/// 3. Ensures signer is the owner of the ticket balance
+/// 4. Verifies the treasury account matches the one stored in raffle
+/// 5. Confirms the ticket balance is greater than 0
+/// 6. Ensures the refund does not exceed the funds collected by the treasury
+/// 7. Only retains the refund processing fee disclosed on the raffle, and none for
+///    cancelled raffles
+/// 8. If the raffle is paid in an SPL token, ensures the refund is paid from the
+///    treasury's associated token account for the payment mint
+/// 9. If the raffle was snapshotted, settles the holding in the snapshot, so it can't
+///    also be refunded with claim_refund_with_proof
+/// 10. Only refunds what the ticket holder paid, returning the part of the ticket cost
+///     paid by a campaign to that campaign
 ///
 /// # Account Validations
-/// * Raffle - Must be in Expired state
+/// * Raffle - Must be in Expired or Cancelled state
 /// * Signer - Must match the owner of the ticket balance
 /// * TicketBalance - PDA storing ticket purchase info, closed after refund
 /// * Treasury - Must match raffle's treasury and use proper PDA seeds
+/// * RecipientTokenAccount - Optional, token account of the signer receiving the refund,
+///   required if the raffle is paid in an SPL token
+/// * TreasuryTokenAccount - Optional, required if the raffle is paid in an SPL token
+/// * TokenProgram - Optional, required if the raffle is paid in an SPL token
+/// * SnapshotClaim - Optional, the uninitialized PDA with seeds ["snapshot_claim",
+///   raffle_key, signer], required if the raffle has a snapshot root
+/// * Campaign - Optional, required if a campaign subsidized the tickets, the campaign
+///   stored on the ticket balance
 ///
 /// # Implementation Notes
-/// - Refunds the full ticket price for all tickets owned
+/// - Refunds the ticket price for all tickets owned, less any pricing tier discount,
+///   campaign subsidy and the raffle's refund processing fee, using checked arithmetic
+/// - The processing fee is charged on the amount the ticket holder paid. Deductions
+///   exceeding it, e.g. for tickets fully paid by a campaign, are taken from the subsidy
+/// - The fee stays in the treasury until the payout authority withdraws it
+/// - Deducts the tickets' share of the crank reward paid on expiry, if one was paid
 /// - Closes the ticket balance account and reclaims rent
-/// - Funds transfer happens directly between PDAs
+/// - Lamport refunds are transferred directly between PDAs, token refunds are signed by
+///   the treasury PDA
+/// - If the raffle has a refund mint, token refunds are paid from the treasury's token
+///   account for it, converted at the refund rate. The event reports the refund in
+///   payment mint units
+/// - Emits TicketsReclaimed with the refund and the deductions
 pub fn reclaim_expired_tickets(ctx: Context<ReclaimExpiredTickets>) -> Result<()> {
+    let cancelled = ctx.accounts.raffle.raffle_state == RaffleState::Cancelled;
     require!(
-        ctx.accounts.raffle.raffle_state == RaffleState::Expired, 
+        ctx.accounts.raffle.raffle_state == RaffleState::Expired || cancelled,
         RaffleError::RaffleNotExpired
     );
+    let now = now(&ctx.accounts.config)?;
+    require!(
+        !ctx.accounts.raffle.refunds_lapsed(now),
+        RaffleError::RefundWindowClosed
+    );
     require!(
         ctx.accounts.signer.key() == ctx.accounts.ticket_balance.owner,
         RaffleError::OwnerMismatch
@@ -38,15 +101,100 @@ pub fn reclaim_expired_tickets(ctx: Context<ReclaimExpiredTickets>) -> Result<()
         ctx.accounts.ticket_balance.ticket_count > 0,
         RaffleError::NoTicketsOwned
     );
+    settle_snapshot_holding(
+        &ctx.accounts.raffle,
+        ctx.accounts.snapshot_claim.as_ref(),
+        &ctx.accounts.signer,
+        &ctx.accounts.system_program,
+        ctx.accounts.ticket_balance.ticket_count,
+        now,
+    )?;
+
+    // Tickets bought at a bulk price are refunded at the price paid
+    let ticket_total = checked_sub(
+        ticket_cost(
+            ctx.accounts.ticket_balance.ticket_count,
+            ctx.accounts.raffle.ticket_price,
+        )?,
+        ctx.accounts.ticket_balance.tier_discount,
+    )?;
+
+    // The part paid by a campaign goes back to the campaign
+    let campaign_subsidy = ctx.accounts.ticket_balance.campaign_subsidy;
+    let paid_total = checked_sub(ticket_total, campaign_subsidy)?;
 
-    let from_pubkey = ctx.accounts.treasury.to_account_info();
-    let to_pubkey = ctx.accounts.signer.to_account_info();
+    // Retain the refund processing fee, covering keeper and rent costs of the failed raffle.
+    // Cancelled raffles were aborted by the operator, so their ticket holders are refunded in full
+    let fee_amount = if cancelled {
+        0
+    } else {
+        bps_of(paid_total, ctx.accounts.raffle.refund_fee_bps)?
+    };
+    // Recover the crank reward paid when the raffle expired, a fixed amount per ticket
+    let crank_share = if ctx.accounts.treasury.total_crank_rewards > 0 {
+        ctx.accounts
+            .raffle
+            .crank_share(ctx.accounts.ticket_balance.ticket_count)?
+    } else {
+        0
+    };
+    let deductions = checked_add(fee_amount, crank_share)?;
+    let refund_amount = paid_total.saturating_sub(deductions);
+    let subsidy_returned = checked_sub(campaign_subsidy, deductions.saturating_sub(paid_total))?;
+    ctx.accounts.treasury.record_refund_fee(fee_amount)?;
 
-    // Transfer lamports by directly deducting from treasury and adding to signer. 
-    // This only works because the treasury is a PDA owned by our program.
-    let total_lamports_to_transfer = ctx.accounts.ticket_balance.ticket_count * ctx.accounts.raffle.ticket_price;
-    from_pubkey.sub_lamports(total_lamports_to_transfer)?;
-    to_pubkey.add_lamports(total_lamports_to_transfer)?;
+    // Refund the tickets, failing if the treasury would pay out more than it collected
+    if ctx.accounts.raffle.payment_mint.is_some() {
+        let (vault, token_program) = refund_token_accounts(
+            &ctx.accounts.raffle,
+            &ctx.accounts.treasury,
+            ctx.accounts.treasury_token_account.as_mut(),
+            ctx.accounts.token_program.as_ref(),
+        )?;
+        let recipient_token_account = ctx
+            .accounts
+            .recipient_token_account
+            .as_ref()
+            .ok_or(RaffleError::InvalidPaymentMint)?;
+        require!(
+            recipient_token_account.owner == ctx.accounts.signer.key(),
+            RaffleError::OwnerMismatch
+        );
+        TokenVault::new(&mut ctx.accounts.treasury, vault, token_program)
+            .with_refund_mint(&ctx.accounts.raffle)
+            .refund(&recipient_token_account.to_account_info(), refund_amount)?;
+    } else {
+        let mut vault = LamportVault::new(&mut ctx.accounts.treasury, &ctx.accounts.system_program);
+        vault.refund(&ctx.accounts.signer.to_account_info(), refund_amount)?;
+
+        // Campaigns only subsidize lamport purchases
+        if subsidy_returned > 0 {
+            let campaign = ctx
+                .accounts
+                .campaign
+                .as_mut()
+                .ok_or(RaffleError::CampaignMismatch)?;
+            require!(
+                ctx.accounts.ticket_balance.subsidy_campaign == Some(campaign.key()),
+                RaffleError::CampaignMismatch
+            );
+            vault.refund(&campaign.to_account_info(), subsidy_returned)?;
+            campaign.record_return(subsidy_returned)?;
+        }
+    }
+
+    let raffle = &mut ctx.accounts.raffle;
+    let event_seq = raffle.next_event_seq()?;
+    emit!(TicketsReclaimed {
+        raffle: raffle.key(),
+        owner: ctx.accounts.signer.key(),
+        ticket_count: ctx.accounts.ticket_balance.ticket_count,
+        refund_amount,
+        fee_amount,
+        crank_share,
+        subsidy_returned,
+        event_seq,
+    });
 
     Ok(())
 }
@@ -71,7 +219,9 @@ pub struct ReclaimExpiredTickets<'info> {
     )]
     pub ticket_balance: Account<'info, TicketBalance>,
 
-    /// The raffle account that must be in Expired state
+    /// The raffle account that must be in Expired or Cancelled state, mutable to advance
+    /// its event sequence
+    #[account(mut)]
     pub raffle: Account<'info, Raffle>,
     
     /// Required by Anchor for transfers
@@ -87,4 +237,38 @@ pub struct ReclaimExpiredTickets<'info> {
         bump = treasury.bump,
     )]
     pub treasury: Account<'info, Treasury>,
+
+    /// Optional token account of the signer receiving the refund, required if the raffle
+    /// is paid in an SPL token
+    #[account(mut)]
+    pub recipient_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Optional associated token account of the treasury holding the payments, or the
+    /// refund mint if the raffle has one, required if the raffle is paid in an SPL token.
+    /// Validated against the mint in the instruction.
+    #[account(mut)]
+    pub treasury_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Required if the raffle is paid in an SPL token
+    pub token_program: Option<Program<'info, Token>>,
+
+    /// Program config, storing the clock offset of devnet builds
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Optional record settling the signer's holding in the raffle's snapshot, required
+    /// if the raffle has a snapshot root. PDA with seeds ["snapshot_claim", raffle_key,
+    /// signer_key], validated and created in the instruction.
+    /// CHECK: The address is checked against the PDA derived from the signer
+    #[account(mut)]
+    pub snapshot_claim: Option<UncheckedAccount<'info>>,
+
+    /// Optional campaign that subsidized the tickets, required if one did. Campaigns only
+    /// exist at PDAs with seeds ["campaign", campaign_id], validated against the ticket
+    /// balance in the instruction
+    #[account(mut)]
+    pub campaign: Option<Account<'info, Campaign>>,
 }
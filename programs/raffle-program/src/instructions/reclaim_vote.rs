@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::get_associated_token_address,
+    token::{self, Token, TokenAccount, Transfer},
+};
+
+use crate::{
+    error::RaffleError,
+    state::{Ballot, Config, Raffle, Vote},
+    time::now,
+};
+
+/// Event emitted when a voter reclaims the tokens locked by their vote
+#[event]
+pub struct VoteReclaimed {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The wallet that voted
+    pub voter: Pubkey,
+    /// Tokens returned to the voter
+    pub amount: u64,
+}
+
+/// Instruction to return the tokens locked by a vote once voting has ended, closing the
+/// vote account
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Ensures voting has ended, so tokens can't be withdrawn and voted again
+/// 2. Returns exactly the tokens the vote locked, to the voter who cast it
+///
+/// # Account Validations
+/// * Ballot - PDA with seeds ["ballot", raffle_key], signing the transfer out of its
+///   token account
+/// * Vote - PDA with seeds ["vote", ballot_key, voter], closed to the voter
+/// * BallotTokenAccount - Associated token account of the ballot for the vote mint
+/// * VoterTokenAccount - Token account of the vote mint owned by the voter
+///
+/// # Implementation Notes
+/// - Votes can be reclaimed whether or not the vote was tallied, as the tally only
+///   reads the ballot
+pub fn reclaim_vote(ctx: Context<ReclaimVote>) -> Result<()> {
+    let ballot = &ctx.accounts.ballot;
+    require!(
+        now(&ctx.accounts.config)? >= ballot.voting_ends_at,
+        RaffleError::VotingNotEnded
+    );
+
+    require_keys_eq!(
+        ctx.accounts.ballot_token_account.key(),
+        get_associated_token_address(&ballot.key(), &ballot.vote_mint),
+        RaffleError::InvalidVoteMint
+    );
+    require_keys_eq!(
+        ctx.accounts.voter_token_account.owner,
+        ctx.accounts.voter.key(),
+        RaffleError::InvalidVoteMint
+    );
+
+    let amount = ctx.accounts.vote.amount;
+    let raffle_key = ctx.accounts.raffle.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[b"ballot", raffle_key.as_ref(), &[ballot.bump]]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.ballot_token_account.to_account_info(),
+                to: ctx.accounts.voter_token_account.to_account_info(),
+                authority: ballot.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+
+    emit!(VoteReclaimed {
+        raffle: raffle_key,
+        voter: ctx.accounts.voter.key(),
+        amount,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ReclaimVote<'info> {
+    pub raffle: Account<'info, Raffle>,
+
+    #[account(
+        seeds = [
+            b"ballot",
+            raffle.key().as_ref(),
+        ],
+        bump = ballot.bump,
+    )]
+    pub ballot: Account<'info, Ballot>,
+
+    #[account(
+        mut,
+        close = voter,
+        seeds = [
+            b"vote",
+            ballot.key().as_ref(),
+            voter.key().as_ref(),
+        ],
+        bump = vote.bump,
+    )]
+    pub vote: Account<'info, Vote>,
+
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    /// Token account of the ballot for the vote mint
+    #[account(mut)]
+    pub ballot_token_account: Account<'info, TokenAccount>,
+
+    /// Token account receiving the returned tokens
+    #[account(
+        mut,
+        constraint = voter_token_account.mint == ballot.vote_mint @ RaffleError::InvalidVoteMint,
+    )]
+    pub voter_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+
+    /// Program config, storing the clock offset of devnet builds
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
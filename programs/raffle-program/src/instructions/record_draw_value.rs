@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use switchboard_on_demand::PRECISION;
+
+use crate::{
+    error::RaffleError,
+    instructions::set_refund_mint::feed_price,
+    math::{lamports_to_usd_cents, USD_RATE_SCALE},
+    state::{Config, Raffle, RaffleState, Treasury},
+};
+
+/// Event emitted when the value of a drawn raffle's prize is recorded
+#[event]
+pub struct DrawValueRecorded {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// Lamports the treasury held for the prize
+    pub value_lamports: u64,
+    /// SOL price in micro-USD read from the price feed
+    pub usd_rate: u64,
+    /// Value of the prize in US cents at `usd_rate`
+    pub value_usd_cents: u64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to snapshot the lamport and USD value of a drawn raffle's prize, so
+/// historical reporting and winner tax documentation have an on-chain exchange rate.
+/// Can be called by anyone, keepers call it alongside the draw.
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Only reads the USD price feed configured by the management authority
+/// 2. Rejects stale feeds, so the rate is the one at draw time
+/// 3. Only allows raffles in Drawing state, between the draw and the claim
+/// 4. Records the value once per raffle, so it can't be replaced by a later price
+///
+/// # Account Validations
+/// * Raffle - Must be in Drawing state, paid in lamports, without a recorded value
+/// * Treasury - PDA with seeds ["treasury", raffle_key]
+/// * Config - PDA storing the USD price feed
+/// * PriceFeed - The config's USD price feed
+///
+/// # Implementation Notes
+/// - The lamport value is what the treasury holds for the prize, see
+///   `Treasury::outstanding`
+/// - finalize_results copies the values to the raffle's Results
+/// - Raffles paid in an SPL token have no lamport value and are rejected
+pub fn record_draw_value(ctx: Context<RecordDrawValue>) -> Result<()> {
+    let price = feed_price(&ctx.accounts.price_feed)?;
+    let usd_rate = price
+        .checked_mul(USD_RATE_SCALE as u128)
+        .ok_or(RaffleError::Overflow)?
+        / 10u128.pow(PRECISION);
+    let usd_rate = u64::try_from(usd_rate).map_err(|_| RaffleError::Overflow)?;
+    require!(usd_rate > 0, RaffleError::InvalidPriceFeed);
+
+    let value_lamports = ctx.accounts.treasury.outstanding()?;
+    let value_usd_cents = lamports_to_usd_cents(value_lamports, usd_rate)?;
+
+    let raffle = &mut ctx.accounts.raffle;
+    raffle.draw_value_lamports = value_lamports;
+    raffle.draw_usd_rate = Some(usd_rate);
+
+    let event_seq = raffle.next_event_seq()?;
+    emit!(DrawValueRecorded {
+        raffle: raffle.key(),
+        value_lamports,
+        usd_rate,
+        value_usd_cents,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RecordDrawValue<'info> {
+    /// The drawn raffle, paid in lamports
+    #[account(
+        mut,
+        constraint = raffle.raffle_state == RaffleState::Drawing @ RaffleError::RaffleNotDrawing,
+        constraint = raffle.payment_mint.is_none() @ RaffleError::InvalidPaymentMint,
+        constraint = raffle.draw_usd_rate.is_none() @ RaffleError::DrawValueAlreadyRecorded,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    /// Treasury PDA for this raffle
+    #[account(
+        seeds = [
+            b"treasury",
+            raffle.key().as_ref(),
+        ],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.usd_price_feed == Some(price_feed.key()) @ RaffleError::InvalidPriceFeed,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: The config's USD price feed, parsed as a Switchboard pull feed in the
+    /// instruction
+    pub price_feed: UncheckedAccount<'info>,
+}
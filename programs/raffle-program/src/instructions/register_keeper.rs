@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, Keeper, KEEPER_ACCOUNT_SIZE},
+    time::now,
+};
+
+/// Event emitted when a keeper is registered
+#[event]
+pub struct KeeperRegistered {
+    /// The registered keeper
+    pub keeper: Pubkey,
+}
+
+/// Instruction to register a keeper allowed to crank draws and expiries while the
+/// config restricts them to keepers
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `keeper` - The key of the keeper to register
+///
+/// # Security Considerations
+/// - Only the management authority can register keepers
+/// - The registration is a PDA with seeds ["keeper", keeper], so a keeper can only be
+///   registered once
+pub fn register_keeper(ctx: Context<RegisterKeeper>, keeper: Pubkey) -> Result<()> {
+    let keeper_registration = &mut ctx.accounts.keeper_registration;
+    keeper_registration.keeper = keeper;
+    keeper_registration.registered_at = now(&ctx.accounts.config)?;
+    keeper_registration.bump = ctx.bumps.keeper_registration;
+
+    emit!(KeeperRegistered { keeper });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(keeper: Pubkey)]
+pub struct RegisterKeeper<'info> {
+    #[account(
+        init,
+        payer = management_authority,
+        space = KEEPER_ACCOUNT_SIZE,
+        seeds = [
+            b"keeper",
+            keeper.as_ref(),
+        ],
+        bump,
+    )]
+    pub keeper_registration: Account<'info, Keeper>,
+
+    #[account(mut)]
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, RandomnessProvider, RegisteredRandomnessProvider},
+};
+
+/// Event emitted when a randomness provider is registered or removed
+#[event]
+pub struct RandomnessProviderRegistered {
+    /// The registered or removed provider
+    pub provider: RandomnessProvider,
+    /// Program owning the provider's randomness accounts, or None if it was removed
+    pub program_id: Option<Pubkey>,
+}
+
+/// Instruction to register a randomness provider raffles can be drawn from, or to
+/// remove one
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `provider` - The provider to register or remove
+/// * `program_id` - Program owning the provider's randomness accounts, replacing the
+///   registered one, or None to remove the provider. Ignored by providers without
+///   randomness accounts, which are registered with any program id
+///
+/// # Security Considerations
+/// - Only the upgrade authority can register providers, as a provider's program
+///   decides the winners of the raffles drawn from it
+/// - Raffles can only select registered providers, and can't request or fulfill
+///   randomness from a provider that was removed
+pub fn register_randomness_provider(
+    ctx: Context<RegisterRandomnessProvider>,
+    provider: RandomnessProvider,
+    program_id: Option<Pubkey>,
+) -> Result<()> {
+    let randomness_providers = &mut ctx.accounts.config.randomness_providers;
+    randomness_providers.retain(|registered| registered.provider != provider);
+    if let Some(program_id) = program_id {
+        randomness_providers.push(RegisteredRandomnessProvider {
+            provider,
+            program_id,
+        });
+    }
+
+    emit!(RandomnessProviderRegistered {
+        provider,
+        program_id,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RegisterRandomnessProvider<'info> {
+    pub upgrade_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = upgrade_authority @ RaffleError::NotProgramUpgradeAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
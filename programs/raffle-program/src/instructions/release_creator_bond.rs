@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, CreatorBond, Raffle, RaffleState},
+};
+
+/// Event emitted when a creator bond is returned to its depositor
+#[event]
+pub struct CreatorBondReleased {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The account the bond was returned to
+    pub depositor: Pubkey,
+    /// Bonded lamports returned, excluding the rent
+    pub amount: u64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to return the bond of a raffle's creator once the raffle was fulfilled
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Anyone can release the bond of a raffle whose prize was claimed
+/// 2. The bond of an expired or cancelled raffle is only released by the upgrade
+///    authority, once it ruled out fraud. It slashes the bond with slash_creator_bond
+///    otherwise
+/// 3. The bond is always returned to the account that deposited it
+///
+/// # Account Validations
+/// * Raffle - Must be in Claimed state, or in Expired or Cancelled state if the upgrade
+///   authority signs
+/// * CreatorBond - PDA with seeds ["creator_bond", raffle_key], closed to the depositor
+/// * Depositor - Must match the depositor stored in the bond
+/// * UpgradeAuthority - Optional, must match the upgrade authority stored in config
+///
+/// # Implementation Notes
+/// - Closing the bond returns its rent to the depositor along with the bonded lamports
+pub fn release_creator_bond(ctx: Context<ReleaseCreatorBond>) -> Result<()> {
+    let released = match ctx.accounts.raffle.raffle_state {
+        RaffleState::Claimed => true,
+        RaffleState::Expired | RaffleState::Cancelled => {
+            ctx.accounts
+                .upgrade_authority
+                .as_ref()
+                .map(|signer| signer.key())
+                == Some(ctx.accounts.config.upgrade_authority)
+        }
+        _ => false,
+    };
+    require!(released, RaffleError::CreatorBondLocked);
+
+    let event_seq = ctx.accounts.raffle.next_event_seq()?;
+    emit!(CreatorBondReleased {
+        raffle: ctx.accounts.raffle.key(),
+        depositor: ctx.accounts.depositor.key(),
+        amount: ctx.accounts.creator_bond.amount,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ReleaseCreatorBond<'info> {
+    #[account(mut)]
+    pub raffle: Account<'info, Raffle>,
+
+    /// The raffle's creator bond, closed to the depositor
+    #[account(
+        mut,
+        close = depositor,
+        seeds = [
+            b"creator_bond",
+            raffle.key().as_ref(),
+        ],
+        bump = creator_bond.bump,
+        has_one = depositor @ RaffleError::InvalidCreatorBond,
+    )]
+    pub creator_bond: Account<'info, CreatorBond>,
+
+    /// The account that deposited the bond
+    #[account(mut)]
+    pub depositor: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The upgrade authority, required to release the bond of an expired or cancelled
+    /// raffle
+    pub upgrade_authority: Option<Signer<'info>>,
+}
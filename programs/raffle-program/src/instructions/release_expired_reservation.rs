@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    instructions::confirm_reservation::release_reservation,
+    state::{Config, Raffle, Reservation},
+    time::now,
+};
+
+/// Instruction to release a reservation whose off-chain payment didn't settle before it
+/// expired, returning its tickets to sale. Can be called by anyone, so held tickets are
+/// never locked by an unavailable management authority.
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Only releases reservations past their expiry, which can no longer be finalized
+/// 2. Only releases reservations of open raffles
+/// 3. The reservation's rent is returned to the management authority, who paid it
+/// 4. Isn't blocked while the program is paused, as releasing never sells tickets
+///
+/// # Account Validations
+/// * Reservation - PDA with seeds ["reservation", raffle_key, seed], closed to the
+///   management authority
+/// * Management Authority - Only receives the reservation's rent, and must match the
+///   management authority stored in config
+///
+/// # Implementation Notes
+/// - Released the same way as by confirm_reservation, emitting ReservationReleased
+pub fn release_expired_reservation(ctx: Context<ReleaseExpiredReservation>) -> Result<()> {
+    require!(
+        now(&ctx.accounts.config)? > ctx.accounts.reservation.expires_at,
+        RaffleError::ReservationNotExpired
+    );
+
+    release_reservation(&mut ctx.accounts.raffle, &ctx.accounts.reservation)
+}
+
+#[derive(Accounts)]
+pub struct ReleaseExpiredReservation<'info> {
+    /// The raffle the tickets were reserved in, mutable to return them to sale
+    #[account(mut)]
+    pub raffle: Account<'info, Raffle>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"reservation",
+            raffle.key().as_ref(),
+            reservation.seed.as_ref(),
+        ],
+        bump = reservation.bump,
+        has_one = raffle,
+        close = management_authority,
+    )]
+    pub reservation: Account<'info, Reservation>,
+
+    /// The management authority, refunded the rent of the reservation
+    #[account(mut)]
+    pub management_authority: SystemAccount<'info>,
+
+    /// Program config, storing the management authority and the clock offset of devnet
+    /// builds
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::{
+    error::RaffleError,
+    instructions::claim_prize::pay_out_prize,
+    state::{Config, PrizeVault, Raffle, RaffleState},
+};
+
+/// Event emitted when an escrowed prize is released
+#[event]
+pub struct PrizeReleased {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The escrowed prize value in lamports, or in base units of the prize mint
+    pub amount: u64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to release the escrowed prize back to the management authority
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Only the management authority can release the prize
+/// 2. Ensures the raffle is Expired or Cancelled, meaning there is no winner. Drawn
+///    raffles pay the prize out to the winner with claim_prize instead
+///
+/// # Account Validations
+/// * Raffle - Must be in Expired or Cancelled state
+/// * PrizeVault - PDA with seeds ["prize_vault", raffle_key], closed to the management authority
+/// * Config - PDA storing the management authority
+/// * AuthorityTokenAccount - Token account of the management authority receiving a token
+///   prize
+/// * VaultTokenAccount - Associated token account of the prize vault holding a token
+///   prize, closed to the management authority
+pub fn release_prize(ctx: Context<ReleasePrize>) -> Result<()> {
+    require!(
+        matches!(
+            ctx.accounts.raffle.raffle_state,
+            RaffleState::Expired | RaffleState::Cancelled
+        ),
+        RaffleError::RaffleNotFinalized
+    );
+
+    let management_authority = ctx.accounts.management_authority.to_account_info();
+    let amount = pay_out_prize(
+        &mut ctx.accounts.prize_vault,
+        None,
+        &management_authority,
+        ctx.accounts.authority_token_account.as_ref(),
+        ctx.accounts.vault_token_account.as_ref(),
+        ctx.accounts.token_program.as_ref(),
+        &management_authority,
+    )?;
+
+    let event_seq = ctx.accounts.raffle.next_event_seq()?;
+    emit!(PrizeReleased {
+        raffle: ctx.accounts.raffle.key(),
+        amount,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ReleasePrize<'info> {
+    #[account(mut)]
+    pub raffle: Account<'info, Raffle>,
+
+    #[account(
+        mut,
+        close = management_authority,
+        seeds = [
+            b"prize_vault",
+            raffle.key().as_ref(),
+        ],
+        bump = prize_vault.bump,
+    )]
+    pub prize_vault: Account<'info, PrizeVault>,
+
+    #[account(mut)]
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Token account of the management authority, required for token prizes
+    #[account(mut)]
+    pub authority_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Associated token account of the prize vault, required for token prizes
+    #[account(mut)]
+    pub vault_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Required for token prizes
+    pub token_program: Option<Program<'info, Token>>,
+}
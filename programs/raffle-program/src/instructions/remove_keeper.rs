@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, Keeper},
+};
+
+/// Event emitted when a keeper is removed
+#[event]
+pub struct KeeperRemoved {
+    /// The removed keeper
+    pub keeper: Pubkey,
+}
+
+/// Instruction to remove a keeper's registration, refunding its rent to the
+/// management authority
+///
+/// # Security Considerations
+/// - Only the management authority can remove keepers
+pub fn remove_keeper(ctx: Context<RemoveKeeper>) -> Result<()> {
+    emit!(KeeperRemoved {
+        keeper: ctx.accounts.keeper_registration.keeper,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RemoveKeeper<'info> {
+    #[account(
+        mut,
+        close = management_authority,
+        seeds = [
+            b"keeper",
+            keeper_registration.keeper.as_ref(),
+        ],
+        bump = keeper_registration.bump,
+    )]
+    pub keeper_registration: Account<'info, Keeper>,
+
+    #[account(mut)]
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
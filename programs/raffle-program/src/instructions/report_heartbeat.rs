@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Heartbeat, HEARTBEAT_ACCOUNT_SIZE},
+    time::cluster_clock,
+};
+
+/// Event emitted when a keeper reports its health
+#[event]
+pub struct HeartbeatReported {
+    /// The reporting keeper
+    pub keeper: Pubkey,
+    /// The last slot the keeper has processed raffles up to
+    pub last_processed_slot: u64,
+    /// Raffles waiting to be drawn
+    pub pending_draws: u64,
+    /// Raffles waiting to be expired
+    pub pending_expiries: u64,
+    /// Concluded raffles waiting for their entries to be closed
+    pub pending_closures: u64,
+}
+
+/// Instruction for keepers to report their progress and backlog, so monitoring and
+/// other keepers can detect a stalled finalization pipeline
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `last_processed_slot` - The last slot the keeper has processed raffles up to
+/// * `pending_draws` - Raffles ended but not yet drawn
+/// * `pending_expiries` - Raffles ended below threshold but not yet expired
+/// * `pending_closures` - Concluded raffles with entries still to close
+///
+/// # Security Considerations
+/// - The heartbeat is a PDA with seeds ["heartbeat", keeper], so keepers can only
+///   report for themselves. Monitoring should only trust heartbeats of registered keepers
+/// - The processed slot can't be in the future and can't move backwards
+pub fn report_heartbeat(
+    ctx: Context<ReportHeartbeat>,
+    last_processed_slot: u64,
+    pending_draws: u64,
+    pending_expiries: u64,
+    pending_closures: u64,
+) -> Result<()> {
+    // Monitors compare heartbeats against the wall clock, so the time is never shifted
+    let clock = cluster_clock()?;
+    let heartbeat = &mut ctx.accounts.heartbeat;
+    require!(
+        last_processed_slot <= clock.slot && last_processed_slot >= heartbeat.last_processed_slot,
+        RaffleError::InvalidHeartbeatSlot
+    );
+
+    heartbeat.keeper = ctx.accounts.keeper.key();
+    heartbeat.last_processed_slot = last_processed_slot;
+    heartbeat.pending_draws = pending_draws;
+    heartbeat.pending_expiries = pending_expiries;
+    heartbeat.pending_closures = pending_closures;
+    heartbeat.reported_slot = clock.slot;
+    heartbeat.reported_at = clock.unix_timestamp;
+    heartbeat.bump = ctx.bumps.heartbeat;
+
+    emit!(HeartbeatReported {
+        keeper: ctx.accounts.keeper.key(),
+        last_processed_slot,
+        pending_draws,
+        pending_expiries,
+        pending_closures,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ReportHeartbeat<'info> {
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        space = HEARTBEAT_ACCOUNT_SIZE,
+        seeds = [
+            b"heartbeat",
+            keeper.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub heartbeat: Account<'info, Heartbeat>,
+
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
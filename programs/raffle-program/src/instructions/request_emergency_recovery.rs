@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    instructions::emergency_recover::require_recoverable,
+    state::{
+        Config, Raffle, RecoveryRequest, EMERGENCY_RECOVERY_DELAY_SECS,
+        RECOVERY_REQUEST_ACCOUNT_SIZE,
+    },
+    time::now,
+};
+
+/// Event emitted when the upgrade authority schedules an emergency recovery
+#[event]
+pub struct EmergencyRecoveryRequested {
+    /// The pubkey of the raffle to recover
+    pub raffle: Pubkey,
+    /// Earliest time the recovery can be executed
+    pub executable_at: i64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to schedule the emergency recovery of a raffle stuck in its state
+/// machine. The recovery can be executed with `emergency_recover` once
+/// `EMERGENCY_RECOVERY_DELAY_SECS` have passed, giving ticket holders and operators
+/// time to react, and cancelled with `cancel_emergency_recovery` until then.
+///
+/// # Security Considerations
+/// - Only the upgrade authority can request a recovery
+/// - Only raffles that were not expired, claimed or cancelled can be recovered
+/// - A raffle can have a single pending recovery
+///
+/// # Account Validations
+/// * Raffle - Must not be in Expired, Claimed or Cancelled state
+/// * Recovery Request - New PDA initialized with seeds ["recovery_request", raffle_key]
+/// * Upgrade Authority - Must match the upgrade authority stored in config
+pub fn request_emergency_recovery(ctx: Context<RequestEmergencyRecovery>) -> Result<()> {
+    require_recoverable(&ctx.accounts.raffle)?;
+
+    let requested_at = now(&ctx.accounts.config)?;
+    let recovery_request = &mut ctx.accounts.recovery_request;
+    recovery_request.raffle = ctx.accounts.raffle.key();
+    recovery_request.requested_at = requested_at;
+    recovery_request.executable_at = requested_at
+        .checked_add(EMERGENCY_RECOVERY_DELAY_SECS)
+        .ok_or(RaffleError::Overflow)?;
+    recovery_request.bump = ctx.bumps.recovery_request;
+
+    let event_seq = ctx.accounts.raffle.next_event_seq()?;
+    emit!(EmergencyRecoveryRequested {
+        raffle: ctx.accounts.raffle.key(),
+        executable_at: ctx.accounts.recovery_request.executable_at,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RequestEmergencyRecovery<'info> {
+    #[account(mut)]
+    pub raffle: Account<'info, Raffle>,
+
+    #[account(
+        init,
+        payer = upgrade_authority,
+        space = RECOVERY_REQUEST_ACCOUNT_SIZE,
+        seeds = [
+            b"recovery_request",
+            raffle.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub recovery_request: Account<'info, RecoveryRequest>,
+
+    #[account(mut)]
+    pub upgrade_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = upgrade_authority @ RaffleError::NotProgramUpgradeAuthority,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,162 @@
+use anchor_lang::prelude::*;
+use switchboard_on_demand::SWITCHBOARD_ON_DEMAND_PROGRAM_ID;
+
+use crate::{
+    error::RaffleError,
+    instructions::draw_winning_ticket::authorize_draw,
+    randomness::{commit_orao, commit_switchboard},
+    state::{Config, Keeper, Raffle, RaffleState, RandomnessMode, RandomnessProvider},
+    time::now,
+};
+
+/// Event emitted when a raffle commits to a randomness account for its draw
+#[event]
+pub struct RandomnessRequested {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The randomness account the draw is settled from
+    pub randomness_account: Pubkey,
+    /// Slot the randomness account was committed in
+    pub commit_slot: u64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Commits a raffle drawn with verifiable randomness to a Switchboard On-Demand
+/// randomness account, whose value is revealed by the oracle and turned into the winning
+/// ticket by settle_randomness. Raffles drawn from the Switchboard or ORAO randomness
+/// provider commit to an account of the provider's registered program the same way, and
+/// are settled by fulfill_randomness.
+///
+/// Execution requirements:
+/// 1. The raffle must be in Open state and use the Vrf randomness mode, or be drawn from
+///    the Switchboard or ORAO randomness provider
+/// 2. The raffle end time must have passed, or all tickets must be sold
+/// 3. The minimum ticket threshold and unique buyer requirement must be met, and at
+///    least as many tickets must be sold as the raffle has winners
+/// 4. The caller must be the raffle's draw authority, if it has one, or otherwise a
+///    registered keeper, if the config restricts cranks to keepers
+/// 5. The randomness account must have been committed in the previous slot and not
+///    revealed yet, so nobody can know its value when the raffle commits to it. ORAO
+///    randomness accounts must not be fulfilled yet
+/// 6. If the raffle defers numbering, finalize_numbering must have numbered every entry
+///
+/// # Arguments
+/// * `ctx` - The context object containing:
+///   - `raffle`: The mutable raffle account being drawn
+///   - `randomness_account`: The Switchboard or ORAO randomness account (manually
+///     validated)
+///   - `keeper`, `keeper_registration`: The calling keeper and its registration, if
+///     cranks are restricted
+///   - `draw_authority`: The raffle's draw authority, if it has one
+///
+/// # Errors
+/// - `NotDrawAuthority` if the raffle has a draw authority that did not sign
+/// - `KeeperNotRegistered` if cranks are restricted and the caller is not a keeper
+/// - `RaffleNotOpen` if the raffle is not in Open state
+/// - `VrfNotEnabled` if the raffle is drawn without a randomness account
+/// - `RandomnessProviderNotRegistered` if the raffle's randomness provider was removed
+///   from the config
+/// - `RandomnessAlreadyRequested` if the raffle already committed to a randomness account
+/// - `RaffleNotEnded` if the raffle end time hasn't been reached
+/// - `InsufficientUniqueBuyers` if too few distinct wallets bought tickets
+/// - `InsufficientTickets` if minimum ticket threshold not met, or fewer tickets were
+///   sold than the raffle has winners
+/// - `NumberingNotFinalized` if the raffle defers numbering and finalize_numbering has
+///   not numbered every entry
+/// - `InvalidRandomnessAccount` if the account is not a randomness account of the
+///   raffle's provider
+/// - `RandomnessNotFresh` if the account was not committed in the previous slot, or
+///   before the last draw reset, or was already fulfilled
+pub fn request_randomness(ctx: Context<RequestRandomness>) -> Result<()> {
+    authorize_draw(
+        &ctx.accounts.raffle,
+        &ctx.accounts.config,
+        ctx.accounts.keeper.as_ref(),
+        ctx.accounts.keeper_registration.as_ref(),
+        ctx.accounts.draw_authority.as_ref(),
+    )?;
+
+    // Raffles drawn from a randomness provider commit to an account of its program
+    let randomness_account = &ctx.accounts.randomness_account;
+    let raffle = &ctx.accounts.raffle;
+    let commit_slot = match raffle.randomness_provider {
+        Some(RandomnessProvider::Orao) => commit_orao(
+            randomness_account,
+            &ctx.accounts.config.randomness_program(RandomnessProvider::Orao)?,
+        )?,
+        Some(provider) => commit_switchboard(
+            randomness_account,
+            &ctx.accounts.config.randomness_program(provider)?,
+            raffle.min_draw_slot,
+        )?,
+        None => commit_switchboard(
+            randomness_account,
+            &SWITCHBOARD_ON_DEMAND_PROGRAM_ID,
+            raffle.min_draw_slot,
+        )?,
+    };
+
+    let raffle = &mut ctx.accounts.raffle;
+    raffle.randomness_account = Some(randomness_account.key());
+    raffle.randomness_commit_slot = commit_slot;
+
+    let event_seq = raffle.next_event_seq()?;
+    emit!(RandomnessRequested {
+        raffle: raffle.key(),
+        randomness_account: randomness_account.key(),
+        commit_slot,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+/// Accounts required for the request_randomness instruction
+#[derive(Accounts)]
+pub struct RequestRandomness<'info> {
+    /// The raffle account to draw a winner for.
+    /// Must be in Open state, past end time or sold out, and have met minimum ticket
+    /// threshold and unique buyer requirement, with a ticket sold for every winner
+    #[account(
+        mut,
+        constraint = raffle.raffle_state == RaffleState::Open @ RaffleError::RaffleNotOpen,
+        constraint = raffle.randomness_mode == RandomnessMode::Vrf
+            || matches!(
+                raffle.randomness_provider,
+                Some(RandomnessProvider::Switchboard | RandomnessProvider::Orao)
+            )
+            @ RaffleError::VrfNotEnabled,
+        constraint = raffle.randomness_account.is_none() @ RaffleError::RandomnessAlreadyRequested,
+        constraint = now(&config)? >= raffle.end_time
+            || raffle.max_tickets == Some(raffle.current_tickets)
+            @ RaffleError::RaffleNotEnded,
+        constraint = raffle.unique_buyers_met() @ RaffleError::InsufficientUniqueBuyers,
+        constraint = raffle.threshold_met()? @ RaffleError::InsufficientTickets,
+        constraint = raffle.current_tickets >= raffle.winner_count as u64 @ RaffleError::InsufficientTickets,
+        constraint = raffle.numbering_finalized() @ RaffleError::NumberingNotFinalized,
+        constraint = raffle.reserved_tickets == 0 @ RaffleError::ReservationsPending,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    /// The Switchboard On-Demand or ORAO randomness account the draw is settled from
+    /// CHECK: The owner is checked against the provider's program and the data is
+    /// parsed as a randomness account in the instruction
+    pub randomness_account: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ RaffleError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The calling keeper, required if the config restricts cranks to keepers
+    pub keeper: Option<Signer<'info>>,
+
+    /// The registration of the calling keeper, validated manually
+    pub keeper_registration: Option<Account<'info, Keeper>>,
+
+    /// The raffle's draw authority, required if the raffle has one
+    pub draw_authority: Option<Signer<'info>>,
+}
@@ -0,0 +1,163 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    instructions::buy_tickets::{validate_direct_purchase, validate_purchase},
+    math::ticket_cost,
+    state::{Config, Raffle, RaffleState, Reservation, RESERVATION_ACCOUNT_SIZE},
+    time::{now, require_seconds},
+};
+
+/// Event emitted when tickets are reserved for a pending payment
+#[event]
+pub struct TicketsReserved {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The pubkey of the reservation
+    pub reservation: Pubkey,
+    /// The wallet the tickets are reserved for
+    pub buyer: Pubkey,
+    /// Number of tickets reserved
+    pub ticket_count: u64,
+    /// Index of the first reserved ticket, or `UNNUMBERED_TICKET_START` if the raffle
+    /// defers numbering
+    pub ticket_start_index: u64,
+    /// Time after which the reservation can only be released
+    pub expires_at: i64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to hold a ticket range for a buyer whose off-chain payment, e.g. a card
+/// payment through a fiat on-ramp, has not settled yet. The reservation is finalized
+/// into an entry or released with `confirm_reservation`, or once expired released by
+/// anyone with `release_expired_reservation`.
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `reservation_seed` - Seed of the reservation PDA and of the entry created for it
+/// * `buyer` - The wallet the entry is created for once the payment settles
+/// * `ticket_count` - The number of tickets to reserve
+/// * `expires_at` - Unix timestamp after which the reservation can only be released
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Validates caller is the management authority via config PDA
+/// 2. Validates the raffle is open and not past its end time
+/// 3. Applies the same ticket limits as a purchase, counting other pending reservations
+/// 4. Ensures the reservation expires after now and no later than the raffle end time
+/// 5. Rejects raffles paid in an SPL token, as reservations are settled in lamports, and
+///    raffles issuing ticket receipts, which only buy_tickets mints
+/// 6. If the config rejects CPI purchases, ensures reserve_tickets is a top-level
+///    instruction of the transaction
+/// 7. Bounds the pending reservations, so the ranges they void when released always
+///    fit in the raffle's `voided_ranges`
+///
+/// # Account Validations
+/// * Raffle - Must be in Open state and not expired
+/// * Reservation - New PDA initialized with seeds ["reservation", raffle_key, reservation_seed]
+/// * Management Authority - Must match the management authority stored in config
+///
+/// # Implementation Notes
+/// - The reserved tickets are numbered after all tickets sold or reserved so far, and
+///   the entry created on confirmation receives exactly that range
+/// - Tickets of raffles with deferred numbering are only numbered by finalize_numbering
+pub fn reserve_tickets(
+    ctx: Context<ReserveTickets>,
+    reservation_seed: [u8; 8],
+    buyer: Pubkey,
+    ticket_count: u64,
+    expires_at: i64,
+) -> Result<()> {
+    validate_direct_purchase(&ctx.accounts.config)?;
+
+    require!(
+        ctx.accounts.raffle.payment_mint.is_none(),
+        RaffleError::TokenPaymentsNotSupported
+    );
+    require!(
+        !ctx.accounts.raffle.ticket_receipts,
+        RaffleError::TicketReceiptsNotSupported
+    );
+    require!(
+        ctx.accounts.raffle.can_reserve(),
+        RaffleError::TooManyReservations
+    );
+    // Tiered prices never exceed the ticket price, which bounds the cost of the tickets
+    validate_purchase(
+        &ctx.accounts.raffle,
+        &ctx.accounts.management_authority,
+        ticket_count,
+        Some(ticket_cost(ticket_count, ctx.accounts.raffle.ticket_price)?),
+        now(&ctx.accounts.config)?,
+    )?;
+
+    require_seconds(expires_at)?;
+    require!(
+        expires_at > now(&ctx.accounts.config)? && expires_at <= ctx.accounts.raffle.end_time,
+        RaffleError::InvalidReservationExpiry
+    );
+
+    let reservation = &mut ctx.accounts.reservation;
+    reservation.raffle = ctx.accounts.raffle.key();
+    reservation.buyer = buyer;
+    reservation.ticket_count = ticket_count;
+    reservation.seed = reservation_seed;
+    reservation.expires_at = expires_at;
+    reservation.bump = ctx.bumps.reservation;
+    reservation.ticket_start_index = ctx.accounts.raffle.next_ticket_start()?;
+
+    // Hold the tickets so they can't be sold while the payment settles
+    let raffle = &mut ctx.accounts.raffle;
+    raffle.record_reservation(ticket_count)?;
+
+    let event_seq = raffle.next_event_seq()?;
+    emit!(TicketsReserved {
+        raffle: raffle.key(),
+        reservation: ctx.accounts.reservation.key(),
+        buyer,
+        ticket_count,
+        ticket_start_index: ctx.accounts.reservation.ticket_start_index,
+        expires_at,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(reservation_seed: [u8; 8])]
+pub struct ReserveTickets<'info> {
+    #[account(
+        mut,
+        constraint = raffle.raffle_state == RaffleState::Open @ RaffleError::RaffleNotOpen,
+        constraint = now(&config)? < raffle.end_time @ RaffleError::RaffleEnded,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    #[account(
+        init,
+        payer = management_authority,
+        space = RESERVATION_ACCOUNT_SIZE,
+        seeds = [
+            b"reservation",
+            raffle.key().as_ref(),
+            reservation_seed.as_ref(),
+        ],
+        bump,
+    )]
+    pub reservation: Account<'info, Reservation>,
+
+    #[account(mut)]
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+        constraint = !config.paused @ RaffleError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub system_program: Program<'info, System>,
+}
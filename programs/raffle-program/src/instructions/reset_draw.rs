@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    math::checked_add,
+    state::{Config, Raffle, RaffleState, CLOSE_REASON_NONE},
+    time::now,
+};
+
+/// Event emitted when a draw is reset, recording the discarded draw for auditing
+#[event]
+pub struct DrawReset {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The discarded winning ticket of the first prize
+    pub winning_ticket: u64,
+    /// The slot whose hash produced the discarded ticket
+    pub draw_slot: u64,
+    /// The lowest slot the redraw may sample entropy from
+    pub min_draw_slot: u64,
+    /// When the draw was reset
+    pub reset_at: i64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to discard a drawn winning ticket whose winner can't be set, e.g.
+/// because the winning entry account was lost or corrupted, or the ticket belongs to a
+/// released reservation
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Only the management authority can reset a draw
+/// 2. Ensures the raffle is still in Drawing state and, for raffles with multiple
+///    winners, that no winner was set yet, so a set winner is never discarded
+/// 3. Raises the raffle's minimum draw slot past the discarded draw, so the redraw
+///    can't reuse the same entropy and land on the same ticket
+/// 4. Only allows the reset once the config's claim window passed since the draw, so
+///    it remains a fallback for winners that can't be set and can't be used to reroll
+///    an unwanted result
+///
+/// # Account Validations
+/// * Raffle - Must be in Drawing state without any winner set, drawn at least the
+///   claim window ago
+/// * Config - PDA storing the management authority and the claim window
+///
+/// # Implementation Notes
+/// - Clears the winning tickets, the recorded entropy, any committed draw slot and any
+///   randomness request, and reverts the raffle to Open, after which anyone can call
+///   draw_winning_ticket, request_randomness for raffles drawn with verifiable
+///   randomness, or fulfill_randomness for raffles drawn from a randomness provider,
+///   again
+/// - The discarded draw is only preserved in the DrawReset event
+pub fn reset_draw(ctx: Context<ResetDraw>) -> Result<()> {
+    let raffle = &mut ctx.accounts.raffle;
+    let winning_ticket = raffle.winning_ticket.ok_or(RaffleError::NoWinningTicket)?;
+    let draw_timestamp = raffle.draw_timestamp.ok_or(RaffleError::NoWinningTicket)?;
+    let reset_at = now(&ctx.accounts.config)?;
+    require!(
+        reset_at >= draw_timestamp.saturating_add(ctx.accounts.config.claim_window_secs),
+        RaffleError::ResetDrawTooEarly
+    );
+    let draw_slot = raffle.draw_slot.unwrap_or_default();
+    let min_draw_slot = checked_add(draw_slot, 1)?;
+
+    raffle.winning_ticket = None;
+    raffle.winning_tickets = Vec::new();
+    raffle.draw_slot = None;
+    raffle.draw_slot_hash = None;
+    raffle.draw_timestamp = None;
+    raffle.min_draw_slot = min_draw_slot;
+    raffle.randomness_account = None;
+    raffle.randomness_commit_slot = 0;
+    raffle.draw_target_slot = 0;
+    raffle.raffle_state = RaffleState::Open;
+    raffle.close_reason = CLOSE_REASON_NONE;
+
+    let event_seq = raffle.next_event_seq()?;
+    emit!(DrawReset {
+        raffle: raffle.key(),
+        winning_ticket,
+        draw_slot,
+        min_draw_slot,
+        reset_at,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ResetDraw<'info> {
+    #[account(
+        mut,
+        constraint = raffle.raffle_state == RaffleState::Drawing @ RaffleError::RaffleNotDrawing,
+        constraint = raffle.winner_address.is_none() && raffle.winner_hash.is_none()
+            @ RaffleError::WinnerAlreadySet,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
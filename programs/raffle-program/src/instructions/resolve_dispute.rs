@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, Dispute, Raffle},
+    transfer::move_lamports,
+};
+
+/// Event emitted when a dispute is resolved
+#[event]
+pub struct DisputeResolved {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The wallet that opened the dispute
+    pub disputer: Pubkey,
+    /// Whether the dispute was upheld, refunding the bond to the disputer
+    pub upheld: bool,
+    /// Bonded lamports refunded to the disputer or slashed to the destination
+    pub amount: u64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to resolve a dispute of a raffle's draw, refunding the bond to the
+/// disputer if the dispute is upheld, or slashing it otherwise
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `upheld` - Whether the dispute is upheld
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Only the management authority can resolve disputes
+/// 2. Ensures a rejected dispute's bond is slashed to the config's insurance fund, or
+///    the payout authority while it has none
+/// 3. Ensures the rent of the dispute is returned to the disputer, who paid it
+///
+/// # Account Validations
+/// * Raffle - Must be the raffle the dispute was opened for
+/// * Dispute - PDA with seeds ["dispute", raffle_key, disputer], closed to the disputer
+/// * Disputer - Must be the wallet that opened the dispute
+/// * Destination - Must be the config's insurance fund or payout authority
+/// * Management Authority - Must match the management authority stored in config
+///
+/// # Implementation Notes
+/// - Disputes can be resolved after the dispute window closed, which only limits when
+///   they are opened
+/// - Resolving a dispute doesn't change the raffle. Upholding one records the
+///   outcome, while remedies such as reset_draw are applied separately
+pub fn resolve_dispute(ctx: Context<ResolveDispute>, upheld: bool) -> Result<()> {
+    let amount = ctx.accounts.dispute.amount;
+    if !upheld {
+        move_lamports(
+            &ctx.accounts.dispute.to_account_info(),
+            &ctx.accounts.destination.to_account_info(),
+            amount,
+        )?;
+    }
+
+    let event_seq = ctx.accounts.raffle.next_event_seq()?;
+    emit!(DisputeResolved {
+        raffle: ctx.accounts.raffle.key(),
+        disputer: ctx.accounts.disputer.key(),
+        upheld,
+        amount,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    #[account(mut)]
+    pub raffle: Account<'info, Raffle>,
+
+    /// The dispute, closed to the disputer along with the bond unless it was slashed
+    #[account(
+        mut,
+        close = disputer,
+        seeds = [
+            b"dispute",
+            raffle.key().as_ref(),
+            disputer.key().as_ref(),
+        ],
+        bump = dispute.bump,
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(mut)]
+    pub disputer: SystemAccount<'info>,
+
+    /// The insurance fund, or the payout authority while the config has none
+    #[account(
+        mut,
+        constraint = destination.key() == config.forfeiture_destination()
+            @ RaffleError::InvalidForfeitureDestination,
+    )]
+    pub destination: SystemAccount<'info>,
+
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
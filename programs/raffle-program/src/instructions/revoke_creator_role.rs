@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, CreatorRole},
+};
+
+/// Event emitted when a creator role is revoked
+#[event]
+pub struct CreatorRoleRevoked {
+    /// The wallet that held the role
+    pub creator: Pubkey,
+}
+
+/// Instruction to revoke a creator role, refunding its rent to the management authority
+///
+/// # Security Considerations
+/// - Only the management authority can revoke creator roles
+///
+/// # Implementation Notes
+/// - The creator's raffles stay open, but only the management authority can update and
+///   extend them from now on
+pub fn revoke_creator_role(ctx: Context<RevokeCreatorRole>) -> Result<()> {
+    emit!(CreatorRoleRevoked {
+        creator: ctx.accounts.creator_role.creator,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RevokeCreatorRole<'info> {
+    #[account(
+        mut,
+        close = management_authority,
+        seeds = [
+            b"creator_role",
+            creator_role.creator.as_ref(),
+        ],
+        bump = creator_role.bump,
+    )]
+    pub creator_role: Account<'info, CreatorRole>,
+
+    #[account(mut)]
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,391 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    instructions::{
+        buy_bundle::create_entry,
+        buy_tickets::{
+            validate_direct_purchase, validate_purchase, validate_wallet_limit,
+            TicketsPurchased,
+        },
+        claim_refund_with_proof::settle_snapshot_holding,
+        confirm_reservation::load_ticket_balance,
+    },
+    math::{checked_add, checked_sub, ticket_cost},
+    notifier::notify_if_sold_out,
+    state::{Config, Entry, Raffle, RaffleState, TicketBalance, Treasury},
+    time::now,
+    treasury::{LamportVault, TreasuryVault},
+};
+
+/// Event emitted when a ticket holder of an expired raffle rolls their balance over to
+/// its successor
+#[event]
+pub struct TicketsRolledOver {
+    /// The pubkey of the expired raffle
+    pub raffle: Pubkey,
+    /// The raffle the balance was rolled over to
+    pub successor: Pubkey,
+    /// The ticket holder, who closed their ticket balance in the expired raffle
+    pub owner: Pubkey,
+    /// Number of tickets of the expired raffle given up
+    pub ticket_count: u64,
+    /// Lamports the tickets were worth, after the crank reward share
+    pub rollover_amount: u64,
+    /// Number of tickets bought in the successor
+    pub successor_ticket_count: u64,
+    /// Lamports paid for the successor's tickets, the rest is returned to the owner
+    pub payment_amount: u64,
+    /// Share of the crank reward deducted from the balance
+    pub crank_share: u64,
+    /// Sequence number of this event within the expired raffle
+    pub event_seq: u64,
+}
+
+/// Instruction for a ticket holder of an expired raffle, who opted into rollover when
+/// buying, to convert their balance into tickets of the raffle's successor instead of
+/// reclaiming it
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `entry_seed` - Seed used to derive the entry PDA in the successor
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Validates the raffle is in Expired state and its refund deadline has not passed,
+///    if it has one
+/// 2. Ensures the signer owns the ticket balance and opted into rollover, and that no
+///    campaign subsidized its tickets
+/// 3. Ensures the successor is the one designated for the raffle and is open
+/// 4. Applies the successor's ticket limits as for a purchase
+/// 5. Ensures the refund does not exceed the funds collected by the expired treasury
+/// 6. If the expired raffle was snapshotted, settles the holding in the snapshot, so it
+///    can't also be refunded with claim_refund_with_proof
+/// 7. If the config rejects CPI purchases, ensures rollover_tickets is a top-level
+///    instruction of the transaction
+/// 8. Applies the successor's purchase restrictions again, as they may have changed
+///    since it was designated: rejects region and age restricted, private and receipt
+///    issuing successors, successors splitting their prize among top holders or in
+///    their priority phase, and gated successors unless the signer already holds a
+///    ticket balance in them
+///
+/// # Account Validations
+/// * Raffle - Must be in Expired state with a successor
+/// * TicketBalance - The signer's ticket balance PDA, closed after the rollover
+/// * Treasury - PDA with seeds ["treasury", raffle_key]
+/// * Successor - The raffle's successor, in Open state and before its end time
+/// * SuccessorTreasury - PDA with seeds ["treasury", successor_key]
+/// * SuccessorTicketBalance - PDA with seeds ["ticket_balance", successor_key, signer],
+///   created if the signer has none yet
+/// * Entry - The uninitialized PDA with seeds ["entry", successor_key, entry_seed]
+/// * SnapshotClaim - Optional, the uninitialized PDA with seeds ["snapshot_claim",
+///   raffle_key, signer], required if the expired raffle has a snapshot root
+///
+/// # Implementation Notes
+/// - The balance is valued as in reclaim_expired_tickets, less the tickets' share of
+///   the crank reward, but without the refund processing fee
+/// - Buys as many tickets of the successor as the balance covers at its ticket price.
+///   The balance is refunded to the signer, who then pays for the tickets, so the rest
+///   stays with the signer
+/// - The signer pays the rent of the entry and of their successor ticket balance
+/// - Emits TicketsRolledOver on the expired raffle and TicketsPurchased on the successor
+pub fn rollover_tickets(ctx: Context<RolloverTickets>, entry_seed: [u8; 8]) -> Result<()> {
+    validate_direct_purchase(&ctx.accounts.config)?;
+
+    let now = now(&ctx.accounts.config)?;
+    require!(
+        !ctx.accounts.raffle.refunds_lapsed(now),
+        RaffleError::RefundWindowClosed
+    );
+    require!(
+        ctx.accounts.ticket_balance.ticket_count > 0,
+        RaffleError::NoTicketsOwned
+    );
+    // The subsidy is returned to its campaign by reclaim_expired_tickets instead
+    require!(
+        ctx.accounts.ticket_balance.campaign_subsidy == 0,
+        RaffleError::SubsidizedRollover
+    );
+    require!(
+        now < ctx.accounts.successor.end_time,
+        RaffleError::RaffleEnded
+    );
+
+    // The successor's purchase restrictions may have changed since it was designated.
+    // Attestations, access codes, profiles, receipts and leaderboards are only handled
+    // by buy_tickets, so rollovers into raffles requiring them are rejected
+    let successor = &ctx.accounts.successor;
+    require!(
+        ctx.accounts.raffle.payment_mint.is_none() && successor.payment_mint.is_none(),
+        RaffleError::TokenPaymentsNotSupported
+    );
+    require!(
+        successor.allowed_regions == 0,
+        RaffleError::RegionAttestationRequired
+    );
+    require!(
+        !successor.age_restricted,
+        RaffleError::AgeVerificationRequired
+    );
+    require!(
+        successor.access_code_hash.is_none(),
+        RaffleError::AccessCodeRequired
+    );
+    require!(
+        !successor.in_priority_phase(now),
+        RaffleError::PriorityAccessRequired
+    );
+    require!(
+        !successor.ticket_receipts,
+        RaffleError::TicketReceiptsNotSupported
+    );
+    require!(
+        successor.top_holders.is_none(),
+        RaffleError::InvalidLeaderboard
+    );
+
+    // Gated raffles only admit wallets that created their ticket balance through the gate
+    require!(
+        successor.gate.is_none() || !ctx.accounts.successor_ticket_balance.data_is_empty(),
+        RaffleError::TicketBalanceNotInitialized
+    );
+
+    settle_snapshot_holding(
+        &ctx.accounts.raffle,
+        ctx.accounts.snapshot_claim.as_ref(),
+        &ctx.accounts.signer,
+        &ctx.accounts.system_program,
+        ctx.accounts.ticket_balance.ticket_count,
+        now,
+    )?;
+
+    // Value the tickets as reclaim_expired_tickets does, less the crank reward share
+    let ticket_balance = &ctx.accounts.ticket_balance;
+    let ticket_total = checked_sub(
+        ticket_cost(
+            ticket_balance.ticket_count,
+            ctx.accounts.raffle.ticket_price,
+        )?,
+        ticket_balance.tier_discount,
+    )?;
+    let crank_share = if ctx.accounts.treasury.total_crank_rewards > 0 {
+        ctx.accounts
+            .raffle
+            .crank_share(ticket_balance.ticket_count)?
+    } else {
+        0
+    };
+    let rollover_amount = checked_sub(ticket_total, crank_share)?;
+
+    let successor_ticket_count = rollover_amount / ctx.accounts.successor.ticket_price;
+    require!(
+        successor_ticket_count > 0,
+        RaffleError::RolloverBalanceTooLow
+    );
+
+    LamportVault::new(&mut ctx.accounts.treasury, &ctx.accounts.system_program)
+        .refund(&ctx.accounts.signer.to_account_info(), rollover_amount)?;
+
+    // The refunded balance pays for the successor's tickets, at most all of it
+    let successor = &mut ctx.accounts.successor;
+    let payment_amount = validate_purchase(
+        successor,
+        &ctx.accounts.signer,
+        successor_ticket_count,
+        Some(rollover_amount),
+        now,
+    )?;
+
+    let owner = ctx.accounts.signer.key();
+    let successor_ticket_balance_info = ctx.accounts.successor_ticket_balance.to_account_info();
+    let mut successor_ticket_balance = load_ticket_balance(
+        &successor_ticket_balance_info,
+        &ctx.accounts.signer,
+        &ctx.accounts.system_program,
+        successor.key(),
+        owner,
+    )?;
+    validate_wallet_limit(
+        successor,
+        successor_ticket_balance.ticket_count,
+        successor_ticket_count,
+    )?;
+
+    let (entry_index, ticket_start_index) = successor.record_entry()?;
+    create_entry(
+        &ctx.accounts.entry.to_account_info(),
+        &ctx.accounts.signer,
+        &ctx.accounts.system_program,
+        Entry {
+            raffle: successor.key(),
+            owner,
+            ticket_count: successor_ticket_count,
+            ticket_start_index,
+            seed: entry_seed,
+            rent_sponsored: false,
+            memo: None,
+            next_entry: successor_ticket_balance.head_entry,
+            entry_index,
+            ..Default::default()
+        },
+        now,
+    )?;
+    successor_ticket_balance.head_entry = Some(ctx.accounts.entry.key());
+
+    successor.current_tickets = checked_add(successor.current_tickets, successor_ticket_count)?;
+    if successor_ticket_balance.ticket_count == 0 {
+        successor.participant_count = checked_add(successor.participant_count, 1)?;
+    }
+    successor_ticket_balance.ticket_count = checked_add(
+        successor_ticket_balance.ticket_count,
+        successor_ticket_count,
+    )?;
+    successor_ticket_balance.total_spent =
+        checked_add(successor_ticket_balance.total_spent, payment_amount)?;
+    let unit_price = successor.unit_price(successor_ticket_count);
+    successor.record_tier_discount(
+        &mut successor_ticket_balance,
+        successor_ticket_count,
+        unit_price,
+    )?;
+    successor_ticket_balance
+        .try_serialize(&mut &mut successor_ticket_balance_info.try_borrow_mut_data()?[..])?;
+
+    LamportVault::new(
+        &mut ctx.accounts.successor_treasury,
+        &ctx.accounts.system_program,
+    )
+    .collect(&ctx.accounts.signer.to_account_info(), payment_amount)?;
+
+    let successor = &mut ctx.accounts.successor;
+    let event_seq = successor.next_event_seq()?;
+    emit!(TicketsPurchased {
+        raffle: successor.key(),
+        buyer: owner,
+        ticket_count: successor_ticket_count,
+        payment_amount,
+        unit_price,
+        ticket_start_index,
+        entry_seed,
+        campaign_discount: 0,
+        rent_sponsored: false,
+        memo: None,
+        referrer: None,
+        cumulative_tickets: successor_ticket_balance.ticket_count,
+        cumulative_spend: successor_ticket_balance.total_spent,
+        win_probability_bps: successor.win_probability_bps(successor_ticket_balance.ticket_count),
+        total_tickets: successor.current_tickets,
+        event_seq,
+    });
+
+    let raffle = &mut ctx.accounts.raffle;
+    let event_seq = raffle.next_event_seq()?;
+    emit!(TicketsRolledOver {
+        raffle: raffle.key(),
+        successor: ctx.accounts.successor.key(),
+        owner,
+        ticket_count: ctx.accounts.ticket_balance.ticket_count,
+        rollover_amount,
+        successor_ticket_count,
+        payment_amount,
+        crank_share,
+        event_seq,
+    });
+
+    notify_if_sold_out(
+        &ctx.accounts.config,
+        ctx.accounts.notifier_program.as_ref(),
+        &ctx.accounts.successor,
+    )
+}
+
+#[derive(Accounts)]
+pub struct RolloverTickets<'info> {
+    /// The ticket holder rolling their balance over
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// The expired raffle, mutable to advance its event sequence
+    #[account(
+        mut,
+        constraint = raffle.raffle_state == RaffleState::Expired @ RaffleError::RaffleNotExpired,
+        constraint = raffle.successor == Some(successor.key()) @ RaffleError::InvalidSuccessorRaffle,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    /// The signer's ticket balance in the expired raffle
+    /// Account is closed and rent is reclaimed
+    #[account(
+        mut,
+        close = signer,
+        seeds = [
+            b"ticket_balance",
+            raffle.key().as_ref(),
+            signer.key().as_ref()
+        ],
+        bump = ticket_balance.bump,
+        constraint = ticket_balance.rollover @ RaffleError::RolloverNotEnabled,
+    )]
+    pub ticket_balance: Account<'info, TicketBalance>,
+
+    /// Treasury PDA of the expired raffle, refunding the balance
+    #[account(
+        mut,
+        seeds = [
+            b"treasury",
+            raffle.key().as_ref(),
+        ],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    /// The raffle's designated successor
+    #[account(
+        mut,
+        constraint = successor.raffle_state == RaffleState::Open @ RaffleError::RaffleNotOpen,
+    )]
+    pub successor: Account<'info, Raffle>,
+
+    /// Treasury PDA of the successor, collecting the payment for its tickets
+    #[account(
+        mut,
+        seeds = [
+            b"treasury",
+            successor.key().as_ref(),
+        ],
+        bump = successor_treasury.bump,
+    )]
+    pub successor_treasury: Account<'info, Treasury>,
+
+    /// CHECK: The signer's ticket balance PDA with seeds ["ticket_balance", successor_key,
+    /// signer], created if the signer has none yet. Validated in the instruction.
+    #[account(mut)]
+    pub successor_ticket_balance: UncheckedAccount<'info>,
+
+    /// The entry created in the successor
+    /// CHECK: The address is checked against the entry PDA when the entry is created
+    #[account(mut)]
+    pub entry: UncheckedAccount<'info>,
+
+    /// Program config, checked for the pause switch
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ RaffleError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Program notified if the rollover sells the successor out, required then if the
+    /// config registers one
+    /// CHECK: Validated against the config's notifier program in the instruction
+    pub notifier_program: Option<UncheckedAccount<'info>>,
+
+    /// Optional record settling the signer's holding in the expired raffle's snapshot,
+    /// required if it has a snapshot root. PDA with seeds ["snapshot_claim", raffle_key,
+    /// signer_key], validated and created in the instruction.
+    /// CHECK: The address is checked against the PDA derived from the signer
+    #[account(mut)]
+    pub snapshot_claim: Option<UncheckedAccount<'info>>,
+}
@@ -0,0 +1,89 @@
+use anchor_lang::{prelude::*, solana_program::hash::hashv};
+
+use crate::{
+    error::RaffleError,
+    state::{Config, Raffle, RaffleState, FEATURE_ACCESS_CODE},
+};
+
+/// Event emitted when the access code of a raffle is changed
+#[event]
+pub struct AccessCodeSet {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// Whether buyers must present the raffle's access code
+    pub access_code_required: bool,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to make a raffle private, so only buyers who were shared its access code
+/// can buy tickets
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `access_code_hash` - Hash of the access code, computed with `access_code_hash`, or
+///   None to open the raffle to everyone
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Only the management authority can set a raffle's access code
+/// 2. Ensures no tickets have been sold yet, so no buyer is held to rules introduced
+///    after their purchase
+///
+/// # Account Validations
+/// * Raffle - Must be in AwaitingPrize state, or Open without any tickets sold
+/// * Config - PDA storing the management authority
+///
+/// # Implementation Notes
+/// - Sets or clears `FEATURE_ACCESS_CODE` in the raffle's feature flags
+/// - Only the hash is stored and emitted, so the code can't be read from the chain
+///   before a buyer reveals it in a purchase
+/// - The hash covers the raffle's key, so a code shared for one raffle can't be reused
+///   for another raffle with the same code
+pub fn set_access_code(
+    ctx: Context<SetAccessCode>,
+    access_code_hash: Option<[u8; 32]>,
+) -> Result<()> {
+    let raffle = &mut ctx.accounts.raffle;
+    raffle.access_code_hash = access_code_hash;
+    if access_code_hash.is_some() {
+        raffle.features |= FEATURE_ACCESS_CODE;
+    } else {
+        raffle.features &= !FEATURE_ACCESS_CODE;
+    }
+
+    let event_seq = raffle.next_event_seq()?;
+    emit!(AccessCodeSet {
+        raffle: raffle.key(),
+        access_code_required: access_code_hash.is_some(),
+        event_seq,
+    });
+
+    Ok(())
+}
+
+/// Hash of `access_code` stored by set_access_code for `raffle`
+pub fn access_code_hash(raffle: &Pubkey, access_code: &str) -> [u8; 32] {
+    hashv(&[raffle.as_ref(), access_code.as_bytes()]).to_bytes()
+}
+
+#[derive(Accounts)]
+pub struct SetAccessCode<'info> {
+    /// The raffle to make private, before any tickets are sold
+    #[account(
+        mut,
+        constraint = raffle.raffle_state == RaffleState::AwaitingPrize
+            || (raffle.raffle_state == RaffleState::Open && raffle.current_tickets == 0)
+            @ RaffleError::PrizeInfoLocked,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::RaffleError, state::Config};
+
+/// Event emitted when the age attestor is changed
+#[event]
+pub struct AgeAttestorSet {
+    /// Key trusted to attest buyers are of legal age, if any
+    pub age_attestor: Option<Pubkey>,
+}
+
+/// Instruction to register the key trusted to attest that buyers are 18 or older
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `age_attestor` - The attestor key, or None to stop accepting attestations
+///
+/// # Security Considerations
+/// - Only the management authority can change the attestor
+/// - Attestations signed by a previous attestor are no longer accepted, so rotating the
+///   key revokes all attestations it issued
+pub fn set_age_attestor(ctx: Context<SetAgeAttestor>, age_attestor: Option<Pubkey>) -> Result<()> {
+    ctx.accounts.config.age_attestor = age_attestor;
+
+    emit!(AgeAttestorSet { age_attestor });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetAgeAttestor<'info> {
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
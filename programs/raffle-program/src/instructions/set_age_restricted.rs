@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, Raffle, RaffleState, FEATURE_AGE_RESTRICTED},
+};
+
+/// Event emitted when a raffle's age restriction is changed
+#[event]
+pub struct AgeRestrictionSet {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// Whether buyers must be attested to be 18 or older
+    pub age_restricted: bool,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to restrict a raffle to buyers attested to be 18 or older
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `age_restricted` - Whether buyers must hold an age attestation
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Only the management authority can restrict a raffle
+/// 2. Ensures no tickets have been sold yet, so no buyer is held to rules introduced
+///    after their purchase
+///
+/// # Account Validations
+/// * Raffle - Must be in AwaitingPrize state, or Open without any tickets sold
+/// * Config - PDA storing the management authority
+///
+/// # Implementation Notes
+/// - Sets or clears `FEATURE_AGE_RESTRICTED` in the raffle's feature flags
+pub fn set_age_restricted(ctx: Context<SetAgeRestricted>, age_restricted: bool) -> Result<()> {
+    let raffle = &mut ctx.accounts.raffle;
+    raffle.age_restricted = age_restricted;
+    if age_restricted {
+        raffle.features |= FEATURE_AGE_RESTRICTED;
+    } else {
+        raffle.features &= !FEATURE_AGE_RESTRICTED;
+    }
+
+    let event_seq = raffle.next_event_seq()?;
+    emit!(AgeRestrictionSet {
+        raffle: raffle.key(),
+        age_restricted,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetAgeRestricted<'info> {
+    /// The raffle to restrict, before any tickets are sold
+    #[account(
+        mut,
+        constraint = raffle.raffle_state == RaffleState::AwaitingPrize
+            || (raffle.raffle_state == RaffleState::Open && raffle.current_tickets == 0)
+            @ RaffleError::PrizeInfoLocked,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, MAX_ALARM_COVERAGE_BPS},
+};
+
+/// Event emitted when the treasury alarm thresholds are changed
+#[event]
+pub struct AlarmThresholdsSet {
+    /// Share of a treasury's tracked liabilities in basis points its balance must cover
+    pub alarm_coverage_bps: u16,
+    /// Smallest shortfall that raises an alert
+    pub alarm_min_shortfall: u64,
+}
+
+/// Instruction to configure when check_invariants raises an alert for a treasury whose
+/// balance doesn't cover its tracked liabilities
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `alarm_coverage_bps` - Share of the tracked liabilities in basis points the
+///   treasury's balance must cover, or zero to disable alerts
+/// * `alarm_min_shortfall` - Smallest shortfall, in lamports or base units of the
+///   raffle's payment mint, that raises an alert
+///
+/// # Security Considerations
+/// - Only the management authority can change the thresholds
+/// - The coverage is capped at `MAX_ALARM_COVERAGE_BPS`, since treasuries never need
+///   to hold more than their tracked liabilities
+pub fn set_alarm_thresholds(
+    ctx: Context<SetAlarmThresholds>,
+    alarm_coverage_bps: u16,
+    alarm_min_shortfall: u64,
+) -> Result<()> {
+    require!(
+        alarm_coverage_bps <= MAX_ALARM_COVERAGE_BPS,
+        RaffleError::InvalidAlarmThreshold
+    );
+
+    ctx.accounts.config.alarm_coverage_bps = alarm_coverage_bps;
+    ctx.accounts.config.alarm_min_shortfall = alarm_min_shortfall;
+
+    emit!(AlarmThresholdsSet {
+        alarm_coverage_bps,
+        alarm_min_shortfall,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetAlarmThresholds<'info> {
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
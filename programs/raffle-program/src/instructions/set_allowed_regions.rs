@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, Raffle, RaffleState, FEATURE_REGION_RESTRICTED},
+};
+
+/// Event emitted when the regions a raffle is sold in are changed
+#[event]
+pub struct AllowedRegionsSet {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// Bitmask of the allowed region codes, zero if unrestricted
+    pub allowed_regions: u64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to restrict a raffle to buyers attested to reside in the given regions,
+/// for raffles subject to jurisdiction rules
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `allowed_regions` - Bitmask of the allowed region codes, or zero to lift the
+///   restriction
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Only the management authority can restrict a raffle
+/// 2. Ensures no tickets have been sold yet, so no buyer is held to rules introduced
+///    after their purchase
+///
+/// # Account Validations
+/// * Raffle - Must be in AwaitingPrize state, or Open without any tickets sold
+/// * Config - PDA storing the management authority
+///
+/// # Implementation Notes
+/// - Sets or clears `FEATURE_REGION_RESTRICTED` in the raffle's feature flags
+pub fn set_allowed_regions(ctx: Context<SetAllowedRegions>, allowed_regions: u64) -> Result<()> {
+    let raffle = &mut ctx.accounts.raffle;
+    raffle.allowed_regions = allowed_regions;
+    if allowed_regions != 0 {
+        raffle.features |= FEATURE_REGION_RESTRICTED;
+    } else {
+        raffle.features &= !FEATURE_REGION_RESTRICTED;
+    }
+
+    let event_seq = raffle.next_event_seq()?;
+    emit!(AllowedRegionsSet {
+        raffle: raffle.key(),
+        allowed_regions,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetAllowedRegions<'info> {
+    /// The raffle to restrict, before any tickets are sold
+    #[account(
+        mut,
+        constraint = raffle.raffle_state == RaffleState::AwaitingPrize
+            || (raffle.raffle_state == RaffleState::Open && raffle.current_tickets == 0)
+            @ RaffleError::PrizeInfoLocked,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    instructions::create_raffle::arweave_tx_id,
+    state::{Config, Raffle, RaffleState},
+};
+
+/// Event emitted when the Arweave mirror of a raffle's metadata is set or cleared
+#[event]
+pub struct ArweaveMirrorSet {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// Arweave transaction id of the mirrored metadata, if any
+    pub arweave_tx_id: Option<[u8; 32]>,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to record the Arweave transaction id of a permanent copy of a raffle's
+/// metadata, for raffles whose metadata URI points to HTTPS or IPFS
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `arweave_tx_id` - Arweave transaction id of the mirrored metadata, or `None` to
+///   clear it
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Only the management authority can set the mirror
+/// 2. Ensures no tickets have been sold yet, so buyers only ever enter raffles on the
+///    metadata they saw
+/// 3. Rejects raffles with `ar://` metadata URIs, whose transaction id is taken from
+///    the URI
+///
+/// # Account Validations
+/// * Raffle - Must be in AwaitingPrize state, or Open without any tickets sold
+/// * Config - PDA storing the management authority
+pub fn set_arweave_mirror(
+    ctx: Context<SetArweaveMirror>,
+    arweave_tx_id: Option<[u8; 32]>,
+) -> Result<()> {
+    let raffle = &mut ctx.accounts.raffle;
+    require!(
+        self::arweave_tx_id(&raffle.metadata_uri).is_none(),
+        RaffleError::ArweaveTxIdFixedByUri
+    );
+    raffle.arweave_tx_id = arweave_tx_id;
+
+    let event_seq = raffle.next_event_seq()?;
+    emit!(ArweaveMirrorSet {
+        raffle: raffle.key(),
+        arweave_tx_id,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetArweaveMirror<'info> {
+    /// The raffle whose metadata mirror is set, before any tickets are sold
+    #[account(
+        mut,
+        constraint = raffle.raffle_state == RaffleState::AwaitingPrize
+            || (raffle.raffle_state == RaffleState::Open && raffle.entry_count == 0)
+            @ RaffleError::PrizeInfoLocked,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{
+        ClaimInstructions, Config, Raffle, RaffleState, CLAIM_INSTRUCTIONS_ACCOUNT_SIZE,
+        MAX_CLAIM_INSTRUCTIONS_LENGTH,
+    },
+};
+
+/// Event emitted when the claim instructions of a raffle are set
+#[event]
+pub struct ClaimInstructionsSet {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The claim instructions, or a URI to a document containing them. Empty if the
+    /// config's events are compact
+    pub instructions: String,
+    /// SHA-256 hash of the referenced document, if any
+    pub content_hash: Option<[u8; 32]>,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to attach the instructions the winner follows to claim the prize
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `instructions` - The claim instructions, or a URI to a document containing them
+///   (max 256 bytes)
+/// * `content_hash` - SHA-256 hash of the referenced document, if `instructions` is a URI
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Only the management authority can set the claim instructions
+/// 2. Ensures no tickets have been sold yet, so the claim flow can't be swapped for a
+///    phishing one once the winner is known
+///
+/// # Account Validations
+/// * Raffle - Must be in AwaitingPrize state, or Open without any tickets sold
+/// * ClaimInstructions - PDA with seeds ["claim_instructions", raffle_key], created on
+///   first use
+/// * Config - PDA storing the management authority
+///
+/// # Implementation Notes
+/// - Calling the instruction again before sales start overwrites the previous
+///   instructions
+pub fn set_claim_instructions(
+    ctx: Context<SetClaimInstructions>,
+    instructions: String,
+    content_hash: Option<[u8; 32]>,
+) -> Result<()> {
+    require!(
+        !instructions.is_empty() && instructions.len() <= MAX_CLAIM_INSTRUCTIONS_LENGTH,
+        RaffleError::InvalidClaimInstructions
+    );
+
+    let claim_instructions = &mut ctx.accounts.claim_instructions;
+    claim_instructions.raffle = ctx.accounts.raffle.key();
+    claim_instructions.instructions = instructions.clone();
+    claim_instructions.content_hash = content_hash;
+    claim_instructions.bump = ctx.bumps.claim_instructions;
+
+    let event_seq = ctx.accounts.raffle.next_event_seq()?;
+    emit!(ClaimInstructionsSet {
+        raffle: ctx.accounts.raffle.key(),
+        instructions: ctx.accounts.config.event_text(instructions),
+        content_hash,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetClaimInstructions<'info> {
+    /// The raffle whose claim flow is described, before any tickets are sold
+    #[account(
+        mut,
+        constraint = raffle.raffle_state == RaffleState::AwaitingPrize
+            || (raffle.raffle_state == RaffleState::Open && raffle.current_tickets == 0)
+            @ RaffleError::ClaimInstructionsLocked,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    #[account(
+        init_if_needed,
+        payer = management_authority,
+        space = CLAIM_INSTRUCTIONS_ACCOUNT_SIZE,
+        seeds = [
+            b"claim_instructions",
+            raffle.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub claim_instructions: Account<'info, ClaimInstructions>,
+
+    #[account(mut)]
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub system_program: Program<'info, System>,
+}
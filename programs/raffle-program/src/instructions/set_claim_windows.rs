@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::RaffleError, state::Config};
+
+/// Event emitted when the claim and dispute windows are changed
+#[event]
+pub struct ClaimWindowsSet {
+    /// How long a drawn winner has to claim, in seconds
+    pub claim_window_secs: i64,
+    /// How long a draw can be disputed, in seconds
+    pub dispute_window_secs: i64,
+}
+
+/// Instruction to configure how long winners have to claim and how long draws can be
+/// disputed
+///
+/// # Security Considerations
+/// - Only the management authority can change the windows
+/// - Both windows must be greater than zero
+pub fn set_claim_windows(
+    ctx: Context<SetClaimWindows>,
+    claim_window_secs: i64,
+    dispute_window_secs: i64,
+) -> Result<()> {
+    require!(
+        claim_window_secs > 0 && dispute_window_secs > 0,
+        RaffleError::InvalidWindow
+    );
+
+    ctx.accounts.config.claim_window_secs = claim_window_secs;
+    ctx.accounts.config.dispute_window_secs = dispute_window_secs;
+
+    emit!(ClaimWindowsSet {
+        claim_window_secs,
+        dispute_window_secs,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetClaimWindows<'info> {
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
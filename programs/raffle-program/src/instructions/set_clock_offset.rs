@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::RaffleError, state::Config};
+
+/// Event emitted when the testing clock offset is changed
+#[event]
+pub struct ClockOffsetSet {
+    /// Seconds added to the cluster clock
+    pub clock_offset: i64,
+}
+
+/// Instruction to shift the time the program reads by a number of seconds, so
+/// integration tests and demos can move raffles past their end time, deadlines and claim
+/// windows deterministically
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `clock_offset` - Seconds added to the cluster clock, negative to move it back, or
+///   zero to follow the cluster clock again
+///
+/// # Security Considerations
+/// - Only the management authority can change the offset
+/// - The instruction fails on builds without the devnet feature, which ignore the offset,
+///   so the time of mainnet raffles can never be moved
+pub fn set_clock_offset(ctx: Context<SetClockOffset>, clock_offset: i64) -> Result<()> {
+    require!(
+        cfg!(feature = "devnet"),
+        RaffleError::ClockOffsetUnavailable
+    );
+
+    ctx.accounts.config.clock_offset = clock_offset;
+
+    emit!(ClockOffsetSet { clock_offset });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetClockOffset<'info> {
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
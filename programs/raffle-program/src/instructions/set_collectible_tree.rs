@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::RaffleError, state::Config};
+
+/// Event emitted when the collectible tree is changed
+#[event]
+pub struct CollectibleTreeSet {
+    /// Bubblegum tree ticket collectibles are minted into, if any
+    pub collectible_tree: Option<Pubkey>,
+}
+
+/// Instruction to register the Bubblegum tree claim_ticket_collectible mints ticket
+/// collectibles into, see `bubblegum`
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `collectible_tree` - Merkle tree whose tree delegate is the config PDA, or None to
+///   stop minting collectibles
+///
+/// # Security Considerations
+/// - Only the management authority can change the tree, as it holds the collectibles
+///   of every raffle
+/// - Bubblegum only accepts mints signed by the tree's delegate, so a tree not
+///   delegated to the config PDA fails every claim
+pub fn set_collectible_tree(
+    ctx: Context<SetCollectibleTree>,
+    collectible_tree: Option<Pubkey>,
+) -> Result<()> {
+    ctx.accounts.config.collectible_tree = collectible_tree;
+
+    emit!(CollectibleTreeSet { collectible_tree });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetCollectibleTree<'info> {
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
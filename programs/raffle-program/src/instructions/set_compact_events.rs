@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::RaffleError, state::Config};
+
+/// Event emitted when events are switched between compact and verbose
+#[event]
+pub struct CompactEventsSet {
+    /// Whether events leave out metadata URIs, memos and claim instructions
+    pub compact: bool,
+}
+
+/// Instruction to switch events between verbose, which include free-form text such as
+/// metadata URIs and memos, and compact, which leave it out to keep transaction logs
+/// small
+///
+/// # Security Considerations
+/// - Only the management authority can change the verbosity
+///
+/// # Implementation Notes
+/// - Event layouts don't change, compact events carry empty strings and no memo, so
+///   indexers read the text from the raffle, entry and claim instructions accounts
+pub fn set_compact_events(ctx: Context<SetCompactEvents>, compact: bool) -> Result<()> {
+    ctx.accounts.config.compact_events = compact;
+
+    emit!(CompactEventsSet { compact });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetCompactEvents<'info> {
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::RaffleError, state::Config};
+
+/// Event emitted when purchases via cross-program invocation are rejected or allowed
+#[event]
+pub struct CpiPurchasesRejectionSet {
+    /// Whether buy_tickets rejects purchases invoked by other programs
+    pub rejected: bool,
+}
+
+/// Instruction to reject ticket purchases invoked by other programs, so tickets are only
+/// bought by end-user wallets, or allow them again
+///
+/// # Security Considerations
+/// - Only the management authority can change the policy
+/// - Purchases are allowed via CPI by default, so other programs can compose with
+///   buy_tickets
+pub fn set_cpi_purchases_rejected(
+    ctx: Context<SetCpiPurchasesRejected>,
+    rejected: bool,
+) -> Result<()> {
+    ctx.accounts.config.reject_cpi_purchases = rejected;
+
+    emit!(CpiPurchasesRejectionSet { rejected });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetCpiPurchasesRejected<'info> {
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, MAX_CRANK_REWARD_BPS},
+};
+
+/// Event emitted when the crank reward is changed
+#[event]
+pub struct CrankRewardSet {
+    /// Share of the ticket price in basis points paid per ticket to crank callers
+    pub crank_reward_bps: u16,
+}
+
+/// Instruction to configure the reward paid to whoever cranks a raffle's expiry or draw,
+/// so the raffle lifecycle doesn't depend on an operator running keepers
+///
+/// # Security Considerations
+/// - Only the management authority can change the reward
+/// - The reward is capped at `MAX_CRANK_REWARD_BPS`
+/// - Only applies to raffles created afterwards, existing raffles keep the reward
+///   disclosed when they were created
+pub fn set_crank_reward(ctx: Context<SetCrankReward>, crank_reward_bps: u16) -> Result<()> {
+    require!(
+        crank_reward_bps <= MAX_CRANK_REWARD_BPS,
+        RaffleError::InvalidCrankReward
+    );
+
+    ctx.accounts.config.crank_reward_bps = crank_reward_bps;
+
+    emit!(CrankRewardSet { crank_reward_bps });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetCrankReward<'info> {
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
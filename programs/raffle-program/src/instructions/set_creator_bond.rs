@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::RaffleError, state::Config};
+
+/// Event emitted when the creator bond is changed
+#[event]
+pub struct CreatorBondSet {
+    /// Lamports the creator of a raffle must bond at creation
+    pub creator_bond: u64,
+}
+
+/// Instruction to require raffle creators to bond lamports at creation, which are
+/// slashed to the insurance fund if the raffle is voided for fraud
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `creator_bond` - Lamports bonded per raffle, or zero to stop requiring a bond
+///
+/// # Security Considerations
+/// - Only the upgrade authority can change the bond, so the management authority
+///   creating raffles can't lift its own bond
+/// - Only applies to raffles created afterwards, bonds already deposited are unchanged
+pub fn set_creator_bond(ctx: Context<SetCreatorBond>, creator_bond: u64) -> Result<()> {
+    ctx.accounts.config.creator_bond = creator_bond;
+
+    emit!(CreatorBondSet { creator_bond });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetCreatorBond<'info> {
+    pub upgrade_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = upgrade_authority @ RaffleError::NotProgramUpgradeAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, Raffle, RaffleState, FEATURE_DEFERRED_NUMBERING},
+};
+
+/// Event emitted when deferred ticket numbering of a raffle is enabled or disabled
+#[event]
+pub struct DeferredNumberingSet {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// Whether ticket numbers are assigned by finalize_numbering after the sale
+    pub deferred_numbering: bool,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to defer the numbering of a raffle's tickets until the sale closed, so
+/// competitors can't watch which ticket positions are taken while tickets are on sale
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `deferred_numbering` - Whether entries only record their ticket counts during the
+///   sale, with ticket numbers assigned by finalize_numbering before the draw
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Only the management authority can change how tickets are numbered
+/// 2. Ensures no tickets have been sold yet, so every entry of the raffle is numbered
+///    the same way
+/// 3. Rejects raffles issuing ticket receipts, as a receipt records the entry's ticket
+///    range at purchase
+///
+/// # Account Validations
+/// * Raffle - Must be in AwaitingPrize state, or Open without any tickets sold
+/// * Config - PDA storing the management authority
+///
+/// # Implementation Notes
+/// - Sets or clears `FEATURE_DEFERRED_NUMBERING` in the raffle's feature flags
+pub fn set_deferred_numbering(
+    ctx: Context<SetDeferredNumbering>,
+    deferred_numbering: bool,
+) -> Result<()> {
+    let raffle = &mut ctx.accounts.raffle;
+    require!(
+        !deferred_numbering || !raffle.ticket_receipts,
+        RaffleError::TicketReceiptsNotSupported
+    );
+    raffle.deferred_numbering = deferred_numbering;
+    if deferred_numbering {
+        raffle.features |= FEATURE_DEFERRED_NUMBERING;
+    } else {
+        raffle.features &= !FEATURE_DEFERRED_NUMBERING;
+    }
+
+    let event_seq = raffle.next_event_seq()?;
+    emit!(DeferredNumberingSet {
+        raffle: raffle.key(),
+        deferred_numbering,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetDeferredNumbering<'info> {
+    /// The raffle whose ticket numbering is set, before any tickets are sold
+    #[account(
+        mut,
+        constraint = raffle.raffle_state == RaffleState::AwaitingPrize
+            || (raffle.raffle_state == RaffleState::Open && raffle.entry_count == 0)
+            @ RaffleError::PrizeInfoLocked,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::RaffleError, state::Config};
+
+/// Event emitted when the dispute bond is changed
+#[event]
+pub struct DisputeBondSet {
+    /// Lamports a wallet must bond to dispute a draw
+    pub dispute_bond: u64,
+}
+
+/// Instruction to set the lamports a wallet must bond to dispute a raffle's draw with
+/// open_dispute
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `dispute_bond` - Lamports bonded per dispute (must be > 0)
+///
+/// # Security Considerations
+/// - Only the upgrade authority can change the bond, so the management authority
+///   resolving disputes can't price disputers out
+/// - Only applies to disputes opened afterwards, bonds already deposited are unchanged
+pub fn set_dispute_bond(ctx: Context<SetDisputeBond>, dispute_bond: u64) -> Result<()> {
+    require!(dispute_bond > 0, RaffleError::InvalidDisputeBond);
+
+    ctx.accounts.config.dispute_bond = dispute_bond;
+
+    emit!(DisputeBondSet { dispute_bond });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetDisputeBond<'info> {
+    pub upgrade_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = upgrade_authority @ RaffleError::NotProgramUpgradeAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
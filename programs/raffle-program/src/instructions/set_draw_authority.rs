@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, Raffle, RaffleState, FEATURE_DRAW_AUTHORITY},
+};
+
+/// Event emitted when the draw authority of a raffle is changed
+#[event]
+pub struct DrawAuthoritySet {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// Key that alone can trigger the draw, if any
+    pub draw_authority: Option<Pubkey>,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to hand the draw of a raffle to a dedicated key, distinct from the
+/// management authority, e.g. a neutral third party for sponsored raffles
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `draw_authority` - Key that alone can trigger the draw, or None to let keepers
+///   crank it again
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Only the management authority can assign the draw authority
+/// 2. Ensures no tickets have been sold yet, so buyers always know who draws the raffle
+///    they bought into and the management authority can't take over the draw later
+///
+/// # Account Validations
+/// * Raffle - Must be in AwaitingPrize state, or Open without any tickets sold
+/// * Config - PDA storing the management authority
+///
+/// # Implementation Notes
+/// - Sets or clears `FEATURE_DRAW_AUTHORITY` in the raffle's feature flags
+pub fn set_draw_authority(
+    ctx: Context<SetDrawAuthority>,
+    draw_authority: Option<Pubkey>,
+) -> Result<()> {
+    let raffle = &mut ctx.accounts.raffle;
+    raffle.draw_authority = draw_authority;
+    if draw_authority.is_some() {
+        raffle.features |= FEATURE_DRAW_AUTHORITY;
+    } else {
+        raffle.features &= !FEATURE_DRAW_AUTHORITY;
+    }
+
+    let event_seq = raffle.next_event_seq()?;
+    emit!(DrawAuthoritySet {
+        raffle: raffle.key(),
+        draw_authority,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetDrawAuthority<'info> {
+    /// The raffle whose draw is assigned, before any tickets are sold
+    #[account(
+        mut,
+        constraint = raffle.raffle_state == RaffleState::AwaitingPrize
+            || (raffle.raffle_state == RaffleState::Open && raffle.current_tickets == 0)
+            @ RaffleError::PrizeInfoLocked,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, Raffle, RaffleState, FEATURE_DRAW_DELAY, MAX_DRAW_DELAY_SLOTS},
+};
+
+/// Event emitted when the draw delay of a raffle is changed
+#[event]
+pub struct DrawDelaySet {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// Maximum number of slots the draw slot is randomly delayed by, 0 if not delayed
+    pub draw_delay_slots: u16,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to randomly delay the slot a raffle drawn from the SlotHashes sysvar is
+/// drawn from, so no slot leader can pre-position for the slot whose hash decides the
+/// winner
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `draw_delay_slots` - Maximum number of slots the draw slot is delayed by after
+///   commit_draw_slot, or 0 to draw from the latest slot hash
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Only the management authority can change the draw delay
+/// 2. Ensures no tickets have been sold yet, so buyers always know how the raffle they
+///    bought into is drawn
+/// 3. Bounds the delay to `MAX_DRAW_DELAY_SLOTS`, so the drawn slot stays in the
+///    SlotHashes sysvar long enough to be drawn from
+///
+/// # Account Validations
+/// * Raffle - Must be in AwaitingPrize state, or Open without any tickets sold
+/// * Config - PDA storing the management authority
+///
+/// # Implementation Notes
+/// - Sets or clears `FEATURE_DRAW_DELAY` in the raffle's feature flags
+/// - Only applies to raffles drawn from the SlotHashes sysvar
+pub fn set_draw_delay(ctx: Context<SetDrawDelay>, draw_delay_slots: u16) -> Result<()> {
+    require!(
+        draw_delay_slots <= MAX_DRAW_DELAY_SLOTS,
+        RaffleError::InvalidDrawDelay
+    );
+
+    let raffle = &mut ctx.accounts.raffle;
+    raffle.draw_delay_slots = draw_delay_slots;
+    if draw_delay_slots > 0 {
+        raffle.features |= FEATURE_DRAW_DELAY;
+    } else {
+        raffle.features &= !FEATURE_DRAW_DELAY;
+    }
+
+    let event_seq = raffle.next_event_seq()?;
+    emit!(DrawDelaySet {
+        raffle: raffle.key(),
+        draw_delay_slots,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetDrawDelay<'info> {
+    /// The raffle whose draw delay is set, before any tickets are sold
+    #[account(
+        mut,
+        constraint = raffle.raffle_state == RaffleState::AwaitingPrize
+            || (raffle.raffle_state == RaffleState::Open && raffle.current_tickets == 0)
+            @ RaffleError::PrizeInfoLocked,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, MAX_EXTENSION_SECS},
+};
+
+/// Event emitted when the raffle extension limits are changed
+#[event]
+pub struct ExtensionLimitsSet {
+    /// Number of times a raffle's end time can be pushed back
+    pub max_raffle_extensions: u8,
+    /// Total time in seconds a raffle's end time can be pushed back by
+    pub max_extension_secs: i64,
+}
+
+/// Instruction to configure how often and how far extend_raffle can push back the end
+/// time of a raffle short of its threshold
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `max_raffle_extensions` - Number of times a raffle can be extended, or zero to stop
+///   extensions
+/// * `max_extension_secs` - Total time in seconds a raffle can be extended by
+///
+/// # Security Considerations
+/// - Only the management authority can change the limits
+/// - The total extension is capped at `MAX_EXTENSION_SECS`
+/// - Applies to every raffle, including the extensions it already received
+pub fn set_extension_limits(
+    ctx: Context<SetExtensionLimits>,
+    max_raffle_extensions: u8,
+    max_extension_secs: i64,
+) -> Result<()> {
+    require!(
+        (0..=MAX_EXTENSION_SECS).contains(&max_extension_secs),
+        RaffleError::InvalidExtensionLimits
+    );
+
+    ctx.accounts.config.max_raffle_extensions = max_raffle_extensions;
+    ctx.accounts.config.max_extension_secs = max_extension_secs;
+
+    emit!(ExtensionLimitsSet {
+        max_raffle_extensions,
+        max_extension_secs,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetExtensionLimits<'info> {
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
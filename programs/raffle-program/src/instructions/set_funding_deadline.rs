@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, Raffle, RaffleState},
+    time::{now, require_seconds},
+};
+
+/// Event emitted when the funding deadline of a raffle is set or cleared
+#[event]
+pub struct FundingDeadlineSet {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// Time by which the raffle must meet its threshold, if any
+    pub funding_deadline: Option<i64>,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to set a funding deadline ahead of a raffle's end time, so a raffle
+/// that clearly fails to sell can be expired and refunded early
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `funding_deadline` - Time by which the raffle must meet its ticket or revenue
+///   threshold, or None to only check the threshold at the end time
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Only the management authority can set the funding deadline
+/// 2. Ensures no tickets have been sold or reserved, so buyers only ever enter raffles
+///    on the terms they saw
+/// 3. Validates that the deadline is in the future and before the end time
+///
+/// # Account Validations
+/// * Raffle - Must be in AwaitingPrize or Open state without any tickets sold or reserved
+/// * Config - PDA storing the management authority
+///
+/// # Implementation Notes
+/// - Once the deadline passes without the threshold met, ticket sales stop and the
+///   raffle can be expired with expire_raffle
+/// - Raffles meeting their threshold in time keep selling until the end time
+pub fn set_funding_deadline(
+    ctx: Context<SetFundingDeadline>,
+    funding_deadline: Option<i64>,
+) -> Result<()> {
+    let raffle = &mut ctx.accounts.raffle;
+    if let Some(funding_deadline) = funding_deadline {
+        require_seconds(funding_deadline)?;
+        require!(
+            funding_deadline > now(&ctx.accounts.config)? && funding_deadline < raffle.end_time,
+            RaffleError::InvalidFundingDeadline
+        );
+    }
+    raffle.funding_deadline = funding_deadline;
+
+    let event_seq = raffle.next_event_seq()?;
+    emit!(FundingDeadlineSet {
+        raffle: raffle.key(),
+        funding_deadline,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetFundingDeadline<'info> {
+    /// The raffle whose funding deadline is set, before its first ticket sale
+    #[account(
+        mut,
+        constraint = raffle.raffle_state == RaffleState::AwaitingPrize
+            || raffle.raffle_state == RaffleState::Open @ RaffleError::RaffleNotOpen,
+        constraint = raffle.current_tickets == 0 && raffle.reserved_tickets == 0
+            @ RaffleError::TicketsAlreadySold,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
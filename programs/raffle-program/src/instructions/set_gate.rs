@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, Gate, Raffle, RaffleState, FEATURE_GATED},
+};
+
+/// Event emitted when the purchase gate of a raffle is changed
+#[event]
+pub struct GateSet {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// Requirement buyers must meet, or None if anyone can buy tickets
+    pub gate: Option<Gate>,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to restrict a raffle to holders of a community token or NFT, or to the
+/// wallets of a fixed allowlist
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `gate` - Requirement buyers must meet, or None to open the raffle to everyone
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Only the management authority can gate a raffle
+/// 2. Ensures no tickets have been sold yet, so no buyer is held to rules introduced
+///    after their purchase
+/// 3. Ensures a token holder gate requires a minimum amount greater than 0
+///
+/// # Account Validations
+/// * Raffle - Must be in AwaitingPrize state, or Open without any tickets sold
+/// * Config - PDA storing the management authority
+///
+/// # Implementation Notes
+/// - Sets or clears `FEATURE_GATED` in the raffle's feature flags
+/// - The gate is checked by `init_ticket_balance`, so wallets that initialized their
+///   ticket balance of an open raffle before it was gated are not checked
+/// - Token holder gates reject transactions that also write to the gate token account,
+///   so the gate can't be passed with tokens held only for the transaction
+pub fn set_gate(ctx: Context<SetGate>, gate: Option<Gate>) -> Result<()> {
+    if let Some(Gate::TokenHolder { min_amount, .. }) = &gate {
+        require!(*min_amount > 0, RaffleError::InvalidGate);
+    }
+
+    let raffle = &mut ctx.accounts.raffle;
+    if gate.is_some() {
+        raffle.features |= FEATURE_GATED;
+    } else {
+        raffle.features &= !FEATURE_GATED;
+    }
+    raffle.gate = gate.clone();
+
+    let event_seq = raffle.next_event_seq()?;
+    emit!(GateSet {
+        raffle: raffle.key(),
+        gate,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetGate<'info> {
+    /// The raffle to gate, before any tickets are sold
+    #[account(
+        mut,
+        constraint = raffle.raffle_state == RaffleState::AwaitingPrize
+            || (raffle.raffle_state == RaffleState::Open && raffle.current_tickets == 0)
+            @ RaffleError::PrizeInfoLocked,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::RaffleError, state::Config};
+
+/// Event emitted when the identity issuer is changed
+#[event]
+pub struct IdentityIssuerSet {
+    /// Key trusted to attest winners' identity credentials, if any
+    pub identity_issuer: Option<Pubkey>,
+}
+
+/// Instruction to register the key trusted to attest that winners hold a verifiable
+/// identity credential
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `identity_issuer` - The issuer key, or None to stop accepting attestations
+///
+/// # Security Considerations
+/// - Only the management authority can change the issuer
+/// - Attestations signed by a previous issuer are no longer accepted, so rotating the
+///   key revokes all attestations it issued
+pub fn set_identity_issuer(
+    ctx: Context<SetIdentityIssuer>,
+    identity_issuer: Option<Pubkey>,
+) -> Result<()> {
+    ctx.accounts.config.identity_issuer = identity_issuer;
+
+    emit!(IdentityIssuerSet { identity_issuer });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetIdentityIssuer<'info> {
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
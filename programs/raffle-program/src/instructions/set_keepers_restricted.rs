@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::RaffleError, state::Config};
+
+/// Event emitted when cranks are restricted to keepers or opened to anyone
+#[event]
+pub struct KeepersRestrictionSet {
+    /// Whether draws and expiries can only be cranked by registered keepers
+    pub restricted: bool,
+}
+
+/// Instruction to restrict draws and expiries to registered keepers, or open them to
+/// anyone again
+///
+/// # Security Considerations
+/// - Only the management authority can change the restriction
+/// - Restricting without registering any keeper halts draws and expiries
+pub fn set_keepers_restricted(ctx: Context<SetKeepersRestricted>, restricted: bool) -> Result<()> {
+    ctx.accounts.config.restrict_keepers = restricted;
+
+    emit!(KeepersRestrictionSet { restricted });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetKeepersRestricted<'info> {
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
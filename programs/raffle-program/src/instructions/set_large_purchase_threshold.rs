@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::RaffleError, state::Config};
+
+/// Event emitted when the large purchase threshold is changed
+#[event]
+pub struct LargePurchaseThresholdSet {
+    /// Lamports a single purchase must exceed to emit a LargePurchase event
+    pub large_purchase_threshold: u64,
+}
+
+/// Instruction to configure above which payment a purchase emits a LargePurchase event,
+/// letting monitoring and community bots react to large purchases
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `large_purchase_threshold` - Lamports a single purchase must exceed, or zero to
+///   stop emitting LargePurchase events
+///
+/// # Security Considerations
+/// - Only the management authority can change the threshold
+/// - The threshold only affects events, purchases are accepted regardless of their size
+pub fn set_large_purchase_threshold(
+    ctx: Context<SetLargePurchaseThreshold>,
+    large_purchase_threshold: u64,
+) -> Result<()> {
+    ctx.accounts.config.large_purchase_threshold = large_purchase_threshold;
+
+    emit!(LargePurchaseThresholdSet {
+        large_purchase_threshold,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetLargePurchaseThreshold<'info> {
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::RaffleError, state::Config};
+
+/// Event emitted when the maximum number of open raffles is changed
+#[event]
+pub struct MaxOpenRafflesSet {
+    /// Maximum number of raffles that can be open at once, or zero if unlimited
+    pub max_open_raffles: u32,
+    /// Number of raffles open when the maximum was set
+    pub open_raffle_count: u32,
+}
+
+/// Instruction to limit how many raffles can be open at once, so a compromised or buggy
+/// creator key can't spam raffles
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `max_open_raffles` - Maximum number of raffles that can be open at once, or zero to
+///   lift the limit
+///
+/// # Security Considerations
+/// - Only the management authority can change the limit
+/// - A limit below the current number of open raffles is accepted, it blocks new raffles
+///   until enough of the open ones expired, were cancelled, were claimed or were paid out
+///
+/// # Implementation Notes
+/// - Raffles created before the count was introduced aren't counted, so the count can
+///   fall short of the raffles actually open until they conclude
+pub fn set_max_open_raffles(ctx: Context<SetMaxOpenRaffles>, max_open_raffles: u32) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.max_open_raffles = max_open_raffles;
+
+    emit!(MaxOpenRafflesSet {
+        max_open_raffles,
+        open_raffle_count: config.open_raffle_count,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetMaxOpenRaffles<'info> {
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
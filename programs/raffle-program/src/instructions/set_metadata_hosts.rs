@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, MAX_METADATA_HOSTS},
+};
+
+/// Event emitted when the metadata host allowlist is changed
+#[event]
+pub struct MetadataHostsSet {
+    /// SHA-256 hashes of the allowed lowercase hostnames
+    pub allowed_metadata_hosts: Vec<[u8; 32]>,
+}
+
+/// Instruction to replace the allowlist of hostnames that HTTPS metadata URIs of new
+/// raffles may point to. An empty list allows any host.
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `allowed_metadata_hosts` - SHA-256 hashes of the allowed lowercase hostnames, as
+///   computed by `metadata_host_hash`
+///
+/// # Security Considerations
+/// - Only the upgrade authority can change the allowlist, so a compromised management
+///   authority cannot point raffles at arbitrary domains
+/// - At most `MAX_METADATA_HOSTS` hosts can be allowed
+pub fn set_metadata_hosts(
+    ctx: Context<SetMetadataHosts>,
+    allowed_metadata_hosts: Vec<[u8; 32]>,
+) -> Result<()> {
+    require!(
+        allowed_metadata_hosts.len() <= MAX_METADATA_HOSTS,
+        RaffleError::TooManyMetadataHosts
+    );
+
+    ctx.accounts.config.allowed_metadata_hosts = allowed_metadata_hosts.clone();
+
+    emit!(MetadataHostsSet {
+        allowed_metadata_hosts,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetMetadataHosts<'info> {
+    pub upgrade_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = upgrade_authority @ RaffleError::NotProgramUpgradeAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::RaffleError, state::Config};
+
+/// Event emitted when the notifier program is changed
+#[event]
+pub struct NotifierProgramSet {
+    /// Program invoked with lifecycle notifications of raffles, if any
+    pub notifier_program: Option<Pubkey>,
+}
+
+/// Instruction to register the program invoked when raffles are created, sell out, are
+/// drawn and are claimed, see `notifier`
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `notifier_program` - Program implementing the notify_raffle_lifecycle instruction,
+///   or None to stop notifying
+///
+/// # Security Considerations
+/// - Only the management authority can change the notifier, as a failing notifier fails
+///   the notifying instructions, including draws and claims
+/// - The notifier authenticates the raffle program by the config PDA signing the
+///   invocation
+pub fn set_notifier_program(
+    ctx: Context<SetNotifierProgram>,
+    notifier_program: Option<Pubkey>,
+) -> Result<()> {
+    ctx.accounts.config.notifier_program = notifier_program;
+
+    emit!(NotifierProgramSet { notifier_program });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetNotifierProgram<'info> {
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
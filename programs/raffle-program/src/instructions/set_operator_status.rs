@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, Raffle, OPERATOR_STATUS_FRAUD_REVIEW},
+};
+
+/// Event emitted when the operator status of a raffle is changed
+#[event]
+pub struct OperatorStatusSet {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// Operational state of the raffle, one of the `OPERATOR_STATUS_*` values
+    pub operator_status: u8,
+    /// Hash of the off-chain note explaining the status
+    pub status_note_hash: Option<[u8; 32]>,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to report the operational state of a raffle, such as its prize being
+/// shipped or delayed, so transparency dashboards can display it from on-chain data
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `operator_status` - Operational state of the raffle, one of the `OPERATOR_STATUS_*`
+///   values
+/// * `status_note_hash` - Hash of the off-chain note explaining the status, if any
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Only the management authority can report the status
+/// 2. Validates the status is a known `OPERATOR_STATUS_*` value
+///
+/// # Account Validations
+/// * Config - PDA storing the management authority
+///
+/// # Implementation Notes
+/// - The status is informational and doesn't affect any other instruction, so it can be
+///   reported in every raffle state
+pub fn set_operator_status(
+    ctx: Context<SetOperatorStatus>,
+    operator_status: u8,
+    status_note_hash: Option<[u8; 32]>,
+) -> Result<()> {
+    require!(
+        operator_status <= OPERATOR_STATUS_FRAUD_REVIEW,
+        RaffleError::InvalidOperatorStatus
+    );
+
+    let raffle = &mut ctx.accounts.raffle;
+    raffle.operator_status = operator_status;
+    raffle.status_note_hash = status_note_hash;
+
+    let event_seq = raffle.next_event_seq()?;
+    emit!(OperatorStatusSet {
+        raffle: raffle.key(),
+        operator_status,
+        status_note_hash,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetOperatorStatus<'info> {
+    /// The raffle whose operator status is reported
+    #[account(mut)]
+    pub raffle: Account<'info, Raffle>,
+
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::RaffleError, state::Config};
+
+/// Event emitted when the program is paused or resumed
+#[event]
+pub struct PauseSet {
+    /// Whether the program is paused
+    pub paused: bool,
+}
+
+/// Emergency switch to halt the program while a vulnerability is investigated, or to
+/// resume it afterwards
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `paused` - Whether the program is paused
+///
+/// # Security Considerations
+/// - Only the upgrade authority can pause or resume the program, so a leaked
+///   management key can neither freeze it nor lift a freeze
+/// - While paused, raffle creation, ticket sales, draws and treasury withdrawals fail
+///   with ProgramPaused
+/// - Refunds through reclaim_expired_tickets stay available, so ticket holders can
+///   always recover their funds
+pub fn set_pause(ctx: Context<SetPause>, paused: bool) -> Result<()> {
+    ctx.accounts.config.paused = paused;
+
+    emit!(PauseSet { paused });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPause<'info> {
+    pub upgrade_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = upgrade_authority @ RaffleError::NotProgramUpgradeAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, MAX_PAYOUT_DESTINATIONS},
+};
+
+/// Event emitted when the approved payout destinations are changed
+#[event]
+pub struct PayoutDestinationsSet {
+    /// Accounts treasury withdrawals may be paid to
+    pub payout_destinations: Vec<Pubkey>,
+}
+
+/// Instruction to replace the accounts treasury withdrawals may be paid to. An empty
+/// list pays withdrawals to the payout authority.
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `payout_destinations` - The approved withdrawal destinations
+///
+/// # Security Considerations
+/// - Only the upgrade authority can change the destinations, so leaked management and
+///   payout keys can only move funds to pre-approved addresses
+/// - At most `MAX_PAYOUT_DESTINATIONS` destinations can be approved
+pub fn set_payout_destinations(
+    ctx: Context<SetPayoutDestinations>,
+    payout_destinations: Vec<Pubkey>,
+) -> Result<()> {
+    require!(
+        payout_destinations.len() <= MAX_PAYOUT_DESTINATIONS,
+        RaffleError::TooManyPayoutDestinations
+    );
+
+    ctx.accounts.config.payout_destinations = payout_destinations.clone();
+
+    emit!(PayoutDestinationsSet {
+        payout_destinations,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPayoutDestinations<'info> {
+    pub upgrade_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = upgrade_authority @ RaffleError::NotProgramUpgradeAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
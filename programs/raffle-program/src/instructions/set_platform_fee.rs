@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, MAX_PLATFORM_FEE_BPS},
+};
+
+/// Event emitted when the platform fee on treasury withdrawals is changed
+#[event]
+pub struct PlatformFeeSet {
+    /// Share of withdrawals in basis points paid to the fee recipient
+    pub fee_bps: u16,
+    /// Account the fee is paid to, or None if no fee is charged
+    pub fee_recipient: Option<Pubkey>,
+}
+
+/// Instruction to configure the platform fee split off treasury withdrawals
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `fee_bps` - Share of withdrawals in basis points paid to the fee recipient, used
+///   by raffles without a fee override
+/// * `fee_recipient` - Account the fee is paid to, or None to stop charging fees
+///
+/// # Security Considerations
+/// - Only the management authority can change the fee
+/// - The fee is capped at `MAX_PLATFORM_FEE_BPS`
+/// - Applies to all later withdrawals, including those of existing raffles
+pub fn set_platform_fee(
+    ctx: Context<SetPlatformFee>,
+    fee_bps: u16,
+    fee_recipient: Option<Pubkey>,
+) -> Result<()> {
+    require!(
+        fee_bps <= MAX_PLATFORM_FEE_BPS,
+        RaffleError::InvalidPlatformFee
+    );
+
+    ctx.accounts.config.fee_bps = fee_bps;
+    ctx.accounts.config.fee_recipient = fee_recipient;
+
+    emit!(PlatformFeeSet {
+        fee_bps,
+        fee_recipient,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPlatformFee<'info> {
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
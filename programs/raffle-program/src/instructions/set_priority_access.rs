@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, Raffle, RaffleState, FEATURE_PRIORITY_ACCESS},
+    time::{now, require_seconds},
+};
+
+/// Event emitted when the priority phase of a raffle is changed
+#[event]
+pub struct PriorityAccessSet {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// Lowest loyalty tier allowed to buy during the priority phase
+    pub priority_min_tier: u8,
+    /// End of the priority phase, zero if the raffle has none
+    pub priority_until: i64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to open the sales of a raffle in two phases, giving wallets of a loyalty
+/// tier a head start before the public sale
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `priority_min_tier` - Lowest `UserProfile` tier allowed to buy during the priority
+///   phase (must be > 0)
+/// * `priority_until` - End of the priority phase, or zero to open sales to everyone
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Only the management authority can configure the priority phase
+/// 2. Ensures no tickets have been sold yet, so the phases can't be changed mid-sale
+/// 3. Ensures the priority phase ends in the future and before the raffle ends, so the
+///    public sale always takes place
+///
+/// # Account Validations
+/// * Raffle - Must be in AwaitingPrize state, or Open without any tickets sold
+/// * Config - PDA storing the management authority
+///
+/// # Implementation Notes
+/// - Sets or clears `FEATURE_PRIORITY_ACCESS` in the raffle's feature flags
+pub fn set_priority_access(
+    ctx: Context<SetPriorityAccess>,
+    priority_min_tier: u8,
+    priority_until: i64,
+) -> Result<()> {
+    let raffle = &mut ctx.accounts.raffle;
+    if priority_until != 0 {
+        require_seconds(priority_until)?;
+        require!(
+            priority_min_tier > 0
+                && priority_until > now(&ctx.accounts.config)?
+                && priority_until < raffle.end_time,
+            RaffleError::InvalidPriorityAccess
+        );
+        raffle.features |= FEATURE_PRIORITY_ACCESS;
+    } else {
+        raffle.features &= !FEATURE_PRIORITY_ACCESS;
+    }
+    raffle.priority_min_tier = priority_min_tier;
+    raffle.priority_until = priority_until;
+
+    let event_seq = raffle.next_event_seq()?;
+    emit!(PriorityAccessSet {
+        raffle: raffle.key(),
+        priority_min_tier,
+        priority_until,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPriorityAccess<'info> {
+    /// The raffle to configure, before any tickets are sold
+    #[account(
+        mut,
+        constraint = raffle.raffle_state == RaffleState::AwaitingPrize
+            || (raffle.raffle_state == RaffleState::Open && raffle.current_tickets == 0)
+            @ RaffleError::PrizeInfoLocked,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::RaffleError, state::Config};
+
+/// Event emitted when the prize deposit requirement is changed
+#[event]
+pub struct PrizeDepositRequirementSet {
+    /// Whether new raffles must escrow their prize before opening
+    pub required: bool,
+}
+
+/// Instruction to require, or stop requiring, a prize deposit before new raffles open
+///
+/// # Security Considerations
+/// - Only the management authority can change the requirement
+/// - Only applies to raffles created afterwards, existing raffles keep their state
+pub fn set_prize_deposit_required(
+    ctx: Context<SetPrizeDepositRequired>,
+    required: bool,
+) -> Result<()> {
+    ctx.accounts.config.require_prize_deposit = required;
+
+    emit!(PrizeDepositRequirementSet { required });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPrizeDepositRequired<'info> {
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{
+        Config, PrizeInfo, PrizeType, Raffle, RaffleState, MAX_PRIZE_TITLE_LENGTH,
+        PRIZE_INFO_ACCOUNT_SIZE,
+    },
+};
+
+/// Event emitted when the prize details of a raffle are set
+#[event]
+pub struct PrizeInfoSet {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The prize title
+    pub title: String,
+    /// Estimated prize value in lamports
+    pub value_estimate: u64,
+    /// The kind of prize
+    pub prize_type: PrizeType,
+    /// The account escrowing the prize, if any
+    pub escrow: Option<Pubkey>,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to set the structured prize details of a raffle
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `title` - Short prize title (max 64 bytes)
+/// * `value_estimate` - Estimated prize value in lamports
+/// * `prize_type` - The kind of prize
+/// * `escrow` - Optional account escrowing the prize
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Only the management authority can set the prize details
+/// 2. Ensures no tickets have been sold yet, so buyers always see the details they
+///    bought into
+///
+/// # Account Validations
+/// * Raffle - Must be in AwaitingPrize state, or Open without any tickets sold
+/// * PrizeInfo - PDA with seeds ["prize_info", raffle_key], created on first use
+/// * Config - PDA storing the management authority
+///
+/// # Implementation Notes
+/// - Calling the instruction again before sales start overwrites the previous details
+pub fn set_prize_info(
+    ctx: Context<SetPrizeInfo>,
+    title: String,
+    value_estimate: u64,
+    prize_type: PrizeType,
+    escrow: Option<Pubkey>,
+) -> Result<()> {
+    require!(
+        title.len() <= MAX_PRIZE_TITLE_LENGTH,
+        RaffleError::PrizeTitleTooLong
+    );
+
+    let prize_info = &mut ctx.accounts.prize_info;
+    prize_info.raffle = ctx.accounts.raffle.key();
+    prize_info.title = title.clone();
+    prize_info.value_estimate = value_estimate;
+    prize_info.prize_type = prize_type.clone();
+    prize_info.escrow = escrow;
+    prize_info.bump = ctx.bumps.prize_info;
+
+    let event_seq = ctx.accounts.raffle.next_event_seq()?;
+    emit!(PrizeInfoSet {
+        raffle: ctx.accounts.raffle.key(),
+        title,
+        value_estimate,
+        prize_type,
+        escrow,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPrizeInfo<'info> {
+    /// The raffle whose prize is described, before any tickets are sold
+    #[account(
+        mut,
+        constraint = raffle.raffle_state == RaffleState::AwaitingPrize
+            || (raffle.raffle_state == RaffleState::Open && raffle.current_tickets == 0)
+            @ RaffleError::PrizeInfoLocked,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    #[account(
+        init_if_needed,
+        payer = management_authority,
+        space = PRIZE_INFO_ACCOUNT_SIZE,
+        seeds = [
+            b"prize_info",
+            raffle.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub prize_info: Account<'info, PrizeInfo>,
+
+    #[account(mut)]
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub system_program: Program<'info, System>,
+}
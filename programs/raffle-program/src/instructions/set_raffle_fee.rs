@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, Raffle, RaffleState, MAX_PLATFORM_FEE_BPS},
+};
+
+/// Event emitted when the platform fee override of a raffle is changed
+#[event]
+pub struct RaffleFeeSet {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// Share of withdrawals in basis points paid to the fee recipient, or None if the
+    /// config's fee applies
+    pub fee_bps_override: Option<u16>,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to charge a raffle a different platform fee than the config's, e.g. for
+/// partner raffles with negotiated terms
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `fee_bps_override` - Share of withdrawals in basis points paid to the fee
+///   recipient, or None to apply the config's fee
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Only the management authority can override the fee
+/// 2. The fee is capped at `MAX_PLATFORM_FEE_BPS`
+/// 3. Ensures the raffle is still on sale, so the split of its revenue can't be changed
+///    once it concluded
+///
+/// # Account Validations
+/// * Raffle - Must be in AwaitingPrize or Open state
+/// * Config - PDA storing the management authority
+///
+/// # Implementation Notes
+/// - No fee is charged while the config has no fee recipient, regardless of the override
+pub fn set_raffle_fee(ctx: Context<SetRaffleFee>, fee_bps_override: Option<u16>) -> Result<()> {
+    if let Some(fee_bps) = fee_bps_override {
+        require!(
+            fee_bps <= MAX_PLATFORM_FEE_BPS,
+            RaffleError::InvalidPlatformFee
+        );
+    }
+
+    let raffle = &mut ctx.accounts.raffle;
+    raffle.fee_bps_override = fee_bps_override;
+
+    let event_seq = raffle.next_event_seq()?;
+    emit!(RaffleFeeSet {
+        raffle: raffle.key(),
+        fee_bps_override,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetRaffleFee<'info> {
+    /// The raffle whose fee is overridden, while it is on sale
+    #[account(
+        mut,
+        constraint = matches!(
+            raffle.raffle_state,
+            RaffleState::AwaitingPrize | RaffleState::Open
+        ) @ RaffleError::RaffleNotOpen,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    instructions::create_raffle::{MAX_DURATION, MIN_DURATION},
+    state::{Config, RafflePreset, RegisteredRafflePreset, MAX_RAFFLE_PRESETS},
+};
+
+/// Event emitted when a raffle preset is registered, replaced or removed
+#[event]
+pub struct RafflePresetSet {
+    /// Name the preset is registered under
+    pub name: [u8; 16],
+    /// Terms of the preset, or None if it was removed
+    pub preset: Option<RafflePreset>,
+}
+
+/// Instruction to register named terms for routine raffles, such as "flash" or
+/// "weekly", which create_raffle_with_preset creates raffles from
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `name` - UTF-8 name of the preset padded with zero bytes
+/// * `preset` - Terms replacing the ones registered under `name`, or None to remove
+///   the preset
+///
+/// # Errors
+/// * `InvalidRafflePreset` - If the name is empty, the duration is outside the range
+///   allowed by create_raffle, the ticket limits are 0 or inconsistent, or the config
+///   already holds the maximum number of presets
+///
+/// # Security Considerations
+/// - Only the management authority can change presets
+/// - Ticket prices are validated when raffles are created from the preset, as the
+///   allowed range depends on the asset the raffle is paid in
+///
+/// # Implementation Notes
+/// - Raffles already created from a preset keep their terms when it is changed
+pub fn set_raffle_preset(
+    ctx: Context<SetRafflePreset>,
+    name: [u8; 16],
+    preset: Option<RafflePreset>,
+) -> Result<()> {
+    require!(name != [0; 16], RaffleError::InvalidRafflePreset);
+
+    let raffle_presets = &mut ctx.accounts.config.raffle_presets;
+    raffle_presets.retain(|registered| registered.name != name);
+    if let Some(preset) = preset {
+        require!(
+            preset.duration_secs > MIN_DURATION && preset.duration_secs <= MAX_DURATION,
+            RaffleError::InvalidRafflePreset
+        );
+        require!(preset.min_tickets > 0, RaffleError::InvalidRafflePreset);
+        if let Some(max_tickets) = preset.max_tickets {
+            require!(
+                max_tickets >= preset.min_tickets,
+                RaffleError::InvalidRafflePreset
+            );
+        }
+        require!(
+            raffle_presets.len() < MAX_RAFFLE_PRESETS,
+            RaffleError::InvalidRafflePreset
+        );
+        raffle_presets.push(RegisteredRafflePreset { name, preset });
+    }
+
+    emit!(RafflePresetSet { name, preset });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetRafflePreset<'info> {
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
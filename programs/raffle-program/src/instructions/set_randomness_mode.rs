@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{
+        Config, Raffle, RaffleState, RandomnessMode, FEATURE_PROVIDER_DRAW, FEATURE_VOTE_DRAW,
+        FEATURE_VRF_DRAW,
+    },
+};
+
+/// Event emitted when the randomness mode of a raffle is changed
+#[event]
+pub struct RandomnessModeSet {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// Source of the entropy the winning ticket is drawn from
+    pub randomness_mode: RandomnessMode,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to choose how the winning ticket of a raffle is drawn, e.g. to require
+/// verifiable randomness for high value raffles, where a colluding slot leader could
+/// profit from manipulating the SlotHashes entropy
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `randomness_mode` - SlotHashes to draw with draw_winning_ticket, Vrf to draw with
+///   request_randomness and settle_randomness, or Vote to decide the winner with
+///   open_vote, cast_vote and tally_vote. Raffles are drawn from a randomness provider
+///   by selecting it with set_randomness_provider instead
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Only the management authority can change the randomness mode
+/// 2. Ensures no tickets have been sold yet, so buyers always know how the raffle they
+///    bought into is drawn
+///
+/// # Account Validations
+/// * Raffle - Must be in AwaitingPrize state, or Open without any tickets sold
+/// * Config - PDA storing the management authority
+///
+/// # Implementation Notes
+/// - Sets or clears `FEATURE_VRF_DRAW` and `FEATURE_VOTE_DRAW` in the raffle's feature
+///   flags, and clears `FEATURE_PROVIDER_DRAW` along with any selected randomness
+///   provider
+pub fn set_randomness_mode(
+    ctx: Context<SetRandomnessMode>,
+    randomness_mode: RandomnessMode,
+) -> Result<()> {
+    let raffle = &mut ctx.accounts.raffle;
+    raffle.randomness_mode = randomness_mode.clone();
+    raffle.randomness_provider = None;
+    raffle.randomness_commitment = None;
+    raffle.features &= !(FEATURE_VRF_DRAW | FEATURE_VOTE_DRAW | FEATURE_PROVIDER_DRAW);
+    match randomness_mode {
+        RandomnessMode::Vrf => raffle.features |= FEATURE_VRF_DRAW,
+        RandomnessMode::Vote => raffle.features |= FEATURE_VOTE_DRAW,
+        RandomnessMode::SlotHashes => {}
+        RandomnessMode::Provider => return err!(RaffleError::InvalidRandomnessProvider),
+    }
+
+    let event_seq = raffle.next_event_seq()?;
+    emit!(RandomnessModeSet {
+        raffle: raffle.key(),
+        randomness_mode,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetRandomnessMode<'info> {
+    /// The raffle whose randomness mode is set, before any tickets are sold
+    #[account(
+        mut,
+        constraint = raffle.raffle_state == RaffleState::AwaitingPrize
+            || (raffle.raffle_state == RaffleState::Open && raffle.current_tickets == 0)
+            @ RaffleError::PrizeInfoLocked,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{
+        Config, Raffle, RaffleState, RandomnessMode, RandomnessProvider, FEATURE_PROVIDER_DRAW,
+        FEATURE_VOTE_DRAW, FEATURE_VRF_DRAW,
+    },
+};
+
+/// Event emitted when a raffle selects the randomness provider it is drawn from
+#[event]
+pub struct RandomnessProviderSet {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The provider the raffle is drawn from
+    pub provider: RandomnessProvider,
+    /// Hash of the secret a CommitReveal raffle is drawn from
+    pub commitment: Option<[u8; 32]>,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to draw a raffle from one of the randomness providers registered in the
+/// config, switching its randomness mode to Provider
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `provider` - The registered provider the raffle is drawn from
+/// * `commitment` - SHA-256 hash of the secret revealed to draw a CommitReveal raffle.
+///   Must be None for every other provider
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Only the management authority can select the randomness provider
+/// 2. Ensures no tickets have been sold yet, so buyers always know how the raffle they
+///    bought into is drawn, and a CommitReveal secret is fixed before the first sale
+/// 3. Only providers registered by the upgrade authority can be selected
+///
+/// # Account Validations
+/// * Raffle - Must be in AwaitingPrize state, or Open without any tickets sold
+/// * Config - PDA storing the management authority and the registered providers
+///
+/// # Implementation Notes
+/// - Sets `FEATURE_PROVIDER_DRAW` and clears `FEATURE_VRF_DRAW` and `FEATURE_VOTE_DRAW`
+///   in the raffle's feature flags
+/// - Switchboard and ORAO raffles are drawn with request_randomness and
+///   fulfill_randomness, SlotHashes and CommitReveal raffles with fulfill_randomness
+pub fn set_randomness_provider(
+    ctx: Context<SetRandomnessProvider>,
+    provider: RandomnessProvider,
+    commitment: Option<[u8; 32]>,
+) -> Result<()> {
+    ctx.accounts.config.randomness_program(provider)?;
+    require!(
+        commitment.is_some() == (provider == RandomnessProvider::CommitReveal),
+        RaffleError::InvalidRandomnessCommitment
+    );
+
+    let raffle = &mut ctx.accounts.raffle;
+    raffle.randomness_mode = RandomnessMode::Provider;
+    raffle.randomness_provider = Some(provider);
+    raffle.randomness_commitment = commitment;
+    raffle.features &= !(FEATURE_VRF_DRAW | FEATURE_VOTE_DRAW);
+    raffle.features |= FEATURE_PROVIDER_DRAW;
+
+    let event_seq = raffle.next_event_seq()?;
+    emit!(RandomnessProviderSet {
+        raffle: raffle.key(),
+        provider,
+        commitment,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetRandomnessProvider<'info> {
+    /// The raffle whose randomness provider is set, before any tickets are sold
+    #[account(
+        mut,
+        constraint = raffle.raffle_state == RaffleState::AwaitingPrize
+            || (raffle.raffle_state == RaffleState::Open && raffle.current_tickets == 0)
+            @ RaffleError::PrizeInfoLocked,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
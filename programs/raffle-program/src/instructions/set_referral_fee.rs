@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, MAX_REFERRAL_FEE_BPS},
+};
+
+/// Event emitted when the referral fee is changed
+#[event]
+pub struct ReferralFeeSet {
+    /// Share of referred purchases in basis points paid to referrers
+    pub referral_fee_bps: u16,
+}
+
+/// Instruction to configure the fee paid to referrers of ticket purchases from the
+/// treasury at withdrawal
+///
+/// # Security Considerations
+/// - Only the management authority can change the fee
+/// - The fee is capped at `MAX_REFERRAL_FEE_BPS`
+/// - Only applies to raffles created afterwards, existing raffles keep the fee disclosed
+///   when they were created
+pub fn set_referral_fee(ctx: Context<SetReferralFee>, referral_fee_bps: u16) -> Result<()> {
+    require!(
+        referral_fee_bps <= MAX_REFERRAL_FEE_BPS,
+        RaffleError::InvalidReferralFee
+    );
+
+    ctx.accounts.config.referral_fee_bps = referral_fee_bps;
+
+    emit!(ReferralFeeSet { referral_fee_bps });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetReferralFee<'info> {
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, MAX_REFUND_FEE_BPS},
+};
+
+/// Event emitted when the refund processing fee is changed
+#[event]
+pub struct RefundFeeSet {
+    /// Share of refunds in basis points retained by the treasury
+    pub refund_fee_bps: u16,
+}
+
+/// Instruction to configure the refund processing fee retained from refunds of expired
+/// raffles
+///
+/// # Security Considerations
+/// - Only the management authority can change the fee
+/// - The fee is capped at `MAX_REFUND_FEE_BPS`
+/// - Only applies to raffles created afterwards, existing raffles keep the fee disclosed
+///   when they were created
+pub fn set_refund_fee(ctx: Context<SetRefundFee>, refund_fee_bps: u16) -> Result<()> {
+    require!(
+        refund_fee_bps <= MAX_REFUND_FEE_BPS,
+        RaffleError::InvalidRefundFee
+    );
+
+    ctx.accounts.config.refund_fee_bps = refund_fee_bps;
+
+    emit!(RefundFeeSet { refund_fee_bps });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetRefundFee<'info> {
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,245 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::{get_associated_token_address, AssociatedToken},
+    token::{self, Mint, Token, TokenAccount, Transfer},
+};
+use switchboard_on_demand::{PullFeedAccountData, PRECISION, SWITCHBOARD_ON_DEMAND_PROGRAM_ID};
+
+use crate::{
+    error::RaffleError,
+    math::{convert_at_rate, BPS_DENOMINATOR, RATE_SCALE},
+    state::{Config, Raffle, RaffleState, Treasury, MAX_REFUND_RATE_DEVIATION_BPS},
+    time::cluster_clock,
+    treasury::open_token_vault,
+};
+
+/// Event emitted when refunds of a raffle are switched to a replacement mint
+#[event]
+pub struct RefundMintSet {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// Mint refunds are paid in from now on
+    pub refund_mint: Pubkey,
+    /// Base units of the refund mint paid per base unit of the payment mint, scaled by
+    /// `RATE_SCALE`
+    pub refund_rate: u64,
+    /// Rate quoted by the price feed, in the unit of `refund_rate`
+    pub oracle_rate: u64,
+    /// Base units of the refund mint deposited to cover the unclaimed refunds
+    pub funded_amount: u64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to pay the refunds of a token raffle in a replacement mint, after the
+/// treasury's payment tokens were frozen by the payment mint's freeze authority, so
+/// refunds can't be blocked for good by a third party
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `refund_rate` - Base units of the refund mint paid per base unit of the payment
+///   mint, scaled by `RATE_SCALE`
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Only the management authority can designate a refund mint
+/// 2. Only allowed for expired or cancelled raffles paid in an SPL token, whose
+///    treasury token account is frozen
+/// 3. The refund rate must be within `MAX_REFUND_RATE_DEVIATION_BPS` of the rate quoted
+///    by a fresh Switchboard pull feed pricing the payment mint in the refund mint
+/// 4. The management authority deposits enough of the refund mint to pay all unclaimed
+///    refunds at the refund rate
+/// 5. A raffle's refund mint can only be designated once
+///
+/// # Account Validations
+/// * Raffle - Must be Expired or Cancelled, paid in `payment_mint`, without a refund mint
+/// * TreasuryTokenAccount - The treasury's associated token account of the payment mint,
+///   must be frozen
+/// * RefundMint - Must differ from the payment mint
+/// * RefundVault - The treasury's associated token account of the refund mint, created
+/// * FundingTokenAccount - Token account of the management authority holding the refund
+///   mint
+/// * PriceFeed - Switchboard pull feed quoting the price of one payment token in refund
+///   tokens
+///
+/// # Implementation Notes
+/// - Refunds and sweeps of unclaimed refunds are paid from the refund vault afterwards,
+///   see `treasury::refund_token_accounts`. The treasury keeps recording them in payment
+///   mint units
+/// - Refund processing fees stay in the frozen payment token account
+pub fn set_refund_mint(ctx: Context<SetRefundMint>, refund_rate: u64) -> Result<()> {
+    require!(
+        ctx.accounts.treasury_token_account.is_frozen(),
+        RaffleError::PaymentMintNotFrozen
+    );
+
+    // The rate has to match what the market pays for the payment mint
+    let oracle_rate = feed_rate(
+        &ctx.accounts.price_feed,
+        ctx.accounts.payment_mint.decimals,
+        ctx.accounts.refund_mint.decimals,
+    )?;
+    let deviation = (refund_rate as u128).abs_diff(oracle_rate as u128);
+    require!(
+        refund_rate > 0
+            && deviation * BPS_DENOMINATOR as u128
+                <= oracle_rate as u128 * MAX_REFUND_RATE_DEVIATION_BPS as u128,
+        RaffleError::RefundRateDeviates
+    );
+
+    let raffle = &mut ctx.accounts.raffle;
+    raffle.refund_mint = Some(ctx.accounts.refund_mint.key());
+    raffle.refund_rate = refund_rate;
+
+    // Cover every refund still owed, each of which is rounded down when paid
+    open_token_vault(
+        &ctx.accounts.management_authority,
+        &ctx.accounts.treasury.to_account_info(),
+        Some(&ctx.accounts.refund_mint),
+        Some(&ctx.accounts.refund_vault),
+        Some(&ctx.accounts.token_program),
+        Some(&ctx.accounts.associated_token_program),
+        &ctx.accounts.system_program,
+    )?;
+    let funded_amount = convert_at_rate(ctx.accounts.treasury.unclaimed_refunds()?, refund_rate)?;
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.funding_token_account.to_account_info(),
+                to: ctx.accounts.refund_vault.to_account_info(),
+                authority: ctx.accounts.management_authority.to_account_info(),
+            },
+        ),
+        funded_amount,
+    )?;
+
+    let raffle = &mut ctx.accounts.raffle;
+    let event_seq = raffle.next_event_seq()?;
+    emit!(RefundMintSet {
+        raffle: raffle.key(),
+        refund_mint: ctx.accounts.refund_mint.key(),
+        refund_rate,
+        oracle_rate,
+        funded_amount,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+/// Rate between base units of the payment and refund mints quoted by a Switchboard pull
+/// feed pricing one payment token in refund tokens, scaled by `RATE_SCALE`
+fn feed_rate(
+    price_feed: &UncheckedAccount,
+    payment_decimals: u8,
+    refund_decimals: u8,
+) -> Result<u64> {
+    let price = feed_price(price_feed)?;
+
+    // Scale down the feed's precision first, so the decimal adjustment can't overflow
+    let rate = price
+        .checked_mul(RATE_SCALE as u128)
+        .ok_or(RaffleError::Overflow)?
+        / 10u128.pow(PRECISION);
+    let rate = rate
+        .checked_mul(10u128.pow(refund_decimals as u32))
+        .ok_or(RaffleError::Overflow)?
+        / 10u128.pow(payment_decimals as u32);
+    u64::try_from(rate).map_err(|_| RaffleError::Overflow.into())
+}
+
+/// Positive price quoted by a Switchboard pull feed, scaled by the feed's `PRECISION`.
+/// Fails if the feed is stale.
+pub(crate) fn feed_price(price_feed: &UncheckedAccount) -> Result<u128> {
+    require_keys_eq!(
+        *price_feed.owner,
+        SWITCHBOARD_ON_DEMAND_PROGRAM_ID,
+        RaffleError::InvalidPriceFeed
+    );
+    let feed = PullFeedAccountData::parse(price_feed.data.borrow())
+        .map_err(|_| RaffleError::InvalidPriceFeed)?;
+    feed.value(&cluster_clock()?)
+        .map_err(|_| RaffleError::InvalidPriceFeed)?;
+    u128::try_from(feed.result.value)
+        .ok()
+        .filter(|price| *price > 0)
+        .ok_or(RaffleError::InvalidPriceFeed.into())
+}
+
+#[derive(Accounts)]
+pub struct SetRefundMint<'info> {
+    /// The expired or cancelled raffle whose refunds are switched to the refund mint
+    #[account(
+        mut,
+        constraint = matches!(
+            raffle.raffle_state,
+            RaffleState::Expired | RaffleState::Cancelled
+        ) @ RaffleError::RaffleNotExpired,
+        constraint = raffle.payment_mint == Some(payment_mint.key())
+            @ RaffleError::InvalidPaymentMint,
+        constraint = raffle.refund_mint.is_none() @ RaffleError::RefundMintAlreadySet,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    /// Pays the rent of the refund vault and deposits the refund mint
+    #[account(mut)]
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Treasury PDA for this raffle, owning the refund vault
+    #[account(
+        seeds = [
+            b"treasury",
+            raffle.key().as_ref(),
+        ],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    /// The raffle's payment mint
+    pub payment_mint: Account<'info, Mint>,
+
+    /// The treasury's associated token account of the payment mint, which must be frozen
+    #[account(
+        constraint = treasury_token_account.key()
+            == get_associated_token_address(&treasury.key(), &payment_mint.key())
+            @ RaffleError::InvalidPaymentMint,
+    )]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+
+    /// Mint refunds are paid in from now on
+    #[account(
+        constraint = refund_mint.key() != payment_mint.key() @ RaffleError::InvalidRefundMint,
+    )]
+    pub refund_mint: Account<'info, Mint>,
+
+    /// CHECK: The treasury's associated token account of the refund mint, created by the
+    /// associated token program, which only accepts the derived address
+    #[account(mut)]
+    pub refund_vault: UncheckedAccount<'info>,
+
+    /// Token account of the management authority the refund mint is deposited from
+    #[account(
+        mut,
+        constraint = funding_token_account.owner == management_authority.key()
+            @ RaffleError::OwnerMismatch,
+    )]
+    pub funding_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Switchboard pull feed quoting the price of one payment token in refund
+    /// tokens, validated in the instruction
+    pub price_feed: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+}
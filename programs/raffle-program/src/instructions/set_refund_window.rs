@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, MIN_REFUND_WINDOW_SECS},
+};
+
+/// Event emitted when the refund window or the insurance fund are changed
+#[event]
+pub struct RefundWindowSet {
+    /// How long ticket holders can reclaim their funds, or zero if refunds never lapse
+    pub refund_window_secs: i64,
+    /// Account forfeited refunds are swept to, or None to sweep them to the payout
+    /// authority
+    pub insurance_fund: Option<Pubkey>,
+}
+
+/// Instruction to configure how long ticket holders of expired and cancelled raffles can
+/// reclaim their funds, and where refunds left unclaimed afterwards are swept to
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `refund_window_secs` - How long refunds stay claimable, or zero for refunds to
+///   never lapse
+/// * `insurance_fund` - Account forfeited refunds are swept to, or None to sweep them
+///   to the payout authority
+///
+/// # Security Considerations
+/// - Only the upgrade authority can change the window and the insurance fund, so leaked
+///   management and payout keys can't redirect forfeited refunds
+/// - The window is at least `MIN_REFUND_WINDOW_SECS`, so ticket holders always have
+///   time to reclaim their funds
+/// - Only applies to raffles expiring or cancelled afterwards, which fix their refund
+///   deadline when their refunds open
+pub fn set_refund_window(
+    ctx: Context<SetRefundWindow>,
+    refund_window_secs: i64,
+    insurance_fund: Option<Pubkey>,
+) -> Result<()> {
+    require!(
+        refund_window_secs == 0 || refund_window_secs >= MIN_REFUND_WINDOW_SECS,
+        RaffleError::InvalidRefundWindow
+    );
+
+    ctx.accounts.config.refund_window_secs = refund_window_secs;
+    ctx.accounts.config.insurance_fund = insurance_fund;
+
+    emit!(RefundWindowSet {
+        refund_window_secs,
+        insurance_fund,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetRefundWindow<'info> {
+    pub upgrade_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = upgrade_authority @ RaffleError::NotProgramUpgradeAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
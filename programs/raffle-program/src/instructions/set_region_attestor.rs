@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::RaffleError, state::Config};
+
+/// Event emitted when the region attestor is changed
+#[event]
+pub struct RegionAttestorSet {
+    /// Key trusted to attest buyer regions, if any
+    pub region_attestor: Option<Pubkey>,
+}
+
+/// Instruction to register the key trusted to attest the regions of buyers
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `region_attestor` - The attestor key, or None to stop accepting attestations
+///
+/// # Security Considerations
+/// - Only the management authority can change the attestor
+/// - Attestations signed by a previous attestor are no longer accepted, so rotating the
+///   key revokes all attestations it issued
+pub fn set_region_attestor(
+    ctx: Context<SetRegionAttestor>,
+    region_attestor: Option<Pubkey>,
+) -> Result<()> {
+    ctx.accounts.config.region_attestor = region_attestor;
+
+    emit!(RegionAttestorSet { region_attestor });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetRegionAttestor<'info> {
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, RentPool},
+};
+
+/// Event emitted when the rent pool is enabled or disabled
+#[event]
+pub struct RentPoolToggled {
+    /// Whether entry rent is now sponsored by the pool
+    pub enabled: bool,
+}
+
+/// Instruction to enable or disable entry rent sponsorship by the rent pool
+///
+/// # Security Considerations
+/// - Only the management authority can toggle the pool
+pub fn set_rent_pool_enabled(ctx: Context<SetRentPoolEnabled>, enabled: bool) -> Result<()> {
+    ctx.accounts.rent_pool.enabled = enabled;
+
+    emit!(RentPoolToggled { enabled });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetRentPoolEnabled<'info> {
+    #[account(
+        mut,
+        seeds = [b"rent_pool"],
+        bump = rent_pool.bump,
+    )]
+    pub rent_pool: Account<'info, RentPool>,
+
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
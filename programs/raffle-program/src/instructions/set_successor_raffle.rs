@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, Raffle, RaffleState},
+};
+
+/// Event emitted when a raffle's successor is designated
+#[event]
+pub struct SuccessorRaffleSet {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// Raffle ticket holders who opted into rollover can move their balance to
+    pub successor: Pubkey,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to designate the raffle that ticket holders who opted into rollover can
+/// move their balance to with rollover_tickets, should the raffle expire
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Only the management authority can designate a successor
+/// 2. Only raffles that haven't been drawn, claimed or cancelled can get a successor
+/// 3. The successor must be open and differ from the raffle
+/// 4. Both raffles must be paid in lamports
+/// 5. The successor must admit any wallet, so it can't be gated, region or age
+///    restricted, and must not issue ticket receipts or split its prize among top
+///    holders, which rollover_tickets doesn't support
+///
+/// # Account Validations
+/// * Raffle - Must be in Open, AwaitingPrize or Expired state
+/// * Successor - Must be in Open state
+/// * Config - PDA storing the management authority
+///
+/// # Implementation Notes
+/// - A successor designated earlier is replaced
+pub fn set_successor_raffle(ctx: Context<SetSuccessorRaffle>) -> Result<()> {
+    let successor = &ctx.accounts.successor;
+    require!(
+        ctx.accounts.raffle.payment_mint.is_none() && successor.payment_mint.is_none(),
+        RaffleError::TokenPaymentsNotSupported
+    );
+    require!(
+        successor.gate.is_none()
+            && successor.allowed_regions == 0
+            && !successor.age_restricted
+            && !successor.ticket_receipts
+            && successor.top_holders.is_none(),
+        RaffleError::InvalidSuccessorRaffle
+    );
+
+    let raffle = &mut ctx.accounts.raffle;
+    raffle.successor = Some(successor.key());
+
+    let event_seq = raffle.next_event_seq()?;
+    emit!(SuccessorRaffleSet {
+        raffle: raffle.key(),
+        successor: successor.key(),
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetSuccessorRaffle<'info> {
+    /// The raffle getting a successor
+    #[account(
+        mut,
+        constraint = matches!(
+            raffle.raffle_state,
+            RaffleState::Open | RaffleState::AwaitingPrize | RaffleState::Expired
+        ) @ RaffleError::RaffleNotOpen,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    /// The raffle balances are rolled over to
+    #[account(
+        constraint = successor.raffle_state == RaffleState::Open @ RaffleError::RaffleNotOpen,
+        constraint = successor.key() != raffle.key() @ RaffleError::InvalidSuccessorRaffle,
+    )]
+    pub successor: Account<'info, Raffle>,
+
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
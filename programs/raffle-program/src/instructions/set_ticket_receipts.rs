@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, Raffle, RaffleState, FEATURE_TICKET_RECEIPTS},
+};
+
+/// Event emitted when ticket receipts of a raffle are enabled or disabled
+#[event]
+pub struct TicketReceiptsSet {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// Whether buy_tickets mints a receipt NFT for every entry
+    pub ticket_receipts: bool,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to make a raffle mint a transferable receipt NFT for every entry, whose
+/// holder is entitled to the prize of the entry's tickets
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `ticket_receipts` - Whether buy_tickets mints a Token-2022 receipt of every entry
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Only the management authority can change whether receipts are issued
+/// 2. Ensures no tickets have been sold yet, so every entry of the raffle has a receipt
+/// 3. Rejects raffles deferring their ticket numbering, as a receipt records the
+///    entry's ticket range at purchase
+///
+/// # Account Validations
+/// * Raffle - Must be in AwaitingPrize state, or Open without any tickets sold
+/// * Config - PDA storing the management authority
+///
+/// # Implementation Notes
+/// - Sets or clears `FEATURE_TICKET_RECEIPTS` in the raffle's feature flags
+pub fn set_ticket_receipts(ctx: Context<SetTicketReceipts>, ticket_receipts: bool) -> Result<()> {
+    let raffle = &mut ctx.accounts.raffle;
+    require!(
+        !ticket_receipts || !raffle.deferred_numbering,
+        RaffleError::TicketReceiptsNotSupported
+    );
+    raffle.ticket_receipts = ticket_receipts;
+    if ticket_receipts {
+        raffle.features |= FEATURE_TICKET_RECEIPTS;
+    } else {
+        raffle.features &= !FEATURE_TICKET_RECEIPTS;
+    }
+
+    let event_seq = raffle.next_event_seq()?;
+    emit!(TicketReceiptsSet {
+        raffle: raffle.key(),
+        ticket_receipts,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetTicketReceipts<'info> {
+    /// The raffle whose receipts are set, before any tickets are sold
+    #[account(
+        mut,
+        constraint = raffle.raffle_state == RaffleState::AwaitingPrize
+            || (raffle.raffle_state == RaffleState::Open && raffle.entry_count == 0)
+            @ RaffleError::PrizeInfoLocked,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
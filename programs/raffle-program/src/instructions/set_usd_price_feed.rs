@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::RaffleError, state::Config};
+
+/// Event emitted when the USD price feed is changed
+#[event]
+pub struct UsdPriceFeedSet {
+    /// Switchboard pull feed quoting the SOL price in USD, if any
+    pub usd_price_feed: Option<Pubkey>,
+}
+
+/// Instruction to configure the price feed record_draw_value snapshots the USD value of
+/// drawn raffles with
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `usd_price_feed` - Switchboard pull feed quoting the SOL price in USD, or None to
+///   stop recording draw values
+///
+/// # Security Considerations
+/// - Only the management authority can change the feed, as the recorded values are used
+///   for reporting and tax documentation
+/// - The feed is validated as a fresh Switchboard pull feed whenever it is read
+pub fn set_usd_price_feed(
+    ctx: Context<SetUsdPriceFeed>,
+    usd_price_feed: Option<Pubkey>,
+) -> Result<()> {
+    ctx.accounts.config.usd_price_feed = usd_price_feed;
+
+    emit!(UsdPriceFeedSet { usd_price_feed });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetUsdPriceFeed<'info> {
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, UserProfile, USER_PROFILE_ACCOUNT_SIZE},
+};
+
+/// Event emitted when the loyalty tier of a wallet is changed
+#[event]
+pub struct UserTierSet {
+    /// The wallet whose tier changed
+    pub wallet: Pubkey,
+    /// The new loyalty tier
+    pub tier: u8,
+}
+
+/// Instruction to assign a wallet's loyalty tier, which grants access to the priority
+/// phase of raffles requiring that tier or lower
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `wallet` - The wallet whose tier is assigned
+/// * `tier` - The loyalty tier, zero for the default tier
+///
+/// # Security Considerations
+/// - Only the management authority can assign tiers
+///
+/// # Account Validations
+/// * User Profile - PDA with seeds ["user_profile", wallet], created on first use
+/// * Management Authority - Must match the management authority stored in config, pays
+///   the rent
+pub fn set_user_tier(ctx: Context<SetUserTier>, wallet: Pubkey, tier: u8) -> Result<()> {
+    let user_profile = &mut ctx.accounts.user_profile;
+    user_profile.wallet = wallet;
+    user_profile.tier = tier;
+    user_profile.bump = ctx.bumps.user_profile;
+
+    emit!(UserTierSet { wallet, tier });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct SetUserTier<'info> {
+    #[account(
+        init_if_needed,
+        payer = management_authority,
+        space = USER_PROFILE_ACCOUNT_SIZE,
+        seeds = [
+            b"user_profile",
+            wallet.as_ref(),
+        ],
+        bump,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    #[account(mut)]
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -1,11 +1,16 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, solana_program::hash::hashv};
+use anchor_spl::token_interface::TokenAccount;
 
 use crate::{
     error::RaffleError,
+    math::checked_add,
+    receipt::receipt_holder,
     state::{
         entry::Entry,
         raffle::{Raffle, RaffleState},
+        Config, Notifications, TicketBalance,
     },
+    time::now,
 };
 
 /// Event emitted when a winner is set for a raffle
@@ -13,50 +18,200 @@ use crate::{
 pub struct WinnerSet {
     /// The pubkey of the raffle
     pub raffle: Pubkey,
-    /// The winner's address
+    /// The winner's address, or the default pubkey if the winner is anonymized
     pub winner: Pubkey,
     /// The winning ticket number
     pub winning_ticket: u64,
+    /// The hash committing to the winner, if the winner is anonymized
+    pub winner_hash: Option<[u8; 32]>,
+    /// Index of the prize the winner won, in prize order
+    pub winner_index: u8,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
 }
 
-/// Sets the winner of a raffle based on the winning ticket number.
+/// Computes the hash under which an anonymized raffle stores its winner. Buyers commit
+/// to it with commit_winner_secret, keeping `secret` off-chain until they reveal it
+/// alongside their signature to claim.
+pub fn winner_hash(raffle: &Pubkey, winner: &Pubkey, secret: &[u8; 32]) -> [u8; 32] {
+    hashv(&[raffle.as_ref(), winner.as_ref(), secret]).to_bytes()
+}
+
+/// Sets the winner of a raffle's prize based on its winning ticket number.
 /// This instruction can only be executed when:
 /// 1. The raffle is in Drawing state
-/// 2. The winning ticket has been drawn
-/// 3. The entry PDA matches the winning ticket number
+/// 2. The winning tickets have been drawn
+/// 3. The winner index is within the raffle's winner count, its winner is not set yet
+///    and the winners of all previous prizes are set
+/// 4. The entry PDA matches the winning ticket number of the prize
+/// 5. If the raffle issues ticket receipts, the receipt token account holds the entry's
+///    receipt
+///
+/// 6. If the raffle is anonymized, the winner ticket balance is the winner's ticket
+///    balance PDA in the raffle, so the winner's commitment can't be left out
 ///
 /// After execution:
-/// - The winner's address is stored in the raffle account
-/// - The raffle state is changed to Drawn
-pub fn set_winner(ctx: Context<SetWinner>, _entry_seed: [u8; 8]) -> Result<()> {
-    // Get the winning ticket number
-    let winning_ticket = ctx
-        .accounts
-        .raffle
-        .winning_ticket
-        .ok_or(RaffleError::NoWinningTicket)?;
+/// - The winner's address is stored in the raffle account, or for anonymized raffles
+///   only the commitment from the winner's ticket balance, see `winner_hash`. The winner
+///   is the entry's owner, or the holder of the entry's receipt if the raffle issues
+///   ticket receipts
+/// - The raffle state is changed to Drawn once the winners of all prizes are set
+///
+/// If the winner's notifications account is passed, a record of the win is appended
+/// to it, except for anonymized winners.
+///
+/// Anonymizing the winner keeps the raffle account and event from naming the winner,
+/// but the winning entry account itself remains public. Winners of anonymized raffles
+/// who didn't commit to a secret before the draw are stored in plaintext.
+pub fn set_winner(ctx: Context<SetWinner>, _entry_seed: [u8; 8], winner_index: u8) -> Result<()> {
+    let winner = if ctx.accounts.raffle.ticket_receipts {
+        receipt_holder(
+            &ctx.accounts.entry.key(),
+            ctx.accounts.receipt_token_account.as_deref(),
+        )?
+    } else {
+        ctx.accounts.entry.owner
+    };
+    let winner_commitment = if ctx.accounts.raffle.anonymize_winner {
+        committed_winner_hash(
+            &ctx.accounts.raffle.key(),
+            &winner,
+            ctx.accounts.winner_ticket_balance.as_ref(),
+        )?
+    } else {
+        None
+    };
+    record_winner(
+        &mut ctx.accounts.raffle,
+        &ctx.accounts.entry,
+        winner,
+        winner_commitment,
+        winner_index,
+        ctx.accounts.notifications.as_mut(),
+        now(&ctx.accounts.config)?,
+    )
+}
+
+/// Reads the commitment of `winner` from their ticket balance in the raffle, which must
+/// be passed as `ticket_balance`. Winners without a ticket balance, e.g. receipt holders
+/// who never bought, or without a commitment have none.
+fn committed_winner_hash(
+    raffle: &Pubkey,
+    winner: &Pubkey,
+    ticket_balance: Option<&UncheckedAccount>,
+) -> Result<Option<[u8; 32]>> {
+    let ticket_balance = ticket_balance.ok_or(RaffleError::WinnerTicketBalanceRequired)?;
+    let (address, _) = Pubkey::find_program_address(
+        &[b"ticket_balance", raffle.as_ref(), winner.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(
+        ticket_balance.key(),
+        address,
+        RaffleError::WinnerTicketBalanceRequired
+    );
+    if ticket_balance.data_is_empty() {
+        return Ok(None);
+    }
+
+    // Only the program can create accounts at its PDAs, so an existing account at the
+    // address is a ticket balance, which the discriminator check confirms
+    let ticket_balance =
+        TicketBalance::try_deserialize(&mut &ticket_balance.try_borrow_data()?[..])?;
+    Ok(ticket_balance.winner_commitment)
+}
+
+/// Sets `winner` as the winner of the prize at `winner_index`, after verifying `entry`
+/// holds the prize's winning ticket. The winner is the entry's owner, or the holder of
+/// its receipt if the raffle issues ticket receipts. On anonymized raffles, only
+/// `winner_commitment` is stored if the winner committed to a secret.
+///
+/// Shared by set_winner and draw_winning_ticket, which resolves the winners of the
+/// entries passed to it in the same transaction as the draw. `now` is recorded as the
+/// time of the win in the winner's notifications.
+pub(crate) fn record_winner(
+    raffle: &mut Account<Raffle>,
+    entry: &Entry,
+    winner: Pubkey,
+    winner_commitment: Option<[u8; 32]>,
+    winner_index: u8,
+    notifications: Option<&mut Account<Notifications>>,
+    now: i64,
+) -> Result<()> {
+    require!(
+        winner_index < raffle.winner_count,
+        RaffleError::InvalidWinnerIndex
+    );
+
+    // Winners are set in prize order, so each prize gets exactly one
+    let winners_set = if raffle.winner_address.is_some() || raffle.winner_hash.is_some() {
+        raffle.additional_winners.len() + 1
+    } else {
+        0
+    };
+    require!(
+        (winner_index as usize) >= winners_set,
+        RaffleError::WinnerAlreadySet
+    );
+    require!(
+        winner_index as usize == winners_set,
+        RaffleError::InvalidWinnerIndex
+    );
+
+    // Get the winning ticket number of the prize
+    let winning_ticket = match winner_index {
+        0 => raffle.winning_ticket,
+        _ => raffle.winning_tickets.get(winner_index as usize).copied(),
+    }
+    .ok_or(RaffleError::NoWinningTicket)?;
 
     // Verify the entry contains the winning ticket
-    let entry = &ctx.accounts.entry;
     require!(
         winning_ticket >= entry.ticket_start_index
-            && winning_ticket
-                < entry
-                    .ticket_start_index
-                    .checked_add(entry.ticket_count)
-                    .ok_or(RaffleError::Overflow)?,
+            && winning_ticket < checked_add(entry.ticket_start_index, entry.ticket_count)?,
         RaffleError::InvalidWinningEntry
     );
 
-    // Set the winner and update state
-    ctx.accounts.raffle.winner_address = Some(entry.owner);
-    ctx.accounts.raffle.raffle_state = RaffleState::Drawn;
+    // Set the winner, or only their commitment if the raffle is anonymized
+    let raffle_key = raffle.key();
+    let hash = winner_commitment.filter(|_| raffle.anonymize_winner);
+    let published_winner = if hash.is_some() {
+        Pubkey::default()
+    } else {
+        winner
+    };
+    if winner_index == 0 {
+        raffle.winner_address = hash.is_none().then_some(winner);
+        raffle.winner_hash = hash;
+    } else {
+        raffle.additional_winners.push(published_winner);
+        raffle.additional_winner_hashes.push(hash);
+    }
+    if raffle.all_winners_set() {
+        raffle.raffle_state = RaffleState::Drawn;
+    }
+
+    // Let the winner's wallet learn about the win by polling a single account
+    if let Some(notifications) = notifications {
+        require_keys_eq!(
+            notifications.wallet,
+            winner,
+            RaffleError::InvalidNotifications
+        );
+        if hash.is_none() {
+            notifications.record(raffle_key, now)?;
+        }
+    }
 
     // Emit winner set event
+    let event_seq = raffle.next_event_seq()?;
     emit!(WinnerSet {
-        raffle: ctx.accounts.raffle.key(),
-        winner: entry.owner,
+        raffle: raffle_key,
+        winner: published_winner,
         winning_ticket,
+        winner_hash: hash,
+        winner_index,
+        event_seq,
     });
 
     Ok(())
@@ -67,7 +222,7 @@ pub fn set_winner(ctx: Context<SetWinner>, _entry_seed: [u8; 8]) -> Result<()> {
 #[instruction(entry_seed: [u8; 8])]
 pub struct SetWinner<'info> {
     /// The raffle account to set the winner for.
-    /// Must be in Drawing state and have a winning ticket drawn
+    /// Must be in Drawing state and have the winning tickets drawn
     #[account(
         mut,
         constraint = raffle.raffle_state == RaffleState::Drawing @ RaffleError::RaffleNotDrawing,
@@ -86,4 +241,25 @@ pub struct SetWinner<'info> {
         bump,
     )]
     pub entry: Account<'info, Entry>,
+
+    /// Optional notifications of the winner, validated against the winner in the
+    /// instruction
+    #[account(mut)]
+    pub notifications: Option<Account<'info, Notifications>>,
+
+    /// Optional token account holding the winning entry's receipt, required if the raffle
+    /// issues ticket receipts. Validated against the entry in the instruction.
+    pub receipt_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Program config, storing the clock offset of devnet builds
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Optional ticket balance PDA of the winner in the raffle, with seeds
+    /// ["ticket_balance", raffle_key, winner], required if the raffle is anonymized
+    /// CHECK: Validated against the winner in the instruction, and may not exist
+    pub winner_ticket_balance: Option<UncheckedAccount<'info>>,
 }
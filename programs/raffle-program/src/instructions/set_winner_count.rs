@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, Raffle, RaffleState, FEATURE_MULTIPLE_WINNERS, MAX_WINNERS},
+};
+
+/// Event emitted when the number of winners of a raffle is changed
+#[event]
+pub struct WinnerCountSet {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// Number of distinct winning tickets drawn
+    pub winner_count: u8,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Fails unless the raffle can draw `winner_count` winners
+pub(crate) fn validate_winner_count(winner_count: u8) -> Result<()> {
+    require!(
+        winner_count > 0 && winner_count <= MAX_WINNERS,
+        RaffleError::InvalidWinnerCount
+    );
+    Ok(())
+}
+
+/// Instruction to set the number of winners of a raffle, e.g. to award 1st, 2nd and 3rd
+/// prizes
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `winner_count` - Number of distinct winning tickets to draw, one per prize
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Only the management authority can set the winner count
+/// 2. Ensures no tickets have been sold yet, so buyers always know how many prizes the
+///    raffle they bought into awards
+/// 3. Ensures the count is between 1 and `MAX_WINNERS`
+///
+/// # Account Validations
+/// * Raffle - Must be in AwaitingPrize state, or Open without any tickets sold
+/// * Config - PDA storing the management authority
+///
+/// # Implementation Notes
+/// - Sets or clears `FEATURE_MULTIPLE_WINNERS` in the raffle's feature flags
+/// - Each winner is set with set_winner and submits their data and claims their share of
+///   the escrowed prize under their winner index. Cash prizes are paid to the first
+///   winner only
+pub fn set_winner_count(ctx: Context<SetWinnerCount>, winner_count: u8) -> Result<()> {
+    let raffle = &mut ctx.accounts.raffle;
+    validate_winner_count(winner_count)?;
+
+    raffle.winner_count = winner_count;
+    if winner_count > 1 {
+        raffle.features |= FEATURE_MULTIPLE_WINNERS;
+    } else {
+        raffle.features &= !FEATURE_MULTIPLE_WINNERS;
+    }
+
+    let event_seq = raffle.next_event_seq()?;
+    emit!(WinnerCountSet {
+        raffle: raffle.key(),
+        winner_count,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetWinnerCount<'info> {
+    /// The raffle whose winner count is set, before any tickets are sold
+    #[account(
+        mut,
+        constraint = raffle.raffle_state == RaffleState::AwaitingPrize
+            || (raffle.raffle_state == RaffleState::Open && raffle.current_tickets == 0)
+            @ RaffleError::PrizeInfoLocked,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, MAX_WINNER_DATA_FORMATS},
+};
+
+/// Event emitted when the supported winner data formats are changed
+#[event]
+pub struct WinnerDataFormatsSet {
+    /// Winner data format versions winners can submit
+    pub format_versions: Vec<u8>,
+    /// Encryption schemes winners can submit their data with
+    pub encryption_schemes: Vec<u8>,
+}
+
+/// Instruction to replace the winner data format versions and encryption schemes winners
+/// can submit, e.g. to phase in HPKE next to X25519 while the decryption pipeline
+/// supports both
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `format_versions` - The supported format versions
+/// * `encryption_schemes` - The supported encryption schemes
+///
+/// # Security Considerations
+/// - Only the management authority can change the supported formats
+/// - Each list holds between 1 and `MAX_WINNER_DATA_FORMATS` values, so winners can
+///   always submit their data
+/// - Data that was already submitted keeps the format it was stored with
+pub fn set_winner_data_formats(
+    ctx: Context<SetWinnerDataFormats>,
+    format_versions: Vec<u8>,
+    encryption_schemes: Vec<u8>,
+) -> Result<()> {
+    for values in [&format_versions, &encryption_schemes] {
+        require!(
+            !values.is_empty() && values.len() <= MAX_WINNER_DATA_FORMATS,
+            RaffleError::InvalidWinnerDataFormats
+        );
+    }
+
+    ctx.accounts.config.supported_format_versions = format_versions.clone();
+    ctx.accounts.config.supported_encryption_schemes = encryption_schemes.clone();
+
+    emit!(WinnerDataFormatsSet {
+        format_versions,
+        encryption_schemes,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetWinnerDataFormats<'info> {
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::RaffleError, state::Config};
+
+/// Event emitted when the withdrawal approver or its threshold are changed
+#[event]
+pub struct WithdrawalApproverSet {
+    /// Key that must approve withdrawals above the threshold, or None if no approval
+    /// is required
+    pub withdrawal_approver: Option<Pubkey>,
+    /// Largest withdrawal that doesn't require approval
+    pub withdrawal_approval_threshold: u64,
+}
+
+/// Instruction to require a second key to approve treasury withdrawals above a
+/// threshold, or to stop requiring approvals
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `withdrawal_approver` - Key that must approve withdrawals above the threshold, or
+///   None to stop requiring approvals
+/// * `withdrawal_approval_threshold` - Largest withdrawal, in lamports or base units of
+///   the raffle's payment mint, that doesn't require approval
+///
+/// # Security Considerations
+/// - Only the upgrade authority can change the approver, so leaked management and
+///   payout keys can't remove the second signature
+pub fn set_withdrawal_approver(
+    ctx: Context<SetWithdrawalApprover>,
+    withdrawal_approver: Option<Pubkey>,
+    withdrawal_approval_threshold: u64,
+) -> Result<()> {
+    ctx.accounts.config.withdrawal_approver = withdrawal_approver;
+    ctx.accounts.config.withdrawal_approval_threshold = withdrawal_approval_threshold;
+
+    emit!(WithdrawalApproverSet {
+        withdrawal_approver,
+        withdrawal_approval_threshold,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetWithdrawalApprover<'info> {
+    pub upgrade_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = upgrade_authority @ RaffleError::NotProgramUpgradeAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    instructions::draw_winning_ticket::derive_vrf_winning_tickets,
+    notifier::{notify_lifecycle, LifecycleEvent},
+    randomness::reveal_switchboard,
+    state::{Config, Raffle, RaffleState, RandomnessMode},
+    time::now,
+};
+
+/// Event emitted when a raffle's winning ticket is drawn from verifiable randomness
+#[event]
+pub struct RandomnessSettled {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The Switchboard randomness account the draw was settled from
+    pub randomness_account: Pubkey,
+    /// The randomness value revealed by the oracle
+    pub value: [u8; 32],
+    /// The drawn winning ticket of the first prize
+    pub winning_ticket: u64,
+    /// The drawn winning tickets of all prizes, in prize order
+    pub winning_tickets: Vec<u64>,
+    /// The slot the oracle revealed the randomness in, stored as the draw slot
+    pub reveal_slot: u64,
+    /// Why the raffle left the Open state, one of the `CLOSE_REASON_*` values
+    pub close_reason: u8,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Draws the winning ticket of a raffle from the randomness revealed into the account it
+/// committed to with request_randomness. Anyone can settle, as the outcome is fixed by
+/// the commitment.
+///
+/// After execution:
+/// - The winning ticket numbers are stored in the raffle account in prize order
+/// - The reveal slot and timestamp are stored as the draw slot and timestamp, and the
+///   revealed value is recorded in the RandomnessSettled event
+/// - The raffle state is changed to Drawing
+///
+/// # Arguments
+/// * `ctx` - The context object containing:
+///   - `raffle`: The mutable raffle account being drawn
+///   - `randomness_account`: The randomness account the raffle committed to
+///
+/// # Errors
+/// - `RaffleNotOpen` if the raffle is not in Open state
+/// - `VrfNotEnabled` if the raffle is drawn from the SlotHashes sysvar
+/// - `InvalidRandomnessAccount` if the account is not the one the raffle committed to,
+///   or was recommitted since
+/// - `RandomnessNotRevealed` if the oracle has not revealed the value yet
+/// - `Overflow` if arithmetic overflow occurs during random number generation
+pub fn settle_randomness(ctx: Context<SettleRandomness>) -> Result<()> {
+    let randomness_account = &ctx.accounts.randomness_account;
+    require!(
+        ctx.accounts.raffle.randomness_account == Some(randomness_account.key()),
+        RaffleError::InvalidRandomnessAccount
+    );
+    let (value, reveal_slot) = reveal_switchboard(
+        randomness_account,
+        ctx.accounts.raffle.randomness_commit_slot,
+    )?;
+
+    let raffle = &mut ctx.accounts.raffle;
+    let winning_tickets = raffle.held_ticket_numbers(derive_vrf_winning_tickets(
+        &value,
+        raffle.current_tickets,
+        raffle.winner_count,
+    )?)?;
+    let winning_ticket = *winning_tickets
+        .first()
+        .ok_or(RaffleError::NoWinningTicket)?;
+
+    raffle.winning_ticket = Some(winning_ticket);
+    raffle.winning_tickets = winning_tickets.clone();
+    raffle.draw_slot = Some(reveal_slot);
+    let drawn_at = now(&ctx.accounts.config)?;
+    raffle.draw_timestamp = Some(drawn_at);
+    raffle.raffle_state = RaffleState::Drawing;
+    raffle.close_reason = raffle.draw_close_reason(drawn_at);
+
+    let event_seq = raffle.next_event_seq()?;
+    emit!(RandomnessSettled {
+        raffle: raffle.key(),
+        randomness_account: randomness_account.key(),
+        value,
+        winning_ticket,
+        winning_tickets,
+        reveal_slot,
+        close_reason: raffle.close_reason,
+        event_seq,
+    });
+
+    notify_lifecycle(
+        &ctx.accounts.config,
+        ctx.accounts.notifier_program.as_ref(),
+        &ctx.accounts.raffle,
+        LifecycleEvent::Drawn,
+    )
+}
+
+/// Accounts required for the settle_randomness instruction
+#[derive(Accounts)]
+pub struct SettleRandomness<'info> {
+    /// The raffle account to draw a winner for, which must have requested randomness
+    #[account(
+        mut,
+        constraint = raffle.raffle_state == RaffleState::Open @ RaffleError::RaffleNotOpen,
+        constraint = raffle.randomness_mode == RandomnessMode::Vrf @ RaffleError::VrfNotEnabled,
+        constraint = raffle.reserved_tickets == 0 @ RaffleError::ReservationsPending,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    /// The randomness account the raffle committed to
+    /// CHECK: The key is checked against the raffle's request and the data is parsed as a
+    /// randomness account in the instruction
+    pub randomness_account: UncheckedAccount<'info>,
+    /// Program config, storing the notifier program
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Program notified of the raffle's draw, required if the config registers one
+    /// CHECK: Validated against the config's notifier program in the instruction
+    pub notifier_program: Option<UncheckedAccount<'info>>,
+}
@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    math::bps_of,
+    state::{Raffle, RaffleState, SponsorMatch, Treasury},
+    transfer::move_lamports,
+};
+
+/// Event emitted when a sponsor's matching contribution is settled
+#[event]
+pub struct SponsorMatchSettled {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The sponsor's address
+    pub sponsor: Pubkey,
+    /// Lamports added to the raffle's treasury
+    pub matched_amount: u64,
+    /// Escrowed lamports returned to the sponsor, excluding rent
+    pub returned_amount: u64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to settle a sponsor's matching contribution once the raffle has concluded.
+/// Can be called by anyone.
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Validates the raffle has been drawn, expired or cancelled
+/// 2. Verifies the treasury and sponsor match PDAs belong to the raffle
+/// 3. Returns the remainder and rent only to the recorded sponsor
+///
+/// # Implementation Notes
+/// - For drawn raffles, `match_bps` of the net ticket sales (up to the cap) is moved
+///   to the treasury and tracked as sponsored funds
+/// - For expired or cancelled raffles, nothing is matched and the full escrow is returned
+/// - The SponsorMatch account is closed and its remaining lamports go to the sponsor
+pub fn settle_sponsor_match(ctx: Context<SettleSponsorMatch>) -> Result<()> {
+    let matched_amount = match ctx.accounts.raffle.raffle_state {
+        RaffleState::Drawing | RaffleState::Drawn | RaffleState::Claimed => bps_of(
+            ctx.accounts.treasury.net_sales()?,
+            ctx.accounts.sponsor_match.match_bps,
+        )?
+        .min(ctx.accounts.sponsor_match.cap),
+        RaffleState::Expired | RaffleState::Cancelled => 0,
+        RaffleState::Open | RaffleState::AwaitingPrize => {
+            return err!(RaffleError::RaffleNotConcluded)
+        }
+    };
+
+    if matched_amount > 0 {
+        ctx.accounts.treasury.record_sponsorship(matched_amount)?;
+        ctx.accounts.treasury.record_deposit(matched_amount)?;
+
+        // The sponsor match is a PDA owned by our program, so lamports can be moved directly
+        move_lamports(
+            &ctx.accounts.sponsor_match.to_account_info(),
+            &ctx.accounts.treasury.to_account_info(),
+            matched_amount,
+        )?;
+    }
+
+    let event_seq = ctx.accounts.raffle.next_event_seq()?;
+    emit!(SponsorMatchSettled {
+        raffle: ctx.accounts.raffle.key(),
+        sponsor: ctx.accounts.sponsor.key(),
+        matched_amount,
+        returned_amount: ctx.accounts.sponsor_match.cap - matched_amount,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SettleSponsorMatch<'info> {
+    #[account(mut)]
+    pub raffle: Account<'info, Raffle>,
+
+    /// Treasury PDA for this raffle that receives the matched funds
+    #[account(
+        mut,
+        seeds = [
+            b"treasury",
+            raffle.key().as_ref(),
+        ],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    /// Escrow PDA holding the sponsor's contribution, closed to the sponsor
+    #[account(
+        mut,
+        close = sponsor,
+        has_one = sponsor,
+        seeds = [
+            b"sponsor_match",
+            raffle.key().as_ref(),
+            sponsor.key().as_ref(),
+        ],
+        bump = sponsor_match.bump,
+    )]
+    pub sponsor_match: Account<'info, SponsorMatch>,
+
+    /// The sponsor receiving the remaining escrow and rent
+    #[account(mut)]
+    pub sponsor: SystemAccount<'info>,
+}
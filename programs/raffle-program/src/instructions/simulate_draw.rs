@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    instructions::draw_winning_ticket::{derive_winning_ticket, read_latest_slot_hash},
+    state::{Config, Raffle},
+    time::now,
+};
+
+/// Dry-runs the draw of a raffle and returns the ticket that would win if
+/// `draw_winning_ticket` ran in the same slot, via return data.
+///
+/// Performs the same entropy extraction and ticket mapping as the real draw, but does
+/// not mutate any state and skips the draw preconditions (end time, threshold), so
+/// operators and monitoring tooling can run it against any raffle with tickets sold.
+///
+/// # Arguments
+/// * `ctx` - The context object containing:
+///   - `raffle`: The raffle account to simulate the draw for
+///   - `recent_slothashes`: The SlotHashes sysvar containing block hashes (manually validated)
+///
+/// # Errors
+/// - `InvalidSlotHashesAccount` if the provided SlotHashes account is invalid
+/// - `Overflow` if the raffle has no tickets sold
+pub fn simulate_draw(ctx: Context<SimulateDraw>) -> Result<u64> {
+    let (slot, slot_hash) = read_latest_slot_hash(&ctx.accounts.recent_slothashes)?;
+
+    derive_winning_ticket(
+        slot,
+        &slot_hash,
+        now(&ctx.accounts.config)?,
+        ctx.accounts.raffle.current_tickets,
+    )
+}
+
+/// Accounts required for the simulate_draw instruction
+#[derive(Accounts)]
+pub struct SimulateDraw<'info> {
+    /// The raffle account to simulate the draw for
+    pub raffle: Account<'info, Raffle>,
+
+    /// The SlotHashes sysvar contains the most recent block hashes
+    /// CHECK: Using UncheckedAccount because we manually validate the correct sysvar.
+    /// This is needed because Anchor will always throw an error on the SlotHashes sysvar.
+    pub recent_slothashes: UncheckedAccount<'info>,
+
+    /// Program config, storing the clock offset of devnet builds
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
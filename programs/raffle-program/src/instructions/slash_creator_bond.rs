@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    state::{Config, CreatorBond, Raffle, RaffleState},
+};
+
+/// Event emitted when a creator bond is slashed for fraud
+#[event]
+pub struct CreatorBondSlashed {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The insurance fund or payout authority the bond was slashed to
+    pub destination: Pubkey,
+    /// Bonded lamports slashed, excluding the rent
+    pub amount: u64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to slash the bond of a raffle's creator to the insurance fund, after the
+/// raffle was voided for fraud
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Only the upgrade authority can slash a bond, so the management authority creating
+///    raffles can neither slash nor protect its own bonds
+/// 2. Ensures the raffle was voided, i.e. is in Expired or Cancelled state
+/// 3. Ensures the bond is slashed to the config's insurance fund, or the payout
+///    authority while it has none
+///
+/// # Account Validations
+/// * Raffle - Must be in Expired or Cancelled state
+/// * CreatorBond - PDA with seeds ["creator_bond", raffle_key], closed to the destination
+/// * Destination - Must be the config's insurance fund or payout authority
+/// * Upgrade Authority - Must match the upgrade authority stored in config
+///
+/// # Implementation Notes
+/// - Closing the bond moves its rent to the destination along with the bonded lamports
+pub fn slash_creator_bond(ctx: Context<SlashCreatorBond>) -> Result<()> {
+    require!(
+        matches!(
+            ctx.accounts.raffle.raffle_state,
+            RaffleState::Expired | RaffleState::Cancelled
+        ),
+        RaffleError::RaffleNotExpired
+    );
+
+    let event_seq = ctx.accounts.raffle.next_event_seq()?;
+    emit!(CreatorBondSlashed {
+        raffle: ctx.accounts.raffle.key(),
+        destination: ctx.accounts.destination.key(),
+        amount: ctx.accounts.creator_bond.amount,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SlashCreatorBond<'info> {
+    #[account(mut)]
+    pub raffle: Account<'info, Raffle>,
+
+    /// The raffle's creator bond, closed to the destination
+    #[account(
+        mut,
+        close = destination,
+        seeds = [
+            b"creator_bond",
+            raffle.key().as_ref(),
+        ],
+        bump = creator_bond.bump,
+    )]
+    pub creator_bond: Account<'info, CreatorBond>,
+
+    /// The insurance fund, or the payout authority while the config has none
+    #[account(
+        mut,
+        constraint = destination.key() == config.forfeiture_destination()
+            @ RaffleError::InvalidForfeitureDestination,
+    )]
+    pub destination: SystemAccount<'info>,
+
+    pub upgrade_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = upgrade_authority @ RaffleError::NotProgramUpgradeAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
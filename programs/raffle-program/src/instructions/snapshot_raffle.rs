@@ -0,0 +1,162 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    math::checked_add,
+    merkle::{append_leaf, branch_root, snapshot_leaf, SNAPSHOT_TREE_DEPTH},
+    state::{Config, Raffle, RaffleState, Snapshot, TicketBalance, SNAPSHOT_ACCOUNT_SIZE},
+    time::now,
+};
+
+/// Event emitted when the snapshot of a raffle's ticket holders is recorded
+#[event]
+pub struct RaffleSnapshotted {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// Merkle root of the (owner, ticket_count) pairs of all ticket holders
+    pub snapshot_root: [u8; 32],
+    /// Number of ticket holders in the snapshot
+    pub holder_count: u64,
+    /// Number of tickets held by all holders in the snapshot
+    pub ticket_count: u64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Crank instruction that records a Merkle root of the (owner, ticket_count) pairs of all
+/// ticket holders of a raffle once its sale closed, so holdings can still be proven with
+/// `merkle::verify_proof` after the raffle's entries and balances are closed. Holders are
+/// added in ascending owner order, so the root doesn't depend on who cranks it. Can be
+/// called by anyone, in as many batches as needed; the root is written to
+/// `Raffle::snapshot_root` once every holder was added.
+///
+/// # Remaining Accounts
+/// TicketBalance accounts of the raffle, in ascending owner order continuing from
+/// `Snapshot::last_owner`. Balances without tickets are skipped
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Validates the raffle's sale closed, either because the end time passed, because it
+///    sold out or because it left the Open state, so no holdings change while the tree
+///    is built
+/// 2. Verifies each ticket balance is the raffle's PDA for its owner and comes after the
+///    last added holder, so no holder is added twice
+/// 3. Only records the root once as many holders as the raffle's participants were
+///    added, holding all of its tickets, so no holder is left out
+///
+/// # Account Validations
+/// * Raffle - Sale must be closed and no snapshot recorded yet
+/// * Snapshot - PDA with seeds ["snapshot", raffle], created by the first call
+pub fn snapshot_raffle<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SnapshotRaffle<'info>>,
+) -> Result<()> {
+    let remaining_accounts = ctx.remaining_accounts;
+    require!(
+        !remaining_accounts.is_empty(),
+        RaffleError::InvalidRemainingAccounts
+    );
+
+    let raffle = &mut ctx.accounts.raffle;
+    let snapshot = &mut ctx.accounts.snapshot;
+    if snapshot.raffle == Pubkey::default() {
+        snapshot.raffle = raffle.key();
+        snapshot.bump = ctx.bumps.snapshot;
+    }
+
+    for ticket_balance_info in remaining_accounts {
+        let ticket_balance = Account::<TicketBalance>::try_from(ticket_balance_info)?;
+        let address = Pubkey::create_program_address(
+            &[
+                b"ticket_balance",
+                raffle.key().as_ref(),
+                ticket_balance.owner.as_ref(),
+                &[ticket_balance.bump],
+            ],
+            &crate::ID,
+        )
+        .map_err(|_| RaffleError::InvalidRemainingAccounts)?;
+        require_keys_eq!(
+            ticket_balance_info.key(),
+            address,
+            RaffleError::InvalidRemainingAccounts
+        );
+        require!(
+            ticket_balance.owner > snapshot.last_owner,
+            RaffleError::TicketBalanceOutOfOrder
+        );
+        snapshot.last_owner = ticket_balance.owner;
+
+        if ticket_balance.ticket_count == 0 {
+            continue;
+        }
+        require!(
+            snapshot.holder_count < (1 << SNAPSHOT_TREE_DEPTH) - 1,
+            RaffleError::SnapshotTreeFull
+        );
+        let holder_count = snapshot.holder_count;
+        append_leaf(
+            &mut snapshot.branch,
+            holder_count,
+            snapshot_leaf(&ticket_balance.owner, ticket_balance.ticket_count),
+        );
+        snapshot.holder_count = checked_add(snapshot.holder_count, 1)?;
+        snapshot.ticket_count = checked_add(snapshot.ticket_count, ticket_balance.ticket_count)?;
+    }
+
+    if snapshot.holder_count == raffle.participant_count {
+        require!(
+            snapshot.ticket_count == raffle.current_tickets,
+            RaffleError::SnapshotMismatch
+        );
+
+        let snapshot_root = branch_root(&snapshot.branch, snapshot.holder_count);
+        raffle.snapshot_root = Some(snapshot_root);
+
+        let event_seq = raffle.next_event_seq()?;
+        emit!(RaffleSnapshotted {
+            raffle: raffle.key(),
+            snapshot_root,
+            holder_count: snapshot.holder_count,
+            ticket_count: snapshot.ticket_count,
+            event_seq,
+        });
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SnapshotRaffle<'info> {
+    /// Pays the rent of the snapshot account on the first call
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// The raffle whose ticket holders are snapshotted, once its sale closed
+    #[account(
+        mut,
+        constraint = raffle.snapshot_root.is_none() @ RaffleError::SnapshotAlreadyRecorded,
+        constraint = (raffle.raffle_state != RaffleState::Open)
+            || (now(&config)? >= raffle.end_time)
+            || (raffle.max_tickets.is_some() && raffle.current_tickets == raffle.max_tickets.unwrap()) @ RaffleError::RaffleNotEnded,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    /// Progress of the snapshot, kept across batches
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = SNAPSHOT_ACCOUNT_SIZE,
+        seeds = [b"snapshot", raffle.key().as_ref()],
+        bump
+    )]
+    pub snapshot: Account<'info, Snapshot>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Program config, storing the clock offset of devnet builds
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    math::BPS_DENOMINATOR,
+    state::{Raffle, RaffleState, SponsorMatch, SPONSOR_MATCH_ACCOUNT_SIZE},
+    transfer::transfer_lamports,
+};
+
+/// Event emitted when a sponsor escrows a matching contribution
+#[event]
+pub struct SponsorMatchCreated {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The sponsor's address
+    pub sponsor: Pubkey,
+    /// Share of net ticket sales that is matched, in basis points
+    pub match_bps: u16,
+    /// Maximum lamports that will be matched
+    pub cap: u64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction for a sponsor to escrow a matching contribution for a raffle.
+/// At draw time, `match_bps` of the net ticket sales (up to `cap`) is added to the
+/// raffle's treasury, and the remainder is returned to the sponsor.
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `match_bps` - Share of net ticket sales to match, in basis points (1-10000)
+/// * `cap` - Maximum lamports to match; this full amount is escrowed up front
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Validates the raffle is in Open state
+/// 2. Validates match_bps and cap are within bounds
+/// 3. Uses a PDA with seeds ["sponsor_match", raffle_key, sponsor_key] as escrow
+/// 4. Rejects raffles paid in an SPL token, as contributions are escrowed in lamports
+///
+/// # Implementation Notes
+/// - The escrowed lamports are held by the SponsorMatch PDA on top of its rent
+/// - See `settle_sponsor_match` for how the escrow is released
+pub fn sponsor_match(ctx: Context<SponsorMatchCtx>, match_bps: u16, cap: u64) -> Result<()> {
+    require!(
+        match_bps > 0 && match_bps as u64 <= BPS_DENOMINATOR,
+        RaffleError::InvalidMatchBps
+    );
+    require!(cap > 0, RaffleError::InvalidMatchCap);
+    require!(
+        ctx.accounts.raffle.payment_mint.is_none(),
+        RaffleError::TokenPaymentsNotSupported
+    );
+
+    let sponsor_match = &mut ctx.accounts.sponsor_match;
+    sponsor_match.raffle = ctx.accounts.raffle.key();
+    sponsor_match.sponsor = ctx.accounts.sponsor.key();
+    sponsor_match.match_bps = match_bps;
+    sponsor_match.cap = cap;
+    sponsor_match.bump = ctx.bumps.sponsor_match;
+
+    // Escrow the full cap in the sponsor match PDA
+    transfer_lamports(
+        &ctx.accounts.sponsor.to_account_info(),
+        &ctx.accounts.sponsor_match.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        cap,
+    )?;
+
+    let event_seq = ctx.accounts.raffle.next_event_seq()?;
+    emit!(SponsorMatchCreated {
+        raffle: ctx.accounts.raffle.key(),
+        sponsor: ctx.accounts.sponsor.key(),
+        match_bps,
+        cap,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SponsorMatchCtx<'info> {
+    /// The raffle being sponsored, must still be selling tickets
+    #[account(
+        mut,
+        constraint = raffle.raffle_state == RaffleState::Open @ RaffleError::RaffleNotOpen,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    /// Escrow PDA holding the sponsor's contribution
+    #[account(
+        init,
+        payer = sponsor,
+        space = SPONSOR_MATCH_ACCOUNT_SIZE,
+        seeds = [
+            b"sponsor_match",
+            raffle.key().as_ref(),
+            sponsor.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub sponsor_match: Account<'info, SponsorMatch>,
+
+    /// The sponsor funding the match
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
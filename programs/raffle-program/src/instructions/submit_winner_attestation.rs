@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    instructions::submit_winner_data::verify_winner,
+    notifier::{notify_lifecycle, LifecycleEvent},
+    state::{raffle::*, Config, IdentityAttestation},
+    time::now,
+};
+
+/// Event emitted when a winner claims with an identity attestation
+#[event]
+pub struct WinnerAttestationSubmitted {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The winner who claimed
+    pub winner: Pubkey,
+    /// The identity attestation linked to the claim
+    pub identity_attestation: Pubkey,
+    /// Hash of the credential the issuer verified
+    pub credential_hash: [u8; 32],
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction for a raffle winner to claim by linking an identity attestation of the
+/// config's identity issuer, as an alternative to submitting encrypted contact data
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `winner_secret` - Secret the winner committed to with commit_winner_secret,
+///   required for anonymized raffles to reveal the preimage of the stored winner hash
+/// * `winner_index` - Index of the prize the signer won, in prize order
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Validates the raffle is in Drawn state
+/// 2. Ensures signer is the designated winner of the prize, or for anonymized raffles
+///    that the signer and revealed secret hash to the stored winner hash
+/// 3. Ensures the attestation belongs to the signer, was signed by the identity issuer
+///    currently registered in the config and hasn't expired
+///
+/// # Account Validations
+/// * Raffle - Must be in Drawn state
+/// * Signer - Must be the designated winner stored in the raffle account
+/// * Identity Attestation - PDA with seeds ["identity_attestation", signer]
+///
+/// # Implementation Notes
+/// - No WinnerData account is created. The operator looks up the winner's identity with
+///   the issuer from the credential hash emitted in WinnerAttestationSubmitted
+/// - Marks every prize the signer won as submitted, like submit_winner_data, and
+///   updates raffle state from Drawn to Claimed once all winners submitted
+/// - Winners of the same raffle can mix both claim paths
+pub fn submit_winner_attestation(
+    ctx: Context<SubmitWinnerAttestation>,
+    winner_secret: Option<[u8; 32]>,
+    winner_index: u8,
+) -> Result<()> {
+    // Verify the signer is the winner
+    verify_winner(
+        &mut ctx.accounts.raffle,
+        &ctx.accounts.signer.key(),
+        winner_secret,
+        winner_index,
+    )?;
+
+    let identity_attestation = &ctx.accounts.identity_attestation;
+    require!(
+        identity_attestation.wallet == ctx.accounts.signer.key()
+            && ctx.accounts.config.identity_issuer == Some(identity_attestation.issuer)
+            && now(&ctx.accounts.config)? < identity_attestation.expires_at,
+        RaffleError::InvalidIdentityAttestation
+    );
+
+    // Update raffle state to Claimed once every winner submitted their data
+    let raffle = &mut ctx.accounts.raffle;
+    raffle.winner_data_submitted |= raffle.winner_indices(&ctx.accounts.signer.key());
+    if raffle.winner_data_submitted == raffle.all_winners_mask() {
+        raffle.raffle_state = RaffleState::Claimed;
+        ctx.accounts.config.record_raffle_closed(raffle);
+        notify_lifecycle(
+            &ctx.accounts.config,
+            ctx.accounts.notifier_program.as_ref(),
+            raffle,
+            LifecycleEvent::Claimed,
+        )?;
+    }
+
+    let event_seq = ctx.accounts.raffle.next_event_seq()?;
+    emit!(WinnerAttestationSubmitted {
+        raffle: ctx.accounts.raffle.key(),
+        winner: ctx.accounts.signer.key(),
+        identity_attestation: ctx.accounts.identity_attestation.key(),
+        credential_hash: ctx.accounts.identity_attestation.credential_hash,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SubmitWinnerAttestation<'info> {
+    /// The raffle account that must be in Drawn state
+    /// Must have the signer as the designated winner, verified in the instruction
+    #[account(
+        mut,
+        constraint = raffle.raffle_state == RaffleState::Drawn @ RaffleError::RaffleNotDrawn,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    /// The identity attestation of the winner, validated in the instruction
+    #[account(
+        seeds = [
+            b"identity_attestation",
+            signer.key().as_ref(),
+        ],
+        bump = identity_attestation.bump,
+    )]
+    pub identity_attestation: Account<'info, IdentityAttestation>,
+
+    /// The winner claiming the prize
+    /// Must match the winner_address or winner_hash stored in the raffle account
+    pub signer: Signer<'info>,
+
+    /// The config account storing the identity issuer
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Program notified of the raffle's claim, required if the config registers one
+    /// CHECK: Validated against the config's notifier program in the instruction
+    pub notifier_program: Option<UncheckedAccount<'info>>,
+}
@@ -2,7 +2,10 @@ use anchor_lang::prelude::*;
 
 use crate::{
     error::RaffleError,
-    state::{raffle::*, WinnerData, WINNER_DATA_ACCOUNT_SIZE},
+    instructions::set_winner::winner_hash,
+    notifier::{notify_lifecycle, LifecycleEvent},
+    state::{raffle::*, Config, WinnerData, WINNER_DATA_ACCOUNT_SIZE},
+    time::now,
 };
 
 /// Event emitted when a winner submits their encrypted data
@@ -10,16 +13,78 @@ use crate::{
 pub struct WinnerDataSubmitted {
     /// The pubkey of the raffle
     pub raffle: Pubkey,
+    /// Layout version of the submitted data
+    pub format_version: u8,
+    /// How the submitted data was encrypted
+    pub encryption_scheme: u8,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Fails unless the winner data fits the account and uses a format version and
+/// encryption scheme supported by the config
+pub(crate) fn validate_winner_data(
+    config: &Config,
+    data: &str,
+    format_version: u8,
+    encryption_scheme: u8,
+) -> Result<()> {
+    require!(data.len() <= 854, RaffleError::InvalidDataLength);
+    require!(!data.is_empty(), RaffleError::InvalidDataLength);
+    require!(
+        config.supported_format_versions.contains(&format_version)
+            && config
+                .supported_encryption_schemes
+                .contains(&encryption_scheme),
+        RaffleError::UnsupportedWinnerDataFormat
+    );
+    Ok(())
+}
+
+/// Fails unless `signer` is the winner of the prize at `winner_index`, or for anonymized
+/// winners that the signer and `winner_secret` hash to the prize's winner hash. Publishes
+/// the winner's address on anonymized raffles, as proving the win reveals it anyway
+pub(crate) fn verify_winner(
+    raffle: &mut Account<Raffle>,
+    signer: &Pubkey,
+    winner_secret: Option<[u8; 32]>,
+    winner_index: u8,
+) -> Result<()> {
+    if raffle.winner(winner_index) == Some(*signer) {
+        return Ok(());
+    }
+
+    let expected_hash = raffle
+        .winner_hash_at(winner_index)
+        .ok_or(RaffleError::NotWinner)?;
+    let winner_secret = winner_secret.ok_or(RaffleError::NotWinner)?;
+    require!(
+        winner_hash(&raffle.key(), signer, &winner_secret) == expected_hash,
+        RaffleError::NotWinner
+    );
+    raffle.publish_winner(winner_index, *signer)
 }
 
 /// Instruction for a raffle winner to submit their encrypted contact information
 ///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `data` - The winner's encrypted contact information
+/// * `winner_secret` - Secret the winner committed to with commit_winner_secret,
+///   required for anonymized raffles to reveal the preimage of the stored winner hash
+/// * `format_version` - Layout version of `data`
+/// * `encryption_scheme` - How `data` was encrypted
+/// * `winner_index` - Index of the prize the signer won, in prize order
+///
 /// # Security Considerations
 /// The instruction performs several critical checks:
 /// 1. Validates the raffle is in Drawn state
-/// 2. Ensures signer is the designated winner of the raffle
+/// 2. Ensures signer is the designated winner of the prize, or for anonymized raffles
+///    that the signer and revealed secret hash to the stored winner hash
 /// 3. Verifies the data length is <= 854 characters
-/// 4. Uses PDAs with proper seeds for secure storage
+/// 4. Ensures the format version and encryption scheme are supported by the config, so
+///    the off-chain pipeline knows how to decrypt every stored blob
+/// 5. Uses PDAs with proper seeds for secure storage
 ///
 /// # Account Validations
 /// * Raffle - Must be in Drawn state
@@ -28,22 +93,64 @@ pub struct WinnerDataSubmitted {
 ///
 /// # Implementation Notes
 /// - Creates a new WinnerData account with encrypted contact information
-/// - Updates raffle state from Drawn to Claimed
+/// - Marks every prize the signer won as submitted, as the data is stored once per
+///   winner, and updates raffle state from Drawn to Claimed once all winners submitted
+/// - Publishes the winner's address on anonymized raffles, as the claim reveals it anyway
 /// - Uses encryption to protect winner's personal information on-chain
 /// - Emits WinnerDataSubmitted event to notify off-chain systems
-pub fn submit_winner_data(ctx: Context<SubmitWinnerData>, data: String) -> Result<()> {
-    require!(data.len() <= 854, RaffleError::InvalidDataLength);
-    require!(data.len() > 0, RaffleError::InvalidDataLength);
+/// - The data can be corrected with update_winner_data until it is locked with
+///   lock_winner_data or `WINNER_DATA_EDIT_WINDOW_SECS` have passed
+pub fn submit_winner_data(
+    ctx: Context<SubmitWinnerData>,
+    data: String,
+    winner_secret: Option<[u8; 32]>,
+    format_version: u8,
+    encryption_scheme: u8,
+    winner_index: u8,
+) -> Result<()> {
+    validate_winner_data(
+        &ctx.accounts.config,
+        &data,
+        format_version,
+        encryption_scheme,
+    )?;
+
+    // Verify the signer is the winner
+    verify_winner(
+        &mut ctx.accounts.raffle,
+        &ctx.accounts.signer.key(),
+        winner_secret,
+        winner_index,
+    )?;
 
     // Store the encrypted username
     ctx.accounts.winner_data.data = data;
+    ctx.accounts.winner_data.format_version = format_version;
+    ctx.accounts.winner_data.encryption_scheme = encryption_scheme;
+    ctx.accounts.winner_data.submitted_at = now(&ctx.accounts.config)?;
+    ctx.accounts.winner_data.locked = false;
 
-    // Update raffle state to Claimed
-    ctx.accounts.raffle.raffle_state = RaffleState::Claimed;
+    // Update raffle state to Claimed once every winner submitted their data
+    let raffle = &mut ctx.accounts.raffle;
+    raffle.winner_data_submitted |= raffle.winner_indices(&ctx.accounts.signer.key());
+    if raffle.winner_data_submitted == raffle.all_winners_mask() {
+        raffle.raffle_state = RaffleState::Claimed;
+        ctx.accounts.config.record_raffle_closed(raffle);
+        notify_lifecycle(
+            &ctx.accounts.config,
+            ctx.accounts.notifier_program.as_ref(),
+            raffle,
+            LifecycleEvent::Claimed,
+        )?;
+    }
 
     // Emit event
+    let event_seq = ctx.accounts.raffle.next_event_seq()?;
     emit!(WinnerDataSubmitted {
-        raffle: ctx.accounts.raffle.key()
+        raffle: ctx.accounts.raffle.key(),
+        format_version,
+        encryption_scheme,
+        event_seq,
     });
 
     Ok(())
@@ -52,11 +159,10 @@ pub fn submit_winner_data(ctx: Context<SubmitWinnerData>, data: String) -> Resul
 #[derive(Accounts)]
 pub struct SubmitWinnerData<'info> {
     /// The raffle account that must be in Drawn state
-    /// Must have the signer as the designated winner
+    /// Must have the signer as the designated winner, verified in the instruction
     #[account(
         mut,
         constraint = raffle.raffle_state == RaffleState::Drawn @ RaffleError::RaffleNotDrawn,
-        constraint = signer.key() == raffle.winner_address.unwrap() @ RaffleError::NotWinner,
     )]
     pub raffle: Account<'info, Raffle>,
 
@@ -75,10 +181,21 @@ pub struct SubmitWinnerData<'info> {
     pub winner_data: Account<'info, WinnerData>,
 
     /// The winner submitting their contact information
-    /// Must match the winner_address stored in the raffle account
+    /// Must match the winner_address or winner_hash stored in the raffle account
     #[account(mut)]
     pub signer: Signer<'info>,
 
+    /// The config account storing the supported winner data formats
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
     /// Required by Anchor for account creation
     pub system_program: Program<'info, System>,
+    /// Program notified of the raffle's claim, required if the config registers one
+    /// CHECK: Validated against the config's notifier program in the instruction
+    pub notifier_program: Option<UncheckedAccount<'info>>,
 }
@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    math::checked_add,
+    state::{Config, Raffle, RaffleState, Treasury},
+    transfer::{excess_lamports, move_lamports, rent_reserve_of},
+};
+
+/// Event emitted when stray lamports are swept from a treasury
+#[event]
+pub struct TreasuryDustSwept {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// Amount swept in lamports
+    pub amount: u64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to sweep lamports that were sent directly to a raffle's treasury
+/// (donations, airdrops) to the payout authority
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Verifies the signer is the management authority
+/// 2. Ensures treasury account matches the one stored in raffle
+/// 3. Only sweeps lamports above the rent reserve and the treasury's recognized balance
+/// 4. For cancelled raffles, ensures all ticket holders were refunded first
+///
+/// # Account Validations
+/// * Signer - Must be the management authority
+/// * Treasury - Must match raffle's treasury and use proper PDA seeds
+/// * Payout Authority - Must match the payout authority stored in config
+///
+/// # Implementation Notes
+/// - The treasury's tracked totals and recognized balance are left untouched, so refunds
+///   and withdrawals remain based on collected funds rather than the raw balance
+pub fn sweep_treasury_dust(ctx: Context<SweepTreasuryDust>) -> Result<()> {
+    // Verify treasury account matches the one stored in raffle
+    require!(
+        ctx.accounts.treasury.key() == ctx.accounts.raffle.treasury,
+        RaffleError::InvalidTreasury
+    );
+    // The treasury of a cancelled raffle is only settled once every ticket holder
+    // reclaimed their tickets
+    if ctx.accounts.raffle.raffle_state == RaffleState::Cancelled {
+        require!(
+            ctx.accounts.treasury.net_sales()? == 0,
+            RaffleError::RefundsPending
+        );
+    }
+
+    let treasury_account = ctx.accounts.treasury.to_account_info();
+    let payout_authority = ctx.accounts.payout_authority.to_account_info();
+
+    // Everything above the rent reserve and the recognized balance was not moved into
+    // the treasury by the program
+    let tracked_lamports = checked_add(
+        rent_reserve_of(&treasury_account)?,
+        ctx.accounts.treasury.recognized_balance,
+    )?;
+    let dust = excess_lamports(&treasury_account, tracked_lamports);
+    require!(dust > 0, RaffleError::NoTreasuryDust);
+
+    // The treasury is a PDA owned by our program, so lamports can be moved directly
+    move_lamports(&treasury_account, &payout_authority, dust)?;
+
+    let event_seq = ctx.accounts.raffle.next_event_seq()?;
+    emit!(TreasuryDustSwept {
+        raffle: ctx.accounts.raffle.key(),
+        amount: dust,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SweepTreasuryDust<'info> {
+    #[account(mut)]
+    pub raffle: Account<'info, Raffle>,
+
+    #[account(mut)]
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"treasury",
+            raffle.key().as_ref(),
+        ],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+        has_one = payout_authority @ RaffleError::NotPayoutAuthority
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub payout_authority: SystemAccount<'info>,
+}
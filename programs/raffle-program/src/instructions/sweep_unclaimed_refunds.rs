@@ -0,0 +1,165 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    math::RATE_SCALE,
+    state::{Config, Raffle, RaffleState, Treasury},
+    time::now,
+    treasury::{refund_token_accounts, LamportVault, TokenVault, TreasuryVault},
+};
+use anchor_spl::token::{Token, TokenAccount};
+
+/// Event emitted when unclaimed refunds are forfeited past the refund deadline
+#[event]
+pub struct UnclaimedRefundsSwept {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The insurance fund or payout authority the refunds were swept to
+    pub destination: Pubkey,
+    /// Amount swept, in lamports or base units of the raffle's payment mint
+    pub amount: u64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to sweep the refunds ticket holders left unclaimed past the refund
+/// deadline of an expired or cancelled raffle, so its treasury doesn't linger forever
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Validates the raffle is in Expired or Cancelled state
+/// 2. Ensures the raffle has a refund deadline and that it has passed
+/// 3. Ensures the refunds are swept to the config's insurance fund, or the payout
+///    authority while it has none
+/// 4. Only sweeps funds owed to ticket holders, leaving refund processing fees to be
+///    withdrawn by the payout authority
+/// 5. If the raffle is paid in an SPL token, ensures the refunds are swept from the
+///    treasury's associated token account into a token account owned by the destination
+///
+/// # Account Validations
+/// * Raffle - Must be in Expired or Cancelled state, past its refund deadline
+/// * Treasury - PDA with seeds ["treasury", raffle_key]
+/// * Destination - Must be the config's insurance fund or payout authority
+/// * DestinationTokenAccount - Optional, token account of the destination, required if
+///   the raffle is paid in an SPL token
+/// * TreasuryTokenAccount - Optional, required if the raffle is paid in an SPL token
+/// * TokenProgram - Optional, required if the raffle is paid in an SPL token
+///
+/// # Implementation Notes
+/// - Anyone can crank the sweep, as the destination is fixed by the config
+/// - The swept funds are recorded as forfeited, so ticket holders can no longer reclaim
+///   them and the raffle can be closed once its refund fees are withdrawn
+/// - If the raffle has a refund mint, the refunds are swept from the treasury's token
+///   account for it, converted at the refund rate
+pub fn sweep_unclaimed_refunds(ctx: Context<SweepUnclaimedRefunds>) -> Result<()> {
+    require!(
+        matches!(
+            ctx.accounts.raffle.raffle_state,
+            RaffleState::Expired | RaffleState::Cancelled
+        ),
+        RaffleError::RaffleNotExpired
+    );
+    require!(
+        ctx.accounts
+            .raffle
+            .refunds_lapsed(now(&ctx.accounts.config)?),
+        RaffleError::RefundWindowOpen
+    );
+
+    // Token payments are held in the treasury's token account, which has its own balance.
+    // The balance of a refund mint vault is converted back to payment mint units
+    let available = match &ctx.accounts.treasury_token_account {
+        Some(vault) if ctx.accounts.raffle.refund_mint.is_some() => {
+            let capacity = (vault.amount as u128)
+                .checked_mul(RATE_SCALE as u128)
+                .ok_or(RaffleError::Overflow)?
+                / ctx.accounts.raffle.refund_rate.max(1) as u128;
+            u64::try_from(capacity).unwrap_or(u64::MAX)
+        }
+        Some(vault) if ctx.accounts.raffle.payment_mint.is_some() => vault.amount,
+        _ => ctx.accounts.treasury.recognized_balance,
+    };
+    let amount = ctx.accounts.treasury.unclaimed_refunds()?.min(available);
+    require!(amount > 0, RaffleError::NoUnclaimedRefunds);
+
+    let destination = ctx.accounts.destination.to_account_info();
+    if ctx.accounts.raffle.payment_mint.is_some() {
+        let (vault, token_program) = refund_token_accounts(
+            &ctx.accounts.raffle,
+            &ctx.accounts.treasury,
+            ctx.accounts.treasury_token_account.as_mut(),
+            ctx.accounts.token_program.as_ref(),
+        )?;
+        let destination_token_account = ctx
+            .accounts
+            .destination_token_account
+            .as_ref()
+            .ok_or(RaffleError::InvalidPaymentMint)?;
+        require!(
+            destination_token_account.owner == destination.key(),
+            RaffleError::InvalidForfeitureDestination
+        );
+        TokenVault::new(&mut ctx.accounts.treasury, vault, token_program)
+            .with_refund_mint(&ctx.accounts.raffle)
+            .forfeit(&destination_token_account.to_account_info(), amount)?;
+    } else {
+        LamportVault::new(&mut ctx.accounts.treasury, &ctx.accounts.system_program)
+            .forfeit(&destination, amount)?;
+    }
+
+    let event_seq = ctx.accounts.raffle.next_event_seq()?;
+    emit!(UnclaimedRefundsSwept {
+        raffle: ctx.accounts.raffle.key(),
+        destination: destination.key(),
+        amount,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SweepUnclaimedRefunds<'info> {
+    #[account(mut)]
+    pub raffle: Account<'info, Raffle>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"treasury",
+            raffle.key().as_ref(),
+        ],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The insurance fund, or the payout authority while the config has none
+    #[account(
+        mut,
+        constraint = destination.key() == config.forfeiture_destination()
+            @ RaffleError::InvalidForfeitureDestination,
+    )]
+    pub destination: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Optional token account of the destination receiving the refunds, required if the
+    /// raffle is paid in an SPL token
+    #[account(mut)]
+    pub destination_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Optional associated token account of the treasury holding the payments, or the
+    /// refund mint if the raffle has one, required if the raffle is paid in an SPL token.
+    /// Validated against the mint in the instruction.
+    #[account(mut)]
+    pub treasury_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Required if the raffle is paid in an SPL token
+    pub token_program: Option<Program<'info, Token>>,
+}
@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    notifier::{notify_lifecycle, LifecycleEvent},
+    state::{Ballot, Config, Entry, Raffle, RaffleState, RandomnessMode},
+    time::now,
+};
+
+/// Event emitted when the community vote of a raffle is tallied
+#[event]
+pub struct VoteTallied {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The shortlisted entry with the most votes
+    pub winning_entry: Pubkey,
+    /// First ticket of the winning entry, stored as the winning ticket
+    pub winning_ticket: u64,
+    /// Tokens voted for the winning entry
+    pub votes: u64,
+    /// Why the raffle left the Open state, one of the `CLOSE_REASON_*` values
+    pub close_reason: u8,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Decides the winner of a raffle drawn by votes once voting has ended. Anyone can tally,
+/// as the outcome is fixed by the recorded votes.
+///
+/// After execution:
+/// - The first ticket of the shortlisted entry with the most votes is stored as the
+///   winning ticket; ties go to the entry shortlisted first
+/// - The raffle state is changed to Drawing, so the winner is set as for any draw
+///
+/// # Arguments
+/// * `ctx` - The context object containing:
+///   - `raffle`: The mutable raffle account being drawn
+///   - `ballot`: The ballot of the raffle
+///   - `winning_entry`: The shortlisted entry with the most votes
+///
+/// # Errors
+/// - `RaffleNotOpen` if the raffle is not in Open state
+/// - `VoteDrawDisabled` if the raffle is not drawn by votes
+/// - `VotingNotEnded` if the voting window is still open
+/// - `InvalidWinningEntry` if the entry is not the one with the most votes
+pub fn tally_vote(ctx: Context<TallyVote>) -> Result<()> {
+    let ballot = &ctx.accounts.ballot;
+    let now = now(&ctx.accounts.config)?;
+    require!(now >= ballot.voting_ends_at, RaffleError::VotingNotEnded);
+
+    let leader = ballot.leader();
+    let winning_entry = &ctx.accounts.winning_entry;
+    require_keys_eq!(
+        winning_entry.key(),
+        ballot.shortlist[leader],
+        RaffleError::InvalidWinningEntry
+    );
+    let winning_ticket = winning_entry.ticket_start_index;
+
+    let raffle = &mut ctx.accounts.raffle;
+    raffle.winning_ticket = Some(winning_ticket);
+    raffle.winning_tickets = vec![winning_ticket];
+    raffle.draw_timestamp = Some(now);
+    raffle.raffle_state = RaffleState::Drawing;
+    raffle.close_reason = raffle.draw_close_reason(now);
+
+    let event_seq = raffle.next_event_seq()?;
+    emit!(VoteTallied {
+        raffle: raffle.key(),
+        winning_entry: winning_entry.key(),
+        winning_ticket,
+        votes: ballot.tallies[leader],
+        close_reason: raffle.close_reason,
+        event_seq,
+    });
+
+    notify_lifecycle(
+        &ctx.accounts.config,
+        ctx.accounts.notifier_program.as_ref(),
+        &ctx.accounts.raffle,
+        LifecycleEvent::Drawn,
+    )
+}
+
+/// Accounts required for the tally_vote instruction
+#[derive(Accounts)]
+pub struct TallyVote<'info> {
+    /// The raffle account to draw a winner for, which must be drawn by votes
+    #[account(
+        mut,
+        constraint = raffle.raffle_state == RaffleState::Open @ RaffleError::RaffleNotOpen,
+        constraint = raffle.randomness_mode == RandomnessMode::Vote @ RaffleError::VoteDrawDisabled,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    /// The ballot of the raffle
+    #[account(
+        seeds = [
+            b"ballot",
+            raffle.key().as_ref(),
+        ],
+        bump = ballot.bump,
+    )]
+    pub ballot: Account<'info, Ballot>,
+
+    /// The shortlisted entry with the most votes
+    pub winning_entry: Account<'info, Entry>,
+    /// Program config, storing the notifier program
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Program notified of the raffle's draw, required if the config registers one
+    /// CHECK: Validated against the config's notifier program in the instruction
+    pub notifier_program: Option<UncheckedAccount<'info>>,
+}
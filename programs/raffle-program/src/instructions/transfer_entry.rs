@@ -0,0 +1,277 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    instructions::{buy_tickets::validate_wallet_limit, confirm_reservation::load_ticket_balance},
+    math::{checked_add, checked_sub},
+    state::{Config, Entry, Raffle, RaffleState, TicketBalance},
+    time::now,
+};
+
+/// Event emitted when an entry is transferred to another wallet
+#[event]
+pub struct EntryTransferred {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The transferred entry
+    pub entry: Pubkey,
+    /// The previous owner of the entry
+    pub from: Pubkey,
+    /// The new owner of the entry
+    pub to: Pubkey,
+    /// Number of tickets in the entry
+    pub ticket_count: u64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction for an entry owner to transfer the entry to another wallet, e.g. to gift
+/// tickets or to move them to a cold wallet
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Ensures the entry belongs to the signer and to the given raffle
+/// 2. Only allows transfers while the raffle is Open and before its end time, so the
+///    owner of the winning entry is settled once the raffle starts drawing
+/// 3. Rejects transfers once the raffle's holders were snapshotted, since the snapshot
+///    lets holders be refunded by proof regardless of their current balance
+/// 4. Rejects transfers to the signer itself
+/// 5. Applies the raffle's wallet limit to the recipient's new balance
+/// 6. Rejects region and age restricted raffles, whose holders must present their own
+///    attestations, raffles splitting their prize among top holders, whose
+///    leaderboard assumes balances only grow, and raffles issuing ticket receipts,
+///    whose entries change hands by transferring the receipt
+/// 7. In gated raffles, requires the recipient to have a ticket balance already, which
+///    init_ticket_balance only creates for wallets meeting the gate
+/// 8. Ensures the previous entry, if given, is the one linking to the transferred entry
+///
+/// # Account Validations
+/// * Raffle - Must be in Open state, before its end time and not snapshotted
+/// * Entry - Existing PDA owned by the signer, re-derived from its stored seed
+/// * TicketBalance - The signer's ticket balance PDA
+/// * RecipientTicketBalance - PDA with seeds ["ticket_balance", raffle_key, recipient],
+///   created if the recipient has none yet
+/// * PreviousEntry - Optional, the signer's entry linking to the transferred entry,
+///   required unless the transferred entry is the signer's most recent one
+///
+/// # Implementation Notes
+/// - Moves the entry's tickets, along with their share of the amount spent, of the
+///   pricing tier discount and of the campaign subsidy, so a refund of the recipient
+///   returns what was paid for them. A recipient subsidized by a different campaign
+///   can't receive subsidized tickets
+/// - Unlinks the entry from the signer's list of entries and prepends it to the
+///   recipient's
+/// - The signer pays the rent of the recipient's ticket balance, if it is created
+/// - Ticket numbers are unchanged, the entry keeps its tickets and seed
+pub fn transfer_entry(ctx: Context<TransferEntry>) -> Result<()> {
+    let raffle = &mut ctx.accounts.raffle;
+    require!(
+        raffle.allowed_regions == 0,
+        RaffleError::RegionAttestationRequired
+    );
+    require!(!raffle.age_restricted, RaffleError::AgeVerificationRequired);
+    require!(
+        raffle.top_holders.is_none() && !raffle.ticket_receipts,
+        RaffleError::EntryNotTransferable
+    );
+
+    let recipient = ctx.accounts.recipient.key();
+    require_keys_neq!(
+        recipient,
+        ctx.accounts.signer.key(),
+        RaffleError::InvalidTransferRecipient
+    );
+
+    // Gated raffles only admit wallets that created their ticket balance through the gate
+    let recipient_ticket_balance_info = ctx.accounts.recipient_ticket_balance.to_account_info();
+    require!(
+        raffle.gate.is_none() || !recipient_ticket_balance_info.data_is_empty(),
+        RaffleError::TicketBalanceNotInitialized
+    );
+    let mut recipient_ticket_balance = load_ticket_balance(
+        &recipient_ticket_balance_info,
+        &ctx.accounts.signer,
+        &ctx.accounts.system_program,
+        raffle.key(),
+        recipient,
+    )?;
+
+    let entry = &mut ctx.accounts.entry;
+    let ticket_count = entry.ticket_count;
+    validate_wallet_limit(raffle, recipient_ticket_balance.ticket_count, ticket_count)?;
+
+    // Unlink the entry from the signer's entries
+    let ticket_balance = &mut ctx.accounts.ticket_balance;
+    if ticket_balance.head_entry == Some(entry.key()) {
+        ticket_balance.head_entry = entry.next_entry;
+    } else {
+        let previous_entry = ctx
+            .accounts
+            .previous_entry
+            .as_mut()
+            .ok_or(RaffleError::InvalidPreviousEntry)?;
+        require!(
+            previous_entry.next_entry == Some(entry.key()),
+            RaffleError::InvalidPreviousEntry
+        );
+        previous_entry.next_entry = entry.next_entry;
+    }
+
+    // Move the entry's share of the amount spent, of the tier discount and of the subsidy
+    let spent_share = entry_share(
+        ticket_balance.total_spent,
+        ticket_count,
+        ticket_balance.ticket_count,
+    )?;
+    let discount_share = entry_share(
+        ticket_balance.tier_discount,
+        ticket_count,
+        ticket_balance.ticket_count,
+    )?;
+    let subsidy_share = entry_share(
+        ticket_balance.campaign_subsidy,
+        ticket_count,
+        ticket_balance.ticket_count,
+    )?;
+    ticket_balance.ticket_count = checked_sub(ticket_balance.ticket_count, ticket_count)?;
+    ticket_balance.total_spent = checked_sub(ticket_balance.total_spent, spent_share)?;
+    ticket_balance.tier_discount = checked_sub(ticket_balance.tier_discount, discount_share)?;
+    ticket_balance.campaign_subsidy = checked_sub(ticket_balance.campaign_subsidy, subsidy_share)?;
+    if ticket_balance.ticket_count == 0 {
+        raffle.participant_count = checked_sub(raffle.participant_count, 1)?;
+    }
+
+    if recipient_ticket_balance.ticket_count == 0 {
+        raffle.participant_count = checked_add(raffle.participant_count, 1)?;
+    }
+    recipient_ticket_balance.ticket_count =
+        checked_add(recipient_ticket_balance.ticket_count, ticket_count)?;
+    recipient_ticket_balance.total_spent =
+        checked_add(recipient_ticket_balance.total_spent, spent_share)?;
+    recipient_ticket_balance.tier_discount =
+        checked_add(recipient_ticket_balance.tier_discount, discount_share)?;
+    if subsidy_share > 0 {
+        let subsidy_campaign = ticket_balance
+            .subsidy_campaign
+            .ok_or(RaffleError::CampaignMismatch)?;
+        require!(
+            recipient_ticket_balance
+                .subsidy_campaign
+                .unwrap_or(subsidy_campaign)
+                == subsidy_campaign,
+            RaffleError::CampaignMismatch
+        );
+        recipient_ticket_balance.subsidy_campaign = Some(subsidy_campaign);
+        recipient_ticket_balance.campaign_subsidy =
+            checked_add(recipient_ticket_balance.campaign_subsidy, subsidy_share)?;
+    }
+
+    // Prepend the entry to the recipient's entries
+    entry.owner = recipient;
+    entry.next_entry = recipient_ticket_balance.head_entry;
+    recipient_ticket_balance.head_entry = Some(entry.key());
+    recipient_ticket_balance
+        .try_serialize(&mut &mut recipient_ticket_balance_info.try_borrow_mut_data()?[..])?;
+
+    let event_seq = raffle.next_event_seq()?;
+    emit!(EntryTransferred {
+        raffle: raffle.key(),
+        entry: entry.key(),
+        from: ctx.accounts.signer.key(),
+        to: recipient,
+        ticket_count,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+/// Share of `amount`, accumulated over `total_tickets`, attributable to `ticket_count`
+/// of them, rounded down
+fn entry_share(amount: u64, ticket_count: u64, total_tickets: u64) -> Result<u64> {
+    if ticket_count == total_tickets {
+        return Ok(amount);
+    }
+    let share = (amount as u128)
+        .checked_mul(ticket_count as u128)
+        .ok_or(RaffleError::Overflow)?
+        .checked_div(total_tickets as u128)
+        .ok_or(RaffleError::Overflow)?;
+    u64::try_from(share).map_err(|_| RaffleError::Overflow.into())
+}
+
+#[derive(Accounts)]
+pub struct TransferEntry<'info> {
+    /// The raffle the entry belongs to, must be in Open state, before its end time and
+    /// not snapshotted
+    #[account(
+        mut,
+        constraint = raffle.raffle_state == RaffleState::Open @ RaffleError::RaffleNotOpen,
+        constraint = now(&config)? < raffle.end_time @ RaffleError::RaffleEnded,
+        constraint = raffle.snapshot_root.is_none() @ RaffleError::SnapshotAlreadyRecorded,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    /// The transferred entry
+    /// PDA with seeds ["entry", raffle_key, entry_seed]
+    #[account(
+        mut,
+        seeds = [
+            b"entry",
+            raffle.key().as_ref(),
+            entry.seed.as_ref()
+        ],
+        bump,
+        has_one = raffle @ RaffleError::EntryRaffleMismatch,
+        constraint = entry.owner == signer.key() @ RaffleError::OwnerMismatch,
+    )]
+    pub entry: Account<'info, Entry>,
+
+    /// The signer's entry linking to the transferred entry, required unless the
+    /// transferred entry is the signer's most recent one. Validated in the instruction.
+    #[account(
+        mut,
+        has_one = raffle @ RaffleError::EntryRaffleMismatch,
+        constraint = previous_entry.owner == signer.key() @ RaffleError::OwnerMismatch,
+    )]
+    pub previous_entry: Option<Account<'info, Entry>>,
+
+    /// The signer's ticket balance
+    /// PDA with seeds ["ticket_balance", raffle_key, signer_key]
+    #[account(
+        mut,
+        seeds = [
+            b"ticket_balance",
+            raffle.key().as_ref(),
+            signer.key().as_ref()
+        ],
+        bump = ticket_balance.bump
+    )]
+    pub ticket_balance: Account<'info, TicketBalance>,
+
+    /// CHECK: Any wallet can receive the entry
+    pub recipient: UncheckedAccount<'info>,
+
+    /// CHECK: The recipient's ticket balance PDA with seeds ["ticket_balance", raffle_key,
+    /// recipient], created if the recipient has none yet. Validated in the instruction.
+    #[account(mut)]
+    pub recipient_ticket_balance: UncheckedAccount<'info>,
+
+    /// The entry owner, who pays the rent of the recipient's ticket balance
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Required for creating the recipient's ticket balance
+    pub system_program: Program<'info, System>,
+
+    /// Program config, checked for the pause switch
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ RaffleError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    math::{checked_add, ticket_cost},
+    state::{Config, Raffle, RaffleState},
+};
+
+/// Event emitted when the ticket supply of a raffle is changed
+#[event]
+pub struct MaxTicketsUpdated {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The previous maximum number of tickets
+    pub previous_max_tickets: Option<u64>,
+    /// The new maximum number of tickets
+    pub max_tickets: u64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to change the maximum number of tickets of an open raffle, e.g. to add
+/// supply to a raffle selling out faster than expected
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `max_tickets` - The new maximum number of tickets
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Only the management authority can change the supply
+/// 2. Ensures the new maximum covers all sold and reserved tickets, so no purchase is
+///    invalidated
+/// 3. Ensures the new maximum keeps the ticket or revenue threshold reachable
+///
+/// # Account Validations
+/// * Raffle - Must be in Open state
+/// * Config - PDA storing the management authority
+pub fn update_max_tickets(ctx: Context<UpdateMaxTickets>, max_tickets: u64) -> Result<()> {
+    let raffle = &mut ctx.accounts.raffle;
+
+    let held_tickets = checked_add(raffle.current_tickets, raffle.reserved_tickets)?;
+    require!(
+        max_tickets >= held_tickets && max_tickets >= raffle.min_tickets,
+        RaffleError::MaxTicketsTooLow
+    );
+    if let Some(min_revenue) = raffle.min_revenue {
+        require!(
+            ticket_cost(max_tickets, raffle.ticket_price)? >= min_revenue,
+            RaffleError::MaxTicketsTooLow
+        );
+    }
+
+    let previous_max_tickets = raffle.max_tickets;
+    raffle.max_tickets = Some(max_tickets);
+
+    let event_seq = raffle.next_event_seq()?;
+    emit!(MaxTicketsUpdated {
+        raffle: raffle.key(),
+        previous_max_tickets,
+        max_tickets,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateMaxTickets<'info> {
+    #[account(
+        mut,
+        constraint = raffle.raffle_state == RaffleState::Open @ RaffleError::RaffleNotOpen,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    pub management_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    instructions::create_raffle::{arweave_tx_id, validate_raffle_params, RaffleParams},
+    state::{require_raffle_manager, Config, CreatorRole, Raffle, RaffleState},
+    time::now,
+};
+
+/// Event emitted when the parameters of a raffle are updated before its first sale
+#[event]
+pub struct RaffleUpdated {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The metadata URI for the raffle, or empty if the config's events are compact
+    pub metadata_uri: String,
+    /// Unix timestamp when the raffle ends
+    pub end_time: i64,
+    /// Minimum tickets that must be sold for the raffle to be drawn
+    pub min_tickets: u64,
+    /// Maximum tickets that can be sold, if limited
+    pub max_tickets: Option<u64>,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction to correct the metadata URI, end time or ticket limits of a raffle before
+/// any ticket is sold
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `metadata_uri` - URI pointing to the raffle's metadata (max 256 chars)
+/// * `end_time` - Unix timestamp when the raffle ends
+/// * `min_tickets` - Minimum tickets that must be sold for the raffle to be drawn
+/// * `max_tickets` - Optional maximum number of tickets that can be sold
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Only the management authority, or the creator of a raffle created through a
+///    creator role, can update a raffle
+/// 2. Ensures no tickets have been sold or reserved, so buyers only ever enter raffles
+///    on the terms they saw
+/// 3. Applies all checks of create_raffle to the updated parameters, against the
+///    current config and time
+/// 4. Ensures a funding deadline set on the raffle stays before the new end time
+///
+/// # Account Validations
+/// * Raffle - Must be in Open state without any tickets sold or reserved
+/// * Config - PDA storing the management authority
+/// * CreatorRole - Optional, the signer's creator role, required unless the signer is
+///   the management authority
+///
+/// # Implementation Notes
+/// - Every parameter is replaced, so unchanged values must be passed again
+/// - The stored Arweave transaction id is taken from the new metadata URI, clearing a
+///   mirror set with set_arweave_mirror for the previous metadata
+pub fn update_raffle(
+    ctx: Context<UpdateRaffle>,
+    metadata_uri: String,
+    end_time: i64,
+    min_tickets: u64,
+    max_tickets: Option<u64>,
+) -> Result<()> {
+    require_raffle_manager(
+        &ctx.accounts.config,
+        &ctx.accounts.authority,
+        ctx.accounts.raffle.creator,
+        ctx.accounts.creator_role.as_ref(),
+    )?;
+
+    let raffle = &mut ctx.accounts.raffle;
+    let params = RaffleParams {
+        metadata_uri,
+        end_time,
+        min_tickets,
+        max_tickets,
+        ..RaffleParams::from_raffle(raffle)
+    };
+    validate_raffle_params(&params, &ctx.accounts.config, now(&ctx.accounts.config)?)?;
+    // The funding deadline must remain ahead of the moved end time
+    if let Some(funding_deadline) = raffle.funding_deadline {
+        require!(
+            funding_deadline < end_time,
+            RaffleError::InvalidFundingDeadline
+        );
+    }
+
+    raffle.arweave_tx_id = arweave_tx_id(&params.metadata_uri);
+    raffle.metadata_uri = params.metadata_uri;
+    raffle.end_time = end_time;
+    raffle.min_tickets = min_tickets;
+    raffle.max_tickets = max_tickets;
+
+    let event_seq = raffle.next_event_seq()?;
+    emit!(RaffleUpdated {
+        raffle: raffle.key(),
+        metadata_uri: ctx.accounts.config.event_text(raffle.metadata_uri.clone()),
+        end_time,
+        min_tickets,
+        max_tickets,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateRaffle<'info> {
+    /// The raffle being updated, before its first ticket sale
+    #[account(
+        mut,
+        constraint = raffle.raffle_state == RaffleState::Open @ RaffleError::RaffleNotOpen,
+        constraint = raffle.current_tickets == 0 && raffle.reserved_tickets == 0
+            @ RaffleError::TicketsAlreadySold,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    /// The management authority, or the creator of the raffle
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Creator role of the signer, required unless the signer is the management
+    /// authority. Validated against the signer and the raffle in the instruction.
+    pub creator_role: Option<Account<'info, CreatorRole>>,
+}
@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    instructions::submit_winner_data::validate_winner_data,
+    state::{Config, Raffle, RaffleState, WinnerData},
+    time::now,
+};
+
+/// Event emitted when a winner overwrites their submitted data
+#[event]
+pub struct WinnerDataUpdated {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// Layout version of the submitted data
+    pub format_version: u8,
+    /// How the submitted data was encrypted
+    pub encryption_scheme: u8,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Instruction for a raffle winner to correct their submitted contact information, e.g.
+/// after a typo, until it is locked
+///
+/// # Arguments
+/// * `ctx` - The context object containing all required accounts
+/// * `data` - The winner's encrypted contact information
+/// * `format_version` - Layout version of `data`
+/// * `encryption_scheme` - How `data` was encrypted
+///
+/// # Security Considerations
+/// The instruction performs several critical checks:
+/// 1. Validates the raffle is in Claimed state
+/// 2. Ensures the signer is the winner, as only the winner's data PDA exists
+/// 3. Ensures the data is not locked and the edit window since the first submission
+///    has not passed
+/// 4. Applies the same length and format checks as the first submission
+///
+/// # Account Validations
+/// * Raffle - Must be in Claimed state
+/// * WinnerData - PDA with seeds ["winner_data", raffle_key, signer]
+/// * Config - PDA storing the supported winner data formats
+pub fn update_winner_data(
+    ctx: Context<UpdateWinnerData>,
+    data: String,
+    format_version: u8,
+    encryption_scheme: u8,
+) -> Result<()> {
+    validate_winner_data(
+        &ctx.accounts.config,
+        &data,
+        format_version,
+        encryption_scheme,
+    )?;
+    require!(
+        ctx.accounts
+            .winner_data
+            .editable(now(&ctx.accounts.config)?),
+        RaffleError::WinnerDataLocked
+    );
+
+    let winner_data = &mut ctx.accounts.winner_data;
+    winner_data.data = data;
+    winner_data.format_version = format_version;
+    winner_data.encryption_scheme = encryption_scheme;
+
+    let event_seq = ctx.accounts.raffle.next_event_seq()?;
+    emit!(WinnerDataUpdated {
+        raffle: ctx.accounts.raffle.key(),
+        format_version,
+        encryption_scheme,
+        event_seq,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateWinnerData<'info> {
+    #[account(
+        mut,
+        constraint = raffle.raffle_state == RaffleState::Claimed @ RaffleError::RaffleNotClaimed,
+    )]
+    pub raffle: Account<'info, Raffle>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"winner_data",
+            raffle.key().as_ref(),
+            signer.key().as_ref(),
+        ],
+        bump
+    )]
+    pub winner_data: Account<'info, WinnerData>,
+
+    /// The winner correcting their contact information
+    pub signer: Signer<'info>,
+
+    /// The config account storing the supported winner data formats
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
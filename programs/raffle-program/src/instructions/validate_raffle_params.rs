@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::{
+    instructions::create_raffle::{raffle_param_violations, RaffleParams},
+    state::{raffle::PricingTier, Config, RandomnessMode},
+    time::now,
+};
+
+/// Outcome of `validate_raffle_params`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RaffleParamsValidation {
+    /// Whether create_raffle would accept the parameters
+    pub valid: bool,
+    /// Codes of the errors of every rule the parameters violate, in the order
+    /// create_raffle checks them. create_raffle fails with the first of them.
+    pub error_codes: Vec<u32>,
+}
+
+/// Runs the parameter validations of create_raffle without creating any accounts and
+/// returns the rules the parameters violate via return data, so forms can be validated
+/// against the exact on-chain rules with a simulated call.
+///
+/// # Arguments
+/// * `ctx` - The context object containing:
+///   - `config`: The config, whose metadata host allowlist applies to HTTPS URIs
+///   - `payment_mint`: Optional SPL token mint tickets would be paid in, which lifts the
+///     lamport price bounds
+/// * The raffle parameters, as passed to `create_raffle`
+///
+/// The parameters are validated against the current time, as create_raffle would if it
+/// were sent in the same slot. Checks depending on the signer, such as the limits of a
+/// creator role, and on its funds are not run.
+#[allow(clippy::too_many_arguments)]
+pub fn validate_raffle_params(
+    ctx: Context<ValidateRaffleParams>,
+    metadata_uri: String,
+    ticket_price: u64,
+    end_time: i64,
+    min_tickets: u64,
+    max_tickets: Option<u64>,
+    max_per_purchase: Option<u64>,
+    anonymize_winner: bool,
+    min_revenue: Option<u64>,
+    collectible: bool,
+    cash_prize_bps: Option<u16>,
+    top_holders: Option<u8>,
+    min_unique_buyers: Option<u64>,
+    max_tickets_per_wallet: Option<u64>,
+    pricing_tiers: Option<Vec<PricingTier>>,
+    prize_value_usd_cents: Option<u64>,
+    ticket_count_display_cap: Option<u64>,
+) -> Result<RaffleParamsValidation> {
+    let params = RaffleParams {
+        metadata_uri,
+        ticket_price,
+        end_time,
+        min_tickets,
+        max_tickets,
+        max_per_purchase,
+        anonymize_winner,
+        min_revenue,
+        collectible,
+        cash_prize_bps,
+        top_holders,
+        allowed_regions: 0,
+        age_restricted: false,
+        draw_authority: None,
+        priority_min_tier: 0,
+        priority_until: 0,
+        min_unique_buyers,
+        payment_mint: ctx.accounts.payment_mint.as_ref().map(|mint| mint.key()),
+        randomness_mode: RandomnessMode::SlotHashes,
+        winner_count: 1,
+        max_tickets_per_wallet,
+        gate: None,
+        deferred_numbering: false,
+        pricing_tiers: pricing_tiers.unwrap_or_default(),
+        prize_value_usd_cents,
+        ticket_count_display_cap,
+    };
+
+    let error_codes: Vec<u32> = raffle_param_violations(
+        &params,
+        &ctx.accounts.config,
+        now(&ctx.accounts.config)?,
+    )
+    .into_iter()
+    .map(u32::from)
+    .collect();
+    Ok(RaffleParamsValidation {
+        valid: error_codes.is_empty(),
+        error_codes,
+    })
+}
+
+/// Accounts required for the validate_raffle_params instruction
+#[derive(Accounts)]
+pub struct ValidateRaffleParams<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Optional SPL token mint tickets would be paid in, instead of lamports
+    pub payment_mint: Option<Account<'info, Mint>>,
+}
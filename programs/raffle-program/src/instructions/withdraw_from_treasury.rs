@@ -2,62 +2,333 @@ use anchor_lang::prelude::*;
 
 use crate::{
     error::RaffleError,
-    state::{Config, Raffle, Treasury, TREASURY_ACCOUNT_SIZE},
+    math::{bps_of, checked_sub},
+    state::{Config, Raffle, RaffleState, ReferralStats, Treasury},
+    treasury::{available_lamports, token_accounts, LamportVault, TokenVault, TreasuryVault},
 };
+use anchor_spl::token::{Token, TokenAccount};
 
 /// Event emitted when treasury funds are withdrawn
 #[event]
 pub struct TreasuryWithdrawn {
     /// The pubkey of the raffle
     pub raffle: Pubkey,
-    /// Amount withdrawn in lamports
+    /// Amount paid to `recipient` in lamports, or in base units of the raffle's payment
+    /// mint
     pub amount: u64,
+    /// The account the withdrawal was paid to
+    pub recipient: Pubkey,
+    /// Platform fee deducted from the withdrawal and paid to `fee_recipient`, in the unit
+    /// of `amount`
+    pub fee_amount: u64,
+    /// The account the platform fee was paid to, if one was charged
+    pub fee_recipient: Option<Pubkey>,
+    /// Lamports left in the treasury after the withdrawal, including its rent reserve, or
+    /// tokens left in its token account if the raffle is paid in an SPL token
+    pub remaining_balance: u64,
+    /// The raffle state at the time of the withdrawal
+    pub raffle_state: RaffleState,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
 }
 
-/// Instruction to withdraw all funds from a raffle's treasury to the payout authority
+/// Event emitted when a referrer is paid its referral fees
+#[event]
+pub struct ReferralFeePaid {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// The referrer the fees were paid to
+    pub referrer: Pubkey,
+    /// Lamports paid to the referrer
+    pub amount: u64,
+    /// Sequence number of this event within the raffle
+    pub event_seq: u64,
+}
+
+/// Amount of a raffle's treasury the payout authority may withdraw, before limiting it to
+/// the treasury's balance. Only the retained refund fees of expired raffles are
+/// withdrawable, and nothing of cancelled or cash prize raffles. Unpaid referral fees
+/// are reserved for the referrers
+pub(crate) fn withdrawable_amount(
+    raffle: &Raffle,
+    treasury: &Treasury,
+    config: &Config,
+) -> Result<u64> {
+    require!(
+        raffle.cash_prize_bps.is_none(),
+        RaffleError::CashPrizeRaffle
+    );
+    // The funds of a cancelled raffle are refunded to ticket holders in full
+    require!(
+        raffle.raffle_state != RaffleState::Cancelled,
+        RaffleError::RaffleIsCancelled
+    );
+    if raffle.raffle_state == RaffleState::Expired {
+        // The funds of an expired raffle are owed to ticket holders, except for the
+        // refund fees retained so far
+        return checked_sub(treasury.total_refund_fees, treasury.total_withdrawn);
+    }
+
+    // Verify that the threshold has been met
+    require!(raffle.threshold_met()?, RaffleError::ThresholdNotMet);
+    // Ticket revenue is only released once the winner's prize is held in escrow
+    if config.require_prize_deposit {
+        require!(raffle.prize_escrowed, RaffleError::PrizeNotDeposited);
+    }
+    // Referral fees accrued by the sales are reserved for the referrers
+    checked_sub(treasury.outstanding()?, treasury.unpaid_referral_fees()?)
+}
+
+/// Pays the unpaid referral fees of the referrers passed as pairs of remaining accounts,
+/// each a referral stats account of the raffle followed by its referrer
+fn pay_referral_fees<'info>(
+    raffle: &mut Account<'info, Raffle>,
+    treasury: &mut Account<'info, Treasury>,
+    system_program: &Program<'info, System>,
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<()> {
+    require!(
+        remaining_accounts.len().is_multiple_of(2),
+        RaffleError::InvalidReferralStats
+    );
+    for accounts in remaining_accounts.chunks(2) {
+        let mut referral_stats = Account::<ReferralStats>::try_from(&accounts[0])?;
+        let referrer = &accounts[1];
+        require!(
+            referral_stats.raffle == raffle.key() && referral_stats.referrer == referrer.key(),
+            RaffleError::InvalidReferralStats
+        );
+
+        let amount = referral_stats.unpaid_fees()?;
+        if amount == 0 {
+            continue;
+        }
+        LamportVault::new(treasury, system_program).pay_referral_fee(referrer, amount)?;
+        referral_stats.fees_paid = referral_stats.fees_owed;
+        referral_stats.exit(&crate::ID)?;
+
+        let event_seq = raffle.next_event_seq()?;
+        emit!(ReferralFeePaid {
+            raffle: raffle.key(),
+            referrer: referrer.key(),
+            amount,
+            event_seq,
+        });
+    }
+
+    Ok(())
+}
+
+/// Account withdrawals are paid to: an approved payout destination if the config
+/// approves any, so leaked keys can't redirect the funds, or the payout authority
+pub(crate) fn payout_recipient<'info>(
+    config: &Config,
+    payout_authority: &SystemAccount<'info>,
+    destination: Option<&SystemAccount<'info>>,
+) -> Result<AccountInfo<'info>> {
+    if config.payout_destinations.is_empty() {
+        return Ok(payout_authority.to_account_info());
+    }
+    let destination = destination.ok_or(RaffleError::PayoutDestinationNotAllowed)?;
+    require!(
+        config.payout_destinations.contains(&destination.key()),
+        RaffleError::PayoutDestinationNotAllowed
+    );
+    Ok(destination.to_account_info())
+}
+
+/// Splits a withdrawal of `amount` into the share paid to the payout recipient and the
+/// platform fee charged by the config, or the raffle's fee override
+pub(crate) fn split_platform_fee(raffle: &Raffle, config: &Config, amount: u64) -> Result<(u64, u64)> {
+    let fee_amount = bps_of(amount, config.platform_fee_bps(raffle))?;
+    Ok((checked_sub(amount, fee_amount)?, fee_amount))
+}
+
+/// Account the platform fee of a withdrawal is paid to, which must be the config's fee
+/// recipient
+pub(crate) fn fee_recipient<'info>(
+    config: &Config,
+    fee_recipient: Option<&SystemAccount<'info>>,
+) -> Result<AccountInfo<'info>> {
+    let fee_recipient = fee_recipient.ok_or(RaffleError::InvalidFeeRecipient)?;
+    require!(
+        config.fee_recipient == Some(fee_recipient.key()),
+        RaffleError::InvalidFeeRecipient
+    );
+    Ok(fee_recipient.to_account_info())
+}
+
+/// Instruction to withdraw all funds from a raffle's treasury to the payout authority, or
+/// to an approved payout destination if the config approves any
 ///
 /// # Security Considerations
 /// The instruction performs several critical checks:
-/// 1. Validates the ticket threshold has been met
-/// 2. Verifies the signer is the management authority
-/// 3. Ensures treasury account matches the one stored in raffle
-/// 4. Validates treasury has funds to withdraw
+/// 1. Validates the ticket or revenue threshold has been met. The funds of expired
+///    raffles are owed to ticket holders, so only their retained refund fees can be
+///    withdrawn. Cancelled raffles refund everything, so nothing can be withdrawn
+/// 2. Rejects cash prize raffles, whose treasury is split with the winner by pay_winner
+/// 3. Verifies the signer is the management authority
+/// 4. Ensures treasury account matches the one stored in raffle
+/// 5. Validates treasury has funds to withdraw
+/// 6. Limits the withdrawal to collected funds that were not yet refunded or withdrawn
+/// 7. If the config approves payout destinations, ensures the withdrawal is paid to one
+///    of them
+/// 8. If the raffle is paid in an SPL token, ensures the withdrawal is paid from the
+///    treasury's associated token account into a token account owned by the recipient
+/// 9. If the config requires prize deposits, ensures the prize was escrowed with
+///    deposit_prize before ticket revenue is withdrawn
+/// 10. If a platform fee is charged, ensures it is paid to the config's fee recipient,
+///     into a token account it owns if the raffle is paid in an SPL token
+/// 11. Fails with ProgramPaused while the config's pause switch is on
+/// 12. If the config has a withdrawal approver, ensures withdrawals above its threshold
+///     were approved with approve_withdrawal
+/// 13. Ensures each referral stats account passed belongs to the raffle and is
+///     followed by its referrer, and never pays a referrer more than its unpaid fees
 ///
 /// # Account Validations
 /// * Raffle - Must be in Drawn state
 /// * Signer - Must be the management authority
 /// * Treasury - Must match raffle's treasury and use proper PDA seeds
-pub fn withdraw_from_treasury(ctx: Context<WithdrawFromTreasury>) -> Result<()> {
-    // Verify that the threshold has been met
-    require!(
-        ctx.accounts.raffle.current_tickets >= ctx.accounts.raffle.min_tickets,
-        RaffleError::ThresholdNotMet,
-    );
+/// * Destination - Required if the config approves payout destinations, must be one of
+///   them. Otherwise the withdrawal is paid to the payout authority
+/// * RecipientTokenAccount - Optional, token account of the payout authority or
+///   destination, required if the raffle is paid in an SPL token
+/// * TreasuryTokenAccount - Optional, required if the raffle is paid in an SPL token
+/// * TokenProgram - Optional, required if the raffle is paid in an SPL token
+/// * FeeRecipient - Optional, the config's fee recipient, required if a platform fee is
+///   charged
+/// * FeeRecipientTokenAccount - Optional, token account of the fee recipient, required
+///   if a platform fee is charged and the raffle is paid in an SPL token
+/// * Remaining accounts - Pairs of a referral stats account of the raffle and its
+///   referrer, paid their unpaid referral fees
+///
+/// # Implementation Notes
+/// - The platform fee is the config's `fee_bps`, or the raffle's `fee_bps_override`, of
+///   the withdrawn amount, rounded down. No fee is charged while the config has no fee
+///   recipient
+/// - The payout and the fee are paid in the same instruction, so neither can be
+///   withdrawn without the other
+/// - The approval threshold applies to the withdrawn amount including the fee. An
+///   approval is consumed by the next withdrawal from the treasury, whatever its amount
+/// - Referral fees of referrers that are not passed stay reserved in the treasury until
+///   a later withdrawal pays them. Referral fees of expired raffles are never paid, as
+///   the referred purchases are refunded
+pub fn withdraw_from_treasury<'info>(
+    ctx: Context<'_, '_, 'info, 'info, WithdrawFromTreasury<'info>>,
+) -> Result<()> {
+    let withdrawable = withdrawable_amount(
+        &ctx.accounts.raffle,
+        &ctx.accounts.treasury,
+        &ctx.accounts.config,
+    )?;
+    if ctx.accounts.raffle.raffle_state == RaffleState::Expired {
+        require!(withdrawable > 0, RaffleError::RaffleIsExpired);
+    } else {
+        pay_referral_fees(
+            &mut ctx.accounts.raffle,
+            &mut ctx.accounts.treasury,
+            &ctx.accounts.system_program,
+            ctx.remaining_accounts,
+        )?;
+    }
     // Verify treasury account matches the one stored in raffle
     require!(
         ctx.accounts.treasury.key() == ctx.accounts.raffle.treasury,
         RaffleError::InvalidTreasury
     );
-    let treasury_account = ctx.accounts.treasury.to_account_info();
-    let payout_authority = ctx.accounts.payout_authority.to_account_info();
+    // Token payments are held in the treasury's token account, which has its own balance.
+    // Lamports are limited to the recognized balance, which excludes lamports sent to the
+    // treasury directly, and to the lamports above the rent reserve of the treasury's
+    // actual size
+    let available = match &ctx.accounts.treasury_token_account {
+        Some(vault) if ctx.accounts.raffle.payment_mint.is_some() => vault.amount,
+        _ => available_lamports(&ctx.accounts.treasury)?,
+    };
+
+    // Only pay out funds collected from ticket sales, so funds sent directly to the
+    // treasury can't inflate the withdrawal, and track it for the solvency checks
+    let amount_to_withdraw = available
+        .min(ctx.accounts.treasury.outstanding()?)
+        .min(withdrawable);
+
+    // Large withdrawals need the withdrawal approver's co-signature
+    ctx.accounts
+        .treasury
+        .consume_withdrawal_approval(&ctx.accounts.config, amount_to_withdraw)?;
 
-    // Get total balance including rent
-    let treasury_balance = treasury_account.lamports();
-    require!(treasury_balance > 0, RaffleError::InsufficientFunds);
+    let recipient = payout_recipient(
+        &ctx.accounts.config,
+        &ctx.accounts.payout_authority,
+        ctx.accounts.destination.as_ref(),
+    )?;
 
-    // Get rent exempt balance to make sure we don't deduct ALL lamports, as the raffle might still be open
-    let rent_lamports = (Rent::get()?).minimum_balance(TREASURY_ACCOUNT_SIZE);
-    let lamports_to_withdraw = treasury_balance - rent_lamports;
+    // Split off the platform fee, paid alongside the payout
+    let (payout_amount, fee_amount) = split_platform_fee(
+        &ctx.accounts.raffle,
+        &ctx.accounts.config,
+        amount_to_withdraw,
+    )?;
+    let fee_recipient = if fee_amount > 0 {
+        Some(fee_recipient(
+            &ctx.accounts.config,
+            ctx.accounts.fee_recipient.as_ref(),
+        )?)
+    } else {
+        None
+    };
 
-    // Transfer lamports by directly deducting from treasury and adding to payout_authority.
-    // This only works because the treasury is a PDA owned by our program.
-    treasury_account.sub_lamports(lamports_to_withdraw)?;
-    payout_authority.add_lamports(lamports_to_withdraw)?;
+    let remaining_balance = if ctx.accounts.raffle.payment_mint.is_some() {
+        let (vault, token_program) = token_accounts(
+            &ctx.accounts.raffle,
+            &ctx.accounts.treasury,
+            ctx.accounts.treasury_token_account.as_mut(),
+            ctx.accounts.token_program.as_ref(),
+        )?;
+        let recipient_token_account = ctx
+            .accounts
+            .recipient_token_account
+            .as_ref()
+            .ok_or(RaffleError::InvalidPaymentMint)?;
+        require!(
+            recipient_token_account.owner == recipient.key(),
+            RaffleError::PayoutDestinationNotAllowed
+        );
+        let mut token_vault = TokenVault::new(&mut ctx.accounts.treasury, vault, token_program);
+        token_vault.withdraw(&recipient_token_account.to_account_info(), payout_amount)?;
+        if let Some(fee_recipient) = &fee_recipient {
+            let fee_recipient_token_account = ctx
+                .accounts
+                .fee_recipient_token_account
+                .as_ref()
+                .ok_or(RaffleError::InvalidFeeRecipient)?;
+            require!(
+                fee_recipient_token_account.owner == fee_recipient.key(),
+                RaffleError::InvalidFeeRecipient
+            );
+            token_vault.withdraw(&fee_recipient_token_account.to_account_info(), fee_amount)?;
+        }
+        vault.amount
+    } else {
+        let mut lamport_vault =
+            LamportVault::new(&mut ctx.accounts.treasury, &ctx.accounts.system_program);
+        lamport_vault.withdraw(&recipient, payout_amount)?;
+        if let Some(fee_recipient) = &fee_recipient {
+            lamport_vault.withdraw(fee_recipient, fee_amount)?;
+        }
+        ctx.accounts.treasury.to_account_info().lamports()
+    };
 
     // Emit the treasury withdrawn event
+    let event_seq = ctx.accounts.raffle.next_event_seq()?;
     emit!(TreasuryWithdrawn {
         raffle: ctx.accounts.raffle.key(),
-        amount: lamports_to_withdraw,
+        amount: payout_amount,
+        recipient: recipient.key(),
+        fee_amount,
+        fee_recipient: fee_recipient.map(|fee_recipient| fee_recipient.key()),
+        remaining_balance,
+        raffle_state: ctx.accounts.raffle.raffle_state.clone(),
+        event_seq,
     });
 
     Ok(())
@@ -65,6 +336,7 @@ pub fn withdraw_from_treasury(ctx: Context<WithdrawFromTreasury>) -> Result<()>
 
 #[derive(Accounts)]
 pub struct WithdrawFromTreasury<'info> {
+    #[account(mut)]
     pub raffle: Account<'info, Raffle>,
 
     #[account(mut)]
@@ -85,7 +357,8 @@ pub struct WithdrawFromTreasury<'info> {
         seeds = [b"config"],
         bump = config.bump,
         has_one = management_authority @ RaffleError::NotProgramManagementAuthority,
-        has_one = payout_authority @ RaffleError::NotPayoutAuthority
+        has_one = payout_authority @ RaffleError::NotPayoutAuthority,
+        constraint = !config.paused @ RaffleError::ProgramPaused,
     )]
     pub config: Account<'info, Config>,
 
@@ -93,4 +366,33 @@ pub struct WithdrawFromTreasury<'info> {
 
     #[account(mut)]
     pub payout_authority: SystemAccount<'info>,
+
+    /// Approved payout destination the withdrawal is paid to, required if the config
+    /// approves payout destinations
+    #[account(mut)]
+    pub destination: Option<SystemAccount<'info>>,
+
+    /// Optional token account of the payout authority or destination receiving the
+    /// withdrawal, required if the raffle is paid in an SPL token
+    #[account(mut)]
+    pub recipient_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Optional associated token account of the treasury holding the payments, required
+    /// if the raffle is paid in an SPL token. Validated against the payment mint in the
+    /// instruction.
+    #[account(mut)]
+    pub treasury_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Required if the raffle is paid in an SPL token
+    pub token_program: Option<Program<'info, Token>>,
+
+    /// The config's fee recipient the platform fee is paid to, required if a fee is
+    /// charged
+    #[account(mut)]
+    pub fee_recipient: Option<SystemAccount<'info>>,
+
+    /// Optional token account of the fee recipient receiving the platform fee, required
+    /// if a fee is charged and the raffle is paid in an SPL token
+    #[account(mut)]
+    pub fee_recipient_token_account: Option<Account<'info, TokenAccount>>,
 }
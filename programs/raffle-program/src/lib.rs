@@ -1,9 +1,21 @@
 use anchor_lang::prelude::*;
 use instructions::*;
 
+pub mod bubblegum;
 pub mod error;
 pub mod instructions;
+pub mod math;
+pub mod merkle;
+pub mod notifier;
+#[cfg(feature = "no-entrypoint")]
+pub mod pda;
+pub mod rand;
+pub mod randomness;
+pub mod receipt;
 pub mod state;
+pub mod time;
+pub mod transfer;
+pub mod treasury;
 
 declare_id!("V1RALU8Rkwxb6uc6bALeNeMgdNoMZMx4L14Dojkgy2X");
 
@@ -15,6 +27,7 @@ pub mod raffle_program {
         instructions::init_config::init_config(ctx)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn create_raffle(
         ctx: Context<CreateRaffle>,
         metadata_uri: String,
@@ -22,6 +35,17 @@ pub mod raffle_program {
         end_time: i64,
         min_tickets: u64,
         max_tickets: Option<u64>,
+        max_per_purchase: Option<u64>,
+        anonymize_winner: bool,
+        min_revenue: Option<u64>,
+        collectible: bool,
+        cash_prize_bps: Option<u16>,
+        top_holders: Option<u8>,
+        min_unique_buyers: Option<u64>,
+        max_tickets_per_wallet: Option<u64>,
+        pricing_tiers: Option<Vec<state::PricingTier>>,
+        prize_value_usd_cents: Option<u64>,
+        ticket_count_display_cap: Option<u64>,
     ) -> Result<()> {
         instructions::create_raffle::create_raffle(
             ctx,
@@ -30,42 +54,1017 @@ pub mod raffle_program {
             end_time,
             min_tickets,
             max_tickets,
+            max_per_purchase,
+            anonymize_winner,
+            min_revenue,
+            collectible,
+            cash_prize_bps,
+            top_holders,
+            min_unique_buyers,
+            max_tickets_per_wallet,
+            pricing_tiers,
+            prize_value_usd_cents,
+            ticket_count_display_cap,
         )
     }
 
+    pub fn create_raffle_with_preset(
+        ctx: Context<CreateRaffle>,
+        metadata_uri: String,
+        preset: [u8; 16],
+    ) -> Result<()> {
+        instructions::create_raffle_with_preset::create_raffle_with_preset(
+            ctx,
+            metadata_uri,
+            preset,
+        )
+    }
+
+    pub fn clone_raffle(ctx: Context<CloneRaffle>) -> Result<()> {
+        instructions::clone_raffle::clone_raffle(ctx)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn buy_tickets(
         ctx: Context<BuyTickets>,
         ticket_count: u64,
         entry_seed: [u8; 8],
+        expected_total: Option<u64>,
+        memo: Option<String>,
+        idempotency_key: Option<[u8; 16]>,
+        referrer: Option<Pubkey>,
+        rollover: bool,
+        access_code: Option<String>,
+    ) -> Result<PurchaseReceipt> {
+        instructions::buy_tickets::buy_tickets(
+            ctx,
+            ticket_count,
+            entry_seed,
+            expected_total,
+            memo,
+            idempotency_key,
+            referrer,
+            rollover,
+            access_code,
+        )
+    }
+
+    pub fn gift_tickets_multi<'info>(
+        ctx: Context<'_, '_, 'info, 'info, GiftTicketsMulti<'info>>,
+        gifts: Vec<GiftAllocation>,
+        expected_total: Option<u64>,
     ) -> Result<()> {
-        instructions::buy_tickets::buy_tickets(ctx, ticket_count, entry_seed)
+        instructions::gift_tickets_multi::gift_tickets_multi(ctx, gifts, expected_total)
     }
 
-    pub fn init_ticket_balance(ctx: Context<InitTicketBalance>) -> Result<()> {
-        instructions::init_ticket_balance::init_ticket_balance(ctx)
+    pub fn create_bundle<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateBundle<'info>>,
+        bundle_id: u64,
+        price: u64,
+    ) -> Result<()> {
+        instructions::create_bundle::create_bundle(ctx, bundle_id, price)
+    }
+
+    pub fn create_campaign(
+        ctx: Context<CreateCampaign>,
+        campaign_id: u64,
+        discount_bps: u16,
+        budget: u64,
+    ) -> Result<()> {
+        instructions::create_campaign::create_campaign(ctx, campaign_id, discount_bps, budget)
+    }
+
+    pub fn buy_bundle<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BuyBundle<'info>>,
+        entry_seed: [u8; 8],
+        expected_total: Option<u64>,
+    ) -> Result<()> {
+        instructions::buy_bundle::buy_bundle(ctx, entry_seed, expected_total)
+    }
+
+    pub fn reserve_tickets(
+        ctx: Context<ReserveTickets>,
+        reservation_seed: [u8; 8],
+        buyer: Pubkey,
+        ticket_count: u64,
+        expires_at: i64,
+    ) -> Result<()> {
+        instructions::reserve_tickets::reserve_tickets(
+            ctx,
+            reservation_seed,
+            buyer,
+            ticket_count,
+            expires_at,
+        )
+    }
+
+    pub fn confirm_reservation<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ConfirmReservation<'info>>,
+        finalize: bool,
+    ) -> Result<()> {
+        instructions::confirm_reservation::confirm_reservation(ctx, finalize)
+    }
+
+    pub fn release_expired_reservation(ctx: Context<ReleaseExpiredReservation>) -> Result<()> {
+        instructions::release_expired_reservation::release_expired_reservation(ctx)
+    }
+
+    pub fn extend_entry(
+        ctx: Context<ExtendEntry>,
+        ticket_count: u64,
+        expected_total: Option<u64>,
+    ) -> Result<()> {
+        instructions::extend_entry::extend_entry(ctx, ticket_count, expected_total)
+    }
+
+    pub fn claim_ticket_collectible(
+        ctx: Context<ClaimTicketCollectible>,
+        entry_seed: [u8; 8],
+        ticket_index: u64,
+    ) -> Result<()> {
+        instructions::claim_ticket_collectible::claim_ticket_collectible(
+            ctx,
+            entry_seed,
+            ticket_index,
+        )
+    }
+
+    pub fn transfer_entry(ctx: Context<TransferEntry>) -> Result<()> {
+        instructions::transfer_entry::transfer_entry(ctx)
+    }
+
+    pub fn claim_participation_proof(ctx: Context<ClaimParticipationProof>) -> Result<()> {
+        instructions::claim_participation_proof::claim_participation_proof(ctx)
+    }
+
+    pub fn close_entries<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CloseEntries<'info>>,
+    ) -> Result<()> {
+        instructions::close_entries::close_entries(ctx)
+    }
+
+    pub fn migrate_entry_v1_to_v2(ctx: Context<MigrateEntryV1ToV2>) -> Result<()> {
+        instructions::migrate_entry_v1_to_v2::migrate_entry_v1_to_v2(ctx)
+    }
+
+    pub fn close_entry(ctx: Context<CloseEntry>, entry_seed: [u8; 8]) -> Result<()> {
+        instructions::close_entry::close_entry(ctx, entry_seed)
+    }
+
+    pub fn close_purchase_dedupes<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClosePurchaseDedupes<'info>>,
+    ) -> Result<()> {
+        instructions::close_purchase_dedupes::close_purchase_dedupes(ctx)
+    }
+
+    pub fn init_leaderboard(ctx: Context<InitLeaderboard>) -> Result<()> {
+        instructions::init_leaderboard::init_leaderboard(ctx)
+    }
+
+    pub fn init_notifications(ctx: Context<InitNotifications>, wallet: Pubkey) -> Result<()> {
+        instructions::init_notifications::init_notifications(ctx, wallet)
+    }
+
+    pub fn init_referral_stats(ctx: Context<InitReferralStats>, referrer: Pubkey) -> Result<()> {
+        instructions::init_referral_stats::init_referral_stats(ctx, referrer)
+    }
+
+    pub fn init_ticket_balance(
+        ctx: Context<InitTicketBalance>,
+        allowlist_proof: Option<Vec<[u8; 32]>>,
+    ) -> Result<()> {
+        instructions::init_ticket_balance::init_ticket_balance(ctx, allowlist_proof)
     }
 
     pub fn expire_raffle(ctx: Context<ExpireRaffle>) -> Result<()> {
         instructions::expire_raffle::expire_raffle(ctx)
     }
 
+    pub fn expire_many<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExpireMany<'info>>,
+    ) -> Result<()> {
+        instructions::expire_many::expire_many(ctx)
+    }
+
+    pub fn pay_top_holders<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PayTopHolders<'info>>,
+    ) -> Result<()> {
+        instructions::pay_top_holders::pay_top_holders(ctx)
+    }
+
+    pub fn pay_winner(ctx: Context<PayWinner>) -> Result<()> {
+        instructions::pay_winner::pay_winner(ctx)
+    }
+
+    pub fn rollover_tickets(ctx: Context<RolloverTickets>, entry_seed: [u8; 8]) -> Result<()> {
+        instructions::rollover_tickets::rollover_tickets(ctx, entry_seed)
+    }
+
+    pub fn set_successor_raffle(ctx: Context<SetSuccessorRaffle>) -> Result<()> {
+        instructions::set_successor_raffle::set_successor_raffle(ctx)
+    }
+
     pub fn reclaim_expired_tickets(ctx: Context<ReclaimExpiredTickets>) -> Result<()> {
         instructions::reclaim_expired_tickets::reclaim_expired_tickets(ctx)
     }
 
-    pub fn withdraw_from_treasury(ctx: Context<WithdrawFromTreasury>) -> Result<()> {
+    pub fn claim_refund_with_proof(
+        ctx: Context<ClaimRefundWithProof>,
+        ticket_count: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::claim_refund_with_proof::claim_refund_with_proof(ctx, ticket_count, proof)
+    }
+
+    pub fn close_raffle(ctx: Context<CloseRaffle>) -> Result<()> {
+        instructions::close_raffle::close_raffle(ctx)
+    }
+
+    pub fn close_winner_data(ctx: Context<CloseWinnerData>) -> Result<()> {
+        instructions::close_winner_data::close_winner_data(ctx)
+    }
+
+    pub fn withdraw_from_treasury<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WithdrawFromTreasury<'info>>,
+    ) -> Result<()> {
         instructions::withdraw_from_treasury::withdraw_from_treasury(ctx)
     }
 
-    pub fn set_winner(ctx: Context<SetWinner>, entry_seed: [u8; 8]) -> Result<()> {
-        instructions::set_winner::set_winner(ctx, entry_seed)
+    pub fn approve_withdrawal(ctx: Context<ApproveWithdrawal>, amount: u64) -> Result<()> {
+        instructions::approve_withdrawal::approve_withdrawal(ctx, amount)
+    }
+
+    pub fn set_withdrawal_approver(
+        ctx: Context<SetWithdrawalApprover>,
+        withdrawal_approver: Option<Pubkey>,
+        withdrawal_approval_threshold: u64,
+    ) -> Result<()> {
+        instructions::set_withdrawal_approver::set_withdrawal_approver(
+            ctx,
+            withdrawal_approver,
+            withdrawal_approval_threshold,
+        )
+    }
+
+    pub fn consolidate_treasuries<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ConsolidateTreasuries<'info>>,
+    ) -> Result<()> {
+        instructions::consolidate_treasuries::consolidate_treasuries(ctx)
+    }
+
+    pub fn prove_entry(ctx: Context<ProveEntry>) -> Result<EntryProof> {
+        instructions::prove_entry::prove_entry(ctx)
+    }
+
+    pub fn simulate_draw(ctx: Context<SimulateDraw>) -> Result<u64> {
+        instructions::simulate_draw::simulate_draw(ctx)
+    }
+
+    pub fn set_winner_data_formats(
+        ctx: Context<SetWinnerDataFormats>,
+        format_versions: Vec<u8>,
+        encryption_schemes: Vec<u8>,
+    ) -> Result<()> {
+        instructions::set_winner_data_formats::set_winner_data_formats(
+            ctx,
+            format_versions,
+            encryption_schemes,
+        )
+    }
+
+    pub fn set_refund_fee(ctx: Context<SetRefundFee>, refund_fee_bps: u16) -> Result<()> {
+        instructions::set_refund_fee::set_refund_fee(ctx, refund_fee_bps)
+    }
+
+    pub fn set_creator_bond(ctx: Context<SetCreatorBond>, creator_bond: u64) -> Result<()> {
+        instructions::set_creator_bond::set_creator_bond(ctx, creator_bond)
+    }
+
+    pub fn set_dispute_bond(ctx: Context<SetDisputeBond>, dispute_bond: u64) -> Result<()> {
+        instructions::set_dispute_bond::set_dispute_bond(ctx, dispute_bond)
+    }
+
+    pub fn set_refund_mint(ctx: Context<SetRefundMint>, refund_rate: u64) -> Result<()> {
+        instructions::set_refund_mint::set_refund_mint(ctx, refund_rate)
+    }
+
+    pub fn set_refund_window(
+        ctx: Context<SetRefundWindow>,
+        refund_window_secs: i64,
+        insurance_fund: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_refund_window::set_refund_window(ctx, refund_window_secs, insurance_fund)
+    }
+
+    pub fn set_crank_reward(ctx: Context<SetCrankReward>, crank_reward_bps: u16) -> Result<()> {
+        instructions::set_crank_reward::set_crank_reward(ctx, crank_reward_bps)
+    }
+
+    pub fn set_referral_fee(ctx: Context<SetReferralFee>, referral_fee_bps: u16) -> Result<()> {
+        instructions::set_referral_fee::set_referral_fee(ctx, referral_fee_bps)
     }
 
-    pub fn draw_winning_ticket(ctx: Context<DrawWinningTicket>) -> Result<()> {
+    pub fn set_large_purchase_threshold(
+        ctx: Context<SetLargePurchaseThreshold>,
+        large_purchase_threshold: u64,
+    ) -> Result<()> {
+        instructions::set_large_purchase_threshold::set_large_purchase_threshold(
+            ctx,
+            large_purchase_threshold,
+        )
+    }
+
+    pub fn create_lookup_table(ctx: Context<CreateLookupTable>, recent_slot: u64) -> Result<()> {
+        instructions::create_lookup_table::create_lookup_table(ctx, recent_slot)
+    }
+
+    pub fn extend_lookup_table(
+        ctx: Context<ExtendLookupTable>,
+        addresses: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::extend_lookup_table::extend_lookup_table(ctx, addresses)
+    }
+
+    pub fn set_alarm_thresholds(
+        ctx: Context<SetAlarmThresholds>,
+        alarm_coverage_bps: u16,
+        alarm_min_shortfall: u64,
+    ) -> Result<()> {
+        instructions::set_alarm_thresholds::set_alarm_thresholds(
+            ctx,
+            alarm_coverage_bps,
+            alarm_min_shortfall,
+        )
+    }
+
+    pub fn set_platform_fee(
+        ctx: Context<SetPlatformFee>,
+        fee_bps: u16,
+        fee_recipient: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_platform_fee::set_platform_fee(ctx, fee_bps, fee_recipient)
+    }
+
+    pub fn set_raffle_fee(ctx: Context<SetRaffleFee>, fee_bps_override: Option<u16>) -> Result<()> {
+        instructions::set_raffle_fee::set_raffle_fee(ctx, fee_bps_override)
+    }
+
+    pub fn set_operator_status(
+        ctx: Context<SetOperatorStatus>,
+        operator_status: u8,
+        status_note_hash: Option<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::set_operator_status::set_operator_status(
+            ctx,
+            operator_status,
+            status_note_hash,
+        )
+    }
+
+    pub fn set_winner(
+        ctx: Context<SetWinner>,
+        entry_seed: [u8; 8],
+        winner_index: u8,
+    ) -> Result<()> {
+        instructions::set_winner::set_winner(ctx, entry_seed, winner_index)
+    }
+
+    pub fn commit_winner_secret(
+        ctx: Context<CommitWinnerSecret>,
+        commitment: [u8; 32],
+    ) -> Result<()> {
+        instructions::commit_winner_secret::commit_winner_secret(ctx, commitment)
+    }
+
+    pub fn draw_winning_ticket<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DrawWinningTicket<'info>>,
+    ) -> Result<()> {
         instructions::draw_winning_ticket::draw_winning_ticket(ctx)
     }
 
-    pub fn submit_winner_data(ctx: Context<SubmitWinnerData>, data: String) -> Result<()> {
-        instructions::submit_winner_data::submit_winner_data(ctx, data)
+    pub fn finalize_raffle(ctx: Context<FinalizeRaffle>) -> Result<()> {
+        instructions::finalize_raffle::finalize_raffle(ctx)
+    }
+
+    pub fn update_max_tickets(ctx: Context<UpdateMaxTickets>, max_tickets: u64) -> Result<()> {
+        instructions::update_max_tickets::update_max_tickets(ctx, max_tickets)
+    }
+
+    pub fn update_raffle(
+        ctx: Context<UpdateRaffle>,
+        metadata_uri: String,
+        end_time: i64,
+        min_tickets: u64,
+        max_tickets: Option<u64>,
+    ) -> Result<()> {
+        instructions::update_raffle::update_raffle(
+            ctx,
+            metadata_uri,
+            end_time,
+            min_tickets,
+            max_tickets,
+        )
+    }
+
+    pub fn set_funding_deadline(
+        ctx: Context<SetFundingDeadline>,
+        funding_deadline: Option<i64>,
+    ) -> Result<()> {
+        instructions::set_funding_deadline::set_funding_deadline(ctx, funding_deadline)
+    }
+
+    pub fn extend_raffle(ctx: Context<ExtendRaffle>, end_time: i64) -> Result<()> {
+        instructions::extend_raffle::extend_raffle(ctx, end_time)
+    }
+
+    pub fn set_extension_limits(
+        ctx: Context<SetExtensionLimits>,
+        max_raffle_extensions: u8,
+        max_extension_secs: i64,
+    ) -> Result<()> {
+        instructions::set_extension_limits::set_extension_limits(
+            ctx,
+            max_raffle_extensions,
+            max_extension_secs,
+        )
+    }
+
+    pub fn set_draw_authority(
+        ctx: Context<SetDrawAuthority>,
+        draw_authority: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_draw_authority::set_draw_authority(ctx, draw_authority)
+    }
+
+    pub fn set_winner_count(ctx: Context<SetWinnerCount>, winner_count: u8) -> Result<()> {
+        instructions::set_winner_count::set_winner_count(ctx, winner_count)
+    }
+
+    pub fn set_randomness_mode(
+        ctx: Context<SetRandomnessMode>,
+        randomness_mode: state::RandomnessMode,
+    ) -> Result<()> {
+        instructions::set_randomness_mode::set_randomness_mode(ctx, randomness_mode)
+    }
+
+    pub fn request_randomness(ctx: Context<RequestRandomness>) -> Result<()> {
+        instructions::request_randomness::request_randomness(ctx)
+    }
+
+    pub fn set_draw_delay(ctx: Context<SetDrawDelay>, draw_delay_slots: u16) -> Result<()> {
+        instructions::set_draw_delay::set_draw_delay(ctx, draw_delay_slots)
+    }
+
+    pub fn commit_draw_slot(ctx: Context<CommitDrawSlot>) -> Result<()> {
+        instructions::commit_draw_slot::commit_draw_slot(ctx)
+    }
+
+    pub fn settle_randomness(ctx: Context<SettleRandomness>) -> Result<()> {
+        instructions::settle_randomness::settle_randomness(ctx)
+    }
+
+    pub fn register_randomness_provider(
+        ctx: Context<RegisterRandomnessProvider>,
+        provider: state::RandomnessProvider,
+        program_id: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::register_randomness_provider::register_randomness_provider(
+            ctx, provider, program_id,
+        )
+    }
+
+    pub fn set_randomness_provider(
+        ctx: Context<SetRandomnessProvider>,
+        provider: state::RandomnessProvider,
+        commitment: Option<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::set_randomness_provider::set_randomness_provider(ctx, provider, commitment)
+    }
+
+    pub fn fulfill_randomness(
+        ctx: Context<FulfillRandomness>,
+        secret: Option<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::fulfill_randomness::fulfill_randomness(ctx, secret)
+    }
+
+    pub fn open_vote<'info>(
+        ctx: Context<'_, '_, 'info, 'info, OpenVote<'info>>,
+        voting_ends_at: i64,
+    ) -> Result<()> {
+        instructions::open_vote::open_vote(ctx, voting_ends_at)
+    }
+
+    pub fn cast_vote(ctx: Context<CastVote>, choice: u8, amount: u64) -> Result<()> {
+        instructions::cast_vote::cast_vote(ctx, choice, amount)
+    }
+
+    pub fn tally_vote(ctx: Context<TallyVote>) -> Result<()> {
+        instructions::tally_vote::tally_vote(ctx)
+    }
+
+    pub fn reclaim_vote(ctx: Context<ReclaimVote>) -> Result<()> {
+        instructions::reclaim_vote::reclaim_vote(ctx)
+    }
+
+    pub fn reset_draw(ctx: Context<ResetDraw>) -> Result<()> {
+        instructions::reset_draw::reset_draw(ctx)
+    }
+
+    pub fn open_dispute(ctx: Context<OpenDispute>, reason_hash: [u8; 32]) -> Result<()> {
+        instructions::open_dispute::open_dispute(ctx, reason_hash)
+    }
+
+    pub fn resolve_dispute(ctx: Context<ResolveDispute>, upheld: bool) -> Result<()> {
+        instructions::resolve_dispute::resolve_dispute(ctx, upheld)
+    }
+
+    pub fn submit_winner_data(
+        ctx: Context<SubmitWinnerData>,
+        data: String,
+        winner_secret: Option<[u8; 32]>,
+        format_version: u8,
+        encryption_scheme: u8,
+        winner_index: u8,
+    ) -> Result<()> {
+        instructions::submit_winner_data::submit_winner_data(
+            ctx,
+            data,
+            winner_secret,
+            format_version,
+            encryption_scheme,
+            winner_index,
+        )
+    }
+
+    pub fn submit_winner_attestation(
+        ctx: Context<SubmitWinnerAttestation>,
+        winner_secret: Option<[u8; 32]>,
+        winner_index: u8,
+    ) -> Result<()> {
+        instructions::submit_winner_attestation::submit_winner_attestation(
+            ctx,
+            winner_secret,
+            winner_index,
+        )
+    }
+
+    pub fn update_winner_data(
+        ctx: Context<UpdateWinnerData>,
+        data: String,
+        format_version: u8,
+        encryption_scheme: u8,
+    ) -> Result<()> {
+        instructions::update_winner_data::update_winner_data(
+            ctx,
+            data,
+            format_version,
+            encryption_scheme,
+        )
+    }
+
+    pub fn lock_winner_data(ctx: Context<LockWinnerData>) -> Result<()> {
+        instructions::lock_winner_data::lock_winner_data(ctx)
+    }
+
+    pub fn cancel_raffle(ctx: Context<CancelRaffle>) -> Result<()> {
+        instructions::cancel_raffle::cancel_raffle(ctx)
+    }
+
+    pub fn force_expire(ctx: Context<ForceExpire>) -> Result<()> {
+        instructions::force_expire::force_expire(ctx)
+    }
+
+    pub fn request_emergency_recovery(ctx: Context<RequestEmergencyRecovery>) -> Result<()> {
+        instructions::request_emergency_recovery::request_emergency_recovery(ctx)
+    }
+
+    pub fn cancel_emergency_recovery(ctx: Context<CancelEmergencyRecovery>) -> Result<()> {
+        instructions::cancel_emergency_recovery::cancel_emergency_recovery(ctx)
+    }
+
+    pub fn emergency_recover(ctx: Context<EmergencyRecover>) -> Result<()> {
+        instructions::emergency_recover::emergency_recover(ctx)
+    }
+
+    pub fn sponsor_match(ctx: Context<SponsorMatchCtx>, match_bps: u16, cap: u64) -> Result<()> {
+        instructions::sponsor_match::sponsor_match(ctx, match_bps, cap)
+    }
+
+    pub fn settle_sponsor_match(ctx: Context<SettleSponsorMatch>) -> Result<()> {
+        instructions::settle_sponsor_match::settle_sponsor_match(ctx)
+    }
+
+    pub fn init_rent_pool(ctx: Context<InitRentPool>) -> Result<()> {
+        instructions::init_rent_pool::init_rent_pool(ctx)
+    }
+
+    pub fn fund_rent_pool(ctx: Context<FundRentPool>, amount: u64) -> Result<()> {
+        instructions::fund_rent_pool::fund_rent_pool(ctx, amount)
+    }
+
+    pub fn set_rent_pool_enabled(ctx: Context<SetRentPoolEnabled>, enabled: bool) -> Result<()> {
+        instructions::set_rent_pool_enabled::set_rent_pool_enabled(ctx, enabled)
+    }
+
+    pub fn sweep_treasury_dust(ctx: Context<SweepTreasuryDust>) -> Result<()> {
+        instructions::sweep_treasury_dust::sweep_treasury_dust(ctx)
+    }
+
+    pub fn sweep_unclaimed_refunds(ctx: Context<SweepUnclaimedRefunds>) -> Result<()> {
+        instructions::sweep_unclaimed_refunds::sweep_unclaimed_refunds(ctx)
+    }
+
+    pub fn set_claim_windows(
+        ctx: Context<SetClaimWindows>,
+        claim_window_secs: i64,
+        dispute_window_secs: i64,
+    ) -> Result<()> {
+        instructions::set_claim_windows::set_claim_windows(
+            ctx,
+            claim_window_secs,
+            dispute_window_secs,
+        )
+    }
+
+    pub fn register_keeper(ctx: Context<RegisterKeeper>, keeper: Pubkey) -> Result<()> {
+        instructions::register_keeper::register_keeper(ctx, keeper)
+    }
+
+    pub fn remove_keeper(ctx: Context<RemoveKeeper>) -> Result<()> {
+        instructions::remove_keeper::remove_keeper(ctx)
+    }
+
+    pub fn grant_creator_role(
+        ctx: Context<GrantCreatorRole>,
+        creator: Pubkey,
+        max_active_raffles: u16,
+        max_ticket_price: u64,
+    ) -> Result<()> {
+        instructions::grant_creator_role::grant_creator_role(
+            ctx,
+            creator,
+            max_active_raffles,
+            max_ticket_price,
+        )
+    }
+
+    pub fn revoke_creator_role(ctx: Context<RevokeCreatorRole>) -> Result<()> {
+        instructions::revoke_creator_role::revoke_creator_role(ctx)
+    }
+
+    pub fn report_heartbeat(
+        ctx: Context<ReportHeartbeat>,
+        last_processed_slot: u64,
+        pending_draws: u64,
+        pending_expiries: u64,
+        pending_closures: u64,
+    ) -> Result<()> {
+        instructions::report_heartbeat::report_heartbeat(
+            ctx,
+            last_processed_slot,
+            pending_draws,
+            pending_expiries,
+            pending_closures,
+        )
+    }
+
+    pub fn set_keepers_restricted(
+        ctx: Context<SetKeepersRestricted>,
+        restricted: bool,
+    ) -> Result<()> {
+        instructions::set_keepers_restricted::set_keepers_restricted(ctx, restricted)
+    }
+
+    pub fn set_compact_events(ctx: Context<SetCompactEvents>, compact: bool) -> Result<()> {
+        instructions::set_compact_events::set_compact_events(ctx, compact)
+    }
+
+    pub fn set_cpi_purchases_rejected(
+        ctx: Context<SetCpiPurchasesRejected>,
+        rejected: bool,
+    ) -> Result<()> {
+        instructions::set_cpi_purchases_rejected::set_cpi_purchases_rejected(ctx, rejected)
+    }
+
+    pub fn set_prize_deposit_required(
+        ctx: Context<SetPrizeDepositRequired>,
+        required: bool,
+    ) -> Result<()> {
+        instructions::set_prize_deposit_required::set_prize_deposit_required(ctx, required)
+    }
+
+    pub fn deposit_prize(ctx: Context<DepositPrize>, amount: u64) -> Result<()> {
+        instructions::deposit_prize::deposit_prize(ctx, amount)
+    }
+
+    pub fn set_metadata_hosts(
+        ctx: Context<SetMetadataHosts>,
+        allowed_metadata_hosts: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::set_metadata_hosts::set_metadata_hosts(ctx, allowed_metadata_hosts)
+    }
+
+    pub fn set_arweave_mirror(
+        ctx: Context<SetArweaveMirror>,
+        arweave_tx_id: Option<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::set_arweave_mirror::set_arweave_mirror(ctx, arweave_tx_id)
+    }
+
+    pub fn set_payout_destinations(
+        ctx: Context<SetPayoutDestinations>,
+        payout_destinations: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_payout_destinations::set_payout_destinations(ctx, payout_destinations)
+    }
+
+    pub fn set_pause(ctx: Context<SetPause>, paused: bool) -> Result<()> {
+        instructions::set_pause::set_pause(ctx, paused)
+    }
+
+    pub fn propose_authority(
+        ctx: Context<ProposeAuthority>,
+        role: state::AuthorityRole,
+        proposed_authority: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::propose_authority::propose_authority(ctx, role, proposed_authority)
+    }
+
+    pub fn accept_authority(
+        ctx: Context<AcceptAuthority>,
+        role: state::AuthorityRole,
+    ) -> Result<()> {
+        instructions::accept_authority::accept_authority(ctx, role)
+    }
+
+    pub fn set_region_attestor(
+        ctx: Context<SetRegionAttestor>,
+        region_attestor: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_region_attestor::set_region_attestor(ctx, region_attestor)
+    }
+
+    pub fn set_allowed_regions(
+        ctx: Context<SetAllowedRegions>,
+        allowed_regions: u64,
+    ) -> Result<()> {
+        instructions::set_allowed_regions::set_allowed_regions(ctx, allowed_regions)
+    }
+
+    pub fn attest_region(
+        ctx: Context<AttestRegion>,
+        wallet: Pubkey,
+        region: u8,
+        expires_at: i64,
+    ) -> Result<()> {
+        instructions::attest_region::attest_region(ctx, wallet, region, expires_at)
+    }
+
+    pub fn set_age_attestor(
+        ctx: Context<SetAgeAttestor>,
+        age_attestor: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_age_attestor::set_age_attestor(ctx, age_attestor)
+    }
+
+    pub fn set_age_restricted(ctx: Context<SetAgeRestricted>, age_restricted: bool) -> Result<()> {
+        instructions::set_age_restricted::set_age_restricted(ctx, age_restricted)
+    }
+
+    pub fn set_gate(ctx: Context<SetGate>, gate: Option<state::Gate>) -> Result<()> {
+        instructions::set_gate::set_gate(ctx, gate)
+    }
+
+    pub fn set_access_code(
+        ctx: Context<SetAccessCode>,
+        access_code_hash: Option<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::set_access_code::set_access_code(ctx, access_code_hash)
+    }
+
+    pub fn set_deferred_numbering(
+        ctx: Context<SetDeferredNumbering>,
+        deferred_numbering: bool,
+    ) -> Result<()> {
+        instructions::set_deferred_numbering::set_deferred_numbering(ctx, deferred_numbering)
+    }
+
+    pub fn set_ticket_receipts(
+        ctx: Context<SetTicketReceipts>,
+        ticket_receipts: bool,
+    ) -> Result<()> {
+        instructions::set_ticket_receipts::set_ticket_receipts(ctx, ticket_receipts)
+    }
+
+    pub fn finalize_numbering<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FinalizeNumbering<'info>>,
+    ) -> Result<()> {
+        instructions::finalize_numbering::finalize_numbering(ctx)
+    }
+
+    pub fn snapshot_raffle<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SnapshotRaffle<'info>>,
+    ) -> Result<()> {
+        instructions::snapshot_raffle::snapshot_raffle(ctx)
+    }
+
+    pub fn attest_age(ctx: Context<AttestAge>, wallet: Pubkey, expires_at: i64) -> Result<()> {
+        instructions::attest_age::attest_age(ctx, wallet, expires_at)
+    }
+
+    pub fn set_identity_issuer(
+        ctx: Context<SetIdentityIssuer>,
+        identity_issuer: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_identity_issuer::set_identity_issuer(ctx, identity_issuer)
+    }
+
+    pub fn set_raffle_preset(
+        ctx: Context<SetRafflePreset>,
+        name: [u8; 16],
+        preset: Option<state::RafflePreset>,
+    ) -> Result<()> {
+        instructions::set_raffle_preset::set_raffle_preset(ctx, name, preset)
+    }
+
+    pub fn attest_identity(
+        ctx: Context<AttestIdentity>,
+        wallet: Pubkey,
+        credential_hash: [u8; 32],
+        expires_at: i64,
+    ) -> Result<()> {
+        instructions::attest_identity::attest_identity(ctx, wallet, credential_hash, expires_at)
+    }
+
+    pub fn set_user_tier(ctx: Context<SetUserTier>, wallet: Pubkey, tier: u8) -> Result<()> {
+        instructions::set_user_tier::set_user_tier(ctx, wallet, tier)
+    }
+
+    pub fn set_priority_access(
+        ctx: Context<SetPriorityAccess>,
+        priority_min_tier: u8,
+        priority_until: i64,
+    ) -> Result<()> {
+        instructions::set_priority_access::set_priority_access(
+            ctx,
+            priority_min_tier,
+            priority_until,
+        )
+    }
+
+    pub fn set_prize_info(
+        ctx: Context<SetPrizeInfo>,
+        title: String,
+        value_estimate: u64,
+        prize_type: state::PrizeType,
+        escrow: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_prize_info::set_prize_info(ctx, title, value_estimate, prize_type, escrow)
+    }
+
+    pub fn set_claim_instructions(
+        ctx: Context<SetClaimInstructions>,
+        instructions: String,
+        content_hash: Option<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::set_claim_instructions::set_claim_instructions(
+            ctx,
+            instructions,
+            content_hash,
+        )
+    }
+
+    pub fn get_claim_instructions(
+        ctx: Context<GetClaimInstructions>,
+    ) -> Result<ClaimInstructionsView> {
+        instructions::get_claim_instructions::get_claim_instructions(ctx)
+    }
+
+    pub fn find_entry_for_ticket<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FindEntryForTicket<'info>>,
+        ticket_index: u64,
+    ) -> Result<TicketOwner> {
+        instructions::find_entry_for_ticket::find_entry_for_ticket(ctx, ticket_index)
+    }
+
+    pub fn get_positions<'info>(
+        ctx: Context<'_, '_, 'info, 'info, GetPositions<'info>>,
+        wallet: Pubkey,
+    ) -> Result<Vec<Position>> {
+        instructions::get_positions::get_positions(ctx, wallet)
+    }
+
+    pub fn get_treasury_statement(ctx: Context<GetTreasuryStatement>) -> Result<TreasuryStatement> {
+        instructions::get_treasury_statement::get_treasury_statement(ctx)
+    }
+
+    pub fn get_withdrawal_preview(ctx: Context<GetWithdrawalPreview>) -> Result<WithdrawalPreview> {
+        instructions::get_withdrawal_preview::get_withdrawal_preview(ctx)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn validate_raffle_params(
+        ctx: Context<ValidateRaffleParams>,
+        metadata_uri: String,
+        ticket_price: u64,
+        end_time: i64,
+        min_tickets: u64,
+        max_tickets: Option<u64>,
+        max_per_purchase: Option<u64>,
+        anonymize_winner: bool,
+        min_revenue: Option<u64>,
+        collectible: bool,
+        cash_prize_bps: Option<u16>,
+        top_holders: Option<u8>,
+        min_unique_buyers: Option<u64>,
+        max_tickets_per_wallet: Option<u64>,
+        pricing_tiers: Option<Vec<state::PricingTier>>,
+        prize_value_usd_cents: Option<u64>,
+        ticket_count_display_cap: Option<u64>,
+    ) -> Result<RaffleParamsValidation> {
+        instructions::validate_raffle_params::validate_raffle_params(
+            ctx,
+            metadata_uri,
+            ticket_price,
+            end_time,
+            min_tickets,
+            max_tickets,
+            max_per_purchase,
+            anonymize_winner,
+            min_revenue,
+            collectible,
+            cash_prize_bps,
+            top_holders,
+            min_unique_buyers,
+            max_tickets_per_wallet,
+            pricing_tiers,
+            prize_value_usd_cents,
+            ticket_count_display_cap,
+        )
+    }
+
+    pub fn check_invariants(ctx: Context<CheckInvariants>) -> Result<()> {
+        instructions::check_invariants::check_invariants(ctx)
+    }
+
+    pub fn release_creator_bond(ctx: Context<ReleaseCreatorBond>) -> Result<()> {
+        instructions::release_creator_bond::release_creator_bond(ctx)
+    }
+
+    pub fn slash_creator_bond(ctx: Context<SlashCreatorBond>) -> Result<()> {
+        instructions::slash_creator_bond::slash_creator_bond(ctx)
+    }
+
+    pub fn release_prize(ctx: Context<ReleasePrize>) -> Result<()> {
+        instructions::release_prize::release_prize(ctx)
+    }
+
+    pub fn claim_prize(
+        ctx: Context<ClaimPrize>,
+        winner_secret: Option<[u8; 32]>,
+        winner_index: u8,
+    ) -> Result<()> {
+        instructions::claim_prize::claim_prize(ctx, winner_secret, winner_index)
+    }
+
+    pub fn record_draw_value(ctx: Context<RecordDrawValue>) -> Result<()> {
+        instructions::record_draw_value::record_draw_value(ctx)
+    }
+
+    pub fn finalize_results(ctx: Context<FinalizeResults>) -> Result<()> {
+        instructions::finalize_results::finalize_results(ctx)
+    }
+
+    pub fn set_usd_price_feed(
+        ctx: Context<SetUsdPriceFeed>,
+        usd_price_feed: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_usd_price_feed::set_usd_price_feed(ctx, usd_price_feed)
+    }
+
+    pub fn set_notifier_program(
+        ctx: Context<SetNotifierProgram>,
+        notifier_program: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_notifier_program::set_notifier_program(ctx, notifier_program)
+    }
+
+    pub fn set_collectible_tree(
+        ctx: Context<SetCollectibleTree>,
+        collectible_tree: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_collectible_tree::set_collectible_tree(ctx, collectible_tree)
+    }
+
+    pub fn set_max_open_raffles(
+        ctx: Context<SetMaxOpenRaffles>,
+        max_open_raffles: u32,
+    ) -> Result<()> {
+        instructions::set_max_open_raffles::set_max_open_raffles(ctx, max_open_raffles)
+    }
+
+    pub fn set_clock_offset(ctx: Context<SetClockOffset>, clock_offset: i64) -> Result<()> {
+        instructions::set_clock_offset::set_clock_offset(ctx, clock_offset)
     }
 }
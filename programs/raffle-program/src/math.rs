@@ -0,0 +1,151 @@
+use anchor_lang::{prelude::*, solana_program::native_token::LAMPORTS_PER_SOL};
+
+use crate::error::RaffleError;
+
+/// Checked addition mapping overflow to `RaffleError::Overflow`
+pub fn checked_add(a: u64, b: u64) -> Result<u64> {
+    a.checked_add(b).ok_or_else(|| RaffleError::Overflow.into())
+}
+
+/// Checked subtraction mapping underflow to `RaffleError::Overflow`
+pub fn checked_sub(a: u64, b: u64) -> Result<u64> {
+    a.checked_sub(b).ok_or_else(|| RaffleError::Overflow.into())
+}
+
+/// Checked multiplication mapping overflow to `RaffleError::Overflow`
+pub fn checked_mul(a: u64, b: u64) -> Result<u64> {
+    a.checked_mul(b).ok_or_else(|| RaffleError::Overflow.into())
+}
+
+/// Total lamports owed for `ticket_count` tickets at `ticket_price` each.
+/// Used both when charging for a purchase and when refunding tickets, since
+/// ticket counts are user-controlled inputs.
+pub fn ticket_cost(ticket_count: u64, ticket_price: u64) -> Result<u64> {
+    checked_mul(ticket_count, ticket_price)
+}
+
+/// Denominator for amounts expressed in basis points
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Share of `amount` expressed in basis points, rounded down
+pub fn bps_of(amount: u64, bps: u16) -> Result<u64> {
+    let share = (amount as u128)
+        .checked_mul(bps as u128)
+        .ok_or(RaffleError::Overflow)?
+        / BPS_DENOMINATOR as u128;
+    u64::try_from(share).map_err(|_| RaffleError::Overflow.into())
+}
+
+/// Share of `total` that `part` makes up in basis points, rounded down, or zero if
+/// `total` is zero
+pub fn share_bps(part: u64, total: u64) -> u16 {
+    if total == 0 {
+        return 0;
+    }
+    (part.min(total) as u128 * BPS_DENOMINATOR as u128 / total as u128) as u16
+}
+
+/// Denominator of conversion rates between token amounts
+pub const RATE_SCALE: u64 = 1_000_000_000;
+
+/// `amount` converted at `rate`, scaled by `RATE_SCALE`, rounded down
+pub fn convert_at_rate(amount: u64, rate: u64) -> Result<u64> {
+    let converted = (amount as u128)
+        .checked_mul(rate as u128)
+        .ok_or(RaffleError::Overflow)?
+        / RATE_SCALE as u128;
+    u64::try_from(converted).map_err(|_| RaffleError::Overflow.into())
+}
+
+/// Denominator of USD prices read from price feeds, which are quoted in micro-USD
+pub const USD_RATE_SCALE: u64 = 1_000_000;
+
+/// Value of `lamports` in US cents at `usd_rate` micro-USD per SOL, rounded down
+pub fn lamports_to_usd_cents(lamports: u64, usd_rate: u64) -> Result<u64> {
+    let cents = (lamports as u128)
+        .checked_mul(usd_rate as u128)
+        .ok_or(RaffleError::Overflow)?
+        / (LAMPORTS_PER_SOL as u128 * (USD_RATE_SCALE / 100) as u128);
+    u64::try_from(cents).map_err(|_| RaffleError::Overflow.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_overflow<T: std::fmt::Debug>(result: Result<T>) -> bool {
+        result.unwrap_err() == RaffleError::Overflow.into()
+    }
+
+    #[test]
+    fn ticket_cost_multiplies_count_and_price() {
+        assert_eq!(ticket_cost(0, 100_000_000).unwrap(), 0);
+        assert_eq!(ticket_cost(3, 100_000_000).unwrap(), 300_000_000);
+        assert_eq!(ticket_cost(1, u64::MAX).unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn ticket_cost_rejects_overflow() {
+        assert!(is_overflow(ticket_cost(u64::MAX, 2)));
+        assert!(is_overflow(ticket_cost(2, u64::MAX)));
+        // Largest allowed ticket price with an absurd ticket count
+        assert!(is_overflow(ticket_cost(u64::MAX / 100, 100_000_000_000)));
+    }
+
+    #[test]
+    fn bps_of_rounds_down() {
+        assert_eq!(bps_of(1_000, 1_000).unwrap(), 100);
+        assert_eq!(bps_of(999, 1).unwrap(), 0);
+        assert_eq!(bps_of(u64::MAX, 10_000).unwrap(), u64::MAX);
+        assert!(is_overflow(bps_of(u64::MAX, 10_001)));
+    }
+
+    #[test]
+    fn share_bps_rounds_down() {
+        assert_eq!(share_bps(1, 3), 3_333);
+        assert_eq!(share_bps(3, 3), 10_000);
+        assert_eq!(share_bps(u64::MAX, u64::MAX), 10_000);
+        assert_eq!(share_bps(1, u64::MAX), 0);
+        assert_eq!(share_bps(1, 0), 0);
+    }
+
+    #[test]
+    fn convert_at_rate_rounds_down() {
+        assert_eq!(convert_at_rate(1_000, RATE_SCALE).unwrap(), 1_000);
+        assert_eq!(convert_at_rate(1_000, RATE_SCALE / 2).unwrap(), 500);
+        assert_eq!(convert_at_rate(3, RATE_SCALE / 2).unwrap(), 1);
+        assert_eq!(convert_at_rate(5, 0).unwrap(), 0);
+        assert!(is_overflow(convert_at_rate(u64::MAX, RATE_SCALE + 1)));
+    }
+
+    #[test]
+    fn lamports_to_usd_cents_rounds_down() {
+        // 2.5 SOL at $150.123456
+        assert_eq!(
+            lamports_to_usd_cents(2_500_000_000, 150_123_456).unwrap(),
+            37_530
+        );
+        assert_eq!(lamports_to_usd_cents(1, 150_000_000).unwrap(), 0);
+        assert!(is_overflow(lamports_to_usd_cents(u64::MAX, u64::MAX)));
+    }
+
+    #[test]
+    fn checked_add_rejects_overflow() {
+        assert_eq!(checked_add(1, 2).unwrap(), 3);
+        assert_eq!(checked_add(u64::MAX - 1, 1).unwrap(), u64::MAX);
+        assert!(is_overflow(checked_add(u64::MAX, 1)));
+    }
+
+    #[test]
+    fn checked_sub_rejects_underflow() {
+        assert_eq!(checked_sub(3, 2).unwrap(), 1);
+        assert_eq!(checked_sub(2, 2).unwrap(), 0);
+        assert!(is_overflow(checked_sub(1, 2)));
+    }
+
+    #[test]
+    fn checked_mul_rejects_overflow() {
+        assert_eq!(checked_mul(6, 7).unwrap(), 42);
+        assert!(is_overflow(checked_mul(u64::MAX / 2 + 1, 2)));
+    }
+}
@@ -0,0 +1,223 @@
+//! Merkle proofs of allowlist membership, checked against the root stored in a raffle's
+//! `Gate::MerkleAllowlist`, and of ticket holdings, checked against the root recorded by
+//! `snapshot_raffle`.
+//!
+//! Leaves are the hash of their contents prefixed with a domain separator, and every
+//! node is the hash of its two children in ascending order, so a proof is the list of
+//! sibling hashes from the leaf up without any position bits.
+//!
+//! Snapshot trees are built on-chain one leaf at a time, so they have a fixed depth of
+//! `SNAPSHOT_TREE_DEPTH`, with leaves in ascending owner order followed by zeroed leaves.
+//! Their proofs always hold `SNAPSHOT_TREE_DEPTH` siblings.
+
+use anchor_lang::{prelude::*, solana_program::hash::hashv};
+
+/// Domain separator of allowlist leaves, so a leaf can never be mistaken for a node
+pub const ALLOWLIST_LEAF_PREFIX: &[u8] = b"allowlist";
+
+/// Leaf of `wallet` in an allowlist Merkle tree
+pub(crate) fn allowlist_leaf(wallet: &Pubkey) -> [u8; 32] {
+    hashv(&[ALLOWLIST_LEAF_PREFIX, wallet.as_ref()]).to_bytes()
+}
+
+/// Domain separator of snapshot leaves
+pub const SNAPSHOT_LEAF_PREFIX: &[u8] = b"snapshot";
+
+/// Depth of snapshot trees, which hold up to 2^20 - 1 ticket holders
+pub const SNAPSHOT_TREE_DEPTH: usize = 20;
+
+/// Leaf of a ticket holder in a snapshot Merkle tree
+pub fn snapshot_leaf(owner: &Pubkey, ticket_count: u64) -> [u8; 32] {
+    hashv(&[
+        SNAPSHOT_LEAF_PREFIX,
+        owner.as_ref(),
+        &ticket_count.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+/// Parent node of two sibling nodes, independent of their order
+fn parent(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    if a <= b {
+        hashv(&[a, b]).to_bytes()
+    } else {
+        hashv(&[b, a]).to_bytes()
+    }
+}
+
+/// Whether `proof` proves that `leaf` is part of the tree with `root`
+pub(crate) fn verify_proof(leaf: [u8; 32], proof: &[[u8; 32]], root: &[u8; 32]) -> bool {
+    proof
+        .iter()
+        .fold(leaf, |node, sibling| parent(&node, sibling))
+        == *root
+}
+
+/// Roots of the empty subtrees of each height, the padding of a partially filled tree
+fn empty_subtrees<const DEPTH: usize>() -> [[u8; 32]; DEPTH] {
+    let mut subtrees = [[0; 32]; DEPTH];
+    for height in 1..DEPTH {
+        subtrees[height] = parent(&subtrees[height - 1], &subtrees[height - 1]);
+    }
+    subtrees
+}
+
+/// Appends `leaf` to a tree holding `leaf_count` leaves, whose rightmost filled subtree
+/// of each height is kept in `branch`. The tree must hold fewer than 2^DEPTH - 1 leaves,
+/// since the last subtree of a full tree has no slot in `branch`
+pub(crate) fn append_leaf<const DEPTH: usize>(
+    branch: &mut [[u8; 32]; DEPTH],
+    leaf_count: u64,
+    leaf: [u8; 32],
+) {
+    let mut node = leaf;
+    let mut size = leaf_count + 1;
+    for subtree in branch.iter_mut() {
+        if size & 1 == 1 {
+            *subtree = node;
+            return;
+        }
+        node = parent(subtree, &node);
+        size >>= 1;
+    }
+}
+
+/// Root of a tree holding `leaf_count` leaves, whose rightmost filled subtree of each
+/// height is kept in `branch`
+pub(crate) fn branch_root<const DEPTH: usize>(
+    branch: &[[u8; 32]; DEPTH],
+    leaf_count: u64,
+) -> [u8; 32] {
+    let empty_subtrees = empty_subtrees::<DEPTH>();
+    let mut node = [0; 32];
+    let mut size = leaf_count;
+    for height in 0..DEPTH {
+        node = if size & 1 == 1 {
+            parent(&branch[height], &node)
+        } else {
+            parent(&node, &empty_subtrees[height])
+        };
+        size >>= 1;
+    }
+    node
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Root of the tree over `leaves` and the proof of each leaf, duplicating the last
+    /// node of odd levels
+    fn tree(leaves: &[[u8; 32]]) -> ([u8; 32], Vec<Vec<[u8; 32]>>) {
+        let mut proofs = vec![Vec::new(); leaves.len()];
+        let mut positions: Vec<usize> = (0..leaves.len()).collect();
+        let mut level = leaves.to_vec();
+        while level.len() > 1 {
+            if level.len() % 2 == 1 {
+                level.push(*level.last().unwrap());
+            }
+            for (proof, position) in proofs.iter_mut().zip(positions.iter_mut()) {
+                proof.push(level[*position ^ 1]);
+                *position /= 2;
+            }
+            level = level
+                .chunks(2)
+                .map(|pair| parent(&pair[0], &pair[1]))
+                .collect();
+        }
+        (level[0], proofs)
+    }
+
+    fn wallets(count: usize) -> Vec<Pubkey> {
+        (0..count).map(|_| Pubkey::new_unique()).collect()
+    }
+
+    #[test]
+    fn test_every_wallet_of_the_allowlist_is_proven() {
+        for count in 1..=9 {
+            let wallets = wallets(count);
+            let leaves: Vec<_> = wallets.iter().map(allowlist_leaf).collect();
+            let (root, proofs) = tree(&leaves);
+            for (leaf, proof) in leaves.iter().zip(&proofs) {
+                assert!(verify_proof(*leaf, proof, &root));
+            }
+        }
+    }
+
+    #[test]
+    fn test_single_wallet_allowlist_has_the_leaf_as_root() {
+        let wallet = Pubkey::new_unique();
+        let root = allowlist_leaf(&wallet);
+        assert!(verify_proof(allowlist_leaf(&wallet), &[], &root));
+        assert!(!verify_proof(
+            allowlist_leaf(&Pubkey::new_unique()),
+            &[],
+            &root
+        ));
+    }
+
+    #[test]
+    fn test_wallets_outside_the_allowlist_are_rejected() {
+        let wallets = wallets(8);
+        let leaves: Vec<_> = wallets.iter().map(allowlist_leaf).collect();
+        let (root, proofs) = tree(&leaves);
+
+        // Another wallet can't reuse a proof of an allowlisted wallet
+        let outsider = allowlist_leaf(&Pubkey::new_unique());
+        for proof in &proofs {
+            assert!(!verify_proof(outsider, proof, &root));
+        }
+    }
+
+    #[test]
+    fn test_tampered_proofs_are_rejected() {
+        let wallets = wallets(8);
+        let leaves: Vec<_> = wallets.iter().map(allowlist_leaf).collect();
+        let (root, proofs) = tree(&leaves);
+
+        let mut proof = proofs[3].clone();
+        proof[1][0] ^= 1;
+        assert!(!verify_proof(leaves[3], &proof, &root));
+
+        // Truncated proofs prove an inner node, not the leaf
+        assert!(!verify_proof(leaves[3], &proofs[3][..2], &root));
+    }
+
+    #[test]
+    fn test_snapshot_tree_matches_the_padded_tree() {
+        const DEPTH: usize = 4;
+        let holders = wallets((1 << DEPTH) - 1);
+        let mut branch = [[0; 32]; DEPTH];
+        let mut leaves = Vec::new();
+        for (leaf_count, owner) in holders.iter().enumerate() {
+            let leaf = snapshot_leaf(owner, leaf_count as u64 + 1);
+            append_leaf(&mut branch, leaf_count as u64, leaf);
+            leaves.push(leaf);
+
+            // The root of the partially filled tree is the root of the tree padded
+            // with zeroed leaves, and proves every leaf appended so far
+            let mut padded = leaves.clone();
+            padded.resize(1 << DEPTH, [0; 32]);
+            let (root, proofs) = tree(&padded);
+            assert_eq!(branch_root(&branch, leaves.len() as u64), root);
+            for (leaf, proof) in leaves.iter().zip(&proofs) {
+                assert!(verify_proof(*leaf, proof, &root));
+            }
+        }
+    }
+
+    #[test]
+    fn test_snapshot_leaves_commit_to_the_ticket_count() {
+        let owner = Pubkey::new_unique();
+        let other = snapshot_leaf(&Pubkey::new_unique(), 1);
+        let mut branch = [[0; 32]; SNAPSHOT_TREE_DEPTH];
+        append_leaf(&mut branch, 0, snapshot_leaf(&owner, 5));
+        append_leaf(&mut branch, 1, other);
+        let root = branch_root(&branch, 2);
+
+        let mut proof = vec![other];
+        proof.extend(empty_subtrees::<SNAPSHOT_TREE_DEPTH>().iter().skip(1));
+        assert!(verify_proof(snapshot_leaf(&owner, 5), &proof, &root));
+        assert!(!verify_proof(snapshot_leaf(&owner, 6), &proof, &root));
+    }
+}
@@ -0,0 +1,108 @@
+//! Lifecycle notifications of raffles to a notifier program.
+//!
+//! If the config registers a notifier program, the raffle program invokes it when a
+//! raffle is created, sells out, is drawn and is claimed, so on-chain automations such
+//! as quests and rewards can react without parsing logs. The notifier implements a
+//! single instruction, identified by [`NOTIFY_DISCRIMINATOR`], whose data is the
+//! discriminator followed by a Borsh encoded [`LifecycleNotification`]. The config PDA
+//! is its only account and signs the invocation, so the notifier can authenticate the
+//! raffle program as the caller.
+//!
+//! A failing notifier fails the notifying instruction, which is why only the management
+//! authority can register one. The notifier can't invoke the raffle program in turn, as
+//! Solana rejects reentrant invocations.
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        program::invoke_signed,
+    },
+};
+
+use crate::{
+    error::RaffleError,
+    state::{Config, Raffle},
+    time::current_slot,
+};
+
+// sha256("global:notify_raffle_lifecycle")[..8]
+pub const NOTIFY_DISCRIMINATOR: &[u8] = &[247, 126, 31, 177, 66, 40, 194, 230];
+
+/// Stage of a raffle's lifecycle the notifier is invoked for
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LifecycleEvent {
+    /// The raffle was created
+    Created,
+    /// The last ticket of a raffle with a ticket cap was sold
+    SoldOut,
+    /// The winning tickets were drawn
+    Drawn,
+    /// Every winner claimed their prize
+    Claimed,
+}
+
+/// Payload the notifier program is invoked with
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct LifecycleNotification {
+    /// The pubkey of the raffle
+    pub raffle: Pubkey,
+    /// Stage the raffle reached
+    pub event: LifecycleEvent,
+    /// Tickets sold so far
+    pub ticket_count: u64,
+    /// Slot the raffle reached the stage in
+    pub slot: u64,
+}
+
+/// Invokes the config's notifier program with `event` of `raffle`, if the config
+/// registers one. `notifier_program` is then required and must be the registered program.
+pub(crate) fn notify_lifecycle<'info>(
+    config: &Account<'info, Config>,
+    notifier_program: Option<&UncheckedAccount<'info>>,
+    raffle: &Account<'info, Raffle>,
+    event: LifecycleEvent,
+) -> Result<()> {
+    let Some(registered) = config.notifier_program else {
+        return Ok(());
+    };
+    let notifier_program = notifier_program.ok_or(RaffleError::NotifierProgramRequired)?;
+    require_keys_eq!(
+        notifier_program.key(),
+        registered,
+        RaffleError::InvalidNotifierProgram
+    );
+
+    let notification = LifecycleNotification {
+        raffle: raffle.key(),
+        event,
+        ticket_count: raffle.current_tickets,
+        slot: current_slot()?,
+    };
+    let mut data = NOTIFY_DISCRIMINATOR.to_vec();
+    notification.serialize(&mut data)?;
+    let instruction = Instruction {
+        program_id: registered,
+        accounts: vec![AccountMeta::new_readonly(config.key(), true)],
+        data,
+    };
+    invoke_signed(
+        &instruction,
+        &[config.to_account_info(), notifier_program.to_account_info()],
+        &[&[b"config", &[config.bump]]],
+    )?;
+    Ok(())
+}
+
+/// Invokes the config's notifier program with a SoldOut notification if `raffle` has a
+/// ticket cap its tickets just reached. Called after every kind of purchase.
+pub(crate) fn notify_if_sold_out<'info>(
+    config: &Account<'info, Config>,
+    notifier_program: Option<&UncheckedAccount<'info>>,
+    raffle: &Account<'info, Raffle>,
+) -> Result<()> {
+    if raffle.max_tickets != Some(raffle.current_tickets) {
+        return Ok(());
+    }
+    notify_lifecycle(config, notifier_program, raffle, LifecycleEvent::SoldOut)
+}
@@ -0,0 +1,180 @@
+//! Address derivation helpers for off-chain integrations.
+//!
+//! Each helper derives a program account address from the same seeds used by the
+//! account constraints, so clients don't need to duplicate seed strings.
+
+use anchor_lang::prelude::Pubkey;
+
+fn find_address(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &crate::ID).0
+}
+
+/// Address of the program config, seeds ["config"]
+pub fn config_address() -> Pubkey {
+    find_address(&[b"config"])
+}
+
+/// Address of the raffle created with the given raffle counter, seeds ["raffle", index]
+pub fn raffle_address(index: u64) -> Pubkey {
+    find_address(&[b"raffle", &index.to_le_bytes()])
+}
+
+/// Address of a bundle, seeds ["bundle", bundle_id]
+pub fn bundle_address(bundle_id: u64) -> Pubkey {
+    find_address(&[b"bundle", &bundle_id.to_le_bytes()])
+}
+
+/// Address of a promotional campaign, seeds ["campaign", campaign_id]
+pub fn campaign_address(campaign_id: u64) -> Pubkey {
+    find_address(&[b"campaign", &campaign_id.to_le_bytes()])
+}
+
+/// Address of a raffle's treasury, seeds ["treasury", raffle]
+pub fn treasury_address(raffle: &Pubkey) -> Pubkey {
+    find_address(&[b"treasury", raffle.as_ref()])
+}
+
+/// Address of an entry, seeds ["entry", raffle, seed]
+pub fn entry_address(raffle: &Pubkey, seed: &[u8; 8]) -> Pubkey {
+    find_address(&[b"entry", raffle.as_ref(), seed])
+}
+
+/// Address of a wallet's ticket balance in a raffle, seeds ["ticket_balance", raffle, owner]
+pub fn ticket_balance_address(raffle: &Pubkey, owner: &Pubkey) -> Pubkey {
+    find_address(&[b"ticket_balance", raffle.as_ref(), owner.as_ref()])
+}
+
+/// Address of a raffle's pending emergency recovery, seeds ["recovery_request", raffle]
+pub fn recovery_request_address(raffle: &Pubkey) -> Pubkey {
+    find_address(&[b"recovery_request", raffle.as_ref()])
+}
+
+/// Address of a raffle's claim instructions, seeds ["claim_instructions", raffle]
+pub fn claim_instructions_address(raffle: &Pubkey) -> Pubkey {
+    find_address(&[b"claim_instructions", raffle.as_ref()])
+}
+
+/// Address of a ticket reservation, seeds ["reservation", raffle, seed]
+pub fn reservation_address(raffle: &Pubkey, seed: &[u8; 8]) -> Pubkey {
+    find_address(&[b"reservation", raffle.as_ref(), seed])
+}
+
+/// Address of the winner's submitted data, seeds ["winner_data", raffle, winner]
+pub fn winner_data_address(raffle: &Pubkey, winner: &Pubkey) -> Pubkey {
+    find_address(&[b"winner_data", raffle.as_ref(), winner.as_ref()])
+}
+
+/// Address of a keeper's heartbeat, seeds ["heartbeat", keeper]
+pub fn heartbeat_address(keeper: &Pubkey) -> Pubkey {
+    find_address(&[b"heartbeat", keeper.as_ref()])
+}
+
+/// Address of a keeper's registration, seeds ["keeper", keeper]
+pub fn keeper_address(keeper: &Pubkey) -> Pubkey {
+    find_address(&[b"keeper", keeper.as_ref()])
+}
+
+/// Address of a raffle's top holder leaderboard, seeds ["leaderboard", raffle]
+pub fn leaderboard_address(raffle: &Pubkey) -> Pubkey {
+    find_address(&[b"leaderboard", raffle.as_ref()])
+}
+
+/// Address of the rent pool, seeds ["rent_pool"]
+pub fn rent_pool_address() -> Pubkey {
+    find_address(&[b"rent_pool"])
+}
+
+/// Address of a sponsor's match escrow, seeds ["sponsor_match", raffle, sponsor]
+pub fn sponsor_match_address(raffle: &Pubkey, sponsor: &Pubkey) -> Pubkey {
+    find_address(&[b"sponsor_match", raffle.as_ref(), sponsor.as_ref()])
+}
+
+/// Address of a raffle's prize escrow, seeds ["prize_vault", raffle]
+pub fn prize_vault_address(raffle: &Pubkey) -> Pubkey {
+    find_address(&[b"prize_vault", raffle.as_ref()])
+}
+
+/// Address of a raffle's prize details, seeds ["prize_info", raffle]
+pub fn prize_info_address(raffle: &Pubkey) -> Pubkey {
+    find_address(&[b"prize_info", raffle.as_ref()])
+}
+
+/// Address of a raffle's results record, seeds ["results", raffle]
+pub fn results_address(raffle: &Pubkey) -> Pubkey {
+    find_address(&[b"results", raffle.as_ref()])
+}
+
+/// Address of a raffle's snapshot progress, seeds ["snapshot", raffle]
+pub fn snapshot_address(raffle: &Pubkey) -> Pubkey {
+    find_address(&[b"snapshot", raffle.as_ref()])
+}
+
+/// Address of a ticket number's collectible claim, seeds ["ticket_collectible", raffle, index]
+pub fn ticket_collectible_address(raffle: &Pubkey, ticket_index: u64) -> Pubkey {
+    find_address(&[
+        b"ticket_collectible",
+        raffle.as_ref(),
+        &ticket_index.to_le_bytes(),
+    ])
+}
+
+/// Address of a purchase's dedupe record,
+/// seeds ["purchase_dedupe", raffle, buyer, idempotency_key]
+pub fn purchase_dedupe_address(
+    raffle: &Pubkey,
+    buyer: &Pubkey,
+    idempotency_key: &[u8; 16],
+) -> Pubkey {
+    find_address(&[
+        b"purchase_dedupe",
+        raffle.as_ref(),
+        buyer.as_ref(),
+        idempotency_key,
+    ])
+}
+
+/// Address of a wallet's region attestation, seeds ["region_attestation", wallet]
+pub fn region_attestation_address(wallet: &Pubkey) -> Pubkey {
+    find_address(&[b"region_attestation", wallet.as_ref()])
+}
+
+/// Address of a wallet's age attestation, seeds ["age_attestation", wallet]
+pub fn age_attestation_address(wallet: &Pubkey) -> Pubkey {
+    find_address(&[b"age_attestation", wallet.as_ref()])
+}
+
+/// Address of a wallet's loyalty profile, seeds ["user_profile", wallet]
+pub fn user_profile_address(wallet: &Pubkey) -> Pubkey {
+    find_address(&[b"user_profile", wallet.as_ref()])
+}
+
+/// Address of a wallet's win notifications, seeds ["notifications", wallet]
+pub fn notifications_address(wallet: &Pubkey) -> Pubkey {
+    find_address(&[b"notifications", wallet.as_ref()])
+}
+
+/// Address of a wallet's participation proof in a raffle,
+/// seeds ["participation_proof", raffle, owner]
+pub fn participation_proof_address(raffle: &Pubkey, owner: &Pubkey) -> Pubkey {
+    find_address(&[b"participation_proof", raffle.as_ref(), owner.as_ref()])
+}
+
+/// Address of the bond deposited by a raffle's creator, seeds ["creator_bond", raffle]
+pub fn creator_bond_address(raffle: &Pubkey) -> Pubkey {
+    find_address(&[b"creator_bond", raffle.as_ref()])
+}
+
+/// Address of the receipt mint of an entry, seeds ["ticket_receipt", entry]
+pub fn ticket_receipt_mint_address(entry: &Pubkey) -> Pubkey {
+    find_address(&[b"ticket_receipt", entry.as_ref()])
+}
+
+/// Address of a wallet's creator role, seeds ["creator_role", creator]
+pub fn creator_role_address(creator: &Pubkey) -> Pubkey {
+    find_address(&[b"creator_role", creator.as_ref()])
+}
+
+/// Address of the authority of the program's lookup tables, seeds ["lookup_table_authority"]
+pub fn lookup_table_authority_address() -> Pubkey {
+    find_address(&[b"lookup_table_authority"])
+}
@@ -0,0 +1,275 @@
+//! Helpers turning raw entropy into unbiased random numbers, shared by every
+//! instruction that draws tickets, so all draws map entropy to tickets identically.
+
+use anchor_lang::prelude::*;
+
+use crate::error::RaffleError;
+
+/// Cryptographic mixing function with strong avalanche properties
+/// Each bit in the output has a ~50% chance of flipping when any input bit changes.
+/// Based on splitmix64 algorithm used in high-quality PRNGs.
+pub(crate) fn mix(a: u64, b: u64) -> u64 {
+    let mut z = a.wrapping_add(b);
+
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z = z ^ (z >> 31);
+
+    z
+}
+
+/// Maps a random number to a range without introducing statistical bias
+/// Standard modulo operations can bias results when the range isn't a power of 2.
+/// This function uses specialized techniques based on range size to ensure fairness.
+pub(crate) fn unbiased_range(x: u64, range: u64) -> Result<u64> {
+    if range == 0 {
+        return Err(RaffleError::Overflow.into());
+    }
+
+    // If range is a power of 2, we can use a simple mask which is unbiased
+    if range.is_power_of_two() {
+        return Ok(x & (range - 1));
+    }
+
+    // For small ranges, simple modulo is fine as bias is minimal
+    if range <= 256 {
+        return Ok(x % range);
+    }
+
+    // Find threshold value to ensure unbiased selection
+    let threshold = u64::MAX - (u64::MAX % range);
+
+    // Use rejection sampling with a limit on computational cost
+    let mut value = x;
+
+    // Cap iterations to ensure reasonable gas costs
+    const MAX_ATTEMPTS: u8 = 3;
+
+    for i in 0..MAX_ATTEMPTS {
+        // If value is below threshold, we can use modulo safely
+        if value < threshold {
+            return Ok(value % range);
+        }
+
+        // Try a new value with additional mixing
+        value = mix(value, value.wrapping_add(i as u64 + 1));
+    }
+
+    // Fallback case - the bias is minimal after the mixing operations
+    Ok(value % range)
+}
+
+/// Picks `count` distinct numbers below `range` from a single random value. The first
+/// pick maps `x` with `unbiased_range`, every further pick remixes the previous state,
+/// so picks that repeat an earlier one are rerolled deterministically.
+pub(crate) fn distinct_in_range(x: u64, range: u64, count: usize) -> Result<Vec<u64>> {
+    require!(count as u64 <= range, RaffleError::Overflow);
+
+    let mut picks = Vec::with_capacity(count);
+    let mut value = x;
+    let mut round = 0u64;
+    while picks.len() < count {
+        let pick = unbiased_range(value, range)?;
+        if !picks.contains(&pick) {
+            picks.push(pick);
+        }
+
+        round += 1;
+        value = mix(value, round);
+    }
+
+    Ok(picks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic stream of well mixed sample values
+    fn samples(seed: u64, count: u64) -> impl Iterator<Item = u64> {
+        (0..count).map(move |i| mix(seed, i))
+    }
+
+    /// Fails if any bucket of `range` deviates from the expected count by more than
+    /// five standard deviations over `count` samples
+    fn assert_uniform(range: u64, count: u64) {
+        let mut buckets = vec![0u64; range as usize];
+        for value in samples(range, count) {
+            buckets[unbiased_range(value, range).unwrap() as usize] += 1;
+        }
+
+        let expected = count as f64 / range as f64;
+        let tolerance = 5.0 * expected.sqrt() + 1.0;
+        for (ticket, hits) in buckets.iter().enumerate() {
+            assert!(
+                (*hits as f64 - expected).abs() <= tolerance,
+                "ticket {ticket} of {range} drawn {hits} times, expected {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn unbiased_range_rejects_empty_range() {
+        assert_eq!(
+            unbiased_range(42, 0).unwrap_err(),
+            RaffleError::Overflow.into()
+        );
+    }
+
+    #[test]
+    fn unbiased_range_stays_within_range() {
+        let ranges = [
+            1,
+            2,
+            3,
+            255,
+            256,
+            257,
+            1_000,
+            (1 << 32) + 1,
+            (1 << 63) + 1,
+            u64::MAX - 1,
+            u64::MAX,
+        ];
+        for range in ranges {
+            for value in samples(range, 1_000).chain([0, 1, u64::MAX - 1, u64::MAX]) {
+                assert!(unbiased_range(value, range).unwrap() < range);
+            }
+        }
+    }
+
+    #[test]
+    fn unbiased_range_masks_powers_of_two() {
+        for shift in 0..64 {
+            let range = 1u64 << shift;
+            for value in samples(shift, 100) {
+                assert_eq!(unbiased_range(value, range).unwrap(), value & (range - 1));
+            }
+        }
+    }
+
+    #[test]
+    fn unbiased_range_uses_modulo_below_the_rejection_threshold() {
+        let range = (1u64 << 63) + 1;
+        // The largest multiple of the range fitting a u64 is the range itself
+        let threshold = u64::MAX - (u64::MAX % range);
+        assert_eq!(threshold, range);
+
+        for value in [0, 1, range / 2, threshold - 1] {
+            assert_eq!(unbiased_range(value, range).unwrap(), value % range);
+        }
+    }
+
+    #[test]
+    fn unbiased_range_rerolls_values_above_the_rejection_threshold() {
+        let range = (1u64 << 63) + 1;
+        let value = u64::MAX - 1;
+
+        // The first reroll mixes the value with its successor and lands below the threshold
+        let rerolled = mix(value, value.wrapping_add(1));
+        assert!(rerolled < range);
+        assert_eq!(unbiased_range(value, range).unwrap(), rerolled);
+        assert_ne!(rerolled, value % range);
+    }
+
+    #[test]
+    fn unbiased_range_falls_back_to_modulo_after_the_last_reroll() {
+        let range = (1u64 << 63) + 1;
+        let value = range + 1;
+
+        // All three rerolls of this value land above the threshold
+        let mut rerolled = value;
+        for i in 0..3u64 {
+            assert!(rerolled >= range);
+            rerolled = mix(rerolled, rerolled.wrapping_add(i + 1));
+        }
+        assert_eq!(unbiased_range(value, range).unwrap(), rerolled % range);
+    }
+
+    #[test]
+    fn unbiased_range_is_uniform_for_small_ranges() {
+        for range in [2, 3, 7, 10, 64, 100, 256] {
+            assert_uniform(range, 100_000);
+        }
+    }
+
+    #[test]
+    fn unbiased_range_is_uniform_for_large_ranges() {
+        for range in [257, 1_000, 4_096] {
+            assert_uniform(range, 1_000_000);
+        }
+    }
+
+    #[test]
+    fn unbiased_range_is_uniform_around_the_rejection_threshold() {
+        // Two thirds of the u64 space lies below the threshold of this range, so the
+        // buckets are only balanced if the rejected third is rerolled
+        let range = u64::MAX / 3 * 2;
+        let mut lower_half = 0u64;
+        let count = 100_000u64;
+        for value in samples(range, count) {
+            if unbiased_range(value, range).unwrap() < range / 2 {
+                lower_half += 1;
+            }
+        }
+
+        let expected = count as f64 / 2.0;
+        assert!((lower_half as f64 - expected).abs() <= 5.0 * expected.sqrt());
+    }
+
+    #[test]
+    fn distinct_in_range_starts_with_the_single_pick() {
+        for value in samples(3, 100) {
+            let picks = distinct_in_range(value, 1_000, 5).unwrap();
+            assert_eq!(picks[0], unbiased_range(value, 1_000).unwrap());
+        }
+    }
+
+    #[test]
+    fn distinct_in_range_never_repeats_a_pick() {
+        for range in [1, 2, 3, 10, 1_000] {
+            for value in samples(range, 100) {
+                let count = range.min(10) as usize;
+                let mut picks = distinct_in_range(value, range, count).unwrap();
+                assert_eq!(picks, distinct_in_range(value, range, count).unwrap());
+
+                picks.sort_unstable();
+                picks.dedup();
+                assert_eq!(picks.len(), count);
+                assert!(picks.iter().all(|&pick| pick < range));
+            }
+        }
+    }
+
+    #[test]
+    fn distinct_in_range_rejects_more_picks_than_the_range() {
+        assert_eq!(
+            distinct_in_range(42, 3, 4).unwrap_err(),
+            RaffleError::Overflow.into()
+        );
+    }
+
+    #[test]
+    fn mix_flips_about_half_the_bits() {
+        let mut flipped = 0u64;
+        let mut trials = 0u64;
+        for a in samples(1, 100) {
+            for bit in 0..64 {
+                flipped += (mix(a, 7) ^ mix(a ^ (1 << bit), 7)).count_ones() as u64;
+                trials += 1;
+            }
+        }
+
+        let average = flipped as f64 / trials as f64;
+        assert!(
+            (30.0..=34.0).contains(&average),
+            "average of {average} bits flipped"
+        );
+    }
+
+    #[test]
+    fn mix_is_deterministic() {
+        assert_eq!(mix(1, 2), mix(1, 2));
+        assert_ne!(mix(1, 2), mix(2, 2));
+    }
+}
@@ -0,0 +1,146 @@
+//! Entropy sources of raffles drawn from a randomness provider.
+//!
+//! Raffles drawn with `RandomnessMode::Provider` select one of the providers registered
+//! in the config with set_randomness_provider. Providers drawing from randomness
+//! accounts, Switchboard and ORAO, are committed to with request_randomness before their
+//! value is revealed, while SlotHashes and CommitReveal raffles are drawn by
+//! fulfill_randomness alone. Every provider yields a 32 byte value the winning tickets
+//! are derived from with `derive_vrf_winning_tickets`, so a new entropy source only
+//! needs its commit and reveal functions here.
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::hash::{hash, hashv},
+};
+use arrayref::array_ref;
+use switchboard_on_demand::RandomnessAccountData;
+
+use crate::{error::RaffleError, time::current_slot};
+
+/// Offset of the randomness in an ORAO VRF randomness account, after the discriminator
+/// and the request seed
+const ORAO_RANDOMNESS_OFFSET: usize = 8 + 32;
+/// Length of the randomness in an ORAO VRF randomness account
+const ORAO_RANDOMNESS_LEN: usize = 64;
+
+/// Validates that `randomness_account` is a Switchboard On-Demand randomness account
+/// owned by `program_id` that was committed in the previous slot and not revealed yet,
+/// so nobody can know its value, and returns its commit slot. The commitment must not
+/// predate `min_draw_slot`, so a reset draw can't reuse the randomness it replaced
+pub(crate) fn commit_switchboard(
+    randomness_account: &AccountInfo,
+    program_id: &Pubkey,
+    min_draw_slot: u64,
+) -> Result<u64> {
+    require_keys_eq!(
+        *randomness_account.owner,
+        *program_id,
+        RaffleError::InvalidRandomnessAccount
+    );
+    let randomness = RandomnessAccountData::parse(randomness_account.data.borrow())
+        .map_err(|_| RaffleError::InvalidRandomnessAccount)?;
+
+    // Only a commitment from the previous slot is guaranteed to be unrevealed
+    let commit_slot = randomness.seed_slot;
+    require!(
+        commit_slot.checked_add(1) == Some(current_slot()?)
+            && randomness.reveal_slot <= commit_slot,
+        RaffleError::RandomnessNotFresh
+    );
+    require!(
+        commit_slot >= min_draw_slot,
+        RaffleError::RandomnessNotFresh
+    );
+
+    Ok(commit_slot)
+}
+
+/// Returns the value revealed into a Switchboard randomness account committed in
+/// `commit_slot`, and the slot it was revealed in
+pub(crate) fn reveal_switchboard(
+    randomness_account: &AccountInfo,
+    commit_slot: u64,
+) -> Result<([u8; 32], u64)> {
+    let randomness = RandomnessAccountData::parse(randomness_account.data.borrow())
+        .map_err(|_| RaffleError::InvalidRandomnessAccount)?;
+
+    // A recommitted account holds randomness the raffle never committed to
+    require!(
+        randomness.seed_slot == commit_slot,
+        RaffleError::InvalidRandomnessAccount
+    );
+    require!(
+        randomness.reveal_slot > randomness.seed_slot,
+        RaffleError::RandomnessNotRevealed
+    );
+
+    Ok((randomness.value, randomness.reveal_slot))
+}
+
+/// Validates that `randomness_account` is an ORAO VRF randomness account owned by
+/// `program_id` whose request was not fulfilled yet, so nobody can know its value, and
+/// returns the current slot as its commit slot
+pub(crate) fn commit_orao(randomness_account: &AccountInfo, program_id: &Pubkey) -> Result<u64> {
+    require!(
+        orao_randomness(randomness_account, program_id)?.is_none(),
+        RaffleError::RandomnessNotFresh
+    );
+    current_slot()
+}
+
+/// Returns the first 32 bytes of the randomness fulfilled into an ORAO VRF randomness
+/// account owned by `program_id`
+pub(crate) fn reveal_orao(
+    randomness_account: &AccountInfo,
+    program_id: &Pubkey,
+) -> Result<[u8; 32]> {
+    let randomness = orao_randomness(randomness_account, program_id)?
+        .ok_or(RaffleError::RandomnessNotRevealed)?;
+    Ok(*array_ref![randomness, 0, 32])
+}
+
+/// Reads the randomness of an ORAO VRF randomness account, laid out as the account
+/// discriminator, the 32 byte request seed and the 64 byte randomness, which stays zero
+/// until the oracles fulfill the request
+fn orao_randomness(
+    randomness_account: &AccountInfo,
+    program_id: &Pubkey,
+) -> Result<Option<[u8; ORAO_RANDOMNESS_LEN]>> {
+    require_keys_eq!(
+        *randomness_account.owner,
+        *program_id,
+        RaffleError::InvalidRandomnessAccount
+    );
+    let data = randomness_account.data.borrow();
+    require!(
+        data.len() >= ORAO_RANDOMNESS_OFFSET + ORAO_RANDOMNESS_LEN
+            && data[..8] == hash(b"account:Randomness").to_bytes()[..8],
+        RaffleError::InvalidRandomnessAccount
+    );
+
+    let randomness = *array_ref![data, ORAO_RANDOMNESS_OFFSET, ORAO_RANDOMNESS_LEN];
+    Ok((randomness != [0; ORAO_RANDOMNESS_LEN]).then_some(randomness))
+}
+
+/// Returns the value of a raffle drawn from the hash of the latest slot, mixed with the
+/// raffle's key so raffles drawn in the same slot don't share their entropy
+pub(crate) fn slot_hash_value(raffle: &Pubkey, slot_hash: &[u8; 32]) -> [u8; 32] {
+    hashv(&[slot_hash, raffle.as_ref()]).to_bytes()
+}
+
+/// Returns the value of a raffle drawn from a revealed `secret`, failing unless it
+/// hashes to the raffle's `commitment`. The value mixes in the raffle's minimum draw
+/// slot, so a reset draw doesn't land on the same tickets
+pub(crate) fn reveal_commitment(
+    raffle: &Pubkey,
+    commitment: Option<[u8; 32]>,
+    secret: Option<[u8; 32]>,
+    min_draw_slot: u64,
+) -> Result<[u8; 32]> {
+    let secret = secret.ok_or(RaffleError::InvalidRandomnessCommitment)?;
+    require!(
+        commitment == Some(hash(&secret).to_bytes()),
+        RaffleError::InvalidRandomnessCommitment
+    );
+    Ok(hashv(&[&secret, raffle.as_ref(), &min_draw_slot.to_le_bytes()]).to_bytes())
+}
@@ -0,0 +1,291 @@
+//! Transferable NFT receipts of ticket purchases.
+//!
+//! Raffles with ticket receipts enabled mint a Token-2022 NFT for every entry bought
+//! through buy_tickets, so tickets show up in wallets and can be traded on marketplaces.
+//! The receipt's mint is a PDA of the entry, and its on-mint metadata records the
+//! raffle, the entry's ticket range and the entry seed. Mint and metadata authorities
+//! are revoked once the single receipt token is minted, so the receipt is immutable.
+//!
+//! The prize of the entry's tickets follows the receipt: set_winner and claim_prize
+//! resolve the winner through [`receipt_holder`] instead of the entry's owner.
+
+use anchor_lang::{
+    prelude::*,
+    system_program::{create_account, CreateAccount},
+};
+use anchor_spl::{
+    associated_token::{self, AssociatedToken},
+    token_2022::{
+        self,
+        spl_token_2022::{
+            extension::ExtensionType, instruction::AuthorityType, state::Mint as MintState,
+        },
+        InitializeMint2, MintTo, SetAuthority, Token2022,
+    },
+    token_2022_extensions::{
+        metadata_pointer_initialize,
+        spl_pod::optional_keys::OptionalNonZeroPubkey,
+        spl_token_metadata_interface::state::{Field, TokenMetadata},
+        token_metadata_initialize, token_metadata_update_authority, token_metadata_update_field,
+        MetadataPointerInitialize, TokenMetadataInitialize, TokenMetadataUpdateAuthority,
+        TokenMetadataUpdateField,
+    },
+    token_interface::TokenAccount,
+};
+
+use crate::{
+    error::RaffleError,
+    math::{checked_add, checked_sub},
+    state::{Entry, Raffle},
+    transfer::rent_reserve,
+};
+
+/// Symbol of all ticket receipts
+pub const RECEIPT_SYMBOL: &str = "TICKET";
+
+/// Address and bump of the receipt mint of `entry`, the PDA with seeds
+/// ["ticket_receipt", entry]
+pub fn receipt_mint_address(entry: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"ticket_receipt", entry.as_ref()], &crate::ID)
+}
+
+/// Mints the receipt of a newly bought `entry` to the associated token account of
+/// `owner`, if the raffle issues ticket receipts. The owner pays the rent of the mint
+/// and of the token account.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn mint_ticket_receipt<'info>(
+    raffle: &Raffle,
+    entry: &Account<'info, Entry>,
+    owner: &Signer<'info>,
+    receipt_mint: Option<&UncheckedAccount<'info>>,
+    receipt_token_account: Option<&UncheckedAccount<'info>>,
+    token_program: Option<&Program<'info, Token2022>>,
+    associated_token_program: Option<&Program<'info, AssociatedToken>>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    if !raffle.ticket_receipts {
+        return Ok(());
+    }
+    let (
+        Some(receipt_mint),
+        Some(receipt_token_account),
+        Some(token_program),
+        Some(associated_token_program),
+    ) = (
+        receipt_mint,
+        receipt_token_account,
+        token_program,
+        associated_token_program,
+    )
+    else {
+        return err!(RaffleError::TicketReceiptRequired);
+    };
+
+    let entry_key = entry.key();
+    let (address, bump) = receipt_mint_address(&entry_key);
+    require_keys_eq!(
+        receipt_mint.key(),
+        address,
+        RaffleError::InvalidTicketReceipt
+    );
+    let signer_seeds: &[&[&[u8]]] = &[&[b"ticket_receipt", entry_key.as_ref(), &[bump]]];
+    let mint_info = receipt_mint.to_account_info();
+    let token_program_info = token_program.to_account_info();
+
+    let last_ticket = checked_sub(
+        checked_add(entry.ticket_start_index, entry.ticket_count)?,
+        1,
+    )?;
+    let metadata = TokenMetadata {
+        update_authority: OptionalNonZeroPubkey(address),
+        mint: address,
+        name: format!("Tickets #{}-{}", entry.ticket_start_index, last_ticket),
+        symbol: RECEIPT_SYMBOL.to_string(),
+        uri: raffle.metadata_uri.clone(),
+        additional_metadata: vec![
+            ("raffle".to_string(), entry.raffle.to_string()),
+            (
+                "ticket_start_index".to_string(),
+                entry.ticket_start_index.to_string(),
+            ),
+            ("ticket_count".to_string(), entry.ticket_count.to_string()),
+            (
+                "entry_seed".to_string(),
+                entry.seed.iter().map(|b| format!("{b:02x}")).collect(),
+            ),
+        ],
+    };
+
+    // The mint is created with room for the metadata pointer only. Token-2022 grows it
+    // to hold the metadata itself, which the rent paid up front already covers
+    let mint_len =
+        ExtensionType::try_calculate_account_len::<MintState>(&[ExtensionType::MetadataPointer])?;
+    create_account(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            CreateAccount {
+                from: owner.to_account_info(),
+                to: mint_info.clone(),
+            },
+            signer_seeds,
+        ),
+        rent_reserve(mint_len + metadata.tlv_size_of()?)?,
+        mint_len as u64,
+        &token_program.key(),
+    )?;
+    metadata_pointer_initialize(
+        CpiContext::new(
+            token_program_info.clone(),
+            MetadataPointerInitialize {
+                token_program_id: token_program_info.clone(),
+                mint: mint_info.clone(),
+            },
+        ),
+        None,
+        Some(address),
+    )?;
+    token_2022::initialize_mint2(
+        CpiContext::new(
+            token_program_info.clone(),
+            InitializeMint2 {
+                mint: mint_info.clone(),
+            },
+        ),
+        0,
+        &address,
+        None,
+    )?;
+
+    // The mint PDA is its own mint and metadata authority until the receipt is minted
+    token_metadata_initialize(
+        CpiContext::new_with_signer(
+            token_program_info.clone(),
+            TokenMetadataInitialize {
+                program_id: token_program_info.clone(),
+                metadata: mint_info.clone(),
+                update_authority: mint_info.clone(),
+                mint_authority: mint_info.clone(),
+                mint: mint_info.clone(),
+            },
+            signer_seeds,
+        ),
+        metadata.name,
+        metadata.symbol,
+        metadata.uri,
+    )?;
+    for (key, value) in metadata.additional_metadata {
+        token_metadata_update_field(
+            CpiContext::new_with_signer(
+                token_program_info.clone(),
+                TokenMetadataUpdateField {
+                    program_id: token_program_info.clone(),
+                    metadata: mint_info.clone(),
+                    update_authority: mint_info.clone(),
+                },
+                signer_seeds,
+            ),
+            Field::Key(key),
+            value,
+        )?;
+    }
+
+    // The associated token program only creates the account at the address derived
+    // from the owner and the mint
+    associated_token::create(CpiContext::new(
+        associated_token_program.to_account_info(),
+        associated_token::Create {
+            payer: owner.to_account_info(),
+            associated_token: receipt_token_account.to_account_info(),
+            authority: owner.to_account_info(),
+            mint: mint_info.clone(),
+            system_program: system_program.to_account_info(),
+            token_program: token_program_info.clone(),
+        },
+    ))?;
+    token_2022::mint_to(
+        CpiContext::new_with_signer(
+            token_program_info.clone(),
+            MintTo {
+                mint: mint_info.clone(),
+                to: receipt_token_account.to_account_info(),
+                authority: mint_info.clone(),
+            },
+            signer_seeds,
+        ),
+        1,
+    )?;
+
+    // Revoke both authorities, so no second receipt can be minted and the recorded
+    // ticket range can't be changed
+    token_2022::set_authority(
+        CpiContext::new_with_signer(
+            token_program_info.clone(),
+            SetAuthority {
+                current_authority: mint_info.clone(),
+                account_or_mint: mint_info.clone(),
+            },
+            signer_seeds,
+        ),
+        AuthorityType::MintTokens,
+        None,
+    )?;
+    token_metadata_update_authority(
+        CpiContext::new_with_signer(
+            token_program_info.clone(),
+            TokenMetadataUpdateAuthority {
+                program_id: token_program_info,
+                metadata: mint_info.clone(),
+                current_authority: mint_info.clone(),
+                new_authority: mint_info,
+            },
+            signer_seeds,
+        ),
+        OptionalNonZeroPubkey::default(),
+    )
+}
+
+/// Returns the wallet holding the receipt of `entry`, failing unless
+/// `receipt_token_account` is a token account holding it
+pub(crate) fn receipt_holder(
+    entry: &Pubkey,
+    receipt_token_account: Option<&TokenAccount>,
+) -> Result<Pubkey> {
+    let receipt_token_account = receipt_token_account.ok_or(RaffleError::TicketReceiptRequired)?;
+    require!(
+        receipt_token_account.mint == receipt_mint_address(entry).0
+            && receipt_token_account.amount == 1,
+        RaffleError::InvalidTicketReceipt
+    );
+    Ok(receipt_token_account.owner)
+}
+
+/// Fails unless `signer` holds the receipt of `entry` and the entry holds the winning
+/// ticket of the prize at `winner_index`. Records the signer as the winner of the prize,
+/// so a receipt sold after the draw passes the win on to its buyer.
+pub(crate) fn verify_receipt_winner(
+    raffle: &mut Account<Raffle>,
+    signer: &Pubkey,
+    entry: Option<&Account<Entry>>,
+    receipt_token_account: Option<&TokenAccount>,
+    winner_index: u8,
+) -> Result<()> {
+    let entry = entry.ok_or(RaffleError::TicketReceiptRequired)?;
+    require_keys_eq!(entry.raffle, raffle.key(), RaffleError::EntryRaffleMismatch);
+    let winning_ticket = match winner_index {
+        0 => raffle.winning_ticket,
+        _ => raffle.winning_tickets.get(winner_index as usize).copied(),
+    }
+    .ok_or(RaffleError::NoWinningTicket)?;
+    require!(
+        winning_ticket >= entry.ticket_start_index
+            && winning_ticket < checked_add(entry.ticket_start_index, entry.ticket_count)?,
+        RaffleError::InvalidWinningEntry
+    );
+    require_keys_eq!(
+        receipt_holder(&entry.key(), receipt_token_account)?,
+        *signer,
+        RaffleError::NotWinner
+    );
+
+    raffle.publish_winner(winner_index, *signer)
+}
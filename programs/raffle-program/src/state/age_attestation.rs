@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+// 8 discriminator + 32 wallet + 32 attestor + 8 expires_at + 1 bump
+pub const AGE_ATTESTATION_ACCOUNT_SIZE: usize = 8 + 32 + 32 + 8 + 1;
+
+// sha256("account:AgeAttestation")[..8]
+pub const AGE_ATTESTATION_DISCRIMINATOR: &[u8] = &[49, 235, 230, 81, 153, 63, 166, 95];
+
+/// Proof that a wallet's owner was verified to be 18 or older by the config's age
+/// attestor, stored at the PDA with seeds ["age_attestation", wallet]
+#[account(discriminator = AGE_ATTESTATION_DISCRIMINATOR)]
+pub struct AgeAttestation {
+    pub wallet: Pubkey,
+    /// The attestor that signed the attestation. Attestations stop being accepted once
+    /// the config's attestor is rotated
+    pub attestor: Pubkey,
+    /// Time after which the attestation is no longer accepted
+    pub expires_at: i64,
+    pub bump: u8,
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+/// Maximum number of entries shortlisted for a community vote
+pub const MAX_SHORTLIST: usize = 8;
+
+// 8 discriminator + 32 raffle + 32 vote_mint + 4 + MAX_SHORTLIST * 32 shortlist
+// + 4 + MAX_SHORTLIST * 8 tallies + 8 voting_ends_at + 1 bump
+pub const BALLOT_ACCOUNT_SIZE: usize =
+    8 + 32 + 32 + 4 + MAX_SHORTLIST * 32 + 4 + MAX_SHORTLIST * 8 + 8 + 1;
+
+// sha256("account:Ballot")[..8]
+pub const BALLOT_DISCRIMINATOR: &[u8] = &[3, 232, 121, 204, 232, 137, 138, 164];
+
+/// Community vote deciding the winner of a raffle drawn by votes, stored at the PDA with
+/// seeds ["ballot", raffle_key]. Votes are weighted by the tokens of `vote_mint` the
+/// voters lock in the ballot's associated token account until voting ends.
+#[account(discriminator = BALLOT_DISCRIMINATOR)]
+pub struct Ballot {
+    pub raffle: Pubkey,
+    /// Mint of the tokens votes are weighted by
+    pub vote_mint: Pubkey,
+    /// Entries the winner is chosen from
+    pub shortlist: Vec<Pubkey>,
+    /// Tokens voted for each shortlisted entry, in shortlist order
+    pub tallies: Vec<u64>,
+    /// Time after which no more votes are accepted and the vote can be tallied
+    pub voting_ends_at: i64,
+    pub bump: u8,
+}
+
+impl Ballot {
+    /// Index of the shortlisted entry with the most votes. Ties go to the entry
+    /// shortlisted first
+    pub fn leader(&self) -> usize {
+        self.tallies
+            .iter()
+            .enumerate()
+            .fold(0, |leader, (index, &tally)| {
+                if tally > self.tallies[leader] {
+                    index
+                } else {
+                    leader
+                }
+            })
+    }
+}
@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+/// Maximum number of raffles a bundle can include
+pub const MAX_BUNDLE_RAFFLES: usize = 5;
+
+// 8 discriminator + 8 bundle_id + 4 raffles length + MAX_BUNDLE_RAFFLES * 32 raffles
+// + 8 price + 1 bump
+pub const BUNDLE_ACCOUNT_SIZE: usize = 8 + 8 + 4 + MAX_BUNDLE_RAFFLES * 32 + 8 + 1;
+
+// sha256("account:Bundle")[..8]
+pub const BUNDLE_DISCRIMINATOR: &[u8] = &[15, 82, 167, 230, 37, 214, 82, 80];
+
+/// A promotion selling one ticket in each of a set of raffles for a discounted price,
+/// stored at the PDA with seeds ["bundle", bundle_id]
+#[account(discriminator = BUNDLE_DISCRIMINATOR)]
+pub struct Bundle {
+    pub bundle_id: u64,
+    /// The raffles a bundle purchase buys a ticket in
+    pub raffles: Vec<Pubkey>,
+    /// Price of the bundle in lamports, below the sum of the included ticket prices
+    pub price: u64,
+    pub bump: u8,
+}
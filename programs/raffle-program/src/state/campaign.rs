@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::RaffleError,
+    math::{checked_add, checked_sub},
+};
+
+// 8 discriminator + 8 campaign_id + 2 discount_bps + 8 budget + 8 spent + 1 bump
+pub const CAMPAIGN_ACCOUNT_SIZE: usize = 8 + 8 + 2 + 8 + 8 + 1;
+
+// sha256("account:Campaign")[..8]
+pub const CAMPAIGN_DISCRIMINATOR: &[u8] = &[50, 40, 49, 11, 157, 220, 229, 192];
+
+/// An operator-funded promotion discounting ticket purchases, stored at the PDA with
+/// seeds ["campaign", campaign_id]. The budget is escrowed in the campaign itself and
+/// discounts stop once it is spent, bounding the operator's liability.
+#[account(discriminator = CAMPAIGN_DISCRIMINATOR)]
+pub struct Campaign {
+    pub campaign_id: u64,
+    /// Share of the ticket cost in basis points paid from the budget
+    pub discount_bps: u16,
+    /// Lamports escrowed for discounts, excluding the campaign's rent
+    pub budget: u64,
+    /// Lamports of the budget paid out as discounts
+    pub spent: u64,
+    pub bump: u8,
+}
+
+impl Campaign {
+    /// Lamports of the budget that were not yet paid out
+    pub fn remaining(&self) -> Result<u64> {
+        checked_sub(self.budget, self.spent)
+    }
+
+    /// Records a discount paid from the budget, failing if it exceeds the remaining budget
+    pub fn record_spend(&mut self, amount: u64) -> Result<()> {
+        let spent = checked_add(self.spent, amount)?;
+        require!(spent <= self.budget, RaffleError::CampaignBudgetExhausted);
+        self.spent = spent;
+        Ok(())
+    }
+
+    /// Records a discount returned to the budget by the refund of the discounted tickets
+    pub fn record_return(&mut self, amount: u64) -> Result<()> {
+        self.spent = checked_sub(self.spent, amount)?;
+        Ok(())
+    }
+}
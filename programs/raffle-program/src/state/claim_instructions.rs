@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+/// Maximum length in bytes of a raffle's claim instructions
+pub const MAX_CLAIM_INSTRUCTIONS_LENGTH: usize = 256;
+
+// 8 discriminator + 32 raffle + (4 + 256) instructions + 33 content_hash + 1 bump
+pub const CLAIM_INSTRUCTIONS_ACCOUNT_SIZE: usize =
+    8 + 32 + 4 + MAX_CLAIM_INSTRUCTIONS_LENGTH + 33 + 1;
+
+// sha256("account:ClaimInstructions")[..8]
+pub const CLAIM_INSTRUCTIONS_DISCRIMINATOR: &[u8] = &[121, 66, 208, 18, 147, 5, 73, 232];
+
+/// How the winner of a raffle claims the prize, e.g. what data to encrypt and where to
+/// go, stored at the PDA with seeds ["claim_instructions", raffle_key]. Fixed before
+/// ticket sales start, so winners can verify the claim flow they are pointed to.
+#[account(discriminator = CLAIM_INSTRUCTIONS_DISCRIMINATOR)]
+pub struct ClaimInstructions {
+    pub raffle: Pubkey,
+    /// The claim instructions, or a URI to a document containing them
+    pub instructions: String,
+    /// SHA-256 hash of the document `instructions` points to, if it is a URI
+    pub content_hash: Option<[u8; 32]>,
+    pub bump: u8,
+}
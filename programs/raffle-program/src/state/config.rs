@@ -1,13 +1,420 @@
 use anchor_lang::prelude::*;
 
+use crate::{error::RaffleError, math::bps_of, state::Raffle};
+
 // 8 discriminator + 32 payout_authority + 32 management_authority + 32 upgrade_authority + 1 bump + 8 raffle_counter
-pub const CONFIG_ACCOUNT_SIZE: usize = 8 + 32 + 32 + 32 + 1 + 8;
+// + 1 require_prize_deposit + 8 claim_window_secs + 8 dispute_window_secs + 1 restrict_keepers
+// + 4 allowed_metadata_hosts length + MAX_METADATA_HOSTS * 32 host hashes + 2 refund_fee_bps
+// + 4 payout_destinations length + MAX_PAYOUT_DESTINATIONS * 32 destinations
+// + 4 supported_format_versions length + MAX_WINNER_DATA_FORMATS format versions
+// + 4 supported_encryption_schemes length + MAX_WINNER_DATA_FORMATS encryption schemes
+// + 33 region_attestor + 33 age_attestor
+// + 33 pending_management_authority + 33 pending_payout_authority + 33 pending_upgrade_authority
+// + 1 reject_cpi_purchases + 2 fee_bps + 33 fee_recipient + 1 compact_events + 1 paused
+// + 33 withdrawal_approver + 8 withdrawal_approval_threshold + 2 crank_reward_bps
+// + 2 referral_fee_bps + 2 alarm_coverage_bps + 8 alarm_min_shortfall
+// + 1 max_raffle_extensions + 8 max_extension_secs + 8 refund_window_secs + 33 insurance_fund
+// + 8 creator_bond + 8 large_purchase_threshold + 33 lookup_table
+// + 33 usd_price_feed + 33 notifier_program + 4 max_open_raffles + 4 open_raffle_count
+// + 4 randomness_providers length + MAX_RANDOMNESS_PROVIDERS * 33 registered providers
+// + 8 clock_offset + 33 identity_issuer
+// + 4 raffle_presets length + MAX_RAFFLE_PRESETS * 57 registered presets + 8 dispute_bond
+// + 33 collectible_tree
+pub const CONFIG_ACCOUNT_SIZE: usize = 8
+    + 32
+    + 32
+    + 32
+    + 1
+    + 8
+    + 1
+    + 8
+    + 8
+    + 1
+    + 4
+    + MAX_METADATA_HOSTS * 32
+    + 2
+    + 4
+    + MAX_PAYOUT_DESTINATIONS * 32
+    + 4
+    + MAX_WINNER_DATA_FORMATS
+    + 4
+    + MAX_WINNER_DATA_FORMATS
+    + 33
+    + 33
+    + 33
+    + 33
+    + 33
+    + 1
+    + 2
+    + 33
+    + 1
+    + 1
+    + 33
+    + 8
+    + 2
+    + 2
+    + 2
+    + 8
+    + 1
+    + 8
+    + 8
+    + 33
+    + 8
+    + 8
+    + 33
+    + 33
+    + 33
+    + 4
+    + 4
+    + 4
+    + MAX_RANDOMNESS_PROVIDERS * 33
+    + 8
+    + 33
+    + 4
+    + MAX_RAFFLE_PRESETS * 57
+    + 8
+    + 33;
+
+/// Maximum number of hostnames in the metadata host allowlist
+pub const MAX_METADATA_HOSTS: usize = 8;
+
+/// Maximum number of approved treasury withdrawal destinations
+pub const MAX_PAYOUT_DESTINATIONS: usize = 4;
+
+/// Maximum number of supported winner data format versions and encryption schemes each
+pub const MAX_WINNER_DATA_FORMATS: usize = 4;
+
+/// Maximum number of randomness providers registered in the config, one of each kind
+pub const MAX_RANDOMNESS_PROVIDERS: usize = 4;
+
+/// Maximum number of raffle presets registered in the config
+pub const MAX_RAFFLE_PRESETS: usize = 8;
+
+/// Maximum refund processing fee in basis points
+pub const MAX_REFUND_FEE_BPS: u16 = 200; // 2%
+
+/// Maximum crank reward in basis points of the ticket price
+pub const MAX_CRANK_REWARD_BPS: u16 = 100; // 1%
+
+/// Maximum referral fee in basis points of the referred purchases
+pub const MAX_REFERRAL_FEE_BPS: u16 = 1_000; // 10%
+
+/// Maximum total time a raffle's end time can be pushed back by with extend_raffle
+pub const MAX_EXTENSION_SECS: i64 = 30 * 24 * 60 * 60; // 30 days
 
-#[account]
+/// Shortest refund window, giving ticket holders of failed raffles time to reclaim
+/// their funds before they are forfeited
+pub const MIN_REFUND_WINDOW_SECS: i64 = 30 * 24 * 60 * 60; // 30 days
+
+/// Maximum platform fee on treasury withdrawals in basis points
+pub const MAX_PLATFORM_FEE_BPS: u16 = 2_000; // 20%
+
+/// Claim window applied until the management authority configures one
+pub const DEFAULT_CLAIM_WINDOW_SECS: i64 = 7 * 24 * 60 * 60; // 7 days
+/// Dispute window applied until the management authority configures one
+pub const DEFAULT_DISPUTE_WINDOW_SECS: i64 = 24 * 60 * 60; // 1 day
+/// Dispute bond applied until the upgrade authority configures one
+pub const DEFAULT_DISPUTE_BOND: u64 = 100_000_000; // 0.1 SOL
+/// Alarm coverage applied until the management authority configures one, alerting on
+/// any shortfall
+pub const DEFAULT_ALARM_COVERAGE_BPS: u16 = 10_000; // 100%
+/// Maximum alarm coverage in basis points of a treasury's tracked liabilities
+pub const MAX_ALARM_COVERAGE_BPS: u16 = 10_000; // 100%
+
+// sha256("account:Config")[..8]
+pub const CONFIG_DISCRIMINATOR: &[u8] = &[155, 12, 170, 224, 30, 250, 204, 130];
+
+#[account(discriminator = CONFIG_DISCRIMINATOR)]
 pub struct Config {
     pub payout_authority: Pubkey,
     pub management_authority: Pubkey,
     pub upgrade_authority: Pubkey,
     pub bump: u8,
     pub raffle_counter: u64,
+    /// Whether new raffles stay in AwaitingPrize state until their prize is deposited
+    pub require_prize_deposit: bool,
+    /// How long a drawn winner has to claim the prize before the raffle can be redrawn
+    pub claim_window_secs: i64,
+    /// How long after a draw the result can be disputed
+    pub dispute_window_secs: i64,
+    /// Whether draws and expiries can only be cranked by registered keepers
+    pub restrict_keepers: bool,
+    /// SHA-256 hashes of the lowercase hostnames HTTPS metadata URIs may point to.
+    /// Any host is allowed while the list is empty
+    pub allowed_metadata_hosts: Vec<[u8; 32]>,
+    /// Share of refunds in basis points retained by the treasury of expired raffles
+    /// created from now on, covering keeper and rent costs
+    pub refund_fee_bps: u16,
+    /// Accounts treasury withdrawals may be paid to. Withdrawals go to the payout
+    /// authority while the list is empty
+    pub payout_destinations: Vec<Pubkey>,
+    /// Winner data format versions winners can submit
+    pub supported_format_versions: Vec<u8>,
+    /// Encryption schemes winners can submit their data with
+    pub supported_encryption_schemes: Vec<u8>,
+    /// Key trusted to attest the regions of buyers of region restricted raffles
+    pub region_attestor: Option<Pubkey>,
+    /// Key trusted to attest that buyers of age restricted raffles are of legal age
+    pub age_attestor: Option<Pubkey>,
+    /// Successor proposed by the management authority, until it accepts the handover
+    pub pending_management_authority: Option<Pubkey>,
+    /// Successor proposed by the payout authority, until it accepts the handover
+    pub pending_payout_authority: Option<Pubkey>,
+    /// Successor proposed by the upgrade authority, until it accepts the handover
+    pub pending_upgrade_authority: Option<Pubkey>,
+    /// Whether buy_tickets rejects purchases invoked by other programs, so tickets can
+    /// only be bought by top-level instructions of end-user wallets
+    pub reject_cpi_purchases: bool,
+    /// Share of treasury withdrawals in basis points paid to `fee_recipient`, unless the
+    /// raffle overrides it
+    pub fee_bps: u16,
+    /// Account the platform fee on treasury withdrawals is paid to. No fee is charged
+    /// while unset
+    pub fee_recipient: Option<Pubkey>,
+    /// Whether events leave out free-form text such as metadata URIs and memos, which
+    /// indexers then read from the accounts, to keep transaction logs small
+    pub compact_events: bool,
+    /// Emergency switch halting raffle creation, ticket sales, draws and treasury
+    /// withdrawals. Refunds stay available so funds are never trapped
+    pub paused: bool,
+    /// Key that must approve treasury withdrawals above
+    /// `withdrawal_approval_threshold` with approve_withdrawal. No approval is required
+    /// while unset
+    pub withdrawal_approver: Option<Pubkey>,
+    /// Largest withdrawal, in lamports or base units of the raffle's payment mint, that
+    /// doesn't require the withdrawal approver's approval
+    pub withdrawal_approval_threshold: u64,
+    /// Share of the ticket price in basis points paid per ticket sold to whoever cranks
+    /// a raffle's expiry or draw, disclosed on raffles created afterwards
+    pub crank_reward_bps: u16,
+    /// Share of referred purchases in basis points paid to the referrer from the
+    /// treasury at withdrawal, disclosed on raffles created afterwards
+    pub referral_fee_bps: u16,
+    /// Share of a treasury's tracked liabilities in basis points its balance must cover
+    /// before check_invariants raises an alert. Alerts are disabled while zero
+    pub alarm_coverage_bps: u16,
+    /// Smallest shortfall, in lamports or base units of the raffle's payment mint, that
+    /// check_invariants raises an alert for, tolerating rounding dust
+    pub alarm_min_shortfall: u64,
+    /// Number of times extend_raffle can push back a raffle's end time. Raffles can't be
+    /// extended while zero
+    pub max_raffle_extensions: u8,
+    /// Total time in seconds extend_raffle can push back a raffle's end time by
+    pub max_extension_secs: i64,
+    /// How long ticket holders of raffles expiring or cancelled from now on can reclaim
+    /// their funds before sweep_unclaimed_refunds forfeits them. Refunds never lapse
+    /// while zero
+    pub refund_window_secs: i64,
+    /// Account forfeited refunds are swept to, instead of the payout authority
+    pub insurance_fund: Option<Pubkey>,
+    /// Lamports the creator of a raffle must bond at creation, slashed to the insurance
+    /// fund if the raffle is voided for fraud. No bond is required while zero
+    pub creator_bond: u64,
+    /// Lamports a single purchase must exceed for a LargePurchase event to be emitted
+    /// alongside TicketsPurchased. No such events are emitted while zero
+    pub large_purchase_threshold: u64,
+    /// Address lookup table holding the accounts most transactions of the program
+    /// reference, created with create_lookup_table
+    pub lookup_table: Option<Pubkey>,
+    /// Switchboard pull feed quoting the SOL price in USD, read by record_draw_value to
+    /// snapshot the USD value of drawn raffles
+    pub usd_price_feed: Option<Pubkey>,
+    /// Program invoked with lifecycle notifications of raffles, see `notifier`
+    pub notifier_program: Option<Pubkey>,
+    /// Maximum number of raffles that can be open at once, counting every raffle that
+    /// was created and hasn't expired, been cancelled, been claimed or been paid out.
+    /// Unlimited while zero
+    pub max_open_raffles: u32,
+    /// Number of raffles created that haven't expired, been cancelled, been claimed or
+    /// been paid out
+    pub open_raffle_count: u32,
+    /// Entropy sources raffles can select with set_randomness_provider, registered with
+    /// register_randomness_provider
+    pub randomness_providers: Vec<RegisteredRandomnessProvider>,
+    /// Seconds added to the cluster clock on devnet builds, set with set_clock_offset to
+    /// move raffles through their lifecycle in integration tests and demos
+    pub clock_offset: i64,
+    /// Key trusted to attest that winners hold a verifiable identity credential, letting
+    /// winners claim with submit_winner_attestation instead of submitting contact data
+    pub identity_issuer: Option<Pubkey>,
+    /// Named terms for routine raffles created with create_raffle_with_preset, registered
+    /// with set_raffle_preset
+    pub raffle_presets: Vec<RegisteredRafflePreset>,
+    /// Lamports a wallet bonds to open a dispute with open_dispute, slashed if the
+    /// dispute is rejected
+    pub dispute_bond: u64,
+    /// Bubblegum tree delegated to the config PDA that ticket collectibles are minted
+    /// into, see `bubblegum`
+    pub collectible_tree: Option<Pubkey>,
+}
+
+/// Entropy source a raffle drawn with `RandomnessMode::Provider` is drawn from by
+/// fulfill_randomness, see `randomness`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum RandomnessProvider {
+    /// The hash of the latest slot in the SlotHashes sysvar
+    SlotHashes = 0,
+    /// A Switchboard On-Demand randomness account committed with request_randomness
+    Switchboard = 1,
+    /// An ORAO VRF randomness account committed with request_randomness
+    Orao = 2,
+    /// A secret whose hash the management authority committed to before tickets were
+    /// sold, revealed once the sale closed
+    CommitReveal = 3,
+}
+
+/// Randomness provider registered in the config
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub struct RegisteredRandomnessProvider {
+    pub provider: RandomnessProvider,
+    /// Program owning the provider's randomness accounts. Unused by providers without
+    /// randomness accounts
+    pub program_id: Pubkey,
+}
+
+/// Terms of a routine raffle applied by create_raffle_with_preset
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub struct RafflePreset {
+    /// Seconds the raffle runs for, counted from its creation
+    pub duration_secs: i64,
+    pub ticket_price: u64,
+    pub min_tickets: u64,
+    pub max_tickets: Option<u64>,
+}
+
+/// Raffle preset registered in the config under a name, such as "weekly"
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub struct RegisteredRafflePreset {
+    /// UTF-8 name padded with zero bytes
+    pub name: [u8; 16],
+    pub preset: RafflePreset,
+}
+
+/// Authorities stored in the config, which are rotated with propose_authority and
+/// accept_authority
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum AuthorityRole {
+    Management = 0,
+    Payout = 1,
+    Upgrade = 2,
+}
+
+impl Config {
+    /// Counts a newly created raffle as open, failing if `max_open_raffles` raffles are
+    /// open already
+    pub fn record_raffle_opened(&mut self) -> Result<()> {
+        require!(
+            self.max_open_raffles == 0 || self.open_raffle_count < self.max_open_raffles,
+            RaffleError::OpenRaffleLimitReached
+        );
+        self.open_raffle_count = self
+            .open_raffle_count
+            .checked_add(1)
+            .ok_or(RaffleError::Overflow)?;
+        Ok(())
+    }
+
+    /// Stops counting `raffle` as open once it expires, is cancelled, is claimed or is
+    /// paid out, whichever comes first. Saturates, as raffles created before the count
+    /// was introduced were never counted
+    pub fn record_raffle_closed(&mut self, raffle: &mut Raffle) {
+        if raffle.open_slot_released {
+            return;
+        }
+        raffle.open_slot_released = true;
+        self.open_raffle_count = self.open_raffle_count.saturating_sub(1);
+    }
+
+    /// Program owning the randomness accounts of `provider`, failing unless the provider
+    /// is registered
+    pub fn randomness_program(&self, provider: RandomnessProvider) -> Result<Pubkey> {
+        self.randomness_providers
+            .iter()
+            .find(|registered| registered.provider == provider)
+            .map(|registered| registered.program_id)
+            .ok_or(RaffleError::RandomnessProviderNotRegistered.into())
+    }
+
+    /// Terms registered under `name`, failing unless the preset is registered
+    pub fn raffle_preset(&self, name: &[u8; 16]) -> Result<RafflePreset> {
+        self.raffle_presets
+            .iter()
+            .find(|registered| &registered.name == name)
+            .map(|registered| registered.preset)
+            .ok_or(RaffleError::RafflePresetNotFound.into())
+    }
+
+    /// Platform fee in basis points charged on withdrawals from `raffle`'s treasury
+    pub fn platform_fee_bps(&self, raffle: &Raffle) -> u16 {
+        match self.fee_recipient {
+            Some(_) => raffle.fee_bps_override.unwrap_or(self.fee_bps),
+            None => 0,
+        }
+    }
+
+    /// Whether withdrawing `amount` from a treasury requires the withdrawal approver's
+    /// approval
+    pub fn withdrawal_requires_approval(&self, amount: u64) -> bool {
+        self.withdrawal_approver.is_some() && amount > self.withdrawal_approval_threshold
+    }
+
+    /// Shortfall of a treasury holding `available` against its tracked `liabilities`
+    /// that check_invariants raises an alert for, if any
+    pub fn alarm_shortfall(&self, liabilities: u64, available: u64) -> Result<Option<u64>> {
+        let required = bps_of(liabilities, self.alarm_coverage_bps)?;
+        Ok(required
+            .checked_sub(available)
+            .filter(|shortfall| *shortfall > 0 && *shortfall >= self.alarm_min_shortfall))
+    }
+
+    /// Deadline for refunds of a raffle expiring or cancelled at `now`, or zero if its
+    /// refunds never lapse
+    pub fn refund_deadline(&self, now: i64) -> Result<i64> {
+        if self.refund_window_secs == 0 {
+            return Ok(0);
+        }
+        now.checked_add(self.refund_window_secs)
+            .ok_or_else(|| RaffleError::Overflow.into())
+    }
+
+    /// Account forfeited refunds are swept to
+    pub fn forfeiture_destination(&self) -> Pubkey {
+        self.insurance_fund.unwrap_or(self.payout_authority)
+    }
+
+    /// `text` to include in an event, or an empty string while events are compact
+    pub fn event_text(&self, text: String) -> String {
+        if self.compact_events {
+            String::new()
+        } else {
+            text
+        }
+    }
+
+    /// The current holder of `role`
+    pub fn authority(&self, role: AuthorityRole) -> Pubkey {
+        match role {
+            AuthorityRole::Management => self.management_authority,
+            AuthorityRole::Payout => self.payout_authority,
+            AuthorityRole::Upgrade => self.upgrade_authority,
+        }
+    }
+
+    /// The current holder of `role` and its proposed successor
+    pub fn authority_slots(&mut self, role: AuthorityRole) -> (&mut Pubkey, &mut Option<Pubkey>) {
+        match role {
+            AuthorityRole::Management => (
+                &mut self.management_authority,
+                &mut self.pending_management_authority,
+            ),
+            AuthorityRole::Payout => (
+                &mut self.payout_authority,
+                &mut self.pending_payout_authority,
+            ),
+            AuthorityRole::Upgrade => (
+                &mut self.upgrade_authority,
+                &mut self.pending_upgrade_authority,
+            ),
+        }
+    }
 }
@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+// 8 discriminator + 32 raffle + 32 depositor + 8 amount + 1 bump
+pub const CREATOR_BOND_ACCOUNT_SIZE: usize = 8 + 32 + 32 + 8 + 1;
+
+// sha256("account:CreatorBond")[..8]
+pub const CREATOR_BOND_DISCRIMINATOR: &[u8] = &[97, 223, 25, 152, 152, 8, 99, 142];
+
+/// Bond the creator of a raffle deposited at creation, held as lamports by the PDA with
+/// seeds ["creator_bond", raffle_key] on top of its rent. Returned to the depositor
+/// once the raffle is fulfilled, or slashed if the raffle is voided for fraud.
+#[account(discriminator = CREATOR_BOND_DISCRIMINATOR)]
+pub struct CreatorBond {
+    pub raffle: Pubkey,
+    /// Account that deposited the bond and the rent, refunded on release
+    pub depositor: Pubkey,
+    /// Bonded lamports, excluding the rent
+    pub amount: u64,
+    pub bump: u8,
+}
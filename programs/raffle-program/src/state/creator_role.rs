@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::RaffleError, state::Config};
+
+// 8 discriminator + 32 creator + 2 max_active_raffles + 8 max_ticket_price
+// + 2 active_raffles + 8 granted_at + 1 bump
+pub const CREATOR_ROLE_ACCOUNT_SIZE: usize = 8 + 32 + 2 + 8 + 2 + 8 + 1;
+
+// sha256("account:CreatorRole")[..8]
+pub const CREATOR_ROLE_DISCRIMINATOR: &[u8] = &[136, 156, 222, 242, 171, 215, 144, 226];
+
+/// Delegation allowing a creator wallet to create raffles and manage the raffles it
+/// created, granted by the management authority at the PDA with seeds
+/// ["creator_role", creator_key].
+#[account(discriminator = CREATOR_ROLE_DISCRIMINATOR)]
+pub struct CreatorRole {
+    pub creator: Pubkey,
+    /// Maximum number of the creator's raffles that can be open at once, counting every
+    /// raffle created until it is closed with close_raffle
+    pub max_active_raffles: u16,
+    /// Maximum ticket price of the creator's raffles, in lamports or base units of the
+    /// payment mint
+    pub max_ticket_price: u64,
+    /// Number of the creator's raffles created and not closed yet
+    pub active_raffles: u16,
+    /// When the role was last granted
+    pub granted_at: i64,
+    pub bump: u8,
+}
+
+/// Ensures `signer` may manage a raffle created by `raffle_creator`. The management
+/// authority manages every raffle, while a wallet holding a creator role only manages
+/// the raffles it created itself.
+pub fn require_raffle_manager(
+    config: &Config,
+    signer: &Signer,
+    raffle_creator: Option<Pubkey>,
+    creator_role: Option<&Account<CreatorRole>>,
+) -> Result<()> {
+    if signer.key() == config.management_authority {
+        return Ok(());
+    }
+
+    let Some(creator_role) = creator_role else {
+        return err!(RaffleError::NotProgramManagementAuthority);
+    };
+    require!(
+        creator_role.creator == signer.key() && raffle_creator == Some(signer.key()),
+        RaffleError::NotRaffleCreator
+    );
+
+    Ok(())
+}
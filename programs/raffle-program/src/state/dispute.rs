@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+// 8 discriminator + 32 raffle + 32 disputer + 8 amount + 32 reason_hash + 8 opened_at + 1 bump
+pub const DISPUTE_ACCOUNT_SIZE: usize = 8 + 32 + 32 + 8 + 32 + 8 + 1;
+
+// sha256("account:Dispute")[..8]
+pub const DISPUTE_DISCRIMINATOR: &[u8] = &[36, 49, 241, 67, 40, 36, 241, 74];
+
+/// Dispute of a raffle's draw opened by any wallet within the config's dispute window,
+/// holding the disputer's bond as lamports on top of its rent at the PDA with seeds
+/// ["dispute", raffle_key, disputer]. Resolved by the management authority with
+/// resolve_dispute, which refunds the bond if the dispute is upheld and slashes it
+/// otherwise.
+#[account(discriminator = DISPUTE_DISCRIMINATOR)]
+pub struct Dispute {
+    pub raffle: Pubkey,
+    /// Wallet that opened the dispute and paid the bond and the rent
+    pub disputer: Pubkey,
+    /// Bonded lamports, excluding the rent
+    pub amount: u64,
+    /// Hash of the disputer's off-chain statement of the grounds for the dispute
+    pub reason_hash: [u8; 32],
+    pub opened_at: i64,
+    pub bump: u8,
+}
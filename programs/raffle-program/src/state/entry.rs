@@ -1,10 +1,59 @@
 use anchor_lang::prelude::*;
 
+/// Maximum length in bytes of the memo attached to a purchase
+pub const MAX_MEMO_LENGTH: usize = 64;
+
 // 8 discriminator + 32 raffle + 32 owner + 8 ticket_count + 8 ticket_start_index + 8 seed
-pub const ENTRY_ACCOUNT_SIZE: usize = 8 + 32 + 32 + 8 + 8 + 8;
+pub const ENTRY_V1_ACCOUNT_SIZE: usize = 8 + 32 + 32 + 8 + 8 + 8;
+
+// ENTRY_V1_ACCOUNT_SIZE + 1 rent_sponsored + (1 + 4 + 64) memo + 33 next_entry
+// + 8 entry_index + 1 version + 1 bump + 8 created_at
+pub const ENTRY_ACCOUNT_SIZE: usize =
+    ENTRY_V1_ACCOUNT_SIZE + 1 + (1 + 4 + MAX_MEMO_LENGTH) + 33 + 8 + 1 + 1 + 8;
+
+/// Layout version of entries created by the program. Entries created before the
+/// version, bump and creation time were recorded are upgraded with
+/// `migrate_entry_v1_to_v2`
+pub const ENTRY_VERSION: u8 = 2;
 
-#[account]
+// sha256("account:Entry")[..8]
+pub const ENTRY_DISCRIMINATOR: &[u8] = &[63, 18, 152, 113, 215, 246, 221, 250];
+
+#[account(discriminator = ENTRY_DISCRIMINATOR)]
+#[derive(Default)]
 pub struct Entry {
+    pub raffle: Pubkey,
+    pub owner: Pubkey,
+    pub ticket_count: u64,
+    /// Index of the first ticket, or `UNNUMBERED_TICKET_START` while the raffle defers
+    /// numbering and `finalize_numbering` has not reached the entry
+    pub ticket_start_index: u64,
+    pub seed: [u8; 8],
+    /// Whether the account rent was reimbursed by the rent pool, in which case
+    /// the rent is returned to the pool when the entry is closed
+    pub rent_sponsored: bool,
+    /// Optional memo attached by the buyer, e.g. an order ID or campaign tag
+    pub memo: Option<String>,
+    /// The owner's previously created entry in the same raffle, `None` for the first entry
+    pub next_entry: Option<Pubkey>,
+    /// Position of the entry in the raffle's entry creation order, in which deferred
+    /// ticket numbers are assigned
+    pub entry_index: u64,
+    /// Layout version of the account, `ENTRY_VERSION` for entries created or migrated
+    /// by the current program
+    pub version: u8,
+    /// Bump of the entry PDA with seeds ["entry", raffle_key, seed]
+    pub bump: u8,
+    /// When the entry was created, or 0 for entries migrated from the v1 layout, which
+    /// didn't record it
+    pub created_at: i64,
+}
+
+/// Layout of entries created by the first deployed program, before the purchase
+/// details, the version, bump and creation time were recorded, read by
+/// `migrate_entry_v1_to_v2`
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct EntryV1 {
     pub raffle: Pubkey,
     pub owner: Pubkey,
     pub ticket_count: u64,
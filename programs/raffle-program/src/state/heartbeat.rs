@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+// 8 discriminator + 32 keeper + 8 last_processed_slot + 8 pending_draws
+// + 8 pending_expiries + 8 pending_closures + 8 reported_slot + 8 reported_at + 1 bump
+pub const HEARTBEAT_ACCOUNT_SIZE: usize = 8 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 1;
+
+// sha256("account:Heartbeat")[..8]
+pub const HEARTBEAT_DISCRIMINATOR: &[u8] = &[106, 180, 216, 51, 176, 116, 67, 49];
+
+/// Latest health report of a keeper, stored at the PDA with seeds
+/// ["heartbeat", keeper_key]. A stale `reported_slot` or growing pending counts
+/// indicate a stalled finalization pipeline.
+#[account(discriminator = HEARTBEAT_DISCRIMINATOR)]
+pub struct Heartbeat {
+    pub keeper: Pubkey,
+    /// The last slot the keeper has processed raffles up to
+    pub last_processed_slot: u64,
+    /// Raffles the keeper has seen ended but not yet drawn
+    pub pending_draws: u64,
+    /// Raffles the keeper has seen ended below threshold but not yet expired
+    pub pending_expiries: u64,
+    /// Concluded raffles with entries still waiting to be closed
+    pub pending_closures: u64,
+    /// Slot and time of the report
+    pub reported_slot: u64,
+    pub reported_at: i64,
+    pub bump: u8,
+}
@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+// 8 discriminator + 32 wallet + 32 issuer + 32 credential_hash + 8 expires_at + 1 bump
+pub const IDENTITY_ATTESTATION_ACCOUNT_SIZE: usize = 8 + 32 + 32 + 32 + 8 + 1;
+
+// sha256("account:IdentityAttestation")[..8]
+pub const IDENTITY_ATTESTATION_DISCRIMINATOR: &[u8] = &[151, 136, 164, 76, 84, 171, 65, 139];
+
+/// Proof that a wallet's owner holds a verifiable credential issued off-chain by the
+/// config's identity issuer, stored at the PDA with seeds ["identity_attestation", wallet]
+#[account(discriminator = IDENTITY_ATTESTATION_DISCRIMINATOR)]
+pub struct IdentityAttestation {
+    pub wallet: Pubkey,
+    /// The issuer that signed the attestation. Attestations stop being accepted once
+    /// the config's issuer is rotated
+    pub issuer: Pubkey,
+    /// Hash of the credential the issuer verified, so the operator can look up the
+    /// winner's identity with the issuer instead of receiving contact data
+    pub credential_hash: [u8; 32],
+    /// Time after which the attestation is no longer accepted
+    pub expires_at: i64,
+    pub bump: u8,
+}
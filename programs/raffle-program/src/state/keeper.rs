@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::RaffleError, state::Config};
+
+// 8 discriminator + 32 keeper + 8 registered_at + 1 bump
+pub const KEEPER_ACCOUNT_SIZE: usize = 8 + 32 + 8 + 1;
+
+// sha256("account:Keeper")[..8]
+pub const KEEPER_DISCRIMINATOR: &[u8] = &[127, 221, 194, 46, 120, 73, 144, 77];
+
+/// Registration of a keeper allowed to crank draws and expiries while the config
+/// restricts them, created at the PDA with seeds ["keeper", keeper_key].
+#[account(discriminator = KEEPER_DISCRIMINATOR)]
+pub struct Keeper {
+    pub keeper: Pubkey,
+    pub registered_at: i64,
+    pub bump: u8,
+}
+
+/// Ensures the caller of a crank is a registered keeper when the config restricts
+/// cranks to keepers. Anyone may call the crank otherwise.
+pub fn require_keeper(
+    config: &Config,
+    keeper: Option<&Signer>,
+    keeper_registration: Option<&Account<Keeper>>,
+) -> Result<()> {
+    if !config.restrict_keepers {
+        return Ok(());
+    }
+
+    let (Some(keeper), Some(keeper_registration)) = (keeper, keeper_registration) else {
+        return err!(RaffleError::KeeperNotRegistered);
+    };
+    require_keys_eq!(
+        keeper_registration.keeper,
+        keeper.key(),
+        RaffleError::KeeperNotRegistered
+    );
+
+    Ok(())
+}
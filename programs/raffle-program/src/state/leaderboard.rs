@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+/// Maximum number of holders the prize of a raffle can be split among
+pub const MAX_TOP_HOLDERS: u8 = 10;
+
+// 8 discriminator + 32 raffle + 4 holders length + MAX_TOP_HOLDERS * (32 owner
+// + 8 ticket_count) + 1 bump
+pub const LEADERBOARD_ACCOUNT_SIZE: usize = 8 + 32 + 4 + MAX_TOP_HOLDERS as usize * (32 + 8) + 1;
+
+// sha256("account:Leaderboard")[..8]
+pub const LEADERBOARD_DISCRIMINATOR: &[u8] = &[247, 186, 238, 243, 194, 30, 9, 36];
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct TopHolder {
+    pub owner: Pubkey,
+    /// The holder's total ticket balance in the raffle
+    pub ticket_count: u64,
+}
+
+/// The largest ticket holders of a raffle that splits its prize among its top holders,
+/// stored at the PDA with seeds ["leaderboard", raffle_key]. Kept up to date by every
+/// purchase, so the split can be computed at close without walking all entries.
+#[account(discriminator = LEADERBOARD_DISCRIMINATOR)]
+pub struct Leaderboard {
+    pub raffle: Pubkey,
+    /// Holders in no particular order
+    pub holders: Vec<TopHolder>,
+    pub bump: u8,
+}
+
+impl Leaderboard {
+    /// Records the new ticket balance of `owner`, keeping at most `capacity` holders.
+    /// Balances only grow, so replacing the smallest holder keeps the board exact.
+    pub fn record(&mut self, owner: Pubkey, ticket_count: u64, capacity: usize) {
+        if let Some(holder) = self.holders.iter_mut().find(|h| h.owner == owner) {
+            holder.ticket_count = ticket_count;
+            return;
+        }
+
+        if self.holders.len() < capacity {
+            self.holders.push(TopHolder {
+                owner,
+                ticket_count,
+            });
+            return;
+        }
+
+        if let Some(smallest) = self.holders.iter_mut().min_by_key(|h| h.ticket_count) {
+            if ticket_count > smallest.ticket_count {
+                *smallest = TopHolder {
+                    owner,
+                    ticket_count,
+                };
+            }
+        }
+    }
+}
@@ -1,13 +1,139 @@
+//! Program account state.
+//!
+//! Every account pins its 8-byte discriminator in a `*_DISCRIMINATOR` constant instead of
+//! relying on Anchor deriving it from the struct name. The constants hold the values
+//! Anchor derived from the original names, so structs can be renamed without making
+//! deployed accounts unreadable. Never change a pinned value of a deployed account.
+
+pub use age_attestation::*;
+pub use ballot::*;
+pub use bundle::*;
+pub use campaign::*;
+pub use claim_instructions::*;
 pub use config::*;
+pub use creator_bond::*;
+pub use creator_role::*;
+pub use dispute::*;
 pub use entry::*;
+pub use heartbeat::*;
+pub use identity_attestation::*;
+pub use keeper::*;
+pub use leaderboard::*;
+pub use notifications::*;
+pub use participation_proof::*;
+pub use prize_info::*;
+pub use prize_vault::*;
+pub use purchase_dedupe::*;
 pub use raffle::*;
+pub use referral_stats::*;
+pub use recovery_request::*;
+pub use region_attestation::*;
+pub use rent_pool::*;
+pub use reservation::*;
+pub use results::*;
+pub use snapshot::*;
+pub use snapshot_claim::*;
+pub use sponsor_match::*;
 pub use ticket_balance::*;
+pub use ticket_collectible::*;
 pub use treasury::*;
+pub use user_profile::*;
+pub use vote::*;
 pub use winner_data::*;
 
+pub mod age_attestation;
+pub mod ballot;
+pub mod bundle;
+pub mod campaign;
+pub mod claim_instructions;
 pub mod config;
+pub mod creator_bond;
+pub mod creator_role;
+pub mod dispute;
 pub mod entry;
+pub mod heartbeat;
+pub mod identity_attestation;
+pub mod keeper;
+pub mod leaderboard;
+pub mod notifications;
+pub mod participation_proof;
+pub mod prize_info;
+pub mod prize_vault;
+pub mod purchase_dedupe;
 pub mod raffle;
+pub mod referral_stats;
+pub mod recovery_request;
+pub mod region_attestation;
+pub mod rent_pool;
+pub mod reservation;
+pub mod results;
+pub mod snapshot;
+pub mod snapshot_claim;
+pub mod sponsor_match;
 pub mod ticket_balance;
+pub mod ticket_collectible;
 pub mod treasury;
+pub mod user_profile;
+pub mod vote;
 pub mod winner_data;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::{solana_program::hash::hash, Discriminator};
+
+    fn derived(name: &str) -> Vec<u8> {
+        hash(format!("account:{name}").as_bytes()).to_bytes()[..8].to_vec()
+    }
+
+    #[test]
+    fn pinned_discriminators_match_original_account_names() {
+        let pinned: [(&[u8], &str); 35] = [
+            (AGE_ATTESTATION_DISCRIMINATOR, "AgeAttestation"),
+            (BALLOT_DISCRIMINATOR, "Ballot"),
+            (BUNDLE_DISCRIMINATOR, "Bundle"),
+            (CAMPAIGN_DISCRIMINATOR, "Campaign"),
+            (CLAIM_INSTRUCTIONS_DISCRIMINATOR, "ClaimInstructions"),
+            (CONFIG_DISCRIMINATOR, "Config"),
+            (CREATOR_BOND_DISCRIMINATOR, "CreatorBond"),
+            (CREATOR_ROLE_DISCRIMINATOR, "CreatorRole"),
+            (DISPUTE_DISCRIMINATOR, "Dispute"),
+            (ENTRY_DISCRIMINATOR, "Entry"),
+            (HEARTBEAT_DISCRIMINATOR, "Heartbeat"),
+            (IDENTITY_ATTESTATION_DISCRIMINATOR, "IdentityAttestation"),
+            (KEEPER_DISCRIMINATOR, "Keeper"),
+            (LEADERBOARD_DISCRIMINATOR, "Leaderboard"),
+            (NOTIFICATIONS_DISCRIMINATOR, "Notifications"),
+            (PARTICIPATION_PROOF_DISCRIMINATOR, "ParticipationProof"),
+            (PRIZE_INFO_DISCRIMINATOR, "PrizeInfo"),
+            (PRIZE_VAULT_DISCRIMINATOR, "PrizeVault"),
+            (PURCHASE_DEDUPE_DISCRIMINATOR, "PurchaseDedupe"),
+            (RAFFLE_DISCRIMINATOR, "Raffle"),
+            (RECOVERY_REQUEST_DISCRIMINATOR, "RecoveryRequest"),
+            (REFERRAL_STATS_DISCRIMINATOR, "ReferralStats"),
+            (REGION_ATTESTATION_DISCRIMINATOR, "RegionAttestation"),
+            (RENT_POOL_DISCRIMINATOR, "RentPool"),
+            (RESERVATION_DISCRIMINATOR, "Reservation"),
+            (RESULTS_DISCRIMINATOR, "Results"),
+            (SNAPSHOT_DISCRIMINATOR, "Snapshot"),
+            (SNAPSHOT_CLAIM_DISCRIMINATOR, "SnapshotClaim"),
+            (SPONSOR_MATCH_DISCRIMINATOR, "SponsorMatch"),
+            (TICKET_BALANCE_DISCRIMINATOR, "TicketBalance"),
+            (TICKET_COLLECTIBLE_DISCRIMINATOR, "TicketCollectible"),
+            (TREASURY_DISCRIMINATOR, "Treasury"),
+            (USER_PROFILE_DISCRIMINATOR, "UserProfile"),
+            (VOTE_DISCRIMINATOR, "Vote"),
+            (WINNER_DATA_DISCRIMINATOR, "WinnerData"),
+        ];
+        for (discriminator, name) in pinned {
+            assert_eq!(discriminator, derived(name), "{name}");
+        }
+    }
+
+    #[test]
+    fn accounts_use_pinned_discriminators() {
+        assert_eq!(Raffle::DISCRIMINATOR, RAFFLE_DISCRIMINATOR);
+        assert_eq!(Entry::DISCRIMINATOR, ENTRY_DISCRIMINATOR);
+        assert_eq!(Treasury::DISCRIMINATOR, TREASURY_DISCRIMINATOR);
+    }
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::math::checked_add;
+
+/// Maximum number of records a wallet's notifications keep, oldest first
+pub const MAX_NOTIFICATIONS: usize = 16;
+
+// 8 discriminator + 32 wallet + 8 total_count + 4 records length
+// + MAX_NOTIFICATIONS * (32 raffle + 8 won_at) + 1 bump
+pub const NOTIFICATIONS_ACCOUNT_SIZE: usize = 8 + 32 + 8 + 4 + MAX_NOTIFICATIONS * (32 + 8) + 1;
+
+// sha256("account:Notifications")[..8]
+pub const NOTIFICATIONS_DISCRIMINATOR: &[u8] = &[30, 176, 182, 182, 168, 242, 223, 233];
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct WinNotification {
+    /// The raffle the wallet won a prize of
+    pub raffle: Pubkey,
+    /// Time the wallet was set as winner
+    pub won_at: i64,
+}
+
+/// Recent wins of a wallet, stored at the PDA with seeds ["notifications", wallet].
+/// Wallets and apps poll this single account to learn about new wins instead of
+/// scanning every raffle.
+#[account(discriminator = NOTIFICATIONS_DISCRIMINATOR)]
+pub struct Notifications {
+    pub wallet: Pubkey,
+    /// Number of records ever appended, so pollers can tell whether new records arrived
+    /// even once the oldest ones were dropped
+    pub total_count: u64,
+    /// The most recent records, oldest first
+    pub records: Vec<WinNotification>,
+    pub bump: u8,
+}
+
+impl Notifications {
+    /// Appends a win record, dropping the oldest record once `MAX_NOTIFICATIONS` are kept
+    pub fn record(&mut self, raffle: Pubkey, won_at: i64) -> Result<()> {
+        if self.records.len() >= MAX_NOTIFICATIONS {
+            self.records.remove(0);
+        }
+        self.records.push(WinNotification { raffle, won_at });
+        self.total_count = checked_add(self.total_count, 1)?;
+        Ok(())
+    }
+}
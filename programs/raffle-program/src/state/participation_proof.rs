@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+// 8 discriminator + 32 raffle + 32 owner + 8 ticket_count + 8 claimed_at + 1 bump
+pub const PARTICIPATION_PROOF_ACCOUNT_SIZE: usize = 8 + 32 + 32 + 8 + 8 + 1;
+
+// sha256("account:ParticipationProof")[..8]
+pub const PARTICIPATION_PROOF_DISCRIMINATOR: &[u8] = &[32, 84, 249, 218, 224, 115, 32, 0];
+
+/// Proof that a wallet participated in a drawn raffle, created at the PDA with seeds
+/// ["participation_proof", raffle_key, owner]. Partners can derive the address of any
+/// wallet and airdrop to it if the account exists with enough tickets, without relying
+/// on an indexer.
+#[account(discriminator = PARTICIPATION_PROOF_DISCRIMINATOR)]
+pub struct ParticipationProof {
+    pub raffle: Pubkey,
+    pub owner: Pubkey,
+    /// Number of tickets the owner held when the raffle was drawn
+    pub ticket_count: u64,
+    pub claimed_at: i64,
+    pub bump: u8,
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+/// Maximum length in bytes of a prize title
+pub const MAX_PRIZE_TITLE_LENGTH: usize = 64;
+
+// 8 discriminator + 32 raffle + (4 + 64) title + 8 value_estimate + 1 prize_type
+// + 33 escrow + 1 bump
+pub const PRIZE_INFO_ACCOUNT_SIZE: usize = 8 + 32 + 4 + MAX_PRIZE_TITLE_LENGTH + 8 + 1 + 33 + 1;
+
+// sha256("account:PrizeInfo")[..8]
+pub const PRIZE_INFO_DISCRIMINATOR: &[u8] = &[121, 177, 177, 245, 35, 88, 137, 145];
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum PrizeType {
+    Sol = 0,
+    Token = 1,
+    Nft = 2,
+    Physical = 3,
+    Other = 4,
+}
+
+/// Structured description of a raffle's prize, stored next to the raffle at the PDA
+/// with seeds ["prize_info", raffle_key] so clients can query prize details without
+/// parsing the metadata JSON.
+#[account(discriminator = PRIZE_INFO_DISCRIMINATOR)]
+pub struct PrizeInfo {
+    pub raffle: Pubkey,
+    pub title: String,
+    /// Estimated prize value in lamports
+    pub value_estimate: u64,
+    pub prize_type: PrizeType,
+    /// The account escrowing the prize, e.g. the raffle's prize vault
+    pub escrow: Option<Pubkey>,
+    pub bump: u8,
+}
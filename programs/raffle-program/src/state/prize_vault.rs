@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+// 8 discriminator + 32 raffle + 8 amount + 1 bump + 33 prize_mint
+pub const PRIZE_VAULT_ACCOUNT_SIZE: usize = 8 + 32 + 8 + 1 + 33;
+
+// sha256("account:PrizeVault")[..8]
+pub const PRIZE_VAULT_DISCRIMINATOR: &[u8] = &[34, 226, 195, 160, 248, 75, 50, 7];
+
+/// Escrow holding the prize deposited for a raffle, giving buyers on-chain proof
+/// that the prize exists before they pay. The winner claims it with claim_prize.
+///
+/// SOL prizes are held by the vault itself. SPL token prizes, including NFTs, are held
+/// by the vault's associated token account for the prize mint.
+#[account(discriminator = PRIZE_VAULT_DISCRIMINATOR)]
+pub struct PrizeVault {
+    pub raffle: Pubkey,
+    /// Prize value escrowed in lamports, or in base units of the prize mint
+    pub amount: u64,
+    pub bump: u8,
+    /// Mint of the escrowed SPL token prize, or None for a SOL prize
+    pub prize_mint: Option<Pubkey>,
+}
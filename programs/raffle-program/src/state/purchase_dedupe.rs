@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+// 8 discriminator + 32 raffle + 32 buyer + 32 entry + 1 bump
+pub const PURCHASE_DEDUPE_ACCOUNT_SIZE: usize = 8 + 32 + 32 + 32 + 1;
+
+// sha256("account:PurchaseDedupe")[..8]
+pub const PURCHASE_DEDUPE_DISCRIMINATOR: &[u8] = &[47, 233, 44, 150, 75, 3, 28, 157];
+
+/// Record of a purchase made with a client-supplied idempotency key, created at the
+/// PDA with seeds ["purchase_dedupe", raffle_key, buyer_key, idempotency_key]. A retry
+/// with the same key fails because the record already exists.
+#[account(discriminator = PURCHASE_DEDUPE_DISCRIMINATOR)]
+pub struct PurchaseDedupe {
+    pub raffle: Pubkey,
+    pub buyer: Pubkey,
+    /// The entry created by the original purchase
+    pub entry: Pubkey,
+    pub bump: u8,
+}
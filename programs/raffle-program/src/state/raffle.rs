@@ -1,5 +1,11 @@
 use anchor_lang::prelude::*;
 
+use crate::{
+    error::RaffleError,
+    math::{bps_of, checked_add, checked_sub, share_bps, ticket_cost},
+    state::{RandomnessProvider, TicketBalance},
+};
+
 // Space calculation:
 // 8 (discriminator) +
 // 32 (treasury) +
@@ -13,9 +19,191 @@ use anchor_lang::prelude::*;
 // 8 (end_time) +
 // 1 (raffle_state) +
 // 33 (winner_address: Option<Pubkey>) +
-// 9 (winning_ticket: Option<u64>) =
-// 383 total bytes
-pub const RAFFLE_ACCOUNT_SIZE: usize = 8 + 32 + 4 + 256 + 8 + 8 + 8 + 9 + 8 + 8 + 1 + 33 + 9;
+// 9 (winning_ticket: Option<u64>) +
+// 9 (draw_slot: Option<u64>) +
+// 33 (draw_slot_hash: Option<[u8; 32]>) +
+// 9 (draw_timestamp: Option<i64>) +
+// 9 (max_per_purchase: Option<u64>) +
+// 8 (participant_count) +
+// 1 (anonymize_winner) +
+// 33 (winner_hash: Option<[u8; 32]>) +
+// 8 (event_seq) +
+// 9 (min_revenue: Option<u64>) +
+// 1 (collectible) +
+// 8 (min_draw_slot) +
+// 3 (cash_prize_bps: Option<u16>) +
+// 2 (top_holders: Option<u8>) +
+// 8 (reserved_tickets) +
+// 2 (refund_fee_bps) +
+// 32 (rent_payer) +
+// 4 (features) +
+// 8 (allowed_regions) +
+// 1 (age_restricted) +
+// 33 (draw_authority: Option<Pubkey>) +
+// 1 (priority_min_tier) +
+// 8 (priority_until) +
+// 9 (min_unique_buyers: Option<u64>) +
+// 33 (payment_mint: Option<Pubkey>) +
+// 1 (randomness_mode) +
+// 33 (randomness_account: Option<Pubkey>) +
+// 8 (randomness_commit_slot) +
+// 1 (prize_escrowed) +
+// 1 (winner_count) +
+// 4 + 8 * MAX_WINNERS (winning_tickets: Vec<u64>) +
+// 4 + 32 * (MAX_WINNERS - 1) (additional_winners: Vec<Pubkey>) +
+// 2 (winner_data_submitted) +
+// 2 (prizes_claimed) +
+// 9 (max_tickets_per_wallet: Option<u64>) +
+// 42 (gate: Option<Gate>) +
+// 1 (deferred_numbering) +
+// 8 (entry_count) +
+// 8 (numbered_entries) +
+// 8 (numbered_tickets) +
+// 3 (fee_bps_override: Option<u16>) +
+// 33 (snapshot_root: Option<[u8; 32]>) +
+// 1 (operator_status) +
+// 33 (status_note_hash: Option<[u8; 32]>) +
+// 9 (funding_deadline: Option<i64>) +
+// 4 + 16 * MAX_PRICING_TIERS (pricing_tiers: Vec<PricingTier>) +
+// 8 (tier_discounts) +
+// 2 (crank_reward_bps) +
+// 2 (referral_fee_bps) +
+// 1 (extension_count) +
+// 8 (extended_secs) +
+// 8 (refund_deadline) +
+// 9 (prize_value_usd_cents: Option<u64>) +
+// 9 (ticket_count_display_cap: Option<u64>) +
+// 1 (ticket_receipts) +
+// 33 (arweave_tx_id: Option<[u8; 32]>) +
+// 33 (creator: Option<Pubkey>) +
+// 2 (draw_delay_slots) +
+// 8 (draw_target_slot) +
+// 33 (refund_mint: Option<Pubkey>) +
+// 8 (refund_rate) +
+// 8 (draw_value_lamports) +
+// 9 (draw_usd_rate: Option<u64>) +
+// 33 (successor: Option<Pubkey>) +
+// 2 (randomness_provider: Option<RandomnessProvider>) +
+// 33 (randomness_commitment: Option<[u8; 32]>) +
+// 33 (access_code_hash: Option<[u8; 32]>) +
+// 1 (close_reason: u8) +
+// 1 (crank_reward_paid: bool) +
+// 4 + 33 * (MAX_WINNERS - 1) (additional_winner_hashes: Vec<Option<[u8; 32]>>) +
+// 4 + 16 * MAX_VOIDED_RANGES (voided_ranges: Vec<TicketRange>) +
+// 8 (pending_reservations) +
+// 1 (open_slot_released: bool) +
+// 8 (campaign_subsidies) =
+// 2178 total bytes
+pub const RAFFLE_ACCOUNT_SIZE: usize = 8
+    + 32
+    + 4
+    + 256
+    + 8
+    + 8
+    + 8
+    + 9
+    + 8
+    + 8
+    + 1
+    + 33
+    + 9
+    + 9
+    + 33
+    + 9
+    + 9
+    + 8
+    + 1
+    + 33
+    + 8
+    + 9
+    + 1
+    + 8
+    + 3
+    + 2
+    + 8
+    + 2
+    + 32
+    + 4
+    + 8
+    + 1
+    + 33
+    + 1
+    + 8
+    + 9
+    + 33
+    + 1
+    + 33
+    + 8
+    + 1
+    + 1
+    + 4
+    + 8 * MAX_WINNERS as usize
+    + 4
+    + 32 * (MAX_WINNERS as usize - 1)
+    + 2
+    + 2
+    + 9
+    + 42
+    + 1
+    + 8
+    + 8
+    + 8
+    + 3
+    + 33
+    + 1
+    + 33
+    + 9
+    + 4
+    + 16 * MAX_PRICING_TIERS
+    + 8
+    + 2
+    + 2
+    + 1
+    + 8
+    + 8
+    + 9
+    + 9
+    + 1
+    + 33
+    + 33
+    + 2
+    + 8
+    + 33
+    + 8
+    + 8
+    + 9
+    + 33
+    + 2
+    + 33
+    + 33
+    + 1
+    + 1
+    + 4
+    + 33 * (MAX_WINNERS as usize - 1)
+    + 4
+    + 16 * MAX_VOIDED_RANGES
+    + 8
+    + 1
+    + 8;
+
+/// Maximum number of winners a raffle can draw, bounded by the bitmasks tracking which
+/// winners submitted their data and claimed their prize
+pub const MAX_WINNERS: u8 = 10;
+
+/// Maximum number of tiers in a raffle's pricing schedule
+pub const MAX_PRICING_TIERS: usize = 4;
+
+/// Maximum number of voided ticket ranges a raffle tracks, which also bounds its
+/// pending reservations, as releasing each of them can void another range
+pub const MAX_VOIDED_RANGES: usize = 16;
+
+/// Maximum random delay of a raffle's draw slot, about two minutes, keeping the drawn
+/// slot well within the SlotHashes sysvar's history
+pub const MAX_DRAW_DELAY_SLOTS: u16 = 300;
+
+/// Maximum deviation in basis points of a refund rate from the rate quoted by its price
+/// feed
+pub const MAX_REFUND_RATE_DEVIATION_BPS: u64 = 100; // 1%
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
 pub enum RaffleState {
@@ -24,9 +212,116 @@ pub enum RaffleState {
     Drawn = 2,
     Expired = 3,
     Claimed = 4,
+    AwaitingPrize = 5,
+    Cancelled = 6,
+}
+
+/// Source of the entropy a raffle's winning ticket is drawn from
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum RandomnessMode {
+    /// Drawn by draw_winning_ticket from the SlotHashes sysvar
+    SlotHashes = 0,
+    /// Drawn by request_randomness and settle_randomness from a Switchboard On-Demand
+    /// randomness account, which the slot leader can't manipulate
+    Vrf = 1,
+    /// Decided by a token-weighted community vote among entries shortlisted with
+    /// open_vote, for contest-style giveaways
+    Vote = 2,
+    /// Drawn by fulfill_randomness from the raffle's randomness provider, selected with
+    /// set_randomness_provider among those registered in the config
+    Provider = 3,
+}
+
+/// Requirement a wallet must meet to buy tickets of a gated raffle
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum Gate {
+    /// Buyers must hold at least `min_amount` base units of `mint`, e.g. a community
+    /// NFT or token
+    TokenHolder { mint: Pubkey, min_amount: u64 },
+    /// Buyers must be a leaf of the Merkle tree with `root`, built from a fixed list of
+    /// wallets
+    MerkleAllowlist { root: [u8; 32] },
+}
+
+/// Bulk price of a raffle's pricing schedule, charged per ticket for purchases of at
+/// least `quantity_threshold` tickets
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub struct PricingTier {
+    pub quantity_threshold: u64,
+    pub price_per_ticket: u64,
 }
 
-#[account]
+/// Range of ticket numbers of a released reservation that no entry holds, as later
+/// tickets were numbered after them
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TicketRange {
+    pub start: u64,
+    pub count: u64,
+}
+
+/// Ticket number of the held ticket at `index`, counting only tickets held by entries,
+/// so draws over `current_tickets` never land on a number in `voided_ranges`. Shared
+/// with off-chain replay tooling
+pub fn held_ticket_number(voided_ranges: &[TicketRange], index: u64) -> Result<u64> {
+    let mut number = index;
+    for range in voided_ranges {
+        if range.start > number {
+            break;
+        }
+        number = checked_add(number, range.count)?;
+    }
+    Ok(number)
+}
+
+/// Feature flags of `Raffle::features`, one bit per optional mechanic enabled at creation
+pub const FEATURE_ANONYMIZED_WINNER: u32 = 1 << 0;
+pub const FEATURE_COLLECTIBLE: u32 = 1 << 1;
+pub const FEATURE_CASH_PRIZE: u32 = 1 << 2;
+pub const FEATURE_TOP_HOLDERS: u32 = 1 << 3;
+pub const FEATURE_MIN_REVENUE: u32 = 1 << 4;
+pub const FEATURE_PURCHASE_LIMIT: u32 = 1 << 5;
+pub const FEATURE_PRIZE_DEPOSIT: u32 = 1 << 6;
+pub const FEATURE_REGION_RESTRICTED: u32 = 1 << 7;
+pub const FEATURE_AGE_RESTRICTED: u32 = 1 << 8;
+pub const FEATURE_DRAW_AUTHORITY: u32 = 1 << 9;
+pub const FEATURE_PRIORITY_ACCESS: u32 = 1 << 10;
+pub const FEATURE_MIN_UNIQUE_BUYERS: u32 = 1 << 11;
+pub const FEATURE_TOKEN_PAYMENTS: u32 = 1 << 12;
+pub const FEATURE_VRF_DRAW: u32 = 1 << 13;
+pub const FEATURE_MULTIPLE_WINNERS: u32 = 1 << 14;
+pub const FEATURE_WALLET_LIMIT: u32 = 1 << 15;
+pub const FEATURE_GATED: u32 = 1 << 16;
+pub const FEATURE_DEFERRED_NUMBERING: u32 = 1 << 17;
+pub const FEATURE_TIERED_PRICING: u32 = 1 << 18;
+pub const FEATURE_VOTE_DRAW: u32 = 1 << 19;
+pub const FEATURE_TICKET_RECEIPTS: u32 = 1 << 20;
+pub const FEATURE_DRAW_DELAY: u32 = 1 << 21;
+pub const FEATURE_PROVIDER_DRAW: u32 = 1 << 22;
+pub const FEATURE_ACCESS_CODE: u32 = 1 << 23;
+
+/// Ticket start index of the entries of a raffle with deferred numbering until
+/// `finalize_numbering` assigns their tickets
+pub const UNNUMBERED_TICKET_START: u64 = u64::MAX;
+
+/// Operational states of `Raffle::operator_status`, reported by the management authority
+/// for transparency dashboards
+pub const OPERATOR_STATUS_NONE: u8 = 0;
+pub const OPERATOR_STATUS_PRIZE_SHIPPED: u8 = 1;
+pub const OPERATOR_STATUS_DELAYED: u8 = 2;
+pub const OPERATOR_STATUS_FRAUD_REVIEW: u8 = 3;
+
+/// Reasons a raffle left the Open state, recorded in `Raffle::close_reason`
+pub const CLOSE_REASON_NONE: u8 = 0;
+pub const CLOSE_REASON_TIME_ELAPSED: u8 = 1;
+pub const CLOSE_REASON_SOLD_OUT: u8 = 2;
+pub const CLOSE_REASON_CANCELLED: u8 = 3;
+pub const CLOSE_REASON_VOIDED: u8 = 4;
+pub const CLOSE_REASON_EARLY_CLOSE: u8 = 5;
+
+// sha256("account:Raffle")[..8]
+pub const RAFFLE_DISCRIMINATOR: &[u8] = &[143, 133, 63, 173, 138, 10, 142, 200];
+
+#[account(discriminator = RAFFLE_DISCRIMINATOR)]
 pub struct Raffle {
     pub treasury: Pubkey,
     pub metadata_uri: String,
@@ -39,4 +334,567 @@ pub struct Raffle {
     pub raffle_state: RaffleState,
     pub winner_address: Option<Pubkey>,
     pub winning_ticket: Option<u64>,
+    /// Most recent slot in the SlotHashes sysvar at the time of the draw
+    pub draw_slot: Option<u64>,
+    /// The hash of `draw_slot`, from which the draw entropy was extracted
+    pub draw_slot_hash: Option<[u8; 32]>,
+    /// The timestamp that was mixed into the draw entropy
+    pub draw_timestamp: Option<i64>,
+    /// Maximum number of tickets that can be bought in a single purchase, if limited
+    pub max_per_purchase: Option<u64>,
+    /// Number of distinct wallets that bought tickets
+    pub participant_count: u64,
+    /// Whether only a hash committing to the winners is stored until they claim
+    pub anonymize_winner: bool,
+    /// Hash of the first prize's winner, set instead of `winner_address` for anonymized
+    /// raffles if the winner committed to a secret
+    pub winner_hash: Option<[u8; 32]>,
+    /// Sequence number of the next event emitted for this raffle, letting indexers
+    /// detect missed or out-of-order events
+    pub event_seq: u64,
+    /// Revenue in lamports that must be reached for the raffle to be drawn. When set,
+    /// it replaces `min_tickets` as the threshold
+    pub min_revenue: Option<u64>,
+    /// Whether entry owners can claim their ticket numbers as collectibles once the
+    /// raffle is drawn
+    pub collectible: bool,
+    /// Lowest slot whose hash may be used as draw entropy. Raised when a draw is reset,
+    /// so the redraw samples fresh entropy
+    pub min_draw_slot: u64,
+    /// Share of the treasury in basis points paid to the winner in SOL by `pay_winner`,
+    /// if the raffle has a cash prize
+    pub cash_prize_bps: Option<u16>,
+    /// Number of largest ticket holders the cash prize is split among in proportion to
+    /// their tickets, instead of paying a single random winner
+    pub top_holders: Option<u8>,
+    /// Tickets held by pending reservations, which count against `max_tickets`
+    pub reserved_tickets: u64,
+    /// Share of refunds in basis points retained by the treasury if the raffle expires,
+    /// fixed from the config when the raffle is created
+    pub refund_fee_bps: u16,
+    /// Account that paid the rent of the raffle and its treasury, which is returned to
+    /// it when they are closed
+    pub rent_payer: Pubkey,
+    /// Bitfield of the `FEATURE_*` mechanics enabled at creation, letting indexers
+    /// filter raffles with a single memcmp
+    pub features: u32,
+    /// Bitmask of the region codes buyers must hold a `RegionAttestation` for, one bit
+    /// per region. Purchases are unrestricted while zero
+    pub allowed_regions: u64,
+    /// Whether buyers must hold an `AgeAttestation` vouching they are of legal age
+    pub age_restricted: bool,
+    /// Key that alone can trigger the draw, e.g. a neutral third party for sponsored
+    /// raffles. Draws are cranked by keepers or anyone while unset
+    pub draw_authority: Option<Pubkey>,
+    /// Lowest `UserProfile` tier allowed to buy tickets during the priority phase
+    pub priority_min_tier: u8,
+    /// End of the priority phase, after which anyone can buy tickets. Zero if the raffle
+    /// has no priority phase
+    pub priority_until: i64,
+    /// Number of distinct wallets that must have bought tickets for the raffle to be
+    /// drawn, in addition to the ticket or revenue threshold
+    pub min_unique_buyers: Option<u64>,
+    /// SPL token mint tickets are paid in, held in the treasury's associated token
+    /// account. Tickets are paid in lamports while unset
+    pub payment_mint: Option<Pubkey>,
+    pub randomness_mode: RandomnessMode,
+    /// Switchboard randomness account committed to by request_randomness, until the draw
+    /// is settled or reset
+    pub randomness_account: Option<Pubkey>,
+    /// Slot the randomness account was committed in, so a recommitted account can't be
+    /// settled
+    pub randomness_commit_slot: u64,
+    /// Whether the prize was escrowed with deposit_prize. Stays set after the winner
+    /// claims it
+    pub prize_escrowed: bool,
+    /// Number of distinct winning tickets drawn, one per prize tier
+    pub winner_count: u8,
+    /// The drawn winning tickets in prize order. The first one is also stored as
+    /// `winning_ticket`
+    pub winning_tickets: Vec<u64>,
+    /// Winners of the prizes after the first, in prize order. The first prize's winner
+    /// is stored as `winner_address`. Anonymized winners are the default pubkey until
+    /// they claim
+    pub additional_winners: Vec<Pubkey>,
+    /// Bitmask of the winner indices whose winner submitted their data
+    pub winner_data_submitted: u16,
+    /// Bitmask of the winner indices whose escrowed prize share was claimed
+    pub prizes_claimed: u16,
+    /// Maximum number of tickets a single wallet can hold across all its purchases, if
+    /// limited
+    pub max_tickets_per_wallet: Option<u64>,
+    /// Holding or allowlist requirement buyers must meet, checked when they initialize
+    /// their ticket balance. Anyone can buy tickets while unset
+    pub gate: Option<Gate>,
+    /// Whether entries only record their ticket counts during the sale, with ticket
+    /// numbers assigned in entry creation order by `finalize_numbering` before the draw
+    pub deferred_numbering: bool,
+    /// Number of entries created, which is also the creation index of the next entry
+    pub entry_count: u64,
+    /// Number of entries, in creation order, whose tickets were numbered by
+    /// `finalize_numbering`
+    pub numbered_entries: u64,
+    /// Number of tickets numbered by `finalize_numbering`, which is also the start index
+    /// of the next entry it numbers
+    pub numbered_tickets: u64,
+    /// Platform fee in basis points charged on withdrawals from this raffle's treasury
+    /// instead of the config's `fee_bps`
+    pub fee_bps_override: Option<u16>,
+    /// Merkle root of the (owner, ticket_count) pairs of all ticket holders, recorded by
+    /// `snapshot_raffle` once the sale closed
+    pub snapshot_root: Option<[u8; 32]>,
+    /// Operational state reported by the management authority, one of the
+    /// `OPERATOR_STATUS_*` values
+    pub operator_status: u8,
+    /// Hash of the off-chain note explaining the operator status
+    pub status_note_hash: Option<[u8; 32]>,
+    /// Time by which the raffle must meet its threshold, after which a raffle that
+    /// missed it can be expired before its end time
+    pub funding_deadline: Option<i64>,
+    /// Bulk prices by ascending quantity threshold, replacing `ticket_price` for larger
+    /// purchases. Every ticket is sold at `ticket_price` while empty
+    pub pricing_tiers: Vec<PricingTier>,
+    /// Total discount buyers received through the pricing schedule, deducted from the
+    /// revenue the threshold is measured by
+    pub tier_discounts: u64,
+    /// Crank reward in basis points of the ticket price, copied from the config at
+    /// creation. Raffles paid in an SPL token pay no crank rewards
+    pub crank_reward_bps: u16,
+    /// Referral fee in basis points of referred purchases, copied from the config at
+    /// creation. Raffles paid in an SPL token or with a cash prize pay no referral fees
+    pub referral_fee_bps: u16,
+    /// Number of times extend_raffle pushed back the end time
+    pub extension_count: u8,
+    /// Total seconds extend_raffle pushed back the end time by
+    pub extended_secs: i64,
+    /// Time after which unclaimed refunds of the expired or cancelled raffle can be
+    /// forfeited with sweep_unclaimed_refunds, fixed from the config when the raffle
+    /// expires or is cancelled. Refunds never lapse while zero
+    pub refund_deadline: i64,
+    /// Value of the prize in US cents, if published, so frontends display it without
+    /// parsing the off-chain metadata
+    pub prize_value_usd_cents: Option<u64>,
+    /// Ticket count above which frontends display the count as capped, e.g. "10,000+",
+    /// if set
+    pub ticket_count_display_cap: Option<u64>,
+    /// Whether buy_tickets mints a transferable NFT receipt of every entry, whose holder
+    /// is paid the prize of the entry's tickets
+    pub ticket_receipts: bool,
+    /// Arweave transaction id of the metadata, taken from `ar://` metadata URIs or set
+    /// with set_arweave_mirror for metadata mirrored to Arweave, so the permanent copy
+    /// can be fetched without parsing the URI
+    pub arweave_tx_id: Option<[u8; 32]>,
+    /// Wallet that created the raffle through its creator role, and may update and
+    /// extend it. None for raffles created by the management authority
+    pub creator: Option<Pubkey>,
+    /// Maximum number of slots the draw slot is randomly delayed by after commit_draw_slot,
+    /// or 0 to draw from the latest slot hash
+    pub draw_delay_slots: u16,
+    /// Slot whose hash, or that of the next produced slot, draws the raffle, committed by
+    /// commit_draw_slot. 0 while not committed
+    pub draw_target_slot: u64,
+    /// Mint refunds are paid in instead of the payment mint, designated with
+    /// set_refund_mint after the treasury's payment tokens were frozen
+    pub refund_mint: Option<Pubkey>,
+    /// Base units of the refund mint paid per base unit of the payment mint, scaled by
+    /// `RATE_SCALE`. 0 while refunds are paid in the payment mint
+    pub refund_rate: u64,
+    /// Lamports the treasury held for the prize when record_draw_value snapshotted the
+    /// drawn raffle
+    pub draw_value_lamports: u64,
+    /// SOL price in micro-USD read from the config's USD price feed by
+    /// record_draw_value, if the draw value was recorded
+    pub draw_usd_rate: Option<u64>,
+    /// Raffle that ticket holders who opted into rollover can move their balance to with
+    /// rollover_tickets if this raffle expires, designated with set_successor_raffle
+    pub successor: Option<Pubkey>,
+    /// Entropy source the raffle is drawn from, if it is drawn with
+    /// `RandomnessMode::Provider`
+    pub randomness_provider: Option<RandomnessProvider>,
+    /// Hash of the secret a raffle drawn by the CommitReveal provider is drawn from
+    pub randomness_commitment: Option<[u8; 32]>,
+    /// Hash of the access code buyers must present to buy tickets of a private raffle,
+    /// set with set_access_code. Anyone can buy tickets while unset
+    pub access_code_hash: Option<[u8; 32]>,
+    /// Why the raffle left the Open state, one of the `CLOSE_REASON_*` values.
+    /// `CLOSE_REASON_NONE` while it is open
+    pub close_reason: u8,
+    /// Whether the crank reward was paid, so a raffle redrawn after reset_draw doesn't
+    /// pay it again
+    pub crank_reward_paid: bool,
+    /// Winner hashes of the prizes after the first, in prize order, for anonymized
+    /// raffles. `None` where the winner is stored in plaintext in `additional_winners`
+    pub additional_winner_hashes: Vec<Option<[u8; 32]>>,
+    /// Ticket ranges of reservations released after later tickets were numbered, which
+    /// no entry holds, sorted by start and never adjacent. Draws skip them
+    pub voided_ranges: Vec<TicketRange>,
+    /// Number of reservations not yet confirmed or released
+    pub pending_reservations: u64,
+    /// Whether the raffle stopped counting towards the config's `open_raffle_count`, so
+    /// it is only released once
+    pub open_slot_released: bool,
+    /// Total paid by promotional campaigns for tickets of the raffle, returned to the
+    /// campaigns instead of the ticket holders on refund
+    pub campaign_subsidies: u64,
+}
+
+impl Raffle {
+    /// Whether all mechanics in the `FEATURE_*` mask were enabled at creation
+    pub fn has_feature(&self, feature: u32) -> bool {
+        self.features & feature == feature
+    }
+
+    /// Feature flags of the mechanics enabled by the raffle's parameters
+    pub fn enabled_features(&self) -> u32 {
+        let mut features = 0;
+        if self.anonymize_winner {
+            features |= FEATURE_ANONYMIZED_WINNER;
+        }
+        if self.collectible {
+            features |= FEATURE_COLLECTIBLE;
+        }
+        if self.cash_prize_bps.is_some() {
+            features |= FEATURE_CASH_PRIZE;
+        }
+        if self.top_holders.is_some() {
+            features |= FEATURE_TOP_HOLDERS;
+        }
+        if self.min_revenue.is_some() {
+            features |= FEATURE_MIN_REVENUE;
+        }
+        if self.max_per_purchase.is_some() {
+            features |= FEATURE_PURCHASE_LIMIT;
+        }
+        if self.raffle_state == RaffleState::AwaitingPrize {
+            features |= FEATURE_PRIZE_DEPOSIT;
+        }
+        if self.allowed_regions != 0 {
+            features |= FEATURE_REGION_RESTRICTED;
+        }
+        if self.age_restricted {
+            features |= FEATURE_AGE_RESTRICTED;
+        }
+        if self.draw_authority.is_some() {
+            features |= FEATURE_DRAW_AUTHORITY;
+        }
+        if self.priority_until != 0 {
+            features |= FEATURE_PRIORITY_ACCESS;
+        }
+        if self.min_unique_buyers.is_some() {
+            features |= FEATURE_MIN_UNIQUE_BUYERS;
+        }
+        if self.payment_mint.is_some() {
+            features |= FEATURE_TOKEN_PAYMENTS;
+        }
+        if self.randomness_mode == RandomnessMode::Vrf {
+            features |= FEATURE_VRF_DRAW;
+        }
+        if self.winner_count > 1 {
+            features |= FEATURE_MULTIPLE_WINNERS;
+        }
+        if self.max_tickets_per_wallet.is_some() {
+            features |= FEATURE_WALLET_LIMIT;
+        }
+        if self.gate.is_some() {
+            features |= FEATURE_GATED;
+        }
+        if self.deferred_numbering {
+            features |= FEATURE_DEFERRED_NUMBERING;
+        }
+        if !self.pricing_tiers.is_empty() {
+            features |= FEATURE_TIERED_PRICING;
+        }
+        if self.randomness_mode == RandomnessMode::Vote {
+            features |= FEATURE_VOTE_DRAW;
+        }
+        if self.ticket_receipts {
+            features |= FEATURE_TICKET_RECEIPTS;
+        }
+        if self.draw_delay_slots > 0 {
+            features |= FEATURE_DRAW_DELAY;
+        }
+        if self.randomness_mode == RandomnessMode::Provider {
+            features |= FEATURE_PROVIDER_DRAW;
+        }
+        if self.access_code_hash.is_some() {
+            features |= FEATURE_ACCESS_CODE;
+        }
+        features
+    }
+
+    /// Whether sales are still limited to buyers of at least `priority_min_tier`
+    pub fn in_priority_phase(&self, now: i64) -> bool {
+        now < self.priority_until
+    }
+
+    /// Whether the refund deadline passed, after which unclaimed refunds can be
+    /// forfeited
+    pub fn refunds_lapsed(&self, now: i64) -> bool {
+        self.refund_deadline != 0 && now > self.refund_deadline
+    }
+
+    /// Why a raffle drawn at `now` left the Open state: it sold out if it was drawn
+    /// before its end time, otherwise its time elapsed
+    pub fn draw_close_reason(&self, now: i64) -> u8 {
+        if now < self.end_time && self.max_tickets == Some(self.current_tickets) {
+            CLOSE_REASON_SOLD_OUT
+        } else {
+            CLOSE_REASON_TIME_ELAPSED
+        }
+    }
+
+    /// Returns the sequence number for the next event and advances the counter
+    pub fn next_event_seq(&mut self) -> Result<u64> {
+        let event_seq = self.event_seq;
+        self.event_seq = checked_add(event_seq, 1)?;
+        Ok(event_seq)
+    }
+
+    /// Whether enough was sold for the raffle to be drawn, measured by revenue if
+    /// `min_revenue` is set and by ticket count otherwise. Raffles requiring a number
+    /// of unique buyers must also have reached it
+    pub fn threshold_met(&self) -> Result<bool> {
+        if !self.unique_buyers_met() {
+            return Ok(false);
+        }
+        match self.min_revenue {
+            Some(min_revenue) => {
+                let revenue = checked_sub(
+                    ticket_cost(self.current_tickets, self.ticket_price)?,
+                    self.tier_discounts,
+                )?;
+                Ok(revenue >= min_revenue)
+            }
+            None => Ok(self.current_tickets >= self.min_tickets),
+        }
+    }
+
+    /// Price per ticket of a purchase of `ticket_count` tickets, from the highest tier of
+    /// the pricing schedule the purchase reaches
+    pub fn unit_price(&self, ticket_count: u64) -> u64 {
+        self.pricing_tiers
+            .iter()
+            .rev()
+            .find(|tier| ticket_count >= tier.quantity_threshold)
+            .map_or(self.ticket_price, |tier| tier.price_per_ticket)
+    }
+
+    /// Records the pricing schedule discount on `ticket_count` tickets sold at
+    /// `unit_price`, so refunds of the tickets return what was paid
+    pub fn record_tier_discount(
+        &mut self,
+        ticket_balance: &mut TicketBalance,
+        ticket_count: u64,
+        unit_price: u64,
+    ) -> Result<()> {
+        let discount = ticket_cost(ticket_count, checked_sub(self.ticket_price, unit_price)?)?;
+        self.tier_discounts = checked_add(self.tier_discounts, discount)?;
+        ticket_balance.tier_discount = checked_add(ticket_balance.tier_discount, discount)?;
+        Ok(())
+    }
+
+    /// Crank reward owed for `ticket_count` tickets, a fixed share of the ticket price
+    /// per ticket so refunds can recover the reward paid on expiry exactly
+    pub fn crank_share(&self, ticket_count: u64) -> Result<u64> {
+        ticket_cost(ticket_count, bps_of(self.ticket_price, self.crank_reward_bps)?)
+    }
+
+    /// Chance in basis points, rounded down, of each drawn ticket being one of
+    /// `ticket_count` tickets held by a single wallet, at the tickets sold so far
+    pub fn win_probability_bps(&self, ticket_count: u64) -> u16 {
+        share_bps(ticket_count, self.current_tickets)
+    }
+
+    /// Whether the raffle's funding deadline passed at `now` without its threshold met
+    pub fn funding_failed(&self, now: i64) -> Result<bool> {
+        match self.funding_deadline {
+            Some(funding_deadline) if funding_deadline < now => Ok(!self.threshold_met()?),
+            _ => Ok(false),
+        }
+    }
+
+    /// Winner of the prize at `winner_index`, if it was set and is not anonymized
+    pub fn winner(&self, winner_index: u8) -> Option<Pubkey> {
+        match winner_index {
+            0 => self.winner_address,
+            _ => self
+                .additional_winners
+                .get(winner_index as usize - 1)
+                .copied()
+                .filter(|winner| *winner != Pubkey::default()),
+        }
+    }
+
+    /// Hash the winner of the prize at `winner_index` is stored under, if anonymized
+    pub fn winner_hash_at(&self, winner_index: u8) -> Option<[u8; 32]> {
+        match winner_index {
+            0 => self.winner_hash,
+            _ => self
+                .additional_winner_hashes
+                .get(winner_index as usize - 1)
+                .copied()
+                .flatten(),
+        }
+    }
+
+    /// Records `winner` as the winner of the prize at `winner_index`, which must be set
+    pub fn publish_winner(&mut self, winner_index: u8, winner: Pubkey) -> Result<()> {
+        match winner_index {
+            0 => self.winner_address = Some(winner),
+            _ => {
+                *self
+                    .additional_winners
+                    .get_mut(winner_index as usize - 1)
+                    .ok_or(RaffleError::NotWinner)? = winner;
+            }
+        }
+        Ok(())
+    }
+
+    /// Bitmask of the winner indices won by `wallet`
+    pub fn winner_indices(&self, wallet: &Pubkey) -> u16 {
+        (0..self.winner_count)
+            .filter(|&winner_index| self.winner(winner_index) == Some(*wallet))
+            .fold(0, |mask, winner_index| mask | 1 << winner_index)
+    }
+
+    /// Whether the winners of all prizes were set
+    pub fn all_winners_set(&self) -> bool {
+        (self.winner_address.is_some() || self.winner_hash.is_some())
+            && self.additional_winners.len() + 1 >= self.winner_count as usize
+    }
+
+    /// Bitmask with a bit for every winner index of the raffle
+    pub fn all_winners_mask(&self) -> u16 {
+        (1 << self.winner_count) - 1
+    }
+
+    /// Records the creation of an entry, returning its creation index and the start
+    /// index of its tickets. The tickets of raffles with deferred numbering stay
+    /// `UNNUMBERED_TICKET_START` until `finalize_numbering` assigns them
+    pub fn record_entry(&mut self) -> Result<(u64, u64)> {
+        let entry_index = self.entry_count;
+        self.entry_count = checked_add(entry_index, 1)?;
+        Ok((entry_index, self.next_ticket_start()?))
+    }
+
+    /// Start index of the next tickets sold or reserved, following the tickets held by
+    /// entries, pending reservations and released reservations. `UNNUMBERED_TICKET_START`
+    /// for raffles with deferred numbering
+    pub fn next_ticket_start(&self) -> Result<u64> {
+        if self.deferred_numbering {
+            Ok(UNNUMBERED_TICKET_START)
+        } else {
+            self.ticket_number_count()
+        }
+    }
+
+    /// Number of ticket numbers assigned so far. Includes the tickets of pending
+    /// reservations and the voided ranges of released ones, unless the raffle defers
+    /// numbering, whose reservations are only numbered once confirmed
+    pub fn ticket_number_count(&self) -> Result<u64> {
+        if self.deferred_numbering {
+            return Ok(self.current_tickets);
+        }
+        self.voided_ranges.iter().try_fold(
+            checked_add(self.current_tickets, self.reserved_tickets)?,
+            |count, range| checked_add(count, range.count),
+        )
+    }
+
+    /// Whether another reservation can be held, leaving room in `voided_ranges` for the
+    /// ranges all pending reservations can void when released
+    pub fn can_reserve(&self) -> bool {
+        self.deferred_numbering
+            || (self.voided_ranges.len() as u64).saturating_add(self.pending_reservations)
+                < MAX_VOIDED_RANGES as u64
+    }
+
+    /// Records a new reservation of `ticket_count` tickets
+    pub fn record_reservation(&mut self, ticket_count: u64) -> Result<()> {
+        self.reserved_tickets = checked_add(self.reserved_tickets, ticket_count)?;
+        self.pending_reservations = checked_add(self.pending_reservations, 1)?;
+        Ok(())
+    }
+
+    /// Releases the reservation of `ticket_count` tickets numbered from `ticket_start`,
+    /// either sold to its buyer or returned to sale. Returned numbers are reused if no
+    /// later tickets were numbered after them, otherwise they are voided, so no two
+    /// entries ever hold the same ticket number
+    pub fn release_reservation(
+        &mut self,
+        ticket_start: u64,
+        ticket_count: u64,
+        sold: bool,
+    ) -> Result<()> {
+        let ticket_number_count = self.ticket_number_count()?;
+        self.reserved_tickets = checked_sub(self.reserved_tickets, ticket_count)?;
+        self.pending_reservations = checked_sub(self.pending_reservations, 1)?;
+        if sold || self.deferred_numbering {
+            return Ok(());
+        }
+
+        if checked_add(ticket_start, ticket_count)? == ticket_number_count {
+            // The next tickets reuse the numbers, along with the voided ranges now ending
+            // at the tail
+            let mut end = ticket_start;
+            while let Some(range) = self.voided_ranges.last().copied() {
+                if checked_add(range.start, range.count)? != end {
+                    break;
+                }
+                end = range.start;
+                self.voided_ranges.pop();
+            }
+            return Ok(());
+        }
+
+        let position = self
+            .voided_ranges
+            .partition_point(|range| range.start < ticket_start);
+        self.voided_ranges.insert(
+            position,
+            TicketRange {
+                start: ticket_start,
+                count: ticket_count,
+            },
+        );
+        // Merge the range with its neighbours, keeping the ranges never adjacent
+        if let Some(next) = self.voided_ranges.get(position + 1).copied() {
+            if checked_add(ticket_start, ticket_count)? == next.start {
+                self.voided_ranges[position].count = checked_add(ticket_count, next.count)?;
+                self.voided_ranges.remove(position + 1);
+            }
+        }
+        if position > 0 {
+            let previous = self.voided_ranges[position - 1];
+            if checked_add(previous.start, previous.count)? == ticket_start {
+                self.voided_ranges[position - 1].count =
+                    checked_add(previous.count, self.voided_ranges[position].count)?;
+                self.voided_ranges.remove(position);
+            }
+        }
+        Ok(())
+    }
+
+    /// Ticket numbers of the held tickets at `indices`, see `held_ticket_number`
+    pub fn held_ticket_numbers(&self, indices: Vec<u64>) -> Result<Vec<u64>> {
+        indices
+            .into_iter()
+            .map(|index| held_ticket_number(&self.voided_ranges, index))
+            .collect()
+    }
+
+    /// Whether the tickets of all entries have numbers, which raffles with deferred
+    /// numbering only have once `finalize_numbering` processed every entry
+    pub fn numbering_finalized(&self) -> bool {
+        !self.deferred_numbering || self.numbered_entries == self.entry_count
+    }
+
+    /// Whether enough distinct wallets bought tickets, if the raffle requires any
+    pub fn unique_buyers_met(&self) -> bool {
+        match self.min_unique_buyers {
+            Some(min_unique_buyers) => self.participant_count >= min_unique_buyers,
+            None => true,
+        }
+    }
 }
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+// 8 discriminator + 32 raffle + 8 requested_at + 8 executable_at + 1 bump
+pub const RECOVERY_REQUEST_ACCOUNT_SIZE: usize = 8 + 32 + 8 + 8 + 1;
+
+/// Time between requesting an emergency recovery and being able to execute it
+pub const EMERGENCY_RECOVERY_DELAY_SECS: i64 = 72 * 60 * 60; // 72 hours
+
+// sha256("account:RecoveryRequest")[..8]
+pub const RECOVERY_REQUEST_DISCRIMINATOR: &[u8] = &[143, 116, 126, 64, 175, 138, 150, 111];
+
+/// Pending emergency recovery of a raffle, stored at the PDA with seeds
+/// ["recovery_request", raffle_key]. The upgrade authority can execute it once
+/// `executable_at` has passed, or cancel it at any time.
+#[account(discriminator = RECOVERY_REQUEST_DISCRIMINATOR)]
+pub struct RecoveryRequest {
+    pub raffle: Pubkey,
+    pub requested_at: i64,
+    /// Earliest time the recovery can be executed
+    pub executable_at: i64,
+    pub bump: u8,
+}
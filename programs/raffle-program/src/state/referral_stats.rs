@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::math::{checked_add, checked_sub};
+
+// 8 discriminator + 32 raffle + 32 referrer + 8 ticket_count + 8 volume + 8 fees_owed
+// + 8 fees_paid + 1 bump
+pub const REFERRAL_STATS_ACCOUNT_SIZE: usize = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 1;
+
+// sha256("account:ReferralStats")[..8]
+pub const REFERRAL_STATS_DISCRIMINATOR: &[u8] = &[1, 195, 51, 123, 240, 93, 99, 240];
+
+/// Tickets sold in a raffle on a referrer's behalf, at the PDA with seeds
+/// ["referral_stats", raffle_key, referrer]
+#[account(discriminator = REFERRAL_STATS_DISCRIMINATOR)]
+pub struct ReferralStats {
+    pub raffle: Pubkey,
+    pub referrer: Pubkey,
+    /// Tickets bought with this referrer
+    pub ticket_count: u64,
+    /// Lamports paid for the referred tickets, or base units of the raffle's payment mint
+    pub volume: u64,
+    /// Referral fees accrued at the raffle's referral fee rate, in lamports
+    pub fees_owed: u64,
+    /// Referral fees paid to the referrer at treasury withdrawals
+    pub fees_paid: u64,
+    pub bump: u8,
+}
+
+impl ReferralStats {
+    /// Records a purchase of `ticket_count` tickets for `amount` made with this referrer,
+    /// accruing `fee` to the referrer
+    pub fn record(&mut self, ticket_count: u64, amount: u64, fee: u64) -> Result<()> {
+        self.ticket_count = checked_add(self.ticket_count, ticket_count)?;
+        self.volume = checked_add(self.volume, amount)?;
+        self.fees_owed = checked_add(self.fees_owed, fee)?;
+        Ok(())
+    }
+
+    /// Referral fees accrued but not yet paid to the referrer
+    pub fn unpaid_fees(&self) -> Result<u64> {
+        checked_sub(self.fees_owed, self.fees_paid)
+    }
+}
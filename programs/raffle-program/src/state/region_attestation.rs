@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+// 8 discriminator + 32 wallet + 1 region + 32 attestor + 8 expires_at + 1 bump
+pub const REGION_ATTESTATION_ACCOUNT_SIZE: usize = 8 + 32 + 1 + 32 + 8 + 1;
+
+/// Number of distinct region codes, one per bit of `Raffle::allowed_regions`
+pub const MAX_REGIONS: u8 = 64;
+
+// sha256("account:RegionAttestation")[..8]
+pub const REGION_ATTESTATION_DISCRIMINATOR: &[u8] = &[230, 109, 210, 69, 8, 69, 130, 216];
+
+/// A wallet's jurisdiction as attested by the config's region attestor, stored at the
+/// PDA with seeds ["region_attestation", wallet]
+#[account(discriminator = REGION_ATTESTATION_DISCRIMINATOR)]
+pub struct RegionAttestation {
+    pub wallet: Pubkey,
+    /// Region code, the index of the region's bit in `Raffle::allowed_regions`
+    pub region: u8,
+    /// The attestor that signed the attestation. Attestations stop being accepted once
+    /// the config's attestor is rotated
+    pub attestor: Pubkey,
+    /// Time after which the attestation is no longer accepted
+    pub expires_at: i64,
+    pub bump: u8,
+}
+
+impl RegionAttestation {
+    /// Whether the attestation vouches for one of the regions in `allowed_regions`
+    pub fn allows(&self, allowed_regions: u64) -> bool {
+        self.region < MAX_REGIONS && allowed_regions & (1 << self.region) != 0
+    }
+}
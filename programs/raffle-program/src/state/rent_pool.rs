@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+// 8 discriminator + 1 enabled + 8 total_reimbursed + 1 bump
+pub const RENT_POOL_ACCOUNT_SIZE: usize = 8 + 1 + 8 + 1;
+
+// sha256("account:RentPool")[..8]
+pub const RENT_POOL_DISCRIMINATOR: &[u8] = &[147, 139, 254, 34, 153, 44, 233, 123];
+
+/// Operator-funded pool that covers the rent of `Entry` accounts on behalf of buyers.
+/// The pool's lamports above its own rent reserve are available for reimbursements.
+#[account(discriminator = RENT_POOL_DISCRIMINATOR)]
+pub struct RentPool {
+    pub enabled: bool,
+    /// Total entry rent reimbursed to buyers
+    pub total_reimbursed: u64,
+    pub bump: u8,
+}
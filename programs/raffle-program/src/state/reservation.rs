@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+// 8 discriminator + 32 raffle + 32 buyer + 8 ticket_count + 8 seed + 8 expires_at + 1 bump
+// + 8 ticket_start_index
+pub const RESERVATION_ACCOUNT_SIZE: usize = 8 + 32 + 32 + 8 + 8 + 8 + 1 + 8;
+
+// sha256("account:Reservation")[..8]
+pub const RESERVATION_DISCRIMINATOR: &[u8] = &[188, 235, 0, 111, 208, 253, 247, 212];
+
+/// Tickets held for a buyer while their off-chain payment settles, stored at the PDA
+/// with seeds ["reservation", raffle_key, seed]. The held tickets count against the
+/// raffle's `max_tickets` until the reservation is confirmed or released, and their
+/// numbers are fixed when reserving, so the payment maps to an exact ticket range.
+#[account(discriminator = RESERVATION_DISCRIMINATOR)]
+pub struct Reservation {
+    pub raffle: Pubkey,
+    /// The wallet the entry is created for once the payment settles
+    pub buyer: Pubkey,
+    pub ticket_count: u64,
+    /// Seed of the reservation, also used for the entry created on confirmation
+    pub seed: [u8; 8],
+    /// After this time the reservation can only be released
+    pub expires_at: i64,
+    pub bump: u8,
+    /// Index of the first reserved ticket, which the entry receives on confirmation, or
+    /// `UNNUMBERED_TICKET_START` if the raffle defers numbering
+    pub ticket_start_index: u64,
+}
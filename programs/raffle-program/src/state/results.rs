@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+// 8 discriminator + 32 raffle + 32 winner + 8 winning_ticket + 8 total_tickets
+// + 8 total_participants + 33 prize_vault + 9 draw_slot + 33 draw_slot_hash
+// + 9 draw_timestamp + 8 finalized_at + 1 bump + 8 prize_value_lamports + 9 usd_rate
+// + 9 prize_value_usd_cents
+pub const RESULTS_ACCOUNT_SIZE: usize =
+    8 + 32 + 32 + 8 + 8 + 8 + 33 + 9 + 33 + 9 + 8 + 1 + 8 + 9 + 9;
+
+// sha256("account:Results")[..8]
+pub const RESULTS_DISCRIMINATOR: &[u8] = &[158, 56, 160, 177, 25, 255, 13, 136];
+
+/// Public record of a concluded raffle. The account is never closed, so explorers can
+/// display the outcome even after the raffle's entries and balances are gone.
+#[account(discriminator = RESULTS_DISCRIMINATOR)]
+pub struct Results {
+    pub raffle: Pubkey,
+    pub winner: Pubkey,
+    pub winning_ticket: u64,
+    pub total_tickets: u64,
+    /// Number of distinct wallets that bought tickets
+    pub total_participants: u64,
+    /// The prize vault that escrowed the prize, if one was deposited
+    pub prize_vault: Option<Pubkey>,
+    /// Entropy of the draw, copied from the raffle
+    pub draw_slot: Option<u64>,
+    pub draw_slot_hash: Option<[u8; 32]>,
+    pub draw_timestamp: Option<i64>,
+    /// When the results were recorded
+    pub finalized_at: i64,
+    pub bump: u8,
+    /// Lamports the treasury held for the prize at draw time, recorded with
+    /// record_draw_value. 0 if not recorded
+    pub prize_value_lamports: u64,
+    /// SOL price in micro-USD at draw time, if recorded
+    pub usd_rate: Option<u64>,
+    /// Value of the prize in US cents at `usd_rate`, if recorded
+    pub prize_value_usd_cents: Option<u64>,
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::merkle::SNAPSHOT_TREE_DEPTH;
+
+// 8 discriminator + 32 raffle + 8 holder_count + 8 ticket_count + 32 last_owner
+// + 32 * SNAPSHOT_TREE_DEPTH branch + 1 bump
+pub const SNAPSHOT_ACCOUNT_SIZE: usize = 8 + 32 + 8 + 8 + 32 + 32 * SNAPSHOT_TREE_DEPTH + 1;
+
+// sha256("account:Snapshot")[..8]
+pub const SNAPSHOT_DISCRIMINATOR: &[u8] = &[137, 213, 28, 133, 224, 161, 48, 108];
+
+/// Progress of `snapshot_raffle` building the Merkle tree of a raffle's ticket holders,
+/// stored at the PDA with seeds ["snapshot", raffle_key]. Once every holder is added, the
+/// root is written to `Raffle::snapshot_root`.
+#[account(discriminator = SNAPSHOT_DISCRIMINATOR)]
+pub struct Snapshot {
+    pub raffle: Pubkey,
+    /// Number of ticket holders added to the tree, out of `Raffle::participant_count`
+    pub holder_count: u64,
+    /// Number of tickets held by the holders added so far
+    pub ticket_count: u64,
+    /// The last holder added, since holders are added in ascending order
+    pub last_owner: Pubkey,
+    /// The rightmost filled subtree of each height
+    pub branch: [[u8; 32]; SNAPSHOT_TREE_DEPTH],
+    pub bump: u8,
+}
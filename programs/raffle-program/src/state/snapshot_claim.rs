@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+// 8 discriminator + 32 raffle + 32 owner + 8 ticket_count + 8 claimed_at + 1 bump
+pub const SNAPSHOT_CLAIM_ACCOUNT_SIZE: usize = 8 + 32 + 32 + 8 + 8 + 1;
+
+// sha256("account:SnapshotClaim")[..8]
+pub const SNAPSHOT_CLAIM_DISCRIMINATOR: &[u8] = &[165, 200, 250, 28, 123, 121, 244, 67];
+
+/// Record that a holding in a raffle's snapshot was settled, created at the PDA with
+/// seeds ["snapshot_claim", raffle_key, owner] by whichever of claim_refund_with_proof,
+/// reclaim_expired_tickets or rollover_tickets settles it first, so each holding is
+/// refunded at most once.
+#[account(discriminator = SNAPSHOT_CLAIM_DISCRIMINATOR)]
+pub struct SnapshotClaim {
+    pub raffle: Pubkey,
+    pub owner: Pubkey,
+    /// Number of tickets settled, as recorded in the snapshot
+    pub ticket_count: u64,
+    pub claimed_at: i64,
+    pub bump: u8,
+}
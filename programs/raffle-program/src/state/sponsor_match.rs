@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+// 8 discriminator + 32 raffle + 32 sponsor + 2 match_bps + 8 cap + 1 bump
+pub const SPONSOR_MATCH_ACCOUNT_SIZE: usize = 8 + 32 + 32 + 2 + 8 + 1;
+
+// sha256("account:SponsorMatch")[..8]
+pub const SPONSOR_MATCH_DISCRIMINATOR: &[u8] = &[1, 79, 62, 113, 183, 13, 126, 141];
+
+/// Escrow of a sponsor's matching contribution for a raffle.
+/// The escrowed lamports are held by the account itself on top of its rent.
+#[account(discriminator = SPONSOR_MATCH_DISCRIMINATOR)]
+pub struct SponsorMatch {
+    pub raffle: Pubkey,
+    pub sponsor: Pubkey,
+    /// Share of net ticket sales that is matched, in basis points
+    pub match_bps: u16,
+    /// Maximum lamports that will be matched, equal to the escrowed amount
+    pub cap: u64,
+    pub bump: u8,
+}
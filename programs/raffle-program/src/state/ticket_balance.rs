@@ -1,11 +1,38 @@
 use anchor_lang::prelude::*;
 
-// 8 discriminator + 32 owner + 8 ticket_count + 1 bump
-pub const TICKET_BALANCE_ACCOUNT_SIZE: usize = 8 + 32 + 8 + 1;
+// 8 discriminator + 32 owner + 8 ticket_count + 1 bump + 33 head_entry + 8 total_spent +
+// 8 tier_discount + 1 rollover + 33 winner_commitment + 8 campaign_subsidy +
+// 33 subsidy_campaign
+pub const TICKET_BALANCE_ACCOUNT_SIZE: usize = 8 + 32 + 8 + 1 + 33 + 8 + 8 + 1 + 33 + 8 + 33;
 
-#[account]
+// sha256("account:TicketBalance")[..8]
+pub const TICKET_BALANCE_DISCRIMINATOR: &[u8] = &[228, 242, 223, 38, 51, 10, 38, 28];
+
+#[account(discriminator = TICKET_BALANCE_DISCRIMINATOR)]
 pub struct TicketBalance {
     pub owner: Pubkey,
     pub ticket_count: u64,
     pub bump: u8,
+    /// The owner's most recently created entry in this raffle, from which all of
+    /// the owner's entries can be walked through `Entry::next_entry`
+    pub head_entry: Option<Pubkey>,
+    /// Total the owner paid for their tickets in this raffle, in lamports or in base
+    /// units of the raffle's payment mint. Gifted tickets and campaign discounts are
+    /// not included
+    pub total_spent: u64,
+    /// Discount the owner's tickets received through the raffle's pricing schedule,
+    /// deducted from their refund
+    pub tier_discount: u64,
+    /// Whether the owner opted in on a purchase to roll their balance over to the
+    /// raffle's successor with rollover_tickets, should the raffle expire
+    pub rollover: bool,
+    /// Hash committing the owner to a secret they reveal to claim a win in an
+    /// anonymized raffle, see `winner_hash`. Stored as the winner hash if they win
+    pub winner_commitment: Option<[u8; 32]>,
+    /// Part of the owner's ticket cost paid by `subsidy_campaign`, returned to the
+    /// campaign instead of the owner on refund
+    pub campaign_subsidy: u64,
+    /// The promotional campaign that paid `campaign_subsidy`. A balance is only ever
+    /// subsidized by a single campaign
+    pub subsidy_campaign: Option<Pubkey>,
 }
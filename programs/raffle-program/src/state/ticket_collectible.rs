@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+// 8 discriminator + 32 raffle + 32 owner + 8 ticket_index + 8 claimed_at + 1 bump
+pub const TICKET_COLLECTIBLE_ACCOUNT_SIZE: usize = 8 + 32 + 32 + 8 + 8 + 1;
+
+// sha256("account:TicketCollectible")[..8]
+pub const TICKET_COLLECTIBLE_DISCRIMINATOR: &[u8] = &[28, 223, 84, 136, 129, 217, 19, 73];
+
+/// Claim of a numbered ticket collectible, created at the PDA with seeds
+/// ["ticket_collectible", raffle_key, ticket_index]. The account existing guarantees
+/// each ticket number of a raffle is minted at most once.
+#[account(discriminator = TICKET_COLLECTIBLE_DISCRIMINATOR)]
+pub struct TicketCollectible {
+    pub raffle: Pubkey,
+    /// The entry owner who claimed the ticket number
+    pub owner: Pubkey,
+    pub ticket_index: u64,
+    pub claimed_at: i64,
+    pub bump: u8,
+}
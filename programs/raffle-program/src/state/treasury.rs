@@ -1,10 +1,199 @@
 use anchor_lang::prelude::*;
 
-// 8 discriminator, 32 pubkey, 1 bump
-pub const TREASURY_ACCOUNT_SIZE: usize = 8 + 32 + 1;
+use crate::{
+    error::RaffleError,
+    math::{checked_add, checked_sub},
+    state::Config,
+};
 
-#[account]
+// 8 discriminator, 32 pubkey, 1 bump, 8 total_collected, 8 total_refunded, 8 total_withdrawn,
+// 8 total_sponsored, 8 total_refund_fees, 8 approved_withdrawal, 8 total_crank_rewards,
+// 8 total_referral_fees, 8 total_referral_fees_paid, 8 total_forfeited, 8 recognized_balance
+pub const TREASURY_ACCOUNT_SIZE: usize = 8 + 32 + 1 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8;
+
+// sha256("account:Treasury")[..8]
+pub const TREASURY_DISCRIMINATOR: &[u8] = &[238, 239, 123, 238, 89, 1, 168, 253];
+
+#[account(discriminator = TREASURY_DISCRIMINATOR)]
 pub struct Treasury {
     pub raffle: Pubkey,
     pub bump: u8,
+    /// Lamports received from ticket sales
+    pub total_collected: u64,
+    /// Lamports refunded to ticket holders
+    pub total_refunded: u64,
+    /// Lamports withdrawn to the payout authority
+    pub total_withdrawn: u64,
+    /// Lamports added to the prize pool by sponsor matching contributions
+    pub total_sponsored: u64,
+    /// Lamports retained from refunds as refund processing fees, owed to the payout
+    /// authority
+    pub total_refund_fees: u64,
+    /// Largest withdrawal the config's withdrawal approver approved with
+    /// approve_withdrawal, consumed by the next withdrawal
+    pub approved_withdrawal: u64,
+    /// Lamports paid to the callers cranking the raffle's expiry or draw
+    pub total_crank_rewards: u64,
+    /// Lamports accrued to referrers of ticket purchases, reserved from withdrawals
+    /// until paid
+    pub total_referral_fees: u64,
+    /// Lamports paid to referrers at withdrawals
+    pub total_referral_fees_paid: u64,
+    /// Lamports of refunds left unclaimed past the raffle's refund deadline, swept to
+    /// the insurance fund or the payout authority
+    pub total_forfeited: u64,
+    /// Lamports held above the rent reserve that were moved into the treasury by program
+    /// instructions and not yet paid out. Lamports transferred to the treasury directly
+    /// are not recognized, so they can't distort refunds and withdrawals
+    pub recognized_balance: u64,
+}
+
+impl Treasury {
+    /// Lamports collected from ticket sales and sponsors that have not yet been refunded,
+    /// withdrawn, forfeited or paid as crank rewards or referral fees. This is the upper
+    /// bound for any payout, regardless of the treasury's raw balance.
+    pub fn outstanding(&self) -> Result<u64> {
+        checked_sub(
+            checked_sub(
+                checked_sub(
+                    checked_sub(
+                        checked_sub(
+                            checked_add(self.total_collected, self.total_sponsored)?,
+                            self.total_refunded,
+                        )?,
+                        self.total_withdrawn,
+                    )?,
+                    self.total_crank_rewards,
+                )?,
+                self.total_referral_fees_paid,
+            )?,
+            self.total_forfeited,
+        )
+    }
+
+    /// Lamports still owed to ticket holders of an expired or cancelled raffle, i.e. the
+    /// outstanding funds less the refund processing fees not yet withdrawn
+    pub fn unclaimed_refunds(&self) -> Result<u64> {
+        checked_sub(
+            self.outstanding()?,
+            self.total_refund_fees.saturating_sub(self.total_withdrawn),
+        )
+    }
+
+    /// Lamports accrued to referrers that were not yet paid
+    pub fn unpaid_referral_fees(&self) -> Result<u64> {
+        checked_sub(self.total_referral_fees, self.total_referral_fees_paid)
+    }
+
+    /// Lamports collected from ticket sales that were not refunded
+    pub fn net_sales(&self) -> Result<u64> {
+        checked_sub(self.total_collected, self.total_refunded)
+    }
+
+    /// Records lamports received from a ticket purchase
+    pub fn record_collection(&mut self, amount: u64) -> Result<()> {
+        self.total_collected = checked_add(self.total_collected, amount)?;
+        Ok(())
+    }
+
+    /// Records lamports added by a sponsor matching contribution
+    pub fn record_sponsorship(&mut self, amount: u64) -> Result<()> {
+        self.total_sponsored = checked_add(self.total_sponsored, amount)?;
+        Ok(())
+    }
+
+    /// Records lamports moved into the treasury by a program instruction
+    pub fn record_deposit(&mut self, amount: u64) -> Result<()> {
+        self.recognized_balance = checked_add(self.recognized_balance, amount)?;
+        Ok(())
+    }
+
+    /// Records lamports paid out of the treasury, failing if they exceed the recognized
+    /// balance
+    pub fn record_payout(&mut self, amount: u64) -> Result<()> {
+        require!(
+            amount <= self.recognized_balance,
+            RaffleError::TreasuryInsolvent
+        );
+        self.recognized_balance = checked_sub(self.recognized_balance, amount)?;
+        Ok(())
+    }
+
+    /// Records a refund to a ticket holder, failing if it exceeds the outstanding funds
+    pub fn record_refund(&mut self, amount: u64) -> Result<()> {
+        require!(
+            amount <= self.outstanding()?,
+            RaffleError::TreasuryInsolvent
+        );
+        self.total_refunded = checked_add(self.total_refunded, amount)?;
+        Ok(())
+    }
+
+    /// Records a crank reward, failing if it exceeds the outstanding funds
+    pub fn record_crank_reward(&mut self, amount: u64) -> Result<()> {
+        require!(
+            amount <= self.outstanding()?,
+            RaffleError::TreasuryInsolvent
+        );
+        self.total_crank_rewards = checked_add(self.total_crank_rewards, amount)?;
+        Ok(())
+    }
+
+    /// Records a referral fee accrued by a ticket purchase
+    pub fn record_referral_fee(&mut self, amount: u64) -> Result<()> {
+        self.total_referral_fees = checked_add(self.total_referral_fees, amount)?;
+        Ok(())
+    }
+
+    /// Records a referral fee paid to a referrer, failing if it exceeds the outstanding
+    /// funds
+    pub fn record_referral_payout(&mut self, amount: u64) -> Result<()> {
+        require!(
+            amount <= self.outstanding()?,
+            RaffleError::TreasuryInsolvent
+        );
+        self.total_referral_fees_paid = checked_add(self.total_referral_fees_paid, amount)?;
+        Ok(())
+    }
+
+    /// Records unclaimed refunds forfeited past the refund deadline, failing if they
+    /// exceed the outstanding funds
+    pub fn record_forfeiture(&mut self, amount: u64) -> Result<()> {
+        require!(
+            amount <= self.outstanding()?,
+            RaffleError::TreasuryInsolvent
+        );
+        self.total_forfeited = checked_add(self.total_forfeited, amount)?;
+        Ok(())
+    }
+
+    /// Records lamports retained from a refund as a refund processing fee. The fee stays
+    /// outstanding until it is withdrawn
+    pub fn record_refund_fee(&mut self, amount: u64) -> Result<()> {
+        self.total_refund_fees = checked_add(self.total_refund_fees, amount)?;
+        Ok(())
+    }
+
+    /// Consumes the withdrawal approval for a withdrawal of `amount`, failing if the
+    /// config requires an approval that doesn't cover it
+    pub fn consume_withdrawal_approval(&mut self, config: &Config, amount: u64) -> Result<()> {
+        if config.withdrawal_requires_approval(amount) {
+            require!(
+                amount <= self.approved_withdrawal,
+                RaffleError::WithdrawalNotApproved
+            );
+        }
+        self.approved_withdrawal = 0;
+        Ok(())
+    }
+
+    /// Records a withdrawal to the payout authority, failing if it exceeds the outstanding funds
+    pub fn record_withdrawal(&mut self, amount: u64) -> Result<()> {
+        require!(
+            amount <= self.outstanding()?,
+            RaffleError::TreasuryInsolvent
+        );
+        self.total_withdrawn = checked_add(self.total_withdrawn, amount)?;
+        Ok(())
+    }
 }
@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+// 8 discriminator + 32 wallet + 1 tier + 1 bump
+pub const USER_PROFILE_ACCOUNT_SIZE: usize = 8 + 32 + 1 + 1;
+
+// sha256("account:UserProfile")[..8]
+pub const USER_PROFILE_DISCRIMINATOR: &[u8] = &[32, 37, 119, 205, 179, 180, 13, 194];
+
+/// Loyalty standing of a wallet assigned by the management authority, stored at the PDA
+/// with seeds ["user_profile", wallet]
+#[account(discriminator = USER_PROFILE_DISCRIMINATOR)]
+pub struct UserProfile {
+    pub wallet: Pubkey,
+    /// Loyalty tier, higher tiers get earlier access to priority sales. Zero is the
+    /// default tier of wallets without a profile
+    pub tier: u8,
+    pub bump: u8,
+}
@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+// 8 discriminator + 32 ballot + 32 voter + 1 choice + 8 amount + 1 bump
+pub const VOTE_ACCOUNT_SIZE: usize = 8 + 32 + 32 + 1 + 8 + 1;
+
+// sha256("account:Vote")[..8]
+pub const VOTE_DISCRIMINATOR: &[u8] = &[96, 91, 104, 57, 145, 35, 172, 155];
+
+/// A wallet's vote in a ballot, stored at the PDA with seeds ["vote", ballot_key,
+/// voter]. Closed by reclaim_vote, which returns the locked tokens.
+#[account(discriminator = VOTE_DISCRIMINATOR)]
+pub struct Vote {
+    pub ballot: Pubkey,
+    pub voter: Pubkey,
+    /// Index of the shortlisted entry voted for
+    pub choice: u8,
+    /// Tokens locked in the ballot, which is the weight of the vote
+    pub amount: u64,
+    pub bump: u8,
+}
@@ -1,9 +1,45 @@
 use anchor_lang::prelude::*;
 
-// 8 (discriminator) + 4 (string length) + 854 (max string size)
-pub const WINNER_DATA_ACCOUNT_SIZE: usize = 8 + 4 + 854;
+// 8 (discriminator) + 4 (string length) + 854 (max string size) + 1 (format_version)
+// + 1 (encryption_scheme) + 8 (submitted_at) + 1 (locked)
+pub const WINNER_DATA_ACCOUNT_SIZE: usize = 8 + 4 + 854 + 1 + 1 + 8 + 1;
 
-#[account]
+/// How long after the first submission the winner can overwrite their data
+pub const WINNER_DATA_EDIT_WINDOW_SECS: i64 = 24 * 60 * 60; // 1 day
+
+/// Layout of the winner data blob supported since the first release
+pub const WINNER_DATA_FORMAT_V1: u8 = 1;
+
+/// Blob encrypted to an X25519 key of the fulfillment team
+pub const ENCRYPTION_SCHEME_X25519: u8 = 1;
+/// Blob encrypted with HPKE (RFC 9180)
+pub const ENCRYPTION_SCHEME_HPKE: u8 = 2;
+
+// sha256("account:WinnerData")[..8]
+pub const WINNER_DATA_DISCRIMINATOR: &[u8] = &[45, 20, 105, 126, 134, 28, 6, 102];
+
+#[account(discriminator = WINNER_DATA_DISCRIMINATOR)]
 pub struct WinnerData {
     pub data: String,
+    /// Layout version of `data`, one of the config's supported format versions at the
+    /// time of submission
+    pub format_version: u8,
+    /// How `data` was encrypted, one of the config's supported encryption schemes at the
+    /// time of submission
+    pub encryption_scheme: u8,
+    /// Time of the first submission, from which the edit window is measured
+    pub submitted_at: i64,
+    /// Whether the data is final and can no longer be overwritten
+    pub locked: bool,
+}
+
+impl WinnerData {
+    /// Whether the data can still be overwritten at `now`
+    pub fn editable(&self, now: i64) -> bool {
+        !self.locked
+            && now
+                < self
+                    .submitted_at
+                    .saturating_add(WINNER_DATA_EDIT_WINDOW_SECS)
+    }
 }
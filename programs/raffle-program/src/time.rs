@@ -0,0 +1,56 @@
+//! Time source of the program.
+//!
+//! Every instruction reads the cluster clock through `clock` or `now`, so devnet builds
+//! can shift the time by the config's clock offset and move raffles past their end time,
+//! funding deadline or claim windows without waiting. Mainnet builds ignore the offset.
+//! Slots are never shifted, as the draw entropy is tied to the real slot history.
+
+use anchor_lang::prelude::*;
+
+use crate::{error::RaffleError, state::Config};
+
+/// Largest timestamp accepted as seconds. Later timestamps, past the year 5138, are
+/// milliseconds, e.g. from a client passing `Date.now()`
+pub const MAX_TIMESTAMP_SECONDS: i64 = 100_000_000_000;
+
+/// Returns the cluster clock, with the unix timestamp shifted by the config's clock
+/// offset on devnet builds
+pub fn clock(config: &Config) -> Result<Clock> {
+    let mut clock = cluster_clock()?;
+    if cfg!(feature = "devnet") {
+        clock.unix_timestamp = clock
+            .unix_timestamp
+            .checked_add(config.clock_offset)
+            .ok_or(RaffleError::Overflow)?;
+    }
+    Ok(clock)
+}
+
+/// Returns the current unix timestamp, shifted by the config's clock offset on devnet
+/// builds
+pub fn now(config: &Config) -> Result<i64> {
+    Ok(clock(config)?.unix_timestamp)
+}
+
+/// Returns the unshifted cluster clock, for validating accounts the cluster or its
+/// oracles timestamped, such as price feeds and randomness accounts
+pub fn cluster_clock() -> Result<Clock> {
+    Ok(Clock::get()?)
+}
+
+/// Returns the current slot, which is never shifted
+pub fn current_slot() -> Result<u64> {
+    Ok(cluster_clock()?.slot)
+}
+
+/// Whether a timestamp passed to the program is a unix timestamp in seconds
+pub fn in_seconds(timestamp: i64) -> bool {
+    (0..=MAX_TIMESTAMP_SECONDS).contains(&timestamp)
+}
+
+/// Ensures a timestamp passed to the program is in seconds, so a timestamp in
+/// milliseconds fails with a clear error rather than a misleading range check
+pub fn require_seconds(timestamp: i64) -> Result<()> {
+    require!(in_seconds(timestamp), RaffleError::TimestampNotInSeconds);
+    Ok(())
+}
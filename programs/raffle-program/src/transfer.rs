@@ -0,0 +1,137 @@
+//! Lamport transfers shared by all instructions moving SOL.
+//!
+//! Accounts owned by this program, such as treasuries, escrows and vaults, are debited
+//! directly with [`move_lamports`]. Wallets pay into program accounts through the system
+//! program with [`transfer_lamports`], which verifies the recipient was credited. Both
+//! map failures to the same errors, `RaffleError::InsufficientFunds` when the payer
+//! can't cover the amount and `RaffleError::Overflow` when the recipient's balance
+//! overflows, so payment features don't each roll their own checks.
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{program::invoke, system_instruction},
+};
+
+use crate::{error::RaffleError, math::checked_add};
+
+/// Moves `amount` lamports from `from`, which must be owned by this program, to `to` by
+/// adjusting both balances directly
+pub fn move_lamports<'info>(
+    from: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let from_balance = from
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(RaffleError::InsufficientFunds)?;
+    **from.try_borrow_mut_lamports()? = from_balance;
+    let to_balance = checked_add(to.lamports(), amount)?;
+    **to.try_borrow_mut_lamports()? = to_balance;
+    Ok(())
+}
+
+/// Transfers `amount` lamports from `from`, a signing system account, to `to` through
+/// the system program, verifying that `to` was credited the full amount
+pub fn transfer_lamports<'info>(
+    from: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    require!(from.lamports() >= amount, RaffleError::InsufficientFunds);
+    let expected_balance = checked_add(to.lamports(), amount)?;
+
+    invoke(
+        &system_instruction::transfer(from.key, to.key, amount),
+        &[from.clone(), to.clone(), system_program.clone()],
+    )?;
+
+    require!(
+        to.lamports() == expected_balance,
+        RaffleError::TransferFailed
+    );
+    Ok(())
+}
+
+/// Lamports an account of `data_len` bytes must hold to be rent exempt
+pub fn rent_reserve(data_len: usize) -> Result<u64> {
+    Ok(Rent::get()?.minimum_balance(data_len))
+}
+
+/// Lamports `account` must hold to be rent exempt at its current data length, which
+/// unlike the account's size constant stays correct across size migrations
+pub fn rent_reserve_of(account: &AccountInfo) -> Result<u64> {
+    rent_reserve(account.data_len())
+}
+
+/// Lamports `account` holds above `reserve`, which can be moved out without dipping
+/// into the reserve
+pub fn excess_lamports(account: &AccountInfo, reserve: u64) -> u64 {
+    account.lamports().saturating_sub(reserve)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account<'a>(key: &'a Pubkey, lamports: &'a mut u64, data: &'a mut [u8]) -> AccountInfo<'a> {
+        AccountInfo::new(key, false, true, lamports, data, &crate::ID, false, 0)
+    }
+
+    #[test]
+    fn move_lamports_adjusts_both_balances() {
+        let (from_key, to_key) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (mut from_lamports, mut to_lamports) = (1_000, 50);
+        let (mut from_data, mut to_data) = ([0u8; 0], [0u8; 0]);
+        let from = account(&from_key, &mut from_lamports, &mut from_data);
+        let to = account(&to_key, &mut to_lamports, &mut to_data);
+
+        move_lamports(&from, &to, 400).unwrap();
+        assert_eq!(from.lamports(), 600);
+        assert_eq!(to.lamports(), 450);
+
+        move_lamports(&from, &to, 600).unwrap();
+        assert_eq!(from.lamports(), 0);
+        assert_eq!(to.lamports(), 1_050);
+    }
+
+    #[test]
+    fn move_lamports_rejects_insufficient_funds() {
+        let (from_key, to_key) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (mut from_lamports, mut to_lamports) = (100, 0);
+        let (mut from_data, mut to_data) = ([0u8; 0], [0u8; 0]);
+        let from = account(&from_key, &mut from_lamports, &mut from_data);
+        let to = account(&to_key, &mut to_lamports, &mut to_data);
+
+        let error = move_lamports(&from, &to, 101).unwrap_err();
+        assert_eq!(error, RaffleError::InsufficientFunds.into());
+        assert_eq!(from.lamports(), 100);
+        assert_eq!(to.lamports(), 0);
+    }
+
+    #[test]
+    fn move_lamports_rejects_overflow() {
+        let (from_key, to_key) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (mut from_lamports, mut to_lamports) = (100, u64::MAX);
+        let (mut from_data, mut to_data) = ([0u8; 0], [0u8; 0]);
+        let from = account(&from_key, &mut from_lamports, &mut from_data);
+        let to = account(&to_key, &mut to_lamports, &mut to_data);
+
+        let error = move_lamports(&from, &to, 1).unwrap_err();
+        assert_eq!(error, RaffleError::Overflow.into());
+        assert_eq!(to.lamports(), u64::MAX);
+    }
+
+    #[test]
+    fn excess_lamports_saturates_below_reserve() {
+        let key = Pubkey::new_unique();
+        let mut lamports = 1_500;
+        let mut data = [0u8; 0];
+        let account = account(&key, &mut lamports, &mut data);
+
+        assert_eq!(excess_lamports(&account, 1_000), 500);
+        assert_eq!(excess_lamports(&account, 1_500), 0);
+        assert_eq!(excess_lamports(&account, 2_000), 0);
+    }
+}
@@ -0,0 +1,319 @@
+//! Movement of ticket payments in and out of raffle treasuries.
+//!
+//! Instructions move funds through a [`TreasuryVault`] rather than transferring directly,
+//! so the transfer, its verification and the treasury's solvency bookkeeping are
+//! implemented once per kind of vault. [`LamportVault`] holds SOL in the treasury PDA
+//! itself, tracking the lamports it moved as the treasury's recognized balance;
+//! [`TokenVault`] holds SPL tokens in the treasury's associated token account, for
+//! raffles paid in an SPL token. If the treasury's payment tokens are frozen, refunds are
+//! paid from its token account for the raffle's refund mint instead, converted at the
+//! refund rate.
+
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::{self, get_associated_token_address, AssociatedToken},
+    token::{self, Mint, Token, TokenAccount, Transfer},
+};
+
+use crate::{
+    error::RaffleError,
+    math::{checked_add, convert_at_rate},
+    state::{Raffle, Treasury},
+    transfer::{excess_lamports, move_lamports, rent_reserve_of, transfer_lamports},
+};
+
+/// Common interface over the vaults holding a raffle's ticket revenue
+pub trait TreasuryVault<'info> {
+    /// Moves `amount` from the payer into the vault and records it as collected
+    fn collect(&mut self, payer: &AccountInfo<'info>, amount: u64) -> Result<()>;
+
+    /// Moves `amount` from the vault to a ticket holder, failing if it exceeds the
+    /// outstanding funds
+    fn refund(&mut self, recipient: &AccountInfo<'info>, amount: u64) -> Result<()>;
+
+    /// Moves `amount` from the vault to the payout authority, failing if it exceeds the
+    /// outstanding funds
+    fn withdraw(&mut self, recipient: &AccountInfo<'info>, amount: u64) -> Result<()>;
+
+    /// Moves `amount` of unclaimed refunds from the vault to the forfeiture destination,
+    /// failing if it exceeds the outstanding funds
+    fn forfeit(&mut self, recipient: &AccountInfo<'info>, amount: u64) -> Result<()>;
+}
+
+/// Vault holding lamports directly in the treasury PDA
+pub struct LamportVault<'a, 'info> {
+    treasury: &'a mut Account<'info, Treasury>,
+    system_program: &'a Program<'info, System>,
+}
+
+impl<'a, 'info> LamportVault<'a, 'info> {
+    pub fn new(
+        treasury: &'a mut Account<'info, Treasury>,
+        system_program: &'a Program<'info, System>,
+    ) -> Self {
+        Self {
+            treasury,
+            system_program,
+        }
+    }
+
+    /// Transfers lamports by directly deducting from the treasury and adding to the
+    /// recipient. This only works because the treasury is a PDA owned by our program.
+    fn pay_out(&mut self, recipient: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        self.treasury.record_payout(amount)?;
+        move_lamports(&self.treasury.to_account_info(), recipient, amount)
+    }
+
+    /// Moves `amount` from the vault to the caller of a crank, failing if it exceeds the
+    /// outstanding funds
+    pub fn pay_crank_reward(&mut self, recipient: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        self.treasury.record_crank_reward(amount)?;
+        self.pay_out(recipient, amount)
+    }
+
+    /// Moves `amount` from the vault to a referrer, failing if it exceeds the outstanding
+    /// funds
+    pub fn pay_referral_fee(&mut self, recipient: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        self.treasury.record_referral_payout(amount)?;
+        self.pay_out(recipient, amount)
+    }
+}
+
+impl<'info> TreasuryVault<'info> for LamportVault<'_, 'info> {
+    fn collect(&mut self, payer: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        transfer_lamports(
+            payer,
+            &self.treasury.to_account_info(),
+            &self.system_program.to_account_info(),
+            amount,
+        )?;
+
+        // Track the collected funds for the treasury solvency checks
+        self.treasury.record_deposit(amount)?;
+        self.treasury.record_collection(amount)
+    }
+
+    fn refund(&mut self, recipient: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        self.treasury.record_refund(amount)?;
+        self.pay_out(recipient, amount)
+    }
+
+    fn withdraw(&mut self, recipient: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        self.treasury.record_withdrawal(amount)?;
+        self.pay_out(recipient, amount)
+    }
+
+    fn forfeit(&mut self, recipient: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        self.treasury.record_forfeiture(amount)?;
+        self.pay_out(recipient, amount)
+    }
+}
+
+/// Vault holding SPL tokens in the treasury's associated token account for the raffle's
+/// payment mint
+pub struct TokenVault<'a, 'info> {
+    treasury: &'a mut Account<'info, Treasury>,
+    vault: &'a mut Account<'info, TokenAccount>,
+    token_program: &'a Program<'info, Token>,
+    payer_authority: Option<AccountInfo<'info>>,
+    refund_rate: Option<u64>,
+}
+
+impl<'a, 'info> TokenVault<'a, 'info> {
+    pub fn new(
+        treasury: &'a mut Account<'info, Treasury>,
+        vault: &'a mut Account<'info, TokenAccount>,
+        token_program: &'a Program<'info, Token>,
+    ) -> Self {
+        Self {
+            treasury,
+            vault,
+            token_program,
+            payer_authority: None,
+            refund_rate: None,
+        }
+    }
+
+    /// Sets the owner of the token accounts funds are collected from, which has to sign
+    /// the transfer into the vault
+    pub fn with_payer_authority(mut self, authority: &AccountInfo<'info>) -> Self {
+        self.payer_authority = Some(authority.clone());
+        self
+    }
+
+    /// Pays refunds and forfeitures from the vault of the raffle's refund mint, if it has
+    /// one, converting the recorded amounts in payment mint units at the refund rate
+    pub fn with_refund_mint(mut self, raffle: &Raffle) -> Self {
+        if raffle.refund_mint.is_some() {
+            self.refund_rate = Some(raffle.refund_rate);
+        }
+        self
+    }
+
+    /// Transfers tokens out of the vault, signed by the treasury PDA that owns it
+    fn pay_out(&mut self, recipient: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        let amount = match self.refund_rate {
+            Some(rate) => convert_at_rate(amount, rate)?,
+            None => amount,
+        };
+        let raffle = self.treasury.raffle;
+        token::transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: self.vault.to_account_info(),
+                    to: recipient.clone(),
+                    authority: self.treasury.to_account_info(),
+                },
+                &[&[b"treasury", raffle.as_ref(), &[self.treasury.bump]]],
+            ),
+            amount,
+        )?;
+        self.vault.reload()
+    }
+}
+
+impl<'info> TreasuryVault<'info> for TokenVault<'_, 'info> {
+    fn collect(&mut self, payer: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        let authority = self
+            .payer_authority
+            .clone()
+            .ok_or(RaffleError::TransferFailed)?;
+
+        // Store pre-transfer balance for verification
+        let pre_transfer_balance = self.vault.amount;
+
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: payer.clone(),
+                    to: self.vault.to_account_info(),
+                    authority,
+                },
+            ),
+            amount,
+        )?;
+
+        // Verify the transfer was successful by checking the vault balance
+        self.vault.reload()?;
+        require!(
+            self.vault.amount == checked_add(pre_transfer_balance, amount)?,
+            RaffleError::TransferFailed
+        );
+
+        // Track the collected funds for the treasury solvency checks
+        self.treasury.record_collection(amount)
+    }
+
+    fn refund(&mut self, recipient: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        self.treasury.record_refund(amount)?;
+        self.pay_out(recipient, amount)
+    }
+
+    fn withdraw(&mut self, recipient: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        self.treasury.record_withdrawal(amount)?;
+        self.pay_out(recipient, amount)
+    }
+
+    fn forfeit(&mut self, recipient: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        self.treasury.record_forfeiture(amount)?;
+        self.pay_out(recipient, amount)
+    }
+}
+
+/// Lamports that can be paid out of a treasury holding lamports: its recognized balance,
+/// limited to the lamports above the rent reserve of its current data length, so a
+/// treasury grown by a migration keeps enough lamports to stay rent exempt
+pub fn available_lamports(treasury: &Account<Treasury>) -> Result<u64> {
+    let treasury_info = treasury.to_account_info();
+    let above_rent = excess_lamports(&treasury_info, rent_reserve_of(&treasury_info)?);
+    Ok(treasury.recognized_balance.min(above_rent))
+}
+
+/// Returns the vault and token program of a raffle paid in an SPL token, ensuring the
+/// vault is the treasury's associated token account for the raffle's payment mint
+pub fn token_accounts<'a, 'info>(
+    raffle: &Raffle,
+    treasury: &Account<'info, Treasury>,
+    vault: Option<&'a mut Account<'info, TokenAccount>>,
+    token_program: Option<&'a Program<'info, Token>>,
+) -> Result<(
+    &'a mut Account<'info, TokenAccount>,
+    &'a Program<'info, Token>,
+)> {
+    let payment_mint = raffle
+        .payment_mint
+        .ok_or(RaffleError::TokenPaymentsNotSupported)?;
+    let (Some(vault), Some(token_program)) = (vault, token_program) else {
+        return err!(RaffleError::InvalidPaymentMint);
+    };
+    require_keys_eq!(
+        vault.key(),
+        get_associated_token_address(&treasury.key(), &payment_mint),
+        RaffleError::InvalidPaymentMint
+    );
+    Ok((vault, token_program))
+}
+
+/// Returns the vault and token program refunds of a raffle paid in an SPL token are paid
+/// from, ensuring the vault is the treasury's associated token account for the raffle's
+/// refund mint, or for its payment mint while it has none
+pub fn refund_token_accounts<'a, 'info>(
+    raffle: &Raffle,
+    treasury: &Account<'info, Treasury>,
+    vault: Option<&'a mut Account<'info, TokenAccount>>,
+    token_program: Option<&'a Program<'info, Token>>,
+) -> Result<(
+    &'a mut Account<'info, TokenAccount>,
+    &'a Program<'info, Token>,
+)> {
+    let Some(refund_mint) = raffle.refund_mint else {
+        return token_accounts(raffle, treasury, vault, token_program);
+    };
+    let (Some(vault), Some(token_program)) = (vault, token_program) else {
+        return err!(RaffleError::InvalidPaymentMint);
+    };
+    require_keys_eq!(
+        vault.key(),
+        get_associated_token_address(&treasury.key(), &refund_mint),
+        RaffleError::InvalidPaymentMint
+    );
+    Ok((vault, token_program))
+}
+
+/// Creates the associated token account of a program owned escrow, e.g. the treasury,
+/// for `mint`, if the escrowed asset is an SPL token. Lamports need no vault besides the
+/// escrow itself.
+pub fn open_token_vault<'info>(
+    payer: &Signer<'info>,
+    owner: &AccountInfo<'info>,
+    mint: Option<&Account<'info, Mint>>,
+    vault: Option<&UncheckedAccount<'info>>,
+    token_program: Option<&Program<'info, Token>>,
+    associated_token_program: Option<&Program<'info, AssociatedToken>>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let Some(mint) = mint else {
+        return Ok(());
+    };
+    let (Some(vault), Some(token_program), Some(associated_token_program)) =
+        (vault, token_program, associated_token_program)
+    else {
+        return err!(RaffleError::InvalidPaymentMint);
+    };
+
+    // The associated token program only creates the account at the address derived
+    // from the owner and the mint
+    associated_token::create(CpiContext::new(
+        associated_token_program.to_account_info(),
+        associated_token::Create {
+            payer: payer.to_account_info(),
+            associated_token: vault.to_account_info(),
+            authority: owner.clone(),
+            mint: mint.to_account_info(),
+            system_program: system_program.to_account_info(),
+            token_program: token_program.to_account_info(),
+        },
+    ))
+}
@@ -0,0 +1,157 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("approve_withdrawal", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		const payoutAuthority = new Keypair();
+		provider.client.airdrop(
+			payoutAuthority.publicKey,
+			BigInt(0.1 * LAMPORTS_PER_SOL),
+		);
+		const approver = new Keypair();
+		provider.client.airdrop(approver.publicKey, BigInt(0.1 * LAMPORTS_PER_SOL));
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: payoutAuthority.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(1),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		// Sell 5 tickets, 0.5 SOL of revenue
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+		await raffleProgram.methods
+			.buyTickets(new BN(5), Array.from(new Uint8Array(8)), null, null, null, null, false, null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+
+		// Manually set the raffle state to drawn
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		const raffleData = await raffleProgram.coder.accounts.encode("raffle", {
+			...raffle,
+			raffleState: { drawn: {} },
+			winnerAddress: new Keypair().publicKey,
+		});
+		provider.client.setAccount(raffleAccountId, {
+			executable: false,
+			owner: raffleProgram.programId,
+			lamports: 1 * LAMPORTS_PER_SOL,
+			data: raffleData,
+		});
+
+		const setApprover = (threshold: number) =>
+			raffleProgram.methods
+				.setWithdrawalApprover(approver.publicKey, new BN(threshold))
+				.rpc();
+
+		const approve = (amount: number, signer: Keypair = approver) =>
+			raffleProgram.methods
+				.approveWithdrawal(new BN(amount))
+				.accounts({ raffle: raffleAccountId, approver: signer.publicKey })
+				.signers([signer])
+				.rpc();
+
+		const withdraw = () =>
+			raffleProgram.methods
+				.withdrawFromTreasury()
+				.accounts({
+					raffle: raffleAccountId,
+					payoutAuthority: payoutAuthority.publicKey,
+					feeRecipient: null,
+				})
+				.rpc();
+
+		return {
+			provider,
+			payoutAuthority,
+			setApprover,
+			approve,
+			withdraw,
+		};
+	};
+
+	it("should withdraw above the threshold once approved", async () => {
+		const { provider, payoutAuthority, setApprover, approve, withdraw } =
+			await setup();
+
+		await setApprover(0.1 * LAMPORTS_PER_SOL);
+		expect(withdraw()).rejects.toThrow(/WithdrawalNotApproved/);
+
+		// An approval below the withdrawal doesn't cover it
+		await approve(0.4 * LAMPORTS_PER_SOL);
+		expect(withdraw()).rejects.toThrow(/WithdrawalNotApproved/);
+
+		await approve(0.5 * LAMPORTS_PER_SOL);
+		const balanceBefore = provider.client.getBalance(payoutAuthority.publicKey)!;
+		await withdraw();
+		expect(
+			provider.client.getBalance(payoutAuthority.publicKey)! - balanceBefore,
+		).toEqual(BigInt(0.5 * LAMPORTS_PER_SOL));
+	});
+
+	it("should withdraw up to the threshold without approval", async () => {
+		const { provider, payoutAuthority, setApprover, withdraw } = await setup();
+
+		await setApprover(0.5 * LAMPORTS_PER_SOL);
+		const balanceBefore = provider.client.getBalance(payoutAuthority.publicKey)!;
+		await withdraw();
+		expect(
+			provider.client.getBalance(payoutAuthority.publicKey)! - balanceBefore,
+		).toEqual(BigInt(0.5 * LAMPORTS_PER_SOL));
+	});
+
+	it("should fail to approve with a key other than the approver", async () => {
+		const { provider, setApprover, approve } = await setup();
+
+		await setApprover(0);
+		const account = new Keypair();
+		provider.client.airdrop(account.publicKey, BigInt(LAMPORTS_PER_SOL));
+		expect(approve(0.5 * LAMPORTS_PER_SOL, account)).rejects.toThrow(
+			/NotWithdrawalApprover/,
+		);
+	});
+});
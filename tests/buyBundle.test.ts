@@ -0,0 +1,176 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("buy_bundle", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		// Create two raffles with different ticket prices
+		const creationTime = client.getClock().unixTimestamp;
+		const ticketPrices = [
+			new BN(0.1 * LAMPORTS_PER_SOL),
+			new BN(0.2 * LAMPORTS_PER_SOL),
+		];
+		const raffles: { raffle: PublicKey; treasury: PublicKey }[] = [];
+		for (const [index, ticketPrice] of ticketPrices.entries()) {
+			await raffleProgram.methods
+				.createRaffle(
+					"https://www.example.org",
+					ticketPrice,
+					new BN((creationTime + BigInt(3601)).toString()),
+					new BN(1),
+					null,
+					null,
+					false,
+					null,
+					false,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+				)
+				.rpc();
+			const raffle = PublicKey.findProgramAddressSync(
+				[
+					Buffer.from("raffle"),
+					new Uint8Array(new BN(index).toArray("le", 8)),
+				],
+				raffleProgram.programId,
+			)[0];
+			const treasury = PublicKey.findProgramAddressSync(
+				[Buffer.from("treasury"), raffle.toBytes()],
+				raffleProgram.programId,
+			)[0];
+			raffles.push({ raffle, treasury });
+		}
+
+		const bundleId = PublicKey.findProgramAddressSync(
+			[Buffer.from("bundle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		return { provider, raffleProgram, raffles, bundleId };
+	};
+
+	const raffleAccounts = (raffles: { raffle: PublicKey }[]) =>
+		raffles.map(({ raffle }) => ({
+			pubkey: raffle,
+			isSigner: false,
+			isWritable: false,
+		}));
+
+	it("should buy a ticket in every raffle of the bundle at a discount", async () => {
+		const { provider, raffleProgram, raffles, bundleId } = await setup();
+
+		const bundlePrice = new BN(0.24 * LAMPORTS_PER_SOL);
+		await raffleProgram.methods
+			.createBundle(new BN(0), bundlePrice)
+			.remainingAccounts(raffleAccounts(raffles))
+			.rpc();
+
+		const bundle = await raffleProgram.account.bundle.fetch(bundleId);
+		expect(bundle.raffles.map((r) => r.toBase58())).toEqual(
+			raffles.map(({ raffle }) => raffle.toBase58()),
+		);
+
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+		const entrySeed = new Uint8Array(8);
+		const remainingAccounts = [];
+		for (const { raffle, treasury } of raffles) {
+			await raffleProgram.methods
+				.initTicketBalance(null)
+				.accounts({ signer: buyer.publicKey, raffle })
+				.signers([buyer])
+				.rpc();
+			const ticketBalance = PublicKey.findProgramAddressSync(
+				[
+					Buffer.from("ticket_balance"),
+					raffle.toBytes(),
+					buyer.publicKey.toBytes(),
+				],
+				raffleProgram.programId,
+			)[0];
+			const entry = PublicKey.findProgramAddressSync(
+				[Buffer.from("entry"), raffle.toBytes(), entrySeed],
+				raffleProgram.programId,
+			)[0];
+			for (const pubkey of [raffle, treasury, ticketBalance, entry]) {
+				remainingAccounts.push({ pubkey, isSigner: false, isWritable: true });
+			}
+		}
+
+		await raffleProgram.methods
+			.buyBundle(Array.from(entrySeed), bundlePrice)
+			.accounts({ bundle: bundleId, signer: buyer.publicKey })
+			.remainingAccounts(remainingAccounts)
+			.signers([buyer])
+			.rpc();
+
+		// The price is split 1:2 like the ticket prices, and the rest of each ticket
+		// price is recorded as a discount
+		const expectedShares = [0.08 * LAMPORTS_PER_SOL, 0.16 * LAMPORTS_PER_SOL];
+		const expectedDiscounts = [20_000_000, 40_000_000];
+		for (const [index, { raffle, treasury }] of raffles.entries()) {
+			const raffleAccount = await raffleProgram.account.raffle.fetch(raffle);
+			expect(raffleAccount.currentTickets.toNumber()).toBe(1);
+			expect(raffleAccount.participantCount.toNumber()).toBe(1);
+			expect(raffleAccount.tierDiscounts.toNumber()).toBe(
+				expectedDiscounts[index],
+			);
+
+			const treasuryAccount =
+				await raffleProgram.account.treasury.fetch(treasury);
+			expect(treasuryAccount.totalCollected.toNumber()).toBe(
+				expectedShares[index],
+			);
+		}
+	});
+
+	it("should fail to create a bundle that is not discounted", async () => {
+		const { raffleProgram, raffles } = await setup();
+
+		expect(
+			raffleProgram.methods
+				.createBundle(new BN(0), new BN(0.3 * LAMPORTS_PER_SOL))
+				.remainingAccounts(raffleAccounts(raffles))
+				.rpc(),
+		).rejects.toThrow(/BundleNotDiscounted/);
+	});
+
+	it("should fail to create a bundle with a single or duplicate raffle", async () => {
+		const { raffleProgram, raffles } = await setup();
+
+		expect(
+			raffleProgram.methods
+				.createBundle(new BN(0), new BN(0.05 * LAMPORTS_PER_SOL))
+				.remainingAccounts(raffleAccounts(raffles.slice(0, 1)))
+				.rpc(),
+		).rejects.toThrow(/InvalidBundle/);
+		expect(
+			raffleProgram.methods
+				.createBundle(new BN(0), new BN(0.15 * LAMPORTS_PER_SOL))
+				.remainingAccounts(raffleAccounts([raffles[0], raffles[0]]))
+				.rpc(),
+		).rejects.toThrow(/InvalidBundle/);
+	});
+});
@@ -37,7 +37,24 @@ describe("buy_tickets", async () => {
 
 		// Create raffle
 		await raffleProgram.methods
-			.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
 			.rpc();
 		const raffleAccountId = PublicKey.findProgramAddressSync(
 			[
@@ -135,7 +152,7 @@ describe("buy_tickets", async () => {
 			} catch (err) {
 				if (err instanceof Error && err.message.includes("Could not find")) {
 					await raffleProgram.methods
-						.initTicketBalance()
+						.initTicketBalance(null)
 						.accounts({
 							signer: buyer.publicKey,
 							raffle: new PublicKey(raffleAccountId),
@@ -151,7 +168,7 @@ describe("buy_tickets", async () => {
 
 			// Purchase tickets
 			await raffleProgram.methods
-				.buyTickets(amountToPurchase, Array.from(entrySeed))
+				.buyTickets(amountToPurchase, Array.from(entrySeed), null, null, null, null, false, null)
 				.accounts({
 					signer: buyer.publicKey,
 					raffle: new PublicKey(raffleAccountId),
@@ -259,7 +276,24 @@ describe("buy_tickets", async () => {
 
 			// Create raffle, so that defaults are set, PDAs are created, etc.
 			await raffleProgram.methods
-				.createRaffle(metadataUri, ticketPrice, endTime, minTickets, maxTickets)
+				.createRaffle(
+					metadataUri,
+					ticketPrice,
+					endTime,
+					minTickets,
+					maxTickets,
+					null,
+					false,
+					null,
+					false,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+				)
 				.rpc();
 			const raffleAccountId = PublicKey.findProgramAddressSync(
 				[
@@ -293,7 +327,7 @@ describe("buy_tickets", async () => {
 
 			// Init ticket balance before setting the raffle into an non-open state
 			await raffleProgram.methods
-				.initTicketBalance()
+				.initTicketBalance(null)
 				.accounts({
 					signer: buyer.publicKey,
 					raffle: raffleAccountId,
@@ -307,6 +341,57 @@ describe("buy_tickets", async () => {
 			const raffleData = await raffleProgram.coder.accounts.encode("raffle", {
 				...oldRaffleData,
 				currentTickets: new BN(input.ticketCountBeforePurchase),
+				participantCount: new BN(0),
+				eventSeq: new BN(0),
+				minRevenue: null,
+				minDrawSlot: new BN(0),
+				reservedTickets: new BN(0),
+				refundFeeBps: 0,
+				rentPayer: provider.publicKey,
+				features: 0,
+				allowedRegions: new BN(0),
+				ageRestricted: false,
+				drawAuthority: null,
+				priorityMinTier: 0,
+				priorityUntil: new BN(0),
+				minUniqueBuyers: null,
+				paymentMint: null,
+				randomnessMode: { slotHashes: {} },
+				randomnessAccount: null,
+				randomnessCommitSlot: new BN(0),
+				prizeEscrowed: false,
+				winnerCount: 1,
+				winningTickets: [],
+				additionalWinners: [],
+				winnerDataSubmitted: 0,
+				prizesClaimed: 0,
+				maxTicketsPerWallet: null,
+				gate: null,
+				deferredNumbering: false,
+				entryCount: new BN(0),
+				numberedEntries: new BN(0),
+				numberedTickets: new BN(0),
+				feeBpsOverride: null,
+				snapshotRoot: null,
+				operatorStatus: 0,
+				statusNoteHash: null,
+				fundingDeadline: null,
+				pricingTiers: [],
+				tierDiscounts: new BN(0),
+				crankRewardBps: 0,
+				referralFeeBps: 0,
+				extensionCount: 0,
+				extendedSecs: new BN(0),
+				refundDeadline: new BN(0),
+				prizeValueUsdCents: null,
+				ticketCountDisplayCap: null,
+				ticketReceipts: false,
+				arweaveTxId: null,
+				creator: null,
+				drawDelaySlots: 0,
+				drawTargetSlot: new BN(0),
+				refundMint: null,
+				refundRate: new BN(0), drawValueLamports: new BN(0), drawUsdRate: null, successor: null, randomnessProvider: null, randomnessCommitment: null, accessCodeHash: null, closeReason: 0, crankRewardPaid: false, additionalWinnerHashes: [], voidedRanges: [], pendingReservations: new BN(0), openSlotReleased: false, campaignSubsidies: new BN(0),
 			});
 			provider.client.setAccount(raffleAccountId, {
 				executable: false,
@@ -321,7 +406,7 @@ describe("buy_tickets", async () => {
 
 			// Purchase the last ticket
 			await raffleProgram.methods
-				.buyTickets(amountToPurchase, Array.from(entrySeed))
+				.buyTickets(amountToPurchase, Array.from(entrySeed), null, null, null, null, false, null)
 				.accounts({
 					raffle: raffleAccountId,
 					signer: buyer.publicKey,
@@ -371,7 +456,24 @@ describe("buy_tickets", async () => {
 
 		// Create raffle, so that defaults are set, PDAs are created, etc.
 		await raffleProgram.methods
-			.createRaffle(metadataUri, ticketPrice, endTime, minTickets, maxTickets)
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				maxTickets,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
 			.rpc();
 		const raffleAccountId = PublicKey.findProgramAddressSync(
 			[
@@ -405,7 +507,7 @@ describe("buy_tickets", async () => {
 
 		// Init ticket balance before setting the raffle into an non-open state
 		await raffleProgram.methods
-			.initTicketBalance()
+			.initTicketBalance(null)
 			.accounts({
 				signer: buyer.publicKey,
 				raffle: raffleAccountId,
@@ -419,6 +521,57 @@ describe("buy_tickets", async () => {
 		const raffleData = await raffleProgram.coder.accounts.encode("raffle", {
 			...oldRaffleData,
 			currentTickets: maxTickets,
+			participantCount: new BN(0),
+			eventSeq: new BN(0),
+			minRevenue: null,
+			minDrawSlot: new BN(0),
+			reservedTickets: new BN(0),
+			refundFeeBps: 0,
+			rentPayer: provider.publicKey,
+			features: 0,
+			allowedRegions: new BN(0),
+			ageRestricted: false,
+			drawAuthority: null,
+			priorityMinTier: 0,
+			priorityUntil: new BN(0),
+			minUniqueBuyers: null,
+			paymentMint: null,
+			randomnessMode: { slotHashes: {} },
+			randomnessAccount: null,
+			randomnessCommitSlot: new BN(0),
+			prizeEscrowed: false,
+			winnerCount: 1,
+			winningTickets: [],
+			additionalWinners: [],
+			winnerDataSubmitted: 0,
+			prizesClaimed: 0,
+			maxTicketsPerWallet: null,
+			gate: null,
+			deferredNumbering: false,
+			entryCount: new BN(0),
+			numberedEntries: new BN(0),
+			numberedTickets: new BN(0),
+			feeBpsOverride: null,
+			snapshotRoot: null,
+			operatorStatus: 0,
+			statusNoteHash: null,
+			fundingDeadline: null,
+			pricingTiers: [],
+			tierDiscounts: new BN(0),
+			crankRewardBps: 0,
+			referralFeeBps: 0,
+			extensionCount: 0,
+			extendedSecs: new BN(0),
+			refundDeadline: new BN(0),
+			prizeValueUsdCents: null,
+			ticketCountDisplayCap: null,
+			ticketReceipts: false,
+			arweaveTxId: null,
+			creator: null,
+			drawDelaySlots: 0,
+			drawTargetSlot: new BN(0),
+			refundMint: null,
+			refundRate: new BN(0), drawValueLamports: new BN(0), drawUsdRate: null, successor: null, randomnessProvider: null, randomnessCommitment: null, accessCodeHash: null, closeReason: 0, crankRewardPaid: false, additionalWinnerHashes: [], voidedRanges: [], pendingReservations: new BN(0), openSlotReleased: false, campaignSubsidies: new BN(0),
 		});
 		provider.client.setAccount(raffleAccountId, {
 			executable: false,
@@ -433,7 +586,7 @@ describe("buy_tickets", async () => {
 
 		expect(
 			raffleProgram.methods
-				.buyTickets(amountToPurchase, Array.from(entrySeed))
+				.buyTickets(amountToPurchase, Array.from(entrySeed), null, null, null, null, false, null)
 				.accounts({
 					raffle: raffleAccountId,
 					signer: buyer.publicKey,
@@ -474,7 +627,24 @@ describe("buy_tickets", async () => {
 
 		// Create raffle, so that defaults are set, PDAs are created, etc.
 		await raffleProgram.methods
-			.createRaffle(metadataUri, ticketPrice, endTime, minTickets, maxTickets)
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				maxTickets,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
 			.rpc();
 		const raffleAccountId = PublicKey.findProgramAddressSync(
 			[
@@ -508,7 +678,7 @@ describe("buy_tickets", async () => {
 
 		// Init ticket balance before setting the raffle into an non-open state
 		await raffleProgram.methods
-			.initTicketBalance()
+			.initTicketBalance(null)
 			.accounts({
 				signer: buyer.publicKey,
 				raffle: raffleAccountId,
@@ -522,6 +692,57 @@ describe("buy_tickets", async () => {
 		const raffleData = await raffleProgram.coder.accounts.encode("raffle", {
 			...oldRaffleData,
 			currentTickets: maxTickets.sub(new BN(1)),
+			participantCount: new BN(0),
+			eventSeq: new BN(0),
+			minRevenue: null,
+			minDrawSlot: new BN(0),
+			reservedTickets: new BN(0),
+			refundFeeBps: 0,
+			rentPayer: provider.publicKey,
+			features: 0,
+			allowedRegions: new BN(0),
+			ageRestricted: false,
+			drawAuthority: null,
+			priorityMinTier: 0,
+			priorityUntil: new BN(0),
+			minUniqueBuyers: null,
+			paymentMint: null,
+			randomnessMode: { slotHashes: {} },
+			randomnessAccount: null,
+			randomnessCommitSlot: new BN(0),
+			prizeEscrowed: false,
+			winnerCount: 1,
+			winningTickets: [],
+			additionalWinners: [],
+			winnerDataSubmitted: 0,
+			prizesClaimed: 0,
+			maxTicketsPerWallet: null,
+			gate: null,
+			deferredNumbering: false,
+			entryCount: new BN(0),
+			numberedEntries: new BN(0),
+			numberedTickets: new BN(0),
+			feeBpsOverride: null,
+			snapshotRoot: null,
+			operatorStatus: 0,
+			statusNoteHash: null,
+			fundingDeadline: null,
+			pricingTiers: [],
+			tierDiscounts: new BN(0),
+			crankRewardBps: 0,
+			referralFeeBps: 0,
+			extensionCount: 0,
+			extendedSecs: new BN(0),
+			refundDeadline: new BN(0),
+			prizeValueUsdCents: null,
+			ticketCountDisplayCap: null,
+			ticketReceipts: false,
+			arweaveTxId: null,
+			creator: null,
+			drawDelaySlots: 0,
+			drawTargetSlot: new BN(0),
+			refundMint: null,
+			refundRate: new BN(0), drawValueLamports: new BN(0), drawUsdRate: null, successor: null, randomnessProvider: null, randomnessCommitment: null, accessCodeHash: null, closeReason: 0, crankRewardPaid: false, additionalWinnerHashes: [], voidedRanges: [], pendingReservations: new BN(0), openSlotReleased: false, campaignSubsidies: new BN(0),
 		});
 		provider.client.setAccount(raffleAccountId, {
 			executable: false,
@@ -536,7 +757,7 @@ describe("buy_tickets", async () => {
 
 		expect(
 			raffleProgram.methods
-				.buyTickets(amountToPurchase, Array.from(entrySeed))
+				.buyTickets(amountToPurchase, Array.from(entrySeed), null, null, null, null, false, null)
 				.accounts({
 					raffle: raffleAccountId,
 					signer: buyer.publicKey,
@@ -577,7 +798,24 @@ describe("buy_tickets", async () => {
 
 		// Create raffle
 		await raffleProgram.methods
-			.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
 			.rpc();
 		const raffleAccountId = PublicKey.findProgramAddressSync(
 			[
@@ -613,7 +851,7 @@ describe("buy_tickets", async () => {
 		);
 
 		await raffleProgram.methods
-			.initTicketBalance()
+			.initTicketBalance(null)
 			.accounts({
 				signer: buyer.publicKey,
 				raffle: new PublicKey(raffleAccountId),
@@ -628,7 +866,7 @@ describe("buy_tickets", async () => {
 		// Purchase tickets, should fail since we are purchasing 0 tickets
 		expect(
 			raffleProgram.methods
-				.buyTickets(amountToPurchase, Array.from(entrySeed))
+				.buyTickets(amountToPurchase, Array.from(entrySeed), null, null, null, null, false, null)
 				.accounts({
 					signer: buyer.publicKey,
 					raffle: new PublicKey(raffleAccountId),
@@ -638,6 +876,716 @@ describe("buy_tickets", async () => {
 		).rejects.toThrow(/InvalidTicketCount/);
 	});
 
+	it("should fail when the purchase price exceeds the expected total", async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		// Fetch config before creating raffle, so we can get the raffle PDA later
+		const configId = PublicKey.findProgramAddressSync(
+			[Buffer.from("config")],
+			raffleProgram.programId,
+		)[0];
+		const config = await raffleProgram.account.config.fetch(configId);
+		const creationTime = client.getClock().unixTimestamp;
+		const initialRaffleCounter = config.raffleCounter;
+
+		const metadataUri = "https://www.example.org";
+		const ticketPrice = new BN(0.1 * LAMPORTS_PER_SOL);
+		const minTickets = new BN(1);
+		const endTime = new BN((creationTime + BigInt(3601)).toString());
+
+		// Create raffle
+		await raffleProgram.methods
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[
+				Buffer.from("raffle"),
+				new Uint8Array(new BN(initialRaffleCounter).toArray("le", 8)),
+			],
+			raffleProgram.programId,
+		)[0];
+
+		const buyer = new Keypair();
+		const amountToPurchase = new BN(3);
+		const totalTicketsPrice = amountToPurchase.mul(ticketPrice);
+
+		// Mint enough for two purchases, including rent and fees
+		provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({
+				signer: buyer.publicKey,
+				raffle: new PublicKey(raffleAccountId),
+			})
+			.signers([buyer])
+			.rpc();
+
+		const randomBytes = new Uint8Array(8);
+		crypto.getRandomValues(randomBytes);
+		const entrySeed = randomBytes;
+
+		// Purchase tickets, should fail since the buyer expected to pay one lamport less
+		expect(
+			raffleProgram.methods
+				.buyTickets(
+					amountToPurchase,
+					Array.from(entrySeed),
+					totalTicketsPrice.sub(new BN(1)),
+					null,
+					null,
+					null,
+					false,
+					null,
+				)
+				.accounts({
+					signer: buyer.publicKey,
+					raffle: new PublicKey(raffleAccountId),
+				})
+				.signers([buyer])
+				.rpc(),
+		).rejects.toThrow(/PriceExceedsExpectedTotal/);
+
+		// Purchasing with the exact expected total should succeed
+		await raffleProgram.methods
+			.buyTickets(
+				amountToPurchase,
+				Array.from(entrySeed),
+				totalTicketsPrice,
+				null,
+				null,
+				null,
+				false,
+				null,
+			)
+			.accounts({
+				signer: buyer.publicKey,
+				raffle: new PublicKey(raffleAccountId),
+			})
+			.signers([buyer])
+			.rpc();
+
+		const raffleAccount =
+			await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffleAccount.currentTickets.toNumber()).toBe(3);
+	});
+
+	it("should store the memo on the entry and reject memos longer than 64 bytes", async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		// Fetch config before creating raffle, so we can get the raffle PDA later
+		const configId = PublicKey.findProgramAddressSync(
+			[Buffer.from("config")],
+			raffleProgram.programId,
+		)[0];
+		const config = await raffleProgram.account.config.fetch(configId);
+		const creationTime = client.getClock().unixTimestamp;
+		const initialRaffleCounter = config.raffleCounter;
+
+		const metadataUri = "https://www.example.org";
+		const ticketPrice = new BN(0.1 * LAMPORTS_PER_SOL);
+		const minTickets = new BN(1);
+		const endTime = new BN((creationTime + BigInt(3601)).toString());
+
+		// Create raffle
+		await raffleProgram.methods
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[
+				Buffer.from("raffle"),
+				new Uint8Array(new BN(initialRaffleCounter).toArray("le", 8)),
+			],
+			raffleProgram.programId,
+		)[0];
+
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({
+				signer: buyer.publicKey,
+				raffle: new PublicKey(raffleAccountId),
+			})
+			.signers([buyer])
+			.rpc();
+
+		const randomBytes = new Uint8Array(8);
+		crypto.getRandomValues(randomBytes);
+		const entrySeed = randomBytes;
+
+		// Purchase tickets, should fail since the memo is 65 bytes long
+		expect(
+			raffleProgram.methods
+				.buyTickets(
+					new BN(1),
+					Array.from(entrySeed),
+					null,
+					"a".repeat(65),
+					null,
+					null,
+					false,
+					null,
+				)
+				.accounts({
+					signer: buyer.publicKey,
+					raffle: new PublicKey(raffleAccountId),
+				})
+				.signers([buyer])
+				.rpc(),
+		).rejects.toThrow(/MemoTooLong/);
+
+		// Purchasing with a memo of the maximum length should succeed
+		const memo = "order:".padEnd(64, "0");
+		await raffleProgram.methods
+			.buyTickets(new BN(1), Array.from(entrySeed), null, memo, null, null, false, null)
+			.accounts({
+				signer: buyer.publicKey,
+				raffle: new PublicKey(raffleAccountId),
+			})
+			.signers([buyer])
+			.rpc();
+
+		const entryId = PublicKey.findProgramAddressSync(
+			[Buffer.from("entry"), raffleAccountId.toBytes(), entrySeed],
+			raffleProgram.programId,
+		)[0];
+		const entryAccount = await raffleProgram.account.entry.fetch(entryId);
+		expect(entryAccount.memo).toBe(memo);
+	});
+
+	it("should link all entries of a buyer through the ticket balance", async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		const metadataUri = "https://www.example.org";
+		const ticketPrice = new BN(0.1 * LAMPORTS_PER_SOL);
+		const minTickets = new BN(1);
+		const endTime = new BN((creationTime + BigInt(3601)).toString());
+
+		// Create raffle
+		await raffleProgram.methods
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({
+				signer: buyer.publicKey,
+				raffle: new PublicKey(raffleAccountId),
+			})
+			.signers([buyer])
+			.rpc();
+
+		const ticketBalanceId = PublicKey.findProgramAddressSync(
+			[
+				Buffer.from("ticket_balance"),
+				raffleAccountId.toBytes(),
+				buyer.publicKey.toBytes(),
+			],
+			raffleProgram.programId,
+		)[0];
+		let ticketBalance =
+			await raffleProgram.account.ticketBalance.fetch(ticketBalanceId);
+		expect(ticketBalance.headEntry).toBeNull();
+
+		// Purchase tickets three times, creating three entries
+		const entryIds: PublicKey[] = [];
+		for (let i = 0; i < 3; i++) {
+			const randomBytes = new Uint8Array(8);
+			crypto.getRandomValues(randomBytes);
+			entryIds.push(
+				PublicKey.findProgramAddressSync(
+					[Buffer.from("entry"), raffleAccountId.toBytes(), randomBytes],
+					raffleProgram.programId,
+				)[0],
+			);
+
+			await raffleProgram.methods
+				.buyTickets(new BN(1), Array.from(randomBytes), null, null, null, null, false, null)
+				.accounts({
+					signer: buyer.publicKey,
+					raffle: new PublicKey(raffleAccountId),
+				})
+				.signers([buyer])
+				.rpc();
+		}
+
+		// Walk the list from the head, which yields the entries newest first
+		ticketBalance =
+			await raffleProgram.account.ticketBalance.fetch(ticketBalanceId);
+		const walked: PublicKey[] = [];
+		let next = ticketBalance.headEntry;
+		while (next) {
+			walked.push(next);
+			const entry = await raffleProgram.account.entry.fetch(next);
+			next = entry.nextEntry;
+		}
+		expect(walked.map((id) => id.toBase58())).toEqual(
+			entryIds.reverse().map((id) => id.toBase58()),
+		);
+
+		// The ticket balance keeps the buyer's running totals for receipts
+		expect(ticketBalance.ticketCount.toNumber()).toBe(3);
+		expect(ticketBalance.totalSpent.toString()).toEqual(
+			ticketPrice.mul(new BN(3)).toString(),
+		);
+	});
+
+	it("should fail when purchasing more tickets than allowed per purchase", async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		const metadataUri = "https://www.example.org";
+		const ticketPrice = new BN(0.1 * LAMPORTS_PER_SOL);
+		const minTickets = new BN(1);
+		const endTime = new BN((creationTime + BigInt(3601)).toString());
+
+		// A purchase limit of zero is rejected
+		expect(
+			raffleProgram.methods
+				.createRaffle(
+					metadataUri,
+					ticketPrice,
+					endTime,
+					minTickets,
+					null,
+					new BN(0),
+					false,
+					null,
+					false,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+				)
+				.rpc(),
+		).rejects.toThrow(/InvalidMaxPerPurchase/);
+
+		// Create raffle with a limit of 5 tickets per purchase
+		await raffleProgram.methods
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				new BN(5),
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({
+				signer: buyer.publicKey,
+				raffle: new PublicKey(raffleAccountId),
+			})
+			.signers([buyer])
+			.rpc();
+
+		const randomBytes = new Uint8Array(8);
+		crypto.getRandomValues(randomBytes);
+		const entrySeed = randomBytes;
+
+		// Purchase tickets, should fail since the purchase exceeds the limit
+		expect(
+			raffleProgram.methods
+				.buyTickets(new BN(6), Array.from(entrySeed), null, null, null, null, false, null)
+				.accounts({
+					signer: buyer.publicKey,
+					raffle: new PublicKey(raffleAccountId),
+				})
+				.signers([buyer])
+				.rpc(),
+		).rejects.toThrow(/PurchaseExceedsMaxPerPurchase/);
+
+		// Purchasing exactly the limit should succeed
+		await raffleProgram.methods
+			.buyTickets(new BN(5), Array.from(entrySeed), null, null, null, null, false, null)
+			.accounts({
+				signer: buyer.publicKey,
+				raffle: new PublicKey(raffleAccountId),
+			})
+			.signers([buyer])
+			.rpc();
+
+		const raffleAccount =
+			await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffleAccount.maxPerPurchase?.toNumber()).toBe(5);
+		expect(raffleAccount.currentTickets.toNumber()).toBe(5);
+	});
+
+	it("should fail when a wallet buys more tickets than allowed per wallet", async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		const metadataUri = "https://www.example.org";
+		const ticketPrice = new BN(0.1 * LAMPORTS_PER_SOL);
+		const minTickets = new BN(1);
+		const endTime = new BN((creationTime + BigInt(3601)).toString());
+
+		// A wallet limit of zero is rejected
+		expect(
+			raffleProgram.methods
+				.createRaffle(
+					metadataUri,
+					ticketPrice,
+					endTime,
+					minTickets,
+					null,
+					null,
+					false,
+					null,
+					false,
+					null,
+					null,
+					null,
+					new BN(0),
+					null,
+					null,
+					null,
+				)
+				.rpc(),
+		).rejects.toThrow(/InvalidMaxTicketsPerWallet/);
+
+		// Create raffle with a limit of 5 tickets per wallet
+		await raffleProgram.methods
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				new BN(5),
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({
+				signer: buyer.publicKey,
+				raffle: new PublicKey(raffleAccountId),
+			})
+			.signers([buyer])
+			.rpc();
+
+		// The first purchase stays within the limit
+		await raffleProgram.methods
+			.buyTickets(new BN(3), Array.from(new Uint8Array(8)), null, null, null, null, false, null)
+			.accounts({
+				signer: buyer.publicKey,
+				raffle: new PublicKey(raffleAccountId),
+			})
+			.signers([buyer])
+			.rpc();
+
+		// A second purchase fails once the wallet's total would exceed the limit
+		const entrySeed = new Uint8Array(8).fill(1);
+		expect(
+			raffleProgram.methods
+				.buyTickets(new BN(3), Array.from(entrySeed), null, null, null, null, false, null)
+				.accounts({
+					signer: buyer.publicKey,
+					raffle: new PublicKey(raffleAccountId),
+				})
+				.signers([buyer])
+				.rpc(),
+		).rejects.toThrow(/WalletLimitExceeded/);
+
+		// Purchasing up to the limit should succeed
+		await raffleProgram.methods
+			.buyTickets(new BN(2), Array.from(entrySeed), null, null, null, null, false, null)
+			.accounts({
+				signer: buyer.publicKey,
+				raffle: new PublicKey(raffleAccountId),
+			})
+			.signers([buyer])
+			.rpc();
+
+		const raffleAccount =
+			await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffleAccount.maxTicketsPerWallet?.toNumber()).toBe(5);
+		expect(raffleAccount.currentTickets.toNumber()).toBe(5);
+	});
+
+	it("should fail when retrying a purchase with the same idempotency key", async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		const metadataUri = "https://www.example.org";
+		const ticketPrice = new BN(0.1 * LAMPORTS_PER_SOL);
+		const minTickets = new BN(1);
+		const endTime = new BN((creationTime + BigInt(3601)).toString());
+
+		// Create raffle
+		await raffleProgram.methods
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({
+				signer: buyer.publicKey,
+				raffle: new PublicKey(raffleAccountId),
+			})
+			.signers([buyer])
+			.rpc();
+
+		const idempotencyKey = new Uint8Array(16);
+		crypto.getRandomValues(idempotencyKey);
+		const purchaseDedupeId = PublicKey.findProgramAddressSync(
+			[
+				Buffer.from("purchase_dedupe"),
+				raffleAccountId.toBytes(),
+				buyer.publicKey.toBytes(),
+				idempotencyKey,
+			],
+			raffleProgram.programId,
+		)[0];
+
+		const purchase = () => {
+			// Every attempt uses a fresh entry seed, like a wallet retrying the purchase
+			const randomBytes = new Uint8Array(8);
+			crypto.getRandomValues(randomBytes);
+			return raffleProgram.methods
+				.buyTickets(
+					new BN(1),
+					Array.from(randomBytes),
+					null,
+					null,
+					Array.from(idempotencyKey),
+					null,
+					false,
+					null,
+				)
+				.accounts({
+					signer: buyer.publicKey,
+					raffle: new PublicKey(raffleAccountId),
+					purchaseDedupe: purchaseDedupeId,
+				})
+				.signers([buyer])
+				.rpc();
+		};
+
+		await purchase();
+
+		const purchaseDedupe =
+			await raffleProgram.account.purchaseDedupe.fetch(purchaseDedupeId);
+		expect(purchaseDedupe.raffle.equals(raffleAccountId)).toBeTrue();
+		expect(purchaseDedupe.buyer.equals(buyer.publicKey)).toBeTrue();
+
+		// Retrying with the same key must not purchase again
+		expect(purchase()).rejects.toThrow(/DuplicatePurchase/);
+
+		const raffleAccount =
+			await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffleAccount.currentTickets.toNumber()).toBe(1);
+
+		// Dedupe records can only be closed once the raffle is concluded
+		expect(
+			raffleProgram.methods
+				.closePurchaseDedupes()
+				.accounts({ raffle: raffleAccountId })
+				.remainingAccounts([
+					{ pubkey: purchaseDedupeId, isSigner: false, isWritable: true },
+					{ pubkey: buyer.publicKey, isSigner: false, isWritable: true },
+				])
+				.rpc(),
+		).rejects.toThrow(/RaffleNotFinalized/);
+	});
+
 	it("should fail when attempting to purchase on a raffle that is not open", async () => {
 		const client = fromWorkspace(".");
 		const provider = new LiteSVMProvider(client);
@@ -674,7 +1622,24 @@ describe("buy_tickets", async () => {
 
 			// Create raffle, so that defaults are set, PDAs are created, etc.
 			await raffleProgram.methods
-				.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+				.createRaffle(
+					metadataUri,
+					ticketPrice,
+					endTime,
+					minTickets,
+					null,
+					null,
+					false,
+					null,
+					false,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+				)
 				.rpc();
 			const raffleAccountId = PublicKey.findProgramAddressSync(
 				[
@@ -713,7 +1678,7 @@ describe("buy_tickets", async () => {
 
 			// Init ticket balance before setting the raffle into an non-open state
 			await raffleProgram.methods
-				.initTicketBalance()
+				.initTicketBalance(null)
 				.accounts({
 					signer: buyer.publicKey,
 					raffle: raffleAccountId,
@@ -736,6 +1701,57 @@ describe("buy_tickets", async () => {
 				winnerAddress: null,
 				winningTicket: null,
 				maxTickets: null,
+				participantCount: new BN(0),
+				eventSeq: new BN(0),
+				minRevenue: null,
+				minDrawSlot: new BN(0),
+				reservedTickets: new BN(0),
+				refundFeeBps: 0,
+				rentPayer: provider.publicKey,
+				features: 0,
+				allowedRegions: new BN(0),
+				ageRestricted: false,
+				drawAuthority: null,
+				priorityMinTier: 0,
+				priorityUntil: new BN(0),
+				minUniqueBuyers: null,
+				paymentMint: null,
+				randomnessMode: { slotHashes: {} },
+				randomnessAccount: null,
+				randomnessCommitSlot: new BN(0),
+				prizeEscrowed: false,
+				winnerCount: 1,
+				winningTickets: [],
+				additionalWinners: [],
+				winnerDataSubmitted: 0,
+				prizesClaimed: 0,
+				maxTicketsPerWallet: null,
+				gate: null,
+				deferredNumbering: false,
+				entryCount: new BN(0),
+				numberedEntries: new BN(0),
+				numberedTickets: new BN(0),
+				feeBpsOverride: null,
+				snapshotRoot: null,
+				operatorStatus: 0,
+				statusNoteHash: null,
+				fundingDeadline: null,
+				pricingTiers: [],
+				tierDiscounts: new BN(0),
+				crankRewardBps: 0,
+				referralFeeBps: 0,
+				extensionCount: 0,
+				extendedSecs: new BN(0),
+				refundDeadline: new BN(0),
+				prizeValueUsdCents: null,
+				ticketCountDisplayCap: null,
+				ticketReceipts: false,
+				arweaveTxId: null,
+				creator: null,
+				drawDelaySlots: 0,
+				drawTargetSlot: new BN(0),
+				refundMint: null,
+				refundRate: new BN(0), drawValueLamports: new BN(0), drawUsdRate: null, successor: null, randomnessProvider: null, randomnessCommitment: null, accessCodeHash: null, closeReason: 0, crankRewardPaid: false, additionalWinnerHashes: [], voidedRanges: [], pendingReservations: new BN(0), openSlotReleased: false, campaignSubsidies: new BN(0),
 			});
 			provider.client.setAccount(raffleAccountId, {
 				executable: false,
@@ -750,7 +1766,7 @@ describe("buy_tickets", async () => {
 
 			expect(
 				raffleProgram.methods
-					.buyTickets(amountToPurchase, Array.from(entrySeed))
+					.buyTickets(amountToPurchase, Array.from(entrySeed), null, null, null, null, false, null)
 					.accounts({
 						raffle: raffleAccountId,
 						signer: buyer.publicKey,
@@ -791,7 +1807,24 @@ describe("buy_tickets", async () => {
 
 		// Create raffle, so that defaults are set, PDAs are created, etc.
 		await raffleProgram.methods
-			.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
 			.rpc();
 		const raffleAccountId = PublicKey.findProgramAddressSync(
 			[
@@ -830,7 +1863,7 @@ describe("buy_tickets", async () => {
 
 		// Init ticket balance before setting the raffle into an non-open state
 		await raffleProgram.methods
-			.initTicketBalance()
+			.initTicketBalance(null)
 			.accounts({
 				signer: buyer.publicKey,
 				raffle: raffleAccountId,
@@ -853,6 +1886,57 @@ describe("buy_tickets", async () => {
 			winnerAddress: null,
 			winningTicket: null,
 			maxTickets: null,
+			participantCount: new BN(0),
+			eventSeq: new BN(0),
+			minRevenue: null,
+			minDrawSlot: new BN(0),
+			reservedTickets: new BN(0),
+			refundFeeBps: 0,
+			rentPayer: provider.publicKey,
+			features: 0,
+			allowedRegions: new BN(0),
+			ageRestricted: false,
+			drawAuthority: null,
+			priorityMinTier: 0,
+			priorityUntil: new BN(0),
+			minUniqueBuyers: null,
+			paymentMint: null,
+			randomnessMode: { slotHashes: {} },
+			randomnessAccount: null,
+			randomnessCommitSlot: new BN(0),
+			prizeEscrowed: false,
+			winnerCount: 1,
+			winningTickets: [],
+			additionalWinners: [],
+			winnerDataSubmitted: 0,
+			prizesClaimed: 0,
+			maxTicketsPerWallet: null,
+			gate: null,
+			deferredNumbering: false,
+			entryCount: new BN(0),
+			numberedEntries: new BN(0),
+			numberedTickets: new BN(0),
+			feeBpsOverride: null,
+			snapshotRoot: null,
+			operatorStatus: 0,
+			statusNoteHash: null,
+			fundingDeadline: null,
+			pricingTiers: [],
+			tierDiscounts: new BN(0),
+			crankRewardBps: 0,
+			referralFeeBps: 0,
+			extensionCount: 0,
+			extendedSecs: new BN(0),
+			refundDeadline: new BN(0),
+			prizeValueUsdCents: null,
+			ticketCountDisplayCap: null,
+			ticketReceipts: false,
+			arweaveTxId: null,
+			creator: null,
+			drawDelaySlots: 0,
+			drawTargetSlot: new BN(0),
+			refundMint: null,
+			refundRate: new BN(0), drawValueLamports: new BN(0), drawUsdRate: null, successor: null, randomnessProvider: null, randomnessCommitment: null, accessCodeHash: null, closeReason: 0, crankRewardPaid: false, additionalWinnerHashes: [], voidedRanges: [], pendingReservations: new BN(0), openSlotReleased: false, campaignSubsidies: new BN(0),
 		});
 		provider.client.setAccount(raffleAccountId, {
 			executable: false,
@@ -867,7 +1951,7 @@ describe("buy_tickets", async () => {
 
 		expect(
 			raffleProgram.methods
-				.buyTickets(amountToPurchase, Array.from(entrySeed))
+				.buyTickets(amountToPurchase, Array.from(entrySeed), null, null, null, null, false, null)
 				.accounts({
 					raffle: raffleAccountId,
 					signer: buyer.publicKey,
@@ -908,7 +1992,24 @@ describe("buy_tickets", async () => {
 
 		// Create raffle
 		await raffleProgram.methods
-			.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
 			.rpc();
 		const raffleAccountId = PublicKey.findProgramAddressSync(
 			[
@@ -942,7 +2043,7 @@ describe("buy_tickets", async () => {
 
 		// Create ticket balance
 		await raffleProgram.methods
-			.initTicketBalance()
+			.initTicketBalance(null)
 			.accounts({
 				signer: buyer.publicKey,
 				raffle: new PublicKey(raffleAccountId),
@@ -957,7 +2058,7 @@ describe("buy_tickets", async () => {
 
 		expect(
 			raffleProgram.methods
-				.buyTickets(amountToPurchase, Array.from(entrySeed))
+				.buyTickets(amountToPurchase, Array.from(entrySeed), null, null, null, null, false, null)
 				.accounts({
 					signer: buyer.publicKey,
 					raffle: new PublicKey(raffleAccountId),
@@ -990,7 +2091,24 @@ describe("buy_tickets", async () => {
 		const endTime = new BN((creationTime + BigInt(3601)).toString());
 
 		await raffleProgram.methods
-			.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
 			.rpc();
 		const raffleAccountId = PublicKey.findProgramAddressSync(
 			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
@@ -1039,7 +2157,24 @@ describe("buy_tickets", async () => {
 
 			// Create raffle
 			await raffleProgram.methods
-				.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+				.createRaffle(
+					metadataUri,
+					ticketPrice,
+					endTime,
+					minTickets,
+					null,
+					null,
+					false,
+					null,
+					false,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+				)
 				.rpc();
 			const raffleAccountId = PublicKey.findProgramAddressSync(
 				[
@@ -1075,7 +2210,7 @@ describe("buy_tickets", async () => {
 
 			// Create ticket balance
 			await raffleProgram.methods
-				.initTicketBalance()
+				.initTicketBalance(null)
 				.accounts({
 					signer: buyer.publicKey,
 					raffle: new PublicKey(raffleAccountId),
@@ -1090,7 +2225,7 @@ describe("buy_tickets", async () => {
 
 			expect(
 				raffleProgram.methods
-					.buyTickets(amountToPurchase, Array.from(entrySeed))
+					.buyTickets(amountToPurchase, Array.from(entrySeed), null, null, null, null, false, null)
 					.accountsPartial({
 						signer: buyer.publicKey,
 						raffle: new PublicKey(raffleAccountId),
@@ -1125,7 +2260,24 @@ describe("buy_tickets", async () => {
 		const endTime = new BN((creationTime + BigInt(3601)).toString());
 
 		await raffleProgram.methods
-			.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
 			.rpc();
 		const raffleAccountId = PublicKey.findProgramAddressSync(
 			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
@@ -1162,7 +2314,7 @@ describe("buy_tickets", async () => {
 
 		expect(
 			raffleProgram.methods
-				.buyTickets(amountToPurchase, Array.from(entrySeed))
+				.buyTickets(amountToPurchase, Array.from(entrySeed), null, null, null, null, false, null)
 				.accounts({
 					signer: buyer.publicKey,
 					raffle: new PublicKey(raffleAccountId),
@@ -1203,7 +2355,24 @@ describe("buy_tickets", async () => {
 
 		// Create raffle
 		await raffleProgram.methods
-			.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
 			.rpc();
 		const raffleAccountId = PublicKey.findProgramAddressSync(
 			[
@@ -1240,7 +2409,7 @@ describe("buy_tickets", async () => {
 
 		// Create ticket balance
 		await raffleProgram.methods
-			.initTicketBalance()
+			.initTicketBalance(null)
 			.accounts({
 				signer: buyer.publicKey,
 				raffle: new PublicKey(raffleAccountId),
@@ -1254,7 +2423,7 @@ describe("buy_tickets", async () => {
 
 		// Purchase tickets. This should succeed as it's the first time
 		await raffleProgram.methods
-			.buyTickets(amountToPurchase, Array.from(entrySeed))
+			.buyTickets(amountToPurchase, Array.from(entrySeed), null, null, null, null, false, null)
 			.accounts({
 				signer: buyer.publicKey,
 				raffle: new PublicKey(raffleAccountId),
@@ -1266,7 +2435,7 @@ describe("buy_tickets", async () => {
 		// I think this is because if we don't change this, we send two transactions with the same signature.
 		expect(
 			raffleProgram.methods
-				.buyTickets(new BN(1), Array.from(entrySeed))
+				.buyTickets(new BN(1), Array.from(entrySeed), null, null, null, null, false, null)
 				.accounts({
 					signer: buyer.publicKey,
 					raffle: new PublicKey(raffleAccountId),
@@ -1307,7 +2476,24 @@ describe("buy_tickets", async () => {
 
 		// Create raffle
 		await raffleProgram.methods
-			.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
 			.rpc();
 		const raffleAccountId = PublicKey.findProgramAddressSync(
 			[
@@ -1347,7 +2533,7 @@ describe("buy_tickets", async () => {
 		provider.client.airdrop(diffBuyer.publicKey, BigInt(1 * LAMPORTS_PER_SOL));
 
 		await raffleProgram.methods
-			.initTicketBalance()
+			.initTicketBalance(null)
 			.accounts({
 				signer: diffBuyer.publicKey,
 				raffle: new PublicKey(raffleAccountId),
@@ -1372,7 +2558,7 @@ describe("buy_tickets", async () => {
 		// Purchase tickets, should fail because we are using someone else's ticket balance here
 		expect(
 			raffleProgram.methods
-				.buyTickets(amountToPurchase, Array.from(entrySeed))
+				.buyTickets(amountToPurchase, Array.from(entrySeed), null, null, null, null, false, null)
 				.accountsPartial({
 					ticketBalance: ticketBalanceId,
 					signer: buyer.publicKey,
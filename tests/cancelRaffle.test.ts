@@ -0,0 +1,170 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("cancel_raffle", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		// Refunds of expired raffles would retain a 1% fee
+		await raffleProgram.methods.setRefundFee(100).rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(1),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+		const treasuryId = PublicKey.findProgramAddressSync(
+			[Buffer.from("treasury"), raffleAccountId.toBytes()],
+			raffleProgram.programId,
+		)[0];
+
+		// Buy enough tickets to meet the threshold
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+		await raffleProgram.methods
+			.buyTickets(new BN(5), Array.from(new Uint8Array(8)), null, null, null, null, false, null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+
+		return { provider, raffleProgram, raffleAccountId, treasuryId, buyer };
+	};
+
+	it("should cancel a raffle and refund ticket holders in full", async () => {
+		const { provider, raffleProgram, raffleAccountId, treasuryId, buyer } =
+			await setup();
+
+		await raffleProgram.methods
+			.cancelRaffle()
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.raffleState.cancelled).toBeDefined();
+		expect(raffle.closeReason).toBe(3); // Cancelled
+
+		// The treasury can no longer be withdrawn
+		expect(
+			raffleProgram.methods
+				.withdrawFromTreasury()
+				.accounts({
+					raffle: raffleAccountId,
+					payoutAuthority: provider.publicKey,
+				})
+				.rpc(),
+		).rejects.toThrow(/RaffleIsCancelled/);
+
+		// Stray lamports can't be swept before the ticket holders are refunded
+		provider.client.airdrop(treasuryId, BigInt(0.01 * LAMPORTS_PER_SOL));
+		expect(
+			raffleProgram.methods
+				.sweepTreasuryDust()
+				.accountsPartial({
+					raffle: raffleAccountId,
+					payoutAuthority: provider.publicKey,
+				})
+				.rpc(),
+		).rejects.toThrow(/RefundsPending/);
+
+		// Ticket holders get the full ticket cost back, without the refund fee
+		const treasuryBefore = provider.client.getBalance(treasuryId)!;
+		await raffleProgram.methods
+			.reclaimExpiredTickets()
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+		expect(treasuryBefore - provider.client.getBalance(treasuryId)!).toEqual(
+			BigInt(0.5 * LAMPORTS_PER_SOL),
+		);
+
+		await raffleProgram.methods
+			.sweepTreasuryDust()
+			.accountsPartial({
+				raffle: raffleAccountId,
+				payoutAuthority: provider.publicKey,
+			})
+			.rpc();
+	});
+
+	it("should fail once the raffle was drawn", async () => {
+		const { provider, raffleProgram, raffleAccountId } = await setup();
+
+		// Manually set the raffle state to drawing
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		const raffleData = await raffleProgram.coder.accounts.encode("raffle", {
+			...raffle,
+			raffleState: { drawing: {} },
+		});
+		provider.client.setAccount(raffleAccountId, {
+			executable: false,
+			owner: raffleProgram.programId,
+			lamports: 1 * LAMPORTS_PER_SOL,
+			data: raffleData,
+		});
+
+		expect(
+			raffleProgram.methods
+				.cancelRaffle()
+				.accounts({ raffle: raffleAccountId })
+				.rpc(),
+		).rejects.toThrow(/RaffleNotOpen/);
+	});
+
+	it("should fail from accounts other than the management authority", async () => {
+		const { provider, raffleProgram, raffleAccountId } = await setup();
+
+		const other = new Keypair();
+		provider.client.airdrop(other.publicKey, BigInt(1 * LAMPORTS_PER_SOL));
+		expect(
+			raffleProgram.methods
+				.cancelRaffle()
+				.accounts({
+					raffle: raffleAccountId,
+					managementAuthority: other.publicKey,
+				})
+				.signers([other])
+				.rpc(),
+		).rejects.toThrow(/NotProgramManagementAuthority/);
+	});
+});
@@ -0,0 +1,129 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("check_invariants", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(10),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+		const treasuryId = PublicKey.findProgramAddressSync(
+			[Buffer.from("treasury"), raffleAccountId.toBytes()],
+			raffleProgram.programId,
+		)[0];
+
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+		await raffleProgram.methods
+			.buyTickets(new BN(5), Array.from(new Uint8Array(8)), null, null, null, null, false, null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+
+		// Simulates accounting drift by removing lamports from the treasury
+		const drainTreasury = (lamports: number) => {
+			const treasury = client.getAccount(treasuryId)!;
+			client.setAccount(treasuryId, {
+				...treasury,
+				lamports: treasury.lamports - lamports,
+			});
+		};
+
+		const checkInvariants = async () =>
+			(
+				await raffleProgram.methods
+					.checkInvariants()
+					.accounts({ raffle: raffleAccountId })
+					.simulate()
+			).events;
+
+		return { raffleProgram, raffleAccountId, drainTreasury, checkInvariants };
+	};
+
+	it("should not alert while the treasury covers its liabilities", async () => {
+		const { checkInvariants } = await setup();
+
+		expect(await checkInvariants()).toHaveLength(0);
+	});
+
+	it("should alert when the treasury falls short of its liabilities", async () => {
+		const { raffleAccountId, drainTreasury, checkInvariants } = await setup();
+
+		drainTreasury(0.1 * LAMPORTS_PER_SOL);
+
+		const events = await checkInvariants();
+		expect(events).toHaveLength(1);
+		expect(events[0].name).toBe("TreasuryShortfallDetected");
+		expect(events[0].data.raffle.equals(raffleAccountId)).toBeTrue();
+		expect(events[0].data.liabilities.toNumber()).toBe(0.5 * LAMPORTS_PER_SOL);
+		expect(events[0].data.shortfall.toNumber()).toBe(0.1 * LAMPORTS_PER_SOL);
+	});
+
+	it("should tolerate shortfalls below the configured thresholds", async () => {
+		const { raffleProgram, drainTreasury, checkInvariants } = await setup();
+
+		await raffleProgram.methods
+			.setAlarmThresholds(9_000, new BN(0.01 * LAMPORTS_PER_SOL))
+			.rpc();
+
+		// 90% coverage of 0.5 SOL tolerates a 0.05 SOL shortfall
+		drainTreasury(0.05 * LAMPORTS_PER_SOL);
+		expect(await checkInvariants()).toHaveLength(0);
+
+		// 0.1 SOL short, the treasury lacks 0.05 SOL of the required 0.45 SOL
+		drainTreasury(0.05 * LAMPORTS_PER_SOL);
+		expect(await checkInvariants()).toHaveLength(1);
+	});
+
+	it("should fail to set an alarm coverage above 100%", async () => {
+		const { raffleProgram } = await setup();
+
+		expect(
+			raffleProgram.methods.setAlarmThresholds(10_001, new BN(0)).rpc(),
+		).rejects.toThrow(/InvalidAlarmThreshold/);
+	});
+});
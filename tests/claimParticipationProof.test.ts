@@ -0,0 +1,119 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("claim_participation_proof", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+		const buyer = new Keypair();
+
+		provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(1),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+		await raffleProgram.methods
+			.buyTickets(new BN(3), Array.from(new Uint8Array(8)), null, null, null, null, false, null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+
+		const setDrawn = async () => {
+			const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+			const raffleData = await raffleProgram.coder.accounts.encode("raffle", {
+				...raffle,
+				raffleState: { drawn: {} },
+				winnerAddress: buyer.publicKey,
+				winningTicket: new BN(1),
+			});
+			provider.client.setAccount(raffleAccountId, {
+				executable: false,
+				owner: raffleProgram.programId,
+				lamports: 1 * LAMPORTS_PER_SOL,
+				data: raffleData,
+			});
+		};
+
+		const claimProof = () =>
+			raffleProgram.methods
+				.claimParticipationProof()
+				.accounts({ owner: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+
+		return { raffleProgram, raffleAccountId, buyer, setDrawn, claimProof };
+	};
+
+	it("should record the tickets the participant held once", async () => {
+		const { raffleProgram, raffleAccountId, buyer, setDrawn, claimProof } =
+			await setup();
+
+		await setDrawn();
+		await claimProof();
+
+		const proofId = PublicKey.findProgramAddressSync(
+			[
+				Buffer.from("participation_proof"),
+				raffleAccountId.toBytes(),
+				buyer.publicKey.toBytes(),
+			],
+			raffleProgram.programId,
+		)[0];
+		const proof = await raffleProgram.account.participationProof.fetch(proofId);
+		expect(proof.raffle.equals(raffleAccountId)).toBeTrue();
+		expect(proof.owner.equals(buyer.publicKey)).toBeTrue();
+		expect(proof.ticketCount.toNumber()).toBe(3);
+
+		// A wallet can only claim one proof per raffle
+		expect(claimProof()).rejects.toThrow();
+	});
+
+	it("should fail before the raffle is drawn", async () => {
+		const { claimProof } = await setup();
+
+		expect(claimProof()).rejects.toThrow(/RaffleNotDrawn/);
+	});
+});
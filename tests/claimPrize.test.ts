@@ -0,0 +1,319 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { LiteSVM } from "litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+const TOKEN_PROGRAM_ID = new PublicKey(
+	"TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+);
+const ASSOCIATED_TOKEN_PROGRAM_ID = new PublicKey(
+	"ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL",
+);
+
+const associatedTokenAddress = (owner: PublicKey, mint: PublicKey) =>
+	PublicKey.findProgramAddressSync(
+		[owner.toBuffer(), TOKEN_PROGRAM_ID.toBuffer(), mint.toBuffer()],
+		ASSOCIATED_TOKEN_PROGRAM_ID,
+	)[0];
+
+// Writes an initialized SPL token mint without a freeze authority
+const setMint = (client: LiteSVM, mint: PublicKey, authority: PublicKey) => {
+	const data = Buffer.alloc(82);
+	data.writeUInt32LE(1, 0);
+	authority.toBuffer().copy(data, 4);
+	data.writeBigUInt64LE(BigInt(1), 36);
+	data.writeUInt8(0, 44);
+	data.writeUInt8(1, 45);
+	client.setAccount(mint, {
+		executable: false,
+		owner: TOKEN_PROGRAM_ID,
+		lamports: LAMPORTS_PER_SOL,
+		data,
+	});
+};
+
+// Writes an initialized SPL token account holding `amount` tokens
+const setTokenAccount = (
+	client: LiteSVM,
+	address: PublicKey,
+	mint: PublicKey,
+	owner: PublicKey,
+	amount: number,
+) => {
+	const data = Buffer.alloc(165);
+	mint.toBuffer().copy(data, 0);
+	owner.toBuffer().copy(data, 32);
+	data.writeBigUInt64LE(BigInt(amount), 64);
+	data.writeUInt8(1, 108);
+	client.setAccount(address, {
+		executable: false,
+		owner: TOKEN_PROGRAM_ID,
+		lamports: LAMPORTS_PER_SOL,
+		data,
+	});
+};
+
+const tokenBalance = (client: LiteSVM, address: PublicKey) =>
+	Number(Buffer.from(client.getAccount(address)!.data).readBigUInt64LE(64));
+
+describe("claim_prize", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(1),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+		const prizeVaultId = PublicKey.findProgramAddressSync(
+			[Buffer.from("prize_vault"), raffleAccountId.toBytes()],
+			raffleProgram.programId,
+		)[0];
+
+		const winner = new Keypair();
+		provider.client.airdrop(winner.publicKey, BigInt(LAMPORTS_PER_SOL));
+
+		// Manually set the raffle state to drawn
+		const drawRaffle = async () => {
+			const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+			const raffleData = await raffleProgram.coder.accounts.encode("raffle", {
+				...raffle,
+				raffleState: { drawn: {} },
+				winnerAddress: winner.publicKey,
+			});
+			provider.client.setAccount(raffleAccountId, {
+				executable: false,
+				owner: raffleProgram.programId,
+				lamports: 1 * LAMPORTS_PER_SOL,
+				data: raffleData,
+			});
+		};
+
+		return {
+			client,
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			prizeVaultId,
+			winner,
+			drawRaffle,
+		};
+	};
+
+	it("should pay the escrowed SOL prize to the winner", async () => {
+		const {
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			prizeVaultId,
+			winner,
+			drawRaffle,
+		} = await setup();
+
+		const prizeAmount = 2 * LAMPORTS_PER_SOL;
+		await raffleProgram.methods
+			.depositPrize(new BN(prizeAmount))
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+		let raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.prizeEscrowed).toBeTrue();
+
+		await drawRaffle();
+
+		const winnerBalance = provider.client.getBalance(winner.publicKey)!;
+		await raffleProgram.methods
+			.claimPrize(null, 0)
+			.accounts({ raffle: raffleAccountId, winner: winner.publicKey })
+			.signers([winner])
+			.rpc();
+
+		expect(provider.client.getBalance(winner.publicKey)).toBeGreaterThan(
+			winnerBalance + BigInt(prizeAmount) - BigInt(LAMPORTS_PER_SOL / 100),
+		);
+		expect(provider.client.getAccount(prizeVaultId)).toBeNull();
+
+		// The raffle state is left for the winner data submission
+		raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.raffleState.drawn).toBeDefined();
+	});
+
+	it("should pay an escrowed NFT prize to the winner", async () => {
+		const {
+			client,
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			prizeVaultId,
+			winner,
+			drawRaffle,
+		} = await setup();
+
+		const mint = new Keypair().publicKey;
+		setMint(client, mint, provider.publicKey);
+		const sourceTokenAccount = new Keypair().publicKey;
+		setTokenAccount(client, sourceTokenAccount, mint, provider.publicKey, 1);
+		const vaultTokenAccount = associatedTokenAddress(prizeVaultId, mint);
+
+		await raffleProgram.methods
+			.depositPrize(new BN(1))
+			.accountsPartial({
+				raffle: raffleAccountId,
+				prizeMint: mint,
+				sourceTokenAccount,
+				vaultTokenAccount,
+				tokenProgram: TOKEN_PROGRAM_ID,
+				associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+			})
+			.rpc();
+		expect(tokenBalance(client, vaultTokenAccount)).toBe(1);
+		expect(tokenBalance(client, sourceTokenAccount)).toBe(0);
+
+		const prizeVault =
+			await raffleProgram.account.prizeVault.fetch(prizeVaultId);
+		expect(prizeVault.prizeMint?.toBase58()).toBe(mint.toBase58());
+
+		await drawRaffle();
+
+		// The prize can't be paid to a token account the winner doesn't own
+		const winnerTokenAccount = new Keypair().publicKey;
+		setTokenAccount(client, winnerTokenAccount, mint, provider.publicKey, 0);
+		expect(
+			raffleProgram.methods
+				.claimPrize(null, 0)
+				.accountsPartial({
+					raffle: raffleAccountId,
+					winner: winner.publicKey,
+					winnerTokenAccount,
+					vaultTokenAccount,
+					tokenProgram: TOKEN_PROGRAM_ID,
+				})
+				.signers([winner])
+				.rpc(),
+		).rejects.toThrow(/InvalidPrizeMint/);
+
+		setTokenAccount(client, winnerTokenAccount, mint, winner.publicKey, 0);
+		await raffleProgram.methods
+			.claimPrize(null, 0)
+			.accountsPartial({
+				raffle: raffleAccountId,
+				winner: winner.publicKey,
+				winnerTokenAccount,
+				vaultTokenAccount,
+				tokenProgram: TOKEN_PROGRAM_ID,
+			})
+			.signers([winner])
+			.rpc();
+
+		expect(tokenBalance(client, winnerTokenAccount)).toBe(1);
+		expect(client.getAccount(vaultTokenAccount)).toBeNull();
+		expect(client.getAccount(prizeVaultId)).toBeNull();
+	});
+
+	it("should fail to claim the prize when not signed by the winner", async () => {
+		const { provider, raffleProgram, raffleAccountId, drawRaffle } =
+			await setup();
+
+		await raffleProgram.methods
+			.depositPrize(new BN(LAMPORTS_PER_SOL))
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+		await drawRaffle();
+
+		const account = new Keypair();
+		provider.client.airdrop(account.publicKey, BigInt(LAMPORTS_PER_SOL));
+		expect(
+			raffleProgram.methods
+				.claimPrize(null, 0)
+				.accounts({ raffle: raffleAccountId, winner: account.publicKey })
+				.signers([account])
+				.rpc(),
+		).rejects.toThrow(/NotWinner/);
+	});
+
+	it("should fail to claim the prize before the raffle is drawn", async () => {
+		const { raffleProgram, raffleAccountId, winner } = await setup();
+
+		await raffleProgram.methods
+			.depositPrize(new BN(LAMPORTS_PER_SOL))
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		expect(
+			raffleProgram.methods
+				.claimPrize(null, 0)
+				.accounts({ raffle: raffleAccountId, winner: winner.publicKey })
+				.signers([winner])
+				.rpc(),
+		).rejects.toThrow(/RaffleNotDrawn/);
+	});
+
+	it("should withhold ticket revenue until the prize is deposited when required", async () => {
+		const { provider, raffleProgram, raffleAccountId } = await setup();
+
+		await raffleProgram.methods.setPrizeDepositRequired(true).rpc();
+
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+		await raffleProgram.methods
+			.buyTickets(new BN(1), Array.from(new Uint8Array(8)), null, null, null, null, false, null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+
+		expect(
+			raffleProgram.methods
+				.withdrawFromTreasury()
+				.accounts({ raffle: raffleAccountId })
+				.rpc(),
+		).rejects.toThrow(/PrizeNotDeposited/);
+
+		await raffleProgram.methods
+			.depositPrize(new BN(LAMPORTS_PER_SOL))
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+		await raffleProgram.methods
+			.withdrawFromTreasury()
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+	});
+});
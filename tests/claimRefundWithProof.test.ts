@@ -0,0 +1,237 @@
+import { describe, expect, it } from "bun:test";
+import { createHash } from "crypto";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+const SNAPSHOT_TREE_DEPTH = 20;
+
+const sha256 = (...parts: Buffer[]) =>
+	createHash("sha256").update(Buffer.concat(parts)).digest();
+
+// Parent node of two siblings, hashed in ascending order
+const parent = (a: Buffer, b: Buffer) =>
+	Buffer.compare(a, b) <= 0 ? sha256(a, b) : sha256(b, a);
+
+const snapshotLeaf = (owner: PublicKey, ticketCount: number) =>
+	sha256(
+		Buffer.from("snapshot"),
+		owner.toBuffer(),
+		Buffer.from(new BN(ticketCount).toArray("le", 8)),
+	);
+
+// Proof of the leaf at `index` in a snapshot tree padded with zeroed leaves
+const snapshotProof = (leaves: Buffer[], index: number) => {
+	const proof: number[][] = [];
+	let layer = leaves;
+	let empty = Buffer.alloc(32);
+	for (let height = 0; height < SNAPSHOT_TREE_DEPTH; height++) {
+		proof.push(Array.from(layer[index ^ 1] ?? empty));
+		const next: Buffer[] = [];
+		for (let i = 0; i < layer.length; i += 2) {
+			next.push(parent(layer[i] as Buffer, layer[i + 1] ?? empty));
+		}
+		layer = next;
+		index >>= 1;
+		empty = parent(empty, empty);
+	}
+	return proof;
+};
+
+describe("claim_refund_with_proof", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(1),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		// Two holders, in ascending owner order
+		const holders = [
+			{ buyer: new Keypair(), ticketCount: 3 },
+			{ buyer: new Keypair(), ticketCount: 2 },
+		].sort((a, b) =>
+			Buffer.compare(a.buyer.publicKey.toBuffer(), b.buyer.publicKey.toBuffer()),
+		);
+		for (const [seed, { buyer, ticketCount }] of holders.entries()) {
+			provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+			await raffleProgram.methods
+				.initTicketBalance(null)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+			await raffleProgram.methods
+				.buyTickets(
+					new BN(ticketCount),
+					Array.from(new Uint8Array([seed, 0, 0, 0, 0, 0, 0, 0])),
+					null,
+					null,
+					null,
+					null,
+					false,
+					null,
+				)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+		}
+
+		// Cancel the raffle and snapshot its holders
+		await raffleProgram.methods
+			.cancelRaffle()
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+		await raffleProgram.methods
+			.snapshotRaffle()
+			.accounts({ raffle: raffleAccountId })
+			.remainingAccounts(
+				holders.map(({ buyer }) => ({
+					pubkey: PublicKey.findProgramAddressSync(
+						[
+							Buffer.from("ticket_balance"),
+							raffleAccountId.toBuffer(),
+							buyer.publicKey.toBuffer(),
+						],
+						raffleProgram.programId,
+					)[0],
+					isSigner: false,
+					isWritable: false,
+				})),
+			)
+			.rpc();
+
+		const leaves = holders.map(({ buyer, ticketCount }) =>
+			snapshotLeaf(buyer.publicKey, ticketCount),
+		);
+		const snapshotClaimId = (owner: PublicKey) =>
+			PublicKey.findProgramAddressSync(
+				[
+					Buffer.from("snapshot_claim"),
+					raffleAccountId.toBuffer(),
+					owner.toBuffer(),
+				],
+				raffleProgram.programId,
+			)[0];
+
+		const claim = (holder: number, ticketCount: number) =>
+			raffleProgram.methods
+				.claimRefundWithProof(
+					new BN(ticketCount),
+					snapshotProof(leaves, holder),
+				)
+				.accounts({
+					signer: holders[holder]!.buyer.publicKey,
+					raffle: raffleAccountId,
+				})
+				.signers([holders[holder]!.buyer])
+				.rpc();
+		const reclaim = (holder: number, snapshotClaim: PublicKey | null) =>
+			raffleProgram.methods
+				.reclaimExpiredTickets()
+				.accounts({
+					signer: holders[holder]!.buyer.publicKey,
+					raffle: raffleAccountId,
+					snapshotClaim,
+				})
+				.signers([holders[holder]!.buyer])
+				.rpc();
+
+		return {
+			client,
+			raffleProgram,
+			holders,
+			snapshotClaimId,
+			claim,
+			reclaim,
+		};
+	};
+
+	it("should refund a holder from the snapshot alone", async () => {
+		const { client, raffleProgram, holders, snapshotClaimId, claim } =
+			await setup();
+
+		const { buyer, ticketCount } = holders[0]!;
+		const buyerBalance = client.getBalance(buyer.publicKey) as bigint;
+		await claim(0, ticketCount);
+
+		// The tickets are refunded in full, less the rent of the snapshot claim
+		const claimRent = client.getBalance(snapshotClaimId(buyer.publicKey)) as bigint;
+		expect(client.getBalance(buyer.publicKey)).toBe(
+			buyerBalance +
+				BigInt(ticketCount) * BigInt(0.1 * LAMPORTS_PER_SOL) -
+				claimRent,
+		);
+
+		const snapshotClaim = await raffleProgram.account.snapshotClaim.fetch(
+			snapshotClaimId(buyer.publicKey),
+		);
+		expect(snapshotClaim.owner).toEqual(buyer.publicKey);
+		expect(snapshotClaim.ticketCount.toNumber()).toBe(ticketCount);
+
+		// The holding can't be refunded again, by proof or from the ticket balance
+		expect(claim(0, ticketCount)).rejects.toThrow(/SnapshotHoldingClaimed/);
+		expect(reclaim(0, snapshotClaimId(buyer.publicKey))).rejects.toThrow(
+			/SnapshotHoldingClaimed/,
+		);
+	});
+
+	it("should fail to claim a holding already reclaimed", async () => {
+		const { holders, snapshotClaimId, claim, reclaim } = await setup();
+
+		await reclaim(1, snapshotClaimId(holders[1]!.buyer.publicKey));
+
+		expect(claim(1, holders[1]!.ticketCount)).rejects.toThrow(
+			/SnapshotHoldingClaimed/,
+		);
+	});
+
+	it("should require the snapshot claim to reclaim from a snapshotted raffle", async () => {
+		const { reclaim } = await setup();
+
+		expect(reclaim(0, null)).rejects.toThrow(/InvalidSnapshotClaim/);
+	});
+
+	it("should fail for a ticket count that isn't in the snapshot", async () => {
+		const { holders, claim } = await setup();
+
+		expect(claim(0, holders[0]!.ticketCount + 1)).rejects.toThrow(
+			/InvalidSnapshotProof/,
+		);
+	});
+});
@@ -0,0 +1,160 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("claim_ticket_collectible", async () => {
+	const setup = async (collectible: boolean) => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+		const buyer = new Keypair();
+
+		provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		const metadataUri = "https://www.example.org";
+		const ticketPrice = new BN(0.1 * LAMPORTS_PER_SOL);
+		const minTickets = new BN(1);
+		const maxTickets = new BN(100);
+		const endTime = new BN((creationTime + BigInt(3601)).toString());
+
+		// Create raffle
+		await raffleProgram.methods
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				maxTickets,
+				null,
+				false,
+				null,
+				collectible,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({
+				signer: buyer.publicKey,
+				raffle: raffleAccountId,
+			})
+			.signers([buyer])
+			.rpc();
+
+		// Buy tickets 0 to 4
+		const entrySeed = Array.from(new Uint8Array(8).fill(1));
+		await raffleProgram.methods
+			.buyTickets(new BN(5), entrySeed, null, null, null, null, false, null)
+			.accounts({
+				signer: buyer.publicKey,
+				raffle: raffleAccountId,
+			})
+			.signers([buyer])
+			.rpc();
+
+		// Manually set the raffle state to drawn
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		const raffleData = await raffleProgram.coder.accounts.encode("raffle", {
+			...raffle,
+			raffleState: {
+				drawn: {},
+			},
+			winnerAddress: buyer.publicKey,
+			winningTicket: new BN(3),
+		});
+		provider.client.setAccount(raffleAccountId, {
+			executable: false,
+			owner: raffleProgram.programId,
+			lamports: 1 * LAMPORTS_PER_SOL,
+			data: raffleData,
+		});
+
+		// Claims a ticket number, minting it into `merkleTree` through Bubblegum
+		const bubblegumProgramId = new PublicKey(
+			"BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY",
+		);
+		const claim = (ticketIndex: number, merkleTree: PublicKey) =>
+			raffleProgram.methods
+				.claimTicketCollectible(entrySeed, new BN(ticketIndex))
+				.accounts({
+					owner: buyer.publicKey,
+					raffle: raffleAccountId,
+					merkleTree,
+					treeAuthority: PublicKey.findProgramAddressSync(
+						[merkleTree.toBytes()],
+						bubblegumProgramId,
+					)[0],
+				})
+				.signers([buyer])
+				.rpc();
+
+		return { raffleProgram, claim };
+	};
+
+	it("should fail until a collectible tree is registered", async () => {
+		const { claim } = await setup(true);
+
+		expect(claim(2, new Keypair().publicKey)).rejects.toThrow(
+			/CollectibleTreeNotSet/,
+		);
+	});
+
+	it("should only mint tickets of the owner's entry into the registered tree", async () => {
+		const { raffleProgram, claim } = await setup(true);
+
+		const collectibleTree = new Keypair().publicKey;
+		await raffleProgram.methods.setCollectibleTree(collectibleTree).rpc();
+
+		expect(claim(2, new Keypair().publicKey)).rejects.toThrow(
+			/InvalidCollectibleTree/,
+		);
+
+		// Tickets outside the entry cannot be claimed
+		expect(claim(7, collectibleTree)).rejects.toThrow(/TicketNotInEntry/);
+	});
+
+	it("should only let the management authority set the collectible tree", async () => {
+		const { raffleProgram } = await setup(true);
+
+		const other = new Keypair();
+		expect(
+			raffleProgram.methods
+				.setCollectibleTree(new Keypair().publicKey)
+				.accounts({ managementAuthority: other.publicKey })
+				.signers([other])
+				.rpc(),
+		).rejects.toThrow(/NotProgramManagementAuthority/);
+	});
+
+	it("should fail for a raffle that is not collectible", async () => {
+		const { claim } = await setup(false);
+
+		expect(claim(2, new Keypair().publicKey)).rejects.toThrow(
+			/RaffleNotCollectible/,
+		);
+	});
+});
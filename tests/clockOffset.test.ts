@@ -0,0 +1,68 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("clock handling", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const createRaffle = (endTime: BN) =>
+			raffleProgram.methods
+				.createRaffle(
+					"https://www.example.org",
+					new BN(0.1 * LAMPORTS_PER_SOL),
+					endTime,
+					new BN(1),
+					null,
+					null,
+					false,
+					null,
+					false,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+				)
+				.rpc();
+
+		return { client, raffleProgram, createRaffle };
+	};
+
+	it("should fail to set the clock offset outside devnet builds", async () => {
+		const { raffleProgram } = await setup();
+
+		expect(raffleProgram.methods.setClockOffset(new BN(3600)).rpc()).rejects.toThrow(
+			/ClockOffsetUnavailable/,
+		);
+	});
+
+	it("should reject end times in milliseconds", async () => {
+		const { client, createRaffle } = await setup();
+
+		const endTime = client.getClock().unixTimestamp + BigInt(3601);
+		expect(
+			createRaffle(new BN((endTime * BigInt(1000)).toString())),
+		).rejects.toThrow(/TimestampNotInSeconds/);
+
+		// The same end time in seconds is accepted
+		await createRaffle(new BN(endTime.toString()));
+	});
+});
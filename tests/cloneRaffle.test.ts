@@ -0,0 +1,115 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("clone_raffle", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.2 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(7200)).toString()),
+				new BN(5),
+				new BN(100),
+				new BN(10),
+				true,
+				null,
+				true,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = (index: number) =>
+			PublicKey.findProgramAddressSync(
+				[
+					Buffer.from("raffle"),
+					new Uint8Array(new BN(index).toArray("le", 8)),
+				],
+				raffleProgram.programId,
+			)[0];
+
+		return { client, provider, raffleProgram, raffleAccountId };
+	};
+
+	it("should copy the parameters into a new raffle", async () => {
+		const { client, raffleProgram, raffleAccountId } = await setup();
+
+		await raffleProgram.methods
+			.setAgeRestricted(true)
+			.accounts({ raffle: raffleAccountId(0) })
+			.rpc();
+
+		// Rerun the raffle a day later
+		const newClock = client.getClock();
+		newClock.unixTimestamp = newClock.unixTimestamp + BigInt(24 * 60 * 60);
+		client.setClock(newClock);
+
+		await raffleProgram.methods
+			.cloneRaffle()
+			.accounts({ source: raffleAccountId(0) })
+			.rpc();
+
+		const source = await raffleProgram.account.raffle.fetch(
+			raffleAccountId(0),
+		);
+		const clone = await raffleProgram.account.raffle.fetch(raffleAccountId(1));
+		expect(clone.metadataUri).toBe(source.metadataUri);
+		expect(clone.ticketPrice.eq(source.ticketPrice)).toBeTrue();
+		expect(clone.minTickets.eq(source.minTickets)).toBeTrue();
+		expect(clone.maxTickets?.eq(new BN(100))).toBeTrue();
+		expect(clone.maxPerPurchase?.eq(new BN(10))).toBeTrue();
+		expect(clone.anonymizeWinner).toBeTrue();
+		expect(clone.collectible).toBeTrue();
+		expect(clone.ageRestricted).toBeTrue();
+		expect(clone.features).toBe(source.features);
+		expect(clone.raffleState).toEqual({ open: {} });
+		expect(clone.currentTickets.toNumber()).toBe(0);
+
+		// The clone runs for as long as the source, starting from its creation
+		expect(clone.creationTime.toString()).toBe(
+			newClock.unixTimestamp.toString(),
+		);
+		expect(clone.endTime.sub(clone.creationTime).toNumber()).toBe(7200);
+	});
+
+	it("should fail from accounts other than the management authority", async () => {
+		const { provider, raffleProgram, raffleAccountId } = await setup();
+
+		const account = new Keypair();
+		provider.client.airdrop(account.publicKey, BigInt(LAMPORTS_PER_SOL));
+
+		expect(
+			raffleProgram.methods
+				.cloneRaffle()
+				.accounts({
+					source: raffleAccountId(0),
+					managementAuthority: account.publicKey,
+				})
+				.signers([account])
+				.rpc(),
+		).rejects.toThrow(/NotProgramManagementAuthority/);
+	});
+});
@@ -0,0 +1,170 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("close_entries", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		const metadataUri = "https://www.example.org";
+		const ticketPrice = new BN(0.1 * LAMPORTS_PER_SOL);
+		const minTickets = new BN(10);
+		const endTime = new BN((creationTime + BigInt(3601)).toString());
+
+		// Create raffle
+		await raffleProgram.methods
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		// Two buyers purchase tickets, without meeting the threshold
+		const purchases: { buyer: Keypair; entryId: PublicKey }[] = [];
+		for (let i = 0; i < 2; i++) {
+			const buyer = new Keypair();
+			provider.client.airdrop(buyer.publicKey, BigInt(1 * LAMPORTS_PER_SOL));
+			await raffleProgram.methods
+				.initTicketBalance(null)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+
+			const randomBytes = new Uint8Array(8);
+			crypto.getRandomValues(randomBytes);
+			await raffleProgram.methods
+				.buyTickets(new BN(1), Array.from(randomBytes), null, null, null, null, false, null)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+
+			const entryId = PublicKey.findProgramAddressSync(
+				[Buffer.from("entry"), raffleAccountId.toBytes(), randomBytes],
+				raffleProgram.programId,
+			)[0];
+			purchases.push({ buyer, entryId });
+		}
+
+		const expire = async () => {
+			const newClock = client.getClock();
+			newClock.unixTimestamp = creationTime + BigInt(3602);
+			client.setClock(newClock);
+			await raffleProgram.methods
+				.expireRaffle()
+				.accounts({ raffle: raffleAccountId })
+				.rpc();
+		};
+
+		return { provider, raffleProgram, raffleAccountId, purchases, expire };
+	};
+
+	it("should close all entries of an expired raffle and refund the rent to their owners", async () => {
+		const { provider, raffleProgram, raffleAccountId, purchases, expire } =
+			await setup();
+		await expire();
+
+		const entryRent = provider.client.minimumBalanceForRentExemption(
+			BigInt(raffleProgram.account.entry.size),
+		);
+		const balancesBefore = purchases.map(({ buyer }) =>
+			provider.client.getBalance(buyer.publicKey),
+		);
+
+		await raffleProgram.methods
+			.closeEntries()
+			.accounts({ raffle: raffleAccountId })
+			.remainingAccounts(
+				purchases.flatMap(({ buyer, entryId }) => [
+					{ pubkey: entryId, isSigner: false, isWritable: true },
+					{ pubkey: buyer.publicKey, isSigner: false, isWritable: true },
+				]),
+			)
+			.rpc();
+
+		for (const [i, { buyer, entryId }] of purchases.entries()) {
+			const balanceBefore = balancesBefore[i];
+			if (!balanceBefore) {
+				throw new Error("Failed to fetch balance");
+			}
+			expect(provider.client.getBalance(buyer.publicKey)).toEqual(
+				balanceBefore + entryRent,
+			);
+			expect(raffleProgram.account.entry.fetch(entryId)).rejects.toThrow(
+				/Account does not exist/,
+			);
+		}
+	});
+
+	it("should fail to refund the rent to an account other than the entry owner", async () => {
+		const { raffleProgram, raffleAccountId, purchases, expire } = await setup();
+		await expire();
+
+		expect(
+			raffleProgram.methods
+				.closeEntries()
+				.accounts({ raffle: raffleAccountId })
+				.remainingAccounts([
+					{ pubkey: purchases[0].entryId, isSigner: false, isWritable: true },
+					{
+						pubkey: purchases[1].buyer.publicKey,
+						isSigner: false,
+						isWritable: true,
+					},
+				])
+				.rpc(),
+		).rejects.toThrow(/OwnerMismatch/);
+	});
+
+	it("should fail while the raffle is still open", async () => {
+		const { raffleProgram, raffleAccountId, purchases } = await setup();
+
+		expect(
+			raffleProgram.methods
+				.closeEntries()
+				.accounts({ raffle: raffleAccountId })
+				.remainingAccounts([
+					{ pubkey: purchases[0].entryId, isSigner: false, isWritable: true },
+					{
+						pubkey: purchases[0].buyer.publicKey,
+						isSigner: false,
+						isWritable: true,
+					},
+				])
+				.rpc(),
+		).rejects.toThrow(/RaffleNotFinalized/);
+	});
+});
@@ -0,0 +1,157 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("close_entry", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(10),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		// A buyer purchases tickets, without meeting the threshold
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(1 * LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+		const entrySeed = Array.from(new Uint8Array([1, 0, 0, 0, 0, 0, 0, 0]));
+		await raffleProgram.methods
+			.buyTickets(new BN(1), entrySeed, null, null, null, null, false, null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+		const entryId = PublicKey.findProgramAddressSync(
+			[
+				Buffer.from("entry"),
+				raffleAccountId.toBytes(),
+				new Uint8Array(entrySeed),
+			],
+			raffleProgram.programId,
+		)[0];
+
+		const expire = async () => {
+			const newClock = client.getClock();
+			newClock.unixTimestamp = creationTime + BigInt(3602);
+			client.setClock(newClock);
+			await raffleProgram.methods
+				.expireRaffle()
+				.accounts({ raffle: raffleAccountId })
+				.rpc();
+		};
+
+		const reclaim = () =>
+			raffleProgram.methods
+				.reclaimExpiredTickets()
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+
+		const closeEntry = (owner: Keypair) =>
+			raffleProgram.methods
+				.closeEntry(entrySeed)
+				.accounts({ raffle: raffleAccountId, owner: owner.publicKey })
+				.signers([owner])
+				.rpc();
+
+		return {
+			provider,
+			raffleProgram,
+			buyer,
+			entryId,
+			expire,
+			reclaim,
+			closeEntry,
+		};
+	};
+
+	it("should close the entry of a refunded raffle and return the rent to its owner", async () => {
+		const {
+			provider,
+			raffleProgram,
+			buyer,
+			entryId,
+			expire,
+			reclaim,
+			closeEntry,
+		} = await setup();
+		await expire();
+		await reclaim();
+
+		const entryRent = provider.client.minimumBalanceForRentExemption(
+			BigInt(raffleProgram.account.entry.size),
+		);
+		const balanceBefore = provider.client.getBalance(buyer.publicKey)!;
+		await closeEntry(buyer);
+
+		expect(provider.client.getBalance(buyer.publicKey)).toEqual(
+			balanceBefore + entryRent,
+		);
+		expect(raffleProgram.account.entry.fetch(entryId)).rejects.toThrow(
+			/Account does not exist/,
+		);
+	});
+
+	it("should fail to close an entry while refunds are still owed", async () => {
+		const { buyer, expire, closeEntry } = await setup();
+		await expire();
+
+		expect(closeEntry(buyer)).rejects.toThrow(/TreasuryNotSettled/);
+	});
+
+	it("should fail to close an entry of an open raffle", async () => {
+		const { buyer, closeEntry } = await setup();
+
+		expect(closeEntry(buyer)).rejects.toThrow(/RaffleNotFinalized/);
+	});
+
+	it("should fail to close an entry owned by another wallet", async () => {
+		const { provider, expire, reclaim, closeEntry } = await setup();
+		await expire();
+		await reclaim();
+
+		const account = new Keypair();
+		provider.client.airdrop(account.publicKey, BigInt(LAMPORTS_PER_SOL));
+		expect(closeEntry(account)).rejects.toThrow(/OwnerMismatch/);
+	});
+});
@@ -0,0 +1,136 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("close_raffle", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(10),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+		const treasuryId = PublicKey.findProgramAddressSync(
+			[Buffer.from("treasury"), raffleAccountId.toBytes()],
+			raffleProgram.programId,
+		)[0];
+
+		// Buy 0.5 SOL worth of tickets, below the threshold
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+		await raffleProgram.methods
+			.buyTickets(new BN(5), Array.from(new Uint8Array(8)), null, null, null, null, false, null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+
+		// Time-travel to when the raffle ends and expire it
+		const newClock = client.getClock();
+		newClock.unixTimestamp = creationTime + BigInt(3602);
+		client.setClock(newClock);
+		await raffleProgram.methods
+			.expireRaffle()
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		return { provider, raffleProgram, raffleAccountId, treasuryId, buyer };
+	};
+
+	it("should return the rent to the account that paid it", async () => {
+		const { provider, raffleProgram, raffleAccountId, treasuryId, buyer } =
+			await setup();
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.rentPayer.equals(provider.publicKey)).toBeTrue();
+
+		// The treasury still owes the buyer a refund
+		expect(
+			raffleProgram.methods
+				.closeRaffle()
+				.accounts({ raffle: raffleAccountId, rentPayer: provider.publicKey })
+				.rpc(),
+		).rejects.toThrow(/TreasuryNotSettled/);
+
+		await raffleProgram.methods
+			.reclaimExpiredTickets()
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+
+		const rentPayerBalanceBefore = provider.client.getBalance(
+			provider.publicKey,
+		);
+		if (!rentPayerBalanceBefore) {
+			throw new Error("Failed to fetch balance");
+		}
+
+		await raffleProgram.methods
+			.closeRaffle()
+			.accounts({ raffle: raffleAccountId, rentPayer: provider.publicKey })
+			.rpc();
+
+		expect(provider.client.getBalance(provider.publicKey)).toBeGreaterThan(
+			rentPayerBalanceBefore,
+		);
+		expect(provider.client.getAccount(raffleAccountId)).toBeNull();
+		expect(provider.client.getAccount(treasuryId)).toBeNull();
+	});
+
+	it("should fail to return the rent to other accounts", async () => {
+		const { raffleProgram, raffleAccountId, buyer } = await setup();
+
+		await raffleProgram.methods
+			.reclaimExpiredTickets()
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+
+		expect(
+			raffleProgram.methods
+				.closeRaffle()
+				.accounts({ raffle: raffleAccountId, rentPayer: buyer.publicKey })
+				.rpc(),
+		).rejects.toThrow(/NotRentPayer/);
+	});
+});
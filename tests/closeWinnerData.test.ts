@@ -0,0 +1,147 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("close_winner_data", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+		const winner = new Keypair();
+
+		provider.client.airdrop(winner.publicKey, BigInt(LAMPORTS_PER_SOL));
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(5),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+		const winnerDataId = PublicKey.findProgramAddressSync(
+			[
+				Buffer.from("winner_data"),
+				raffleAccountId.toBytes(),
+				winner.publicKey.toBytes(),
+			],
+			raffleProgram.programId,
+		)[0];
+
+		// Manually set the raffle state to drawn
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		const raffleData = await raffleProgram.coder.accounts.encode("raffle", {
+			...raffle,
+			raffleState: { drawn: {} },
+			winnerAddress: winner.publicKey,
+		});
+		provider.client.setAccount(raffleAccountId, {
+			executable: false,
+			owner: raffleProgram.programId,
+			lamports: 1 * LAMPORTS_PER_SOL,
+			data: raffleData,
+		});
+
+		// The winner submits their data, which claims the raffle
+		await raffleProgram.methods
+			.submitWinnerData("data", null, 1, 1, 0)
+			.accounts({ raffle: raffleAccountId, signer: winner.publicKey })
+			.signers([winner])
+			.rpc();
+
+		const lockWinnerData = () =>
+			raffleProgram.methods
+				.lockWinnerData()
+				.accounts({
+					raffle: raffleAccountId,
+					winner: winner.publicKey,
+					signer: winner.publicKey,
+				})
+				.signers([winner])
+				.rpc();
+
+		const closeWinnerData = (winnerId: PublicKey) =>
+			raffleProgram.methods
+				.closeWinnerData()
+				.accounts({ raffle: raffleAccountId, winner: winnerId })
+				.rpc();
+
+		return {
+			provider,
+			raffleProgram,
+			winner,
+			winnerDataId,
+			lockWinnerData,
+			closeWinnerData,
+		};
+	};
+
+	it("should close locked winner data and return the rent to the winner", async () => {
+		const {
+			provider,
+			raffleProgram,
+			winner,
+			winnerDataId,
+			lockWinnerData,
+			closeWinnerData,
+		} = await setup();
+		await lockWinnerData();
+
+		const winnerDataRent = provider.client.getBalance(winnerDataId)!;
+		const balanceBefore = provider.client.getBalance(winner.publicKey)!;
+		await closeWinnerData(winner.publicKey);
+
+		expect(provider.client.getBalance(winner.publicKey)).toEqual(
+			balanceBefore + winnerDataRent,
+		);
+		expect(
+			raffleProgram.account.winnerData.fetch(winnerDataId),
+		).rejects.toThrow(/Account does not exist/);
+	});
+
+	it("should fail to close winner data that is not locked", async () => {
+		const { winner, closeWinnerData } = await setup();
+
+		expect(closeWinnerData(winner.publicKey)).rejects.toThrow(
+			/WinnerDataNotLocked/,
+		);
+	});
+
+	it("should fail to return the rent to an account other than the winner", async () => {
+		const { lockWinnerData, closeWinnerData } = await setup();
+		await lockWinnerData();
+
+		expect(closeWinnerData(new Keypair().publicKey)).rejects.toThrow(
+			/NotWinner/,
+		);
+	});
+});
@@ -0,0 +1,191 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("consolidate_treasuries", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		const payoutAuthority = new Keypair();
+		provider.client.airdrop(
+			payoutAuthority.publicKey,
+			BigInt(0.1 * LAMPORTS_PER_SOL),
+		);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: payoutAuthority.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		// Creates a raffle with 5 tickets sold and returns its raffle and treasury
+		const createRaffle = async (raffleIndex: number) => {
+			const creationTime = client.getClock().unixTimestamp;
+			await raffleProgram.methods
+				.createRaffle(
+					"https://www.example.org",
+					new BN(0.1 * LAMPORTS_PER_SOL),
+					new BN((creationTime + BigInt(3601)).toString()),
+					new BN(1),
+					null,
+					null,
+					false,
+					null,
+					false,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+				)
+				.rpc();
+			const raffleId = PublicKey.findProgramAddressSync(
+				[
+					Buffer.from("raffle"),
+					new Uint8Array(new BN(raffleIndex).toArray("le", 8)),
+				],
+				raffleProgram.programId,
+			)[0];
+			const treasuryId = PublicKey.findProgramAddressSync(
+				[Buffer.from("treasury"), raffleId.toBytes()],
+				raffleProgram.programId,
+			)[0];
+
+			const buyer = new Keypair();
+			provider.client.airdrop(buyer.publicKey, BigInt(LAMPORTS_PER_SOL));
+			await raffleProgram.methods
+				.initTicketBalance(null)
+				.accounts({ signer: buyer.publicKey, raffle: raffleId })
+				.signers([buyer])
+				.rpc();
+			await raffleProgram.methods
+				.buyTickets(new BN(5), Array.from(new Uint8Array(8)), null, null, null, null, false, null)
+				.accounts({ signer: buyer.publicKey, raffle: raffleId })
+				.signers([buyer])
+				.rpc();
+
+			return { raffleId, treasuryId };
+		};
+
+		// Manually set the raffle state to drawn
+		const drawRaffle = async (raffleId: PublicKey) => {
+			const raffle = await raffleProgram.account.raffle.fetch(raffleId);
+			const raffleData = await raffleProgram.coder.accounts.encode("raffle", {
+				...raffle,
+				raffleState: { drawn: {} },
+				winnerAddress: new Keypair().publicKey,
+			});
+			provider.client.setAccount(raffleId, {
+				executable: false,
+				owner: raffleProgram.programId,
+				lamports: 1 * LAMPORTS_PER_SOL,
+				data: raffleData,
+			});
+		};
+
+		return {
+			provider,
+			raffleProgram,
+			payoutAuthority,
+			createRaffle,
+			drawRaffle,
+		};
+	};
+
+	const pairs = (raffles: { raffleId: PublicKey; treasuryId: PublicKey }[]) =>
+		raffles.flatMap(({ raffleId, treasuryId }) => [
+			{ pubkey: raffleId, isSigner: false, isWritable: true },
+			{ pubkey: treasuryId, isSigner: false, isWritable: true },
+		]);
+
+	it("should withdraw the treasuries of all concluded raffles at once", async () => {
+		const {
+			provider,
+			raffleProgram,
+			payoutAuthority,
+			createRaffle,
+			drawRaffle,
+		} = await setup();
+
+		const raffles = [await createRaffle(0), await createRaffle(1)];
+		for (const { raffleId } of raffles) {
+			await drawRaffle(raffleId);
+		}
+
+		const balanceBefore = provider.client.getBalance(
+			payoutAuthority.publicKey,
+		)!;
+		await raffleProgram.methods
+			.consolidateTreasuries()
+			.accounts({ payoutAuthority: payoutAuthority.publicKey })
+			.remainingAccounts(pairs(raffles))
+			.rpc();
+
+		expect(
+			provider.client.getBalance(payoutAuthority.publicKey)! - balanceBefore,
+		).toEqual(BigInt(LAMPORTS_PER_SOL));
+		for (const { treasuryId } of raffles) {
+			const treasury = await raffleProgram.account.treasury.fetch(treasuryId);
+			expect(treasury.totalWithdrawn.toNumber()).toBe(0.5 * LAMPORTS_PER_SOL);
+		}
+
+		// Treasuries that were already withdrawn are skipped
+		await raffleProgram.methods
+			.consolidateTreasuries()
+			.accounts({ payoutAuthority: payoutAuthority.publicKey })
+			.remainingAccounts(pairs(raffles))
+			.rpc();
+		expect(
+			provider.client.getBalance(payoutAuthority.publicKey)! - balanceBefore,
+		).toEqual(BigInt(LAMPORTS_PER_SOL));
+	});
+
+	it("should fail when a raffle has not concluded yet", async () => {
+		const { raffleProgram, payoutAuthority, createRaffle, drawRaffle } =
+			await setup();
+
+		const raffles = [await createRaffle(0), await createRaffle(1)];
+		await drawRaffle(raffles[0].raffleId);
+
+		expect(
+			raffleProgram.methods
+				.consolidateTreasuries()
+				.accounts({ payoutAuthority: payoutAuthority.publicKey })
+				.remainingAccounts(pairs(raffles))
+				.rpc(),
+		).rejects.toThrow(/RaffleNotFinalized/);
+	});
+
+	it("should fail when not signed by the management authority", async () => {
+		const { provider, raffleProgram, payoutAuthority, createRaffle, drawRaffle } =
+			await setup();
+
+		const raffles = [await createRaffle(0)];
+		await drawRaffle(raffles[0].raffleId);
+
+		const account = new Keypair();
+		provider.client.airdrop(account.publicKey, BigInt(LAMPORTS_PER_SOL));
+		expect(
+			raffleProgram.methods
+				.consolidateTreasuries()
+				.accounts({
+					managementAuthority: account.publicKey,
+					payoutAuthority: payoutAuthority.publicKey,
+				})
+				.remainingAccounts(pairs(raffles))
+				.signers([account])
+				.rpc(),
+		).rejects.toThrow(/NotProgramManagementAuthority/);
+	});
+});
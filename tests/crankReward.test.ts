@@ -0,0 +1,197 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+const SLOT_HASHES = new PublicKey("SysvarS1otHashes111111111111111111111111111");
+
+describe("crank_reward", async () => {
+	const setup = async (minTickets: number) => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config with a crank reward of 1% of the ticket price
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+		await raffleProgram.methods.setCrankReward(100).rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(minTickets),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		// Sell 5 tickets
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+		await raffleProgram.methods
+			.buyTickets(new BN(5), Array.from(new Uint8Array(8)), null, null, null, null, false, null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+
+		const newClock = client.getClock();
+		newClock.unixTimestamp = creationTime + BigInt(3602);
+		client.setClock(newClock);
+
+		const cranker = new Keypair();
+		provider.client.airdrop(cranker.publicKey, BigInt(0.1 * LAMPORTS_PER_SOL));
+		const finalize = () =>
+			raffleProgram.methods
+				.finalizeRaffle()
+				.accounts({
+					raffle: raffleAccountId,
+					recentSlothashes: SLOT_HASHES,
+					cranker: cranker.publicKey,
+				})
+				.signers([cranker])
+				.rpc();
+
+		return {
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			buyer,
+			cranker,
+			finalize,
+		};
+	};
+
+	it("should pay the cranker for drawing a raffle that met its threshold", async () => {
+		const { provider, raffleProgram, raffleAccountId, cranker, finalize } =
+			await setup(1);
+
+		const balanceBefore = provider.client.getBalance(cranker.publicKey)!;
+		await finalize();
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.raffleState).toEqual({ drawing: {} });
+		// 5 tickets at 1% of 0.1 SOL
+		expect(
+			provider.client.getBalance(cranker.publicKey)! - balanceBefore,
+		).toEqual(BigInt(0.005 * LAMPORTS_PER_SOL));
+	});
+
+	it("should not pay the reward again when a reset raffle is redrawn", async () => {
+		const { provider, raffleProgram, raffleAccountId, cranker, finalize } =
+			await setup(1);
+
+		const balanceBefore = provider.client.getBalance(cranker.publicKey)!;
+		await finalize();
+
+		// The draw can only be reset once the claim window passed
+		const clock = provider.client.getClock();
+		clock.unixTimestamp += BigInt(7 * 24 * 60 * 60);
+		provider.client.setClock(clock);
+		await raffleProgram.methods
+			.resetDraw()
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+		// The redraw needs entropy newer than the discarded draw, and repeats the first
+		// transaction, so it needs a new blockhash
+		provider.client.warpToSlot(BigInt(100));
+		provider.client.expireBlockhash();
+		await finalize();
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.raffleState).toEqual({ drawing: {} });
+		expect(raffle.crankRewardPaid).toBe(true);
+		expect(
+			provider.client.getBalance(cranker.publicKey)! - balanceBefore,
+		).toEqual(BigInt(0.005 * LAMPORTS_PER_SOL));
+	});
+
+	it("should pay the cranker for expiring a raffle and recover it from refunds", async () => {
+		const {
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			buyer,
+			cranker,
+			finalize,
+		} = await setup(10);
+
+		const balanceBefore = provider.client.getBalance(cranker.publicKey)!;
+		await finalize();
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.raffleState).toEqual({ expired: {} });
+		expect(
+			provider.client.getBalance(cranker.publicKey)! - balanceBefore,
+		).toEqual(BigInt(0.005 * LAMPORTS_PER_SOL));
+
+		// The buyer is refunded their tickets less the crank reward paid for them
+		const ticketBalanceId = PublicKey.findProgramAddressSync(
+			[
+				Buffer.from("ticket_balance"),
+				raffleAccountId.toBytes(),
+				buyer.publicKey.toBytes(),
+			],
+			raffleProgram.programId,
+		)[0];
+		const ticketBalanceRent = provider.client.getBalance(ticketBalanceId)!;
+		const buyerBalanceBefore = provider.client.getBalance(buyer.publicKey)!;
+		await raffleProgram.methods
+			.reclaimExpiredTickets()
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+		expect(
+			provider.client.getBalance(buyer.publicKey)! -
+				buyerBalanceBefore -
+				ticketBalanceRent,
+		).toEqual(BigInt(0.495 * LAMPORTS_PER_SOL));
+	});
+
+	it("should fail to set a reward above the maximum", async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		expect(raffleProgram.methods.setCrankReward(101).rpc()).rejects.toThrow(
+			/InvalidCrankReward/,
+		);
+	});
+});
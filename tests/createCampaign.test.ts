@@ -0,0 +1,225 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("create_campaign", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(1),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+		const treasuryId = PublicKey.findProgramAddressSync(
+			[Buffer.from("treasury"), raffleAccountId.toBytes()],
+			raffleProgram.programId,
+		)[0];
+		const campaignId = PublicKey.findProgramAddressSync(
+			[Buffer.from("campaign"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		return {
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			treasuryId,
+			campaignId,
+		};
+	};
+
+	it("should discount purchases until the budget is exhausted", async () => {
+		const {
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			treasuryId,
+			campaignId,
+		} = await setup();
+
+		// 50% off, with enough budget for one and a half discounts
+		await raffleProgram.methods
+			.createCampaign(new BN(0), 5000, new BN(0.075 * LAMPORTS_PER_SOL))
+			.rpc();
+
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+
+		const expectedSpent = [0.05, 0.075, 0.075];
+		for (const [index, spent] of expectedSpent.entries()) {
+			await raffleProgram.methods
+				.buyTickets(
+					new BN(1),
+					Array.from(new Uint8Array(8).fill(index)),
+					null,
+					null,
+					null,
+					null,
+					false,
+					null,
+				)
+				.accounts({
+					signer: buyer.publicKey,
+					raffle: raffleAccountId,
+					campaign: campaignId,
+				})
+				.signers([buyer])
+				.rpc();
+
+			const campaign = await raffleProgram.account.campaign.fetch(campaignId);
+			expect(campaign.spent.toNumber()).toBe(spent * LAMPORTS_PER_SOL);
+		}
+
+		// The treasury collected the full ticket price either way
+		const treasury = await raffleProgram.account.treasury.fetch(treasuryId);
+		expect(treasury.totalCollected.toNumber()).toBe(0.3 * LAMPORTS_PER_SOL);
+		expect(provider.client.getBalance(campaignId)).toBe(
+			provider.client.minimumBalanceForRentExemption(
+				BigInt(raffleProgram.account.campaign.size),
+			),
+		);
+	});
+
+	it("should return the subsidy to the campaign when the tickets are refunded", async () => {
+		const {
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			treasuryId,
+			campaignId,
+		} = await setup();
+
+		// 50% off, with enough budget for one discount
+		await raffleProgram.methods
+			.createCampaign(new BN(0), 5000, new BN(0.05 * LAMPORTS_PER_SOL))
+			.rpc();
+
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+		await raffleProgram.methods
+			.buyTickets(new BN(1), Array.from(new Uint8Array(8)), null, null, null, null, false, null)
+			.accounts({
+				signer: buyer.publicKey,
+				raffle: raffleAccountId,
+				campaign: campaignId,
+			})
+			.signers([buyer])
+			.rpc();
+
+		const ticketBalanceId = PublicKey.findProgramAddressSync(
+			[
+				Buffer.from("ticket_balance"),
+				raffleAccountId.toBytes(),
+				buyer.publicKey.toBytes(),
+			],
+			raffleProgram.programId,
+		)[0];
+		const ticketBalance =
+			await raffleProgram.account.ticketBalance.fetch(ticketBalanceId);
+		expect(ticketBalance.campaignSubsidy.toNumber()).toBe(0.05 * LAMPORTS_PER_SOL);
+		expect(ticketBalance.subsidyCampaign?.toBase58()).toBe(campaignId.toBase58());
+
+		await raffleProgram.methods
+			.cancelRaffle()
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		// The buyer gets back what they paid, the campaign its subsidy
+		const treasuryBefore = provider.client.getBalance(treasuryId)!;
+		const campaignBefore = provider.client.getBalance(campaignId)!;
+		await raffleProgram.methods
+			.reclaimExpiredTickets()
+			.accounts({
+				signer: buyer.publicKey,
+				raffle: raffleAccountId,
+				campaign: campaignId,
+			})
+			.signers([buyer])
+			.rpc();
+		expect(treasuryBefore - provider.client.getBalance(treasuryId)!).toEqual(
+			BigInt(0.1 * LAMPORTS_PER_SOL),
+		);
+		expect(provider.client.getBalance(campaignId)! - campaignBefore).toEqual(
+			BigInt(0.05 * LAMPORTS_PER_SOL),
+		);
+		const campaign = await raffleProgram.account.campaign.fetch(campaignId);
+		expect(campaign.spent.toNumber()).toBe(0);
+	});
+
+	it("should fail with an invalid discount or budget", async () => {
+		const { raffleProgram } = await setup();
+
+		for (const [discountBps, budget] of [
+			[0, LAMPORTS_PER_SOL],
+			[10001, LAMPORTS_PER_SOL],
+			[5000, 0],
+		]) {
+			expect(
+				raffleProgram.methods
+					.createCampaign(new BN(0), discountBps, new BN(budget))
+					.rpc(),
+			).rejects.toThrow(/InvalidCampaign/);
+		}
+	});
+
+	it("should fail from accounts other than the management authority", async () => {
+		const { provider, raffleProgram } = await setup();
+
+		const account = new Keypair();
+		provider.client.airdrop(account.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+
+		expect(
+			raffleProgram.methods
+				.createCampaign(new BN(0), 5000, new BN(LAMPORTS_PER_SOL))
+				.accounts({ managementAuthority: account.publicKey })
+				.signers([account])
+				.rpc(),
+		).rejects.toThrow(/NotProgramManagementAuthority/);
+	});
+});
@@ -96,7 +96,24 @@ describe("create_raffle", async () => {
 			const maxTickets = input.maxTickets ?? null;
 
 			await raffleProgram.methods
-				.createRaffle(metadataUri, ticketPrice, endTime, minTickets, maxTickets)
+				.createRaffle(
+					metadataUri,
+					ticketPrice,
+					endTime,
+					minTickets,
+					maxTickets,
+					null,
+					false,
+					null,
+					false,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+				)
 				.rpc();
 
 			const raffleAccountId = PublicKey.findProgramAddressSync(
@@ -134,6 +151,7 @@ describe("create_raffle", async () => {
 			expect(raffleAccount.raffleState.expired).toBeUndefined();
 			expect(raffleAccount.winnerAddress).toBeNull();
 			expect(raffleAccount.winningTicket).toBeNull();
+			expect(raffleAccount.features).toBe(0);
 
 			// Validate treasury account is initialized correctly
 			const treasuryAccount =
@@ -149,6 +167,53 @@ describe("create_raffle", async () => {
 		}
 	});
 
+	it("should record the enabled mechanics as feature flags", async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(1),
+				new BN(100),
+				new BN(5),
+				true,
+				null,
+				true,
+				5000,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		// Anonymized winner, collectible, cash prize and purchase limit
+		const raffleAccount =
+			await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffleAccount.features).toBe(0b100111);
+	});
+
 	it("should fail with invalid metadata uris", async () => {
 		const client = fromWorkspace(".");
 		const provider = new LiteSVMProvider(client);
@@ -192,6 +257,13 @@ describe("create_raffle", async () => {
 				expiresIn: BigInt(3601),
 				errorRegex: /InvalidMetadataUri/,
 			},
+			{
+				metadataUri: "ar://not-a-transaction-id",
+				ticketPrice: new BN(0.1 * LAMPORTS_PER_SOL),
+				minTickets: new BN(1),
+				expiresIn: BigInt(3601),
+				errorRegex: /InvalidMetadataUri/,
+			},
 		];
 
 		for (const input of inputs) {
@@ -203,7 +275,24 @@ describe("create_raffle", async () => {
 
 			expect(
 				raffleProgram.methods
-					.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+					.createRaffle(
+						metadataUri,
+						ticketPrice,
+						endTime,
+						minTickets,
+						null,
+						null,
+						false,
+						null,
+						false,
+						null,
+						null,
+						null,
+						null,
+						null,
+						null,
+						null,
+					)
 					.rpc(),
 			).rejects.toThrow(input.errorRegex);
 		}
@@ -263,7 +352,24 @@ describe("create_raffle", async () => {
 
 			expect(
 				raffleProgram.methods
-					.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+					.createRaffle(
+						metadataUri,
+						ticketPrice,
+						endTime,
+						minTickets,
+						null,
+						null,
+						false,
+						null,
+						false,
+						null,
+						null,
+						null,
+						null,
+						null,
+						null,
+						null,
+					)
 					.rpc(),
 			).rejects.toThrow(input.errorRegex);
 		}
@@ -316,7 +422,24 @@ describe("create_raffle", async () => {
 
 			expect(
 				raffleProgram.methods
-					.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+					.createRaffle(
+						metadataUri,
+						ticketPrice,
+						endTime,
+						minTickets,
+						null,
+						null,
+						false,
+						null,
+						false,
+						null,
+						null,
+						null,
+						null,
+						null,
+						null,
+						null,
+					)
 					.rpc(),
 			).rejects.toThrow(input.errorRegex);
 		}
@@ -379,6 +502,17 @@ describe("create_raffle", async () => {
 						endTime,
 						minTickets,
 						maxTickets,
+						null,
+						false,
+						null,
+						false,
+						null,
+						null,
+						null,
+						null,
+						null,
+						null,
+						null,
 					)
 					.rpc(),
 			).rejects.toThrow(input.errorRegex);
@@ -446,7 +580,24 @@ describe("create_raffle", async () => {
 
 			expect(
 				raffleProgram.methods
-					.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+					.createRaffle(
+						metadataUri,
+						ticketPrice,
+						endTime,
+						minTickets,
+						null,
+						null,
+						false,
+						null,
+						false,
+						null,
+						null,
+						null,
+						null,
+						null,
+						null,
+						null,
+					)
 					.rpc(),
 			).rejects.toThrow(input.errorRegex);
 		}
@@ -476,8 +627,135 @@ describe("create_raffle", async () => {
 		// Create raffle from the provider keypair, which is NOT the management authority in this case
 		expect(
 			raffleProgram.methods
-				.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+				.createRaffle(
+					metadataUri,
+					ticketPrice,
+					endTime,
+					minTickets,
+					null,
+					null,
+					false,
+					null,
+					false,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+				)
 				.rpc(),
 		).rejects.toThrow(/NotProgramManagementAuthority/);
 	});
+
+	it("should record the typed display values of the raffle", async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		const createRaffle = (ticketCountDisplayCap: BN) =>
+			raffleProgram.methods
+				.createRaffle(
+					"https://www.example.org",
+					new BN(0.1 * LAMPORTS_PER_SOL),
+					new BN((creationTime + BigInt(3601)).toString()),
+					new BN(1),
+					null,
+					null,
+					false,
+					null,
+					false,
+					null,
+					null,
+					null,
+					null,
+					null,
+					new BN(1_250_000),
+					ticketCountDisplayCap,
+				)
+				.rpc();
+
+		expect(createRaffle(new BN(0))).rejects.toThrow(
+			/InvalidTicketCountDisplayCap/,
+		);
+		await createRaffle(new BN(10_000));
+
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+		const raffleAccount =
+			await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffleAccount.prizeValueUsdCents?.toNumber()).toBe(1_250_000);
+		expect(raffleAccount.ticketCountDisplayCap?.toNumber()).toBe(10_000);
+	});
+
+	it("should store the transaction id of Arweave metadata", async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		// The transaction id is the base64url encoding of 32 bytes, here 0 to 30 and 124
+		const txId = [...Array.from({ length: 31 }, (_, i) => i), 124];
+		const encodedTxId = Buffer.from(txId).toString("base64url");
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				`ar://${encodedTxId}`,
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(1),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+		const raffleAccount =
+			await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffleAccount.arweaveTxId).toEqual(txId);
+
+		// The transaction id of ar:// metadata can't be replaced by a mirror
+		expect(
+			raffleProgram.methods
+				.setArweaveMirror(null)
+				.accounts({ raffle: raffleAccountId })
+				.rpc(),
+		).rejects.toThrow(/ArweaveTxIdFixedByUri/);
+	});
 });
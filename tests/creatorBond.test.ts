@@ -0,0 +1,181 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("creator_bond", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+		const insuranceFund = new Keypair();
+
+		// Init config requiring a 1 SOL bond per raffle
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+		await raffleProgram.methods.setCreatorBond(new BN(LAMPORTS_PER_SOL)).rpc();
+		await raffleProgram.methods
+			.setRefundWindow(new BN(0), insuranceFund.publicKey)
+			.rpc();
+
+		const raffleAddress = (index: number) =>
+			PublicKey.findProgramAddressSync(
+				[Buffer.from("raffle"), new Uint8Array(new BN(index).toArray("le", 8))],
+				raffleProgram.programId,
+			)[0];
+		const bondAddress = (raffle: PublicKey) =>
+			PublicKey.findProgramAddressSync(
+				[Buffer.from("creator_bond"), raffle.toBytes()],
+				raffleProgram.programId,
+			)[0];
+
+		const createRaffle = (creatorBond: PublicKey | null) => {
+			const creationTime = client.getClock().unixTimestamp;
+			return raffleProgram.methods
+				.createRaffle(
+					"https://www.example.org",
+					new BN(0.1 * LAMPORTS_PER_SOL),
+					new BN((creationTime + BigInt(3601)).toString()),
+					new BN(1),
+					null,
+					null,
+					false,
+					null,
+					false,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+				)
+				.accounts({ creatorBond })
+				.rpc();
+		};
+
+		const raffleAccountId = raffleAddress(0);
+		const creatorBondId = bondAddress(raffleAccountId);
+		await createRaffle(creatorBondId);
+
+		const setState = async (raffleState: object) => {
+			const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+			const raffleData = await raffleProgram.coder.accounts.encode("raffle", {
+				...raffle,
+				raffleState,
+			});
+			provider.client.setAccount(raffleAccountId, {
+				executable: false,
+				owner: raffleProgram.programId,
+				lamports: 1 * LAMPORTS_PER_SOL,
+				data: raffleData,
+			});
+		};
+
+		const release = (upgradeAuthority: Keypair | null = null) =>
+			raffleProgram.methods
+				.releaseCreatorBond()
+				.accounts({
+					raffle: raffleAccountId,
+					depositor: provider.publicKey,
+					upgradeAuthority: upgradeAuthority?.publicKey ?? null,
+				})
+				.signers(upgradeAuthority ? [upgradeAuthority] : [])
+				.rpc();
+
+		const slash = (destination: PublicKey) =>
+			raffleProgram.methods
+				.slashCreatorBond()
+				.accounts({ raffle: raffleAccountId, destination })
+				.rpc();
+
+		return {
+			provider,
+			raffleProgram,
+			insuranceFund,
+			raffleAddress,
+			bondAddress,
+			createRaffle,
+			raffleAccountId,
+			creatorBondId,
+			setState,
+			release,
+			slash,
+		};
+	};
+
+	it("should hold the bond deposited at creation", async () => {
+		const { provider, raffleProgram, raffleAccountId, creatorBondId } =
+			await setup();
+
+		const bond = await raffleProgram.account.creatorBond.fetch(creatorBondId);
+		expect(bond.raffle.equals(raffleAccountId)).toBeTrue();
+		expect(bond.depositor.equals(provider.publicKey)).toBeTrue();
+		expect(bond.amount.toNumber()).toBe(LAMPORTS_PER_SOL);
+		expect(provider.client.getBalance(creatorBondId)!).toBeGreaterThan(
+			BigInt(LAMPORTS_PER_SOL),
+		);
+	});
+
+	it("should fail to create a raffle without the bond", async () => {
+		const { raffleAddress, bondAddress, createRaffle } = await setup();
+
+		expect(createRaffle(null)).rejects.toThrow(/CreatorBondRequired/);
+		// The bond must be at the raffle's PDA
+		expect(createRaffle(bondAddress(raffleAddress(0)))).rejects.toThrow(
+			/InvalidCreatorBond/,
+		);
+	});
+
+	it("should return the bond once the raffle is fulfilled", async () => {
+		const { provider, creatorBondId, setState, release } = await setup();
+
+		expect(release()).rejects.toThrow(/CreatorBondLocked/);
+
+		await setState({ claimed: {} });
+		const balanceBefore = provider.client.getBalance(provider.publicKey)!;
+		await release();
+
+		expect(provider.client.getAccount(creatorBondId)).toBeNull();
+		expect(
+			provider.client.getBalance(provider.publicKey)! - balanceBefore,
+		).toBeGreaterThan(BigInt(LAMPORTS_PER_SOL - 10_000));
+	});
+
+	it("should slash the bond of a voided raffle to the insurance fund", async () => {
+		const {
+			provider,
+			raffleProgram,
+			insuranceFund,
+			raffleAccountId,
+			creatorBondId,
+			release,
+			slash,
+		} = await setup();
+
+		await raffleProgram.methods
+			.forceExpire()
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		// Only the upgrade authority rules on the bond of a voided raffle
+		expect(release(new Keypair())).rejects.toThrow(/CreatorBondLocked/);
+		expect(slash(provider.publicKey)).rejects.toThrow(
+			/InvalidForfeitureDestination/,
+		);
+		await slash(insuranceFund.publicKey);
+
+		expect(provider.client.getAccount(creatorBondId)).toBeNull();
+		expect(provider.client.getBalance(insuranceFund.publicKey)!).toBeGreaterThan(
+			BigInt(LAMPORTS_PER_SOL),
+		);
+	});
+});
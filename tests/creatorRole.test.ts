@@ -0,0 +1,143 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("creator_role", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creatorRoleId = (creator: PublicKey) =>
+			PublicKey.findProgramAddressSync(
+				[Buffer.from("creator_role"), creator.toBytes()],
+				raffleProgram.programId,
+			)[0];
+		const raffleId = (index: number) =>
+			PublicKey.findProgramAddressSync(
+				[Buffer.from("raffle"), new Uint8Array(new BN(index).toArray("le", 8))],
+				raffleProgram.programId,
+			)[0];
+
+		// Allow the creator one active raffle, with tickets of up to 1 SOL
+		const creator = new Keypair();
+		provider.client.airdrop(creator.publicKey, BigInt(LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.grantCreatorRole(creator.publicKey, 1, new BN(LAMPORTS_PER_SOL))
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		const createRaffle = (signer: Keypair, ticketPrice: number) =>
+			raffleProgram.methods
+				.createRaffle(
+					"https://www.example.org",
+					new BN(ticketPrice * LAMPORTS_PER_SOL),
+					new BN((creationTime + BigInt(3601)).toString()),
+					new BN(1),
+					null,
+					null,
+					false,
+					null,
+					false,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+				)
+				.accounts({
+					creator: signer.publicKey,
+					creatorRole: creatorRoleId(signer.publicKey),
+				})
+				.signers([signer])
+				.rpc();
+
+		const extendRaffle = (signer: Keypair, raffle: PublicKey) =>
+			raffleProgram.methods
+				.extendRaffle(new BN((creationTime + BigInt(7200)).toString()))
+				.accounts({
+					raffle,
+					authority: signer.publicKey,
+					creatorRole: creatorRoleId(signer.publicKey),
+				})
+				.signers([signer])
+				.rpc();
+
+		return {
+			raffleProgram,
+			creator,
+			creatorRoleId,
+			raffleId,
+			createRaffle,
+			extendRaffle,
+		};
+	};
+
+	it("should let creators create raffles within the limits of their role", async () => {
+		const { raffleProgram, creator, creatorRoleId, raffleId, createRaffle } =
+			await setup();
+
+		expect(createRaffle(creator, 2)).rejects.toThrow(
+			/CreatorTicketPriceTooHigh/,
+		);
+		await createRaffle(creator, 0.5);
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleId(0));
+		expect(raffle.creator?.equals(creator.publicKey)).toBeTrue();
+		expect(raffle.rentPayer.equals(creator.publicKey)).toBeTrue();
+		const creatorRole = await raffleProgram.account.creatorRole.fetch(
+			creatorRoleId(creator.publicKey),
+		);
+		expect(creatorRole.activeRaffles).toBe(1);
+
+		expect(createRaffle(creator, 0.5)).rejects.toThrow(
+			/CreatorRaffleLimitReached/,
+		);
+	});
+
+	it("should only let creators manage their own raffles", async () => {
+		const { raffleProgram, creator, raffleId, createRaffle, extendRaffle } =
+			await setup();
+		await raffleProgram.methods.setExtensionLimits(1, new BN(86400)).rpc();
+		await createRaffle(creator, 0.5);
+
+		const other = new Keypair();
+		await raffleProgram.methods
+			.grantCreatorRole(other.publicKey, 1, new BN(LAMPORTS_PER_SOL))
+			.rpc();
+		expect(extendRaffle(other, raffleId(0))).rejects.toThrow(
+			/NotRaffleCreator/,
+		);
+
+		await extendRaffle(creator, raffleId(0));
+		const raffle = await raffleProgram.account.raffle.fetch(raffleId(0));
+		expect(raffle.extensionCount).toBe(1);
+	});
+
+	it("should fail to create raffles once the role is revoked", async () => {
+		const { raffleProgram, creator, creatorRoleId, createRaffle } =
+			await setup();
+
+		await raffleProgram.methods
+			.revokeCreatorRole()
+			.accounts({ creatorRole: creatorRoleId(creator.publicKey) })
+			.rpc();
+
+		expect(createRaffle(creator, 0.5)).rejects.toThrow();
+	});
+});
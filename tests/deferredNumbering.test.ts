@@ -0,0 +1,202 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+const UNNUMBERED_TICKET_START = "18446744073709551615";
+
+describe("deferred_numbering", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(1),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		const buy = async (seed: number, ticketCount: number) => {
+			const buyer = new Keypair();
+			provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+			await raffleProgram.methods
+				.initTicketBalance(null)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+			const entrySeed = new Uint8Array([seed, 0, 0, 0, 0, 0, 0, 0]);
+			await raffleProgram.methods
+				.buyTickets(new BN(ticketCount), Array.from(entrySeed), null, null, null, null, false, null)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+			return PublicKey.findProgramAddressSync(
+				[Buffer.from("entry"), raffleAccountId.toBytes(), entrySeed],
+				raffleProgram.programId,
+			)[0];
+		};
+
+		const endRaffle = () => {
+			const newClock = client.getClock();
+			newClock.unixTimestamp = creationTime + BigInt(3601);
+			client.setClock(newClock);
+		};
+
+		const finalizeNumbering = (entryIds: PublicKey[]) =>
+			raffleProgram.methods
+				.finalizeNumbering()
+				.accounts({ raffle: raffleAccountId })
+				.remainingAccounts(
+					entryIds.map((pubkey) => ({
+						pubkey,
+						isSigner: false,
+						isWritable: true,
+					})),
+				)
+				.rpc();
+
+		const draw = () =>
+			raffleProgram.methods
+				.drawWinningTicket()
+				.accounts({
+					raffle: raffleAccountId,
+					recentSlothashes: new PublicKey(
+						"SysvarS1otHashes111111111111111111111111111",
+					),
+				})
+				.rpc();
+
+		return {
+			raffleProgram,
+			raffleAccountId,
+			buy,
+			endRaffle,
+			finalizeNumbering,
+			draw,
+		};
+	};
+
+	it("should hide ticket positions during the sale and number entries in creation order", async () => {
+		const {
+			raffleProgram,
+			raffleAccountId,
+			buy,
+			endRaffle,
+			finalizeNumbering,
+			draw,
+		} = await setup();
+
+		await raffleProgram.methods
+			.setDeferredNumbering(true)
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		const entryIds = [await buy(0, 3), await buy(1, 5), await buy(2, 2)];
+		for (const [i, entryId] of entryIds.entries()) {
+			const entry = await raffleProgram.account.entry.fetch(entryId);
+			expect(entry.ticketStartIndex.toString()).toBe(UNNUMBERED_TICKET_START);
+			expect(entry.entryIndex.toNumber()).toBe(i);
+		}
+
+		endRaffle();
+
+		// The raffle can't be drawn until every entry is numbered
+		expect(draw()).rejects.toThrow(/NumberingNotFinalized/);
+
+		// Entries must be numbered in creation order, across as many batches as needed
+		expect(finalizeNumbering([entryIds[1] as PublicKey])).rejects.toThrow(
+			/EntryOutOfOrder/,
+		);
+		await finalizeNumbering(entryIds.slice(0, 2));
+		expect(draw()).rejects.toThrow(/NumberingNotFinalized/);
+		await finalizeNumbering(entryIds.slice(2));
+
+		const starts = await Promise.all(
+			entryIds.map(async (entryId) =>
+				(await raffleProgram.account.entry.fetch(entryId)).ticketStartIndex.toNumber(),
+			),
+		);
+		expect(starts).toEqual([0, 3, 8]);
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.numberedEntries.toNumber()).toBe(3);
+		expect(raffle.numberedTickets.toNumber()).toBe(10);
+
+		await draw();
+		const drawn = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(drawn.raffleState.drawing).toBeDefined();
+	});
+
+	it("should fail to finalize numbering before the sale closed", async () => {
+		const { raffleProgram, raffleAccountId, buy, finalizeNumbering } =
+			await setup();
+
+		await raffleProgram.methods
+			.setDeferredNumbering(true)
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+		const entryId = await buy(0, 1);
+
+		expect(finalizeNumbering([entryId])).rejects.toThrow(/RaffleNotEnded/);
+	});
+
+	it("should fail to finalize numbering of a raffle numbering tickets at purchase", async () => {
+		const { raffleProgram, raffleAccountId, buy, endRaffle, finalizeNumbering } =
+			await setup();
+
+		const entryId = await buy(0, 1);
+		const entry = await raffleProgram.account.entry.fetch(entryId);
+		expect(entry.ticketStartIndex.toNumber()).toBe(0);
+		endRaffle();
+
+		expect(finalizeNumbering([entryId])).rejects.toThrow(
+			/NumberingNotDeferred/,
+		);
+	});
+
+	it("should fail to defer numbering once tickets were sold", async () => {
+		const { raffleProgram, raffleAccountId, buy } = await setup();
+
+		await buy(0, 1);
+
+		expect(
+			raffleProgram.methods
+				.setDeferredNumbering(true)
+				.accounts({ raffle: raffleAccountId })
+				.rpc(),
+		).rejects.toThrow(/PrizeInfoLocked/);
+	});
+});
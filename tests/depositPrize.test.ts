@@ -0,0 +1,154 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("deposit_prize", async () => {
+	const setup = async (requirePrizeDeposit: boolean) => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		if (requirePrizeDeposit) {
+			await raffleProgram.methods.setPrizeDepositRequired(true).rpc();
+		}
+
+		const creationTime = client.getClock().unixTimestamp;
+		const metadataUri = "https://www.example.org";
+		const ticketPrice = new BN(0.1 * LAMPORTS_PER_SOL);
+		const minTickets = new BN(1);
+		const endTime = new BN((creationTime + BigInt(3601)).toString());
+
+		// Create raffle
+		await raffleProgram.methods
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+		const prizeVaultId = PublicKey.findProgramAddressSync(
+			[Buffer.from("prize_vault"), raffleAccountId.toBytes()],
+			raffleProgram.programId,
+		)[0];
+
+		return { provider, raffleProgram, raffleAccountId, prizeVaultId };
+	};
+
+	it("should keep the raffle closed for sales until the prize is deposited", async () => {
+		const { provider, raffleProgram, raffleAccountId, prizeVaultId } =
+			await setup(true);
+
+		let raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.raffleState.awaitingPrize).toBeDefined();
+
+		// Ticket balances can't be created before the raffle opens
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+		expect(
+			raffleProgram.methods
+				.initTicketBalance(null)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc(),
+		).rejects.toThrow(/RaffleNotOpen/);
+
+		const prizeAmount = new BN(2 * LAMPORTS_PER_SOL);
+		await raffleProgram.methods
+			.depositPrize(prizeAmount)
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.raffleState.open).toBeDefined();
+
+		const prizeVault =
+			await raffleProgram.account.prizeVault.fetch(prizeVaultId);
+		expect(prizeVault.raffle.equals(raffleAccountId)).toBeTrue();
+		expect(prizeVault.amount.toString()).toEqual(prizeAmount.toString());
+
+		const vaultRent = provider.client.minimumBalanceForRentExemption(
+			BigInt(raffleProgram.account.prizeVault.size),
+		);
+		expect(provider.client.getBalance(prizeVaultId)).toEqual(
+			vaultRent + BigInt(prizeAmount.toString()),
+		);
+
+		// Buying tickets is possible now
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+	});
+
+	it("should open raffles immediately when no prize deposit is required", async () => {
+		const { raffleProgram, raffleAccountId } = await setup(false);
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.raffleState.open).toBeDefined();
+	});
+
+	it("should fail to release the prize before the raffle is finalized", async () => {
+		const { raffleProgram, raffleAccountId } = await setup(true);
+
+		await raffleProgram.methods
+			.depositPrize(new BN(LAMPORTS_PER_SOL))
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		expect(
+			raffleProgram.methods
+				.releasePrize()
+				.accounts({ raffle: raffleAccountId })
+				.rpc(),
+		).rejects.toThrow(/RaffleNotFinalized/);
+	});
+
+	it("should fail to deposit a prize when not signed by the management authority", async () => {
+		const { provider, raffleProgram, raffleAccountId } = await setup(true);
+
+		const account = new Keypair();
+		provider.client.airdrop(account.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+
+		expect(
+			raffleProgram.methods
+				.depositPrize(new BN(LAMPORTS_PER_SOL))
+				.accountsPartial({
+					raffle: raffleAccountId,
+					managementAuthority: account.publicKey,
+				})
+				.signers([account])
+				.rpc(),
+		).rejects.toThrow(/NotProgramManagementAuthority/);
+	});
+});
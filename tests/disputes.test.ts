@@ -0,0 +1,217 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("disputes", async () => {
+	const recentSlothashes = new PublicKey(
+		"SysvarS1otHashes111111111111111111111111111",
+	);
+	const reasonHash = Array.from(new Uint8Array(32).fill(7));
+
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		client.withSysvars();
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(1),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+		await raffleProgram.methods
+			.buyTickets(
+				new BN(5),
+				Array.from(new Uint8Array(8)),
+				null,
+				null,
+				null,
+				null,
+				false,
+				null,
+			)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+
+		// Set time so that the raffle has ended, and draw
+		const newClock = client.getClock();
+		newClock.unixTimestamp = creationTime + BigInt(3601);
+		client.setClock(newClock);
+		await raffleProgram.methods
+			.drawWinningTicket()
+			.accounts({ raffle: raffleAccountId, recentSlothashes })
+			.rpc();
+
+		const disputer = new Keypair();
+		provider.client.airdrop(disputer.publicKey, BigInt(LAMPORTS_PER_SOL));
+		const disputeAccountId = PublicKey.findProgramAddressSync(
+			[
+				Buffer.from("dispute"),
+				raffleAccountId.toBuffer(),
+				disputer.publicKey.toBuffer(),
+			],
+			raffleProgram.programId,
+		)[0];
+
+		const openDispute = () =>
+			raffleProgram.methods
+				.openDispute(reasonHash)
+				.accounts({ raffle: raffleAccountId, disputer: disputer.publicKey })
+				.signers([disputer])
+				.rpc();
+		const resolveDispute = (upheld: boolean) =>
+			raffleProgram.methods
+				.resolveDispute(upheld)
+				.accounts({
+					raffle: raffleAccountId,
+					disputer: disputer.publicKey,
+					destination: provider.publicKey,
+				})
+				.rpc();
+
+		return {
+			client,
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			disputer,
+			disputeAccountId,
+			openDispute,
+			resolveDispute,
+		};
+	};
+
+	it("should hold the bond and refund it when the dispute is upheld", async () => {
+		const {
+			client,
+			raffleProgram,
+			raffleAccountId,
+			disputer,
+			disputeAccountId,
+			openDispute,
+			resolveDispute,
+		} = await setup();
+
+		await openDispute();
+
+		const dispute =
+			await raffleProgram.account.dispute.fetch(disputeAccountId);
+		expect(dispute.raffle).toEqual(raffleAccountId);
+		expect(dispute.disputer).toEqual(disputer.publicKey);
+		expect(dispute.amount.toNumber()).toBe(0.1 * LAMPORTS_PER_SOL);
+		expect(dispute.reasonHash).toEqual(reasonHash);
+
+		// Only one dispute per wallet and raffle
+		await expect(openDispute()).rejects.toThrow();
+
+		const disputerBalance = client.getBalance(disputer.publicKey) as bigint;
+		const disputeLamports = client.getBalance(disputeAccountId) as bigint;
+		await resolveDispute(true);
+
+		// The bond and the rent are returned to the disputer
+		expect(client.getAccount(disputeAccountId)).toBeNull();
+		expect(client.getBalance(disputer.publicKey)).toBe(
+			disputerBalance + disputeLamports,
+		);
+	});
+
+	it("should slash the bond when the dispute is rejected", async () => {
+		const {
+			client,
+			provider,
+			disputer,
+			disputeAccountId,
+			openDispute,
+			resolveDispute,
+		} = await setup();
+
+		await openDispute();
+		const disputerBalance = client.getBalance(disputer.publicKey) as bigint;
+		const disputeLamports = client.getBalance(disputeAccountId) as bigint;
+		const destinationBalance = client.getBalance(provider.publicKey) as bigint;
+		await resolveDispute(false);
+
+		// The bond is slashed to the payout authority, and only the rent returned
+		const bond = BigInt(0.1 * LAMPORTS_PER_SOL);
+		expect(client.getBalance(disputer.publicKey)).toBe(
+			disputerBalance + disputeLamports - bond,
+		);
+		expect(client.getBalance(provider.publicKey)).toBe(
+			destinationBalance + bond - BigInt(5000),
+		);
+	});
+
+	it("should fail once the dispute window has closed", async () => {
+		const { client, openDispute } = await setup();
+
+		const newClock = client.getClock();
+		newClock.unixTimestamp += BigInt(24 * 60 * 60 + 1);
+		client.setClock(newClock);
+
+		expect(openDispute()).rejects.toThrow(/DisputeWindowClosed/);
+	});
+
+	it("should fail when not resolved by the management authority", async () => {
+		const { provider, raffleProgram, raffleAccountId, disputer, openDispute } =
+			await setup();
+
+		await openDispute();
+
+		const account = new Keypair();
+		provider.client.airdrop(account.publicKey, BigInt(LAMPORTS_PER_SOL));
+		expect(
+			raffleProgram.methods
+				.resolveDispute(false)
+				.accounts({
+					raffle: raffleAccountId,
+					disputer: disputer.publicKey,
+					destination: provider.publicKey,
+					managementAuthority: account.publicKey,
+				})
+				.signers([account])
+				.rpc(),
+		).rejects.toThrow(/NotProgramManagementAuthority/);
+	});
+});
@@ -0,0 +1,141 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("draw_delay", async () => {
+	const recentSlothashes = new PublicKey(
+		"SysvarS1otHashes111111111111111111111111111",
+	);
+
+	const setup = async (drawDelaySlots = 20) => {
+		const client = fromWorkspace(".");
+		client.withSysvars();
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(1),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		if (drawDelaySlots > 0) {
+			await raffleProgram.methods
+				.setDrawDelay(drawDelaySlots)
+				.accounts({ raffle: raffleAccountId })
+				.rpc();
+		}
+
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+		await raffleProgram.methods
+			.buyTickets(new BN(5), Array.from(new Uint8Array(8)), null, null, null, null, false, null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+
+		// Set time so that the raffle has ended
+		const newClock = client.getClock();
+		newClock.unixTimestamp = creationTime + BigInt(3601);
+		client.setClock(newClock);
+
+		const commitDrawSlot = () =>
+			raffleProgram.methods
+				.commitDrawSlot()
+				.accounts({ raffle: raffleAccountId, recentSlothashes })
+				.rpc();
+		const drawWinningTicket = () =>
+			raffleProgram.methods
+				.drawWinningTicket()
+				.accounts({ raffle: raffleAccountId, recentSlothashes })
+				.rpc();
+
+		return {
+			client,
+			raffleProgram,
+			raffleAccountId,
+			commitDrawSlot,
+			drawWinningTicket,
+		};
+	};
+
+	it("should commit a draw slot within the delay window", async () => {
+		const { client, raffleProgram, raffleAccountId, commitDrawSlot } =
+			await setup();
+
+		const commitSlot = client.getClock().slot;
+		await commitDrawSlot();
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.drawDelaySlots).toBe(20);
+		const target = BigInt(raffle.drawTargetSlot.toString());
+		expect(target >= commitSlot + BigInt(1)).toBeTrue();
+		expect(target <= commitSlot + BigInt(21)).toBeTrue();
+
+		// The committed slot can't be rerolled while it can still be drawn from
+		expect(commitDrawSlot()).rejects.toThrow(/DrawSlotAlreadyCommitted/);
+	});
+
+	it("should not draw before the committed slot is reached", async () => {
+		const { commitDrawSlot, drawWinningTicket } = await setup();
+
+		expect(drawWinningTicket()).rejects.toThrow(/DrawSlotNotCommitted/);
+		await commitDrawSlot();
+		expect(drawWinningTicket()).rejects.toThrow(/DrawSlotNotReached/);
+	});
+
+	it("should fail to commit a draw slot without a draw delay", async () => {
+		const { commitDrawSlot } = await setup(0);
+
+		expect(commitDrawSlot()).rejects.toThrow(/DrawDelayNotEnabled/);
+	});
+
+	it("should fail to set a delay beyond the maximum", async () => {
+		const { raffleProgram, raffleAccountId } = await setup(0);
+
+		expect(
+			raffleProgram.methods
+				.setDrawDelay(301)
+				.accounts({ raffle: raffleAccountId })
+				.rpc(),
+		).rejects.toThrow(/InvalidDrawDelay/);
+	});
+});
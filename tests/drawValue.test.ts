@@ -0,0 +1,147 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { LiteSVM } from "litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+const SWITCHBOARD_ON_DEMAND_PROGRAM_ID = new PublicKey(
+	"SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv",
+);
+const PULL_FEED_DISCRIMINATOR = [196, 27, 108, 196, 10, 215, 219, 40];
+
+// Writes a Switchboard pull feed whose latest result is `price`, scaled by 1e18
+const setPriceFeed = (
+	client: LiteSVM,
+	address: PublicKey,
+	price: bigint,
+	slot: bigint,
+) => {
+	const data = Buffer.alloc(8 + 3200);
+	Buffer.from(PULL_FEED_DISCRIMINATOR).copy(data, 0);
+	data.writeBigUInt64LE(price & BigInt("0xffffffffffffffff"), 8 + 2256);
+	data.writeBigInt64LE(price >> BigInt(64), 8 + 2264);
+	data.writeBigUInt64LE(slot, 8 + 2256 + 104);
+	client.setAccount(address, {
+		executable: false,
+		owner: SWITCHBOARD_ON_DEMAND_PROGRAM_ID,
+		lamports: LAMPORTS_PER_SOL,
+		data,
+	});
+};
+
+describe("record_draw_value", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(1),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		// Sell 25 tickets, 2.5 SOL
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(3 * LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+		await raffleProgram.methods
+			.buyTickets(new BN(25), [1, 0, 0, 0, 0, 0, 0, 0], null, null, null, null, false, null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+
+		const newClock = client.getClock();
+		newClock.unixTimestamp = creationTime + BigInt(3601);
+		client.setClock(newClock);
+		await raffleProgram.methods
+			.drawWinningTicket()
+			.accounts({
+				raffle: raffleAccountId,
+				recentSlothashes: new PublicKey(
+					"SysvarS1otHashes111111111111111111111111111",
+				),
+			})
+			.rpc();
+
+		// SOL at $150.123456
+		const priceFeed = new Keypair().publicKey;
+		setPriceFeed(
+			client,
+			priceFeed,
+			BigInt("150123456000000000000"),
+			client.getClock().slot,
+		);
+		await raffleProgram.methods.setUsdPriceFeed(priceFeed).rpc();
+
+		const recordDrawValue = (feed = priceFeed) =>
+			raffleProgram.methods
+				.recordDrawValue()
+				.accounts({ raffle: raffleAccountId, priceFeed: feed })
+				.rpc();
+
+		return { client, raffleProgram, raffleAccountId, priceFeed, recordDrawValue };
+	};
+
+	it("should snapshot the lamport and USD value of the drawn raffle", async () => {
+		const { raffleProgram, raffleAccountId, recordDrawValue } = await setup();
+
+		await recordDrawValue();
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.drawValueLamports.toNumber()).toBe(2.5 * LAMPORTS_PER_SOL);
+		expect(raffle.drawUsdRate?.toNumber()).toBe(150_123_456);
+
+		// The value can't be replaced by a later price
+		expect(recordDrawValue()).rejects.toThrow(/DrawValueAlreadyRecorded/);
+	});
+
+	it("should only read the configured price feed", async () => {
+		const { client, recordDrawValue } = await setup();
+
+		const otherFeed = new Keypair().publicKey;
+		setPriceFeed(
+			client,
+			otherFeed,
+			BigInt("300000000000000000000"),
+			client.getClock().slot,
+		);
+		expect(recordDrawValue(otherFeed)).rejects.toThrow(/InvalidPriceFeed/);
+	});
+});
@@ -59,7 +59,24 @@ describe("draw_winning_ticket", async () => {
 
 			// Create raffle
 			await raffleProgram.methods
-				.createRaffle(metadataUri, ticketPrice, endTime, minTickets, maxTickets)
+				.createRaffle(
+					metadataUri,
+					ticketPrice,
+					endTime,
+					minTickets,
+					maxTickets,
+					null,
+					false,
+					null,
+					false,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+				)
 				.rpc();
 			const raffleAccountId = PublicKey.findProgramAddressSync(
 				[
@@ -96,7 +113,7 @@ describe("draw_winning_ticket", async () => {
 
 				// Create ticket balance
 				await raffleProgram.methods
-					.initTicketBalance()
+					.initTicketBalance(null)
 					.accounts({
 						signer: buyer.publicKey,
 						raffle: new PublicKey(raffleAccountId),
@@ -110,7 +127,7 @@ describe("draw_winning_ticket", async () => {
 
 				// Purchase tickets
 				await raffleProgram.methods
-					.buyTickets(amountToPurchase, Array.from(entrySeed))
+					.buyTickets(amountToPurchase, Array.from(entrySeed), null, null, null, null, false, null)
 					.accounts({
 						signer: buyer.publicKey,
 						raffle: new PublicKey(raffleAccountId),
@@ -140,6 +157,7 @@ describe("draw_winning_ticket", async () => {
 				await raffleProgram.account.raffle.fetch(raffleAccountId);
 
 			expect(raffleAccount.raffleState.drawing).toBeDefined();
+			expect(raffleAccount.closeReason).toBe(1); // Time elapsed
 			expect(raffleAccount.raffleState.open).toBeUndefined();
 			expect(raffleAccount.raffleState.drawn).toBeUndefined();
 			expect(raffleAccount.raffleState.expired).toBeUndefined();
@@ -149,6 +167,15 @@ describe("draw_winning_ticket", async () => {
 			const totalTickets = input.numBuyers * input.ticketsPerBuyer;
 			expect(raffleAccount.winningTicket?.lt(new BN(totalTickets))).toBeTrue();
 			expect(raffleAccount.winningTicket?.gte(new BN(0))).toBeTrue();
+
+			// Validate the entropy inputs used for the draw were recorded
+			expect(raffleAccount.drawSlot).not.toBeNull();
+			expect(raffleAccount.drawSlotHash).toHaveLength(32);
+			expect(
+				raffleAccount.drawTimestamp?.eq(
+					new BN(newClock.unixTimestamp.toString()),
+				),
+			).toBeTrue();
 		}
 	});
 
@@ -202,7 +229,24 @@ describe("draw_winning_ticket", async () => {
 
 			// Create raffle
 			await raffleProgram.methods
-				.createRaffle(metadataUri, ticketPrice, endTime, minTickets, maxTickets)
+				.createRaffle(
+					metadataUri,
+					ticketPrice,
+					endTime,
+					minTickets,
+					maxTickets,
+					null,
+					false,
+					null,
+					false,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+				)
 				.rpc();
 			const raffleAccountId = PublicKey.findProgramAddressSync(
 				[
@@ -239,7 +283,7 @@ describe("draw_winning_ticket", async () => {
 
 				// Create ticket balance
 				await raffleProgram.methods
-					.initTicketBalance()
+					.initTicketBalance(null)
 					.accounts({
 						signer: buyer.publicKey,
 						raffle: new PublicKey(raffleAccountId),
@@ -253,7 +297,7 @@ describe("draw_winning_ticket", async () => {
 
 				// Purchase tickets
 				await raffleProgram.methods
-					.buyTickets(amountToPurchase, Array.from(entrySeed))
+					.buyTickets(amountToPurchase, Array.from(entrySeed), null, null, null, null, false, null)
 					.accounts({
 						signer: buyer.publicKey,
 						raffle: new PublicKey(raffleAccountId),
@@ -278,6 +322,7 @@ describe("draw_winning_ticket", async () => {
 				await raffleProgram.account.raffle.fetch(raffleAccountId);
 
 			expect(raffleAccount.raffleState.drawing).toBeDefined();
+			expect(raffleAccount.closeReason).toBe(2); // Sold out
 			expect(raffleAccount.raffleState.open).toBeUndefined();
 			expect(raffleAccount.raffleState.drawn).toBeUndefined();
 			expect(raffleAccount.raffleState.expired).toBeUndefined();
@@ -290,6 +335,100 @@ describe("draw_winning_ticket", async () => {
 		}
 	});
 
+	it("should set the winner in the same transaction when the winning entry is passed", async () => {
+		const client = fromWorkspace(".");
+		client.withSysvars();
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(1),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		// Sell three entries of two tickets each
+		const entries: PublicKey[] = [];
+		for (const seed of [1, 2, 3]) {
+			const buyer = new Keypair();
+			provider.client.airdrop(buyer.publicKey, BigInt(LAMPORTS_PER_SOL));
+			await raffleProgram.methods
+				.initTicketBalance(null)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+			const entrySeed = new Uint8Array([seed, 0, 0, 0, 0, 0, 0, 0]);
+			await raffleProgram.methods
+				.buyTickets(new BN(2), Array.from(entrySeed), null, null, null, null, false, null)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+			entries.push(
+				PublicKey.findProgramAddressSync(
+					[Buffer.from("entry"), raffleAccountId.toBytes(), entrySeed],
+					raffleProgram.programId,
+				)[0],
+			);
+		}
+
+		// Set time so that the raffle has ended
+		const newClock = client.getClock();
+		newClock.unixTimestamp = creationTime + BigInt(3601);
+		client.setClock(newClock);
+
+		// Draw with every entry passed, so the winning entry is among them
+		await raffleProgram.methods
+			.drawWinningTicket()
+			.accounts({
+				raffle: raffleAccountId,
+				recentSlothashes: new PublicKey(
+					"SysvarS1otHashes111111111111111111111111111",
+				),
+			})
+			.remainingAccounts(
+				entries.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false })),
+			)
+			.rpc();
+
+		const raffleAccount = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffleAccount.raffleState.drawn).toBeDefined();
+
+		const winningEntry = await raffleProgram.account.entry.fetch(
+			entries[Math.floor(raffleAccount.winningTicket!.toNumber() / 2)],
+		);
+		expect(raffleAccount.winnerAddress?.equals(winningEntry.owner)).toBeTrue();
+	});
+
 	it("should fail when the current raffle is not in open state", async () => {
 		const client = fromWorkspace(".");
 		client.withSysvars();
@@ -323,7 +462,24 @@ describe("draw_winning_ticket", async () => {
 
 			// Create raffle, so that defaults are set, PDAs are created, etc.
 			await raffleProgram.methods
-				.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+				.createRaffle(
+					metadataUri,
+					ticketPrice,
+					endTime,
+					minTickets,
+					null,
+					null,
+					false,
+					null,
+					false,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+				)
 				.rpc();
 			const raffleAccountId = PublicKey.findProgramAddressSync(
 				[
@@ -362,7 +518,7 @@ describe("draw_winning_ticket", async () => {
 
 			// Init ticket balance before setting the raffle into an non-open state
 			await raffleProgram.methods
-				.initTicketBalance()
+				.initTicketBalance(null)
 				.accounts({
 					signer: buyer.publicKey,
 					raffle: raffleAccountId,
@@ -385,6 +541,57 @@ describe("draw_winning_ticket", async () => {
 				winnerAddress: null,
 				winningTicket: null,
 				maxTickets: null,
+				participantCount: new BN(0),
+				eventSeq: new BN(0),
+				minRevenue: null,
+				minDrawSlot: new BN(0),
+				reservedTickets: new BN(0),
+				refundFeeBps: 0,
+				rentPayer: provider.publicKey,
+				features: 0,
+				allowedRegions: new BN(0),
+				ageRestricted: false,
+				drawAuthority: null,
+				priorityMinTier: 0,
+				priorityUntil: new BN(0),
+				minUniqueBuyers: null,
+				paymentMint: null,
+				randomnessMode: { slotHashes: {} },
+				randomnessAccount: null,
+				randomnessCommitSlot: new BN(0),
+				prizeEscrowed: false,
+				winnerCount: 1,
+				winningTickets: [],
+				additionalWinners: [],
+				winnerDataSubmitted: 0,
+				prizesClaimed: 0,
+				maxTicketsPerWallet: null,
+				gate: null,
+				deferredNumbering: false,
+				entryCount: new BN(0),
+				numberedEntries: new BN(0),
+				numberedTickets: new BN(0),
+				feeBpsOverride: null,
+				snapshotRoot: null,
+				operatorStatus: 0,
+				statusNoteHash: null,
+				fundingDeadline: null,
+				pricingTiers: [],
+				tierDiscounts: new BN(0),
+				crankRewardBps: 0,
+				referralFeeBps: 0,
+				extensionCount: 0,
+				extendedSecs: new BN(0),
+				refundDeadline: new BN(0),
+				prizeValueUsdCents: null,
+				ticketCountDisplayCap: null,
+				ticketReceipts: false,
+				arweaveTxId: null,
+				creator: null,
+				drawDelaySlots: 0,
+				drawTargetSlot: new BN(0),
+				refundMint: null,
+				refundRate: new BN(0), drawValueLamports: new BN(0), drawUsdRate: null, successor: null, randomnessProvider: null, randomnessCommitment: null, accessCodeHash: null, closeReason: 0, crankRewardPaid: false, additionalWinnerHashes: [], voidedRanges: [], pendingReservations: new BN(0), openSlotReleased: false, campaignSubsidies: new BN(0),
 			});
 			provider.client.setAccount(raffleAccountId, {
 				executable: false,
@@ -439,7 +646,24 @@ describe("draw_winning_ticket", async () => {
 
 		// Create raffle
 		await raffleProgram.methods
-			.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
 			.rpc();
 		const raffleAccountId = PublicKey.findProgramAddressSync(
 			[
@@ -475,7 +699,7 @@ describe("draw_winning_ticket", async () => {
 
 		// Create ticket balance
 		await raffleProgram.methods
-			.initTicketBalance()
+			.initTicketBalance(null)
 			.accounts({
 				signer: buyer.publicKey,
 				raffle: new PublicKey(raffleAccountId),
@@ -489,7 +713,7 @@ describe("draw_winning_ticket", async () => {
 
 		// Purchase tickets
 		await raffleProgram.methods
-			.buyTickets(amountToPurchase, Array.from(entrySeed))
+			.buyTickets(amountToPurchase, Array.from(entrySeed), null, null, null, null, false, null)
 			.accounts({
 				signer: buyer.publicKey,
 				raffle: new PublicKey(raffleAccountId),
@@ -543,7 +767,24 @@ describe("draw_winning_ticket", async () => {
 
 		// Create raffle
 		await raffleProgram.methods
-			.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
 			.rpc();
 		const raffleAccountId = PublicKey.findProgramAddressSync(
 			[
@@ -605,7 +846,24 @@ describe("draw_winning_ticket", async () => {
 
 		// Create raffle
 		await raffleProgram.methods
-			.createRaffle(metadataUri, ticketPrice, endTime, minTickets, maxTickets)
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				maxTickets,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
 			.rpc();
 		const raffleAccountId = PublicKey.findProgramAddressSync(
 			[
@@ -666,7 +924,24 @@ describe("draw_winning_ticket", async () => {
 
 		// Create raffle
 		await raffleProgram.methods
-			.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
 			.rpc();
 		const raffleAccountId = PublicKey.findProgramAddressSync(
 			[
@@ -702,7 +977,7 @@ describe("draw_winning_ticket", async () => {
 
 		// Create ticket balance
 		await raffleProgram.methods
-			.initTicketBalance()
+			.initTicketBalance(null)
 			.accounts({
 				signer: buyer.publicKey,
 				raffle: new PublicKey(raffleAccountId),
@@ -716,7 +991,7 @@ describe("draw_winning_ticket", async () => {
 
 		// Purchase tickets
 		await raffleProgram.methods
-			.buyTickets(amountToPurchase, Array.from(entrySeed))
+			.buyTickets(amountToPurchase, Array.from(entrySeed), null, null, null, null, false, null)
 			.accounts({
 				signer: buyer.publicKey,
 				raffle: new PublicKey(raffleAccountId),
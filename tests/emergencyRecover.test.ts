@@ -0,0 +1,187 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("emergency_recover", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(1),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+		const recoveryRequestId = PublicKey.findProgramAddressSync(
+			[Buffer.from("recovery_request"), raffleAccountId.toBytes()],
+			raffleProgram.programId,
+		)[0];
+
+		// Buy 0.5 SOL worth of tickets
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+		await raffleProgram.methods
+			.buyTickets(new BN(5), Array.from(new Uint8Array(8)), null, null, null, null, false, null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+
+		const timeTravel = (seconds: number) => {
+			const newClock = client.getClock();
+			newClock.unixTimestamp = newClock.unixTimestamp + BigInt(seconds);
+			client.setClock(newClock);
+		};
+
+		return {
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			recoveryRequestId,
+			buyer,
+			timeTravel,
+		};
+	};
+
+	it("should expire the raffle once the timelock elapsed", async () => {
+		const {
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			recoveryRequestId,
+			buyer,
+			timeTravel,
+		} = await setup();
+
+		await raffleProgram.methods
+			.requestEmergencyRecovery()
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		const recoveryRequest =
+			await raffleProgram.account.recoveryRequest.fetch(recoveryRequestId);
+		expect(
+			recoveryRequest.executableAt.sub(recoveryRequest.requestedAt).toNumber(),
+		).toBe(72 * 60 * 60);
+
+		// The recovery can't be executed before the timelock elapsed
+		timeTravel(72 * 60 * 60 - 1);
+		expect(
+			raffleProgram.methods
+				.emergencyRecover()
+				.accounts({ raffle: raffleAccountId })
+				.rpc(),
+		).rejects.toThrow(/RecoveryTimelockActive/);
+
+		timeTravel(1);
+		await raffleProgram.methods
+			.emergencyRecover()
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.raffleState).toEqual({ expired: {} });
+		expect(provider.client.getAccount(recoveryRequestId)).toBeNull();
+
+		// Ticket holders can reclaim their funds
+		const buyerBalanceBefore = provider.client.getBalance(buyer.publicKey);
+		if (!buyerBalanceBefore) {
+			throw new Error("Failed to fetch balance");
+		}
+		await raffleProgram.methods
+			.reclaimExpiredTickets()
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+		expect(provider.client.getBalance(buyer.publicKey)).toBeGreaterThan(
+			buyerBalanceBefore + BigInt(0.5 * LAMPORTS_PER_SOL),
+		);
+	});
+
+	it("should not execute a cancelled recovery", async () => {
+		const {
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			recoveryRequestId,
+			timeTravel,
+		} = await setup();
+
+		await raffleProgram.methods
+			.requestEmergencyRecovery()
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+		await raffleProgram.methods
+			.cancelEmergencyRecovery()
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+		expect(provider.client.getAccount(recoveryRequestId)).toBeNull();
+
+		timeTravel(72 * 60 * 60);
+		expect(
+			raffleProgram.methods
+				.emergencyRecover()
+				.accounts({ raffle: raffleAccountId })
+				.rpc(),
+		).rejects.toThrow(/AccountNotInitialized/);
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.raffleState).toEqual({ open: {} });
+	});
+
+	it("should fail from accounts other than the upgrade authority", async () => {
+		const { provider, raffleProgram, raffleAccountId } = await setup();
+
+		const account = new Keypair();
+		provider.client.airdrop(account.publicKey, BigInt(LAMPORTS_PER_SOL));
+
+		expect(
+			raffleProgram.methods
+				.requestEmergencyRecovery()
+				.accounts({
+					raffle: raffleAccountId,
+					upgradeAuthority: account.publicKey,
+				})
+				.signers([account])
+				.rpc(),
+		).rejects.toThrow(/NotProgramUpgradeAuthority/);
+	});
+});
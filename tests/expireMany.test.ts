@@ -0,0 +1,169 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("expire_many", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+
+		// Creates a raffle with `ticketsBought` tickets sold and returns its raffle and
+		// treasury
+		const createRaffle = async (
+			raffleIndex: number,
+			minTickets: number,
+			ticketsBought: number,
+		) => {
+			await raffleProgram.methods
+				.createRaffle(
+					"https://www.example.org",
+					new BN(0.1 * LAMPORTS_PER_SOL),
+					new BN((creationTime + BigInt(3601)).toString()),
+					new BN(minTickets),
+					null,
+					null,
+					false,
+					null,
+					false,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+				)
+				.rpc();
+			const raffleId = PublicKey.findProgramAddressSync(
+				[
+					Buffer.from("raffle"),
+					new Uint8Array(new BN(raffleIndex).toArray("le", 8)),
+				],
+				raffleProgram.programId,
+			)[0];
+			const treasuryId = PublicKey.findProgramAddressSync(
+				[Buffer.from("treasury"), raffleId.toBytes()],
+				raffleProgram.programId,
+			)[0];
+
+			if (ticketsBought > 0) {
+				const buyer = new Keypair();
+				provider.client.airdrop(buyer.publicKey, BigInt(LAMPORTS_PER_SOL));
+				await raffleProgram.methods
+					.initTicketBalance(null)
+					.accounts({ signer: buyer.publicKey, raffle: raffleId })
+					.signers([buyer])
+					.rpc();
+				await raffleProgram.methods
+					.buyTickets(
+						new BN(ticketsBought),
+						Array.from(new Uint8Array(8)),
+						null,
+						null,
+						null,
+						null,
+						false,
+						null,
+					)
+					.accounts({ signer: buyer.publicKey, raffle: raffleId })
+					.signers([buyer])
+					.rpc();
+			}
+
+			return { raffleId, treasuryId };
+		};
+
+		// Time-travel to when the raffles end
+		const endRaffles = () => {
+			const newClock = client.getClock();
+			newClock.unixTimestamp = creationTime + BigInt(3602);
+			client.setClock(newClock);
+		};
+
+		return { raffleProgram, createRaffle, endRaffles };
+	};
+
+	const pairs = (raffles: { raffleId: PublicKey; treasuryId: PublicKey }[]) =>
+		raffles.flatMap(({ raffleId, treasuryId }) => [
+			{ pubkey: raffleId, isSigner: false, isWritable: true },
+			{ pubkey: treasuryId, isSigner: false, isWritable: true },
+		]);
+
+	it("should expire every qualifying raffle and skip the others", async () => {
+		const { raffleProgram, createRaffle, endRaffles } = await setup();
+
+		const unsold = await createRaffle(0, 10, 0);
+		const belowThreshold = await createRaffle(1, 10, 5);
+		const thresholdMet = await createRaffle(2, 1, 5);
+		endRaffles();
+
+		await raffleProgram.methods
+			.expireMany()
+			.remainingAccounts(pairs([unsold, belowThreshold, thresholdMet]))
+			.rpc();
+
+		for (const { raffleId } of [unsold, belowThreshold]) {
+			const raffle = await raffleProgram.account.raffle.fetch(raffleId);
+			expect(raffle.raffleState.expired).toBeDefined();
+		}
+		const raffle = await raffleProgram.account.raffle.fetch(
+			thresholdMet.raffleId,
+		);
+		expect(raffle.raffleState.open).toBeDefined();
+	});
+
+	it("should skip raffles that have not ended yet", async () => {
+		const { raffleProgram, createRaffle } = await setup();
+
+		const unsold = await createRaffle(0, 10, 0);
+
+		await raffleProgram.methods
+			.expireMany()
+			.remainingAccounts(pairs([unsold]))
+			.rpc();
+
+		const raffle = await raffleProgram.account.raffle.fetch(unsold.raffleId);
+		expect(raffle.raffleState.open).toBeDefined();
+	});
+
+	it("should fail when a treasury doesn't belong to its raffle", async () => {
+		const { raffleProgram, createRaffle, endRaffles } = await setup();
+
+		const first = await createRaffle(0, 10, 0);
+		const second = await createRaffle(1, 10, 0);
+		endRaffles();
+
+		expect(
+			raffleProgram.methods
+				.expireMany()
+				.remainingAccounts(
+					pairs([{ raffleId: first.raffleId, treasuryId: second.treasuryId }]),
+				)
+				.rpc(),
+		).rejects.toThrow(/InvalidTreasury/);
+	});
+
+	it("should fail without any raffles", async () => {
+		const { raffleProgram } = await setup();
+
+		expect(raffleProgram.methods.expireMany().rpc()).rejects.toThrow(
+			/InvalidRemainingAccounts/,
+		);
+	});
+});
@@ -48,7 +48,24 @@ describe("expire_raffle", async () => {
 
 			// Create raffle
 			await raffleProgram.methods
-				.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+				.createRaffle(
+					metadataUri,
+					ticketPrice,
+					endTime,
+					minTickets,
+					null,
+					null,
+					false,
+					null,
+					false,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+				)
 				.rpc();
 			const raffleAccountId = PublicKey.findProgramAddressSync(
 				[
@@ -67,7 +84,7 @@ describe("expire_raffle", async () => {
 				);
 
 				await raffleProgram.methods
-					.initTicketBalance()
+					.initTicketBalance(null)
 					.accounts({
 						signer: buyer.publicKey,
 						raffle: new PublicKey(raffleAccountId),
@@ -81,7 +98,16 @@ describe("expire_raffle", async () => {
 
 				// Purchase tickets
 				await raffleProgram.methods
-					.buyTickets(new BN(input.ticketsBought), Array.from(entrySeed))
+					.buyTickets(
+						new BN(input.ticketsBought),
+						Array.from(entrySeed),
+						null,
+						null,
+						null,
+						null,
+						false,
+						null,
+					)
 					.accounts({
 						signer: buyer.publicKey,
 						raffle: new PublicKey(raffleAccountId),
@@ -108,6 +134,7 @@ describe("expire_raffle", async () => {
 			const raffleAccount =
 				await raffleProgram.account.raffle.fetch(raffleAccountId);
 			expect(raffleAccount.raffleState.expired).toBeDefined();
+			expect(raffleAccount.closeReason).toBe(1); // Time elapsed
 			expect(raffleAccount.raffleState.claimed).toBeUndefined();
 			expect(raffleAccount.raffleState.drawing).toBeUndefined();
 			expect(raffleAccount.raffleState.drawn).toBeUndefined();
@@ -170,7 +197,24 @@ describe("expire_raffle", async () => {
 
 			// Create raffle, so that defaults are set, PDAs are created, etc.
 			await raffleProgram.methods
-				.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+				.createRaffle(
+					metadataUri,
+					ticketPrice,
+					endTime,
+					minTickets,
+					null,
+					null,
+					false,
+					null,
+					false,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+				)
 				.rpc();
 			const raffleAccountId = PublicKey.findProgramAddressSync(
 				[
@@ -200,6 +244,57 @@ describe("expire_raffle", async () => {
 				winnerAddress: null,
 				winningTicket: null,
 				maxTickets: null,
+				participantCount: new BN(0),
+				eventSeq: new BN(0),
+				minRevenue: null,
+				minDrawSlot: new BN(0),
+				reservedTickets: new BN(0),
+				refundFeeBps: 0,
+				rentPayer: provider.publicKey,
+				features: 0,
+				allowedRegions: new BN(0),
+				ageRestricted: false,
+				drawAuthority: null,
+				priorityMinTier: 0,
+				priorityUntil: new BN(0),
+				minUniqueBuyers: null,
+				paymentMint: null,
+				randomnessMode: { slotHashes: {} },
+				randomnessAccount: null,
+				randomnessCommitSlot: new BN(0),
+				prizeEscrowed: false,
+				winnerCount: 1,
+				winningTickets: [],
+				additionalWinners: [],
+				winnerDataSubmitted: 0,
+				prizesClaimed: 0,
+				maxTicketsPerWallet: null,
+				gate: null,
+				deferredNumbering: false,
+				entryCount: new BN(0),
+				numberedEntries: new BN(0),
+				numberedTickets: new BN(0),
+				feeBpsOverride: null,
+				snapshotRoot: null,
+				operatorStatus: 0,
+				statusNoteHash: null,
+				fundingDeadline: null,
+				pricingTiers: [],
+				tierDiscounts: new BN(0),
+				crankRewardBps: 0,
+				referralFeeBps: 0,
+				extensionCount: 0,
+				extendedSecs: new BN(0),
+				refundDeadline: new BN(0),
+				prizeValueUsdCents: null,
+				ticketCountDisplayCap: null,
+				ticketReceipts: false,
+				arweaveTxId: null,
+				creator: null,
+				drawDelaySlots: 0,
+				drawTargetSlot: new BN(0),
+				refundMint: null,
+				refundRate: new BN(0), drawValueLamports: new BN(0), drawUsdRate: null, successor: null, randomnessProvider: null, randomnessCommitment: null, accessCodeHash: null, closeReason: 0, crankRewardPaid: false, additionalWinnerHashes: [], voidedRanges: [], pendingReservations: new BN(0), openSlotReleased: false, campaignSubsidies: new BN(0),
 			});
 			provider.client.setAccount(raffleAccountId, {
 				executable: false,
@@ -250,7 +345,24 @@ describe("expire_raffle", async () => {
 
 		// Create raffle
 		await raffleProgram.methods
-			.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
 			.rpc();
 		const raffleAccountId = PublicKey.findProgramAddressSync(
 			[
@@ -300,7 +412,24 @@ describe("expire_raffle", async () => {
 
 		// Create raffle
 		await raffleProgram.methods
-			.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
 			.rpc();
 		const raffleAccountId = PublicKey.findProgramAddressSync(
 			[
@@ -318,7 +447,7 @@ describe("expire_raffle", async () => {
 		);
 
 		await raffleProgram.methods
-			.initTicketBalance()
+			.initTicketBalance(null)
 			.accounts({
 				signer: buyer.publicKey,
 				raffle: new PublicKey(raffleAccountId),
@@ -332,7 +461,7 @@ describe("expire_raffle", async () => {
 
 		// Purchase tickets
 		await raffleProgram.methods
-			.buyTickets(minTickets, Array.from(entrySeed))
+			.buyTickets(minTickets, Array.from(entrySeed), null, null, null, null, false, null)
 			.accounts({
 				signer: buyer.publicKey,
 				raffle: new PublicKey(raffleAccountId),
@@ -352,4 +481,101 @@ describe("expire_raffle", async () => {
 				.rpc(),
 		).rejects.toThrow(/ThresholdIsMet/);
 	});
+
+	it("should measure the threshold by revenue when a revenue target is set", async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const configId = PublicKey.findProgramAddressSync(
+			[Buffer.from("config")],
+			raffleProgram.programId,
+		)[0];
+		const config = await raffleProgram.account.config.fetch(configId);
+		const creationTime = client.getClock().unixTimestamp;
+
+		const metadataUri = "https://www.example.org";
+		const ticketPrice = new BN(0.1 * LAMPORTS_PER_SOL);
+		const minTickets = new BN(1);
+		const minRevenue = new BN(0.5 * LAMPORTS_PER_SOL);
+		const endTime = new BN((creationTime + BigInt(3601)).toString());
+
+		// Create raffle whose revenue target takes precedence over min tickets
+		await raffleProgram.methods
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				minRevenue,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[
+				Buffer.from("raffle"),
+				new Uint8Array(new BN(config.raffleCounter).toArray("le", 8)),
+			],
+			raffleProgram.programId,
+		)[0];
+
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(1 * LAMPORTS_PER_SOL));
+
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({
+				signer: buyer.publicKey,
+				raffle: raffleAccountId,
+			})
+			.signers([buyer])
+			.rpc();
+
+		// Buy more than min tickets but less than the revenue target
+		const entrySeed = new Uint8Array(8);
+		crypto.getRandomValues(entrySeed);
+		await raffleProgram.methods
+			.buyTickets(new BN(4), Array.from(entrySeed), null, null, null, null, false, null)
+			.accounts({
+				signer: buyer.publicKey,
+				raffle: raffleAccountId,
+			})
+			.signers([buyer])
+			.rpc();
+
+		// Time-travel to when the raffle ends
+		const newClock = client.getClock();
+		newClock.unixTimestamp = creationTime + BigInt(3602);
+		client.setClock(newClock);
+
+		await raffleProgram.methods
+			.expireRaffle()
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		const raffleAccount =
+			await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffleAccount.raffleState.expired).toBeDefined();
+		expect(raffleAccount.minRevenue?.eq(minRevenue)).toBeTrue();
+	});
 });
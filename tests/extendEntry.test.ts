@@ -0,0 +1,203 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("extend_entry", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		const metadataUri = "https://www.example.org";
+		const ticketPrice = new BN(0.1 * LAMPORTS_PER_SOL);
+		const minTickets = new BN(1);
+		const endTime = new BN((creationTime + BigInt(3601)).toString());
+
+		// Create raffle
+		await raffleProgram.methods
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		const createBuyer = async () => {
+			const buyer = new Keypair();
+			provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+			await raffleProgram.methods
+				.initTicketBalance(null)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+			return buyer;
+		};
+
+		const buyTickets = async (buyer: Keypair, ticketCount: number) => {
+			const randomBytes = new Uint8Array(8);
+			crypto.getRandomValues(randomBytes);
+			const entryId = PublicKey.findProgramAddressSync(
+				[Buffer.from("entry"), raffleAccountId.toBytes(), randomBytes],
+				raffleProgram.programId,
+			)[0];
+			await raffleProgram.methods
+				.buyTickets(
+					new BN(ticketCount),
+					Array.from(randomBytes),
+					null,
+					null,
+					null,
+					null,
+					false,
+					null,
+				)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+			return entryId;
+		};
+
+		return {
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			ticketPrice,
+			createBuyer,
+			buyTickets,
+		};
+	};
+
+	it("should extend an entry that is contiguous with the new purchase", async () => {
+		const {
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			ticketPrice,
+			createBuyer,
+			buyTickets,
+		} = await setup();
+
+		const buyer = await createBuyer();
+		const entryId = await buyTickets(buyer, 2);
+
+		const treasuryId = PublicKey.findProgramAddressSync(
+			[Buffer.from("treasury"), raffleAccountId.toBytes()],
+			raffleProgram.programId,
+		)[0];
+		const treasuryBalanceBefore = provider.client.getBalance(treasuryId);
+		if (!treasuryBalanceBefore) {
+			throw new Error("Treasury balance is null");
+		}
+
+		await raffleProgram.methods
+			.extendEntry(new BN(3), null)
+			.accounts({
+				signer: buyer.publicKey,
+				raffle: raffleAccountId,
+				entry: entryId,
+			})
+			.signers([buyer])
+			.rpc();
+
+		const entry = await raffleProgram.account.entry.fetch(entryId);
+		expect(entry.ticketStartIndex.toNumber()).toBe(0);
+		expect(entry.ticketCount.toNumber()).toBe(5);
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.currentTickets.toNumber()).toBe(5);
+		// Creation, purchase and extension each emitted one event
+		expect(raffle.eventSeq.toNumber()).toBe(3);
+
+		const ticketBalanceId = PublicKey.findProgramAddressSync(
+			[
+				Buffer.from("ticket_balance"),
+				raffleAccountId.toBytes(),
+				buyer.publicKey.toBytes(),
+			],
+			raffleProgram.programId,
+		)[0];
+		const ticketBalance =
+			await raffleProgram.account.ticketBalance.fetch(ticketBalanceId);
+		expect(ticketBalance.ticketCount.toNumber()).toBe(5);
+
+		expect(provider.client.getBalance(treasuryId)).toEqual(
+			treasuryBalanceBefore + BigInt(ticketPrice.muln(3).toString()),
+		);
+	});
+
+	it("should fail when another purchase happened after the entry", async () => {
+		const { raffleProgram, raffleAccountId, createBuyer, buyTickets } =
+			await setup();
+
+		const buyer = await createBuyer();
+		const entryId = await buyTickets(buyer, 2);
+
+		const otherBuyer = await createBuyer();
+		await buyTickets(otherBuyer, 1);
+
+		expect(
+			raffleProgram.methods
+				.extendEntry(new BN(1), null)
+				.accounts({
+					signer: buyer.publicKey,
+					raffle: raffleAccountId,
+					entry: entryId,
+				})
+				.signers([buyer])
+				.rpc(),
+		).rejects.toThrow(/EntryNotContiguous/);
+	});
+
+	it("should fail when the entry belongs to another buyer", async () => {
+		const { raffleProgram, raffleAccountId, createBuyer, buyTickets } =
+			await setup();
+
+		const buyer = await createBuyer();
+		const entryId = await buyTickets(buyer, 2);
+
+		const otherBuyer = await createBuyer();
+
+		expect(
+			raffleProgram.methods
+				.extendEntry(new BN(1), null)
+				.accounts({
+					signer: otherBuyer.publicKey,
+					raffle: raffleAccountId,
+					entry: entryId,
+				})
+				.signers([otherBuyer])
+				.rpc(),
+		).rejects.toThrow(/OwnerMismatch/);
+	});
+});
@@ -0,0 +1,128 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("extend_raffle", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config allowing two extensions of up to a day in total
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+		await raffleProgram.methods.setExtensionLimits(2, new BN(86400)).rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		const endTime = creationTime + BigInt(7200);
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN(endTime.toString()),
+				new BN(2),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		const extend = (offset: number) =>
+			raffleProgram.methods
+				.extendRaffle(new BN((endTime + BigInt(offset)).toString()))
+				.accounts({ raffle: raffleAccountId })
+				.rpc();
+
+		const buy = async (ticketCount: number) => {
+			const buyer = new Keypair();
+			provider.client.airdrop(buyer.publicKey, BigInt(LAMPORTS_PER_SOL));
+			await raffleProgram.methods
+				.initTicketBalance(null)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+			await raffleProgram.methods
+				.buyTickets(
+					new BN(ticketCount),
+					Array.from(new Uint8Array(8)),
+					null,
+					null,
+					null,
+					null,
+					false,
+					null,
+				)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+		};
+
+		const setTime = (offset: number) => {
+			const newClock = client.getClock();
+			newClock.unixTimestamp = creationTime + BigInt(offset);
+			client.setClock(newClock);
+		};
+
+		return { raffleProgram, raffleAccountId, endTime, extend, buy, setTime };
+	};
+
+	it("should push back the end time of a raffle short of its threshold", async () => {
+		const { raffleProgram, raffleAccountId, endTime, extend, buy } =
+			await setup();
+
+		await buy(1);
+		await extend(3600);
+		await extend(7200);
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.endTime.toString()).toBe((endTime + BigInt(7200)).toString());
+		expect(raffle.extensionCount).toBe(2);
+		expect(raffle.extendedSecs.toNumber()).toBe(7200);
+
+		// The config allows two extensions
+		expect(extend(10800)).rejects.toThrow(/ExtensionLimitReached/);
+	});
+
+	it("should fail to extend beyond the configured total duration", async () => {
+		const { extend } = await setup();
+
+		expect(extend(86401)).rejects.toThrow(/ExtensionLimitReached/);
+		expect(extend(0)).rejects.toThrow(/InvalidExtension/);
+	});
+
+	it("should fail to extend a raffle that met its threshold", async () => {
+		const { extend, buy } = await setup();
+
+		await buy(2);
+		expect(extend(3600)).rejects.toThrow(/ThresholdIsMet/);
+	});
+
+	it("should fail to extend a raffle that has ended", async () => {
+		const { extend, setTime } = await setup();
+
+		setTime(7200);
+		expect(extend(3600)).rejects.toThrow(/RaffleEnded/);
+	});
+});
@@ -0,0 +1,228 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("finalize_results", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+		const winnerId = new Keypair();
+
+		provider.client.airdrop(winnerId.publicKey, BigInt(1 * LAMPORTS_PER_SOL));
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		const metadataUri = "https://www.example.org";
+		const ticketPrice = new BN(0.1 * LAMPORTS_PER_SOL);
+		const minTickets = new BN(5);
+		const endTime = new BN((creationTime + BigInt(3601)).toString());
+
+		// Create raffle
+		await raffleProgram.methods
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+		const treasuryId = PublicKey.findProgramAddressSync(
+			[Buffer.from("treasury"), raffleAccountId.toBytes()],
+			raffleProgram.programId,
+		)[0];
+		const resultsId = PublicKey.findProgramAddressSync(
+			[Buffer.from("results"), raffleAccountId.toBytes()],
+			raffleProgram.programId,
+		)[0];
+
+		// Manually set the raffle state to drawn
+		const drawSlotHash = Array.from(new Uint8Array(32).fill(7));
+		const raffleData = await raffleProgram.coder.accounts.encode("raffle", {
+			metadataUri,
+			ticketPrice,
+			minTickets,
+			currentTickets: new BN(12),
+			endTime: new BN(creationTime.toString()),
+			treasury: treasuryId,
+			creationTime: new BN(creationTime.toString()),
+			raffleState: {
+				drawn: {},
+			},
+			winnerAddress: winnerId.publicKey,
+			winningTicket: new BN(3),
+			maxTickets: null,
+			drawSlot: new BN(100),
+			drawSlotHash,
+			drawTimestamp: new BN(creationTime.toString()),
+			participantCount: new BN(4),
+			eventSeq: new BN(0),
+			minRevenue: null,
+			minDrawSlot: new BN(0),
+			reservedTickets: new BN(0),
+			refundFeeBps: 0,
+			rentPayer: provider.publicKey,
+			features: 0,
+			allowedRegions: new BN(0),
+			ageRestricted: false,
+			drawAuthority: null,
+			priorityMinTier: 0,
+			priorityUntil: new BN(0),
+			minUniqueBuyers: null,
+			paymentMint: null,
+			randomnessMode: { slotHashes: {} },
+			randomnessAccount: null,
+			randomnessCommitSlot: new BN(0),
+			prizeEscrowed: false,
+			winnerCount: 1,
+			winningTickets: [],
+			additionalWinners: [],
+			winnerDataSubmitted: 0,
+			prizesClaimed: 0,
+			maxTicketsPerWallet: null,
+			gate: null,
+			deferredNumbering: false,
+			entryCount: new BN(0),
+			numberedEntries: new BN(0),
+			numberedTickets: new BN(0),
+			feeBpsOverride: null,
+			snapshotRoot: null,
+			operatorStatus: 0,
+			statusNoteHash: null,
+			fundingDeadline: null,
+			pricingTiers: [],
+			tierDiscounts: new BN(0),
+			crankRewardBps: 0,
+			referralFeeBps: 0,
+			extensionCount: 0,
+			extendedSecs: new BN(0),
+			refundDeadline: new BN(0),
+			prizeValueUsdCents: null,
+			ticketCountDisplayCap: null,
+			ticketReceipts: false,
+			arweaveTxId: null,
+			creator: null,
+			drawDelaySlots: 0,
+			drawTargetSlot: new BN(0),
+			refundMint: null,
+			refundRate: new BN(0), drawValueLamports: new BN(0), drawUsdRate: null, successor: null, randomnessProvider: null, randomnessCommitment: null, accessCodeHash: null, closeReason: 0, crankRewardPaid: false, additionalWinnerHashes: [], voidedRanges: [], pendingReservations: new BN(0), openSlotReleased: false, campaignSubsidies: new BN(0),
+		});
+		provider.client.setAccount(raffleAccountId, {
+			executable: false,
+			owner: raffleProgram.programId,
+			lamports: 1 * LAMPORTS_PER_SOL,
+			data: raffleData,
+		});
+
+		return {
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			resultsId,
+			winnerId,
+			drawSlotHash,
+		};
+	};
+
+	it("should record the results of a claimed raffle", async () => {
+		const { raffleProgram, raffleAccountId, resultsId, winnerId, drawSlotHash } =
+			await setup();
+
+		await raffleProgram.methods
+			.submitWinnerData("data", null, 1, 1, 0)
+			.accounts({
+				signer: winnerId.publicKey,
+				raffle: raffleAccountId,
+			})
+			.signers([winnerId])
+			.rpc();
+
+		await raffleProgram.methods
+			.finalizeResults()
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		const results = await raffleProgram.account.results.fetch(resultsId);
+		expect(results.raffle.equals(raffleAccountId)).toBeTrue();
+		expect(results.winner.equals(winnerId.publicKey)).toBeTrue();
+		expect(results.winningTicket.toNumber()).toBe(3);
+		expect(results.totalTickets.toNumber()).toBe(12);
+		expect(results.totalParticipants.toNumber()).toBe(4);
+		expect(results.prizeVault).toBeNull();
+		expect(results.drawSlot?.toNumber()).toBe(100);
+		expect(results.drawSlotHash).toEqual(drawSlotHash);
+
+		// Results can only be recorded once
+		expect(
+			raffleProgram.methods
+				.finalizeResults()
+				.accounts({ raffle: raffleAccountId })
+				.rpc(),
+		).rejects.toThrow();
+	});
+
+	it("should fail when not called by the management authority", async () => {
+		const { provider, raffleProgram, raffleAccountId, winnerId } =
+			await setup();
+
+		await raffleProgram.methods
+			.submitWinnerData("data", null, 1, 1, 0)
+			.accounts({
+				signer: winnerId.publicKey,
+				raffle: raffleAccountId,
+			})
+			.signers([winnerId])
+			.rpc();
+
+		const nonAuthority = new Keypair();
+		provider.client.airdrop(nonAuthority.publicKey, BigInt(LAMPORTS_PER_SOL));
+		expect(
+			raffleProgram.methods
+				.finalizeResults()
+				.accounts({
+					raffle: raffleAccountId,
+					managementAuthority: nonAuthority.publicKey,
+				})
+				.signers([nonAuthority])
+				.rpc(),
+		).rejects.toThrow(/NotProgramManagementAuthority/);
+	});
+
+	it("should fail when the raffle has not been claimed", async () => {
+		const { raffleProgram, raffleAccountId } = await setup();
+
+		expect(
+			raffleProgram.methods
+				.finalizeResults()
+				.accounts({ raffle: raffleAccountId })
+				.rpc(),
+		).rejects.toThrow(/RaffleNotClaimed/);
+	});
+});
@@ -0,0 +1,119 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("find_entry_for_ticket", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(1),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		// Two buyers with 3 and 5 tickets, covering tickets 0-2 and 3-7
+		const buyers = [new Keypair(), new Keypair()];
+		const entries = [];
+		for (const [index, buyer] of buyers.entries()) {
+			provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+			await raffleProgram.methods
+				.initTicketBalance(null)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+			const entrySeed = new Uint8Array([index, 0, 0, 0, 0, 0, 0, 0]);
+			await raffleProgram.methods
+				.buyTickets(
+					new BN(3 + index * 2),
+					Array.from(entrySeed),
+					null,
+					null,
+					null,
+					null,
+					false,
+					null,
+				)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+			entries.push(
+				PublicKey.findProgramAddressSync(
+					[Buffer.from("entry"), raffleAccountId.toBytes(), entrySeed],
+					raffleProgram.programId,
+				)[0],
+			);
+		}
+
+		const findEntry = (ticketIndex: number) =>
+			raffleProgram.methods
+				.findEntryForTicket(new BN(ticketIndex))
+				.accounts({ raffle: raffleAccountId })
+				.remainingAccounts(
+					entries.map((pubkey) => ({
+						pubkey,
+						isSigner: false,
+						isWritable: false,
+					})),
+				)
+				.view();
+
+		return { raffleAccountId, buyers, entries, findEntry };
+	};
+
+	it("should return the entry and owner of a ticket", async () => {
+		const { raffleAccountId, buyers, entries, findEntry } = await setup();
+
+		const first = await findEntry(2);
+		expect(first.raffle.equals(raffleAccountId)).toBeTrue();
+		expect(first.entry.equals(entries[0])).toBeTrue();
+		expect(first.owner.equals(buyers[0].publicKey)).toBeTrue();
+
+		const second = await findEntry(3);
+		expect(second.entry.equals(entries[1])).toBeTrue();
+		expect(second.owner.equals(buyers[1].publicKey)).toBeTrue();
+		expect(second.ticketStartIndex.toNumber()).toBe(3);
+		expect(second.ticketCount.toNumber()).toBe(5);
+	});
+
+	it("should fail for tickets that were not sold", async () => {
+		const { findEntry } = await setup();
+
+		expect(findEntry(8)).rejects.toThrow(/TicketNotSold/);
+	});
+});
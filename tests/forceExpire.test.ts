@@ -0,0 +1,176 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("force_expire", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		const metadataUri = "https://www.example.org";
+		const ticketPrice = new BN(0.1 * LAMPORTS_PER_SOL);
+		const minTickets = new BN(1);
+		const endTime = new BN((creationTime + BigInt(3601)).toString());
+
+		// Create raffle
+		await raffleProgram.methods
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		// Buy enough tickets to meet the threshold
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+		await raffleProgram.methods
+			.buyTickets(new BN(5), Array.from(new Uint8Array(8)), null, null, null, null, false, null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+
+		return { client, provider, raffleProgram, raffleAccountId, buyer };
+	};
+
+	it("should expire a raffle that met its threshold and refund ticket holders", async () => {
+		const { provider, raffleProgram, raffleAccountId, buyer } = await setup();
+
+		await raffleProgram.methods
+			.forceExpire()
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.raffleState.expired).toBeDefined();
+		expect(raffle.closeReason).toBe(4); // Voided
+
+		// The treasury can no longer be withdrawn
+		expect(
+			raffleProgram.methods
+				.withdrawFromTreasury()
+				.accounts({
+					raffle: raffleAccountId,
+					payoutAuthority: provider.publicKey,
+				})
+				.rpc(),
+		).rejects.toThrow(/RaffleIsExpired/);
+
+		// Ticket holders get their funds back
+		const balanceBefore = provider.client.getBalance(buyer.publicKey);
+		await raffleProgram.methods
+			.reclaimExpiredTickets()
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+		const balanceAfter = provider.client.getBalance(buyer.publicKey);
+		if (!balanceBefore || !balanceAfter) {
+			throw new Error("Failed to fetch balance");
+		}
+		expect(balanceAfter - balanceBefore).toBeGreaterThanOrEqual(
+			BigInt(0.5 * LAMPORTS_PER_SOL),
+		);
+	});
+
+	it("should fail once the treasury has been withdrawn", async () => {
+		const { provider, raffleProgram, raffleAccountId } = await setup();
+
+		await raffleProgram.methods
+			.withdrawFromTreasury()
+			.accounts({
+				raffle: raffleAccountId,
+				payoutAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		expect(
+			raffleProgram.methods
+				.forceExpire()
+				.accounts({ raffle: raffleAccountId })
+				.rpc(),
+		).rejects.toThrow(/TreasuryAlreadyWithdrawn/);
+	});
+
+	it("should fail once a draw slot is committed", async () => {
+		const { client, raffleProgram, raffleAccountId } = await setup();
+		client.withSysvars();
+
+		await raffleProgram.methods
+			.setDrawDelay(20)
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+		const newClock = client.getClock();
+		newClock.unixTimestamp = newClock.unixTimestamp + BigInt(3601);
+		client.setClock(newClock);
+		await raffleProgram.methods
+			.commitDrawSlot()
+			.accounts({
+				raffle: raffleAccountId,
+				recentSlothashes: new PublicKey(
+					"SysvarS1otHashes111111111111111111111111111",
+				),
+			})
+			.rpc();
+
+		expect(
+			raffleProgram.methods
+				.forceExpire()
+				.accounts({ raffle: raffleAccountId })
+				.rpc(),
+		).rejects.toThrow(/DrawSlotAlreadyCommitted/);
+	});
+
+	it("should fail from accounts other than the management authority", async () => {
+		const { provider, raffleProgram, raffleAccountId } = await setup();
+
+		const other = new Keypair();
+		provider.client.airdrop(other.publicKey, BigInt(1 * LAMPORTS_PER_SOL));
+		expect(
+			raffleProgram.methods
+				.forceExpire()
+				.accounts({
+					raffle: raffleAccountId,
+					managementAuthority: other.publicKey,
+				})
+				.signers([other])
+				.rpc(),
+		).rejects.toThrow(/NotProgramManagementAuthority/);
+	});
+});
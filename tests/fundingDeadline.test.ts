@@ -0,0 +1,171 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("set_funding_deadline", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(7200)).toString()),
+				new BN(2),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		const setFundingDeadline = (fundingDeadline: bigint | null) =>
+			raffleProgram.methods
+				.setFundingDeadline(
+					fundingDeadline === null ? null : new BN(fundingDeadline.toString()),
+				)
+				.accounts({ raffle: raffleAccountId })
+				.rpc();
+
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+		const buy = (ticketCount: number, seed: number = 0) =>
+			raffleProgram.methods
+				.buyTickets(
+					new BN(ticketCount),
+					Array.from(new Uint8Array([seed, 0, 0, 0, 0, 0, 0, 0])),
+					null,
+					null,
+					null,
+					null,
+					false,
+					null,
+				)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+
+		const expire = () =>
+			raffleProgram.methods
+				.expireRaffle()
+				.accounts({ raffle: raffleAccountId })
+				.rpc();
+
+		const warpTo = (time: bigint) => {
+			const newClock = client.getClock();
+			newClock.unixTimestamp = time;
+			client.setClock(newClock);
+		};
+
+		return {
+			raffleProgram,
+			raffleAccountId,
+			creationTime,
+			setFundingDeadline,
+			buy,
+			expire,
+			warpTo,
+		};
+	};
+
+	it("should expire a raffle that missed its funding deadline early", async () => {
+		const {
+			raffleProgram,
+			raffleAccountId,
+			creationTime,
+			setFundingDeadline,
+			buy,
+			expire,
+			warpTo,
+		} = await setup();
+
+		await setFundingDeadline(creationTime + BigInt(3600));
+		await buy(1);
+
+		// Can't expire before the funding deadline
+		expect(expire()).rejects.toThrow(/RaffleNotEnded/);
+
+		warpTo(creationTime + BigInt(3601));
+		expect(buy(1, 1)).rejects.toThrow(/FundingDeadlineMissed/);
+		await expire();
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.raffleState).toEqual({ expired: {} });
+		expect(raffle.closeReason).toBe(5); // Early close
+	});
+
+	it("should keep selling when funded by the deadline", async () => {
+		const {
+			raffleProgram,
+			raffleAccountId,
+			creationTime,
+			setFundingDeadline,
+			buy,
+			expire,
+			warpTo,
+		} = await setup();
+
+		await setFundingDeadline(creationTime + BigInt(3600));
+		await buy(2);
+
+		warpTo(creationTime + BigInt(3601));
+		await buy(1, 1);
+		expect(expire()).rejects.toThrow(/RaffleNotEnded/);
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.currentTickets.toNumber()).toBe(3);
+	});
+
+	it("should fail to set a deadline at or after the end time", async () => {
+		const { creationTime, setFundingDeadline } = await setup();
+
+		expect(setFundingDeadline(creationTime + BigInt(7200))).rejects.toThrow(
+			/InvalidFundingDeadline/,
+		);
+		expect(setFundingDeadline(creationTime - BigInt(1))).rejects.toThrow(
+			/InvalidFundingDeadline/,
+		);
+	});
+
+	it("should fail to set a deadline once tickets were sold", async () => {
+		const { creationTime, setFundingDeadline, buy } = await setup();
+
+		await buy(1);
+		expect(setFundingDeadline(creationTime + BigInt(3600))).rejects.toThrow(
+			/TicketsAlreadySold/,
+		);
+	});
+});
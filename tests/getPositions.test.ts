@@ -0,0 +1,146 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("get_positions", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(5 * LAMPORTS_PER_SOL));
+
+		// Create three raffles, of which the buyer enters the first two
+		const creationTime = client.getClock().unixTimestamp;
+		const raffles: PublicKey[] = [];
+		for (let i = 0; i < 3; i++) {
+			await raffleProgram.methods
+				.createRaffle(
+					"https://www.example.org",
+					new BN(0.1 * LAMPORTS_PER_SOL),
+					new BN((creationTime + BigInt(3601)).toString()),
+					new BN(1),
+					null,
+					null,
+					false,
+					null,
+					false,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+				)
+				.rpc();
+			raffles.push(
+				PublicKey.findProgramAddressSync(
+					[Buffer.from("raffle"), new Uint8Array(new BN(i).toArray("le", 8))],
+					raffleProgram.programId,
+				)[0],
+			);
+		}
+		for (const [i, raffle] of raffles.slice(0, 2).entries()) {
+			await raffleProgram.methods
+				.initTicketBalance(null)
+				.accounts({ signer: buyer.publicKey, raffle })
+				.signers([buyer])
+				.rpc();
+			await raffleProgram.methods
+				.buyTickets(
+					new BN(i + 2),
+					Array.from(new Uint8Array(8)),
+					null,
+					null,
+					null,
+					null,
+					false,
+					null,
+				)
+				.accounts({ signer: buyer.publicKey, raffle })
+				.signers([buyer])
+				.rpc();
+		}
+
+		const ticketBalance = (raffle: PublicKey, wallet: PublicKey) =>
+			PublicKey.findProgramAddressSync(
+				[Buffer.from("ticket_balance"), raffle.toBuffer(), wallet.toBuffer()],
+				raffleProgram.programId,
+			)[0];
+		const getPositions = (accounts: PublicKey[]) =>
+			raffleProgram.methods
+				.getPositions(buyer.publicKey)
+				.remainingAccounts(
+					accounts.map((pubkey) => ({
+						pubkey,
+						isSigner: false,
+						isWritable: false,
+					})),
+				)
+				.view();
+
+		return { raffleProgram, buyer, raffles, ticketBalance, getPositions };
+	};
+
+	it("should list the raffles the wallet holds tickets in", async () => {
+		const { raffleProgram, buyer, raffles, ticketBalance, getPositions } =
+			await setup();
+
+		await raffleProgram.methods
+			.cancelRaffle()
+			.accounts({ raffle: raffles[1] })
+			.rpc();
+
+		const positions = await getPositions(
+			raffles.flatMap((raffle) => [
+				raffle,
+				ticketBalance(raffle, buyer.publicKey),
+			]),
+		);
+
+		// The third raffle was never entered
+		expect(positions.length).toBe(2);
+		expect(positions[0].raffle).toEqual(raffles[0]);
+		expect(positions[0].tickets.toNumber()).toBe(2);
+		expect(positions[0].refundable).toBe(false);
+		expect(positions[0].state.open).toBeDefined();
+		expect(positions[1].raffle).toEqual(raffles[1]);
+		expect(positions[1].tickets.toNumber()).toBe(3);
+		expect(positions[1].refundable).toBe(true);
+		expect(positions[1].state.cancelled).toBeDefined();
+	});
+
+	it("should fail for a ticket balance of another wallet", async () => {
+		const { raffles, ticketBalance, getPositions } = await setup();
+
+		expect(
+			getPositions([
+				raffles[0],
+				ticketBalance(raffles[0], new Keypair().publicKey),
+			]),
+		).rejects.toThrow(/InvalidRemainingAccounts/);
+	});
+
+	it("should fail when the accounts are not pairs", async () => {
+		const { raffles, getPositions } = await setup();
+
+		expect(getPositions([raffles[0]])).rejects.toThrow(
+			/InvalidRemainingAccounts/,
+		);
+	});
+});
@@ -0,0 +1,108 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("get_treasury_statement", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(10),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		const buyTickets = async (ticketCount: number) => {
+			const buyer = new Keypair();
+			provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+			await raffleProgram.methods
+				.initTicketBalance(null)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+			await raffleProgram.methods
+				.buyTickets(
+					new BN(ticketCount),
+					Array.from(new Uint8Array(8)),
+					null,
+					null,
+					null,
+					null,
+					false,
+					null,
+				)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+		};
+
+		const getStatement = () =>
+			raffleProgram.methods
+				.getTreasuryStatement()
+				.accounts({ raffle: raffleAccountId })
+				.view();
+
+		return { raffleAccountId, buyTickets, getStatement };
+	};
+
+	it("should report nothing withdrawable below the threshold", async () => {
+		const { raffleAccountId, buyTickets, getStatement } = await setup();
+
+		await buyTickets(5);
+
+		const statement = await getStatement();
+		expect(statement.raffle.equals(raffleAccountId)).toBeTrue();
+		expect(statement.raffleState).toEqual({ open: {} });
+		expect(statement.totalCollected.toNumber()).toBe(0.5 * LAMPORTS_PER_SOL);
+		expect(statement.outstanding.toNumber()).toBe(0.5 * LAMPORTS_PER_SOL);
+		expect(statement.withdrawable.toNumber()).toBe(0);
+	});
+
+	it("should report the outstanding funds once the threshold is met", async () => {
+		const { buyTickets, getStatement } = await setup();
+
+		await buyTickets(10);
+
+		const statement = await getStatement();
+		expect(statement.totalCollected.toNumber()).toBe(LAMPORTS_PER_SOL);
+		expect(statement.totalRefunded.toNumber()).toBe(0);
+		expect(statement.totalWithdrawn.toNumber()).toBe(0);
+		expect(statement.withdrawable.toNumber()).toBe(LAMPORTS_PER_SOL);
+		expect(statement.balance.toNumber()).toBeGreaterThan(LAMPORTS_PER_SOL);
+	});
+});
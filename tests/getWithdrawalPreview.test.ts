@@ -0,0 +1,117 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("get_withdrawal_preview", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(5),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		const buyTickets = async (ticketCount: number) => {
+			const buyer = new Keypair();
+			provider.client.airdrop(buyer.publicKey, BigInt(LAMPORTS_PER_SOL));
+			await raffleProgram.methods
+				.initTicketBalance(null)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+			await raffleProgram.methods
+				.buyTickets(
+					new BN(ticketCount),
+					Array.from(new Uint8Array(8)),
+					null,
+					null,
+					null,
+					null,
+					false,
+					null,
+				)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+		};
+
+		const getPreview = () =>
+			raffleProgram.methods
+				.getWithdrawalPreview()
+				.accounts({ raffle: raffleAccountId })
+				.view();
+
+		return { raffleProgram, buyTickets, getPreview };
+	};
+
+	it("should split the withdrawable revenue into the platform fee and the payout", async () => {
+		const { raffleProgram, buyTickets, getPreview } = await setup();
+
+		const feeRecipient = new Keypair().publicKey;
+		await raffleProgram.methods.setPlatformFee(1_000, feeRecipient).rpc();
+		await buyTickets(5);
+
+		const preview = await getPreview();
+		expect(preview.amount.toNumber()).toBe(0.5 * LAMPORTS_PER_SOL);
+		expect(preview.feeBps).toBe(1_000);
+		expect(preview.feeAmount.toNumber()).toBe(0.05 * LAMPORTS_PER_SOL);
+		expect(preview.feeRecipient?.equals(feeRecipient)).toBeTrue();
+		expect(preview.payoutAmount.toNumber()).toBe(0.45 * LAMPORTS_PER_SOL);
+		expect(preview.reservedReferralFees.toNumber()).toBe(0);
+		expect(preview.approvalRequired).toBeFalse();
+	});
+
+	it("should pay everything out without a fee recipient", async () => {
+		const { buyTickets, getPreview } = await setup();
+
+		await buyTickets(5);
+
+		const preview = await getPreview();
+		expect(preview.feeAmount.toNumber()).toBe(0);
+		expect(preview.feeRecipient).toBeNull();
+		expect(preview.payoutAmount.toNumber()).toBe(0.5 * LAMPORTS_PER_SOL);
+	});
+
+	it("should fail while the threshold is not met", async () => {
+		const { buyTickets, getPreview } = await setup();
+
+		await buyTickets(2);
+		expect(getPreview()).rejects.toThrow(/ThresholdNotMet/);
+	});
+});
@@ -0,0 +1,157 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("gift_tickets_multi", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(1),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		const payer = new Keypair();
+		provider.client.airdrop(payer.publicKey, BigInt(5 * LAMPORTS_PER_SOL));
+
+		const giftAccounts = (recipients: PublicKey[]) =>
+			recipients.flatMap((recipient, index) => [
+				{ pubkey: recipient, isSigner: false, isWritable: false },
+				{
+					pubkey: PublicKey.findProgramAddressSync(
+						[
+							Buffer.from("ticket_balance"),
+							raffleAccountId.toBytes(),
+							recipient.toBytes(),
+						],
+						raffleProgram.programId,
+					)[0],
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: PublicKey.findProgramAddressSync(
+						[
+							Buffer.from("entry"),
+							raffleAccountId.toBytes(),
+							new Uint8Array([index, 0, 0, 0, 0, 0, 0, 0]),
+						],
+						raffleProgram.programId,
+					)[0],
+					isSigner: false,
+					isWritable: true,
+				},
+			]);
+		const gifts = (ticketCounts: number[]) =>
+			ticketCounts.map((ticketCount, index) => ({
+				ticketCount: new BN(ticketCount),
+				entrySeed: [index, 0, 0, 0, 0, 0, 0, 0],
+			}));
+
+		return {
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			payer,
+			giftAccounts,
+			gifts,
+		};
+	};
+
+	it("should credit every recipient with their own entry", async () => {
+		const {
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			payer,
+			giftAccounts,
+			gifts,
+		} = await setup();
+
+		const recipients = [new Keypair(), new Keypair(), new Keypair()].map(
+			(keypair) => keypair.publicKey,
+		);
+		const payerBalanceBefore = provider.client.getBalance(payer.publicKey);
+		if (!payerBalanceBefore) {
+			throw new Error("Failed to fetch balance");
+		}
+
+		await raffleProgram.methods
+			.giftTicketsMulti(gifts([1, 2, 3]), null)
+			.accounts({ signer: payer.publicKey, raffle: raffleAccountId })
+			.remainingAccounts(giftAccounts(recipients))
+			.signers([payer])
+			.rpc();
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.currentTickets.toNumber()).toBe(6);
+		expect(raffle.participantCount.toNumber()).toBe(3);
+
+		for (const [index, recipient] of recipients.entries()) {
+			const ticketBalance = await raffleProgram.account.ticketBalance.fetch(
+				giftAccounts(recipients)[index * 3 + 1].pubkey,
+			);
+			expect(ticketBalance.owner.equals(recipient)).toBeTrue();
+			expect(ticketBalance.ticketCount.toNumber()).toBe(index + 1);
+			const entry = await raffleProgram.account.entry.fetch(
+				giftAccounts(recipients)[index * 3 + 2].pubkey,
+			);
+			expect(entry.owner.equals(recipient)).toBeTrue();
+		}
+
+		// The payer paid for all tickets, in addition to the rent
+		expect(provider.client.getBalance(payer.publicKey)).toBeLessThan(
+			payerBalanceBefore - BigInt(0.6 * LAMPORTS_PER_SOL),
+		);
+	});
+
+	it("should fail with mismatched remaining accounts", async () => {
+		const { raffleProgram, raffleAccountId, payer, giftAccounts, gifts } =
+			await setup();
+
+		expect(
+			raffleProgram.methods
+				.giftTicketsMulti(gifts([1, 1]), null)
+				.accounts({ signer: payer.publicKey, raffle: raffleAccountId })
+				.remainingAccounts(giftAccounts([new Keypair().publicKey]))
+				.signers([payer])
+				.rpc(),
+		).rejects.toThrow(/InvalidRemainingAccounts/);
+	});
+});
@@ -37,7 +37,24 @@ describe("init_ticket_balance", async () => {
 
 		// Create raffle
 		await raffleProgram.methods
-			.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
 			.rpc();
 		const raffleAccountId = PublicKey.findProgramAddressSync(
 			[
@@ -53,7 +70,7 @@ describe("init_ticket_balance", async () => {
 			provider.client.airdrop(wallet.publicKey, BigInt(1 * LAMPORTS_PER_SOL));
 
 			await raffleProgram.methods
-				.initTicketBalance()
+				.initTicketBalance(null)
 				.accounts({
 					signer: wallet.publicKey,
 					raffle: new PublicKey(raffleAccountId),
@@ -116,7 +133,24 @@ describe("init_ticket_balance", async () => {
 
 			// Create raffle
 			await raffleProgram.methods
-				.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+				.createRaffle(
+					metadataUri,
+					ticketPrice,
+					endTime,
+					minTickets,
+					null,
+					null,
+					false,
+					null,
+					false,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+				)
 				.rpc();
 			const raffleAccountId = PublicKey.findProgramAddressSync(
 				[
@@ -127,7 +161,7 @@ describe("init_ticket_balance", async () => {
 			)[0];
 
 			await raffleProgram.methods
-				.initTicketBalance()
+				.initTicketBalance(null)
 				.accounts({
 					signer: wallet.publicKey,
 					raffle: new PublicKey(raffleAccountId),
@@ -186,7 +220,24 @@ describe("init_ticket_balance", async () => {
 
 			// Create raffle, so that defaults are set, PDAs are created, etc.
 			await raffleProgram.methods
-				.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+				.createRaffle(
+					metadataUri,
+					ticketPrice,
+					endTime,
+					minTickets,
+					null,
+					null,
+					false,
+					null,
+					false,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+				)
 				.rpc();
 			const raffleAccountId = PublicKey.findProgramAddressSync(
 				[
@@ -219,6 +270,57 @@ describe("init_ticket_balance", async () => {
 				winnerAddress: null,
 				winningTicket: null,
 				maxTickets: null,
+				participantCount: new BN(0),
+				eventSeq: new BN(0),
+				minRevenue: null,
+				minDrawSlot: new BN(0),
+				reservedTickets: new BN(0),
+				refundFeeBps: 0,
+				rentPayer: provider.publicKey,
+				features: 0,
+				allowedRegions: new BN(0),
+				ageRestricted: false,
+				drawAuthority: null,
+				priorityMinTier: 0,
+				priorityUntil: new BN(0),
+				minUniqueBuyers: null,
+				paymentMint: null,
+				randomnessMode: { slotHashes: {} },
+				randomnessAccount: null,
+				randomnessCommitSlot: new BN(0),
+				prizeEscrowed: false,
+				winnerCount: 1,
+				winningTickets: [],
+				additionalWinners: [],
+				winnerDataSubmitted: 0,
+				prizesClaimed: 0,
+				maxTicketsPerWallet: null,
+				gate: null,
+				deferredNumbering: false,
+				entryCount: new BN(0),
+				numberedEntries: new BN(0),
+				numberedTickets: new BN(0),
+				feeBpsOverride: null,
+				snapshotRoot: null,
+				operatorStatus: 0,
+				statusNoteHash: null,
+				fundingDeadline: null,
+				pricingTiers: [],
+				tierDiscounts: new BN(0),
+				crankRewardBps: 0,
+				referralFeeBps: 0,
+				extensionCount: 0,
+				extendedSecs: new BN(0),
+				refundDeadline: new BN(0),
+				prizeValueUsdCents: null,
+				ticketCountDisplayCap: null,
+				ticketReceipts: false,
+				arweaveTxId: null,
+				creator: null,
+				drawDelaySlots: 0,
+				drawTargetSlot: new BN(0),
+				refundMint: null,
+				refundRate: new BN(0), drawValueLamports: new BN(0), drawUsdRate: null, successor: null, randomnessProvider: null, randomnessCommitment: null, accessCodeHash: null, closeReason: 0, crankRewardPaid: false, additionalWinnerHashes: [], voidedRanges: [], pendingReservations: new BN(0), openSlotReleased: false, campaignSubsidies: new BN(0),
 			});
 			provider.client.setAccount(raffleAccountId, {
 				executable: false,
@@ -230,7 +332,7 @@ describe("init_ticket_balance", async () => {
 			// Init ticket balance, should fail
 			expect(
 				raffleProgram.methods
-					.initTicketBalance()
+					.initTicketBalance(null)
 					.accounts({
 						signer: buyer.publicKey,
 						raffle: raffleAccountId,
@@ -272,7 +374,24 @@ describe("init_ticket_balance", async () => {
 
 		// Create raffle
 		await raffleProgram.methods
-			.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
 			.rpc();
 		const raffleAccountId = PublicKey.findProgramAddressSync(
 			[
@@ -287,7 +406,7 @@ describe("init_ticket_balance", async () => {
 
 		// First initialization should work
 		await raffleProgram.methods
-			.initTicketBalance()
+			.initTicketBalance(null)
 			.accounts({
 				signer: wallet.publicKey,
 				raffle: new PublicKey(raffleAccountId),
@@ -297,7 +416,7 @@ describe("init_ticket_balance", async () => {
 
 		expect(
 			raffleProgram.methods
-				.initTicketBalance()
+				.initTicketBalance(null)
 				.accounts({
 					signer: wallet.publicKey,
 					raffle: new PublicKey(raffleAccountId),
@@ -338,7 +457,24 @@ describe("init_ticket_balance", async () => {
 
 		// Create raffle
 		await raffleProgram.methods
-			.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
 			.rpc();
 		const raffleAccountId = PublicKey.findProgramAddressSync(
 			[
@@ -353,7 +489,7 @@ describe("init_ticket_balance", async () => {
 
 		expect(
 			raffleProgram.methods
-				.initTicketBalance()
+				.initTicketBalance(null)
 				.accountsPartial({
 					signer: new Keypair().publicKey,
 					raffle: new PublicKey(raffleAccountId),
@@ -0,0 +1,145 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("keepers", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		const metadataUri = "https://www.example.org";
+		const ticketPrice = new BN(0.1 * LAMPORTS_PER_SOL);
+		const minTickets = new BN(1);
+		const endTime = new BN((creationTime + BigInt(3601)).toString());
+
+		// Create raffle
+		await raffleProgram.methods
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		// Time-travel to when the raffle ends
+		const newClock = client.getClock();
+		newClock.unixTimestamp = creationTime + BigInt(3602);
+		client.setClock(newClock);
+
+		const keeper = new Keypair();
+		provider.client.airdrop(keeper.publicKey, BigInt(1 * LAMPORTS_PER_SOL));
+		const keeperRegistrationId = PublicKey.findProgramAddressSync(
+			[Buffer.from("keeper"), keeper.publicKey.toBytes()],
+			raffleProgram.programId,
+		)[0];
+
+		return { raffleProgram, raffleAccountId, keeper, keeperRegistrationId };
+	};
+
+	it("should only let registered keepers crank while restricted", async () => {
+		const { raffleProgram, raffleAccountId, keeper, keeperRegistrationId } =
+			await setup();
+
+		await raffleProgram.methods.setKeepersRestricted(true).rpc();
+
+		// Unregistered callers are rejected
+		expect(
+			raffleProgram.methods
+				.expireRaffle()
+				.accounts({
+					raffle: raffleAccountId,
+					keeper: null,
+					keeperRegistration: null,
+				})
+				.rpc(),
+		).rejects.toThrow(/KeeperNotRegistered/);
+
+		await raffleProgram.methods.registerKeeper(keeper.publicKey).rpc();
+		const keeperRegistration =
+			await raffleProgram.account.keeper.fetch(keeperRegistrationId);
+		expect(keeperRegistration.keeper.equals(keeper.publicKey)).toBeTrue();
+
+		await raffleProgram.methods
+			.expireRaffle()
+			.accounts({
+				raffle: raffleAccountId,
+				keeper: keeper.publicKey,
+				keeperRegistration: keeperRegistrationId,
+			})
+			.signers([keeper])
+			.rpc();
+
+		const raffleAccount =
+			await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffleAccount.raffleState.expired).toBeDefined();
+	});
+
+	it("should reject removed keepers", async () => {
+		const { raffleProgram, raffleAccountId, keeper, keeperRegistrationId } =
+			await setup();
+
+		await raffleProgram.methods.setKeepersRestricted(true).rpc();
+		await raffleProgram.methods.registerKeeper(keeper.publicKey).rpc();
+		await raffleProgram.methods
+			.removeKeeper()
+			.accounts({ keeperRegistration: keeperRegistrationId })
+			.rpc();
+
+		expect(
+			raffleProgram.methods
+				.expireRaffle()
+				.accounts({
+					raffle: raffleAccountId,
+					keeper: keeper.publicKey,
+					keeperRegistration: keeperRegistrationId,
+				})
+				.signers([keeper])
+				.rpc(),
+		).rejects.toThrow();
+	});
+
+	it("should let anyone crank while unrestricted", async () => {
+		const { raffleProgram, raffleAccountId } = await setup();
+
+		await raffleProgram.methods
+			.expireRaffle()
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		const raffleAccount =
+			await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffleAccount.raffleState.expired).toBeDefined();
+	});
+});
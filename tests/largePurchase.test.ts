@@ -0,0 +1,129 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("large_purchase", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(1),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+
+		// Simulates a purchase of `ticketCount` tickets, returning the emitted events
+		const purchaseEvents = async (ticketCount: number) =>
+			(
+				await raffleProgram.methods
+					.buyTickets(
+						new BN(ticketCount),
+						Array.from(new Uint8Array(8)),
+						null,
+						null,
+						null,
+						null,
+						false,
+						null,
+					)
+					.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+					.signers([buyer])
+					.simulate()
+			).events;
+
+		return { provider, raffleProgram, raffleAccountId, buyer, purchaseEvents };
+	};
+
+	it("should emit a large purchase event above the threshold", async () => {
+		const { raffleProgram, raffleAccountId, buyer, purchaseEvents } =
+			await setup();
+
+		await raffleProgram.methods
+			.setLargePurchaseThreshold(new BN(0.5 * LAMPORTS_PER_SOL))
+			.rpc();
+
+		// A purchase paying exactly the threshold doesn't exceed it
+		const atThreshold = await purchaseEvents(5);
+		expect(atThreshold.map((event) => event.name)).toEqual([
+			"TicketsPurchased",
+		]);
+
+		const events = await purchaseEvents(6);
+		expect(events.map((event) => event.name)).toEqual([
+			"TicketsPurchased",
+			"LargePurchase",
+		]);
+		const largePurchase = events[1].data;
+		expect(largePurchase.raffle.equals(raffleAccountId)).toBeTrue();
+		expect(largePurchase.buyer.equals(buyer.publicKey)).toBeTrue();
+		expect(largePurchase.ticketCount.toNumber()).toBe(6);
+		expect(largePurchase.paymentAmount.toNumber()).toBe(
+			0.6 * LAMPORTS_PER_SOL,
+		);
+		expect(largePurchase.threshold.toNumber()).toBe(0.5 * LAMPORTS_PER_SOL);
+	});
+
+	it("should not emit large purchase events without a threshold", async () => {
+		const { purchaseEvents } = await setup();
+
+		const events = await purchaseEvents(10);
+		expect(events.map((event) => event.name)).toEqual(["TicketsPurchased"]);
+	});
+
+	it("should fail from accounts other than the management authority", async () => {
+		const { provider, raffleProgram } = await setup();
+
+		const other = new Keypair();
+		provider.client.airdrop(other.publicKey, BigInt(1 * LAMPORTS_PER_SOL));
+		expect(
+			raffleProgram.methods
+				.setLargePurchaseThreshold(new BN(1))
+				.accounts({ managementAuthority: other.publicKey })
+				.signers([other])
+				.rpc(),
+		).rejects.toThrow(/NotProgramManagementAuthority/);
+	});
+});
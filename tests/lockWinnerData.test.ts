@@ -0,0 +1,173 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("lock_winner_data", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+		const winner = new Keypair();
+
+		provider.client.airdrop(winner.publicKey, BigInt(LAMPORTS_PER_SOL));
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(5),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+		const winnerDataId = PublicKey.findProgramAddressSync(
+			[
+				Buffer.from("winner_data"),
+				raffleAccountId.toBytes(),
+				winner.publicKey.toBytes(),
+			],
+			raffleProgram.programId,
+		)[0];
+
+		// Manually set the raffle state to drawn
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		const raffleData = await raffleProgram.coder.accounts.encode("raffle", {
+			...raffle,
+			raffleState: { drawn: {} },
+			winnerAddress: winner.publicKey,
+		});
+		provider.client.setAccount(raffleAccountId, {
+			executable: false,
+			owner: raffleProgram.programId,
+			lamports: 1 * LAMPORTS_PER_SOL,
+			data: raffleData,
+		});
+
+		await raffleProgram.methods
+			.submitWinnerData("first", null, 1, 1, 0)
+			.accounts({ raffle: raffleAccountId, signer: winner.publicKey })
+			.signers([winner])
+			.rpc();
+
+		const updateWinnerData = (data: string) =>
+			raffleProgram.methods
+				.updateWinnerData(data, 1, 1)
+				.accounts({ raffle: raffleAccountId, signer: winner.publicKey })
+				.signers([winner])
+				.rpc();
+
+		const timeTravel = (seconds: number) => {
+			const newClock = client.getClock();
+			newClock.unixTimestamp = newClock.unixTimestamp + BigInt(seconds);
+			client.setClock(newClock);
+		};
+
+		return {
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			winner,
+			winnerDataId,
+			updateWinnerData,
+			timeTravel,
+		};
+	};
+
+	it("should let the winner correct their data until they lock it", async () => {
+		const {
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			winner,
+			winnerDataId,
+			updateWinnerData,
+		} = await setup();
+
+		await updateWinnerData("second");
+		let winnerData = await raffleProgram.account.winnerData.fetch(winnerDataId);
+		expect(winnerData.data).toBe("second");
+		expect(winnerData.locked).toBeFalse();
+
+		// Only the winner can lock the data while the edit window is open
+		const other = new Keypair();
+		provider.client.airdrop(other.publicKey, BigInt(LAMPORTS_PER_SOL));
+		expect(
+			raffleProgram.methods
+				.lockWinnerData()
+				.accounts({
+					raffle: raffleAccountId,
+					winner: winner.publicKey,
+					signer: other.publicKey,
+				})
+				.signers([other])
+				.rpc(),
+		).rejects.toThrow(/WinnerDataEditWindowOpen/);
+
+		await raffleProgram.methods
+			.lockWinnerData()
+			.accounts({
+				raffle: raffleAccountId,
+				winner: winner.publicKey,
+				signer: winner.publicKey,
+			})
+			.signers([winner])
+			.rpc();
+
+		winnerData = await raffleProgram.account.winnerData.fetch(winnerDataId);
+		expect(winnerData.locked).toBeTrue();
+		expect(updateWinnerData("third")).rejects.toThrow(/WinnerDataLocked/);
+	});
+
+	it("should make the data final once the edit window passed", async () => {
+		const {
+			raffleProgram,
+			raffleAccountId,
+			winner,
+			winnerDataId,
+			updateWinnerData,
+			timeTravel,
+		} = await setup();
+
+		timeTravel(24 * 60 * 60);
+		expect(updateWinnerData("second")).rejects.toThrow(/WinnerDataLocked/);
+
+		// Anyone can lock the data now
+		await raffleProgram.methods
+			.lockWinnerData()
+			.accounts({ raffle: raffleAccountId, winner: winner.publicKey })
+			.rpc();
+
+		const winnerData =
+			await raffleProgram.account.winnerData.fetch(winnerDataId);
+		expect(winnerData.data).toBe("first");
+		expect(winnerData.locked).toBeTrue();
+	});
+});
@@ -0,0 +1,133 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import {
+	AddressLookupTableAccount,
+	AddressLookupTableProgram,
+	Keypair,
+	PublicKey,
+} from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("lookup_table", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const configId = PublicKey.findProgramAddressSync(
+			[Buffer.from("config")],
+			raffleProgram.programId,
+		)[0];
+		const authority = PublicKey.findProgramAddressSync(
+			[Buffer.from("lookup_table_authority")],
+			raffleProgram.programId,
+		)[0];
+
+		// The table address is derived from a slot in the SlotHashes sysvar
+		client.warpToSlot(BigInt(100));
+		const recentSlot = Number(client.getClock().slot) - 1;
+		const lookupTable = PublicKey.findProgramAddressSync(
+			[authority.toBuffer(), new BN(recentSlot).toArrayLike(Buffer, "le", 8)],
+			AddressLookupTableProgram.programId,
+		)[0];
+
+		const createLookupTable = (table = lookupTable) =>
+			raffleProgram.methods
+				.createLookupTable(new BN(recentSlot))
+				.accounts({
+					lookupTable: table,
+					addressLookupTableProgram: AddressLookupTableProgram.programId,
+				})
+				.rpc();
+
+		const readAddresses = (table: PublicKey) =>
+			AddressLookupTableAccount.deserialize(client.getAccount(table)!.data)
+				.addresses;
+
+		return {
+			raffleProgram,
+			configId,
+			lookupTable,
+			createLookupTable,
+			readAddresses,
+		};
+	};
+
+	it("should create a table with the program's accounts and record it", async () => {
+		const {
+			raffleProgram,
+			configId,
+			lookupTable,
+			createLookupTable,
+			readAddresses,
+		} = await setup();
+
+		await createLookupTable();
+
+		const config = await raffleProgram.account.config.fetch(configId);
+		expect(config.lookupTable?.equals(lookupTable)).toBeTrue();
+		const addresses = readAddresses(lookupTable);
+		expect(addresses[0].equals(configId)).toBeTrue();
+		expect(
+			addresses.some((address) => address.equals(raffleProgram.programId)),
+		).toBeTrue();
+	});
+
+	it("should only extend the recorded table", async () => {
+		const { raffleProgram, lookupTable, createLookupTable, readAddresses } =
+			await setup();
+		await createLookupTable();
+		const initialLength = readAddresses(lookupTable).length;
+
+		const raffle = new Keypair().publicKey;
+		await raffleProgram.methods
+			.extendLookupTable([raffle])
+			.accounts({
+				lookupTable,
+				addressLookupTableProgram: AddressLookupTableProgram.programId,
+			})
+			.rpc();
+		const addresses = readAddresses(lookupTable);
+		expect(addresses.length).toBe(initialLength + 1);
+		expect(addresses[initialLength].equals(raffle)).toBeTrue();
+
+		expect(
+			raffleProgram.methods
+				.extendLookupTable([raffle])
+				.accounts({
+					lookupTable: new Keypair().publicKey,
+					addressLookupTableProgram: AddressLookupTableProgram.programId,
+				})
+				.rpc(),
+		).rejects.toThrow(/InvalidLookupTable/);
+		expect(
+			raffleProgram.methods
+				.extendLookupTable([])
+				.accounts({
+					lookupTable,
+					addressLookupTableProgram: AddressLookupTableProgram.programId,
+				})
+				.rpc(),
+		).rejects.toThrow(/InvalidLookupTableExtension/);
+	});
+
+	it("should fail to create a table at an address not derived from the slot", async () => {
+		const { createLookupTable } = await setup();
+
+		expect(createLookupTable(new Keypair().publicKey)).rejects.toThrow(
+			/InvalidLookupTable/,
+		);
+	});
+});
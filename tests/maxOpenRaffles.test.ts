@@ -0,0 +1,106 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("max_open_raffles", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+		const configId = PublicKey.findProgramAddressSync(
+			[Buffer.from("config")],
+			raffleProgram.programId,
+		)[0];
+
+		const createRaffle = () =>
+			raffleProgram.methods
+				.createRaffle(
+					"https://www.example.org",
+					new BN(0.1 * LAMPORTS_PER_SOL),
+					new BN(
+						(client.getClock().unixTimestamp + BigInt(3601)).toString(),
+					),
+					new BN(1),
+					null,
+					null,
+					false,
+					null,
+					false,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+				)
+				.rpc();
+		const raffleAccountId = (index: number) =>
+			PublicKey.findProgramAddressSync(
+				[Buffer.from("raffle"), new Uint8Array(new BN(index).toArray("le", 8))],
+				raffleProgram.programId,
+			)[0];
+		const openRaffleCount = async () =>
+			(await raffleProgram.account.config.fetch(configId)).openRaffleCount;
+
+		return {
+			provider,
+			raffleProgram,
+			createRaffle,
+			raffleAccountId,
+			openRaffleCount,
+		};
+	};
+
+	it("should reject raffles beyond the limit until one concludes", async () => {
+		const { raffleProgram, createRaffle, raffleAccountId, openRaffleCount } =
+			await setup();
+
+		await raffleProgram.methods.setMaxOpenRaffles(2).rpc();
+		await createRaffle();
+		await createRaffle();
+		expect(await openRaffleCount()).toBe(2);
+		expect(createRaffle()).rejects.toThrow(/OpenRaffleLimitReached/);
+
+		// A cancelled raffle no longer counts as open
+		await raffleProgram.methods
+			.cancelRaffle()
+			.accounts({ raffle: raffleAccountId(0) })
+			.rpc();
+		expect(await openRaffleCount()).toBe(1);
+		await createRaffle();
+		expect(await openRaffleCount()).toBe(2);
+
+		// Lifting the limit allows any number of raffles again
+		await raffleProgram.methods.setMaxOpenRaffles(0).rpc();
+		await createRaffle();
+		expect(await openRaffleCount()).toBe(3);
+	});
+
+	it("should only let the management authority set the limit", async () => {
+		const { provider, raffleProgram } = await setup();
+
+		const other = new Keypair();
+		provider.client.airdrop(other.publicKey, BigInt(LAMPORTS_PER_SOL));
+		expect(
+			raffleProgram.methods
+				.setMaxOpenRaffles(1)
+				.accounts({ managementAuthority: other.publicKey })
+				.signers([other])
+				.rpc(),
+		).rejects.toThrow(/NotProgramManagementAuthority/);
+	});
+});
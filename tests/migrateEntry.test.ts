@@ -0,0 +1,96 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+const ENTRY_DISCRIMINATOR = [63, 18, 152, 113, 215, 246, 221, 250];
+const ENTRY_V1_ACCOUNT_SIZE = 96;
+
+// Encodes an entry with the v1 layout of the first deployed program
+const entryV1Data = (
+	raffle: PublicKey,
+	owner: PublicKey,
+	seed: Uint8Array,
+	ticketCount: number,
+	ticketStartIndex: number,
+) => {
+	const data = Buffer.alloc(ENTRY_V1_ACCOUNT_SIZE);
+	Buffer.from(ENTRY_DISCRIMINATOR).copy(data, 0);
+	raffle.toBuffer().copy(data, 8);
+	owner.toBuffer().copy(data, 40);
+	data.writeBigUInt64LE(BigInt(ticketCount), 72);
+	data.writeBigUInt64LE(BigInt(ticketStartIndex), 80);
+	Buffer.from(seed).copy(data, 88);
+	return data;
+};
+
+describe("migrate_entry_v1_to_v2", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		const raffle = new Keypair().publicKey;
+		const owner = new Keypair().publicKey;
+		const seed = new Uint8Array(8).fill(3);
+		const entryAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("entry"), raffle.toBuffer(), seed],
+			raffleProgram.programId,
+		)[0];
+		client.setAccount(entryAccountId, {
+			executable: false,
+			owner: raffleProgram.programId,
+			lamports: LAMPORTS_PER_SOL / 100,
+			data: entryV1Data(raffle, owner, seed, 5, 10),
+		});
+
+		return { raffleProgram, raffle, owner, seed, entryAccountId };
+	};
+
+	it("should migrate a v1 entry in place", async () => {
+		const { raffleProgram, raffle, owner, seed, entryAccountId } =
+			await setup();
+
+		await raffleProgram.methods
+			.migrateEntryV1ToV2()
+			.accounts({ entry: entryAccountId })
+			.rpc();
+
+		const entry = await raffleProgram.account.entry.fetch(entryAccountId);
+		expect(entry.raffle.equals(raffle)).toBeTrue();
+		expect(entry.owner.equals(owner)).toBeTrue();
+		expect(entry.ticketCount.toNumber()).toBe(5);
+		expect(entry.ticketStartIndex.toNumber()).toBe(10);
+		expect(entry.seed).toEqual(Array.from(seed));
+		expect(entry.memo).toBeNull();
+		expect(entry.nextEntry).toBeNull();
+		expect(entry.rentSponsored).toBeFalse();
+		expect(entry.entryIndex.toNumber()).toBe(0);
+		expect(entry.version).toBe(2);
+		expect(entry.bump).toBe(
+			PublicKey.findProgramAddressSync(
+				[Buffer.from("entry"), raffle.toBuffer(), seed],
+				raffleProgram.programId,
+			)[1],
+		);
+		expect(entry.createdAt.eq(new BN(0))).toBeTrue();
+	});
+
+	it("should fail to migrate an entry twice", async () => {
+		const { raffleProgram, entryAccountId } = await setup();
+
+		await raffleProgram.methods
+			.migrateEntryV1ToV2()
+			.accounts({ entry: entryAccountId })
+			.rpc();
+
+		expect(
+			raffleProgram.methods
+				.migrateEntryV1ToV2()
+				.accounts({ entry: entryAccountId })
+				.rpc(),
+		).rejects.toThrow(/EntryNotLegacy/);
+	});
+});
@@ -0,0 +1,129 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("min_unique_buyers", async () => {
+	const setup = async (minUniqueBuyers: BN | null) => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(1),
+				new BN(20),
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				minUniqueBuyers,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		const buy = async (seed: number) => {
+			const buyer = new Keypair();
+			provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+			await raffleProgram.methods
+				.initTicketBalance(null)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+			await raffleProgram.methods
+				.buyTickets(
+					new BN(5),
+					Array.from(new Uint8Array([seed, 0, 0, 0, 0, 0, 0, 0])),
+					null,
+					null,
+					null,
+					null,
+					false,
+					null,
+				)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+		};
+
+		const endRaffle = () => {
+			const newClock = client.getClock();
+			newClock.unixTimestamp = creationTime + BigInt(3601);
+			client.setClock(newClock);
+		};
+
+		const draw = () =>
+			raffleProgram.methods
+				.drawWinningTicket()
+				.accounts({
+					raffle: raffleAccountId,
+					recentSlothashes: new PublicKey(
+						"SysvarS1otHashes111111111111111111111111111",
+					),
+				})
+				.rpc();
+
+		return { raffleProgram, raffleAccountId, buy, endRaffle, draw };
+	};
+
+	it("should refuse to draw with too few unique buyers", async () => {
+		const { raffleProgram, raffleAccountId, buy, endRaffle, draw } =
+			await setup(new BN(2));
+
+		await buy(0);
+		endRaffle();
+
+		expect(draw()).rejects.toThrow(/InsufficientUniqueBuyers/);
+
+		// The raffle can be expired instead so buyers get refunded
+		await raffleProgram.methods
+			.expireRaffle()
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.raffleState.expired).toBeDefined();
+	});
+
+	it("should draw once enough unique buyers participated", async () => {
+		const { raffleProgram, raffleAccountId, buy, endRaffle, draw } =
+			await setup(new BN(2));
+
+		await buy(0);
+		await buy(1);
+		endRaffle();
+
+		await draw();
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.minUniqueBuyers?.toNumber()).toBe(2);
+		expect(raffle.raffleState.drawing).toBeDefined();
+	});
+
+	it("should fail to create a raffle requiring zero unique buyers", async () => {
+		expect(setup(new BN(0))).rejects.toThrow(/InvalidMinUniqueBuyers/);
+	});
+});
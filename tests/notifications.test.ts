@@ -0,0 +1,284 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("notifications", async () => {
+	const setup = async (anonymizeWinner = false) => {
+		const client = fromWorkspace(".");
+		client.withSysvars();
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		const metadataUri = "https://www.example.org";
+		const ticketPrice = new BN(0.1 * LAMPORTS_PER_SOL);
+		const minTickets = new BN(1);
+		await raffleProgram.methods
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				new BN((creationTime + BigInt(3601)).toString()),
+				minTickets,
+				null,
+				null,
+				anonymizeWinner,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+		const treasuryId = PublicKey.findProgramAddressSync(
+			[Buffer.from("treasury"), raffleAccountId.toBytes()],
+			raffleProgram.programId,
+		)[0];
+
+		// Write the winning entry of the winner
+		const winner = new Keypair();
+		const entrySeed = crypto.getRandomValues(new Uint8Array(8));
+		const entryAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("entry"), raffleAccountId.toBytes(), entrySeed],
+			raffleProgram.programId,
+		)[0];
+		provider.client.setAccount(entryAccountId, {
+			executable: false,
+			owner: raffleProgram.programId,
+			lamports: LAMPORTS_PER_SOL,
+			data: await raffleProgram.coder.accounts.encode("entry", {
+				raffle: raffleAccountId,
+				owner: winner.publicKey,
+				ticketCount: new BN(1),
+				ticketStartIndex: new BN(0),
+				seed: Array.from(entrySeed),
+				entryIndex: new BN(0),
+				version: 2,
+				bump: 0,
+				createdAt: new BN(0),
+			}),
+		});
+
+		// Write the winner's ticket balance, committing to a secret
+		const [ticketBalanceId, ticketBalanceBump] =
+			PublicKey.findProgramAddressSync(
+				[
+					Buffer.from("ticket_balance"),
+					raffleAccountId.toBytes(),
+					winner.publicKey.toBytes(),
+				],
+				raffleProgram.programId,
+			);
+		provider.client.setAccount(ticketBalanceId, {
+			executable: false,
+			owner: raffleProgram.programId,
+			lamports: LAMPORTS_PER_SOL,
+			data: await raffleProgram.coder.accounts.encode("ticketBalance", {
+				owner: winner.publicKey,
+				ticketCount: new BN(1),
+				bump: ticketBalanceBump,
+				headEntry: entryAccountId,
+				totalSpent: new BN(0),
+				tierDiscount: new BN(0),
+				rollover: false,
+				winnerCommitment: Array.from(
+					crypto.getRandomValues(new Uint8Array(32)),
+				),
+				campaignSubsidy: new BN(0),
+				subsidyCampaign: null,
+			}),
+		});
+
+		// Move the raffle to drawing with the winner's ticket drawn
+		const raffleData = await raffleProgram.coder.accounts.encode("raffle", {
+			metadataUri,
+			ticketPrice,
+			minTickets,
+			endTime: new BN(creationTime.toString()),
+			treasury: treasuryId,
+			currentTickets: new BN(1),
+			creationTime: new BN(creationTime.toString()),
+			raffleState: { drawing: {} },
+			winnerAddress: null,
+			winningTicket: new BN(0),
+			maxTickets: null,
+			participantCount: new BN(1),
+			eventSeq: new BN(0),
+			anonymizeWinner,
+			minRevenue: null,
+			minDrawSlot: new BN(0),
+			reservedTickets: new BN(0),
+			refundFeeBps: 0,
+			rentPayer: provider.publicKey,
+			features: 0,
+			allowedRegions: new BN(0),
+			ageRestricted: false,
+			drawAuthority: null,
+			priorityMinTier: 0,
+			priorityUntil: new BN(0),
+			minUniqueBuyers: null,
+			paymentMint: null,
+			randomnessMode: { slotHashes: {} },
+			randomnessAccount: null,
+			randomnessCommitSlot: new BN(0),
+			prizeEscrowed: false,
+			winnerCount: 1,
+			winningTickets: [],
+			additionalWinners: [],
+			winnerDataSubmitted: 0,
+			prizesClaimed: 0,
+			maxTicketsPerWallet: null,
+			gate: null,
+			deferredNumbering: false,
+			entryCount: new BN(0),
+			numberedEntries: new BN(0),
+			numberedTickets: new BN(0),
+			feeBpsOverride: null,
+			snapshotRoot: null,
+			operatorStatus: 0,
+			statusNoteHash: null,
+			fundingDeadline: null,
+			pricingTiers: [],
+			tierDiscounts: new BN(0),
+			crankRewardBps: 0,
+			referralFeeBps: 0,
+			extensionCount: 0,
+			extendedSecs: new BN(0),
+			refundDeadline: new BN(0),
+			prizeValueUsdCents: null,
+			ticketCountDisplayCap: null,
+			ticketReceipts: false,
+			arweaveTxId: null,
+			creator: null,
+			drawDelaySlots: 0,
+			drawTargetSlot: new BN(0),
+			refundMint: null,
+			refundRate: new BN(0), drawValueLamports: new BN(0), drawUsdRate: null, successor: null, randomnessProvider: null, randomnessCommitment: null, accessCodeHash: null, closeReason: 0, crankRewardPaid: false, additionalWinnerHashes: [], voidedRanges: [], pendingReservations: new BN(0), openSlotReleased: false, campaignSubsidies: new BN(0),
+		});
+		provider.client.setAccount(raffleAccountId, {
+			executable: false,
+			owner: raffleProgram.programId,
+			lamports: LAMPORTS_PER_SOL,
+			// Leave room for the winner to be set
+			data: Buffer.concat([
+				raffleData,
+				Buffer.alloc(raffleProgram.account.raffle.size - raffleData.length),
+			]),
+		});
+
+		const notificationsOf = (wallet: PublicKey) =>
+			PublicKey.findProgramAddressSync(
+				[Buffer.from("notifications"), wallet.toBytes()],
+				raffleProgram.programId,
+			)[0];
+		const initNotifications = (wallet: PublicKey) =>
+			raffleProgram.methods.initNotifications(wallet).rpc();
+		const setWinner = (notifications: PublicKey | null) =>
+			raffleProgram.methods
+				.setWinner(Array.from(entrySeed), 0)
+				.accounts({
+					raffle: raffleAccountId,
+					notifications,
+					winnerTicketBalance: ticketBalanceId,
+				})
+				.rpc();
+
+		return {
+			client,
+			raffleProgram,
+			raffleAccountId,
+			winner,
+			notificationsOf,
+			initNotifications,
+			setWinner,
+		};
+	};
+
+	it("should record the win in the winner's notifications", async () => {
+		const {
+			client,
+			raffleProgram,
+			raffleAccountId,
+			winner,
+			notificationsOf,
+			initNotifications,
+			setWinner,
+		} = await setup();
+
+		await initNotifications(winner.publicKey);
+		let notifications = await raffleProgram.account.notifications.fetch(
+			notificationsOf(winner.publicKey),
+		);
+		expect(notifications.wallet.equals(winner.publicKey)).toBeTrue();
+		expect(notifications.totalCount.toNumber()).toBe(0);
+		expect(notifications.records).toHaveLength(0);
+
+		await setWinner(notificationsOf(winner.publicKey));
+		notifications = await raffleProgram.account.notifications.fetch(
+			notificationsOf(winner.publicKey),
+		);
+		expect(notifications.totalCount.toNumber()).toBe(1);
+		expect(notifications.records).toHaveLength(1);
+		expect(notifications.records[0].raffle.equals(raffleAccountId)).toBeTrue();
+		expect(notifications.records[0].wonAt.toString()).toBe(
+			client.getClock().unixTimestamp.toString(),
+		);
+	});
+
+	it("should still set the winner without notifications", async () => {
+		const { raffleProgram, raffleAccountId, winner, setWinner } =
+			await setup();
+
+		await setWinner(null);
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.winnerAddress?.equals(winner.publicKey)).toBeTrue();
+	});
+
+	it("should reject notifications of another wallet", async () => {
+		const { notificationsOf, initNotifications, setWinner } = await setup();
+		const other = new Keypair().publicKey;
+
+		await initNotifications(other);
+		expect(setWinner(notificationsOf(other))).rejects.toThrow(
+			/InvalidNotifications/,
+		);
+	});
+
+	it("should not reveal anonymized winners", async () => {
+		const {
+			raffleProgram,
+			winner,
+			notificationsOf,
+			initNotifications,
+			setWinner,
+		} = await setup(true);
+
+		await initNotifications(winner.publicKey);
+		await setWinner(notificationsOf(winner.publicKey));
+		const notifications = await raffleProgram.account.notifications.fetch(
+			notificationsOf(winner.publicKey),
+		);
+		expect(notifications.totalCount.toNumber()).toBe(0);
+		expect(notifications.records).toHaveLength(0);
+	});
+});
@@ -0,0 +1,90 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("notifier", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const createRaffle = (notifierProgram: PublicKey | null) =>
+			raffleProgram.methods
+				.createRaffle(
+					"https://www.example.org",
+					new BN(0.1 * LAMPORTS_PER_SOL),
+					new BN(
+						(client.getClock().unixTimestamp + BigInt(3601)).toString(),
+					),
+					new BN(1),
+					null,
+					null,
+					false,
+					null,
+					false,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+				)
+				.accounts({ notifierProgram })
+				.rpc();
+
+		return { provider, raffleProgram, createRaffle };
+	};
+
+	it("should require the registered notifier program once one is set", async () => {
+		const { raffleProgram, createRaffle } = await setup();
+
+		// Nothing is notified without a registered notifier
+		await createRaffle(null);
+
+		const notifierProgram = new Keypair().publicKey;
+		await raffleProgram.methods.setNotifierProgram(notifierProgram).rpc();
+		const configId = PublicKey.findProgramAddressSync(
+			[Buffer.from("config")],
+			raffleProgram.programId,
+		)[0];
+		const config = await raffleProgram.account.config.fetch(configId);
+		expect(config.notifierProgram?.equals(notifierProgram)).toBeTrue();
+
+		expect(createRaffle(null)).rejects.toThrow(/NotifierProgramRequired/);
+		expect(createRaffle(new Keypair().publicKey)).rejects.toThrow(
+			/InvalidNotifierProgram/,
+		);
+
+		// Unregistering the notifier stops the notifications
+		await raffleProgram.methods.setNotifierProgram(null).rpc();
+		await createRaffle(null);
+	});
+
+	it("should only let the management authority register a notifier", async () => {
+		const { provider, raffleProgram } = await setup();
+
+		const other = new Keypair();
+		provider.client.airdrop(other.publicKey, BigInt(LAMPORTS_PER_SOL));
+		expect(
+			raffleProgram.methods
+				.setNotifierProgram(new Keypair().publicKey)
+				.accounts({ managementAuthority: other.publicKey })
+				.signers([other])
+				.rpc(),
+		).rejects.toThrow(/NotProgramManagementAuthority/);
+	});
+});
@@ -0,0 +1,233 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("pay_top_holders", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+		const payoutAuthority = new Keypair();
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: payoutAuthority.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		const metadataUri = "https://www.example.org";
+		const ticketPrice = new BN(0.1 * LAMPORTS_PER_SOL);
+		const minTickets = new BN(1);
+		const endTime = new BN((creationTime + BigInt(3601)).toString());
+
+		// Create a raffle splitting half of the treasury among its top 2 holders
+		await raffleProgram.methods
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				5000,
+				2,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+		const leaderboardId = PublicKey.findProgramAddressSync(
+			[Buffer.from("leaderboard"), raffleAccountId.toBytes()],
+			raffleProgram.programId,
+		)[0];
+
+		await raffleProgram.methods
+			.initLeaderboard()
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		return {
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			leaderboardId,
+			payoutAuthority,
+		};
+	};
+
+	const buyTickets = async (
+		provider: LiteSVMProvider,
+		raffleProgram: Program<RaffleProgram>,
+		raffleAccountId: PublicKey,
+		leaderboardId: PublicKey | null,
+		ticketCount: number,
+	) => {
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+		await raffleProgram.methods
+			.buyTickets(
+				new BN(ticketCount),
+				Array.from(new Uint8Array(8)),
+				null,
+				null,
+				null,
+				null,
+				false,
+				null,
+			)
+			.accounts({
+				signer: buyer.publicKey,
+				raffle: raffleAccountId,
+				leaderboard: leaderboardId,
+			})
+			.signers([buyer])
+			.rpc();
+		return buyer;
+	};
+
+	it("should split the prize among the largest holders in proportion to their tickets", async () => {
+		const {
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			leaderboardId,
+			payoutAuthority,
+		} = await setup();
+
+		// The smallest holder is pushed off the leaderboard by the largest one
+		await buyTickets(
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			leaderboardId,
+			1,
+		);
+		const second = await buyTickets(
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			leaderboardId,
+			3,
+		);
+		const first = await buyTickets(
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			leaderboardId,
+			6,
+		);
+
+		const leaderboard =
+			await raffleProgram.account.leaderboard.fetch(leaderboardId);
+		expect(leaderboard.holders.map((h) => h.owner.toBase58())).toEqual([
+			first.publicKey.toBase58(),
+			second.publicKey.toBase58(),
+		]);
+
+		// Manually set the raffle state to drawn
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		const raffleData = await raffleProgram.coder.accounts.encode("raffle", {
+			...raffle,
+			raffleState: {
+				drawn: {},
+			},
+		});
+		provider.client.setAccount(raffleAccountId, {
+			executable: false,
+			owner: raffleProgram.programId,
+			lamports: 1 * LAMPORTS_PER_SOL,
+			data: raffleData,
+		});
+
+		const firstBalanceBefore = provider.client.getBalance(first.publicKey);
+		const secondBalanceBefore = provider.client.getBalance(second.publicKey);
+		if (!firstBalanceBefore || !secondBalanceBefore) {
+			throw new Error("Failed to fetch balance");
+		}
+
+		await raffleProgram.methods
+			.payTopHolders()
+			.accounts({
+				raffle: raffleAccountId,
+				payoutAuthority: payoutAuthority.publicKey,
+			})
+			.remainingAccounts([
+				{ pubkey: first.publicKey, isSigner: false, isWritable: true },
+				{ pubkey: second.publicKey, isSigner: false, isWritable: true },
+			])
+			.rpc();
+
+		// 10 tickets sold for 1 SOL, of which 0.5 SOL is split 6:3
+		expect(provider.client.getBalance(first.publicKey)).toBe(
+			firstBalanceBefore + BigInt(333_333_333),
+		);
+		expect(provider.client.getBalance(second.publicKey)).toBe(
+			secondBalanceBefore + BigInt(166_666_666),
+		);
+		expect(provider.client.getBalance(payoutAuthority.publicKey)).toBe(
+			BigInt(500_000_001),
+		);
+	});
+
+	it("should require the leaderboard for ticket purchases", async () => {
+		const { provider, raffleProgram, raffleAccountId } = await setup();
+
+		expect(
+			buyTickets(provider, raffleProgram, raffleAccountId, null, 1),
+		).rejects.toThrow(/InvalidLeaderboard/);
+	});
+
+	it("should fail to pay a single winner", async () => {
+		const { provider, raffleProgram, raffleAccountId, payoutAuthority } =
+			await setup();
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		const winner = new Keypair();
+		const raffleData = await raffleProgram.coder.accounts.encode("raffle", {
+			...raffle,
+			raffleState: {
+				drawn: {},
+			},
+			winnerAddress: winner.publicKey,
+		});
+		provider.client.setAccount(raffleAccountId, {
+			executable: false,
+			owner: raffleProgram.programId,
+			lamports: 1 * LAMPORTS_PER_SOL,
+			data: raffleData,
+		});
+
+		expect(
+			raffleProgram.methods
+				.payWinner()
+				.accounts({
+					raffle: raffleAccountId,
+					winner: winner.publicKey,
+					payoutAuthority: payoutAuthority.publicKey,
+				})
+				.rpc(),
+		).rejects.toThrow(/TopHoldersRaffle/);
+	});
+});
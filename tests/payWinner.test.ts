@@ -0,0 +1,190 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("pay_winner", async () => {
+	const setup = async (cashPrizeBps: number | null) => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+		const payoutAuthority = new Keypair();
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: payoutAuthority.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		const metadataUri = "https://www.example.org";
+		const ticketPrice = new BN(0.1 * LAMPORTS_PER_SOL);
+		const minTickets = new BN(1);
+		const endTime = new BN((creationTime + BigInt(3601)).toString());
+
+		// Create raffle
+		await raffleProgram.methods
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				cashPrizeBps,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		// Sell 1 SOL worth of tickets
+		const winner = new Keypair();
+		provider.client.airdrop(winner.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: winner.publicKey, raffle: raffleAccountId })
+			.signers([winner])
+			.rpc();
+		await raffleProgram.methods
+			.buyTickets(new BN(10), Array.from(new Uint8Array(8)), null, null, null, null, false, null)
+			.accounts({ signer: winner.publicKey, raffle: raffleAccountId })
+			.signers([winner])
+			.rpc();
+
+		// Manually set the raffle state to drawn
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		const raffleData = await raffleProgram.coder.accounts.encode("raffle", {
+			...raffle,
+			raffleState: {
+				drawn: {},
+			},
+			winnerAddress: winner.publicKey,
+			winningTicket: new BN(3),
+		});
+		provider.client.setAccount(raffleAccountId, {
+			executable: false,
+			owner: raffleProgram.programId,
+			lamports: 1 * LAMPORTS_PER_SOL,
+			data: raffleData,
+		});
+
+		return {
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			winner,
+			payoutAuthority,
+		};
+	};
+
+	it("should split the treasury between the winner and the payout authority", async () => {
+		const {
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			winner,
+			payoutAuthority,
+		} = await setup(8000);
+
+		const winnerBalanceBefore = provider.client.getBalance(winner.publicKey);
+		if (!winnerBalanceBefore) {
+			throw new Error("Failed to fetch balance");
+		}
+
+		await raffleProgram.methods
+			.payWinner()
+			.accounts({
+				raffle: raffleAccountId,
+				winner: winner.publicKey,
+				payoutAuthority: payoutAuthority.publicKey,
+			})
+			.rpc();
+
+		expect(provider.client.getBalance(winner.publicKey)).toBe(
+			winnerBalanceBefore + BigInt(0.8 * LAMPORTS_PER_SOL),
+		);
+		expect(provider.client.getBalance(payoutAuthority.publicKey)).toBe(
+			BigInt(0.2 * LAMPORTS_PER_SOL),
+		);
+
+		// The paid out raffle no longer counts as open
+		const configId = PublicKey.findProgramAddressSync(
+			[Buffer.from("config")],
+			raffleProgram.programId,
+		)[0];
+		const config = await raffleProgram.account.config.fetch(configId);
+		expect(config.openRaffleCount).toBe(0);
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.openSlotReleased).toBeTrue();
+
+		// Nothing is left to pay out
+		expect(
+			raffleProgram.methods
+				.payWinner()
+				.accounts({
+					raffle: raffleAccountId,
+					winner: winner.publicKey,
+					payoutAuthority: payoutAuthority.publicKey,
+				})
+				.rpc(),
+		).rejects.toThrow(/InsufficientFunds/);
+	});
+
+	it("should keep the treasury from being withdrawn directly", async () => {
+		const { raffleProgram, raffleAccountId, payoutAuthority } =
+			await setup(8000);
+
+		expect(
+			raffleProgram.methods
+				.withdrawFromTreasury()
+				.accounts({
+					raffle: raffleAccountId,
+					payoutAuthority: payoutAuthority.publicKey,
+				})
+				.rpc(),
+		).rejects.toThrow(/CashPrizeRaffle/);
+	});
+
+	it("should fail for the wrong winner or a raffle without a cash prize", async () => {
+		const cashPrize = await setup(8000);
+		expect(
+			cashPrize.raffleProgram.methods
+				.payWinner()
+				.accounts({
+					raffle: cashPrize.raffleAccountId,
+					winner: new Keypair().publicKey,
+					payoutAuthority: cashPrize.payoutAuthority.publicKey,
+				})
+				.rpc(),
+		).rejects.toThrow(/NotWinner/);
+
+		const noCashPrize = await setup(null);
+		expect(
+			noCashPrize.raffleProgram.methods
+				.payWinner()
+				.accounts({
+					raffle: noCashPrize.raffleAccountId,
+					winner: noCashPrize.winner.publicKey,
+					payoutAuthority: noCashPrize.payoutAuthority.publicKey,
+				})
+				.rpc(),
+		).rejects.toThrow(/NoCashPrize/);
+	});
+});
@@ -0,0 +1,237 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("platform_fee", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		const payoutAuthority = new Keypair();
+		provider.client.airdrop(
+			payoutAuthority.publicKey,
+			BigInt(0.1 * LAMPORTS_PER_SOL),
+		);
+		const feeRecipient = new Keypair();
+		provider.client.airdrop(
+			feeRecipient.publicKey,
+			BigInt(0.1 * LAMPORTS_PER_SOL),
+		);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: payoutAuthority.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(1),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+		const treasuryId = PublicKey.findProgramAddressSync(
+			[Buffer.from("treasury"), raffleAccountId.toBytes()],
+			raffleProgram.programId,
+		)[0];
+
+		// Sell 5 tickets, 0.5 SOL of revenue
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+		await raffleProgram.methods
+			.buyTickets(new BN(5), Array.from(new Uint8Array(8)), null, null, null, null, false, null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+
+		// Manually set the raffle state to drawn
+		const drawRaffle = async () => {
+			const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+			const raffleData = await raffleProgram.coder.accounts.encode("raffle", {
+				...raffle,
+				raffleState: { drawn: {} },
+				winnerAddress: new Keypair().publicKey,
+			});
+			provider.client.setAccount(raffleAccountId, {
+				executable: false,
+				owner: raffleProgram.programId,
+				lamports: 1 * LAMPORTS_PER_SOL,
+				data: raffleData,
+			});
+		};
+
+		const setPlatformFee = (feeBps: number, recipient: PublicKey | null) =>
+			raffleProgram.methods.setPlatformFee(feeBps, recipient).rpc();
+
+		const withdraw = (feeRecipientId: PublicKey | null) =>
+			raffleProgram.methods
+				.withdrawFromTreasury()
+				.accounts({
+					raffle: raffleAccountId,
+					payoutAuthority: payoutAuthority.publicKey,
+					feeRecipient: feeRecipientId,
+				})
+				.rpc();
+
+		return {
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			treasuryId,
+			payoutAuthority,
+			feeRecipient,
+			drawRaffle,
+			setPlatformFee,
+			withdraw,
+		};
+	};
+
+	it("should split the withdrawal between the payout authority and the fee recipient", async () => {
+		const {
+			provider,
+			raffleProgram,
+			treasuryId,
+			payoutAuthority,
+			feeRecipient,
+			drawRaffle,
+			setPlatformFee,
+			withdraw,
+		} = await setup();
+
+		await setPlatformFee(1_000, feeRecipient.publicKey);
+		await drawRaffle();
+
+		const payoutBefore = provider.client.getBalance(payoutAuthority.publicKey)!;
+		const feeBefore = provider.client.getBalance(feeRecipient.publicKey)!;
+		await withdraw(feeRecipient.publicKey);
+
+		expect(
+			provider.client.getBalance(payoutAuthority.publicKey)! - payoutBefore,
+		).toEqual(BigInt(0.45 * LAMPORTS_PER_SOL));
+		expect(
+			provider.client.getBalance(feeRecipient.publicKey)! - feeBefore,
+		).toEqual(BigInt(0.05 * LAMPORTS_PER_SOL));
+
+		// The fee counts towards the treasury's withdrawals
+		const treasury = await raffleProgram.account.treasury.fetch(treasuryId);
+		expect(treasury.totalWithdrawn.toNumber()).toBe(0.5 * LAMPORTS_PER_SOL);
+	});
+
+	it("should charge the raffle's fee override instead of the config's fee", async () => {
+		const {
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			payoutAuthority,
+			feeRecipient,
+			drawRaffle,
+			setPlatformFee,
+			withdraw,
+		} = await setup();
+
+		await setPlatformFee(1_000, feeRecipient.publicKey);
+		await raffleProgram.methods
+			.setRaffleFee(200)
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+		await drawRaffle();
+
+		const payoutBefore = provider.client.getBalance(payoutAuthority.publicKey)!;
+		const feeBefore = provider.client.getBalance(feeRecipient.publicKey)!;
+		await withdraw(feeRecipient.publicKey);
+
+		expect(
+			provider.client.getBalance(payoutAuthority.publicKey)! - payoutBefore,
+		).toEqual(BigInt(0.49 * LAMPORTS_PER_SOL));
+		expect(
+			provider.client.getBalance(feeRecipient.publicKey)! - feeBefore,
+		).toEqual(BigInt(0.01 * LAMPORTS_PER_SOL));
+	});
+
+	it("should not charge a fee while the config has no fee recipient", async () => {
+		const { provider, payoutAuthority, drawRaffle, setPlatformFee, withdraw } =
+			await setup();
+
+		await setPlatformFee(1_000, null);
+		await drawRaffle();
+
+		const payoutBefore = provider.client.getBalance(payoutAuthority.publicKey)!;
+		await withdraw(null);
+
+		expect(
+			provider.client.getBalance(payoutAuthority.publicKey)! - payoutBefore,
+		).toEqual(BigInt(0.5 * LAMPORTS_PER_SOL));
+	});
+
+	it("should fail to pay the fee to an account other than the fee recipient", async () => {
+		const { feeRecipient, drawRaffle, setPlatformFee, withdraw } = await setup();
+
+		await setPlatformFee(1_000, feeRecipient.publicKey);
+		await drawRaffle();
+
+		expect(withdraw(null)).rejects.toThrow(/InvalidFeeRecipient/);
+		expect(withdraw(new Keypair().publicKey)).rejects.toThrow(
+			/InvalidFeeRecipient/,
+		);
+	});
+
+	it("should fail to set a fee above the maximum", async () => {
+		const { raffleProgram, raffleAccountId, feeRecipient, setPlatformFee } =
+			await setup();
+
+		expect(setPlatformFee(2_001, feeRecipient.publicKey)).rejects.toThrow(
+			/InvalidPlatformFee/,
+		);
+		expect(
+			raffleProgram.methods
+				.setRaffleFee(2_001)
+				.accounts({ raffle: raffleAccountId })
+				.rpc(),
+		).rejects.toThrow(/InvalidPlatformFee/);
+	});
+
+	it("should fail to override the fee of a concluded raffle", async () => {
+		const { raffleProgram, raffleAccountId, drawRaffle } = await setup();
+
+		await drawRaffle();
+
+		expect(
+			raffleProgram.methods
+				.setRaffleFee(0)
+				.accounts({ raffle: raffleAccountId })
+				.rpc(),
+		).rejects.toThrow(/RaffleNotOpen/);
+	});
+});
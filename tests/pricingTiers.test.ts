@@ -0,0 +1,229 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+const TICKET_PRICE = 0.1 * LAMPORTS_PER_SOL;
+
+describe("pricing_tiers", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		const createRaffle = (
+			pricingTiers: { quantityThreshold: BN; pricePerTicket: BN }[] | null,
+		) =>
+			raffleProgram.methods
+				.createRaffle(
+					"https://www.example.org",
+					new BN(TICKET_PRICE),
+					new BN((creationTime + BigInt(3601)).toString()),
+					new BN(50),
+					null,
+					null,
+					false,
+					null,
+					false,
+					null,
+					null,
+					null,
+					null,
+					pricingTiers,
+					null,
+					null,
+				)
+				.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(5 * LAMPORTS_PER_SOL));
+		// Purchases are bounded by the list price unless another total is given
+		const buy = async (
+			ticketCount: number,
+			seed: number,
+			expectedTotal: BN | null = new BN(ticketCount * TICKET_PRICE),
+		) => {
+			const balanceBefore = provider.client.getBalance(buyer.publicKey)!;
+			await raffleProgram.methods
+				.buyTickets(
+					new BN(ticketCount),
+					Array.from(new Uint8Array([seed, 0, 0, 0, 0, 0, 0, 0])),
+					expectedTotal,
+					null,
+					null,
+					null,
+					false,
+					null,
+				)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+			return balanceBefore - provider.client.getBalance(buyer.publicKey)!;
+		};
+
+		const endRaffle = () => {
+			const newClock = client.getClock();
+			newClock.unixTimestamp = creationTime + BigInt(3602);
+			client.setClock(newClock);
+		};
+
+		return {
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			buyer,
+			createRaffle,
+			buy,
+			endRaffle,
+		};
+	};
+
+	const tier = (quantityThreshold: number, pricePerTicket: number) => ({
+		quantityThreshold: new BN(quantityThreshold),
+		pricePerTicket: new BN(pricePerTicket),
+	});
+
+	it("should charge the price of the highest tier reached", async () => {
+		const { raffleProgram, raffleAccountId, buyer, createRaffle, buy } =
+			await setup();
+
+		// 10 tickets for the price of 9, 20 tickets for the price of 16
+		await createRaffle([
+			tier(10, 0.09 * LAMPORTS_PER_SOL),
+			tier(20, 0.08 * LAMPORTS_PER_SOL),
+		]);
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+
+		// The entry rent is paid on top of the tickets
+		const smallPurchase = await buy(5, 0);
+		const tierPurchase = await buy(10, 1);
+		const topTierPurchase = await buy(25, 2);
+		expect(tierPurchase - smallPurchase).toEqual(
+			BigInt(0.4 * LAMPORTS_PER_SOL),
+		);
+		expect(topTierPurchase - smallPurchase).toEqual(
+			BigInt(1.5 * LAMPORTS_PER_SOL),
+		);
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.tierDiscounts.toString()).toBe(
+			(0.1 * LAMPORTS_PER_SOL + 0.5 * LAMPORTS_PER_SOL).toString(),
+		);
+	});
+
+	it("should refund tier purchases at the price paid", async () => {
+		const {
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			buyer,
+			createRaffle,
+			buy,
+			endRaffle,
+		} = await setup();
+
+		await createRaffle([tier(10, 0.09 * LAMPORTS_PER_SOL)]);
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+		await buy(10, 0);
+
+		endRaffle();
+		await raffleProgram.methods
+			.expireRaffle()
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		const ticketBalanceId = PublicKey.findProgramAddressSync(
+			[
+				Buffer.from("ticket_balance"),
+				raffleAccountId.toBytes(),
+				buyer.publicKey.toBytes(),
+			],
+			raffleProgram.programId,
+		)[0];
+		const ticketBalanceRent = provider.client.getBalance(ticketBalanceId)!;
+		const balanceBefore = provider.client.getBalance(buyer.publicKey)!;
+		await raffleProgram.methods
+			.reclaimExpiredTickets()
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+		expect(
+			provider.client.getBalance(buyer.publicKey)! -
+				balanceBefore -
+				ticketBalanceRent,
+		).toEqual(BigInt(0.9 * LAMPORTS_PER_SOL));
+	});
+
+	it("should fail without an expected total", async () => {
+		const { raffleProgram, raffleAccountId, buyer, createRaffle, buy } =
+			await setup();
+
+		await createRaffle([tier(10, 0.09 * LAMPORTS_PER_SOL)]);
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+
+		expect(buy(10, 0, null)).rejects.toThrow(/ExpectedTotalRequired/);
+		// The tier price must be within the expected total
+		expect(buy(10, 0, new BN(0.8 * LAMPORTS_PER_SOL))).rejects.toThrow(
+			/PriceExceedsExpectedTotal/,
+		);
+	});
+
+	it("should fail to create a raffle with an invalid schedule", async () => {
+		const { createRaffle } = await setup();
+
+		// Thresholds must start above a single ticket
+		expect(createRaffle([tier(1, 0.09 * LAMPORTS_PER_SOL)])).rejects.toThrow(
+			/InvalidPricingTiers/,
+		);
+		// Prices must be below the ticket price
+		expect(createRaffle([tier(10, TICKET_PRICE)])).rejects.toThrow(
+			/InvalidPricingTiers/,
+		);
+		// Larger purchases must be cheaper
+		expect(
+			createRaffle([
+				tier(10, 0.08 * LAMPORTS_PER_SOL),
+				tier(20, 0.09 * LAMPORTS_PER_SOL),
+			]),
+		).rejects.toThrow(/InvalidPricingTiers/);
+		// At most 4 tiers
+		expect(
+			createRaffle([
+				tier(2, 0.099 * LAMPORTS_PER_SOL),
+				tier(3, 0.098 * LAMPORTS_PER_SOL),
+				tier(4, 0.097 * LAMPORTS_PER_SOL),
+				tier(5, 0.096 * LAMPORTS_PER_SOL),
+				tier(6, 0.095 * LAMPORTS_PER_SOL),
+			]),
+		).rejects.toThrow(/InvalidPricingTiers/);
+	});
+});
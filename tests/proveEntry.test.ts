@@ -0,0 +1,89 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("prove_entry", async () => {
+	it("should return the owner, ticket range and raffle state of an entry", async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		const metadataUri = "https://www.example.org";
+		const ticketPrice = new BN(0.1 * LAMPORTS_PER_SOL);
+		const minTickets = new BN(1);
+		const endTime = new BN((creationTime + BigInt(3601)).toString());
+
+		// Create raffle
+		await raffleProgram.methods
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+
+		// Buy two entries, so the second one starts after the first
+		const entrySeeds = [new Uint8Array(8), new Uint8Array(8).fill(1)];
+		for (const entrySeed of entrySeeds) {
+			await raffleProgram.methods
+				.buyTickets(new BN(3), Array.from(entrySeed), null, null, null, null, false, null)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+		}
+		const entryId = PublicKey.findProgramAddressSync(
+			[Buffer.from("entry"), raffleAccountId.toBytes(), entrySeeds[1]],
+			raffleProgram.programId,
+		)[0];
+
+		const proof = await raffleProgram.methods
+			.proveEntry()
+			.accounts({ raffle: raffleAccountId, entry: entryId })
+			.view();
+
+		expect(proof.raffle.equals(raffleAccountId)).toBeTrue();
+		expect(proof.owner.equals(buyer.publicKey)).toBeTrue();
+		expect(proof.ticketStartIndex.toNumber()).toBe(3);
+		expect(proof.ticketCount.toNumber()).toBe(3);
+		expect(proof.raffleState.open).toBeDefined();
+	});
+});
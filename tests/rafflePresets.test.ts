@@ -0,0 +1,136 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+// Preset name as stored in the config, padded with zero bytes
+const presetName = (name: string) => {
+	const bytes = new Uint8Array(16);
+	bytes.set(Buffer.from(name));
+	return Array.from(bytes);
+};
+
+const weekly = {
+	durationSecs: new BN(7 * 24 * 60 * 60),
+	ticketPrice: new BN(0.5 * LAMPORTS_PER_SOL),
+	minTickets: new BN(10),
+	maxTickets: new BN(1000),
+};
+
+describe("raffle presets", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		await raffleProgram.methods
+			.setRafflePreset(presetName("weekly"), weekly)
+			.rpc();
+
+		const configAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("config")],
+			raffleProgram.programId,
+		)[0];
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		return {
+			client,
+			provider,
+			raffleProgram,
+			configAccountId,
+			raffleAccountId,
+		};
+	};
+
+	it("should create a raffle with the preset's terms", async () => {
+		const { client, raffleProgram, configAccountId, raffleAccountId } =
+			await setup();
+
+		const config = await raffleProgram.account.config.fetch(configAccountId);
+		expect(config.rafflePresets.length).toBe(1);
+		expect(config.rafflePresets[0].name).toEqual(presetName("weekly"));
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffleWithPreset("https://www.example.org", presetName("weekly"))
+			.rpc();
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.ticketPrice.toNumber()).toBe(0.5 * LAMPORTS_PER_SOL);
+		expect(raffle.minTickets.toNumber()).toBe(10);
+		expect(raffle.maxTickets?.toNumber()).toBe(1000);
+		expect(raffle.endTime.toString()).toBe(
+			(creationTime + BigInt(7 * 24 * 60 * 60)).toString(),
+		);
+	});
+
+	it("should fail for a preset that isn't registered", async () => {
+		const { raffleProgram } = await setup();
+
+		await raffleProgram.methods
+			.setRafflePreset(presetName("weekly"), null)
+			.rpc();
+
+		expect(
+			raffleProgram.methods
+				.createRaffleWithPreset("https://www.example.org", presetName("weekly"))
+				.rpc(),
+		).rejects.toThrow(/RafflePresetNotFound/);
+		expect(
+			raffleProgram.methods
+				.createRaffleWithPreset("https://www.example.org", presetName("flash"))
+				.rpc(),
+		).rejects.toThrow(/RafflePresetNotFound/);
+	});
+
+	it("should reject presets outside the allowed durations", async () => {
+		const { raffleProgram } = await setup();
+
+		expect(
+			raffleProgram.methods
+				.setRafflePreset(presetName("flash"), {
+					...weekly,
+					durationSecs: new BN(60),
+				})
+				.rpc(),
+		).rejects.toThrow(/InvalidRafflePreset/);
+		expect(
+			raffleProgram.methods
+				.setRafflePreset(presetName("quarterly"), {
+					...weekly,
+					durationSecs: new BN(90 * 24 * 60 * 60),
+				})
+				.rpc(),
+		).rejects.toThrow(/InvalidRafflePreset/);
+	});
+
+	it("should fail when not signed by the management authority", async () => {
+		const { provider, raffleProgram } = await setup();
+
+		const account = new Keypair();
+		provider.client.airdrop(account.publicKey, BigInt(LAMPORTS_PER_SOL));
+
+		expect(
+			raffleProgram.methods
+				.setRafflePreset(presetName("weekly"), null)
+				.accounts({ managementAuthority: account.publicKey })
+				.signers([account])
+				.rpc(),
+		).rejects.toThrow(/NotProgramManagementAuthority/);
+	});
+});
@@ -0,0 +1,201 @@
+import { describe, expect, it } from "bun:test";
+import { createHash } from "node:crypto";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("randomness providers", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(1),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		const buyTickets = async () => {
+			const buyer = new Keypair();
+			provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+			await raffleProgram.methods
+				.initTicketBalance(null)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+			await raffleProgram.methods
+				.buyTickets(new BN(5), Array.from(new Uint8Array(8)), null, null, null, null, false, null)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+		};
+
+		const endRaffle = () => {
+			const newClock = client.getClock();
+			newClock.unixTimestamp = newClock.unixTimestamp + BigInt(3601);
+			client.setClock(newClock);
+			client.warpToSlot(BigInt(100));
+		};
+
+		const secret = new Uint8Array(32).fill(9);
+		const commitment = Array.from(createHash("sha256").update(secret).digest());
+
+		return {
+			raffleProgram,
+			raffleAccountId,
+			buyTickets,
+			endRaffle,
+			secret,
+			commitment,
+		};
+	};
+
+	it("should draw a commit-reveal raffle from the revealed secret", async () => {
+		const {
+			raffleProgram,
+			raffleAccountId,
+			buyTickets,
+			endRaffle,
+			secret,
+			commitment,
+		} = await setup();
+
+		await raffleProgram.methods
+			.registerRandomnessProvider({ commitReveal: {} }, PublicKey.default)
+			.rpc();
+		await raffleProgram.methods
+			.setRandomnessProvider({ commitReveal: {} }, commitment)
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+		await buyTickets();
+		endRaffle();
+
+		expect(
+			raffleProgram.methods
+				.fulfillRandomness(Array.from(new Uint8Array(32)))
+				.accounts({ raffle: raffleAccountId, randomnessAccount: null })
+				.rpc(),
+		).rejects.toThrow(/InvalidRandomnessCommitment/);
+
+		await raffleProgram.methods
+			.fulfillRandomness(Array.from(secret))
+			.accounts({ raffle: raffleAccountId, randomnessAccount: null })
+			.rpc();
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.raffleState.drawing).toBeDefined();
+		expect(raffle.randomnessProvider?.commitReveal).toBeDefined();
+		expect(raffle.winningTicket?.toNumber()).toBeLessThan(5);
+	});
+
+	it("should draw from the SlotHashes provider", async () => {
+		const { raffleProgram, raffleAccountId, buyTickets, endRaffle } =
+			await setup();
+
+		await raffleProgram.methods
+			.registerRandomnessProvider({ slotHashes: {} }, PublicKey.default)
+			.rpc();
+		await raffleProgram.methods
+			.setRandomnessProvider({ slotHashes: {} }, null)
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+		await buyTickets();
+		endRaffle();
+
+		await raffleProgram.methods
+			.fulfillRandomness(null)
+			.accounts({
+				raffle: raffleAccountId,
+				randomnessAccount: new PublicKey(
+					"SysvarS1otHashes111111111111111111111111111",
+				),
+			})
+			.rpc();
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.raffleState.drawing).toBeDefined();
+		expect(raffle.drawSlotHash).not.toBeNull();
+	});
+
+	it("should fail to select a provider that is not registered", async () => {
+		const { raffleProgram, raffleAccountId, commitment } = await setup();
+
+		expect(
+			raffleProgram.methods
+				.setRandomnessProvider({ commitReveal: {} }, commitment)
+				.accounts({ raffle: raffleAccountId })
+				.rpc(),
+		).rejects.toThrow(/RandomnessProviderNotRegistered/);
+
+		// Removed providers can't be selected either
+		await raffleProgram.methods
+			.registerRandomnessProvider({ commitReveal: {} }, PublicKey.default)
+			.rpc();
+		await raffleProgram.methods
+			.registerRandomnessProvider({ commitReveal: {} }, null)
+			.rpc();
+		expect(
+			raffleProgram.methods
+				.setRandomnessProvider({ commitReveal: {} }, commitment)
+				.accounts({ raffle: raffleAccountId })
+				.rpc(),
+		).rejects.toThrow(/RandomnessProviderNotRegistered/);
+	});
+
+	it("should require a commitment for commit-reveal raffles only", async () => {
+		const { raffleProgram, raffleAccountId, commitment } = await setup();
+
+		await raffleProgram.methods
+			.registerRandomnessProvider({ commitReveal: {} }, PublicKey.default)
+			.rpc();
+		await raffleProgram.methods
+			.registerRandomnessProvider({ slotHashes: {} }, PublicKey.default)
+			.rpc();
+
+		expect(
+			raffleProgram.methods
+				.setRandomnessProvider({ commitReveal: {} }, null)
+				.accounts({ raffle: raffleAccountId })
+				.rpc(),
+		).rejects.toThrow(/InvalidRandomnessCommitment/);
+		expect(
+			raffleProgram.methods
+				.setRandomnessProvider({ slotHashes: {} }, commitment)
+				.accounts({ raffle: raffleAccountId })
+				.rpc(),
+		).rejects.toThrow(/InvalidRandomnessCommitment/);
+	});
+});
@@ -45,7 +45,24 @@ describe("reclaim_expired_tickets", async () => {
 
 			// Create raffle
 			await raffleProgram.methods
-				.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+				.createRaffle(
+					metadataUri,
+					ticketPrice,
+					endTime,
+					minTickets,
+					null,
+					null,
+					false,
+					null,
+					false,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+				)
 				.rpc();
 			const raffleAccountId = PublicKey.findProgramAddressSync(
 				[
@@ -86,7 +103,7 @@ describe("reclaim_expired_tickets", async () => {
 
 			// Initialize ticket balance
 			await raffleProgram.methods
-				.initTicketBalance()
+				.initTicketBalance(null)
 				.accounts({
 					signer: buyer.publicKey,
 					raffle: new PublicKey(raffleAccountId),
@@ -101,7 +118,7 @@ describe("reclaim_expired_tickets", async () => {
 
 			// Purchase tickets
 			await raffleProgram.methods
-				.buyTickets(ticketsToPurchase, Array.from(entrySeed))
+				.buyTickets(ticketsToPurchase, Array.from(entrySeed), null, null, null, null, false, null)
 				.accounts({
 					signer: buyer.publicKey,
 					raffle: new PublicKey(raffleAccountId),
@@ -175,6 +192,11 @@ describe("reclaim_expired_tickets", async () => {
 				BigInt(expectedRefund),
 			);
 
+			// Verify the refund has been tracked by the treasury
+			const treasuryAccount =
+				await raffleProgram.account.treasury.fetch(treasuryId);
+			expect(treasuryAccount.totalRefunded.eq(totalTicketsPrice)).toBeTrue();
+
 			// Verify ticket balance account is closed (should throw)
 			expect(
 				raffleProgram.account.ticketBalance.fetch(ticketBalanceId),
@@ -213,7 +235,24 @@ describe("reclaim_expired_tickets", async () => {
 
 		// Create raffle
 		await raffleProgram.methods
-			.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
 			.rpc();
 		const raffleAccountId = PublicKey.findProgramAddressSync(
 			[
@@ -270,7 +309,7 @@ describe("reclaim_expired_tickets", async () => {
 
 		// Initialize ticket balances for both buyers
 		await raffleProgram.methods
-			.initTicketBalance()
+			.initTicketBalance(null)
 			.accounts({
 				signer: buyer1.publicKey,
 				raffle: new PublicKey(raffleAccountId),
@@ -279,7 +318,7 @@ describe("reclaim_expired_tickets", async () => {
 			.rpc();
 
 		await raffleProgram.methods
-			.initTicketBalance()
+			.initTicketBalance(null)
 			.accounts({
 				signer: buyer2.publicKey,
 				raffle: new PublicKey(raffleAccountId),
@@ -298,7 +337,7 @@ describe("reclaim_expired_tickets", async () => {
 
 		// Both buyers purchase tickets
 		await raffleProgram.methods
-			.buyTickets(ticketsToPurchase1, Array.from(entrySeed1))
+			.buyTickets(ticketsToPurchase1, Array.from(entrySeed1), null, null, null, null, false, null)
 			.accounts({
 				signer: buyer1.publicKey,
 				raffle: new PublicKey(raffleAccountId),
@@ -307,7 +346,7 @@ describe("reclaim_expired_tickets", async () => {
 			.rpc();
 
 		await raffleProgram.methods
-			.buyTickets(ticketsToPurchase2, Array.from(entrySeed2))
+			.buyTickets(ticketsToPurchase2, Array.from(entrySeed2), null, null, null, null, false, null)
 			.accounts({
 				signer: buyer2.publicKey,
 				raffle: new PublicKey(raffleAccountId),
@@ -412,7 +451,24 @@ describe("reclaim_expired_tickets", async () => {
 
 			// Create raffle, so that defaults are set, PDAs are created, etc.
 			await raffleProgram.methods
-				.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+				.createRaffle(
+					metadataUri,
+					ticketPrice,
+					endTime,
+					minTickets,
+					null,
+					null,
+					false,
+					null,
+					false,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+				)
 				.rpc();
 			const raffleAccountId = PublicKey.findProgramAddressSync(
 				[
@@ -432,7 +488,7 @@ describe("reclaim_expired_tickets", async () => {
 			provider.client.airdrop(buyer.publicKey, BigInt(1 * LAMPORTS_PER_SOL));
 
 			await raffleProgram.methods
-				.initTicketBalance()
+				.initTicketBalance(null)
 				.accounts({
 					signer: buyer.publicKey,
 					raffle: new PublicKey(raffleAccountId),
@@ -448,6 +504,57 @@ describe("reclaim_expired_tickets", async () => {
 				raffleState: {
 					[state]: {},
 				},
+				participantCount: new BN(0),
+				eventSeq: new BN(0),
+				minRevenue: null,
+				minDrawSlot: new BN(0),
+				reservedTickets: new BN(0),
+				refundFeeBps: 0,
+				rentPayer: provider.publicKey,
+				features: 0,
+				allowedRegions: new BN(0),
+				ageRestricted: false,
+				drawAuthority: null,
+				priorityMinTier: 0,
+				priorityUntil: new BN(0),
+				minUniqueBuyers: null,
+				paymentMint: null,
+				randomnessMode: { slotHashes: {} },
+				randomnessAccount: null,
+				randomnessCommitSlot: new BN(0),
+				prizeEscrowed: false,
+				winnerCount: 1,
+				winningTickets: [],
+				additionalWinners: [],
+				winnerDataSubmitted: 0,
+				prizesClaimed: 0,
+				maxTicketsPerWallet: null,
+				gate: null,
+				deferredNumbering: false,
+				entryCount: new BN(0),
+				numberedEntries: new BN(0),
+				numberedTickets: new BN(0),
+				feeBpsOverride: null,
+				snapshotRoot: null,
+				operatorStatus: 0,
+				statusNoteHash: null,
+				fundingDeadline: null,
+				pricingTiers: [],
+				tierDiscounts: new BN(0),
+				crankRewardBps: 0,
+				referralFeeBps: 0,
+				extensionCount: 0,
+				extendedSecs: new BN(0),
+				refundDeadline: new BN(0),
+				prizeValueUsdCents: null,
+				ticketCountDisplayCap: null,
+				ticketReceipts: false,
+				arweaveTxId: null,
+				creator: null,
+				drawDelaySlots: 0,
+				drawTargetSlot: new BN(0),
+				refundMint: null,
+				refundRate: new BN(0), drawValueLamports: new BN(0), drawUsdRate: null, successor: null, randomnessProvider: null, randomnessCommitment: null, accessCodeHash: null, closeReason: 0, crankRewardPaid: false, additionalWinnerHashes: [], voidedRanges: [], pendingReservations: new BN(0), openSlotReleased: false, campaignSubsidies: new BN(0),
 			});
 			provider.client.setAccount(raffleAccountId, {
 				executable: false,
@@ -472,6 +579,10 @@ describe("reclaim_expired_tickets", async () => {
 					owner: buyer.publicKey,
 					ticketCount: new BN(1),
 					bump: ticketBalanceBump,
+					totalSpent: new BN(0),
+					tierDiscount: new BN(0),
+					campaignSubsidy: new BN(0),
+					subsidyCampaign: null,
 				},
 			);
 			provider.client.setAccount(ticketBalanceId, {
@@ -526,7 +637,24 @@ describe("reclaim_expired_tickets", async () => {
 
 		// Create raffle
 		await raffleProgram.methods
-			.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
 			.rpc();
 		const raffleAccountId = PublicKey.findProgramAddressSync(
 			[
@@ -564,7 +692,7 @@ describe("reclaim_expired_tickets", async () => {
 
 		// Initialize ticket balances
 		await raffleProgram.methods
-			.initTicketBalance()
+			.initTicketBalance(null)
 			.accounts({
 				signer: ticketOwner.publicKey,
 				raffle: new PublicKey(raffleAccountId),
@@ -573,7 +701,7 @@ describe("reclaim_expired_tickets", async () => {
 			.rpc();
 
 		await raffleProgram.methods
-			.initTicketBalance()
+			.initTicketBalance(null)
 			.accounts({
 				signer: unauthorizedUser.publicKey,
 				raffle: new PublicKey(raffleAccountId),
@@ -588,7 +716,7 @@ describe("reclaim_expired_tickets", async () => {
 
 		// Owner purchases tickets
 		await raffleProgram.methods
-			.buyTickets(ticketsToProcess, Array.from(entrySeed))
+			.buyTickets(ticketsToProcess, Array.from(entrySeed), null, null, null, null, false, null)
 			.accounts({
 				signer: ticketOwner.publicKey,
 				raffle: new PublicKey(raffleAccountId),
@@ -680,7 +808,24 @@ describe("reclaim_expired_tickets", async () => {
 
 		// Create first raffle (this will be the expired one)
 		await raffleProgram.methods
-			.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
 			.rpc();
 		const raffleAccountId = PublicKey.findProgramAddressSync(
 			[
@@ -692,7 +837,24 @@ describe("reclaim_expired_tickets", async () => {
 
 		// Create second raffle (this will provide the incorrect treasury)
 		await raffleProgram.methods
-			.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
 			.rpc();
 		const secondRaffleAccountId = PublicKey.findProgramAddressSync(
 			[
@@ -726,7 +888,7 @@ describe("reclaim_expired_tickets", async () => {
 
 		// Initialize ticket balance for first raffle
 		await raffleProgram.methods
-			.initTicketBalance()
+			.initTicketBalance(null)
 			.accounts({
 				signer: buyer.publicKey,
 				raffle: new PublicKey(raffleAccountId),
@@ -741,7 +903,7 @@ describe("reclaim_expired_tickets", async () => {
 
 		// Buy tickets for first raffle
 		await raffleProgram.methods
-			.buyTickets(ticketsToProcess, Array.from(entrySeed))
+			.buyTickets(ticketsToProcess, Array.from(entrySeed), null, null, null, null, false, null)
 			.accounts({
 				signer: buyer.publicKey,
 				raffle: new PublicKey(raffleAccountId),
@@ -757,6 +919,57 @@ describe("reclaim_expired_tickets", async () => {
 		const raffleData = await raffleProgram.coder.accounts.encode("raffle", {
 			...firstRaffleAccount,
 			treasury: incorrectTreasuryId,
+			participantCount: new BN(0),
+			eventSeq: new BN(0),
+			minRevenue: null,
+			minDrawSlot: new BN(0),
+			reservedTickets: new BN(0),
+			refundFeeBps: 0,
+			rentPayer: provider.publicKey,
+			features: 0,
+			allowedRegions: new BN(0),
+			ageRestricted: false,
+			drawAuthority: null,
+			priorityMinTier: 0,
+			priorityUntil: new BN(0),
+			minUniqueBuyers: null,
+			paymentMint: null,
+			randomnessMode: { slotHashes: {} },
+			randomnessAccount: null,
+			randomnessCommitSlot: new BN(0),
+			prizeEscrowed: false,
+			winnerCount: 1,
+			winningTickets: [],
+			additionalWinners: [],
+			winnerDataSubmitted: 0,
+			prizesClaimed: 0,
+			maxTicketsPerWallet: null,
+			gate: null,
+			deferredNumbering: false,
+			entryCount: new BN(0),
+			numberedEntries: new BN(0),
+			numberedTickets: new BN(0),
+			feeBpsOverride: null,
+			snapshotRoot: null,
+			operatorStatus: 0,
+			statusNoteHash: null,
+			fundingDeadline: null,
+			pricingTiers: [],
+			tierDiscounts: new BN(0),
+			crankRewardBps: 0,
+			referralFeeBps: 0,
+			extensionCount: 0,
+			extendedSecs: new BN(0),
+			refundDeadline: new BN(0),
+			prizeValueUsdCents: null,
+			ticketCountDisplayCap: null,
+			ticketReceipts: false,
+			arweaveTxId: null,
+			creator: null,
+			drawDelaySlots: 0,
+			drawTargetSlot: new BN(0),
+			refundMint: null,
+			refundRate: new BN(0), drawValueLamports: new BN(0), drawUsdRate: null, successor: null, randomnessProvider: null, randomnessCommitment: null, accessCodeHash: null, closeReason: 0, crankRewardPaid: false, additionalWinnerHashes: [], voidedRanges: [], pendingReservations: new BN(0), openSlotReleased: false, campaignSubsidies: new BN(0),
 		});
 		provider.client.setAccount(raffleAccountId, {
 			executable: false,
@@ -820,7 +1033,24 @@ describe("reclaim_expired_tickets", async () => {
 
 		// Create raffle
 		await raffleProgram.methods
-			.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
 			.rpc();
 		const raffleAccountId = PublicKey.findProgramAddressSync(
 			[
@@ -837,7 +1067,7 @@ describe("reclaim_expired_tickets", async () => {
 
 		// Initialize ticket balance for raffle but don't buy any tickets
 		await raffleProgram.methods
-			.initTicketBalance()
+			.initTicketBalance(null)
 			.accounts({
 				signer: buyer.publicKey,
 				raffle: new PublicKey(raffleAccountId),
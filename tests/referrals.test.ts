@@ -0,0 +1,199 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("referrals", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		const payoutAuthority = new Keypair();
+		provider.client.airdrop(
+			payoutAuthority.publicKey,
+			BigInt(0.1 * LAMPORTS_PER_SOL),
+		);
+
+		// Init config with a referral fee of 5%
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: payoutAuthority.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+		await raffleProgram.methods.setReferralFee(500).rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(1),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		const referrer = new Keypair();
+		provider.client.airdrop(referrer.publicKey, BigInt(0.1 * LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.initReferralStats(referrer.publicKey)
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+		const referralStatsId = PublicKey.findProgramAddressSync(
+			[
+				Buffer.from("referral_stats"),
+				raffleAccountId.toBytes(),
+				referrer.publicKey.toBytes(),
+			],
+			raffleProgram.programId,
+		)[0];
+
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+		const buy = (seed: number, ticketCount: number, referrerKey: PublicKey) =>
+			raffleProgram.methods
+				.buyTickets(
+					new BN(ticketCount),
+					Array.from(new Uint8Array([seed, 0, 0, 0, 0, 0, 0, 0])),
+					null,
+					null,
+					null,
+					referrerKey,
+					false,
+					null,
+				)
+				.accounts({
+					signer: buyer.publicKey,
+					raffle: raffleAccountId,
+					referralStats: referralStatsId,
+				})
+				.signers([buyer])
+				.rpc();
+
+		const setDrawn = async () => {
+			const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+			const raffleData = await raffleProgram.coder.accounts.encode("raffle", {
+				...raffle,
+				raffleState: { drawn: {} },
+				winnerAddress: buyer.publicKey,
+			});
+			provider.client.setAccount(raffleAccountId, {
+				executable: false,
+				owner: raffleProgram.programId,
+				lamports: 1 * LAMPORTS_PER_SOL,
+				data: raffleData,
+			});
+		};
+
+		const withdraw = (referralAccounts: PublicKey[]) =>
+			raffleProgram.methods
+				.withdrawFromTreasury()
+				.accounts({
+					raffle: raffleAccountId,
+					payoutAuthority: payoutAuthority.publicKey,
+					feeRecipient: null,
+				})
+				.remainingAccounts(
+					referralAccounts.map((pubkey) => ({
+						pubkey,
+						isSigner: false,
+						isWritable: true,
+					})),
+				)
+				.rpc();
+
+		return {
+			provider,
+			raffleProgram,
+			payoutAuthority,
+			referrer,
+			referralStatsId,
+			buyer,
+			buy,
+			setDrawn,
+			withdraw,
+		};
+	};
+
+	it("should attribute purchases and pay the referral fee at withdrawal", async () => {
+		const {
+			provider,
+			raffleProgram,
+			payoutAuthority,
+			referrer,
+			referralStatsId,
+			buy,
+			setDrawn,
+			withdraw,
+		} = await setup();
+
+		await buy(1, 3, referrer.publicKey);
+		await buy(2, 1, referrer.publicKey);
+
+		const stats = await raffleProgram.account.referralStats.fetch(referralStatsId);
+		expect(stats.ticketCount.toNumber()).toBe(4);
+		expect(stats.volume.toNumber()).toBe(0.4 * LAMPORTS_PER_SOL);
+		expect(stats.feesOwed.toNumber()).toBe(0.02 * LAMPORTS_PER_SOL);
+
+		await setDrawn();
+		const referrerBefore = provider.client.getBalance(referrer.publicKey)!;
+		const payoutBefore = provider.client.getBalance(payoutAuthority.publicKey)!;
+		await withdraw([referralStatsId, referrer.publicKey]);
+
+		expect(
+			provider.client.getBalance(referrer.publicKey)! - referrerBefore,
+		).toEqual(BigInt(0.02 * LAMPORTS_PER_SOL));
+		expect(
+			provider.client.getBalance(payoutAuthority.publicKey)! - payoutBefore,
+		).toEqual(BigInt(0.38 * LAMPORTS_PER_SOL));
+	});
+
+	it("should reserve unpaid referral fees from the withdrawal", async () => {
+		const { provider, payoutAuthority, referrer, buy, setDrawn, withdraw } =
+			await setup();
+
+		await buy(1, 4, referrer.publicKey);
+		await setDrawn();
+
+		const payoutBefore = provider.client.getBalance(payoutAuthority.publicKey)!;
+		await withdraw([]);
+		expect(
+			provider.client.getBalance(payoutAuthority.publicKey)! - payoutBefore,
+		).toEqual(BigInt(0.38 * LAMPORTS_PER_SOL));
+	});
+
+	it("should fail to refer a purchase to the buyer or another referrer", async () => {
+		const { buyer, buy } = await setup();
+
+		expect(buy(1, 1, buyer.publicKey)).rejects.toThrow(/SelfReferral/);
+		expect(buy(1, 1, new Keypair().publicKey)).rejects.toThrow(
+			/InvalidReferralStats/,
+		);
+	});
+});
@@ -0,0 +1,299 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { LiteSVM } from "litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+const TOKEN_PROGRAM_ID = new PublicKey(
+	"TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+);
+const ASSOCIATED_TOKEN_PROGRAM_ID = new PublicKey(
+	"ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL",
+);
+const SWITCHBOARD_ON_DEMAND_PROGRAM_ID = new PublicKey(
+	"SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv",
+);
+const PULL_FEED_DISCRIMINATOR = [196, 27, 108, 196, 10, 215, 219, 40];
+
+// Price of a ticket in base units of the 6 decimal payment mint
+const TICKET_PRICE = 1_000_000;
+// Refund mint base units per payment mint base unit, scaled by 1e9, for a price of 0.5
+// refund tokens of 9 decimals per payment token
+const REFUND_RATE = 500_000_000_000;
+
+const associatedTokenAddress = (owner: PublicKey, mint: PublicKey) =>
+	PublicKey.findProgramAddressSync(
+		[owner.toBuffer(), TOKEN_PROGRAM_ID.toBuffer(), mint.toBuffer()],
+		ASSOCIATED_TOKEN_PROGRAM_ID,
+	)[0];
+
+// Writes an initialized SPL token mint without a freeze authority
+const setMint = (
+	client: LiteSVM,
+	mint: PublicKey,
+	authority: PublicKey,
+	decimals: number,
+) => {
+	const data = Buffer.alloc(82);
+	data.writeUInt32LE(1, 0);
+	authority.toBuffer().copy(data, 4);
+	data.writeUInt8(decimals, 44);
+	data.writeUInt8(1, 45);
+	client.setAccount(mint, {
+		executable: false,
+		owner: TOKEN_PROGRAM_ID,
+		lamports: LAMPORTS_PER_SOL,
+		data,
+	});
+};
+
+// Writes an initialized SPL token account holding `amount` tokens
+const setTokenAccount = (
+	client: LiteSVM,
+	address: PublicKey,
+	mint: PublicKey,
+	owner: PublicKey,
+	amount: number,
+) => {
+	const data = Buffer.alloc(165);
+	mint.toBuffer().copy(data, 0);
+	owner.toBuffer().copy(data, 32);
+	data.writeBigUInt64LE(BigInt(amount), 64);
+	data.writeUInt8(1, 108);
+	client.setAccount(address, {
+		executable: false,
+		owner: TOKEN_PROGRAM_ID,
+		lamports: LAMPORTS_PER_SOL,
+		data,
+	});
+};
+
+// Freezes a token account, as the freeze authority of its mint would
+const freezeTokenAccount = (client: LiteSVM, address: PublicKey) => {
+	const account = client.getAccount(address)!;
+	const data = Buffer.from(account.data);
+	data.writeUInt8(2, 108);
+	client.setAccount(address, { ...account, data });
+};
+
+// Writes a Switchboard pull feed whose latest result is `price`, with 18 decimals
+const setPriceFeed = (
+	client: LiteSVM,
+	address: PublicKey,
+	price: bigint,
+	slot: bigint,
+) => {
+	const data = Buffer.alloc(8 + 3200);
+	Buffer.from(PULL_FEED_DISCRIMINATOR).copy(data, 0);
+	data.writeBigUInt64LE(price & BigInt("0xffffffffffffffff"), 8 + 2256);
+	data.writeBigInt64LE(price >> BigInt(64), 8 + 2264);
+	data.writeBigUInt64LE(slot, 8 + 2256 + 104);
+	client.setAccount(address, {
+		executable: false,
+		owner: SWITCHBOARD_ON_DEMAND_PROGRAM_ID,
+		lamports: LAMPORTS_PER_SOL,
+		data,
+	});
+};
+
+const tokenBalance = (client: LiteSVM, address: PublicKey) =>
+	Number(Buffer.from(client.getAccount(address)!.data).readBigUInt64LE(64));
+
+describe("set_refund_mint", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const paymentMint = new Keypair().publicKey;
+		setMint(client, paymentMint, provider.publicKey, 6);
+		const refundMint = new Keypair().publicKey;
+		setMint(client, refundMint, provider.publicKey, 9);
+
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+		const treasury = PublicKey.findProgramAddressSync(
+			[Buffer.from("treasury"), raffleAccountId.toBuffer()],
+			raffleProgram.programId,
+		)[0];
+		const treasuryTokenAccount = associatedTokenAddress(treasury, paymentMint);
+		const refundVault = associatedTokenAddress(treasury, refundMint);
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(TICKET_PRICE),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(5),
+				new BN(10),
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.accountsPartial({
+				paymentMint,
+				treasuryTokenAccount,
+				tokenProgram: TOKEN_PROGRAM_ID,
+				associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+			})
+			.rpc();
+
+		// Sell two tickets, then let the raffle expire short of its threshold
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(0.1 * LAMPORTS_PER_SOL));
+		const buyerTokenAccount = new Keypair().publicKey;
+		setTokenAccount(
+			client,
+			buyerTokenAccount,
+			paymentMint,
+			buyer.publicKey,
+			2 * TICKET_PRICE,
+		);
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+		await raffleProgram.methods
+			.buyTickets(new BN(2), Array.from(new Uint8Array(8)), null, null, null, null, false, null)
+			.accountsPartial({
+				signer: buyer.publicKey,
+				raffle: raffleAccountId,
+				payerTokenAccount: buyerTokenAccount,
+				treasuryTokenAccount,
+				tokenProgram: TOKEN_PROGRAM_ID,
+			})
+			.signers([buyer])
+			.rpc();
+
+		const newClock = client.getClock();
+		newClock.unixTimestamp = creationTime + BigInt(3601);
+		client.setClock(newClock);
+		await raffleProgram.methods
+			.expireRaffle()
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		// Quote 0.5 refund tokens per payment token
+		const priceFeed = new Keypair().publicKey;
+		setPriceFeed(
+			client,
+			priceFeed,
+			BigInt("500000000000000000"),
+			client.getClock().slot,
+		);
+		const fundingTokenAccount = new Keypair().publicKey;
+		setTokenAccount(
+			client,
+			fundingTokenAccount,
+			refundMint,
+			provider.publicKey,
+			10 * LAMPORTS_PER_SOL,
+		);
+
+		const setRefundMint = (refundRate: number) =>
+			raffleProgram.methods
+				.setRefundMint(new BN(refundRate))
+				.accountsPartial({
+					raffle: raffleAccountId,
+					paymentMint,
+					treasuryTokenAccount,
+					refundMint,
+					refundVault,
+					fundingTokenAccount,
+					priceFeed,
+				})
+				.rpc();
+
+		return {
+			client,
+			raffleProgram,
+			raffleAccountId,
+			refundMint,
+			buyer,
+			treasuryTokenAccount,
+			refundVault,
+			setRefundMint,
+		};
+	};
+
+	it("should refund tickets in the refund mint once the payment tokens are frozen", async () => {
+		const {
+			client,
+			raffleProgram,
+			raffleAccountId,
+			refundMint,
+			buyer,
+			treasuryTokenAccount,
+			refundVault,
+			setRefundMint,
+		} = await setup();
+
+		freezeTokenAccount(client, treasuryTokenAccount);
+		await setRefundMint(REFUND_RATE);
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.refundMint?.equals(refundMint)).toBeTrue();
+		expect(raffle.refundRate.toNumber()).toBe(REFUND_RATE);
+		// Two tickets of one payment token each, at 0.5 refund tokens apiece
+		expect(tokenBalance(client, refundVault)).toBe(LAMPORTS_PER_SOL);
+
+		const recipientTokenAccount = new Keypair().publicKey;
+		setTokenAccount(client, recipientTokenAccount, refundMint, buyer.publicKey, 0);
+		await raffleProgram.methods
+			.reclaimExpiredTickets()
+			.accountsPartial({
+				signer: buyer.publicKey,
+				raffle: raffleAccountId,
+				recipientTokenAccount,
+				treasuryTokenAccount: refundVault,
+				tokenProgram: TOKEN_PROGRAM_ID,
+			})
+			.signers([buyer])
+			.rpc();
+
+		expect(tokenBalance(client, recipientTokenAccount)).toBe(LAMPORTS_PER_SOL);
+		expect(tokenBalance(client, refundVault)).toBe(0);
+
+		// The refund mint can't be swapped once designated
+		expect(setRefundMint(REFUND_RATE)).rejects.toThrow(/RefundMintAlreadySet/);
+	});
+
+	it("should fail while the payment tokens can still be refunded", async () => {
+		const { setRefundMint } = await setup();
+
+		expect(setRefundMint(REFUND_RATE)).rejects.toThrow(/PaymentMintNotFrozen/);
+	});
+
+	it("should fail at a rate deviating from the price feed", async () => {
+		const { client, treasuryTokenAccount, setRefundMint } = await setup();
+
+		freezeTokenAccount(client, treasuryTokenAccount);
+		expect(setRefundMint(REFUND_RATE * 1.02)).rejects.toThrow(
+			/RefundRateDeviates/,
+		);
+	});
+});
@@ -0,0 +1,162 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("rent_pool", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		const metadataUri = "https://www.example.org";
+		const ticketPrice = new BN(0.1 * LAMPORTS_PER_SOL);
+		const minTickets = new BN(10);
+		const endTime = new BN((creationTime + BigInt(3601)).toString());
+
+		// Create raffle
+		await raffleProgram.methods
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+		const rentPoolId = PublicKey.findProgramAddressSync(
+			[Buffer.from("rent_pool")],
+			raffleProgram.programId,
+		)[0];
+
+		// Init and fund the rent pool
+		await raffleProgram.methods.initRentPool().rpc();
+		await raffleProgram.methods
+			.fundRentPool(new BN(1 * LAMPORTS_PER_SOL))
+			.rpc();
+
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+
+		const buyTickets = async () => {
+			const randomBytes = new Uint8Array(8);
+			crypto.getRandomValues(randomBytes);
+			const entryId = PublicKey.findProgramAddressSync(
+				[Buffer.from("entry"), raffleAccountId.toBytes(), randomBytes],
+				raffleProgram.programId,
+			)[0];
+			await raffleProgram.methods
+				.buyTickets(new BN(1), Array.from(randomBytes), null, null, null, null, false, null)
+				.accounts({
+					signer: buyer.publicKey,
+					raffle: raffleAccountId,
+					rentPool: rentPoolId,
+				})
+				.signers([buyer])
+				.rpc();
+			return entryId;
+		};
+
+		return {
+			provider,
+			raffleProgram,
+			rentPoolId,
+			buyer,
+			ticketPrice,
+			buyTickets,
+		};
+	};
+
+	it("should reimburse the entry rent from the rent pool when enabled", async () => {
+		const { provider, raffleProgram, rentPoolId, buyer, ticketPrice, buyTickets } =
+			await setup();
+
+		await raffleProgram.methods.setRentPoolEnabled(true).rpc();
+
+		const buyerBalanceBefore = provider.client.getBalance(buyer.publicKey);
+		const rentPoolBalanceBefore = provider.client.getBalance(rentPoolId);
+		if (!buyerBalanceBefore || !rentPoolBalanceBefore) {
+			throw new Error("Failed to fetch balances");
+		}
+
+		const entryId = await buyTickets();
+
+		const entryRent = provider.client.minimumBalanceForRentExemption(
+			BigInt(raffleProgram.account.entry.size),
+		);
+		const entry = await raffleProgram.account.entry.fetch(entryId);
+		expect(entry.rentSponsored).toBeTrue();
+
+		// The pool paid the rent, the buyer only paid for the ticket and the transaction fee
+		expect(provider.client.getBalance(rentPoolId)).toEqual(
+			rentPoolBalanceBefore - entryRent,
+		);
+		expect(provider.client.getBalance(buyer.publicKey)).toEqual(
+			buyerBalanceBefore - BigInt(ticketPrice.toString()) - BigInt(5000),
+		);
+
+		const rentPool = await raffleProgram.account.rentPool.fetch(rentPoolId);
+		expect(rentPool.totalReimbursed.toString()).toEqual(entryRent.toString());
+	});
+
+	it("should let the buyer pay the entry rent when the rent pool is disabled", async () => {
+		const { provider, raffleProgram, rentPoolId, buyTickets } = await setup();
+
+		const rentPoolBalanceBefore = provider.client.getBalance(rentPoolId);
+		const entryId = await buyTickets();
+
+		const entry = await raffleProgram.account.entry.fetch(entryId);
+		expect(entry.rentSponsored).toBeFalse();
+		expect(provider.client.getBalance(rentPoolId)).toEqual(
+			rentPoolBalanceBefore,
+		);
+	});
+
+	it("should fail to toggle the rent pool when not signed by the management authority", async () => {
+		const { provider, raffleProgram } = await setup();
+
+		const account = new Keypair();
+		provider.client.airdrop(account.publicKey, BigInt(1 * LAMPORTS_PER_SOL));
+
+		expect(
+			raffleProgram.methods
+				.setRentPoolEnabled(true)
+				.accountsPartial({ managementAuthority: account.publicKey })
+				.signers([account])
+				.rpc(),
+		).rejects.toThrow(/NotProgramManagementAuthority/);
+	});
+});
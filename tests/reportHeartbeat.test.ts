@@ -0,0 +1,54 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("report_heartbeat", async () => {
+	it("should record the keeper's progress and reject stale slots", async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		const keeper = new Keypair();
+		provider.client.airdrop(keeper.publicKey, BigInt(1 * LAMPORTS_PER_SOL));
+		const heartbeatId = PublicKey.findProgramAddressSync(
+			[Buffer.from("heartbeat"), keeper.publicKey.toBytes()],
+			raffleProgram.programId,
+		)[0];
+
+		client.warpToSlot(BigInt(100));
+		await raffleProgram.methods
+			.reportHeartbeat(new BN(90), new BN(2), new BN(1), new BN(5))
+			.accounts({ keeper: keeper.publicKey })
+			.signers([keeper])
+			.rpc();
+
+		const heartbeat = await raffleProgram.account.heartbeat.fetch(heartbeatId);
+		expect(heartbeat.keeper.equals(keeper.publicKey)).toBeTrue();
+		expect(heartbeat.lastProcessedSlot.toNumber()).toBe(90);
+		expect(heartbeat.pendingDraws.toNumber()).toBe(2);
+		expect(heartbeat.pendingExpiries.toNumber()).toBe(1);
+		expect(heartbeat.pendingClosures.toNumber()).toBe(5);
+		expect(heartbeat.reportedSlot.toNumber()).toBe(100);
+
+		// The processed slot can't move backwards
+		expect(
+			raffleProgram.methods
+				.reportHeartbeat(new BN(80), new BN(0), new BN(0), new BN(0))
+				.accounts({ keeper: keeper.publicKey })
+				.signers([keeper])
+				.rpc(),
+		).rejects.toThrow(/InvalidHeartbeatSlot/);
+
+		// Nor be ahead of the current slot
+		expect(
+			raffleProgram.methods
+				.reportHeartbeat(new BN(101), new BN(0), new BN(0), new BN(0))
+				.accounts({ keeper: keeper.publicKey })
+				.signers([keeper])
+				.rpc(),
+		).rejects.toThrow(/InvalidHeartbeatSlot/);
+	});
+});
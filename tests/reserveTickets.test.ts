@@ -0,0 +1,415 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("reserve_tickets", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		const metadataUri = "https://www.example.org";
+		const ticketPrice = new BN(0.1 * LAMPORTS_PER_SOL);
+		const minTickets = new BN(1);
+		const maxTickets = new BN(10);
+		const endTime = new BN((creationTime + BigInt(3601)).toString());
+
+		// Create raffle
+		await raffleProgram.methods
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				maxTickets,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		// The buyer pays off-chain and holds no SOL
+		const buyer = new Keypair();
+		const reservationSeed = new Uint8Array(8).fill(7);
+		const reservationId = PublicKey.findProgramAddressSync(
+			[Buffer.from("reservation"), raffleAccountId.toBytes(), reservationSeed],
+			raffleProgram.programId,
+		)[0];
+		const entryId = PublicKey.findProgramAddressSync(
+			[Buffer.from("entry"), raffleAccountId.toBytes(), reservationSeed],
+			raffleProgram.programId,
+		)[0];
+		const ticketBalanceId = PublicKey.findProgramAddressSync(
+			[
+				Buffer.from("ticket_balance"),
+				raffleAccountId.toBytes(),
+				buyer.publicKey.toBytes(),
+			],
+			raffleProgram.programId,
+		)[0];
+
+		await raffleProgram.methods
+			.reserveTickets(
+				Array.from(reservationSeed),
+				buyer.publicKey,
+				new BN(6),
+				endTime,
+			)
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		return {
+			client,
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			buyer,
+			reservationId,
+			entryId,
+			ticketBalanceId,
+		};
+	};
+
+	it("should hold the reserved tickets and create the entry on confirmation", async () => {
+		const {
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			buyer,
+			reservationId,
+			entryId,
+			ticketBalanceId,
+		} = await setup();
+
+		let raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.reservedTickets.toNumber()).toBe(6);
+
+		// Only 4 of the 10 tickets are left for sale
+		const other = new Keypair();
+		provider.client.airdrop(other.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: other.publicKey, raffle: raffleAccountId })
+			.signers([other])
+			.rpc();
+		expect(
+			raffleProgram.methods
+				.buyTickets(new BN(5), Array.from(new Uint8Array(8)), null, null, null, null, false, null)
+				.accounts({ signer: other.publicKey, raffle: raffleAccountId })
+				.signers([other])
+				.rpc(),
+		).rejects.toThrow(/PurchaseExceedsThreshold/);
+
+		await raffleProgram.methods
+			.confirmReservation(true)
+			.accounts({
+				raffle: raffleAccountId,
+				reservation: reservationId,
+				entry: entryId,
+				ticketBalance: ticketBalanceId,
+			})
+			.rpc();
+
+		raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.reservedTickets.toNumber()).toBe(0);
+		expect(raffle.currentTickets.toNumber()).toBe(6);
+		expect(raffle.participantCount.toNumber()).toBe(1);
+
+		const entry = await raffleProgram.account.entry.fetch(entryId);
+		expect(entry.owner.equals(buyer.publicKey)).toBeTrue();
+		expect(entry.ticketStartIndex.toNumber()).toBe(0);
+		expect(entry.ticketCount.toNumber()).toBe(6);
+
+		const ticketBalance =
+			await raffleProgram.account.ticketBalance.fetch(ticketBalanceId);
+		expect(ticketBalance.ticketCount.toNumber()).toBe(6);
+		expect(ticketBalance.headEntry?.equals(entryId)).toBeTrue();
+
+		expect(provider.client.getAccount(reservationId)).toBeNull();
+	});
+
+	it("should return released tickets to sale", async () => {
+		const { provider, raffleProgram, raffleAccountId, reservationId, entryId } =
+			await setup();
+
+		await raffleProgram.methods
+			.confirmReservation(false)
+			.accounts({ raffle: raffleAccountId, reservation: reservationId })
+			.rpc();
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.reservedTickets.toNumber()).toBe(0);
+		expect(raffle.currentTickets.toNumber()).toBe(0);
+		expect(provider.client.getAccount(reservationId)).toBeNull();
+		expect(provider.client.getAccount(entryId)).toBeNull();
+	});
+
+	it("should number the reserved tickets when reserving", async () => {
+		const {
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			reservationId,
+			entryId,
+			ticketBalanceId,
+		} = await setup();
+
+		const reservation =
+			await raffleProgram.account.reservation.fetch(reservationId);
+		expect(reservation.ticketStartIndex.toNumber()).toBe(0);
+
+		// Tickets sold while the payment settles are numbered after the reserved ones
+		const other = new Keypair();
+		provider.client.airdrop(other.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: other.publicKey, raffle: raffleAccountId })
+			.signers([other])
+			.rpc();
+		const otherSeed = new Uint8Array(8).fill(1);
+		await raffleProgram.methods
+			.buyTickets(new BN(2), Array.from(otherSeed), null, null, null, null, false, null)
+			.accounts({ signer: other.publicKey, raffle: raffleAccountId })
+			.signers([other])
+			.rpc();
+		const otherEntry = await raffleProgram.account.entry.fetch(
+			PublicKey.findProgramAddressSync(
+				[Buffer.from("entry"), raffleAccountId.toBytes(), otherSeed],
+				raffleProgram.programId,
+			)[0],
+		);
+		expect(otherEntry.ticketStartIndex.toNumber()).toBe(6);
+
+		await raffleProgram.methods
+			.confirmReservation(true)
+			.accounts({
+				raffle: raffleAccountId,
+				reservation: reservationId,
+				entry: entryId,
+				ticketBalance: ticketBalanceId,
+			})
+			.rpc();
+
+		const entry = await raffleProgram.account.entry.fetch(entryId);
+		expect(entry.ticketStartIndex.toNumber()).toBe(0);
+		expect(entry.ticketCount.toNumber()).toBe(6);
+	});
+
+	it("should void the tickets of a released reservation numbered before sold tickets", async () => {
+		const { provider, raffleProgram, raffleAccountId, reservationId } =
+			await setup();
+
+		const other = new Keypair();
+		provider.client.airdrop(other.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: other.publicKey, raffle: raffleAccountId })
+			.signers([other])
+			.rpc();
+		await raffleProgram.methods
+			.buyTickets(new BN(2), Array.from(new Uint8Array(8).fill(1)), null, null, null, null, false, null)
+			.accounts({ signer: other.publicKey, raffle: raffleAccountId })
+			.signers([other])
+			.rpc();
+
+		await raffleProgram.methods
+			.confirmReservation(false)
+			.accounts({ raffle: raffleAccountId, reservation: reservationId })
+			.rpc();
+
+		// The released numbers stay unused, and later tickets follow the sold ones
+		let raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.reservedTickets.toNumber()).toBe(0);
+		expect(raffle.pendingReservations.toNumber()).toBe(0);
+		expect(
+			raffle.voidedRanges.map((range) => [range.start.toNumber(), range.count.toNumber()]),
+		).toEqual([[0, 6]]);
+
+		const nextSeed = new Uint8Array(8).fill(2);
+		await raffleProgram.methods
+			.buyTickets(new BN(1), Array.from(nextSeed), null, null, null, null, false, null)
+			.accounts({ signer: other.publicKey, raffle: raffleAccountId })
+			.signers([other])
+			.rpc();
+		const nextEntry = await raffleProgram.account.entry.fetch(
+			PublicKey.findProgramAddressSync(
+				[Buffer.from("entry"), raffleAccountId.toBytes(), nextSeed],
+				raffleProgram.programId,
+			)[0],
+		);
+		expect(nextEntry.ticketStartIndex.toNumber()).toBe(8);
+
+		raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.currentTickets.toNumber()).toBe(3);
+	});
+
+	it("should only draw tickets held by entries, skipping voided tickets", async () => {
+		const { client, provider, raffleProgram, raffleAccountId, reservationId } =
+			await setup();
+		client.withSysvars();
+		const draw = () =>
+			raffleProgram.methods
+				.drawWinningTicket()
+				.accounts({
+					raffle: raffleAccountId,
+					recentSlothashes: new PublicKey(
+						"SysvarS1otHashes111111111111111111111111111",
+					),
+				})
+				.rpc();
+
+		const other = new Keypair();
+		provider.client.airdrop(other.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: other.publicKey, raffle: raffleAccountId })
+			.signers([other])
+			.rpc();
+		await raffleProgram.methods
+			.buyTickets(new BN(1), Array.from(new Uint8Array(8).fill(1)), null, null, null, null, false, null)
+			.accounts({ signer: other.publicKey, raffle: raffleAccountId })
+			.signers([other])
+			.rpc();
+
+		// Move past the raffle end time, the draw waits for the pending reservation
+		const newClock = client.getClock();
+		newClock.unixTimestamp = newClock.unixTimestamp + BigInt(3602);
+		client.setClock(newClock);
+		expect(draw()).rejects.toThrow(/ReservationsPending/);
+
+		await raffleProgram.methods
+			.releaseExpiredReservation()
+			.accounts({ raffle: raffleAccountId, reservation: reservationId })
+			.rpc();
+		await draw();
+
+		// Ticket 6 is the only ticket held by an entry
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.winningTickets.map((ticket) => ticket.toNumber())).toEqual([6]);
+	});
+
+	it("should let anyone release an expired reservation, also while paused", async () => {
+		const { client, provider, raffleProgram, raffleAccountId, reservationId } =
+			await setup();
+
+		// No account signs besides the fee payer
+		const release = () =>
+			raffleProgram.methods
+				.releaseExpiredReservation()
+				.accounts({ raffle: raffleAccountId, reservation: reservationId })
+				.rpc();
+
+		expect(release()).rejects.toThrow(/ReservationNotExpired/);
+
+		// Move past the reservation expiry and pause the program
+		const newClock = client.getClock();
+		newClock.unixTimestamp = newClock.unixTimestamp + BigInt(3602);
+		client.setClock(newClock);
+		await raffleProgram.methods.setPause(true).rpc();
+
+		await release();
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.reservedTickets.toNumber()).toBe(0);
+		expect(raffle.voidedRanges).toEqual([]);
+		expect(provider.client.getAccount(reservationId)).toBeNull();
+	});
+
+	it("should fail to release a reservation once the raffle left the Open state", async () => {
+		const { client, raffleProgram, raffleAccountId, reservationId } =
+			await setup();
+
+		// Move past the reservation expiry and expire the raffle without a sale
+		const newClock = client.getClock();
+		newClock.unixTimestamp = newClock.unixTimestamp + BigInt(3602);
+		client.setClock(newClock);
+		await raffleProgram.methods
+			.expireRaffle()
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		expect(
+			raffleProgram.methods
+				.releaseExpiredReservation()
+				.accounts({ raffle: raffleAccountId, reservation: reservationId })
+				.rpc(),
+		).rejects.toThrow(/RaffleNotOpen/);
+	});
+
+	it("should fail to finalize a reservation after the raffle ended", async () => {
+		const {
+			client,
+			raffleProgram,
+			raffleAccountId,
+			reservationId,
+			entryId,
+			ticketBalanceId,
+		} = await setup();
+
+		// Move past the raffle end time
+		const newClock = client.getClock();
+		newClock.unixTimestamp = newClock.unixTimestamp + BigInt(3601);
+		client.setClock(newClock);
+
+		expect(
+			raffleProgram.methods
+				.confirmReservation(true)
+				.accounts({
+					raffle: raffleAccountId,
+					reservation: reservationId,
+					entry: entryId,
+					ticketBalance: ticketBalanceId,
+				})
+				.rpc(),
+		).rejects.toThrow(/RaffleEnded/);
+	});
+
+	it("should fail to reserve from accounts other than the management authority", async () => {
+		const { provider, raffleProgram, raffleAccountId } = await setup();
+
+		const account = new Keypair();
+		provider.client.airdrop(account.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+
+		expect(
+			raffleProgram.methods
+				.reserveTickets(
+					Array.from(new Uint8Array(8)),
+					account.publicKey,
+					new BN(1),
+					new BN(0),
+				)
+				.accounts({
+					raffle: raffleAccountId,
+					managementAuthority: account.publicKey,
+				})
+				.signers([account])
+				.rpc(),
+		).rejects.toThrow(/NotProgramManagementAuthority/);
+	});
+});
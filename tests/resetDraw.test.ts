@@ -0,0 +1,152 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("reset_draw", async () => {
+	const recentSlothashes = new PublicKey(
+		"SysvarS1otHashes111111111111111111111111111",
+	);
+
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		client.withSysvars();
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		const metadataUri = "https://www.example.org";
+		const ticketPrice = new BN(0.1 * LAMPORTS_PER_SOL);
+		const minTickets = new BN(1);
+		const endTime = new BN((creationTime + BigInt(3601)).toString());
+
+		// Create raffle
+		await raffleProgram.methods
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+		await raffleProgram.methods
+			.buyTickets(new BN(5), Array.from(new Uint8Array(8)), null, null, null, null, false, null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+
+		// Set time so that the raffle has ended, and draw
+		const newClock = client.getClock();
+		newClock.unixTimestamp = creationTime + BigInt(3601);
+		client.setClock(newClock);
+		await raffleProgram.methods
+			.drawWinningTicket()
+			.accounts({ raffle: raffleAccountId, recentSlothashes })
+			.rpc();
+
+		// A draw can only be reset once the claim window of 7 days passed
+		const passClaimWindow = () => {
+			const clock = client.getClock();
+			clock.unixTimestamp += BigInt(7 * 24 * 60 * 60);
+			client.setClock(clock);
+		};
+
+		return { provider, raffleProgram, raffleAccountId, passClaimWindow };
+	};
+
+	it("should revert a drawing raffle to open and require fresh entropy", async () => {
+		const { raffleProgram, raffleAccountId, passClaimWindow } = await setup();
+
+		const drawnRaffle =
+			await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(drawnRaffle.raffleState.drawing).toBeDefined();
+
+		passClaimWindow();
+		await raffleProgram.methods
+			.resetDraw()
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.raffleState.open).toBeDefined();
+		expect(raffle.winningTicket).toBeNull();
+		expect(raffle.drawSlot).toBeNull();
+		expect(raffle.drawSlotHash).toBeNull();
+		expect(raffle.drawTimestamp).toBeNull();
+		expect(
+			raffle.minDrawSlot.eq((drawnRaffle.drawSlot as BN).add(new BN(1))),
+		).toBeTrue();
+
+		// The discarded entropy can't be sampled again
+		expect(
+			raffleProgram.methods
+				.drawWinningTicket()
+				.accounts({ raffle: raffleAccountId, recentSlothashes })
+				.rpc(),
+		).rejects.toThrow(/StaleDrawEntropy/);
+	});
+
+	it("should fail before the claim window passed since the draw", async () => {
+		const { raffleProgram, raffleAccountId } = await setup();
+
+		expect(
+			raffleProgram.methods
+				.resetDraw()
+				.accounts({ raffle: raffleAccountId })
+				.rpc(),
+		).rejects.toThrow(/ResetDrawTooEarly/);
+	});
+
+	it("should fail from accounts other than the management authority", async () => {
+		const { provider, raffleProgram, raffleAccountId } = await setup();
+
+		const other = new Keypair();
+		provider.client.airdrop(other.publicKey, BigInt(1 * LAMPORTS_PER_SOL));
+		expect(
+			raffleProgram.methods
+				.resetDraw()
+				.accounts({
+					raffle: raffleAccountId,
+					managementAuthority: other.publicKey,
+				})
+				.signers([other])
+				.rpc(),
+		).rejects.toThrow(/NotProgramManagementAuthority/);
+	});
+});
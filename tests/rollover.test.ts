@@ -0,0 +1,209 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("rollover_tickets", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		// The first raffle needs ten tickets sold and expires, its successor sells
+		// tickets at half the price and ends an hour later
+		const creationTime = client.getClock().unixTimestamp;
+		const raffleIds: PublicKey[] = [];
+		for (const [index, ticketPrice, duration, minTickets] of [
+			[0, 0.1, 3601, 10],
+			[1, 0.05, 7201, 1],
+		]) {
+			await raffleProgram.methods
+				.createRaffle(
+					"https://www.example.org",
+					new BN(ticketPrice * LAMPORTS_PER_SOL),
+					new BN((creationTime + BigInt(duration)).toString()),
+					new BN(minTickets),
+					null,
+					null,
+					false,
+					null,
+					false,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+				)
+				.rpc();
+			raffleIds.push(
+				PublicKey.findProgramAddressSync(
+					[Buffer.from("raffle"), new Uint8Array(new BN(index).toArray("le", 8))],
+					raffleProgram.programId,
+				)[0],
+			);
+		}
+		const [raffleAccountId, successorId] = raffleIds;
+
+		const buy = async (rollover: boolean) => {
+			const buyer = new Keypair();
+			provider.client.airdrop(buyer.publicKey, BigInt(LAMPORTS_PER_SOL));
+			await raffleProgram.methods
+				.initTicketBalance(null)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+			await raffleProgram.methods
+				.buyTickets(
+					new BN(2),
+					Array.from(new Uint8Array([1, 0, 0, 0, 0, 0, 0, 0])),
+					null,
+					null,
+					null,
+					null,
+					rollover,
+					null,
+				)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+			return buyer;
+		};
+
+		const expire = async () => {
+			const newClock = client.getClock();
+			newClock.unixTimestamp = creationTime + BigInt(3602);
+			client.setClock(newClock);
+			await raffleProgram.methods
+				.expireRaffle()
+				.accounts({ raffle: raffleAccountId })
+				.rpc();
+		};
+
+		const setSuccessor = () =>
+			raffleProgram.methods
+				.setSuccessorRaffle()
+				.accounts({ raffle: raffleAccountId, successor: successorId })
+				.rpc();
+
+		const entrySeed = new Uint8Array([7, 0, 0, 0, 0, 0, 0, 0]);
+		const rolloverTickets = (buyer: Keypair) =>
+			raffleProgram.methods
+				.rolloverTickets(Array.from(entrySeed))
+				.accounts({
+					signer: buyer.publicKey,
+					raffle: raffleAccountId,
+					successor: successorId,
+					successorTicketBalance: PublicKey.findProgramAddressSync(
+						[
+							Buffer.from("ticket_balance"),
+							successorId.toBytes(),
+							buyer.publicKey.toBytes(),
+						],
+						raffleProgram.programId,
+					)[0],
+					entry: PublicKey.findProgramAddressSync(
+						[Buffer.from("entry"), successorId.toBytes(), entrySeed],
+						raffleProgram.programId,
+					)[0],
+				})
+				.signers([buyer])
+				.rpc();
+
+		return {
+			raffleProgram,
+			raffleAccountId,
+			successorId,
+			buy,
+			expire,
+			setSuccessor,
+			rolloverTickets,
+		};
+	};
+
+	it("should move an opted-in balance into the successor raffle", async () => {
+		const {
+			raffleProgram,
+			raffleAccountId,
+			successorId,
+			buy,
+			expire,
+			setSuccessor,
+			rolloverTickets,
+		} = await setup();
+
+		const buyer = await buy(true);
+		await expire();
+		await setSuccessor();
+		await rolloverTickets(buyer);
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.successor?.equals(successorId)).toBeTrue();
+
+		// The two tickets at 0.1 SOL pay for four successor tickets at 0.05 SOL
+		const successor = await raffleProgram.account.raffle.fetch(successorId);
+		expect(successor.currentTickets.toNumber()).toBe(4);
+		expect(successor.participantCount.toNumber()).toBe(1);
+	});
+
+	it("should fail for a wallet that didn't opt in to rollover", async () => {
+		const { buy, expire, setSuccessor, rolloverTickets } = await setup();
+
+		const buyer = await buy(false);
+		await expire();
+		await setSuccessor();
+		expect(rolloverTickets(buyer)).rejects.toThrow(/RolloverNotEnabled/);
+	});
+
+	it("should fail once the successor was made private", async () => {
+		const {
+			raffleProgram,
+			successorId,
+			buy,
+			expire,
+			setSuccessor,
+			rolloverTickets,
+		} = await setup();
+
+		const buyer = await buy(true);
+		await expire();
+		await setSuccessor();
+		await raffleProgram.methods
+			.setAccessCode(Array(32).fill(1))
+			.accounts({ raffle: successorId })
+			.rpc();
+		expect(rolloverTickets(buyer)).rejects.toThrow(/AccessCodeRequired/);
+	});
+
+	it("should fail without a successor set", async () => {
+		const { buy, expire, rolloverTickets } = await setup();
+
+		const buyer = await buy(true);
+		await expire();
+		expect(rolloverTickets(buyer)).rejects.toThrow(/InvalidSuccessorRaffle/);
+	});
+
+	it("should fail to succeed a raffle with itself", async () => {
+		const { raffleProgram, raffleAccountId } = await setup();
+
+		expect(
+			raffleProgram.methods
+				.setSuccessorRaffle()
+				.accounts({ raffle: raffleAccountId, successor: raffleAccountId })
+				.rpc(),
+		).rejects.toThrow(/InvalidSuccessorRaffle/);
+	});
+});
@@ -0,0 +1,115 @@
+import { describe, expect, it } from "bun:test";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("rotate_authority", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+		const configId = PublicKey.findProgramAddressSync(
+			[Buffer.from("config")],
+			raffleProgram.programId,
+		)[0];
+
+		const successor = new Keypair();
+		provider.client.airdrop(successor.publicKey, BigInt(LAMPORTS_PER_SOL));
+
+		return { provider, raffleProgram, configId, successor };
+	};
+
+	it("should hand the management authority over once the successor accepts", async () => {
+		const { provider, raffleProgram, configId, successor } = await setup();
+
+		await raffleProgram.methods
+			.proposeAuthority({ management: {} }, successor.publicKey)
+			.accounts({ authority: provider.publicKey })
+			.rpc();
+
+		// The current authority stays in charge until the handover is accepted
+		let config = await raffleProgram.account.config.fetch(configId);
+		expect(config.managementAuthority.toBase58()).toBe(
+			provider.publicKey.toBase58(),
+		);
+		expect(config.pendingManagementAuthority?.toBase58()).toBe(
+			successor.publicKey.toBase58(),
+		);
+
+		await raffleProgram.methods
+			.acceptAuthority({ management: {} })
+			.accounts({ newAuthority: successor.publicKey })
+			.signers([successor])
+			.rpc();
+
+		config = await raffleProgram.account.config.fetch(configId);
+		expect(config.managementAuthority.toBase58()).toBe(
+			successor.publicKey.toBase58(),
+		);
+		expect(config.pendingManagementAuthority).toBeNull();
+		expect(config.payoutAuthority.toBase58()).toBe(
+			provider.publicKey.toBase58(),
+		);
+
+		// The previous authority lost access to management instructions
+		expect(
+			raffleProgram.methods.setPrizeDepositRequired(true).rpc(),
+		).rejects.toThrow(/NotProgramManagementAuthority/);
+	});
+
+	it("should fail to propose a successor when not signed by the current authority", async () => {
+		const { raffleProgram, successor } = await setup();
+
+		expect(
+			raffleProgram.methods
+				.proposeAuthority({ payout: {} }, successor.publicKey)
+				.accounts({ authority: successor.publicKey })
+				.signers([successor])
+				.rpc(),
+		).rejects.toThrow(/NotCurrentAuthority/);
+	});
+
+	it("should fail to accept the handover when not the proposed successor", async () => {
+		const { provider, raffleProgram, successor } = await setup();
+
+		await raffleProgram.methods
+			.proposeAuthority({ upgrade: {} }, successor.publicKey)
+			.accounts({ authority: provider.publicKey })
+			.rpc();
+
+		const account = new Keypair();
+		provider.client.airdrop(account.publicKey, BigInt(LAMPORTS_PER_SOL));
+		expect(
+			raffleProgram.methods
+				.acceptAuthority({ upgrade: {} })
+				.accounts({ newAuthority: account.publicKey })
+				.signers([account])
+				.rpc(),
+		).rejects.toThrow(/NotPendingAuthority/);
+
+		// Withdrawn proposals can't be accepted either
+		await raffleProgram.methods
+			.proposeAuthority({ upgrade: {} }, null)
+			.accounts({ authority: provider.publicKey })
+			.rpc();
+		expect(
+			raffleProgram.methods
+				.acceptAuthority({ upgrade: {} })
+				.accounts({ newAuthority: successor.publicKey })
+				.signers([successor])
+				.rpc(),
+		).rejects.toThrow(/NotPendingAuthority/);
+	});
+});
@@ -0,0 +1,153 @@
+import { describe, expect, it } from "bun:test";
+import { createHash } from "node:crypto";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+// Access code hash as computed by the program, salted with the raffle's key
+const accessCodeHash = (raffle: PublicKey, accessCode: string) =>
+	Array.from(
+		createHash("sha256")
+			.update(Buffer.concat([raffle.toBuffer(), Buffer.from(accessCode)]))
+			.digest(),
+	);
+
+describe("set_access_code", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(1),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+		await raffleProgram.methods
+			.setAccessCode(accessCodeHash(raffleAccountId, "VIP-2026"))
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+
+		const buyTickets = (seed: number, accessCode: string | null) =>
+			raffleProgram.methods
+				.buyTickets(
+					new BN(1),
+					Array.from(new Uint8Array([seed, 0, 0, 0, 0, 0, 0, 0])),
+					null,
+					null,
+					null,
+					null,
+					false,
+					accessCode,
+				)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+
+		return { provider, raffleProgram, raffleAccountId, buyTickets };
+	};
+
+	it("should only sell to buyers presenting the access code", async () => {
+		const { raffleProgram, raffleAccountId, buyTickets } = await setup();
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.accessCodeHash).toEqual(
+			accessCodeHash(raffleAccountId, "VIP-2026"),
+		);
+		expect(raffle.features & (1 << 23)).toBe(1 << 23);
+
+		expect(buyTickets(0, null)).rejects.toThrow(/AccessCodeRequired/);
+		expect(buyTickets(0, "VIP-2025")).rejects.toThrow(/InvalidAccessCode/);
+
+		await buyTickets(0, "VIP-2026");
+		const updatedRaffle =
+			await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(updatedRaffle.currentTickets.toNumber()).toBe(1);
+	});
+
+	it("should open the raffle to everyone once the access code is cleared", async () => {
+		const { raffleProgram, raffleAccountId, buyTickets } = await setup();
+
+		await raffleProgram.methods
+			.setAccessCode(null)
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.accessCodeHash).toBeNull();
+		expect(raffle.features & (1 << 23)).toBe(0);
+
+		await buyTickets(0, null);
+	});
+
+	it("should fail once tickets were sold", async () => {
+		const { raffleProgram, raffleAccountId, buyTickets } = await setup();
+
+		await buyTickets(0, "VIP-2026");
+
+		expect(
+			raffleProgram.methods
+				.setAccessCode(null)
+				.accounts({ raffle: raffleAccountId })
+				.rpc(),
+		).rejects.toThrow(/PrizeInfoLocked/);
+	});
+
+	it("should fail when not signed by the management authority", async () => {
+		const { provider, raffleProgram, raffleAccountId } = await setup();
+
+		const account = new Keypair();
+		provider.client.airdrop(account.publicKey, BigInt(LAMPORTS_PER_SOL));
+
+		expect(
+			raffleProgram.methods
+				.setAccessCode(null)
+				.accounts({
+					raffle: raffleAccountId,
+					managementAuthority: account.publicKey,
+				})
+				.signers([account])
+				.rpc(),
+		).rejects.toThrow(/NotProgramManagementAuthority/);
+	});
+});
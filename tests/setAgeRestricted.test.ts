@@ -0,0 +1,159 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("set_age_restricted", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+		const attestor = new Keypair();
+		provider.client.airdrop(attestor.publicKey, BigInt(LAMPORTS_PER_SOL));
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+		await raffleProgram.methods.setAgeAttestor(attestor.publicKey).rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(1),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+		await raffleProgram.methods
+			.setAgeRestricted(true)
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+		const ageAttestationId = PublicKey.findProgramAddressSync(
+			[Buffer.from("age_attestation"), buyer.publicKey.toBytes()],
+			raffleProgram.programId,
+		)[0];
+
+		const attest = (signer: Keypair) =>
+			raffleProgram.methods
+				.attestAge(
+					buyer.publicKey,
+					new BN((creationTime + BigInt(600)).toString()),
+				)
+				.accounts({ attestor: signer.publicKey })
+				.signers([signer])
+				.rpc();
+		const buyTickets = (seed: number) =>
+			raffleProgram.methods
+				.buyTickets(
+					new BN(1),
+					Array.from(new Uint8Array([seed, 0, 0, 0, 0, 0, 0, 0])),
+					null,
+					null,
+					null,
+					null,
+					false,
+					null,
+				)
+				.accounts({
+					signer: buyer.publicKey,
+					raffle: raffleAccountId,
+					ageAttestation: ageAttestationId,
+				})
+				.signers([buyer])
+				.rpc();
+
+		return {
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			buyer,
+			attestor,
+			attest,
+			buyTickets,
+		};
+	};
+
+	it("should only sell to buyers attested to be of age", async () => {
+		const {
+			raffleProgram,
+			raffleAccountId,
+			buyer,
+			attestor,
+			attest,
+			buyTickets,
+		} = await setup();
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.ageRestricted).toBe(true);
+		expect(raffle.features & (1 << 8)).toBe(1 << 8);
+
+		// Purchases without an attestation are rejected
+		expect(
+			raffleProgram.methods
+				.buyTickets(new BN(1), Array.from(new Uint8Array(8)), null, null, null, null, false, null)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc(),
+		).rejects.toThrow(/AgeVerificationRequired/);
+
+		await attest(attestor);
+		await buyTickets(0);
+
+		const updatedRaffle =
+			await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(updatedRaffle.currentTickets.toNumber()).toBe(1);
+	});
+
+	it("should reject expired attestations", async () => {
+		const { provider, attestor, attest, buyTickets } = await setup();
+
+		await attest(attestor);
+		const clock = provider.client.getClock();
+		clock.unixTimestamp = clock.unixTimestamp + BigInt(600);
+		provider.client.setClock(clock);
+
+		expect(buyTickets(0)).rejects.toThrow(/AgeVerificationRequired/);
+	});
+
+	it("should fail from accounts other than the age attestor", async () => {
+		const { provider, attest } = await setup();
+
+		const account = new Keypair();
+		provider.client.airdrop(account.publicKey, BigInt(LAMPORTS_PER_SOL));
+
+		expect(attest(account)).rejects.toThrow(/NotAgeAttestor/);
+	});
+});
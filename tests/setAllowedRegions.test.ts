@@ -0,0 +1,173 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("set_allowed_regions", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+		const attestor = new Keypair();
+		provider.client.airdrop(attestor.publicKey, BigInt(LAMPORTS_PER_SOL));
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+		await raffleProgram.methods.setRegionAttestor(attestor.publicKey).rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(1),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+		const regionAttestationId = PublicKey.findProgramAddressSync(
+			[Buffer.from("region_attestation"), buyer.publicKey.toBytes()],
+			raffleProgram.programId,
+		)[0];
+
+		// Only sell to regions 1 and 3
+		await raffleProgram.methods
+			.setAllowedRegions(new BN(0b1010))
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		const attest = (region: number) =>
+			raffleProgram.methods
+				.attestRegion(
+					buyer.publicKey,
+					region,
+					new BN((creationTime + BigInt(600)).toString()),
+				)
+				.accounts({ attestor: attestor.publicKey })
+				.signers([attestor])
+				.rpc();
+		const buyTickets = (seed: number) =>
+			raffleProgram.methods
+				.buyTickets(
+					new BN(1),
+					Array.from(new Uint8Array([seed, 0, 0, 0, 0, 0, 0, 0])),
+					null,
+					null,
+					null,
+					null,
+					false,
+					null,
+				)
+				.accounts({
+					signer: buyer.publicKey,
+					raffle: raffleAccountId,
+					regionAttestation: regionAttestationId,
+				})
+				.signers([buyer])
+				.rpc();
+
+		return {
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			buyer,
+			attest,
+			buyTickets,
+		};
+	};
+
+	it("should only sell to buyers attested in an allowed region", async () => {
+		const { raffleProgram, raffleAccountId, buyer, attest, buyTickets } =
+			await setup();
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.allowedRegions.toNumber()).toBe(0b1010);
+		expect(raffle.features & (1 << 7)).toBe(1 << 7);
+
+		// Purchases without an attestation are rejected
+		expect(
+			raffleProgram.methods
+				.buyTickets(new BN(1), Array.from(new Uint8Array(8)), null, null, null, null, false, null)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc(),
+		).rejects.toThrow(/RegionAttestationRequired/);
+
+		await attest(2);
+		expect(buyTickets(0)).rejects.toThrow(/RegionNotAllowed/);
+
+		await attest(3);
+		await buyTickets(1);
+
+		const updatedRaffle =
+			await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(updatedRaffle.currentTickets.toNumber()).toBe(1);
+	});
+
+	it("should reject expired attestations", async () => {
+		const { provider, attest, buyTickets } = await setup();
+
+		await attest(1);
+		const clock = provider.client.getClock();
+		clock.unixTimestamp = clock.unixTimestamp + BigInt(600);
+		provider.client.setClock(clock);
+
+		expect(buyTickets(0)).rejects.toThrow(/RegionAttestationRequired/);
+	});
+
+	it("should reject attestations of a replaced attestor", async () => {
+		const { raffleProgram, attest, buyTickets } = await setup();
+
+		await attest(1);
+		await raffleProgram.methods.setRegionAttestor(null).rpc();
+
+		expect(buyTickets(0)).rejects.toThrow(/RegionAttestationRequired/);
+	});
+
+	it("should fail from accounts other than the region attestor", async () => {
+		const { provider, raffleProgram, buyer } = await setup();
+
+		const account = new Keypair();
+		provider.client.airdrop(account.publicKey, BigInt(LAMPORTS_PER_SOL));
+
+		expect(
+			raffleProgram.methods
+				.attestRegion(buyer.publicKey, 1, new BN(Date.now()))
+				.accounts({ attestor: account.publicKey })
+				.signers([account])
+				.rpc(),
+		).rejects.toThrow(/NotRegionAttestor/);
+	});
+});
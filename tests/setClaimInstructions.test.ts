@@ -0,0 +1,167 @@
+import { describe, expect, it } from "bun:test";
+import { createHash } from "node:crypto";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("set_claim_instructions", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(1),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		const buyTickets = async () => {
+			const buyer = new Keypair();
+			provider.client.airdrop(buyer.publicKey, BigInt(LAMPORTS_PER_SOL));
+			await raffleProgram.methods
+				.initTicketBalance(null)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+			await raffleProgram.methods
+				.buyTickets(new BN(1), Array.from(new Uint8Array(8)), null, null, null, null, false, null)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+			return buyer;
+		};
+
+		return { provider, raffleProgram, raffleAccountId, buyTickets };
+	};
+
+	const claimUri = "https://www.example.org/claim.md";
+	const claimHash = Array.from(
+		createHash("sha256").update("Encrypt your Discord handle").digest(),
+	);
+
+	it("should surface the claim instructions once the raffle is drawn", async () => {
+		const { provider, raffleProgram, raffleAccountId, buyTickets } =
+			await setup();
+
+		await raffleProgram.methods
+			.setClaimInstructions(claimUri, claimHash)
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		// Not surfaced before the draw
+		expect(
+			raffleProgram.methods
+				.getClaimInstructions()
+				.accounts({ raffle: raffleAccountId })
+				.view(),
+		).rejects.toThrow(/RaffleNotDrawn/);
+
+		const winner = await buyTickets();
+
+		// Manually set the raffle state to drawn
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		const raffleData = await raffleProgram.coder.accounts.encode("raffle", {
+			...raffle,
+			raffleState: { drawn: {} },
+			winnerAddress: winner.publicKey,
+		});
+		const raffleAccount = provider.client.getAccount(raffleAccountId);
+		if (!raffleAccount) {
+			throw new Error("Failed to fetch raffle account");
+		}
+		provider.client.setAccount(raffleAccountId, {
+			...raffleAccount,
+			data: raffleData,
+		});
+
+		const claimInstructions = await raffleProgram.methods
+			.getClaimInstructions()
+			.accounts({ raffle: raffleAccountId })
+			.view();
+		expect(claimInstructions.raffle.equals(raffleAccountId)).toBeTrue();
+		expect(claimInstructions.winner?.equals(winner.publicKey)).toBeTrue();
+		expect(claimInstructions.instructions).toBe(claimUri);
+		expect(claimInstructions.contentHash).toEqual(claimHash);
+	});
+
+	it("should fail to change the claim instructions after sales started", async () => {
+		const { raffleProgram, raffleAccountId, buyTickets } = await setup();
+
+		await raffleProgram.methods
+			.setClaimInstructions(claimUri, claimHash)
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+		await buyTickets();
+
+		expect(
+			raffleProgram.methods
+				.setClaimInstructions("https://phishing.example/claim.md", null)
+				.accounts({ raffle: raffleAccountId })
+				.rpc(),
+		).rejects.toThrow(/ClaimInstructionsLocked/);
+	});
+
+	it("should fail with empty or too long instructions", async () => {
+		const { raffleProgram, raffleAccountId } = await setup();
+
+		for (const instructions of ["", "x".repeat(257)]) {
+			expect(
+				raffleProgram.methods
+					.setClaimInstructions(instructions, null)
+					.accounts({ raffle: raffleAccountId })
+					.rpc(),
+			).rejects.toThrow(/InvalidClaimInstructions/);
+		}
+	});
+
+	it("should fail from accounts other than the management authority", async () => {
+		const { provider, raffleProgram, raffleAccountId } = await setup();
+
+		const account = new Keypair();
+		provider.client.airdrop(account.publicKey, BigInt(LAMPORTS_PER_SOL));
+
+		expect(
+			raffleProgram.methods
+				.setClaimInstructions(claimUri, null)
+				.accounts({
+					raffle: raffleAccountId,
+					managementAuthority: account.publicKey,
+				})
+				.signers([account])
+				.rpc(),
+		).rejects.toThrow(/NotProgramManagementAuthority/);
+	});
+});
@@ -0,0 +1,72 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("set_claim_windows", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const configId = PublicKey.findProgramAddressSync(
+			[Buffer.from("config")],
+			raffleProgram.programId,
+		)[0];
+
+		return { provider, raffleProgram, configId };
+	};
+
+	it("should default the windows and let the management authority change them", async () => {
+		const { raffleProgram, configId } = await setup();
+
+		let config = await raffleProgram.account.config.fetch(configId);
+		expect(config.claimWindowSecs.toNumber()).toBe(7 * 24 * 60 * 60);
+		expect(config.disputeWindowSecs.toNumber()).toBe(24 * 60 * 60);
+
+		await raffleProgram.methods
+			.setClaimWindows(new BN(3 * 24 * 60 * 60), new BN(60 * 60))
+			.rpc();
+
+		config = await raffleProgram.account.config.fetch(configId);
+		expect(config.claimWindowSecs.toNumber()).toBe(3 * 24 * 60 * 60);
+		expect(config.disputeWindowSecs.toNumber()).toBe(60 * 60);
+	});
+
+	it("should fail with empty windows", async () => {
+		const { raffleProgram } = await setup();
+
+		expect(
+			raffleProgram.methods.setClaimWindows(new BN(0), new BN(60)).rpc(),
+		).rejects.toThrow(/InvalidWindow/);
+		expect(
+			raffleProgram.methods.setClaimWindows(new BN(60), new BN(-1)).rpc(),
+		).rejects.toThrow(/InvalidWindow/);
+	});
+
+	it("should fail from accounts other than the management authority", async () => {
+		const { provider, raffleProgram } = await setup();
+
+		const other = new Keypair();
+		provider.client.airdrop(other.publicKey, BigInt(1 * LAMPORTS_PER_SOL));
+		expect(
+			raffleProgram.methods
+				.setClaimWindows(new BN(60), new BN(60))
+				.accounts({ managementAuthority: other.publicKey })
+				.signers([other])
+				.rpc(),
+		).rejects.toThrow(/NotProgramManagementAuthority/);
+	});
+});
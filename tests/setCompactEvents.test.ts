@@ -0,0 +1,59 @@
+import { describe, expect, it } from "bun:test";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("set_compact_events", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+		const configId = PublicKey.findProgramAddressSync(
+			[Buffer.from("config")],
+			raffleProgram.programId,
+		)[0];
+
+		return { provider, raffleProgram, configId };
+	};
+
+	it("should switch events between verbose and compact", async () => {
+		const { raffleProgram, configId } = await setup();
+
+		let config = await raffleProgram.account.config.fetch(configId);
+		expect(config.compactEvents).toBeFalse();
+
+		await raffleProgram.methods.setCompactEvents(true).rpc();
+		config = await raffleProgram.account.config.fetch(configId);
+		expect(config.compactEvents).toBeTrue();
+
+		await raffleProgram.methods.setCompactEvents(false).rpc();
+		config = await raffleProgram.account.config.fetch(configId);
+		expect(config.compactEvents).toBeFalse();
+	});
+
+	it("should fail when not signed by the management authority", async () => {
+		const { provider, raffleProgram } = await setup();
+
+		const account = new Keypair();
+		provider.client.airdrop(account.publicKey, BigInt(LAMPORTS_PER_SOL));
+		expect(
+			raffleProgram.methods
+				.setCompactEvents(true)
+				.accounts({ managementAuthority: account.publicKey })
+				.signers([account])
+				.rpc(),
+		).rejects.toThrow(/NotProgramManagementAuthority/);
+	});
+});
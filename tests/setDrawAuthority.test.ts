@@ -0,0 +1,148 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("set_draw_authority", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(1),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		const buyTickets = async () => {
+			const buyer = new Keypair();
+			provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+			await raffleProgram.methods
+				.initTicketBalance(null)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+			await raffleProgram.methods
+				.buyTickets(new BN(5), Array.from(new Uint8Array(8)), null, null, null, null, false, null)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+		};
+
+		const endRaffle = () => {
+			const newClock = client.getClock();
+			newClock.unixTimestamp = newClock.unixTimestamp + BigInt(3601);
+			client.setClock(newClock);
+		};
+
+		return { provider, raffleProgram, raffleAccountId, buyTickets, endRaffle };
+	};
+
+	it("should only let the draw authority draw the raffle", async () => {
+		const { provider, raffleProgram, raffleAccountId, buyTickets, endRaffle } =
+			await setup();
+
+		const drawAuthority = new Keypair();
+		provider.client.airdrop(drawAuthority.publicKey, BigInt(LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.setDrawAuthority(drawAuthority.publicKey)
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		await buyTickets();
+		endRaffle();
+
+		// Neither anyone else nor the management authority can draw
+		expect(
+			raffleProgram.methods
+				.drawWinningTicket()
+				.accounts({
+					raffle: raffleAccountId,
+					recentSlothashes: new PublicKey(
+						"SysvarS1otHashes111111111111111111111111111",
+					),
+				})
+				.rpc(),
+		).rejects.toThrow(/NotDrawAuthority/);
+
+		await raffleProgram.methods
+			.drawWinningTicket()
+			.accounts({
+				raffle: raffleAccountId,
+				recentSlothashes: new PublicKey(
+					"SysvarS1otHashes111111111111111111111111111",
+				),
+				drawAuthority: drawAuthority.publicKey,
+			})
+			.signers([drawAuthority])
+			.rpc();
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.raffleState).toEqual({ drawing: {} });
+		expect(raffle.features & (1 << 9)).toBe(1 << 9);
+	});
+
+	it("should fail once tickets were sold", async () => {
+		const { raffleProgram, raffleAccountId, buyTickets } = await setup();
+
+		await buyTickets();
+
+		expect(
+			raffleProgram.methods
+				.setDrawAuthority(new Keypair().publicKey)
+				.accounts({ raffle: raffleAccountId })
+				.rpc(),
+		).rejects.toThrow(/PrizeInfoLocked/);
+	});
+
+	it("should fail from accounts other than the management authority", async () => {
+		const { provider, raffleProgram, raffleAccountId } = await setup();
+
+		const account = new Keypair();
+		provider.client.airdrop(account.publicKey, BigInt(LAMPORTS_PER_SOL));
+
+		expect(
+			raffleProgram.methods
+				.setDrawAuthority(account.publicKey)
+				.accounts({
+					raffle: raffleAccountId,
+					managementAuthority: account.publicKey,
+				})
+				.signers([account])
+				.rpc(),
+		).rejects.toThrow(/NotProgramManagementAuthority/);
+	});
+});
@@ -0,0 +1,349 @@
+import { describe, expect, it } from "bun:test";
+import { createHash } from "node:crypto";
+import { BN, Program } from "@coral-xyz/anchor";
+import {
+	Keypair,
+	LAMPORTS_PER_SOL,
+	PublicKey,
+	TransactionInstruction,
+} from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { LiteSVM } from "litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+const TOKEN_PROGRAM_ID = new PublicKey(
+	"TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+);
+
+// Writes an initialized SPL token account holding `amount` tokens
+const setTokenAccount = (
+	client: LiteSVM,
+	address: PublicKey,
+	mint: PublicKey,
+	owner: PublicKey,
+	amount: number,
+) => {
+	const data = Buffer.alloc(165);
+	mint.toBuffer().copy(data, 0);
+	owner.toBuffer().copy(data, 32);
+	data.writeBigUInt64LE(BigInt(amount), 64);
+	data.writeUInt8(1, 108);
+	client.setAccount(address, {
+		executable: false,
+		owner: TOKEN_PROGRAM_ID,
+		lamports: LAMPORTS_PER_SOL,
+		data,
+	});
+};
+
+// SPL token transfer of `amount` tokens from `source`, signed by its owner
+const transferInstruction = (
+	source: PublicKey,
+	destination: PublicKey,
+	owner: PublicKey,
+	amount: number,
+) => {
+	const data = Buffer.alloc(9);
+	data.writeUInt8(3, 0);
+	data.writeBigUInt64LE(BigInt(amount), 1);
+	return new TransactionInstruction({
+		programId: TOKEN_PROGRAM_ID,
+		keys: [
+			{ pubkey: source, isSigner: false, isWritable: true },
+			{ pubkey: destination, isSigner: false, isWritable: true },
+			{ pubkey: owner, isSigner: true, isWritable: false },
+		],
+		data,
+	});
+};
+
+const sha256 = (...parts: Buffer[]) =>
+	createHash("sha256").update(Buffer.concat(parts)).digest();
+
+const allowlistLeaf = (wallet: PublicKey) =>
+	sha256(Buffer.from("allowlist"), wallet.toBuffer());
+
+const parent = (a: Buffer, b: Buffer) =>
+	Buffer.compare(a, b) <= 0 ? sha256(a, b) : sha256(b, a);
+
+// Root of the allowlist of four wallets and the proof of each wallet
+const allowlist = (wallets: PublicKey[]) => {
+	const [a, b, c, d] = wallets.map(allowlistLeaf);
+	const ab = parent(a, b);
+	const cd = parent(c, d);
+	return {
+		root: parent(ab, cd),
+		proofs: [
+			[b, cd],
+			[a, cd],
+			[d, ab],
+			[c, ab],
+		].map((proof) => proof.map((node) => Array.from(node))),
+	};
+};
+
+describe("set_gate", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(1),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		const newBuyer = () => {
+			const buyer = new Keypair();
+			provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+			return buyer;
+		};
+		const initTicketBalance = (
+			buyer: Keypair,
+			allowlistProof: number[][] | null = null,
+			gateTokenAccount: PublicKey | null = null,
+		) =>
+			raffleProgram.methods
+				.initTicketBalance(allowlistProof)
+				.accounts({
+					signer: buyer.publicKey,
+					raffle: raffleAccountId,
+					gateTokenAccount,
+				})
+				.signers([buyer])
+				.rpc();
+
+		return {
+			client,
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			newBuyer,
+			initTicketBalance,
+		};
+	};
+
+	it("should only let wallets holding the gate token participate", async () => {
+		const { client, raffleProgram, raffleAccountId, newBuyer, initTicketBalance } =
+			await setup();
+		const mint = new Keypair().publicKey;
+
+		await raffleProgram.methods
+			.setGate({ tokenHolder: { mint, minAmount: new BN(2) } })
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.gate?.tokenHolder?.mint.equals(mint)).toBeTrue();
+		expect(raffle.features & (1 << 16)).toBe(1 << 16);
+
+		// Holder of enough tokens
+		const holder = newBuyer();
+		const holderTokenAccount = new Keypair().publicKey;
+		setTokenAccount(client, holderTokenAccount, mint, holder.publicKey, 2);
+		await initTicketBalance(holder, null, holderTokenAccount);
+
+		// Holder of too few tokens
+		const smallHolder = newBuyer();
+		const smallHolderTokenAccount = new Keypair().publicKey;
+		setTokenAccount(client, smallHolderTokenAccount, mint, smallHolder.publicKey, 1);
+		expect(
+			initTicketBalance(smallHolder, null, smallHolderTokenAccount),
+		).rejects.toThrow(/GateTokenRequired/);
+
+		// Wallets can't borrow another holder's token account or omit it
+		const outsider = newBuyer();
+		expect(
+			initTicketBalance(outsider, null, holderTokenAccount),
+		).rejects.toThrow(/GateTokenRequired/);
+		expect(initTicketBalance(outsider)).rejects.toThrow(/GateTokenRequired/);
+
+		// Tokens of another mint don't count
+		const otherTokenAccount = new Keypair().publicKey;
+		setTokenAccount(
+			client,
+			otherTokenAccount,
+			new Keypair().publicKey,
+			outsider.publicKey,
+			10,
+		);
+		expect(
+			initTicketBalance(outsider, null, otherTokenAccount),
+		).rejects.toThrow(/GateTokenRequired/);
+	});
+
+	it("should reject gate tokens moved in within the same transaction", async () => {
+		const { client, raffleProgram, raffleAccountId, newBuyer } = await setup();
+		const mint = new Keypair().publicKey;
+
+		await raffleProgram.methods
+			.setGate({ tokenHolder: { mint, minAmount: new BN(2) } })
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		// The buyer tops up its gate token account from a second account, e.g. one
+		// funded by a flash loan, right before the gate is checked
+		const buyer = newBuyer();
+		const gateTokenAccount = new Keypair().publicKey;
+		const loanTokenAccount = new Keypair().publicKey;
+		setTokenAccount(client, gateTokenAccount, mint, buyer.publicKey, 1);
+		setTokenAccount(client, loanTokenAccount, mint, buyer.publicKey, 10);
+
+		expect(
+			raffleProgram.methods
+				.initTicketBalance(null)
+				.accounts({
+					signer: buyer.publicKey,
+					raffle: raffleAccountId,
+					gateTokenAccount,
+				})
+				.preInstructions([
+					transferInstruction(
+						loanTokenAccount,
+						gateTokenAccount,
+						buyer.publicKey,
+						5,
+					),
+				])
+				.signers([buyer])
+				.rpc(),
+		).rejects.toThrow(/GateBalanceNotSettled/);
+	});
+
+	it("should only let allowlisted wallets participate", async () => {
+		const { raffleProgram, raffleAccountId, newBuyer, initTicketBalance } =
+			await setup();
+		const buyers = [newBuyer(), newBuyer(), newBuyer(), newBuyer()];
+		const { root, proofs } = allowlist(buyers.map((buyer) => buyer.publicKey));
+
+		await raffleProgram.methods
+			.setGate({ merkleAllowlist: { root: Array.from(root) } })
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		// Proofs can't be reused by other wallets
+		const outsider = newBuyer();
+		expect(initTicketBalance(outsider, proofs[0])).rejects.toThrow(
+			/NotAllowlisted/,
+		);
+		expect(initTicketBalance(outsider)).rejects.toThrow(/NotAllowlisted/);
+		expect(initTicketBalance(buyers[0], proofs[1])).rejects.toThrow(
+			/NotAllowlisted/,
+		);
+
+		for (const [index, buyer] of buyers.entries()) {
+			await initTicketBalance(buyer, proofs[index]);
+		}
+	});
+
+	it("should reopen the raffle to everyone when the gate is cleared", async () => {
+		const { raffleProgram, raffleAccountId, newBuyer, initTicketBalance } =
+			await setup();
+
+		await raffleProgram.methods
+			.setGate({ merkleAllowlist: { root: Array.from(Buffer.alloc(32)) } })
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+		const buyer = newBuyer();
+		expect(initTicketBalance(buyer)).rejects.toThrow(/NotAllowlisted/);
+
+		await raffleProgram.methods
+			.setGate(null)
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.gate).toBeNull();
+		expect(raffle.features & (1 << 16)).toBe(0);
+		await initTicketBalance(buyer);
+	});
+
+	it("should reject token gates without a minimum amount", async () => {
+		const { raffleProgram, raffleAccountId } = await setup();
+
+		expect(
+			raffleProgram.methods
+				.setGate({
+					tokenHolder: { mint: new Keypair().publicKey, minAmount: new BN(0) },
+				})
+				.accounts({ raffle: raffleAccountId })
+				.rpc(),
+		).rejects.toThrow(/InvalidGate/);
+	});
+
+	it("should only allow the management authority to gate a raffle before sales", async () => {
+		const { provider, raffleProgram, raffleAccountId, newBuyer, initTicketBalance } =
+			await setup();
+		const gate = { merkleAllowlist: { root: Array.from(Buffer.alloc(32)) } };
+
+		const attacker = newBuyer();
+		expect(
+			raffleProgram.methods
+				.setGate(gate)
+				.accounts({
+					raffle: raffleAccountId,
+					managementAuthority: attacker.publicKey,
+				})
+				.signers([attacker])
+				.rpc(),
+		).rejects.toThrow(/NotProgramManagementAuthority/);
+
+		// Sell a ticket
+		const buyer = newBuyer();
+		await initTicketBalance(buyer);
+		await raffleProgram.methods
+			.buyTickets(
+				new BN(1),
+				Array.from(new Uint8Array(8)),
+				null,
+				null,
+				null,
+				null,
+				false,
+				null,
+			)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+
+		expect(
+			raffleProgram.methods
+				.setGate(gate)
+				.accounts({
+					raffle: raffleAccountId,
+					managementAuthority: provider.publicKey,
+				})
+				.rpc(),
+		).rejects.toThrow(/PrizeInfoLocked/);
+	});
+});
@@ -0,0 +1,112 @@
+import { describe, expect, it } from "bun:test";
+import { createHash } from "node:crypto";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+const hostHash = (host: string) =>
+	Array.from(createHash("sha256").update(host.toLowerCase()).digest());
+
+describe("set_metadata_hosts", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const configId = PublicKey.findProgramAddressSync(
+			[Buffer.from("config")],
+			raffleProgram.programId,
+		)[0];
+
+		const createRaffle = (metadataUri: string) => {
+			const creationTime = client.getClock().unixTimestamp;
+			return raffleProgram.methods
+				.createRaffle(
+					metadataUri,
+					new BN(0.1 * LAMPORTS_PER_SOL),
+					new BN((creationTime + BigInt(3601)).toString()),
+					new BN(1),
+					null,
+					null,
+					false,
+					null,
+					false,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+				)
+				.rpc();
+		};
+
+		return { provider, raffleProgram, configId, createRaffle };
+	};
+
+	it("should only allow raffles with metadata on an allowed host", async () => {
+		const { raffleProgram, configId, createRaffle } = await setup();
+
+		await raffleProgram.methods
+			.setMetadataHosts([hostHash("www.example.org")])
+			.rpc();
+
+		const config = await raffleProgram.account.config.fetch(configId);
+		expect(config.allowedMetadataHosts).toEqual([hostHash("www.example.org")]);
+
+		await createRaffle("https://WWW.example.org/raffle.json");
+		await createRaffle("ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3o");
+
+		expect(
+			createRaffle("https://phishing.example/raffle.json"),
+		).rejects.toThrow(/MetadataHostNotAllowed/);
+		expect(
+			createRaffle("https://www.example.org@phishing.example/raffle.json"),
+		).rejects.toThrow(/MetadataHostNotAllowed/);
+	});
+
+	it("should allow any host while the allowlist is empty", async () => {
+		const { createRaffle } = await setup();
+
+		await createRaffle("https://phishing.example/raffle.json");
+	});
+
+	it("should fail with too many hosts", async () => {
+		const { raffleProgram } = await setup();
+
+		const hosts = Array.from({ length: 9 }, (_, i) =>
+			hostHash(`host${i}.example.org`),
+		);
+		expect(
+			raffleProgram.methods.setMetadataHosts(hosts).rpc(),
+		).rejects.toThrow(/TooManyMetadataHosts/);
+	});
+
+	it("should fail from accounts other than the upgrade authority", async () => {
+		const { provider, raffleProgram } = await setup();
+
+		const account = new Keypair();
+		provider.client.airdrop(account.publicKey, BigInt(LAMPORTS_PER_SOL));
+
+		expect(
+			raffleProgram.methods
+				.setMetadataHosts([])
+				.accounts({ upgradeAuthority: account.publicKey })
+				.signers([account])
+				.rpc(),
+		).rejects.toThrow(/NotProgramUpgradeAuthority/);
+	});
+});
@@ -0,0 +1,96 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+const OPERATOR_STATUS_PRIZE_SHIPPED = 1;
+
+describe("set_operator_status", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(1),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		return { provider, raffleProgram, raffleAccountId };
+	};
+
+	it("should record the operator status and note hash", async () => {
+		const { raffleProgram, raffleAccountId } = await setup();
+
+		const noteHash = Array.from(new Uint8Array(32).fill(7));
+		await raffleProgram.methods
+			.setOperatorStatus(OPERATOR_STATUS_PRIZE_SHIPPED, noteHash)
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.operatorStatus).toBe(OPERATOR_STATUS_PRIZE_SHIPPED);
+		expect(raffle.statusNoteHash).toEqual(noteHash);
+	});
+
+	it("should fail to set an unknown status", async () => {
+		const { raffleProgram, raffleAccountId } = await setup();
+
+		expect(
+			raffleProgram.methods
+				.setOperatorStatus(4, null)
+				.accounts({ raffle: raffleAccountId })
+				.rpc(),
+		).rejects.toThrow(/InvalidOperatorStatus/);
+	});
+
+	it("should fail when not signed by the management authority", async () => {
+		const { provider, raffleProgram, raffleAccountId } = await setup();
+
+		const account = new Keypair();
+		provider.client.airdrop(account.publicKey, BigInt(LAMPORTS_PER_SOL));
+		expect(
+			raffleProgram.methods
+				.setOperatorStatus(OPERATOR_STATUS_PRIZE_SHIPPED, null)
+				.accounts({
+					raffle: raffleAccountId,
+					managementAuthority: account.publicKey,
+				})
+				.signers([account])
+				.rpc(),
+		).rejects.toThrow(/NotProgramManagementAuthority/);
+	});
+});
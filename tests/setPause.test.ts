@@ -0,0 +1,140 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("set_pause", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		const createRaffle = () =>
+			raffleProgram.methods
+				.createRaffle(
+					"https://www.example.org",
+					new BN(0.1 * LAMPORTS_PER_SOL),
+					new BN((creationTime + BigInt(3601)).toString()),
+					new BN(10),
+					null,
+					null,
+					false,
+					null,
+					false,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+				)
+				.rpc();
+		await createRaffle();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+		const buy = (seed: number) =>
+			raffleProgram.methods
+				.buyTickets(
+					new BN(1),
+					Array.from(new Uint8Array([seed, 0, 0, 0, 0, 0, 0, 0])),
+					null,
+					null,
+					null,
+					null,
+					false,
+					null,
+				)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+		await buy(0);
+
+		const endRaffle = () => {
+			const newClock = client.getClock();
+			newClock.unixTimestamp = creationTime + BigInt(3602);
+			client.setClock(newClock);
+		};
+
+		return {
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			buyer,
+			createRaffle,
+			buy,
+			endRaffle,
+		};
+	};
+
+	it("should halt raffle creation and ticket sales until resumed", async () => {
+		const { raffleProgram, raffleAccountId, createRaffle, buy } = await setup();
+
+		await raffleProgram.methods.setPause(true).rpc();
+		expect(createRaffle()).rejects.toThrow(/ProgramPaused/);
+		expect(buy(1)).rejects.toThrow(/ProgramPaused/);
+
+		await raffleProgram.methods.setPause(false).rpc();
+		await buy(1);
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.currentTickets.toNumber()).toBe(2);
+	});
+
+	it("should keep refunds available while paused", async () => {
+		const { provider, raffleProgram, raffleAccountId, buyer, endRaffle } =
+			await setup();
+
+		await raffleProgram.methods.setPause(true).rpc();
+		endRaffle();
+		await raffleProgram.methods
+			.expireRaffle()
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		const balanceBefore = provider.client.getBalance(buyer.publicKey)!;
+		await raffleProgram.methods
+			.reclaimExpiredTickets()
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+		expect(provider.client.getBalance(buyer.publicKey)! > balanceBefore).toBeTrue();
+	});
+
+	it("should fail when not signed by the upgrade authority", async () => {
+		const { provider, raffleProgram } = await setup();
+
+		const account = new Keypair();
+		provider.client.airdrop(account.publicKey, BigInt(LAMPORTS_PER_SOL));
+		expect(
+			raffleProgram.methods
+				.setPause(true)
+				.accounts({ upgradeAuthority: account.publicKey })
+				.signers([account])
+				.rpc(),
+		).rejects.toThrow(/NotProgramUpgradeAuthority/);
+	});
+});
@@ -0,0 +1,139 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("set_payout_destinations", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+		const payoutAuthority = new Keypair();
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: payoutAuthority.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(1),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		// Sell 1 SOL worth of tickets, meeting the threshold
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+		await raffleProgram.methods
+			.buyTickets(new BN(10), Array.from(new Uint8Array(8)), null, null, null, null, false, null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+
+		return { provider, raffleProgram, raffleAccountId, payoutAuthority };
+	};
+
+	it("should only pay withdrawals to an approved destination", async () => {
+		const { provider, raffleProgram, raffleAccountId, payoutAuthority } =
+			await setup();
+
+		const coldWallet = new Keypair();
+		await raffleProgram.methods
+			.setPayoutDestinations([coldWallet.publicKey])
+			.rpc();
+
+		// Withdrawals to the payout authority or other accounts are rejected
+		expect(
+			raffleProgram.methods
+				.withdrawFromTreasury()
+				.accounts({
+					raffle: raffleAccountId,
+					payoutAuthority: payoutAuthority.publicKey,
+				})
+				.rpc(),
+		).rejects.toThrow(/PayoutDestinationNotAllowed/);
+		expect(
+			raffleProgram.methods
+				.withdrawFromTreasury()
+				.accounts({
+					raffle: raffleAccountId,
+					payoutAuthority: payoutAuthority.publicKey,
+					destination: new Keypair().publicKey,
+				})
+				.rpc(),
+		).rejects.toThrow(/PayoutDestinationNotAllowed/);
+
+		await raffleProgram.methods
+			.withdrawFromTreasury()
+			.accounts({
+				raffle: raffleAccountId,
+				payoutAuthority: payoutAuthority.publicKey,
+				destination: coldWallet.publicKey,
+			})
+			.rpc();
+
+		expect(provider.client.getBalance(coldWallet.publicKey)).toBe(
+			BigInt(LAMPORTS_PER_SOL),
+		);
+		expect(provider.client.getBalance(payoutAuthority.publicKey)).toBeNull();
+	});
+
+	it("should fail with too many destinations", async () => {
+		const { raffleProgram } = await setup();
+
+		const destinations = Array.from(
+			{ length: 5 },
+			() => new Keypair().publicKey,
+		);
+		expect(
+			raffleProgram.methods.setPayoutDestinations(destinations).rpc(),
+		).rejects.toThrow(/TooManyPayoutDestinations/);
+	});
+
+	it("should fail from accounts other than the upgrade authority", async () => {
+		const { provider, raffleProgram } = await setup();
+
+		const account = new Keypair();
+		provider.client.airdrop(account.publicKey, BigInt(LAMPORTS_PER_SOL));
+
+		expect(
+			raffleProgram.methods
+				.setPayoutDestinations([account.publicKey])
+				.accounts({ upgradeAuthority: account.publicKey })
+				.signers([account])
+				.rpc(),
+		).rejects.toThrow(/NotProgramUpgradeAuthority/);
+	});
+});
@@ -0,0 +1,134 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("set_priority_access", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(7200)).toString()),
+				new BN(1),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		// Tier 2 and above get a one hour head start
+		await raffleProgram.methods
+			.setPriorityAccess(2, new BN((creationTime + BigInt(3600)).toString()))
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		const newBuyer = async (tier: number | null) => {
+			const buyer = new Keypair();
+			provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+			await raffleProgram.methods
+				.initTicketBalance(null)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+			if (tier !== null) {
+				await raffleProgram.methods.setUserTier(buyer.publicKey, tier).rpc();
+			}
+			return buyer;
+		};
+
+		const buyTickets = (buyer: Keypair) =>
+			raffleProgram.methods
+				.buyTickets(new BN(1), Array.from(new Uint8Array(8)), null, null, null, null, false, null)
+				.accounts({
+					signer: buyer.publicKey,
+					raffle: raffleAccountId,
+					userProfile: PublicKey.findProgramAddressSync(
+						[Buffer.from("user_profile"), buyer.publicKey.toBytes()],
+						raffleProgram.programId,
+					)[0],
+				})
+				.signers([buyer])
+				.rpc();
+
+		const timeTravel = (seconds: number) => {
+			const newClock = client.getClock();
+			newClock.unixTimestamp = newClock.unixTimestamp + BigInt(seconds);
+			client.setClock(newClock);
+		};
+
+		return {
+			raffleProgram,
+			raffleAccountId,
+			newBuyer,
+			buyTickets,
+			timeTravel,
+		};
+	};
+
+	it("should only sell to priority tiers until the public sale", async () => {
+		const { raffleProgram, raffleAccountId, newBuyer, buyTickets, timeTravel } =
+			await setup();
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.priorityMinTier).toBe(2);
+		expect(raffle.features & (1 << 10)).toBe(1 << 10);
+
+		const goldBuyer = await newBuyer(2);
+		const silverBuyer = await newBuyer(1);
+		await buyTickets(goldBuyer);
+		expect(buyTickets(silverBuyer)).rejects.toThrow(/PriorityAccessRequired/);
+
+		// Anyone can buy once the priority phase ended, even without a profile
+		timeTravel(3600);
+		await buyTickets(silverBuyer);
+		const publicBuyer = await newBuyer(null);
+		await buyTickets(publicBuyer);
+
+		const updatedRaffle =
+			await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(updatedRaffle.currentTickets.toNumber()).toBe(3);
+	});
+
+	it("should fail for a priority phase ending after the raffle", async () => {
+		const { raffleProgram, raffleAccountId } = await setup();
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(
+			raffleProgram.methods
+				.setPriorityAccess(1, raffle.endTime)
+				.accounts({ raffle: raffleAccountId })
+				.rpc(),
+		).rejects.toThrow(/InvalidPriorityAccess/);
+	});
+});
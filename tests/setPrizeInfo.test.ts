@@ -0,0 +1,135 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("set_prize_info", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		const metadataUri = "https://www.example.org";
+		const ticketPrice = new BN(0.1 * LAMPORTS_PER_SOL);
+		const minTickets = new BN(1);
+		const endTime = new BN((creationTime + BigInt(3601)).toString());
+
+		// Create raffle
+		await raffleProgram.methods
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+		const prizeInfoId = PublicKey.findProgramAddressSync(
+			[Buffer.from("prize_info"), raffleAccountId.toBytes()],
+			raffleProgram.programId,
+		)[0];
+
+		return { provider, raffleProgram, raffleAccountId, prizeInfoId };
+	};
+
+	it("should set and overwrite the prize info before sales start", async () => {
+		const { raffleProgram, raffleAccountId, prizeInfoId } = await setup();
+
+		await raffleProgram.methods
+			.setPrizeInfo(
+				"Gaming laptop",
+				new BN(10 * LAMPORTS_PER_SOL),
+				{ physical: {} },
+				null,
+			)
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		let prizeInfo = await raffleProgram.account.prizeInfo.fetch(prizeInfoId);
+		expect(prizeInfo.raffle.equals(raffleAccountId)).toBeTrue();
+		expect(prizeInfo.title).toBe("Gaming laptop");
+		expect(prizeInfo.valueEstimate.toNumber()).toBe(10 * LAMPORTS_PER_SOL);
+		expect(prizeInfo.prizeType.physical).toBeDefined();
+		expect(prizeInfo.escrow).toBeNull();
+
+		const escrow = new Keypair().publicKey;
+		await raffleProgram.methods
+			.setPrizeInfo("2 SOL", new BN(2 * LAMPORTS_PER_SOL), { sol: {} }, escrow)
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		prizeInfo = await raffleProgram.account.prizeInfo.fetch(prizeInfoId);
+		expect(prizeInfo.title).toBe("2 SOL");
+		expect(prizeInfo.prizeType.sol).toBeDefined();
+		expect(prizeInfo.escrow?.equals(escrow)).toBeTrue();
+	});
+
+	it("should fail once tickets have been sold", async () => {
+		const { provider, raffleProgram, raffleAccountId } = await setup();
+
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+		await raffleProgram.methods
+			.buyTickets(new BN(1), Array.from(new Uint8Array(8)), null, null, null, null, false, null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+
+		expect(
+			raffleProgram.methods
+				.setPrizeInfo("Gaming laptop", new BN(0), { physical: {} }, null)
+				.accounts({ raffle: raffleAccountId })
+				.rpc(),
+		).rejects.toThrow(/PrizeInfoLocked/);
+	});
+
+	it("should fail from accounts other than the management authority", async () => {
+		const { provider, raffleProgram, raffleAccountId } = await setup();
+
+		const other = new Keypair();
+		provider.client.airdrop(other.publicKey, BigInt(1 * LAMPORTS_PER_SOL));
+		expect(
+			raffleProgram.methods
+				.setPrizeInfo("Gaming laptop", new BN(0), { physical: {} }, null)
+				.accounts({
+					raffle: raffleAccountId,
+					managementAuthority: other.publicKey,
+				})
+				.signers([other])
+				.rpc(),
+		).rejects.toThrow(/NotProgramManagementAuthority/);
+	});
+});
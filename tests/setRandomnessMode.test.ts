@@ -0,0 +1,234 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+const SWITCHBOARD_ON_DEMAND_PROGRAM_ID = new PublicKey(
+	"SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv",
+);
+const RANDOMNESS_DISCRIMINATOR = [10, 66, 229, 135, 220, 239, 217, 114];
+
+// Encodes a Switchboard randomness account committed in `seedSlot`
+const randomnessData = (
+	seedSlot: bigint,
+	revealSlot: bigint,
+	value: Uint8Array,
+) => {
+	const data = Buffer.alloc(408);
+	Buffer.from(RANDOMNESS_DISCRIMINATOR).copy(data, 0);
+	data.writeBigUInt64LE(seedSlot, 104);
+	data.writeBigUInt64LE(revealSlot, 144);
+	Buffer.from(value).copy(data, 152);
+	return data;
+};
+
+describe("set_randomness_mode", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(1),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		const buyTickets = async () => {
+			const buyer = new Keypair();
+			provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+			await raffleProgram.methods
+				.initTicketBalance(null)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+			await raffleProgram.methods
+				.buyTickets(new BN(5), Array.from(new Uint8Array(8)), null, null, null, null, false, null)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+		};
+
+		const endRaffle = () => {
+			const newClock = client.getClock();
+			newClock.unixTimestamp = newClock.unixTimestamp + BigInt(3601);
+			client.setClock(newClock);
+			client.warpToSlot(BigInt(100));
+		};
+
+		// Commits a randomness account in the slot before the current one
+		const commitRandomness = (owner = SWITCHBOARD_ON_DEMAND_PROGRAM_ID) => {
+			const randomnessAccount = new Keypair().publicKey;
+			const seedSlot = client.getClock().slot - BigInt(1);
+			client.setAccount(randomnessAccount, {
+				executable: false,
+				owner,
+				lamports: LAMPORTS_PER_SOL,
+				data: randomnessData(seedSlot, BigInt(0), new Uint8Array(32)),
+			});
+			const reveal = (value: Uint8Array) =>
+				client.setAccount(randomnessAccount, {
+					executable: false,
+					owner,
+					lamports: LAMPORTS_PER_SOL,
+					data: randomnessData(seedSlot, seedSlot + BigInt(1), value),
+				});
+			return { randomnessAccount, reveal };
+		};
+
+		return {
+			raffleProgram,
+			raffleAccountId,
+			buyTickets,
+			endRaffle,
+			commitRandomness,
+		};
+	};
+
+	it("should draw the winner from revealed randomness", async () => {
+		const {
+			raffleProgram,
+			raffleAccountId,
+			buyTickets,
+			endRaffle,
+			commitRandomness,
+		} = await setup();
+
+		await raffleProgram.methods
+			.setRandomnessMode({ vrf: {} })
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+		await buyTickets();
+		endRaffle();
+
+		// The slot hash draw is disabled for the raffle
+		expect(
+			raffleProgram.methods
+				.drawWinningTicket()
+				.accounts({
+					raffle: raffleAccountId,
+					recentSlothashes: new PublicKey(
+						"SysvarS1otHashes111111111111111111111111111",
+					),
+				})
+				.rpc(),
+		).rejects.toThrow(/SlotHashDrawDisabled/);
+
+		const { randomnessAccount, reveal } = commitRandomness();
+		await raffleProgram.methods
+			.requestRandomness()
+			.accounts({ raffle: raffleAccountId, randomnessAccount })
+			.rpc();
+
+		expect(
+			raffleProgram.methods
+				.settleRandomness()
+				.accounts({ raffle: raffleAccountId, randomnessAccount })
+				.rpc(),
+		).rejects.toThrow(/RandomnessNotRevealed/);
+
+		reveal(new Uint8Array(32).fill(7));
+		await raffleProgram.methods
+			.settleRandomness()
+			.accounts({ raffle: raffleAccountId, randomnessAccount })
+			.rpc();
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.raffleState.drawing).toBeDefined();
+		expect(raffle.randomnessAccount?.toBase58()).toBe(
+			randomnessAccount.toBase58(),
+		);
+		expect(raffle.winningTicket?.toNumber()).toBeLessThan(5);
+	});
+
+	it("should fail to request randomness from an account not owned by Switchboard", async () => {
+		const {
+			raffleProgram,
+			raffleAccountId,
+			buyTickets,
+			endRaffle,
+			commitRandomness,
+		} = await setup();
+
+		await raffleProgram.methods
+			.setRandomnessMode({ vrf: {} })
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+		await buyTickets();
+		endRaffle();
+
+		const { randomnessAccount } = commitRandomness(new Keypair().publicKey);
+		expect(
+			raffleProgram.methods
+				.requestRandomness()
+				.accounts({ raffle: raffleAccountId, randomnessAccount })
+				.rpc(),
+		).rejects.toThrow(/InvalidRandomnessAccount/);
+	});
+
+	it("should fail to request randomness for slot hash raffles", async () => {
+		const {
+			raffleProgram,
+			raffleAccountId,
+			buyTickets,
+			endRaffle,
+			commitRandomness,
+		} = await setup();
+
+		await buyTickets();
+		endRaffle();
+
+		const { randomnessAccount } = commitRandomness();
+		expect(
+			raffleProgram.methods
+				.requestRandomness()
+				.accounts({ raffle: raffleAccountId, randomnessAccount })
+				.rpc(),
+		).rejects.toThrow(/VrfNotEnabled/);
+	});
+
+	it("should fail to change the randomness mode after tickets were sold", async () => {
+		const { raffleProgram, raffleAccountId, buyTickets } = await setup();
+
+		await buyTickets();
+
+		expect(
+			raffleProgram.methods
+				.setRandomnessMode({ vrf: {} })
+				.accounts({ raffle: raffleAccountId })
+				.rpc(),
+		).rejects.toThrow(/PrizeInfoLocked/);
+	});
+});
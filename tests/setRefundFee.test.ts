@@ -0,0 +1,156 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("set_refund_fee", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+		const payoutAuthority = new Keypair();
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: payoutAuthority.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		return { client, provider, raffleProgram, payoutAuthority };
+	};
+
+	it("should retain the disclosed fee from refunds of expired raffles", async () => {
+		const { client, provider, raffleProgram, payoutAuthority } = await setup();
+
+		// 1% refund fee
+		await raffleProgram.methods.setRefundFee(100).rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(10),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+		const treasuryId = PublicKey.findProgramAddressSync(
+			[Buffer.from("treasury"), raffleAccountId.toBytes()],
+			raffleProgram.programId,
+		)[0];
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.refundFeeBps).toBe(100);
+
+		// Buy 0.5 SOL worth of tickets, below the threshold
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+		await raffleProgram.methods
+			.buyTickets(new BN(5), Array.from(new Uint8Array(8)), null, null, null, null, false, null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+
+		// Time-travel to when the raffle ends and expire it
+		const newClock = client.getClock();
+		newClock.unixTimestamp = creationTime + BigInt(3602);
+		client.setClock(newClock);
+		await raffleProgram.methods
+			.expireRaffle()
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		const buyerBalanceBefore = provider.client.getBalance(buyer.publicKey);
+		if (!buyerBalanceBefore) {
+			throw new Error("Failed to fetch balance");
+		}
+		const ticketBalanceRent = provider.client.minimumBalanceForRentExemption(
+			BigInt(raffleProgram.account.ticketBalance.size),
+		);
+
+		await raffleProgram.methods
+			.reclaimExpiredTickets()
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+
+		// The buyer is refunded 99% of the ticket price
+		expect(provider.client.getBalance(buyer.publicKey)).toBe(
+			buyerBalanceBefore + ticketBalanceRent + BigInt(0.495 * LAMPORTS_PER_SOL),
+		);
+		const treasury = await raffleProgram.account.treasury.fetch(treasuryId);
+		expect(treasury.totalRefundFees.toNumber()).toBe(0.005 * LAMPORTS_PER_SOL);
+
+		// Only the retained fee can be withdrawn from the expired raffle
+		await raffleProgram.methods
+			.withdrawFromTreasury()
+			.accounts({
+				raffle: raffleAccountId,
+				payoutAuthority: payoutAuthority.publicKey,
+			})
+			.rpc();
+		expect(provider.client.getBalance(payoutAuthority.publicKey)).toBe(
+			BigInt(0.005 * LAMPORTS_PER_SOL),
+		);
+
+		expect(
+			raffleProgram.methods
+				.withdrawFromTreasury()
+				.accounts({
+					raffle: raffleAccountId,
+					payoutAuthority: payoutAuthority.publicKey,
+				})
+				.rpc(),
+		).rejects.toThrow(/RaffleIsExpired/);
+	});
+
+	it("should fail with a fee above the cap", async () => {
+		const { raffleProgram } = await setup();
+
+		expect(raffleProgram.methods.setRefundFee(201).rpc()).rejects.toThrow(
+			/InvalidRefundFee/,
+		);
+	});
+
+	it("should fail from accounts other than the management authority", async () => {
+		const { provider, raffleProgram } = await setup();
+
+		const account = new Keypair();
+		provider.client.airdrop(account.publicKey, BigInt(LAMPORTS_PER_SOL));
+
+		expect(
+			raffleProgram.methods
+				.setRefundFee(100)
+				.accounts({ managementAuthority: account.publicKey })
+				.signers([account])
+				.rpc(),
+		).rejects.toThrow(/NotProgramManagementAuthority/);
+	});
+});
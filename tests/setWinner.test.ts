@@ -117,7 +117,24 @@ describe("set_winner", async () => {
 
 			// Create raffle
 			await raffleProgram.methods
-				.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+				.createRaffle(
+					metadataUri,
+					ticketPrice,
+					endTime,
+					minTickets,
+					null,
+					null,
+					false,
+					null,
+					false,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+				)
 				.rpc();
 			const raffleAccountId = PublicKey.findProgramAddressSync(
 				[
@@ -155,7 +172,7 @@ describe("set_winner", async () => {
 
 				// Create ticket balance
 				await raffleProgram.methods
-					.initTicketBalance()
+					.initTicketBalance(null)
 					.accounts({
 						signer: entry.keypair.publicKey,
 						raffle: new PublicKey(raffleAccountId),
@@ -179,6 +196,10 @@ describe("set_winner", async () => {
 					ticketCount: new BN(entry.ticketCount),
 					ticketStartIndex: new BN(entry.startTicket),
 					seed: Array.from(entrySeed),
+					entryIndex: new BN(0),
+					version: 2,
+					bump: 0,
+					createdAt: new BN(0),
 				});
 				provider.client.setAccount(entryAccountId, {
 					executable: false,
@@ -205,15 +226,70 @@ describe("set_winner", async () => {
 				raffleState: {
 					drawing: {},
 				},
-				winnerAddress: new Keypair().publicKey, // We need to assign some random public key here, to assign the space
+				winnerAddress: null,
 				winningTicket: new BN(input.winningTicket),
 				maxTickets: null,
+				participantCount: new BN(0),
+				eventSeq: new BN(0),
+				minRevenue: null,
+				minDrawSlot: new BN(0),
+				reservedTickets: new BN(0),
+				refundFeeBps: 0,
+				rentPayer: provider.publicKey,
+				features: 0,
+				allowedRegions: new BN(0),
+				ageRestricted: false,
+				drawAuthority: null,
+				priorityMinTier: 0,
+				priorityUntil: new BN(0),
+				minUniqueBuyers: null,
+				paymentMint: null,
+				randomnessMode: { slotHashes: {} },
+				randomnessAccount: null,
+				randomnessCommitSlot: new BN(0),
+				prizeEscrowed: false,
+				winnerCount: 1,
+				winningTickets: [],
+				additionalWinners: [],
+				winnerDataSubmitted: 0,
+				prizesClaimed: 0,
+				maxTicketsPerWallet: null,
+				gate: null,
+				deferredNumbering: false,
+				entryCount: new BN(0),
+				numberedEntries: new BN(0),
+				numberedTickets: new BN(0),
+				feeBpsOverride: null,
+				snapshotRoot: null,
+				operatorStatus: 0,
+				statusNoteHash: null,
+				fundingDeadline: null,
+				pricingTiers: [],
+				tierDiscounts: new BN(0),
+				crankRewardBps: 0,
+				referralFeeBps: 0,
+				extensionCount: 0,
+				extendedSecs: new BN(0),
+				refundDeadline: new BN(0),
+				prizeValueUsdCents: null,
+				ticketCountDisplayCap: null,
+				ticketReceipts: false,
+				arweaveTxId: null,
+				creator: null,
+				drawDelaySlots: 0,
+				drawTargetSlot: new BN(0),
+				refundMint: null,
+				refundRate: new BN(0), drawValueLamports: new BN(0), drawUsdRate: null, successor: null, randomnessProvider: null, randomnessCommitment: null, accessCodeHash: null, closeReason: 0, crankRewardPaid: false, additionalWinnerHashes: [], voidedRanges: [], pendingReservations: new BN(0), openSlotReleased: false, campaignSubsidies: new BN(0),
 			});
 			provider.client.setAccount(raffleAccountId, {
 				executable: false,
 				owner: raffleProgram.programId,
 				lamports: 1 * LAMPORTS_PER_SOL,
-				data: raffleData,
+				// Leave room for the winner to be set
+				data: Buffer.concat([
+					raffleData,
+					Buffer.alloc(raffleProgram.account.raffle.size - raffleData.length),
+				]),
 			});
 
 			const raffleBeforeDraw =
@@ -221,7 +297,7 @@ describe("set_winner", async () => {
 
 			// Set winner
 			await raffleProgram.methods
-				.setWinner(Array.from(winningSeed))
+				.setWinner(Array.from(winningSeed), 0)
 				.accounts({
 					raffle: raffleAccountId,
 				})
@@ -291,7 +367,24 @@ describe("set_winner", async () => {
 
 			// Create raffle
 			await raffleProgram.methods
-				.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+				.createRaffle(
+					metadataUri,
+					ticketPrice,
+					endTime,
+					minTickets,
+					null,
+					null,
+					false,
+					null,
+					false,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+				)
 				.rpc();
 			const raffleAccountId = PublicKey.findProgramAddressSync(
 				[
@@ -328,7 +421,7 @@ describe("set_winner", async () => {
 
 			// Create ticket balance
 			await raffleProgram.methods
-				.initTicketBalance()
+				.initTicketBalance(null)
 				.accounts({
 					signer: account.publicKey,
 					raffle: new PublicKey(raffleAccountId),
@@ -352,6 +445,10 @@ describe("set_winner", async () => {
 				ticketCount: new BN(1),
 				ticketStartIndex: new BN(0),
 				seed: Array.from(entrySeed),
+				entryIndex: new BN(0),
+				version: 2,
+				bump: 0,
+				createdAt: new BN(0),
 			});
 			provider.client.setAccount(entryAccountId, {
 				executable: false,
@@ -372,21 +469,76 @@ describe("set_winner", async () => {
 				raffleState: {
 					[state]: {},
 				},
-				winnerAddress: new Keypair().publicKey, // We need to assign some random public key here, to assign the space
+				winnerAddress: null,
 				winningTicket: new BN(0),
 				maxTickets: null,
+				participantCount: new BN(0),
+				eventSeq: new BN(0),
+				minRevenue: null,
+				minDrawSlot: new BN(0),
+				reservedTickets: new BN(0),
+				refundFeeBps: 0,
+				rentPayer: provider.publicKey,
+				features: 0,
+				allowedRegions: new BN(0),
+				ageRestricted: false,
+				drawAuthority: null,
+				priorityMinTier: 0,
+				priorityUntil: new BN(0),
+				minUniqueBuyers: null,
+				paymentMint: null,
+				randomnessMode: { slotHashes: {} },
+				randomnessAccount: null,
+				randomnessCommitSlot: new BN(0),
+				prizeEscrowed: false,
+				winnerCount: 1,
+				winningTickets: [],
+				additionalWinners: [],
+				winnerDataSubmitted: 0,
+				prizesClaimed: 0,
+				maxTicketsPerWallet: null,
+				gate: null,
+				deferredNumbering: false,
+				entryCount: new BN(0),
+				numberedEntries: new BN(0),
+				numberedTickets: new BN(0),
+				feeBpsOverride: null,
+				snapshotRoot: null,
+				operatorStatus: 0,
+				statusNoteHash: null,
+				fundingDeadline: null,
+				pricingTiers: [],
+				tierDiscounts: new BN(0),
+				crankRewardBps: 0,
+				referralFeeBps: 0,
+				extensionCount: 0,
+				extendedSecs: new BN(0),
+				refundDeadline: new BN(0),
+				prizeValueUsdCents: null,
+				ticketCountDisplayCap: null,
+				ticketReceipts: false,
+				arweaveTxId: null,
+				creator: null,
+				drawDelaySlots: 0,
+				drawTargetSlot: new BN(0),
+				refundMint: null,
+				refundRate: new BN(0), drawValueLamports: new BN(0), drawUsdRate: null, successor: null, randomnessProvider: null, randomnessCommitment: null, accessCodeHash: null, closeReason: 0, crankRewardPaid: false, additionalWinnerHashes: [], voidedRanges: [], pendingReservations: new BN(0), openSlotReleased: false, campaignSubsidies: new BN(0),
 			});
 			provider.client.setAccount(raffleAccountId, {
 				executable: false,
 				owner: raffleProgram.programId,
 				lamports: 1 * LAMPORTS_PER_SOL,
-				data: raffleData,
+				// Leave room for the winner to be set
+				data: Buffer.concat([
+					raffleData,
+					Buffer.alloc(raffleProgram.account.raffle.size - raffleData.length),
+				]),
 			});
 
 			// Set winner
 			expect(
 				raffleProgram.methods
-					.setWinner(Array.from(entrySeed))
+					.setWinner(Array.from(entrySeed), 0)
 					.accounts({
 						raffle: raffleAccountId,
 					})
@@ -460,7 +612,24 @@ describe("set_winner", async () => {
 
 			// Create raffle
 			await raffleProgram.methods
-				.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+				.createRaffle(
+					metadataUri,
+					ticketPrice,
+					endTime,
+					minTickets,
+					null,
+					null,
+					false,
+					null,
+					false,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+				)
 				.rpc();
 			const raffleAccountId = PublicKey.findProgramAddressSync(
 				[
@@ -497,7 +666,7 @@ describe("set_winner", async () => {
 
 			// Create ticket balance
 			await raffleProgram.methods
-				.initTicketBalance()
+				.initTicketBalance(null)
 				.accounts({
 					signer: account.publicKey,
 					raffle: new PublicKey(raffleAccountId),
@@ -521,6 +690,10 @@ describe("set_winner", async () => {
 				ticketCount: new BN(input.ticketCount),
 				ticketStartIndex: new BN(input.ticketStartIndex),
 				seed: Array.from(entrySeed),
+				entryIndex: new BN(0),
+				version: 2,
+				bump: 0,
+				createdAt: new BN(0),
 			});
 			provider.client.setAccount(entryAccountId, {
 				executable: false,
@@ -541,21 +714,76 @@ describe("set_winner", async () => {
 				raffleState: {
 					drawing: {},
 				},
-				winnerAddress: new Keypair().publicKey, // We need to assign some random public key here, to assign the space
+				winnerAddress: null,
 				winningTicket: new BN(input.winningTicket),
 				maxTickets: null,
+				participantCount: new BN(0),
+				eventSeq: new BN(0),
+				minRevenue: null,
+				minDrawSlot: new BN(0),
+				reservedTickets: new BN(0),
+				refundFeeBps: 0,
+				rentPayer: provider.publicKey,
+				features: 0,
+				allowedRegions: new BN(0),
+				ageRestricted: false,
+				drawAuthority: null,
+				priorityMinTier: 0,
+				priorityUntil: new BN(0),
+				minUniqueBuyers: null,
+				paymentMint: null,
+				randomnessMode: { slotHashes: {} },
+				randomnessAccount: null,
+				randomnessCommitSlot: new BN(0),
+				prizeEscrowed: false,
+				winnerCount: 1,
+				winningTickets: [],
+				additionalWinners: [],
+				winnerDataSubmitted: 0,
+				prizesClaimed: 0,
+				maxTicketsPerWallet: null,
+				gate: null,
+				deferredNumbering: false,
+				entryCount: new BN(0),
+				numberedEntries: new BN(0),
+				numberedTickets: new BN(0),
+				feeBpsOverride: null,
+				snapshotRoot: null,
+				operatorStatus: 0,
+				statusNoteHash: null,
+				fundingDeadline: null,
+				pricingTiers: [],
+				tierDiscounts: new BN(0),
+				crankRewardBps: 0,
+				referralFeeBps: 0,
+				extensionCount: 0,
+				extendedSecs: new BN(0),
+				refundDeadline: new BN(0),
+				prizeValueUsdCents: null,
+				ticketCountDisplayCap: null,
+				ticketReceipts: false,
+				arweaveTxId: null,
+				creator: null,
+				drawDelaySlots: 0,
+				drawTargetSlot: new BN(0),
+				refundMint: null,
+				refundRate: new BN(0), drawValueLamports: new BN(0), drawUsdRate: null, successor: null, randomnessProvider: null, randomnessCommitment: null, accessCodeHash: null, closeReason: 0, crankRewardPaid: false, additionalWinnerHashes: [], voidedRanges: [], pendingReservations: new BN(0), openSlotReleased: false, campaignSubsidies: new BN(0),
 			});
 			provider.client.setAccount(raffleAccountId, {
 				executable: false,
 				owner: raffleProgram.programId,
 				lamports: 1 * LAMPORTS_PER_SOL,
-				data: raffleData,
+				// Leave room for the winner to be set
+				data: Buffer.concat([
+					raffleData,
+					Buffer.alloc(raffleProgram.account.raffle.size - raffleData.length),
+				]),
 			});
 
 			// Set winner
 			expect(
 				raffleProgram.methods
-					.setWinner(Array.from(entrySeed))
+					.setWinner(Array.from(entrySeed), 0)
 					.accounts({
 						raffle: raffleAccountId,
 					})
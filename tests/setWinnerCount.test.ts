@@ -0,0 +1,296 @@
+import { describe, expect, it } from "bun:test";
+import { createHash } from "crypto";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("set_winner_count", async () => {
+	const setup = async (anonymizeWinner: boolean) => {
+		const client = fromWorkspace(".");
+		client.withSysvars();
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(1),
+				null,
+				null,
+				anonymizeWinner,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		return { client, provider, raffleProgram, raffleAccountId, creationTime };
+	};
+
+	it("should draw distinct tickets and let each winner claim a share of the prize", async () => {
+		const { client, provider, raffleProgram, raffleAccountId, creationTime } =
+			await setup(false);
+
+		await raffleProgram.methods
+			.setWinnerCount(3)
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+		await raffleProgram.methods
+			.depositPrize(new BN(3 * LAMPORTS_PER_SOL))
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		// Every buyer holds a single ticket, numbered in order of purchase
+		const buyers: { keypair: Keypair; entrySeed: Uint8Array }[] = [];
+		for (let i = 0; i < 5; i++) {
+			const keypair = new Keypair();
+			provider.client.airdrop(keypair.publicKey, BigInt(LAMPORTS_PER_SOL));
+			const entrySeed = new Uint8Array(8);
+			crypto.getRandomValues(entrySeed);
+			await raffleProgram.methods
+				.initTicketBalance(null)
+				.accounts({ signer: keypair.publicKey, raffle: raffleAccountId })
+				.signers([keypair])
+				.rpc();
+			await raffleProgram.methods
+				.buyTickets(new BN(1), Array.from(entrySeed), null, null, null, null, false, null)
+				.accounts({ signer: keypair.publicKey, raffle: raffleAccountId })
+				.signers([keypair])
+				.rpc();
+			buyers.push({ keypair, entrySeed });
+		}
+
+		const newClock = client.getClock();
+		newClock.unixTimestamp = creationTime + BigInt(3601);
+		client.setClock(newClock);
+		await raffleProgram.methods
+			.drawWinningTicket()
+			.accounts({
+				raffle: raffleAccountId,
+				recentSlothashes: new PublicKey(
+					"SysvarS1otHashes111111111111111111111111111",
+				),
+			})
+			.rpc();
+
+		let raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		const winningTickets = raffle.winningTickets.map((ticket) =>
+			ticket.toNumber(),
+		);
+		expect(winningTickets).toHaveLength(3);
+		expect(new Set(winningTickets).size).toBe(3);
+		expect(winningTickets[0]).toBe(raffle.winningTicket?.toNumber());
+
+		// Winners are set in prize order
+		const winners = winningTickets.map((ticket) => buyers[ticket]);
+		expect(
+			raffleProgram.methods
+				.setWinner(Array.from(winners[1].entrySeed), 1)
+				.accounts({ raffle: raffleAccountId })
+				.rpc(),
+		).rejects.toThrow(/InvalidWinnerIndex/);
+
+		for (const [winnerIndex, winner] of winners.entries()) {
+			await raffleProgram.methods
+				.setWinner(Array.from(winner.entrySeed), winnerIndex)
+				.accounts({ raffle: raffleAccountId })
+				.rpc();
+		}
+		raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.raffleState.drawn).toBeDefined();
+		expect(
+			raffle.additionalWinners.map((winner) => winner.toBase58()),
+		).toEqual(
+			winners.slice(1).map((winner) => winner.keypair.publicKey.toBase58()),
+		);
+
+		// Each prize is set and claimed exactly once
+		expect(
+			raffleProgram.methods
+				.setWinner(Array.from(winners[2].entrySeed), 2)
+				.accounts({ raffle: raffleAccountId })
+				.rpc(),
+		).rejects.toThrow(/WinnerAlreadySet/);
+
+		const prizeVaultId = PublicKey.findProgramAddressSync(
+			[Buffer.from("prize_vault"), raffleAccountId.toBytes()],
+			raffleProgram.programId,
+		)[0];
+		for (const [winnerIndex, winner] of winners.entries()) {
+			const balance = provider.client.getBalance(winner.keypair.publicKey)!;
+			await raffleProgram.methods
+				.claimPrize(null, winnerIndex)
+				.accounts({
+					raffle: raffleAccountId,
+					winner: winner.keypair.publicKey,
+				})
+				.signers([winner.keypair])
+				.rpc();
+			expect(
+				provider.client.getBalance(winner.keypair.publicKey)! - balance,
+			).toBeGreaterThan(BigInt(0.99 * LAMPORTS_PER_SOL));
+
+			if (winnerIndex === 0) {
+				expect(
+					raffleProgram.methods
+						.claimPrize(null, 0)
+						.accounts({
+							raffle: raffleAccountId,
+							winner: winner.keypair.publicKey,
+						})
+						.signers([winner.keypair])
+						.rpc(),
+				).rejects.toThrow(/PrizeAlreadyClaimed/);
+			}
+		}
+		expect(provider.client.getAccount(prizeVaultId)).toBeNull();
+	});
+
+	it("should only store the commitments of anonymized winners", async () => {
+		const { client, provider, raffleProgram, raffleAccountId, creationTime } =
+			await setup(true);
+
+		await raffleProgram.methods
+			.setWinnerCount(2)
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		// Every buyer holds a single ticket and commits to a secret
+		const buyers: {
+			keypair: Keypair;
+			entrySeed: Uint8Array;
+			secret: Uint8Array;
+			ticketBalanceId: PublicKey;
+		}[] = [];
+		for (let i = 0; i < 3; i++) {
+			const keypair = new Keypair();
+			provider.client.airdrop(keypair.publicKey, BigInt(LAMPORTS_PER_SOL));
+			const entrySeed = crypto.getRandomValues(new Uint8Array(8));
+			const secret = crypto.getRandomValues(new Uint8Array(32));
+			await raffleProgram.methods
+				.initTicketBalance(null)
+				.accounts({ signer: keypair.publicKey, raffle: raffleAccountId })
+				.signers([keypair])
+				.rpc();
+			await raffleProgram.methods
+				.buyTickets(new BN(1), Array.from(entrySeed), null, null, null, null, false, null)
+				.accounts({ signer: keypair.publicKey, raffle: raffleAccountId })
+				.signers([keypair])
+				.rpc();
+			const commitment = createHash("sha256")
+				.update(
+					Buffer.concat([
+						raffleAccountId.toBuffer(),
+						keypair.publicKey.toBuffer(),
+						secret,
+					]),
+				)
+				.digest();
+			await raffleProgram.methods
+				.commitWinnerSecret(Array.from(commitment))
+				.accounts({ signer: keypair.publicKey, raffle: raffleAccountId })
+				.signers([keypair])
+				.rpc();
+			const ticketBalanceId = PublicKey.findProgramAddressSync(
+				[
+					Buffer.from("ticket_balance"),
+					raffleAccountId.toBytes(),
+					keypair.publicKey.toBytes(),
+				],
+				raffleProgram.programId,
+			)[0];
+			buyers.push({ keypair, entrySeed, secret, ticketBalanceId });
+		}
+
+		const newClock = client.getClock();
+		newClock.unixTimestamp = creationTime + BigInt(3601);
+		client.setClock(newClock);
+		await raffleProgram.methods
+			.drawWinningTicket()
+			.accounts({
+				raffle: raffleAccountId,
+				recentSlothashes: new PublicKey(
+					"SysvarS1otHashes111111111111111111111111111",
+				),
+			})
+			.rpc();
+
+		let raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		const winners = raffle.winningTickets.map(
+			(ticket) => buyers[ticket.toNumber()],
+		);
+		for (const [winnerIndex, winner] of winners.entries()) {
+			await raffleProgram.methods
+				.setWinner(Array.from(winner.entrySeed), winnerIndex)
+				.accounts({
+					raffle: raffleAccountId,
+					winnerTicketBalance: winner.ticketBalanceId,
+				})
+				.rpc();
+		}
+
+		// No winner index names its winner
+		raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.raffleState.drawn).toBeDefined();
+		expect(raffle.winnerAddress).toBeNull();
+		expect(raffle.winnerHash).not.toBeNull();
+		expect(raffle.additionalWinners[0].equals(PublicKey.default)).toBeTrue();
+		expect(raffle.additionalWinnerHashes[0]).not.toBeNull();
+
+		// Each winner claims by revealing their secret
+		for (const [winnerIndex, winner] of winners.entries()) {
+			await raffleProgram.methods
+				.submitWinnerData("data", Array.from(winner.secret), 1, 1, winnerIndex)
+				.accounts({ raffle: raffleAccountId, signer: winner.keypair.publicKey })
+				.signers([winner.keypair])
+				.rpc();
+		}
+		raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.raffleState.claimed).toBeDefined();
+		expect(
+			raffle.additionalWinners[0].equals(winners[1].keypair.publicKey),
+		).toBeTrue();
+	});
+
+	it("should fail when not signed by the management authority", async () => {
+		const { provider, raffleProgram, raffleAccountId } = await setup(false);
+
+		const account = new Keypair();
+		provider.client.airdrop(account.publicKey, BigInt(LAMPORTS_PER_SOL));
+		expect(
+			raffleProgram.methods
+				.setWinnerCount(2)
+				.accounts({
+					raffle: raffleAccountId,
+					managementAuthority: account.publicKey,
+				})
+				.signers([account])
+				.rpc(),
+		).rejects.toThrow(/NotProgramManagementAuthority/);
+	});
+});
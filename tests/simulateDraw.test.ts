@@ -0,0 +1,97 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("simulate_draw", async () => {
+	it("should return the ticket the draw picks without changing the raffle", async () => {
+		const client = fromWorkspace(".");
+		client.withSysvars();
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		const metadataUri = "https://www.example.org";
+		const ticketPrice = new BN(0.1 * LAMPORTS_PER_SOL);
+		const minTickets = new BN(1);
+		const endTime = new BN((creationTime + BigInt(3601)).toString());
+
+		// Create raffle
+		await raffleProgram.methods
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+		await raffleProgram.methods
+			.buyTickets(new BN(10), Array.from(new Uint8Array(8)), null, null, null, null, false, null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+
+		// Set time so that the raffle has ended
+		const newClock = client.getClock();
+		newClock.unixTimestamp = creationTime + BigInt(3601);
+		client.setClock(newClock);
+
+		const recentSlothashes = new PublicKey(
+			"SysvarS1otHashes111111111111111111111111111",
+		);
+		const simulatedTicket: BN = await raffleProgram.methods
+			.simulateDraw()
+			.accounts({ raffle: raffleAccountId, recentSlothashes })
+			.view();
+
+		let raffleAccount =
+			await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffleAccount.raffleState.open).toBeDefined();
+		expect(raffleAccount.winningTicket).toBeNull();
+
+		// The real draw in the same slot picks the simulated ticket
+		await raffleProgram.methods
+			.drawWinningTicket()
+			.accounts({ raffle: raffleAccountId, recentSlothashes })
+			.rpc();
+
+		raffleAccount = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffleAccount.winningTicket?.eq(simulatedTicket)).toBeTrue();
+	});
+});
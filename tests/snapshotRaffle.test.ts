@@ -0,0 +1,190 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("snapshot_raffle", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(1),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+		const snapshotId = PublicKey.findProgramAddressSync(
+			[Buffer.from("snapshot"), raffleAccountId.toBytes()],
+			raffleProgram.programId,
+		)[0];
+
+		// Buys tickets and returns the buyer's ticket balance
+		const buy = async (seed: number, ticketCount: number) => {
+			const buyer = new Keypair();
+			provider.client.airdrop(buyer.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+			await raffleProgram.methods
+				.initTicketBalance(null)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+			await raffleProgram.methods
+				.buyTickets(
+					new BN(ticketCount),
+					Array.from(new Uint8Array([seed, 0, 0, 0, 0, 0, 0, 0])),
+					null,
+					null,
+					null,
+					null,
+					false,
+					null,
+				)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+			return {
+				owner: buyer.publicKey,
+				ticketBalanceId: PublicKey.findProgramAddressSync(
+					[
+						Buffer.from("ticket_balance"),
+						raffleAccountId.toBytes(),
+						buyer.publicKey.toBytes(),
+					],
+					raffleProgram.programId,
+				)[0],
+			};
+		};
+
+		const endRaffle = () => {
+			const newClock = client.getClock();
+			newClock.unixTimestamp = creationTime + BigInt(3601);
+			client.setClock(newClock);
+		};
+
+		const snapshot = (ticketBalanceIds: PublicKey[]) =>
+			raffleProgram.methods
+				.snapshotRaffle()
+				.accounts({ raffle: raffleAccountId })
+				.remainingAccounts(
+					ticketBalanceIds.map((pubkey) => ({
+						pubkey,
+						isSigner: false,
+						isWritable: false,
+					})),
+				)
+				.rpc();
+
+		return {
+			raffleProgram,
+			raffleAccountId,
+			snapshotId,
+			buy,
+			endRaffle,
+			snapshot,
+		};
+	};
+
+	// Ticket balances of the holders in ascending owner order
+	const sorted = (holders: { owner: PublicKey; ticketBalanceId: PublicKey }[]) =>
+		[...holders]
+			.sort((a, b) => Buffer.compare(a.owner.toBuffer(), b.owner.toBuffer()))
+			.map(({ ticketBalanceId }) => ticketBalanceId);
+
+	it("should record the root once every holder was added", async () => {
+		const {
+			raffleProgram,
+			raffleAccountId,
+			snapshotId,
+			buy,
+			endRaffle,
+			snapshot,
+		} = await setup();
+
+		const ticketBalanceIds = sorted([
+			await buy(0, 3),
+			await buy(1, 5),
+			await buy(2, 2),
+		]);
+		endRaffle();
+
+		// Holders can be added across as many batches as needed
+		await snapshot(ticketBalanceIds.slice(0, 2));
+		let raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.snapshotRoot).toBeNull();
+
+		await snapshot(ticketBalanceIds.slice(2));
+		raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.snapshotRoot).not.toBeNull();
+
+		const progress = await raffleProgram.account.snapshot.fetch(snapshotId);
+		expect(progress.holderCount.toNumber()).toBe(3);
+		expect(progress.ticketCount.toNumber()).toBe(10);
+
+		// The snapshot can only be recorded once
+		expect(snapshot(ticketBalanceIds)).rejects.toThrow(
+			/SnapshotAlreadyRecorded/,
+		);
+	});
+
+	it("should fail to add holders out of order", async () => {
+		const { buy, endRaffle, snapshot } = await setup();
+
+		const ticketBalanceIds = sorted([await buy(0, 1), await buy(1, 1)]);
+		endRaffle();
+
+		expect(snapshot(ticketBalanceIds.reverse())).rejects.toThrow(
+			/TicketBalanceOutOfOrder/,
+		);
+	});
+
+	it("should fail to add a holder twice", async () => {
+		const { buy, endRaffle, snapshot } = await setup();
+
+		const ticketBalanceIds = sorted([await buy(0, 1), await buy(1, 1)]);
+		endRaffle();
+
+		expect(
+			snapshot([ticketBalanceIds[0] as PublicKey, ticketBalanceIds[0] as PublicKey]),
+		).rejects.toThrow(/TicketBalanceOutOfOrder/);
+	});
+
+	it("should fail to snapshot before the sale closed", async () => {
+		const { buy, snapshot } = await setup();
+
+		const { ticketBalanceId } = await buy(0, 1);
+
+		expect(snapshot([ticketBalanceId])).rejects.toThrow(/RaffleNotEnded/);
+	});
+});
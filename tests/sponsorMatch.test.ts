@@ -0,0 +1,247 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("sponsor_match", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		client.withSysvars();
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		const metadataUri = "https://www.example.org";
+		const ticketPrice = new BN(0.1 * LAMPORTS_PER_SOL);
+		const minTickets = new BN(10);
+		const endTime = new BN((creationTime + BigInt(3601)).toString());
+
+		// Create raffle
+		await raffleProgram.methods
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+		const treasuryId = PublicKey.findProgramAddressSync(
+			[Buffer.from("treasury"), raffleAccountId.toBytes()],
+			raffleProgram.programId,
+		)[0];
+
+		const sponsor = new Keypair();
+		provider.client.airdrop(sponsor.publicKey, BigInt(10 * LAMPORTS_PER_SOL));
+		const sponsorMatchId = PublicKey.findProgramAddressSync(
+			[
+				Buffer.from("sponsor_match"),
+				raffleAccountId.toBytes(),
+				sponsor.publicKey.toBytes(),
+			],
+			raffleProgram.programId,
+		)[0];
+
+		const buyTickets = async (ticketCount: BN) => {
+			await raffleProgram.methods
+				.initTicketBalance(null)
+				.accounts({ raffle: raffleAccountId })
+				.rpc();
+			const randomBytes = new Uint8Array(8);
+			crypto.getRandomValues(randomBytes);
+			await raffleProgram.methods
+				.buyTickets(ticketCount, Array.from(randomBytes), null, null, null, null, false, null)
+				.accounts({ raffle: raffleAccountId })
+				.rpc();
+		};
+
+		const endRaffle = () => {
+			const newClock = client.getClock();
+			newClock.unixTimestamp = creationTime + BigInt(3602);
+			client.setClock(newClock);
+		};
+
+		return {
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			treasuryId,
+			sponsor,
+			sponsorMatchId,
+			ticketPrice,
+			minTickets,
+			buyTickets,
+			endRaffle,
+		};
+	};
+
+	it("should add the matched share of sales to the treasury at draw time and return the remainder", async () => {
+		const {
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			treasuryId,
+			sponsor,
+			sponsorMatchId,
+			ticketPrice,
+			minTickets,
+			buyTickets,
+			endRaffle,
+		} = await setup();
+
+		// Match 10% of sales, up to 1 SOL
+		const cap = new BN(1 * LAMPORTS_PER_SOL);
+		await raffleProgram.methods
+			.sponsorMatch(1_000, cap)
+			.accounts({ raffle: raffleAccountId, sponsor: sponsor.publicKey })
+			.signers([sponsor])
+			.rpc();
+
+		const sponsorMatch =
+			await raffleProgram.account.sponsorMatch.fetch(sponsorMatchId);
+		expect(sponsorMatch.matchBps).toEqual(1_000);
+		expect(sponsorMatch.cap.eq(cap)).toBeTrue();
+
+		// Settling is not possible while the raffle is open
+		expect(
+			raffleProgram.methods
+				.settleSponsorMatch()
+				.accounts({ raffle: raffleAccountId, sponsor: sponsor.publicKey })
+				.rpc(),
+		).rejects.toThrow(/RaffleNotConcluded/);
+
+		await buyTickets(minTickets);
+		endRaffle();
+		await raffleProgram.methods
+			.drawWinningTicket()
+			.accounts({
+				raffle: raffleAccountId,
+				recentSlothashes: new PublicKey(
+					"SysvarS1otHashes111111111111111111111111111",
+				),
+			})
+			.rpc();
+
+		const treasuryBalanceBefore = provider.client.getBalance(treasuryId);
+		const sponsorBalanceBefore = provider.client.getBalance(sponsor.publicKey);
+		const escrowBalance = provider.client.getBalance(sponsorMatchId);
+		if (!treasuryBalanceBefore || !sponsorBalanceBefore || !escrowBalance) {
+			throw new Error("Failed to fetch balances");
+		}
+
+		await raffleProgram.methods
+			.settleSponsorMatch()
+			.accounts({ raffle: raffleAccountId, sponsor: sponsor.publicKey })
+			.rpc();
+
+		// 10% of 1 SOL in sales is matched
+		const matched = BigInt(
+			ticketPrice.mul(minTickets).div(new BN(10)).toString(),
+		);
+		expect(provider.client.getBalance(treasuryId)).toEqual(
+			treasuryBalanceBefore + matched,
+		);
+		expect(provider.client.getBalance(sponsor.publicKey)).toEqual(
+			sponsorBalanceBefore + escrowBalance - matched,
+		);
+
+		const treasuryAccount =
+			await raffleProgram.account.treasury.fetch(treasuryId);
+		expect(treasuryAccount.totalSponsored.toString()).toEqual(
+			matched.toString(),
+		);
+		expect(
+			raffleProgram.account.sponsorMatch.fetch(sponsorMatchId),
+		).rejects.toThrow(/Account does not exist/);
+	});
+
+	it("should return the full escrow when the raffle expires", async () => {
+		const {
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			treasuryId,
+			sponsor,
+			sponsorMatchId,
+			endRaffle,
+		} = await setup();
+
+		await raffleProgram.methods
+			.sponsorMatch(5_000, new BN(2 * LAMPORTS_PER_SOL))
+			.accounts({ raffle: raffleAccountId, sponsor: sponsor.publicKey })
+			.signers([sponsor])
+			.rpc();
+
+		endRaffle();
+		await raffleProgram.methods
+			.expireRaffle()
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		const treasuryBalanceBefore = provider.client.getBalance(treasuryId);
+		const sponsorBalanceBefore = provider.client.getBalance(sponsor.publicKey);
+		const escrowBalance = provider.client.getBalance(sponsorMatchId);
+		if (!treasuryBalanceBefore || !sponsorBalanceBefore || !escrowBalance) {
+			throw new Error("Failed to fetch balances");
+		}
+
+		await raffleProgram.methods
+			.settleSponsorMatch()
+			.accounts({ raffle: raffleAccountId, sponsor: sponsor.publicKey })
+			.rpc();
+
+		expect(provider.client.getBalance(treasuryId)).toEqual(
+			treasuryBalanceBefore,
+		);
+		expect(provider.client.getBalance(sponsor.publicKey)).toEqual(
+			sponsorBalanceBefore + escrowBalance,
+		);
+	});
+
+	it("should fail with invalid match parameters", async () => {
+		const { raffleProgram, raffleAccountId, sponsor } = await setup();
+
+		const inputs: { bps: number; cap: BN; error: RegExp }[] = [
+			{ bps: 0, cap: new BN(LAMPORTS_PER_SOL), error: /InvalidMatchBps/ },
+			{ bps: 10_001, cap: new BN(LAMPORTS_PER_SOL), error: /InvalidMatchBps/ },
+			{ bps: 1_000, cap: new BN(0), error: /InvalidMatchCap/ },
+		];
+
+		for (const input of inputs) {
+			expect(
+				raffleProgram.methods
+					.sponsorMatch(input.bps, input.cap)
+					.accounts({ raffle: raffleAccountId, sponsor: sponsor.publicKey })
+					.signers([sponsor])
+					.rpc(),
+			).rejects.toThrow(input.error);
+		}
+	});
+});
@@ -1,4 +1,5 @@
 import { describe, expect, it } from "bun:test";
+import { createHash } from "crypto";
 import { BN, Program } from "@coral-xyz/anchor";
 import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
 import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
@@ -42,7 +43,24 @@ describe("submit_winner_data", async () => {
 
 			// Create raffle
 			await raffleProgram.methods
-				.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+				.createRaffle(
+					metadataUri,
+					ticketPrice,
+					endTime,
+					minTickets,
+					null,
+					null,
+					false,
+					null,
+					false,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+				)
 				.rpc();
 			const raffleAccountId = PublicKey.findProgramAddressSync(
 				[
@@ -71,6 +89,57 @@ describe("submit_winner_data", async () => {
 				winnerAddress: winnerId.publicKey,
 				winningTicket: null,
 				maxTickets: null,
+				participantCount: new BN(0),
+				eventSeq: new BN(0),
+				minRevenue: null,
+				minDrawSlot: new BN(0),
+				reservedTickets: new BN(0),
+				refundFeeBps: 0,
+				rentPayer: provider.publicKey,
+				features: 0,
+				allowedRegions: new BN(0),
+				ageRestricted: false,
+				drawAuthority: null,
+				priorityMinTier: 0,
+				priorityUntil: new BN(0),
+				minUniqueBuyers: null,
+				paymentMint: null,
+				randomnessMode: { slotHashes: {} },
+				randomnessAccount: null,
+				randomnessCommitSlot: new BN(0),
+				prizeEscrowed: false,
+				winnerCount: 1,
+				winningTickets: [],
+				additionalWinners: [],
+				winnerDataSubmitted: 0,
+				prizesClaimed: 0,
+				maxTicketsPerWallet: null,
+				gate: null,
+				deferredNumbering: false,
+				entryCount: new BN(0),
+				numberedEntries: new BN(0),
+				numberedTickets: new BN(0),
+				feeBpsOverride: null,
+				snapshotRoot: null,
+				operatorStatus: 0,
+				statusNoteHash: null,
+				fundingDeadline: null,
+				pricingTiers: [],
+				tierDiscounts: new BN(0),
+				crankRewardBps: 0,
+				referralFeeBps: 0,
+				extensionCount: 0,
+				extendedSecs: new BN(0),
+				refundDeadline: new BN(0),
+				prizeValueUsdCents: null,
+				ticketCountDisplayCap: null,
+				ticketReceipts: false,
+				arweaveTxId: null,
+				creator: null,
+				drawDelaySlots: 0,
+				drawTargetSlot: new BN(0),
+				refundMint: null,
+				refundRate: new BN(0), drawValueLamports: new BN(0), drawUsdRate: null, successor: null, randomnessProvider: null, randomnessCommitment: null, accessCodeHash: null, closeReason: 0, crankRewardPaid: false, additionalWinnerHashes: [], voidedRanges: [], pendingReservations: new BN(0), openSlotReleased: false, campaignSubsidies: new BN(0),
 			});
 			provider.client.setAccount(raffleAccountId, {
 				executable: false,
@@ -82,7 +151,7 @@ describe("submit_winner_data", async () => {
 			// The data on the contract should be set RAW, just like the client sends it
 			const winnerData = input;
 			await raffleProgram.methods
-				.submitWinnerData(winnerData)
+				.submitWinnerData(winnerData, null, 1, 1, 0)
 				.accounts({ raffle: raffleAccountId, signer: winnerId.publicKey })
 				.signers([winnerId])
 				.rpc();
@@ -147,7 +216,24 @@ describe("submit_winner_data", async () => {
 
 			// Create raffle
 			await raffleProgram.methods
-				.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+				.createRaffle(
+					metadataUri,
+					ticketPrice,
+					endTime,
+					minTickets,
+					null,
+					null,
+					false,
+					null,
+					false,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+				)
 				.rpc();
 			const raffleAccountId = PublicKey.findProgramAddressSync(
 				[
@@ -176,6 +262,57 @@ describe("submit_winner_data", async () => {
 				winnerAddress: winnerId.publicKey,
 				winningTicket: null,
 				maxTickets: null,
+				participantCount: new BN(0),
+				eventSeq: new BN(0),
+				minRevenue: null,
+				minDrawSlot: new BN(0),
+				reservedTickets: new BN(0),
+				refundFeeBps: 0,
+				rentPayer: provider.publicKey,
+				features: 0,
+				allowedRegions: new BN(0),
+				ageRestricted: false,
+				drawAuthority: null,
+				priorityMinTier: 0,
+				priorityUntil: new BN(0),
+				minUniqueBuyers: null,
+				paymentMint: null,
+				randomnessMode: { slotHashes: {} },
+				randomnessAccount: null,
+				randomnessCommitSlot: new BN(0),
+				prizeEscrowed: false,
+				winnerCount: 1,
+				winningTickets: [],
+				additionalWinners: [],
+				winnerDataSubmitted: 0,
+				prizesClaimed: 0,
+				maxTicketsPerWallet: null,
+				gate: null,
+				deferredNumbering: false,
+				entryCount: new BN(0),
+				numberedEntries: new BN(0),
+				numberedTickets: new BN(0),
+				feeBpsOverride: null,
+				snapshotRoot: null,
+				operatorStatus: 0,
+				statusNoteHash: null,
+				fundingDeadline: null,
+				pricingTiers: [],
+				tierDiscounts: new BN(0),
+				crankRewardBps: 0,
+				referralFeeBps: 0,
+				extensionCount: 0,
+				extendedSecs: new BN(0),
+				refundDeadline: new BN(0),
+				prizeValueUsdCents: null,
+				ticketCountDisplayCap: null,
+				ticketReceipts: false,
+				arweaveTxId: null,
+				creator: null,
+				drawDelaySlots: 0,
+				drawTargetSlot: new BN(0),
+				refundMint: null,
+				refundRate: new BN(0), drawValueLamports: new BN(0), drawUsdRate: null, successor: null, randomnessProvider: null, randomnessCommitment: null, accessCodeHash: null, closeReason: 0, crankRewardPaid: false, additionalWinnerHashes: [], voidedRanges: [], pendingReservations: new BN(0), openSlotReleased: false, campaignSubsidies: new BN(0),
 			});
 			provider.client.setAccount(raffleAccountId, {
 				executable: false,
@@ -188,7 +325,7 @@ describe("submit_winner_data", async () => {
 			const winnerData = "data";
 			expect(
 				raffleProgram.methods
-					.submitWinnerData(winnerData)
+					.submitWinnerData(winnerData, null, 1, 1, 0)
 					.accounts({ raffle: raffleAccountId, signer: winnerId.publicKey })
 					.signers([winnerId])
 					.rpc(),
@@ -229,7 +366,24 @@ describe("submit_winner_data", async () => {
 
 		// Create raffle
 		await raffleProgram.methods
-			.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
 			.rpc();
 		const raffleAccountId = PublicKey.findProgramAddressSync(
 			[
@@ -258,6 +412,57 @@ describe("submit_winner_data", async () => {
 			winnerAddress: winnerId.publicKey,
 			winningTicket: null,
 			maxTickets: null,
+			participantCount: new BN(0),
+			eventSeq: new BN(0),
+			minRevenue: null,
+			minDrawSlot: new BN(0),
+			reservedTickets: new BN(0),
+			refundFeeBps: 0,
+			rentPayer: provider.publicKey,
+			features: 0,
+			allowedRegions: new BN(0),
+			ageRestricted: false,
+			drawAuthority: null,
+			priorityMinTier: 0,
+			priorityUntil: new BN(0),
+			minUniqueBuyers: null,
+			paymentMint: null,
+			randomnessMode: { slotHashes: {} },
+			randomnessAccount: null,
+			randomnessCommitSlot: new BN(0),
+			prizeEscrowed: false,
+			winnerCount: 1,
+			winningTickets: [],
+			additionalWinners: [],
+			winnerDataSubmitted: 0,
+			prizesClaimed: 0,
+			maxTicketsPerWallet: null,
+			gate: null,
+			deferredNumbering: false,
+			entryCount: new BN(0),
+			numberedEntries: new BN(0),
+			numberedTickets: new BN(0),
+			feeBpsOverride: null,
+			snapshotRoot: null,
+			operatorStatus: 0,
+			statusNoteHash: null,
+			fundingDeadline: null,
+			pricingTiers: [],
+			tierDiscounts: new BN(0),
+			crankRewardBps: 0,
+			referralFeeBps: 0,
+			extensionCount: 0,
+			extendedSecs: new BN(0),
+			refundDeadline: new BN(0),
+			prizeValueUsdCents: null,
+			ticketCountDisplayCap: null,
+			ticketReceipts: false,
+			arweaveTxId: null,
+			creator: null,
+			drawDelaySlots: 0,
+			drawTargetSlot: new BN(0),
+			refundMint: null,
+			refundRate: new BN(0), drawValueLamports: new BN(0), drawUsdRate: null, successor: null, randomnessProvider: null, randomnessCommitment: null, accessCodeHash: null, closeReason: 0, crankRewardPaid: false, additionalWinnerHashes: [], voidedRanges: [], pendingReservations: new BN(0), openSlotReleased: false, campaignSubsidies: new BN(0),
 		});
 		provider.client.setAccount(raffleAccountId, {
 			executable: false,
@@ -276,7 +481,7 @@ describe("submit_winner_data", async () => {
 		const winnerData = "data";
 		expect(
 			raffleProgram.methods
-				.submitWinnerData(winnerData)
+				.submitWinnerData(winnerData, null, 1, 1, 0)
 				.accounts({ raffle: raffleAccountId, signer: notTheWinner.publicKey })
 				.signers([notTheWinner])
 				.rpc(),
@@ -324,7 +529,24 @@ describe("submit_winner_data", async () => {
 
 			// Create raffle
 			await raffleProgram.methods
-				.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+				.createRaffle(
+					metadataUri,
+					ticketPrice,
+					endTime,
+					minTickets,
+					null,
+					null,
+					false,
+					null,
+					false,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+				)
 				.rpc();
 			const raffleAccountId = PublicKey.findProgramAddressSync(
 				[
@@ -353,6 +575,57 @@ describe("submit_winner_data", async () => {
 				winnerAddress: winnerId.publicKey,
 				winningTicket: null,
 				maxTickets: null,
+				participantCount: new BN(0),
+				eventSeq: new BN(0),
+				minRevenue: null,
+				minDrawSlot: new BN(0),
+				reservedTickets: new BN(0),
+				refundFeeBps: 0,
+				rentPayer: provider.publicKey,
+				features: 0,
+				allowedRegions: new BN(0),
+				ageRestricted: false,
+				drawAuthority: null,
+				priorityMinTier: 0,
+				priorityUntil: new BN(0),
+				minUniqueBuyers: null,
+				paymentMint: null,
+				randomnessMode: { slotHashes: {} },
+				randomnessAccount: null,
+				randomnessCommitSlot: new BN(0),
+				prizeEscrowed: false,
+				winnerCount: 1,
+				winningTickets: [],
+				additionalWinners: [],
+				winnerDataSubmitted: 0,
+				prizesClaimed: 0,
+				maxTicketsPerWallet: null,
+				gate: null,
+				deferredNumbering: false,
+				entryCount: new BN(0),
+				numberedEntries: new BN(0),
+				numberedTickets: new BN(0),
+				feeBpsOverride: null,
+				snapshotRoot: null,
+				operatorStatus: 0,
+				statusNoteHash: null,
+				fundingDeadline: null,
+				pricingTiers: [],
+				tierDiscounts: new BN(0),
+				crankRewardBps: 0,
+				referralFeeBps: 0,
+				extensionCount: 0,
+				extendedSecs: new BN(0),
+				refundDeadline: new BN(0),
+				prizeValueUsdCents: null,
+				ticketCountDisplayCap: null,
+				ticketReceipts: false,
+				arweaveTxId: null,
+				creator: null,
+				drawDelaySlots: 0,
+				drawTargetSlot: new BN(0),
+				refundMint: null,
+				refundRate: new BN(0), drawValueLamports: new BN(0), drawUsdRate: null, successor: null, randomnessProvider: null, randomnessCommitment: null, accessCodeHash: null, closeReason: 0, crankRewardPaid: false, additionalWinnerHashes: [], voidedRanges: [], pendingReservations: new BN(0), openSlotReleased: false, campaignSubsidies: new BN(0),
 			});
 			provider.client.setAccount(raffleAccountId, {
 				executable: false,
@@ -365,11 +638,346 @@ describe("submit_winner_data", async () => {
 			const winnerData = input.data;
 			expect(
 				raffleProgram.methods
-					.submitWinnerData(winnerData)
+					.submitWinnerData(winnerData, null, 1, 1, 0)
 					.accounts({ raffle: raffleAccountId, signer: winnerId.publicKey })
 					.signers([winnerId])
 					.rpc(),
 			).rejects.toThrow(input.errorRegex);
 		}
 	});
+	it("should let the winner of an anonymized raffle claim by revealing their secret", async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+		const winnerId = new Keypair();
+
+		provider.client.airdrop(winnerId.publicKey, BigInt(1 * LAMPORTS_PER_SOL));
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		const metadataUri = "https://www.example.org";
+		const ticketPrice = new BN(0.1 * LAMPORTS_PER_SOL);
+		const minTickets = new BN(5);
+		const endTime = new BN((creationTime + BigInt(3601)).toString());
+
+		// Create an anonymized raffle
+		await raffleProgram.methods
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				true,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+		const treasuryId = PublicKey.findProgramAddressSync(
+			[Buffer.from("treasury"), raffleAccountId.toBytes()],
+			raffleProgram.programId,
+		)[0];
+
+		// Manually create the winning entry PDA
+		const randomBytes = new Uint8Array(8);
+		crypto.getRandomValues(randomBytes);
+		const entrySeed = randomBytes;
+		const entryAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("entry"), raffleAccountId.toBytes(), entrySeed],
+			raffleProgram.programId,
+		)[0];
+		const entryData = await raffleProgram.coder.accounts.encode("entry", {
+			raffle: raffleAccountId,
+			owner: winnerId.publicKey,
+			ticketCount: new BN(5),
+			ticketStartIndex: new BN(0),
+			seed: Array.from(entrySeed),
+			entryIndex: new BN(0),
+			version: 2,
+			bump: 0,
+			createdAt: new BN(0),
+		});
+		provider.client.setAccount(entryAccountId, {
+			executable: false,
+			owner: raffleProgram.programId,
+			lamports: 1 * LAMPORTS_PER_SOL,
+			data: entryData,
+		});
+
+		// Manually set the raffle state to drawing, keeping the full account size
+		// so the winner address can be stored on claim
+		const raffleData = await raffleProgram.coder.accounts.encode("raffle", {
+			metadataUri,
+			ticketPrice,
+			minTickets,
+			currentTickets: new BN(5),
+			endTime: new BN(creationTime.toString()),
+			treasury: treasuryId,
+			creationTime: new BN(creationTime.toString()),
+			raffleState: {
+				drawing: {},
+			},
+			winnerAddress: null,
+			winningTicket: new BN(2),
+			maxTickets: null,
+			participantCount: new BN(1),
+			anonymizeWinner: true,
+			eventSeq: new BN(0),
+			minRevenue: null,
+			minDrawSlot: new BN(0),
+			reservedTickets: new BN(0),
+			refundFeeBps: 0,
+			rentPayer: provider.publicKey,
+			features: 0,
+			allowedRegions: new BN(0),
+			ageRestricted: false,
+			drawAuthority: null,
+			priorityMinTier: 0,
+			priorityUntil: new BN(0),
+			minUniqueBuyers: null,
+			paymentMint: null,
+			randomnessMode: { slotHashes: {} },
+			randomnessAccount: null,
+			randomnessCommitSlot: new BN(0),
+			prizeEscrowed: false,
+			winnerCount: 1,
+			winningTickets: [],
+			additionalWinners: [],
+			winnerDataSubmitted: 0,
+			prizesClaimed: 0,
+			maxTicketsPerWallet: null,
+			gate: null,
+			deferredNumbering: false,
+			entryCount: new BN(0),
+			numberedEntries: new BN(0),
+			numberedTickets: new BN(0),
+			feeBpsOverride: null,
+			snapshotRoot: null,
+			operatorStatus: 0,
+			statusNoteHash: null,
+			fundingDeadline: null,
+			pricingTiers: [],
+			tierDiscounts: new BN(0),
+			crankRewardBps: 0,
+			referralFeeBps: 0,
+			extensionCount: 0,
+			extendedSecs: new BN(0),
+			refundDeadline: new BN(0),
+			prizeValueUsdCents: null,
+			ticketCountDisplayCap: null,
+			ticketReceipts: false,
+			arweaveTxId: null,
+			creator: null,
+			drawDelaySlots: 0,
+			drawTargetSlot: new BN(0),
+			refundMint: null,
+			refundRate: new BN(0), drawValueLamports: new BN(0), drawUsdRate: null, successor: null, randomnessProvider: null, randomnessCommitment: null, accessCodeHash: null, closeReason: 0, crankRewardPaid: false, additionalWinnerHashes: [], voidedRanges: [], pendingReservations: new BN(0), openSlotReleased: false, campaignSubsidies: new BN(0),
+		});
+		provider.client.setAccount(raffleAccountId, {
+			executable: false,
+			owner: raffleProgram.programId,
+			lamports: 1 * LAMPORTS_PER_SOL,
+			data: Buffer.concat([
+				raffleData,
+				Buffer.alloc(raffleProgram.account.raffle.size - raffleData.length),
+			]),
+		});
+
+		// The winner committed to a secret before the draw
+		const secret = crypto.getRandomValues(new Uint8Array(32));
+		const commitment = createHash("sha256")
+			.update(
+				Buffer.concat([
+					raffleAccountId.toBuffer(),
+					winnerId.publicKey.toBuffer(),
+					secret,
+				]),
+			)
+			.digest();
+		const [ticketBalanceId, ticketBalanceBump] =
+			PublicKey.findProgramAddressSync(
+				[
+					Buffer.from("ticket_balance"),
+					raffleAccountId.toBytes(),
+					winnerId.publicKey.toBytes(),
+				],
+				raffleProgram.programId,
+			);
+		const ticketBalanceData = await raffleProgram.coder.accounts.encode(
+			"ticketBalance",
+			{
+				owner: winnerId.publicKey,
+				ticketCount: new BN(5),
+				bump: ticketBalanceBump,
+				headEntry: entryAccountId,
+				totalSpent: new BN(0),
+				tierDiscount: new BN(0),
+				rollover: false,
+				winnerCommitment: Array.from(commitment),
+				campaignSubsidy: new BN(0),
+				subsidyCampaign: null,
+			},
+		);
+		provider.client.setAccount(ticketBalanceId, {
+			executable: false,
+			owner: raffleProgram.programId,
+			lamports: 1 * LAMPORTS_PER_SOL,
+			data: ticketBalanceData,
+		});
+
+		// The winner's ticket balance is required, so the commitment can't be left out
+		expect(
+			raffleProgram.methods
+				.setWinner(Array.from(entrySeed), 0)
+				.accounts({ raffle: raffleAccountId })
+				.rpc(),
+		).rejects.toThrow(/WinnerTicketBalanceRequired/);
+
+		await raffleProgram.methods
+			.setWinner(Array.from(entrySeed), 0)
+			.accounts({
+				raffle: raffleAccountId,
+				winnerTicketBalance: ticketBalanceId,
+			})
+			.rpc();
+
+		// Only the commitment of the winner is public
+		let raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.winnerAddress).toBeNull();
+		expect(raffle.winnerHash).toEqual(Array.from(commitment));
+
+		// Claiming with the wrong secret fails
+		expect(
+			raffleProgram.methods
+				.submitWinnerData("data", Array.from(new Uint8Array(32)), 1, 1, 0)
+				.accounts({ raffle: raffleAccountId, signer: winnerId.publicKey })
+				.signers([winnerId])
+				.rpc(),
+		).rejects.toThrow(/NotWinner/);
+
+		await raffleProgram.methods
+			.submitWinnerData("data", Array.from(secret), 1, 1, 0)
+			.accounts({ raffle: raffleAccountId, signer: winnerId.publicKey })
+			.signers([winnerId])
+			.rpc();
+
+		raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.raffleState.claimed).toBeDefined();
+		expect(raffle.winnerAddress?.equals(winnerId.publicKey)).toBeTrue();
+	});
+
+	it("should only accept supported winner data formats", async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+		const winnerId = new Keypair();
+
+		provider.client.airdrop(winnerId.publicKey, BigInt(1 * LAMPORTS_PER_SOL));
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(5),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		// Manually set the raffle state to drawn
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		const raffleData = await raffleProgram.coder.accounts.encode("raffle", {
+			...raffle,
+			raffleState: { drawn: {} },
+			winnerAddress: winnerId.publicKey,
+		});
+		provider.client.setAccount(raffleAccountId, {
+			executable: false,
+			owner: raffleProgram.programId,
+			lamports: 1 * LAMPORTS_PER_SOL,
+			data: raffleData,
+		});
+
+		// HPKE is not supported until the management authority enables it
+		const hpke = 2;
+		expect(
+			raffleProgram.methods
+				.submitWinnerData("data", null, 1, hpke, 0)
+				.accounts({ raffle: raffleAccountId, signer: winnerId.publicKey })
+				.signers([winnerId])
+				.rpc(),
+		).rejects.toThrow(/UnsupportedWinnerDataFormat/);
+		expect(
+			raffleProgram.methods
+				.submitWinnerData("data", null, 2, 1, 0)
+				.accounts({ raffle: raffleAccountId, signer: winnerId.publicKey })
+				.signers([winnerId])
+				.rpc(),
+		).rejects.toThrow(/UnsupportedWinnerDataFormat/);
+
+		await raffleProgram.methods.setWinnerDataFormats([1], [1, hpke]).rpc();
+		await raffleProgram.methods
+			.submitWinnerData("data", null, 1, hpke, 0)
+			.accounts({ raffle: raffleAccountId, signer: winnerId.publicKey })
+			.signers([winnerId])
+			.rpc();
+
+		const winnerDataId = PublicKey.findProgramAddressSync(
+			[
+				Buffer.from("winner_data"),
+				raffleAccountId.toBytes(),
+				winnerId.publicKey.toBytes(),
+			],
+			raffleProgram.programId,
+		)[0];
+		const winnerData =
+			await raffleProgram.account.winnerData.fetch(winnerDataId);
+		expect(winnerData.formatVersion).toBe(1);
+		expect(winnerData.encryptionScheme).toBe(hpke);
+	});
 });
@@ -0,0 +1,178 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("sweep_treasury_dust", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+		const payoutAuthority = new Keypair();
+
+		// Mint some balance to the payoutAuthority to initialize it
+		provider.client.airdrop(
+			payoutAuthority.publicKey,
+			BigInt(0.1 * LAMPORTS_PER_SOL),
+		);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: payoutAuthority.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		const metadataUri = "https://www.example.org";
+		const ticketPrice = new BN(0.1 * LAMPORTS_PER_SOL);
+		const minTickets = new BN(5);
+		const endTime = new BN((creationTime + BigInt(3601)).toString());
+
+		// Create raffle
+		await raffleProgram.methods
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+		const treasuryId = PublicKey.findProgramAddressSync(
+			[Buffer.from("treasury"), raffleAccountId.toBytes()],
+			raffleProgram.programId,
+		)[0];
+
+		// Purchase tickets
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		const randomBytes = new Uint8Array(8);
+		crypto.getRandomValues(randomBytes);
+		await raffleProgram.methods
+			.buyTickets(minTickets, Array.from(randomBytes), null, null, null, null, false, null)
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		return {
+			client,
+			provider,
+			raffleProgram,
+			payoutAuthority,
+			raffleAccountId,
+			treasuryId,
+			collected: BigInt(ticketPrice.mul(minTickets).toString()),
+		};
+	};
+
+	it("should sweep lamports sent directly to the treasury to the payout authority", async () => {
+		const {
+			provider,
+			raffleProgram,
+			payoutAuthority,
+			raffleAccountId,
+			treasuryId,
+			collected,
+		} = await setup();
+
+		// Send lamports directly to the treasury, bypassing the program
+		const strayLamports = BigInt(0.25 * LAMPORTS_PER_SOL);
+		provider.client.airdrop(treasuryId, strayLamports);
+
+		const payoutAuthorityBalanceBefore = provider.client.getBalance(
+			payoutAuthority.publicKey,
+		);
+		if (!payoutAuthorityBalanceBefore) {
+			throw new Error("Failed to fetch payout authority balance");
+		}
+
+		await raffleProgram.methods
+			.sweepTreasuryDust()
+			.accountsPartial({
+				raffle: raffleAccountId,
+				payoutAuthority: payoutAuthority.publicKey,
+			})
+			.rpc();
+
+		// Validate that only the stray lamports were swept
+		expect(provider.client.getBalance(payoutAuthority.publicKey)).toEqual(
+			payoutAuthorityBalanceBefore + strayLamports,
+		);
+		const minRent = provider.client.minimumBalanceForRentExemption(
+			BigInt(raffleProgram.account.treasury.size),
+		);
+		expect(provider.client.getBalance(treasuryId)).toEqual(
+			minRent + collected,
+		);
+
+		// Validate that the tracked totals are untouched
+		const treasuryAccount =
+			await raffleProgram.account.treasury.fetch(treasuryId);
+		expect(treasuryAccount.totalCollected.toString()).toEqual(
+			collected.toString(),
+		);
+		expect(treasuryAccount.totalWithdrawn.isZero()).toBeTrue();
+	});
+
+	it("should fail when the treasury holds no stray lamports", async () => {
+		const { raffleProgram, payoutAuthority, raffleAccountId } = await setup();
+
+		expect(
+			raffleProgram.methods
+				.sweepTreasuryDust()
+				.accountsPartial({
+					raffle: raffleAccountId,
+					payoutAuthority: payoutAuthority.publicKey,
+				})
+				.rpc(),
+		).rejects.toThrow(/NoTreasuryDust/);
+	});
+
+	it("should fail when not signed by the management authority", async () => {
+		const {
+			provider,
+			raffleProgram,
+			payoutAuthority,
+			raffleAccountId,
+			treasuryId,
+		} = await setup();
+		provider.client.airdrop(treasuryId, BigInt(0.25 * LAMPORTS_PER_SOL));
+
+		const account = new Keypair();
+		provider.client.airdrop(account.publicKey, BigInt(1 * LAMPORTS_PER_SOL));
+
+		expect(
+			raffleProgram.methods
+				.sweepTreasuryDust()
+				.accountsPartial({
+					raffle: raffleAccountId,
+					payoutAuthority: payoutAuthority.publicKey,
+					managementAuthority: account.publicKey,
+				})
+				.signers([account])
+				.rpc(),
+		).rejects.toThrow(/NotProgramManagementAuthority/);
+	});
+});
@@ -0,0 +1,175 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+const REFUND_WINDOW = 30 * 24 * 60 * 60;
+
+describe("sweep_unclaimed_refunds", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+		const insuranceFund = new Keypair();
+
+		// Init config with a 30 day refund window
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+		await raffleProgram.methods
+			.setRefundWindow(new BN(REFUND_WINDOW), insuranceFund.publicKey)
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(5),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		// Two buyers hold a ticket each when the raffle expires
+		const buyers: Keypair[] = [];
+		for (const seed of [1, 2]) {
+			const buyer = new Keypair();
+			provider.client.airdrop(buyer.publicKey, BigInt(LAMPORTS_PER_SOL));
+			await raffleProgram.methods
+				.initTicketBalance(null)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+			await raffleProgram.methods
+				.buyTickets(
+					new BN(1),
+					Array.from(new Uint8Array([seed, 0, 0, 0, 0, 0, 0, 0])),
+					null,
+					null,
+					null,
+					null,
+					false,
+					null,
+				)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+			buyers.push(buyer);
+		}
+
+		const setTime = (offset: number) => {
+			const newClock = client.getClock();
+			newClock.unixTimestamp = creationTime + BigInt(offset);
+			client.setClock(newClock);
+		};
+		setTime(3602);
+		await raffleProgram.methods
+			.expireRaffle()
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		const reclaim = (buyer: Keypair) =>
+			raffleProgram.methods
+				.reclaimExpiredTickets()
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+
+		const sweep = (destination: PublicKey) =>
+			raffleProgram.methods
+				.sweepUnclaimedRefunds()
+				.accounts({ raffle: raffleAccountId, destination })
+				.rpc();
+
+		return {
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			creationTime,
+			insuranceFund,
+			buyers,
+			setTime,
+			reclaim,
+			sweep,
+		};
+	};
+
+	it("should sweep refunds left unclaimed past the deadline to the insurance fund", async () => {
+		const {
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			creationTime,
+			insuranceFund,
+			buyers,
+			setTime,
+			reclaim,
+			sweep,
+		} = await setup();
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.refundDeadline.toString()).toBe(
+			(creationTime + BigInt(3602 + REFUND_WINDOW)).toString(),
+		);
+
+		await reclaim(buyers[0]);
+		expect(sweep(insuranceFund.publicKey)).rejects.toThrow(/RefundWindowOpen/);
+
+		setTime(3603 + REFUND_WINDOW);
+		expect(reclaim(buyers[1])).rejects.toThrow(/RefundWindowClosed/);
+		await sweep(insuranceFund.publicKey);
+
+		expect(provider.client.getBalance(insuranceFund.publicKey)).toEqual(
+			BigInt(0.1 * LAMPORTS_PER_SOL),
+		);
+		const treasuryId = PublicKey.findProgramAddressSync(
+			[Buffer.from("treasury"), raffleAccountId.toBytes()],
+			raffleProgram.programId,
+		)[0];
+		const treasury = await raffleProgram.account.treasury.fetch(treasuryId);
+		expect(treasury.totalForfeited.toNumber()).toBe(0.1 * LAMPORTS_PER_SOL);
+
+		// Nothing is left to sweep
+		expect(sweep(insuranceFund.publicKey)).rejects.toThrow(/NoUnclaimedRefunds/);
+	});
+
+	it("should fail to sweep to another account than the insurance fund", async () => {
+		const { provider, setTime, sweep } = await setup();
+
+		setTime(3603 + REFUND_WINDOW);
+		expect(sweep(provider.publicKey)).rejects.toThrow(
+			/InvalidForfeitureDestination/,
+		);
+	});
+
+	it("should fail to set a refund window shorter than 30 days", async () => {
+		const { raffleProgram } = await setup();
+
+		expect(
+			raffleProgram.methods.setRefundWindow(new BN(86400), null).rpc(),
+		).rejects.toThrow(/InvalidRefundWindow/);
+	});
+});
@@ -0,0 +1,195 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+const TOKEN_2022_PROGRAM_ID = new PublicKey(
+	"TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb",
+);
+const ASSOCIATED_TOKEN_PROGRAM_ID = new PublicKey(
+	"ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL",
+);
+
+describe("ticket_receipts", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(1),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+		await raffleProgram.methods
+			.setTicketReceipts(true)
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+
+		const entrySeed = new Uint8Array([1, 0, 0, 0, 0, 0, 0, 0]);
+		const entry = PublicKey.findProgramAddressSync(
+			[Buffer.from("entry"), raffleAccountId.toBytes(), entrySeed],
+			raffleProgram.programId,
+		)[0];
+		const receiptMint = PublicKey.findProgramAddressSync(
+			[Buffer.from("ticket_receipt"), entry.toBytes()],
+			raffleProgram.programId,
+		)[0];
+		const receiptTokenAccount = PublicKey.findProgramAddressSync(
+			[
+				buyer.publicKey.toBuffer(),
+				TOKEN_2022_PROGRAM_ID.toBuffer(),
+				receiptMint.toBuffer(),
+			],
+			ASSOCIATED_TOKEN_PROGRAM_ID,
+		)[0];
+
+		const buyTickets = (withReceipt = true) =>
+			raffleProgram.methods
+				.buyTickets(new BN(3), Array.from(entrySeed), null, null, null, null, false, null)
+				.accounts({
+					signer: buyer.publicKey,
+					raffle: raffleAccountId,
+					receiptMint: withReceipt ? receiptMint : null,
+					receiptTokenAccount: withReceipt ? receiptTokenAccount : null,
+					token2022Program: withReceipt ? TOKEN_2022_PROGRAM_ID : null,
+					associatedTokenProgram: withReceipt
+						? ASSOCIATED_TOKEN_PROGRAM_ID
+						: null,
+				})
+				.signers([buyer])
+				.rpc();
+
+		return {
+			client,
+			raffleProgram,
+			raffleAccountId,
+			buyer,
+			entry,
+			receiptMint,
+			receiptTokenAccount,
+			buyTickets,
+		};
+	};
+
+	it("should mint a receipt of the entry to the buyer", async () => {
+		const {
+			client,
+			raffleProgram,
+			raffleAccountId,
+			buyer,
+			receiptMint,
+			receiptTokenAccount,
+			buyTickets,
+		} = await setup();
+
+		await buyTickets();
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.ticketReceipts).toBeTrue();
+
+		const mint = client.getAccount(receiptMint)!;
+		expect(new PublicKey(mint.owner).equals(TOKEN_2022_PROGRAM_ID)).toBeTrue();
+		// Mint supply is the single receipt, with the mint authority revoked
+		const mintData = Buffer.from(mint.data);
+		expect(mintData.readUInt32LE(0)).toBe(0);
+		expect(Number(mintData.readBigUInt64LE(36))).toBe(1);
+
+		const tokenAccount = Buffer.from(
+			client.getAccount(receiptTokenAccount)!.data,
+		);
+		expect(
+			new PublicKey(tokenAccount.subarray(0, 32)).equals(receiptMint),
+		).toBeTrue();
+		expect(
+			new PublicKey(tokenAccount.subarray(32, 64)).equals(buyer.publicKey),
+		).toBeTrue();
+		expect(Number(tokenAccount.readBigUInt64LE(64))).toBe(1);
+	});
+
+	it("should fail to buy tickets without the receipt accounts", async () => {
+		const { buyTickets } = await setup();
+
+		expect(buyTickets(false)).rejects.toThrow(/TicketReceiptRequired/);
+	});
+
+	it("should fail to transfer an entry with a receipt", async () => {
+		const { raffleProgram, raffleAccountId, buyer, entry, buyTickets } =
+			await setup();
+		await buyTickets();
+
+		const recipient = new Keypair().publicKey;
+		expect(
+			raffleProgram.methods
+				.transferEntry()
+				.accounts({
+					raffle: raffleAccountId,
+					entry,
+					previousEntry: null,
+					recipient,
+					recipientTicketBalance: PublicKey.findProgramAddressSync(
+						[
+							Buffer.from("ticket_balance"),
+							raffleAccountId.toBytes(),
+							recipient.toBytes(),
+						],
+						raffleProgram.programId,
+					)[0],
+					signer: buyer.publicKey,
+				})
+				.signers([buyer])
+				.rpc(),
+		).rejects.toThrow(/EntryNotTransferable/);
+	});
+
+	it("should fail to defer the numbering of a raffle issuing receipts", async () => {
+		const { raffleProgram, raffleAccountId } = await setup();
+
+		expect(
+			raffleProgram.methods
+				.setDeferredNumbering(true)
+				.accounts({ raffle: raffleAccountId })
+				.rpc(),
+		).rejects.toThrow(/TicketReceiptsNotSupported/);
+	});
+});
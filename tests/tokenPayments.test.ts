@@ -0,0 +1,272 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { LiteSVM } from "litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+const TOKEN_PROGRAM_ID = new PublicKey(
+	"TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+);
+const ASSOCIATED_TOKEN_PROGRAM_ID = new PublicKey(
+	"ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL",
+);
+
+// Price of a ticket in base units of the 6 decimal payment mint
+const TICKET_PRICE = 1_000_000;
+
+const associatedTokenAddress = (owner: PublicKey, mint: PublicKey) =>
+	PublicKey.findProgramAddressSync(
+		[owner.toBuffer(), TOKEN_PROGRAM_ID.toBuffer(), mint.toBuffer()],
+		ASSOCIATED_TOKEN_PROGRAM_ID,
+	)[0];
+
+// Writes an initialized SPL token mint without a freeze authority
+const setMint = (client: LiteSVM, mint: PublicKey, authority: PublicKey) => {
+	const data = Buffer.alloc(82);
+	data.writeUInt32LE(1, 0);
+	authority.toBuffer().copy(data, 4);
+	data.writeUInt8(6, 44);
+	data.writeUInt8(1, 45);
+	client.setAccount(mint, {
+		executable: false,
+		owner: TOKEN_PROGRAM_ID,
+		lamports: LAMPORTS_PER_SOL,
+		data,
+	});
+};
+
+// Writes an initialized SPL token account holding `amount` tokens
+const setTokenAccount = (
+	client: LiteSVM,
+	address: PublicKey,
+	mint: PublicKey,
+	owner: PublicKey,
+	amount: number,
+) => {
+	const data = Buffer.alloc(165);
+	mint.toBuffer().copy(data, 0);
+	owner.toBuffer().copy(data, 32);
+	data.writeBigUInt64LE(BigInt(amount), 64);
+	data.writeUInt8(1, 108);
+	client.setAccount(address, {
+		executable: false,
+		owner: TOKEN_PROGRAM_ID,
+		lamports: LAMPORTS_PER_SOL,
+		data,
+	});
+};
+
+const tokenBalance = (client: LiteSVM, address: PublicKey) =>
+	Number(Buffer.from(client.getAccount(address)!.data).readBigUInt64LE(64));
+
+describe("token_payments", async () => {
+	const setup = async (cashPrizeBps: number | null = null) => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const mint = new Keypair().publicKey;
+		setMint(client, mint, provider.publicKey);
+
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+		const treasury = PublicKey.findProgramAddressSync(
+			[Buffer.from("treasury"), raffleAccountId.toBuffer()],
+			raffleProgram.programId,
+		)[0];
+		const treasuryTokenAccount = associatedTokenAddress(treasury, mint);
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(TICKET_PRICE),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(5),
+				new BN(10),
+				null,
+				false,
+				null,
+				false,
+				cashPrizeBps,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.accountsPartial({
+				paymentMint: mint,
+				treasuryTokenAccount,
+				tokenProgram: TOKEN_PROGRAM_ID,
+				associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+			})
+			.rpc();
+
+		// Fund a buyer with tokens, but no SOL beyond fees and rent
+		const buyer = new Keypair();
+		provider.client.airdrop(buyer.publicKey, BigInt(0.1 * LAMPORTS_PER_SOL));
+		const buyerTokenAccount = new Keypair().publicKey;
+		setTokenAccount(
+			client,
+			buyerTokenAccount,
+			mint,
+			buyer.publicKey,
+			8 * TICKET_PRICE,
+		);
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+			.signers([buyer])
+			.rpc();
+
+		const buyTickets = (ticketCount: number) =>
+			raffleProgram.methods
+				.buyTickets(
+					new BN(ticketCount),
+					Array.from(crypto.getRandomValues(new Uint8Array(8))),
+					null,
+					null,
+					null,
+					null,
+					false,
+					null,
+				)
+				.accountsPartial({
+					signer: buyer.publicKey,
+					raffle: raffleAccountId,
+					payerTokenAccount: buyerTokenAccount,
+					treasuryTokenAccount,
+					tokenProgram: TOKEN_PROGRAM_ID,
+				})
+				.signers([buyer])
+				.rpc();
+
+		return {
+			client,
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			mint,
+			creationTime,
+			buyer,
+			buyerTokenAccount,
+			treasuryTokenAccount,
+			buyTickets,
+		};
+	};
+
+	it("should sell tickets for tokens", async () => {
+		const {
+			client,
+			raffleProgram,
+			raffleAccountId,
+			mint,
+			buyerTokenAccount,
+			treasuryTokenAccount,
+			buyTickets,
+		} = await setup();
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.paymentMint?.toBase58()).toBe(mint.toBase58());
+		expect(tokenBalance(client, treasuryTokenAccount)).toBe(0);
+
+		await buyTickets(3);
+
+		expect(tokenBalance(client, treasuryTokenAccount)).toBe(3 * TICKET_PRICE);
+		expect(tokenBalance(client, buyerTokenAccount)).toBe(5 * TICKET_PRICE);
+	});
+
+	it("should fail to buy more tickets than the token balance covers", async () => {
+		const { buyTickets } = await setup();
+
+		expect(buyTickets(9)).rejects.toThrow(/InsufficientFunds/);
+		await buyTickets(8);
+	});
+
+	it("should refund tokens of an expired raffle", async () => {
+		const {
+			client,
+			raffleProgram,
+			raffleAccountId,
+			creationTime,
+			buyer,
+			buyerTokenAccount,
+			treasuryTokenAccount,
+			buyTickets,
+		} = await setup();
+
+		await buyTickets(2);
+
+		const newClock = client.getClock();
+		newClock.unixTimestamp = creationTime + BigInt(3601);
+		client.setClock(newClock);
+		await raffleProgram.methods
+			.expireRaffle()
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		await raffleProgram.methods
+			.reclaimExpiredTickets()
+			.accountsPartial({
+				signer: buyer.publicKey,
+				raffle: raffleAccountId,
+				recipientTokenAccount: buyerTokenAccount,
+				treasuryTokenAccount,
+				tokenProgram: TOKEN_PROGRAM_ID,
+			})
+			.signers([buyer])
+			.rpc();
+
+		expect(tokenBalance(client, treasuryTokenAccount)).toBe(0);
+		expect(tokenBalance(client, buyerTokenAccount)).toBe(8 * TICKET_PRICE);
+	});
+
+	it("should withdraw tokens to the payout authority", async () => {
+		const {
+			client,
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			mint,
+			treasuryTokenAccount,
+			buyTickets,
+		} = await setup();
+
+		await buyTickets(5);
+
+		const payoutTokenAccount = new Keypair().publicKey;
+		setTokenAccount(client, payoutTokenAccount, mint, provider.publicKey, 0);
+		await raffleProgram.methods
+			.withdrawFromTreasury()
+			.accountsPartial({
+				raffle: raffleAccountId,
+				recipientTokenAccount: payoutTokenAccount,
+				treasuryTokenAccount,
+				tokenProgram: TOKEN_PROGRAM_ID,
+			})
+			.rpc();
+
+		expect(tokenBalance(client, treasuryTokenAccount)).toBe(0);
+		expect(tokenBalance(client, payoutTokenAccount)).toBe(5 * TICKET_PRICE);
+	});
+
+	it("should fail to create a token raffle with a cash prize", async () => {
+		expect(setup(5000)).rejects.toThrow(/TokenPaymentsNotSupported/);
+	});
+});
@@ -0,0 +1,233 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("transfer_entry", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(1),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		// The sender buys two entries, of two and three tickets
+		const sender = new Keypair();
+		provider.client.airdrop(sender.publicKey, BigInt(2 * LAMPORTS_PER_SOL));
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({ signer: sender.publicKey, raffle: raffleAccountId })
+			.signers([sender])
+			.rpc();
+		const entries: PublicKey[] = [];
+		for (const [seed, ticketCount] of [
+			[1, 2],
+			[2, 3],
+		]) {
+			const entrySeed = new Uint8Array([seed, 0, 0, 0, 0, 0, 0, 0]);
+			await raffleProgram.methods
+				.buyTickets(
+					new BN(ticketCount),
+					Array.from(entrySeed),
+					null,
+					null,
+					null,
+					null,
+					false,
+					null,
+				)
+				.accounts({ signer: sender.publicKey, raffle: raffleAccountId })
+				.signers([sender])
+				.rpc();
+			entries.push(
+				PublicKey.findProgramAddressSync(
+					[Buffer.from("entry"), raffleAccountId.toBytes(), entrySeed],
+					raffleProgram.programId,
+				)[0],
+			);
+		}
+
+		const ticketBalanceId = (owner: PublicKey) =>
+			PublicKey.findProgramAddressSync(
+				[Buffer.from("ticket_balance"), raffleAccountId.toBytes(), owner.toBytes()],
+				raffleProgram.programId,
+			)[0];
+
+		const transfer = (
+			entry: PublicKey,
+			recipient: PublicKey,
+			previousEntry: PublicKey | null = null,
+		) =>
+			raffleProgram.methods
+				.transferEntry()
+				.accounts({
+					raffle: raffleAccountId,
+					entry,
+					previousEntry,
+					recipient,
+					recipientTicketBalance: ticketBalanceId(recipient),
+					signer: sender.publicKey,
+				})
+				.signers([sender])
+				.rpc();
+
+		return {
+			client,
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			sender,
+			entries,
+			ticketBalanceId,
+			transfer,
+		};
+	};
+
+	it("should move the entry and its tickets to the recipient", async () => {
+		const { raffleProgram, sender, entries, ticketBalanceId, transfer } =
+			await setup();
+		const recipient = new Keypair().publicKey;
+
+		// The first entry is not the sender's most recent, so it has to be unlinked
+		await transfer(entries[0], recipient, entries[1]);
+
+		const entry = await raffleProgram.account.entry.fetch(entries[0]);
+		expect(entry.owner.equals(recipient)).toBeTrue();
+		expect(entry.nextEntry).toBeNull();
+
+		const senderBalance = await raffleProgram.account.ticketBalance.fetch(
+			ticketBalanceId(sender.publicKey),
+		);
+		expect(senderBalance.ticketCount.toNumber()).toBe(3);
+		expect(senderBalance.totalSpent.toNumber()).toBe(0.3 * LAMPORTS_PER_SOL);
+		expect(senderBalance.headEntry?.equals(entries[1])).toBeTrue();
+		const remaining = await raffleProgram.account.entry.fetch(entries[1]);
+		expect(remaining.nextEntry).toBeNull();
+
+		const recipientBalance = await raffleProgram.account.ticketBalance.fetch(
+			ticketBalanceId(recipient),
+		);
+		expect(recipientBalance.owner.equals(recipient)).toBeTrue();
+		expect(recipientBalance.ticketCount.toNumber()).toBe(2);
+		expect(recipientBalance.totalSpent.toNumber()).toBe(0.2 * LAMPORTS_PER_SOL);
+		expect(recipientBalance.headEntry?.equals(entries[0])).toBeTrue();
+	});
+
+	it("should fail without the entry linking to a transferred older entry", async () => {
+		const { entries, transfer } = await setup();
+		const recipient = new Keypair().publicKey;
+
+		expect(transfer(entries[0], recipient)).rejects.toThrow(
+			/InvalidPreviousEntry/,
+		);
+	});
+
+	it("should fail to transfer an entry to its owner", async () => {
+		const { sender, entries, transfer } = await setup();
+
+		expect(transfer(entries[1], sender.publicKey)).rejects.toThrow(
+			/InvalidTransferRecipient/,
+		);
+	});
+
+	it("should fail once the raffle is drawing", async () => {
+		const { provider, raffleProgram, raffleAccountId, entries, transfer } =
+			await setup();
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		const raffleData = await raffleProgram.coder.accounts.encode("raffle", {
+			...raffle,
+			raffleState: { drawing: {} },
+		});
+		provider.client.setAccount(raffleAccountId, {
+			executable: false,
+			owner: raffleProgram.programId,
+			lamports: 1 * LAMPORTS_PER_SOL,
+			data: raffleData,
+		});
+
+		expect(transfer(entries[1], new Keypair().publicKey)).rejects.toThrow(
+			/RaffleNotOpen/,
+		);
+	});
+
+	it("should fail once the raffle ended", async () => {
+		const { client, entries, transfer } = await setup();
+
+		const newClock = client.getClock();
+		newClock.unixTimestamp += BigInt(3601);
+		client.setClock(newClock);
+
+		expect(transfer(entries[1], new Keypair().publicKey)).rejects.toThrow(
+			/RaffleEnded/,
+		);
+	});
+
+	it("should fail once the holders were snapshotted", async () => {
+		const {
+			raffleProgram,
+			raffleAccountId,
+			sender,
+			entries,
+			ticketBalanceId,
+			transfer,
+		} = await setup();
+
+		// Selling out closes the sale while the raffle is still open
+		await raffleProgram.methods
+			.updateMaxTickets(new BN(5))
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+		await raffleProgram.methods
+			.snapshotRaffle()
+			.accounts({ raffle: raffleAccountId })
+			.remainingAccounts([
+				{
+					pubkey: ticketBalanceId(sender.publicKey),
+					isSigner: false,
+					isWritable: false,
+				},
+			])
+			.rpc();
+
+		expect(transfer(entries[1], new Keypair().publicKey)).rejects.toThrow(
+			/SnapshotAlreadyRecorded/,
+		);
+	});
+});
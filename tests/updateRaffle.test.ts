@@ -0,0 +1,133 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("update_raffle", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(1),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		const updateRaffle = (
+			metadataUri: string,
+			endTime: bigint,
+			minTickets: number,
+			maxTickets: number | null,
+		) =>
+			raffleProgram.methods
+				.updateRaffle(
+					metadataUri,
+					new BN(endTime.toString()),
+					new BN(minTickets),
+					maxTickets === null ? null : new BN(maxTickets),
+				)
+				.accounts({ raffle: raffleAccountId })
+				.rpc();
+
+		const buyTicket = async () => {
+			const buyer = new Keypair();
+			provider.client.airdrop(buyer.publicKey, BigInt(LAMPORTS_PER_SOL));
+			await raffleProgram.methods
+				.initTicketBalance(null)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+			await raffleProgram.methods
+				.buyTickets(new BN(1), Array.from(new Uint8Array(8)), null, null, null, null, false, null)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+		};
+
+		return {
+			raffleProgram,
+			raffleAccountId,
+			creationTime,
+			updateRaffle,
+			buyTicket,
+		};
+	};
+
+	it("should replace the parameters of a raffle without sales", async () => {
+		const { raffleProgram, raffleAccountId, creationTime, updateRaffle } =
+			await setup();
+
+		await updateRaffle(
+			"ipfs://bafybeigdyrzt",
+			creationTime + BigInt(7200),
+			5,
+			50,
+		);
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.metadataUri).toBe("ipfs://bafybeigdyrzt");
+		expect(raffle.endTime.toString()).toBe(
+			(creationTime + BigInt(7200)).toString(),
+		);
+		expect(raffle.minTickets.toNumber()).toBe(5);
+		expect(raffle.maxTickets?.toNumber()).toBe(50);
+	});
+
+	it("should apply the checks of create_raffle", async () => {
+		const { creationTime, updateRaffle } = await setup();
+
+		expect(
+			updateRaffle("ftp://example.org", creationTime + BigInt(7200), 1, null),
+		).rejects.toThrow(/InvalidMetadataUri/);
+		expect(
+			updateRaffle("https://www.example.org", creationTime + BigInt(60), 1, null),
+		).rejects.toThrow(/EndTimeTooClose/);
+		expect(
+			updateRaffle("https://www.example.org", creationTime + BigInt(7200), 10, 5),
+		).rejects.toThrow(/MaxTicketsTooLow/);
+	});
+
+	it("should fail to update a raffle once tickets were sold", async () => {
+		const { creationTime, updateRaffle, buyTicket } = await setup();
+
+		await buyTicket();
+
+		expect(
+			updateRaffle("https://www.example.org", creationTime + BigInt(7200), 1, null),
+		).rejects.toThrow(/TicketsAlreadySold/);
+	});
+});
@@ -0,0 +1,104 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+// Code of the program error with the given name
+const errorCode = (name: string): number =>
+	IDL.errors.find((error: { name: string }) => error.name === name).code;
+
+describe("validate_raffle_params", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		const validate = ({
+			metadataUri = "https://www.example.org",
+			ticketPrice = 0.1 * LAMPORTS_PER_SOL,
+			duration = 3601,
+			minTickets = 1,
+			maxTickets = null as number | null,
+		} = {}) =>
+			raffleProgram.methods
+				.validateRaffleParams(
+					metadataUri,
+					new BN(ticketPrice),
+					new BN((creationTime + BigInt(duration)).toString()),
+					new BN(minTickets),
+					maxTickets === null ? null : new BN(maxTickets),
+					null,
+					false,
+					null,
+					false,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+					null,
+				)
+				.view();
+
+		return { raffleProgram, validate };
+	};
+
+	it("should accept the parameters create_raffle accepts", async () => {
+		const { raffleProgram, validate } = await setup();
+
+		const validation = await validate();
+		expect(validation.valid).toBeTrue();
+		expect(validation.errorCodes).toEqual([]);
+
+		// Nothing is created
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+		expect(
+			raffleProgram.account.raffle.fetchNullable(raffleAccountId),
+		).resolves.toBeNull();
+	});
+
+	it("should report every violated rule in the order create_raffle checks them", async () => {
+		const { validate } = await setup();
+
+		const validation = await validate({
+			metadataUri: "http://www.example.org",
+			ticketPrice: 1000,
+			duration: 60,
+			minTickets: 10,
+			maxTickets: 5,
+		});
+		expect(validation.valid).toBeFalse();
+		expect(validation.errorCodes).toEqual(
+			[
+				"InvalidMetadataUri",
+				"TicketPriceTooLow",
+				"MaxTicketsTooLow",
+				"EndTimeTooClose",
+			].map(errorCode),
+		);
+	});
+
+	it("should report raffles running for too long", async () => {
+		const { validate } = await setup();
+
+		const validation = await validate({ duration: 31 * 24 * 60 * 60 });
+		expect(validation.errorCodes).toEqual([errorCode("DurationTooLong")]);
+	});
+});
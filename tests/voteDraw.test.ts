@@ -0,0 +1,293 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { LiteSVM } from "litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+const TOKEN_PROGRAM_ID = new PublicKey(
+	"TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+);
+const ASSOCIATED_TOKEN_PROGRAM_ID = new PublicKey(
+	"ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL",
+);
+
+const associatedTokenAddress = (owner: PublicKey, mint: PublicKey) =>
+	PublicKey.findProgramAddressSync(
+		[owner.toBuffer(), TOKEN_PROGRAM_ID.toBuffer(), mint.toBuffer()],
+		ASSOCIATED_TOKEN_PROGRAM_ID,
+	)[0];
+
+// Writes an initialized SPL token mint without a freeze authority
+const setMint = (client: LiteSVM, mint: PublicKey, authority: PublicKey) => {
+	const data = Buffer.alloc(82);
+	data.writeUInt32LE(1, 0);
+	authority.toBuffer().copy(data, 4);
+	data.writeUInt8(6, 44);
+	data.writeUInt8(1, 45);
+	client.setAccount(mint, {
+		executable: false,
+		owner: TOKEN_PROGRAM_ID,
+		lamports: LAMPORTS_PER_SOL,
+		data,
+	});
+};
+
+// Writes an initialized SPL token account holding `amount` tokens
+const setTokenAccount = (
+	client: LiteSVM,
+	address: PublicKey,
+	mint: PublicKey,
+	owner: PublicKey,
+	amount: number,
+) => {
+	const data = Buffer.alloc(165);
+	mint.toBuffer().copy(data, 0);
+	owner.toBuffer().copy(data, 32);
+	data.writeBigUInt64LE(BigInt(amount), 64);
+	data.writeUInt8(1, 108);
+	client.setAccount(address, {
+		executable: false,
+		owner: TOKEN_PROGRAM_ID,
+		lamports: LAMPORTS_PER_SOL,
+		data,
+	});
+};
+
+const tokenBalance = (client: LiteSVM, address: PublicKey) =>
+	Number(Buffer.from(client.getAccount(address)!.data).readBigUInt64LE(64));
+
+describe("vote_draw", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(1),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+		await raffleProgram.methods
+			.setRandomnessMode({ vote: {} })
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		// Sell two entries of two tickets each, numbered 0-1 and 2-3
+		const entries: PublicKey[] = [];
+		for (const seed of [1, 2]) {
+			const buyer = new Keypair();
+			provider.client.airdrop(buyer.publicKey, BigInt(LAMPORTS_PER_SOL));
+			await raffleProgram.methods
+				.initTicketBalance(null)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+			const entrySeed = new Uint8Array([seed, 0, 0, 0, 0, 0, 0, 0]);
+			await raffleProgram.methods
+				.buyTickets(new BN(2), Array.from(entrySeed), null, null, null, null, false, null)
+				.accounts({ signer: buyer.publicKey, raffle: raffleAccountId })
+				.signers([buyer])
+				.rpc();
+			entries.push(
+				PublicKey.findProgramAddressSync(
+					[Buffer.from("entry"), raffleAccountId.toBytes(), entrySeed],
+					raffleProgram.programId,
+				)[0],
+			);
+		}
+
+		const setTime = (offset: number) => {
+			const newClock = client.getClock();
+			newClock.unixTimestamp = creationTime + BigInt(offset);
+			client.setClock(newClock);
+		};
+		setTime(3602);
+		const votingEndsAt = creationTime + BigInt(7200);
+
+		const voteMint = new Keypair().publicKey;
+		setMint(client, voteMint, provider.publicKey);
+		const ballot = PublicKey.findProgramAddressSync(
+			[Buffer.from("ballot"), raffleAccountId.toBuffer()],
+			raffleProgram.programId,
+		)[0];
+		const ballotTokenAccount = associatedTokenAddress(ballot, voteMint);
+
+		const openVote = (shortlist: PublicKey[] = entries) =>
+			raffleProgram.methods
+				.openVote(new BN(votingEndsAt.toString()))
+				.accounts({
+					raffle: raffleAccountId,
+					voteMint,
+					ballotTokenAccount,
+					associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+				})
+				.remainingAccounts(
+					shortlist.map((pubkey) => ({
+						pubkey,
+						isSigner: false,
+						isWritable: false,
+					})),
+				)
+				.rpc();
+
+		const newVoter = (amount: number) => {
+			const voter = new Keypair();
+			provider.client.airdrop(voter.publicKey, BigInt(LAMPORTS_PER_SOL));
+			const tokenAccount = new Keypair().publicKey;
+			setTokenAccount(client, tokenAccount, voteMint, voter.publicKey, amount);
+			return { voter, tokenAccount };
+		};
+		const castVote = (
+			{ voter, tokenAccount }: { voter: Keypair; tokenAccount: PublicKey },
+			choice: number,
+			amount: number,
+		) =>
+			raffleProgram.methods
+				.castVote(choice, new BN(amount))
+				.accounts({
+					raffle: raffleAccountId,
+					voter: voter.publicKey,
+					voterTokenAccount: tokenAccount,
+					ballotTokenAccount,
+				})
+				.signers([voter])
+				.rpc();
+
+		const tallyVote = (winningEntry: PublicKey) =>
+			raffleProgram.methods
+				.tallyVote()
+				.accounts({ raffle: raffleAccountId, winningEntry })
+				.rpc();
+
+		return {
+			client,
+			raffleProgram,
+			raffleAccountId,
+			entries,
+			ballotTokenAccount,
+			setTime,
+			openVote,
+			newVoter,
+			castVote,
+			tallyVote,
+		};
+	};
+
+	it("should draw the shortlisted entry with the most votes", async () => {
+		const {
+			raffleProgram,
+			raffleAccountId,
+			entries,
+			setTime,
+			openVote,
+			newVoter,
+			castVote,
+			tallyVote,
+		} = await setup();
+
+		await openVote();
+		await castVote(newVoter(100), 0, 100);
+		await castVote(newVoter(300), 1, 250);
+		await castVote(newVoter(200), 0, 120);
+
+		// The vote can't be tallied before voting ends
+		expect(tallyVote(entries[1])).rejects.toThrow(/VotingNotEnded/);
+
+		setTime(7200);
+		expect(tallyVote(entries[0])).rejects.toThrow(/InvalidWinningEntry/);
+		await tallyVote(entries[1]);
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.raffleState).toEqual({ drawing: {} });
+		expect(raffle.winningTicket?.toNumber()).toBe(2);
+	});
+
+	it("should return the locked tokens once voting ended", async () => {
+		const {
+			client,
+			raffleProgram,
+			raffleAccountId,
+			ballotTokenAccount,
+			setTime,
+			openVote,
+			newVoter,
+			castVote,
+		} = await setup();
+
+		await openVote();
+		const voter = newVoter(100);
+		await castVote(voter, 0, 60);
+		expect(tokenBalance(client, voter.tokenAccount)).toBe(40);
+		expect(tokenBalance(client, ballotTokenAccount)).toBe(60);
+
+		// Each wallet votes once
+		expect(castVote(voter, 1, 10)).rejects.toThrow();
+
+		const reclaimVote = () =>
+			raffleProgram.methods
+				.reclaimVote()
+				.accounts({
+					raffle: raffleAccountId,
+					voter: voter.voter.publicKey,
+					ballotTokenAccount,
+					voterTokenAccount: voter.tokenAccount,
+				})
+				.signers([voter.voter])
+				.rpc();
+		expect(reclaimVote()).rejects.toThrow(/VotingNotEnded/);
+
+		setTime(7200);
+		expect(castVote(newVoter(100), 0, 10)).rejects.toThrow(/VotingClosed/);
+		await reclaimVote();
+		expect(tokenBalance(client, voter.tokenAccount)).toBe(100);
+		expect(tokenBalance(client, ballotTokenAccount)).toBe(0);
+	});
+
+	it("should fail to shortlist no entries or an entry twice", async () => {
+		const { entries, openVote } = await setup();
+
+		expect(openVote([entries[0], entries[0]])).rejects.toThrow(
+			/InvalidShortlist/,
+		);
+		expect(openVote([])).rejects.toThrow(/InvalidShortlist/);
+	});
+
+	it("should fail to vote for an entry outside the shortlist", async () => {
+		const { openVote, newVoter, castVote } = await setup();
+
+		await openVote();
+		expect(castVote(newVoter(100), 2, 10)).rejects.toThrow(/InvalidVoteChoice/);
+	});
+});
@@ -0,0 +1,143 @@
+import { describe, expect, it } from "bun:test";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { LiteSVMProvider, fromWorkspace } from "anchor-litesvm";
+import type { RaffleProgram } from "../target/types/raffle_program";
+const IDL = require("../target/idl/raffle_program.json");
+
+describe("submit_winner_attestation", async () => {
+	const setup = async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+		const issuer = new Keypair();
+		const winner = new Keypair();
+		provider.client.airdrop(issuer.publicKey, BigInt(LAMPORTS_PER_SOL));
+		provider.client.airdrop(winner.publicKey, BigInt(LAMPORTS_PER_SOL));
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: provider.publicKey,
+				upgradeAuthority: provider.publicKey,
+			})
+			.rpc();
+		await raffleProgram.methods.setIdentityIssuer(issuer.publicKey).rpc();
+
+		const creationTime = client.getClock().unixTimestamp;
+		await raffleProgram.methods
+			.createRaffle(
+				"https://www.example.org",
+				new BN(0.1 * LAMPORTS_PER_SOL),
+				new BN((creationTime + BigInt(3601)).toString()),
+				new BN(1),
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+
+		// Manually set the raffle state to drawn
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		const raffleData = await raffleProgram.coder.accounts.encode("raffle", {
+			...raffle,
+			raffleState: { drawn: {} },
+			winnerAddress: winner.publicKey,
+		});
+		provider.client.setAccount(raffleAccountId, {
+			executable: false,
+			owner: raffleProgram.programId,
+			lamports: 1 * LAMPORTS_PER_SOL,
+			data: raffleData,
+		});
+
+		const credentialHash = Array.from(new Uint8Array(32).fill(7));
+		const attest = (signer: Keypair) =>
+			raffleProgram.methods
+				.attestIdentity(
+					winner.publicKey,
+					credentialHash,
+					new BN((creationTime + BigInt(600)).toString()),
+				)
+				.accounts({ issuer: signer.publicKey })
+				.signers([signer])
+				.rpc();
+		const submitAttestation = () =>
+			raffleProgram.methods
+				.submitWinnerAttestation(null, 0)
+				.accounts({ raffle: raffleAccountId, signer: winner.publicKey })
+				.signers([winner])
+				.rpc();
+
+		return {
+			provider,
+			raffleProgram,
+			raffleAccountId,
+			issuer,
+			credentialHash,
+			attest,
+			submitAttestation,
+		};
+	};
+
+	it("should claim the raffle with an identity attestation", async () => {
+		const {
+			raffleProgram,
+			raffleAccountId,
+			issuer,
+			attest,
+			submitAttestation,
+		} = await setup();
+
+		await attest(issuer);
+		await submitAttestation();
+
+		const raffle = await raffleProgram.account.raffle.fetch(raffleAccountId);
+		expect(raffle.raffleState.claimed).toBeDefined();
+		expect(raffle.winnerDataSubmitted).toBe(1);
+	});
+
+	it("should reject attestations of a rotated issuer", async () => {
+		const { raffleProgram, issuer, attest, submitAttestation } = await setup();
+
+		await attest(issuer);
+		await raffleProgram.methods.setIdentityIssuer(new Keypair().publicKey).rpc();
+
+		expect(submitAttestation()).rejects.toThrow(/InvalidIdentityAttestation/);
+	});
+
+	it("should reject expired attestations", async () => {
+		const { provider, issuer, attest, submitAttestation } = await setup();
+
+		await attest(issuer);
+		const clock = provider.client.getClock();
+		clock.unixTimestamp = clock.unixTimestamp + BigInt(600);
+		provider.client.setClock(clock);
+
+		expect(submitAttestation()).rejects.toThrow(/InvalidIdentityAttestation/);
+	});
+
+	it("should fail from accounts other than the identity issuer", async () => {
+		const { provider, attest } = await setup();
+
+		const account = new Keypair();
+		provider.client.airdrop(account.publicKey, BigInt(LAMPORTS_PER_SOL));
+
+		expect(attest(account)).rejects.toThrow(/NotIdentityIssuer/);
+	});
+});
@@ -53,7 +53,24 @@ describe("withdraw_from_treasury", async () => {
 
 				// Create raffle
 				await raffleProgram.methods
-					.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+					.createRaffle(
+						metadataUri,
+						ticketPrice,
+						endTime,
+						minTickets,
+						null,
+						null,
+						false,
+						null,
+						false,
+						null,
+						null,
+						null,
+						null,
+						null,
+						null,
+						null,
+					)
 					.rpc();
 				const raffleAccountId = PublicKey.findProgramAddressSync(
 					[
@@ -62,10 +79,10 @@ describe("withdraw_from_treasury", async () => {
 					],
 					raffleProgram.programId,
 				)[0];
-				const treasuryId = PublicKey.findProgramAddressSync(
+				const [treasuryId, treasuryBump] = PublicKey.findProgramAddressSync(
 					[Buffer.from("treasury"), raffleAccountId.toBytes()],
 					raffleProgram.programId,
-				)[0];
+				);
 
 				// Manually set the raffle state
 				const currentTickets =
@@ -84,6 +101,57 @@ describe("withdraw_from_treasury", async () => {
 					winnerAddress: null,
 					winningTicket: null,
 					maxTickets: null,
+					participantCount: new BN(0),
+					eventSeq: new BN(0),
+					minRevenue: null,
+					minDrawSlot: new BN(0),
+					reservedTickets: new BN(0),
+					refundFeeBps: 0,
+					rentPayer: provider.publicKey,
+					features: 0,
+					allowedRegions: new BN(0),
+					ageRestricted: false,
+					drawAuthority: null,
+					priorityMinTier: 0,
+					priorityUntil: new BN(0),
+					minUniqueBuyers: null,
+					paymentMint: null,
+					randomnessMode: { slotHashes: {} },
+					randomnessAccount: null,
+					randomnessCommitSlot: new BN(0),
+					prizeEscrowed: false,
+					winnerCount: 1,
+					winningTickets: [],
+					additionalWinners: [],
+					winnerDataSubmitted: 0,
+					prizesClaimed: 0,
+					maxTicketsPerWallet: null,
+					gate: null,
+					deferredNumbering: false,
+					entryCount: new BN(0),
+					numberedEntries: new BN(0),
+					numberedTickets: new BN(0),
+					feeBpsOverride: null,
+					snapshotRoot: null,
+					operatorStatus: 0,
+					statusNoteHash: null,
+					fundingDeadline: null,
+					pricingTiers: [],
+					tierDiscounts: new BN(0),
+					crankRewardBps: 0,
+					referralFeeBps: 0,
+					extensionCount: 0,
+					extendedSecs: new BN(0),
+					refundDeadline: new BN(0),
+					prizeValueUsdCents: null,
+					ticketCountDisplayCap: null,
+					ticketReceipts: false,
+					arweaveTxId: null,
+					creator: null,
+					drawDelaySlots: 0,
+					drawTargetSlot: new BN(0),
+					refundMint: null,
+					refundRate: new BN(0), drawValueLamports: new BN(0), drawUsdRate: null, successor: null, randomnessProvider: null, randomnessCommitment: null, accessCodeHash: null, closeReason: 0, crankRewardPaid: false, additionalWinnerHashes: [], voidedRanges: [], pendingReservations: new BN(0), openSlotReleased: false, campaignSubsidies: new BN(0),
 				});
 				provider.client.setAccount(raffleAccountId, {
 					executable: false,
@@ -92,16 +160,37 @@ describe("withdraw_from_treasury", async () => {
 					data: raffleData,
 				});
 
-				// Mint balance to the treasury, so that the withdrawFromTreasury instruction can be executed
-				provider.client.airdrop(
-					treasuryId,
-					BigInt(currentTickets.mul(ticketPrice).toString()),
-				);
-
-				// Verify that the treasury has more than the minRent
+				// Manually set the treasury balance and collected funds, so that the withdrawFromTreasury instruction can be executed
 				const minRent = provider.client.minimumBalanceForRentExemption(
 					BigInt(raffleProgram.account.treasury.size),
 				);
+				const treasuryData = await raffleProgram.coder.accounts.encode(
+					"treasury",
+					{
+						raffle: raffleAccountId,
+						bump: treasuryBump,
+						totalCollected: currentTickets.mul(ticketPrice),
+						totalRefunded: new BN(0),
+						totalWithdrawn: new BN(0),
+						totalSponsored: new BN(0),
+						totalRefundFees: new BN(0),
+						approvedWithdrawal: new BN(0),
+						totalCrankRewards: new BN(0),
+						totalReferralFees: new BN(0),
+						totalReferralFeesPaid: new BN(0),
+						totalForfeited: new BN(0),
+						recognizedBalance: currentTickets.mul(ticketPrice),
+					},
+				);
+				provider.client.setAccount(treasuryId, {
+					executable: false,
+					owner: raffleProgram.programId,
+					lamports:
+						Number(minRent) + currentTickets.mul(ticketPrice).toNumber(),
+					data: treasuryData,
+				});
+
+				// Verify that the treasury has more than the minRent
 				const treasuryBalanceBefore = provider.client.getBalance(treasuryId);
 				if (!treasuryBalanceBefore) {
 					throw new Error("Failed to fetch treasury balance");
@@ -149,6 +238,13 @@ describe("withdraw_from_treasury", async () => {
 					payoutAuthorityBalanceBefore +
 						BigInt(ticketPrice.mul(currentTickets).toString()),
 				);
+
+				// Validate that the withdrawal has been tracked
+				const treasuryAccount =
+					await raffleProgram.account.treasury.fetch(treasuryId);
+				expect(
+					treasuryAccount.totalWithdrawn.eq(ticketPrice.mul(currentTickets)),
+				).toBeTrue();
 			}
 		}
 	});
@@ -190,7 +286,24 @@ describe("withdraw_from_treasury", async () => {
 
 		// Create raffle
 		await raffleProgram.methods
-			.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
 			.rpc();
 		const raffleAccountId = PublicKey.findProgramAddressSync(
 			[
@@ -234,7 +347,7 @@ describe("withdraw_from_treasury", async () => {
 
 			// Create ticket balance
 			await raffleProgram.methods
-				.initTicketBalance()
+				.initTicketBalance(null)
 				.accounts({
 					signer: buyer.publicKey,
 					raffle: new PublicKey(raffleAccountId),
@@ -248,7 +361,7 @@ describe("withdraw_from_treasury", async () => {
 
 			// Purchase tickets
 			await raffleProgram.methods
-				.buyTickets(ticketsToBuy, Array.from(entrySeed))
+				.buyTickets(ticketsToBuy, Array.from(entrySeed), null, null, null, null, false, null)
 				.accounts({
 					signer: buyer.publicKey,
 					raffle: new PublicKey(raffleAccountId),
@@ -380,7 +493,24 @@ describe("withdraw_from_treasury", async () => {
 
 		// Create raffle
 		await raffleProgram.methods
-			.createRaffle(metadataUri, ticketPrice, endTime, minTickets, maxTickets)
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				maxTickets,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
 			.rpc();
 		const raffleAccountId = PublicKey.findProgramAddressSync(
 			[
@@ -421,7 +551,7 @@ describe("withdraw_from_treasury", async () => {
 
 		// Create ticket balance
 		await raffleProgram.methods
-			.initTicketBalance()
+			.initTicketBalance(null)
 			.accounts({
 				signer: buyer.publicKey,
 				raffle: new PublicKey(raffleAccountId),
@@ -435,7 +565,7 @@ describe("withdraw_from_treasury", async () => {
 
 		// Purchase tickets
 		await raffleProgram.methods
-			.buyTickets(ticketsToBuy, Array.from(entrySeed))
+			.buyTickets(ticketsToBuy, Array.from(entrySeed), null, null, null, null, false, null)
 			.accounts({
 				signer: buyer.publicKey,
 				raffle: new PublicKey(raffleAccountId),
@@ -538,7 +668,24 @@ describe("withdraw_from_treasury", async () => {
 
 		// Create raffle
 		await raffleProgram.methods
-			.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
 			.rpc();
 		const raffleAccountId = PublicKey.findProgramAddressSync(
 			[
@@ -597,7 +744,24 @@ describe("withdraw_from_treasury", async () => {
 
 		// Create raffle
 		await raffleProgram.methods
-			.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
 			.rpc();
 
 		const firstRaffleAccountId = PublicKey.findProgramAddressSync(
@@ -607,7 +771,7 @@ describe("withdraw_from_treasury", async () => {
 
 		// Buy tickets on the first raffle, to meet threshold
 		await raffleProgram.methods
-			.initTicketBalance()
+			.initTicketBalance(null)
 			.accounts({
 				raffle: firstRaffleAccountId,
 			})
@@ -618,7 +782,7 @@ describe("withdraw_from_treasury", async () => {
 		const entrySeed = randomBytes;
 
 		await raffleProgram.methods
-			.buyTickets(minTickets, Array.from(entrySeed))
+			.buyTickets(minTickets, Array.from(entrySeed), null, null, null, null, false, null)
 			.accounts({ raffle: firstRaffleAccountId })
 			.rpc();
 
@@ -630,6 +794,17 @@ describe("withdraw_from_treasury", async () => {
 				endTime,
 				minTickets.add(new BN(1)),
 				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
 			)
 			.rpc();
 
@@ -689,7 +864,24 @@ describe("withdraw_from_treasury", async () => {
 
 		// Create raffle
 		await raffleProgram.methods
-			.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
 			.rpc();
 		const raffleAccountId = PublicKey.findProgramAddressSync(
 			[
@@ -704,7 +896,7 @@ describe("withdraw_from_treasury", async () => {
 		)[0];
 
 		await raffleProgram.methods
-			.initTicketBalance()
+			.initTicketBalance(null)
 			.accounts({
 				raffle: raffleAccountId,
 			})
@@ -715,7 +907,7 @@ describe("withdraw_from_treasury", async () => {
 		const entrySeed = randomBytes;
 
 		await raffleProgram.methods
-			.buyTickets(minTickets, Array.from(entrySeed))
+			.buyTickets(minTickets, Array.from(entrySeed), null, null, null, null, false, null)
 			.accounts({ raffle: raffleAccountId })
 			.rpc();
 
@@ -770,7 +962,24 @@ describe("withdraw_from_treasury", async () => {
 
 		// Create raffle
 		await raffleProgram.methods
-			.createRaffle(metadataUri, ticketPrice, endTime, minTickets, null)
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
 			.rpc();
 		const raffleAccountId = PublicKey.findProgramAddressSync(
 			[
@@ -785,7 +994,7 @@ describe("withdraw_from_treasury", async () => {
 		)[0];
 
 		await raffleProgram.methods
-			.initTicketBalance()
+			.initTicketBalance(null)
 			.accounts({
 				raffle: raffleAccountId,
 			})
@@ -796,7 +1005,7 @@ describe("withdraw_from_treasury", async () => {
 		const entrySeed = randomBytes;
 
 		await raffleProgram.methods
-			.buyTickets(minTickets, Array.from(entrySeed))
+			.buyTickets(minTickets, Array.from(entrySeed), null, null, null, null, false, null)
 			.accounts({ raffle: raffleAccountId })
 			.rpc();
 
@@ -818,4 +1027,136 @@ describe("withdraw_from_treasury", async () => {
 				.rpc(),
 		).rejects.toThrow(/NotPayoutAuthority/);
 	});
+
+	it("should only withdraw collected funds and leave lamports sent directly to the treasury", async () => {
+		const client = fromWorkspace(".");
+		const provider = new LiteSVMProvider(client);
+		const raffleProgram = new Program<RaffleProgram>(IDL, provider);
+		const payoutAuthority = new Keypair();
+
+		// Mint some balance to the payoutAuthority to initialize it
+		provider.client.airdrop(
+			payoutAuthority.publicKey,
+			BigInt(0.1 * LAMPORTS_PER_SOL),
+		);
+
+		// Init config
+		await raffleProgram.methods
+			.initConfig()
+			.accounts({
+				managementAuthority: provider.publicKey,
+				payoutAuthority: payoutAuthority.publicKey,
+			})
+			.rpc();
+
+		const configId = PublicKey.findProgramAddressSync(
+			[Buffer.from("config")],
+			raffleProgram.programId,
+		)[0];
+		const creationTime = client.getClock().unixTimestamp;
+
+		const metadataUri = "https://www.example.org";
+		const ticketPrice = new BN(0.1 * LAMPORTS_PER_SOL);
+		const minTickets = new BN(5);
+		const endTime = new BN((creationTime + BigInt(3601)).toString());
+
+		// Create raffle
+		await raffleProgram.methods
+			.createRaffle(
+				metadataUri,
+				ticketPrice,
+				endTime,
+				minTickets,
+				null,
+				null,
+				false,
+				null,
+				false,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+				null,
+			)
+			.rpc();
+		const raffleAccountId = PublicKey.findProgramAddressSync(
+			[Buffer.from("raffle"), new Uint8Array(new BN(0).toArray("le", 8))],
+			raffleProgram.programId,
+		)[0];
+		const treasuryId = PublicKey.findProgramAddressSync(
+			[Buffer.from("treasury"), raffleAccountId.toBytes()],
+			raffleProgram.programId,
+		)[0];
+
+		await raffleProgram.methods
+			.initTicketBalance(null)
+			.accounts({
+				raffle: raffleAccountId,
+			})
+			.rpc();
+
+		const randomBytes = new Uint8Array(8);
+		crypto.getRandomValues(randomBytes);
+		const entrySeed = randomBytes;
+
+		await raffleProgram.methods
+			.buyTickets(minTickets, Array.from(entrySeed), null, null, null, null, false, null)
+			.accounts({ raffle: raffleAccountId })
+			.rpc();
+
+		// Send lamports directly to the treasury, bypassing the program
+		const strayLamports = BigInt(0.5 * LAMPORTS_PER_SOL);
+		provider.client.airdrop(treasuryId, strayLamports);
+
+		const payoutAuthorityBalanceBefore = provider.client.getBalance(
+			payoutAuthority.publicKey,
+		);
+		if (!payoutAuthorityBalanceBefore) {
+			throw new Error("Failed to fetch payout authority balance");
+		}
+
+		// Withdraw from treasury
+		await raffleProgram.methods
+			.withdrawFromTreasury()
+			.accountsStrict({
+				config: configId,
+				raffle: raffleAccountId,
+				treasury: treasuryId,
+				payoutAuthority: payoutAuthority.publicKey,
+				managementAuthority: provider.publicKey,
+				systemProgram: new PublicKey("11111111111111111111111111111111"),
+			})
+			.rpc();
+
+		// Validate that only the ticket sales were paid out
+		const payoutAuthorityBalanceAfter = provider.client.getBalance(
+			payoutAuthority.publicKey,
+		);
+		expect(payoutAuthorityBalanceAfter).toEqual(
+			payoutAuthorityBalanceBefore +
+				BigInt(ticketPrice.mul(minTickets).toString()),
+		);
+
+		// Validate that the stray lamports remain in the treasury on top of the rent
+		const minRent = provider.client.minimumBalanceForRentExemption(
+			BigInt(raffleProgram.account.treasury.size),
+		);
+		expect(provider.client.getBalance(treasuryId)).toEqual(
+			minRent + strayLamports,
+		);
+
+		const treasuryAccount =
+			await raffleProgram.account.treasury.fetch(treasuryId);
+		expect(
+			treasuryAccount.totalCollected.eq(ticketPrice.mul(minTickets)),
+		).toBeTrue();
+		expect(
+			treasuryAccount.totalWithdrawn.eq(ticketPrice.mul(minTickets)),
+		).toBeTrue();
+
+		// The stray lamports were never recognized by the treasury
+		expect(treasuryAccount.recognizedBalance.toNumber()).toBe(0);
+	});
 });